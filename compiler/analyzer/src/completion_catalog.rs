@@ -0,0 +1,496 @@
+//! What an editor can offer to complete, captured from a finished analysis.
+//!
+//! Completion is asked for while the user is typing, which is exactly when the
+//! file being edited is least likely to parse. The catalog therefore owns
+//! everything it needs rather than borrowing the [`SemanticContext`]: a caller
+//! keeps the catalog of the last successful analysis and goes on offering its
+//! names while the current text has errors.
+
+use std::collections::HashMap;
+
+use ironplc_dsl::common::{TypeName, VariableType};
+use ironplc_dsl::core::{Id, SourceSpan};
+
+use crate::extractors::TypeSymbolKind;
+use crate::function_environment::FunctionSignature;
+use crate::intermediate_type::{
+    FunctionBlockVarType, IntermediateFunctionParameter, IntermediateType,
+};
+use crate::intermediates::stdlib_function_block::stdlib_function_block_names;
+use crate::semantic_context::SemanticContext;
+use crate::symbol_environment::{ScopeKind, SymbolKind};
+
+/// A named value: a variable in a scope or a member of a type.
+#[derive(Debug, Clone)]
+pub struct CatalogEntry {
+    pub name: Id,
+    /// The declared type, when analysis recorded one.
+    pub type_name: Option<TypeName>,
+    /// The section that declares the variable; `None` for structure fields.
+    pub variable_type: Option<VariableType>,
+}
+
+/// A data type that can be named in a declaration.
+#[derive(Debug, Clone)]
+pub struct CatalogType {
+    pub name: TypeName,
+    pub kind: TypeSymbolKind,
+}
+
+/// The names a completed analysis declared, grouped the way completion looks
+/// them up.
+#[derive(Debug, Default)]
+pub struct CompletionCatalog {
+    /// Variables of each program, function block and function, and the
+    /// globals under [`ScopeKind::Global`].
+    variables: HashMap<ScopeKind, Vec<CatalogEntry>>,
+    /// Members reachable with `.` from an instance of each structure or
+    /// function block, keyed by the type name.
+    members: HashMap<TypeName, Vec<CatalogEntry>>,
+    /// Each enumeration value with the enumeration that declares it.
+    enum_values: Vec<(TypeName, Id)>,
+    functions: Vec<FunctionSignature>,
+    types: Vec<CatalogType>,
+}
+
+impl CompletionCatalog {
+    /// The variables visible in the named POU: its own, then the globals.
+    /// Without a POU, only the globals.
+    pub fn variables(&self, pou: Option<&Id>) -> impl Iterator<Item = &CatalogEntry> {
+        let own = pou
+            .and_then(|pou| self.variables.get(&ScopeKind::Named(pou.clone())))
+            .into_iter()
+            .flatten();
+        let globals = self.variables.get(&ScopeKind::Global).into_iter().flatten();
+        own.chain(globals)
+    }
+
+    /// The members an instance of the type exposes: the inputs, outputs
+    /// and in-outs of a function block, or the fields of a structure.
+    pub fn members(&self, type_name: &TypeName) -> &[CatalogEntry] {
+        self.members.get(type_name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Enumeration values with their enumeration type, limited to one
+    /// enumeration when `type_name` is given.
+    pub fn enum_values<'a>(
+        &'a self,
+        type_name: Option<&'a TypeName>,
+    ) -> impl Iterator<Item = &'a (TypeName, Id)> {
+        self.enum_values
+            .iter()
+            .filter(move |(enum_type, _)| type_name.is_none_or(|name| enum_type == name))
+    }
+
+    /// Every callable function: the standard library and user functions.
+    pub fn functions(&self) -> &[FunctionSignature] {
+        &self.functions
+    }
+
+    /// What a call of the name in the POU takes: the signature of a
+    /// function or, when the name is an instance of a function block, a
+    /// signature named after the instance with the function block's inputs,
+    /// in-outs and outputs as parameters.
+    pub fn call_signature(&self, name: &Id, pou: Option<&Id>) -> Option<FunctionSignature> {
+        if let Some(signature) = self.functions.iter().find(|sig| sig.name == *name) {
+            return Some(signature.clone());
+        }
+        let instance = self.variables(pou).find(|entry| entry.name == *name)?;
+        let type_name = instance
+            .type_name
+            .as_ref()
+            .filter(|ty| self.is_function_block(ty))?;
+        let parameters = self
+            .members(type_name)
+            .iter()
+            .map(|member| IntermediateFunctionParameter {
+                name: member.name.clone(),
+                param_type: member
+                    .type_name
+                    .clone()
+                    .unwrap_or_else(|| TypeName::from("ANY")),
+                is_input: member.variable_type == Some(VariableType::Input),
+                is_output: member.variable_type == Some(VariableType::Output),
+                is_inout: member.variable_type == Some(VariableType::InOut),
+                is_reference: false,
+            })
+            .collect();
+        Some(FunctionSignature::new(
+            instance.name.clone(),
+            None,
+            parameters,
+            SourceSpan::default(),
+        ))
+    }
+
+    /// Every type a declaration can name, other than the elementary types.
+    pub fn types(&self) -> &[CatalogType] {
+        &self.types
+    }
+
+    /// Whether the type is a function block, so an instance of it is
+    /// called rather than read.
+    pub fn is_function_block(&self, type_name: &TypeName) -> bool {
+        self.types
+            .iter()
+            .any(|ty| ty.kind == TypeSymbolKind::FunctionBlock && ty.name == *type_name)
+    }
+
+    /// Takes what a newer analysis of part of the sources found: the
+    /// variables of each POU it analyzed, the members of each type, and the
+    /// globals, enumeration values, functions and types it saw, while
+    /// keeping the names from the sources it left out.
+    pub fn update(&mut self, newer: CompletionCatalog) {
+        for (scope, entries) in newer.variables {
+            match (&scope, self.variables.get_mut(&scope)) {
+                (ScopeKind::Global, Some(globals)) => {
+                    for entry in entries {
+                        globals.retain(|global| global.name != entry.name);
+                        globals.push(entry);
+                    }
+                    globals.sort_by_key(|entry| entry.name.lower_case().clone());
+                }
+                _ => {
+                    self.variables.insert(scope, entries);
+                }
+            }
+        }
+        self.members.extend(newer.members);
+        for value in newer.enum_values {
+            if !self.enum_values.contains(&value) {
+                self.enum_values.push(value);
+            }
+        }
+        self.enum_values
+            .sort_by_key(|(enum_type, value)| (enum_type.to_string(), value.to_string()));
+        for signature in newer.functions {
+            self.functions.retain(|sig| sig.name != signature.name);
+            self.functions.push(signature);
+        }
+        self.functions
+            .sort_by_key(|signature| signature.name.lower_case().clone());
+        for ty in newer.types {
+            self.types.retain(|other| other.name != ty.name);
+            self.types.push(ty);
+        }
+        self.types
+            .sort_by_key(|ty| ty.name.to_string().to_lowercase());
+    }
+}
+
+impl From<&SemanticContext> for CompletionCatalog {
+    fn from(context: &SemanticContext) -> Self {
+        let symbols = context.symbols();
+        let mut catalog = CompletionCatalog::default();
+
+        for (scope, scope_symbols) in symbols
+            .get_scoped_symbols()
+            .iter()
+            .chain([(&ScopeKind::Global, symbols.get_global_symbols())])
+        {
+            let mut entries: Vec<CatalogEntry> = scope_symbols
+                .iter()
+                .filter(|(_, info)| {
+                    matches!(
+                        info.kind,
+                        SymbolKind::Variable
+                            | SymbolKind::Parameter
+                            | SymbolKind::OutputParameter
+                            | SymbolKind::InOutParameter
+                    )
+                })
+                .map(|(name, info)| CatalogEntry {
+                    name: name.clone(),
+                    type_name: info.data_type.as_deref().map(TypeName::from),
+                    variable_type: info.variable_type.clone(),
+                })
+                .collect();
+            entries.sort_by_key(|entry| entry.name.lower_case().clone());
+            // A scope without variables is kept so that an update can empty
+            // the scope it replaces.
+            catalog.variables.insert(scope.clone(), entries);
+        }
+
+        for (name, info) in symbols.get_global_symbols().iter().chain(
+            symbols
+                .get_scoped_symbols()
+                .values()
+                .flat_map(|scope| scope.iter()),
+        ) {
+            if let (SymbolKind::EnumerationValue, Some(enum_type)) = (&info.kind, &info.enum_type) {
+                catalog.enum_values.push((enum_type.clone(), name.clone()));
+            }
+        }
+        catalog
+            .enum_values
+            .sort_by_key(|(enum_type, value)| (enum_type.to_string(), value.to_string()));
+
+        for (type_name, attributes) in context.types().iter() {
+            let Some(fields) = attributes.representation.member_fields() else {
+                continue;
+            };
+            let scope = ScopeKind::Named(Id::from(type_name.to_string().as_str()));
+            let entries = match symbols.get_scope_symbols(&scope) {
+                // A user function block's scope has every variable with its
+                // type as written; the intermediate representation leaves
+                // out members it could not lay out.
+                Some(declared) if attributes.representation.is_function_block() => {
+                    let mut declared: Vec<_> = declared
+                        .iter()
+                        .filter(|(_, info)| {
+                            matches!(
+                                info.variable_type,
+                                Some(
+                                    VariableType::Input
+                                        | VariableType::Output
+                                        | VariableType::InOut
+                                )
+                            )
+                        })
+                        .collect();
+                    declared.sort_by_key(|(_, info)| info.span.start);
+                    declared
+                        .into_iter()
+                        .map(|(name, info)| CatalogEntry {
+                            name: name.clone(),
+                            type_name: info.data_type.as_deref().map(TypeName::from),
+                            variable_type: info.variable_type.clone(),
+                        })
+                        .collect()
+                }
+                _ => fields
+                    .iter()
+                    .filter_map(|field| {
+                        let variable_type = match field.var_type {
+                            Some(FunctionBlockVarType::Input) => Some(VariableType::Input),
+                            Some(FunctionBlockVarType::Output) => Some(VariableType::Output),
+                            Some(FunctionBlockVarType::InOut) => Some(VariableType::InOut),
+                            Some(FunctionBlockVarType::Internal) => return None,
+                            None => None,
+                        };
+                        Some(CatalogEntry {
+                            name: field.name.clone(),
+                            type_name: type_name_of(context, &field.field_type),
+                            variable_type,
+                        })
+                    })
+                    .collect(),
+            };
+            catalog.members.insert(type_name.clone(), entries);
+        }
+
+        let mut functions: Vec<FunctionSignature> = context
+            .functions()
+            .iter()
+            .map(|(_, signature)| signature.clone())
+            .collect();
+        functions.sort_by_key(|signature| signature.name.lower_case().clone());
+        catalog.functions = functions;
+
+        catalog.types = context
+            .user_defined_types()
+            .into_iter()
+            .map(|view| CatalogType {
+                name: view.name.clone(),
+                kind: view.kind,
+            })
+            .chain(
+                symbols
+                    .get_function_blocks()
+                    .into_iter()
+                    .map(|(name, _)| TypeName::from(name.original().as_str()))
+                    .chain(
+                        stdlib_function_block_names()
+                            .iter()
+                            .map(|name| TypeName::from(name)),
+                    )
+                    .map(|name| CatalogType {
+                        name,
+                        kind: TypeSymbolKind::FunctionBlock,
+                    }),
+            )
+            .collect();
+        catalog
+            .types
+            .sort_by_key(|ty| ty.name.to_string().to_lowercase());
+        catalog
+    }
+}
+
+/// The name of a member's type when the intermediate representation still
+/// carries one.
+fn type_name_of(context: &SemanticContext, it: &IntermediateType) -> Option<TypeName> {
+    match it {
+        IntermediateType::FunctionBlock { name, .. } => Some(TypeName::from(name.as_str())),
+        _ => context
+            .types()
+            .elementary_type_name_for(it)
+            .map(|name| TypeName::from(name.to_string().to_uppercase().as_str())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::parse_only;
+
+    fn catalog(program: &str) -> CompletionCatalog {
+        let library = parse_only(program);
+        let (_library, context) = crate::stages::analyze(
+            &[&library],
+            &ironplc_parser::options::CompilerOptions::default(),
+        )
+        .unwrap();
+        CompletionCatalog::from(&context)
+    }
+
+    const PROGRAM: &str = "TYPE
+    Mode : (Idle, Busy);
+    Point : STRUCT
+        X : INT;
+        Y : INT;
+    END_STRUCT;
+END_TYPE
+FUNCTION_BLOCK Counter
+VAR_INPUT
+    Delta : INT;
+END_VAR
+VAR_OUTPUT
+    Total : DINT;
+END_VAR
+VAR
+    Hidden : BOOL;
+END_VAR
+    Total := Total + Delta;
+END_FUNCTION_BLOCK
+PROGRAM Main
+VAR
+    Tally : Counter;
+    Timer : TON;
+    State : Mode;
+END_VAR
+    Tally(Delta := 1);
+END_PROGRAM";
+
+    fn names<'a>(entries: impl IntoIterator<Item = &'a CatalogEntry>) -> Vec<String> {
+        entries
+            .into_iter()
+            .map(|entry| entry.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn variables_when_pou_then_own_variables() {
+        let catalog = catalog(PROGRAM);
+
+        let variables = names(catalog.variables(Some(&Id::from("Main"))));
+
+        assert_eq!(variables, vec!["State", "Tally", "Timer"]);
+    }
+
+    #[test]
+    fn update_when_pou_reanalyzed_then_replaces_its_variables_and_keeps_others() {
+        let mut older = catalog(PROGRAM);
+        let newer = catalog(
+            "PROGRAM Main
+VAR
+    Level : INT;
+END_VAR
+    Level := 1;
+END_PROGRAM",
+        );
+
+        older.update(newer);
+
+        assert_eq!(
+            names(older.variables(Some(&Id::from("Main")))),
+            vec!["Level"]
+        );
+        assert!(older.is_function_block(&TypeName::from("Counter")));
+    }
+
+    #[test]
+    fn members_when_user_function_block_then_inputs_and_outputs_with_declared_type() {
+        let catalog = catalog(PROGRAM);
+
+        let members = catalog.members(&TypeName::from("Counter"));
+
+        assert_eq!(names(members), vec!["Delta", "Total"]);
+        assert_eq!(members[1].type_name, Some(TypeName::from("DINT")));
+    }
+
+    #[test]
+    fn members_when_standard_function_block_then_inputs_and_outputs() {
+        let catalog = catalog(PROGRAM);
+
+        let members = names(catalog.members(&TypeName::from("TON")));
+
+        assert_eq!(members, vec!["IN", "PT", "Q", "ET"]);
+    }
+
+    #[test]
+    fn members_when_structure_then_fields() {
+        let catalog = catalog(PROGRAM);
+
+        let members = names(catalog.members(&TypeName::from("Point")));
+
+        assert_eq!(members, vec!["X", "Y"]);
+    }
+
+    #[test]
+    fn enum_values_when_type_given_then_values_of_type() {
+        let catalog = catalog(PROGRAM);
+
+        let values: Vec<String> = catalog
+            .enum_values(Some(&TypeName::from("Mode")))
+            .map(|(_, value)| value.to_string())
+            .collect();
+
+        assert_eq!(values, vec!["Busy", "Idle"]);
+    }
+
+    #[test]
+    fn types_when_declared_then_includes_user_and_standard_function_blocks() {
+        let catalog = catalog(PROGRAM);
+
+        assert!(catalog.is_function_block(&TypeName::from("Counter")));
+        assert!(catalog.is_function_block(&TypeName::from("TON")));
+        assert!(!catalog.is_function_block(&TypeName::from("Point")));
+    }
+
+    #[test]
+    fn call_signature_when_function_block_instance_then_inputs_before_outputs() {
+        let catalog = catalog(PROGRAM);
+
+        let signature = catalog
+            .call_signature(&Id::from("tally"), Some(&Id::from("Main")))
+            .unwrap();
+
+        let inputs: Vec<String> = signature
+            .input_parameters()
+            .map(|param| param.name.to_string())
+            .collect();
+        assert_eq!(signature.name, Id::from("Tally"));
+        assert_eq!(inputs, vec!["Delta"]);
+        assert!(signature.parameters[1].is_output);
+    }
+
+    #[test]
+    fn call_signature_when_variable_not_function_block_then_none() {
+        let catalog = catalog(PROGRAM);
+
+        assert!(catalog
+            .call_signature(&Id::from("State"), Some(&Id::from("Main")))
+            .is_none());
+    }
+
+    #[test]
+    fn functions_when_analyzed_then_includes_standard_library() {
+        let catalog = catalog(PROGRAM);
+
+        assert!(catalog
+            .functions()
+            .iter()
+            .any(|signature| signature.name == Id::from("LIMIT")));
+    }
+}
@@ -22,6 +22,7 @@ use ironplc_dsl::core::Id;
 
 use crate::function_environment::FunctionSignature;
 use crate::intermediate_type::IntermediateType;
+use crate::intermediates::stdlib_function::get_all_stdlib_functions;
 use crate::semantic_context::SemanticContext;
 use crate::symbol_environment::{ScopeKind, SymbolEnvironment, SymbolInfo, SymbolKind};
 use crate::type_attributes::TypeAttributes;
@@ -179,6 +180,14 @@ impl<'a> FunctionSymbolView<'a> {
     }
 }
 
+/// The signature of the standard library function with the name, for
+/// tooling that describes a call without a completed analysis.
+pub fn stdlib_function(name: &Id) -> Option<FunctionSignature> {
+    get_all_stdlib_functions()
+        .into_iter()
+        .find(|signature| signature.name == *name)
+}
+
 /// A function parameter, augmented with its outline direction.
 #[derive(Debug, Clone, Copy)]
 pub struct ParameterView<'a> {
@@ -385,6 +394,22 @@ mod tests {
         assert!(funcs.is_empty(), "expected no user-defined functions");
     }
 
+    #[test]
+    fn stdlib_function_when_standard_name_then_signature() {
+        let limit = stdlib_function(&Id::from("limit")).unwrap();
+        let inputs: Vec<_> = limit
+            .parameters
+            .iter()
+            .map(|p| p.name.original().as_str())
+            .collect();
+        assert_eq!(inputs, vec!["MN", "IN", "MX"]);
+    }
+
+    #[test]
+    fn stdlib_function_when_user_name_then_none() {
+        assert!(stdlib_function(&Id::from("my_function")).is_none());
+    }
+
     #[test]
     fn user_defined_types_when_enum_then_returned() {
         let ctx = analyze_source("TYPE\nMyEnum : (A, B, C);\nEND_TYPE\nPROGRAM p\nEND_PROGRAM");
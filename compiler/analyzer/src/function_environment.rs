@@ -107,6 +107,12 @@ impl FunctionSignature {
     pub fn input_parameter_count(&self) -> usize {
         self.parameters.iter().filter(|p| p.is_input).count()
     }
+
+    /// Returns the parameters that take a value from the caller, in the
+    /// order that positional arguments bind to them.
+    pub fn input_parameters(&self) -> impl Iterator<Item = &IntermediateFunctionParameter> {
+        self.parameters.iter().filter(|p| p.is_input_compatible())
+    }
 }
 
 /// The function environment tracks all function signatures.
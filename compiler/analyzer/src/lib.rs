@@ -21,13 +21,16 @@ fn init_test_logger() {
 mod test_macros;
 
 mod call_assignment_check;
+pub mod completion_catalog;
 mod constant_folding;
 mod function_environment;
 pub mod intermediate_type;
+pub mod literal_types;
 mod result;
 mod rule_abstract_not_instantiated;
 mod rule_bit_access_range;
 mod rule_case_bit_string_label;
+mod rule_case_exhaustive;
 mod rule_decl_struct_element_unique_names;
 mod rule_decl_subrange_limits;
 mod rule_enumeration_values_unique;
@@ -47,8 +50,10 @@ mod rule_string_encoding_compat;
 mod rule_struct_initializer_expression_allowed;
 mod rule_support;
 mod rule_task_names_unique;
+mod rule_task_races;
 mod rule_unsupported_extension;
 mod rule_unsupported_stdlib_type;
+mod rule_unused;
 mod rule_use_declared_enumerated_value;
 mod rule_use_declared_symbolic_var;
 mod rule_var_decl_const_initialized;
@@ -61,10 +66,12 @@ pub mod stages;
 mod stdlib;
 mod string_similarity;
 pub mod symbol_environment;
+pub mod symbol_index;
 mod type_attributes;
 mod type_category;
 mod type_environment;
 mod type_table;
+pub mod value_range;
 mod xform_fold_constant_expressions;
 mod xform_fold_initializer_expressions;
 mod xform_insert_implicit_deref;
@@ -0,0 +1,405 @@
+//! The types analysis resolved for literals, looked up by source position
+//! for editor hover, and the types that untyped literals take from their
+//! context, for editor inlay hints.
+//!
+//! A bare literal such as `0` has no type of its own: analysis gives it a
+//! generic type (`ANY_INT`, `ANY_REAL`) that takes whatever type its context
+//! requires (ADR-0028), while a typed literal such as `DINT#7` keeps its type
+//! and widens only as ADR-0029 allows. Showing the resolved type lets a user
+//! see which rule applies.
+
+use ironplc_dsl::common::{
+    FunctionBlockDeclaration, FunctionDeclaration, Library, ProgramDeclaration, TypeName,
+};
+use ironplc_dsl::core::{FileId, Id, Located, SourceSpan};
+use ironplc_dsl::textual::{
+    Assignment, CompareOp, Expr, ExprKind, FbCall, Function, ParamAssignmentKind,
+    SymbolicVariableKind, Variable,
+};
+use ironplc_dsl::visitor::Visitor;
+
+use crate::semantic_context::SemanticContext;
+use crate::symbol_environment::ScopeKind;
+
+/// A literal in the analyzed library and the type analysis resolved for it.
+#[derive(Debug, Clone)]
+pub struct ResolvedLiteral {
+    pub span: SourceSpan,
+    pub type_name: TypeName,
+}
+
+/// The innermost literal at the byte offset in the file, if analysis
+/// resolved a type for it.
+///
+/// `library` must be the analyzed library: literals in the library as
+/// parsed have no resolved type.
+pub fn literal_at(library: &Library, file_id: &FileId, offset: usize) -> Option<ResolvedLiteral> {
+    let mut finder = LiteralFinder {
+        file_id,
+        offset,
+        found: None,
+    };
+    let _ = finder.walk(library);
+    finder.found
+}
+
+struct LiteralFinder<'a> {
+    file_id: &'a FileId,
+    offset: usize,
+    found: Option<ResolvedLiteral>,
+}
+
+impl Visitor<()> for LiteralFinder<'_> {
+    type Value = ();
+
+    fn visit_expr(&mut self, node: &Expr) -> Result<(), ()> {
+        if let (ExprKind::Const(_), Some(type_name)) = (&node.kind, &node.resolved_type) {
+            let span = node.span();
+            let contains = span.file_id == *self.file_id
+                && span.start <= self.offset
+                && self.offset <= span.end;
+            let narrower = self
+                .found
+                .as_ref()
+                .is_none_or(|found| span.end - span.start < found.span.end - found.span.start);
+            if contains && narrower {
+                self.found = Some(ResolvedLiteral {
+                    span,
+                    type_name: type_name.clone(),
+                });
+            }
+        }
+        node.recurse_visit(self)
+    }
+}
+
+/// Each literal in the file written without a type prefix, with the type
+/// its context gives it: the type of the variable it is assigned to, of the
+/// parameter it is passed to, or of the other operand of an operator. A
+/// literal whose context leaves the type open is left out.
+///
+/// `library` must be the analyzed library, in which the named arguments of
+/// a function call are already in the order of the function's parameters.
+pub fn untyped_literals(
+    library: &Library,
+    context: &SemanticContext,
+    file_id: &FileId,
+) -> Vec<ResolvedLiteral> {
+    let mut finder = UntypedLiteralFinder {
+        context,
+        file_id,
+        scope: ScopeKind::Global,
+        found: vec![],
+    };
+    let _ = finder.walk(library);
+    finder.found
+}
+
+/// Whether the type is a generic category such as `ANY_INT` rather than a
+/// type a value can have.
+fn is_generic(type_name: &TypeName) -> bool {
+    type_name.to_string().to_uppercase().starts_with("ANY")
+}
+
+/// The type analysis resolved for an expression when it is not generic,
+/// written in upper case as a declaration writes it.
+pub(crate) fn concrete_type(type_name: &TypeName) -> Option<TypeName> {
+    (!is_generic(type_name)).then(|| TypeName::from(type_name.to_string().to_uppercase().as_str()))
+}
+
+struct UntypedLiteralFinder<'a> {
+    context: &'a SemanticContext,
+    file_id: &'a FileId,
+    /// The POU being walked, where variable names are looked up.
+    scope: ScopeKind,
+    found: Vec<ResolvedLiteral>,
+}
+
+impl UntypedLiteralFinder<'_> {
+    /// The declared type of the variable when it is not generic.
+    fn variable_type(&self, name: &Id, scope: &ScopeKind) -> Option<TypeName> {
+        self.context
+            .symbols()
+            .find(name, scope)?
+            .data_type
+            .as_deref()
+            .map(TypeName::from)
+            .filter(|type_name| !is_generic(type_name))
+    }
+
+    /// Walks an expression whose context requires the type `expected`.
+    fn expr(&mut self, node: &Expr, expected: Option<&TypeName>) -> Result<(), ()> {
+        match &node.kind {
+            ExprKind::Const(_) => {
+                let span = node.span();
+                let untyped = node.resolved_type.as_ref().is_some_and(is_generic);
+                if let Some(expected) = expected.filter(|_| untyped) {
+                    // Literals without a span of their own cannot be shown.
+                    if span.file_id == *self.file_id && span.end > span.start {
+                        self.found.push(ResolvedLiteral {
+                            span,
+                            type_name: expected.clone(),
+                        });
+                    }
+                }
+                Ok(())
+            }
+            ExprKind::BinaryOp(op) => self.operands(&op.left, &op.right, expected),
+            ExprKind::Compare(compare) => {
+                // A comparison is BOOL whatever its operands are, so only
+                // the operands of a logical operator take the required type.
+                let logical = matches!(
+                    compare.op,
+                    CompareOp::And | CompareOp::Or | CompareOp::Xor | CompareOp::AndThen
+                );
+                self.operands(&compare.left, &compare.right, expected.filter(|_| logical))
+            }
+            ExprKind::UnaryOp(op) => self.expr(&op.term, expected),
+            ExprKind::Expression(inner) => self.expr(inner, expected),
+            ExprKind::Function(call) => self.call(call, node.resolved_type.as_ref()),
+            _ => node.recurse_visit(self),
+        }
+    }
+
+    /// Each operand takes the type of the other when that is not generic,
+    /// otherwise the type the operator's context requires.
+    fn operands(
+        &mut self,
+        left: &Expr,
+        right: &Expr,
+        expected: Option<&TypeName>,
+    ) -> Result<(), ()> {
+        let concrete = |expr: &Expr| expr.resolved_type.as_ref().and_then(concrete_type);
+        let (left_type, right_type) = (concrete(left), concrete(right));
+        self.expr(left, right_type.as_ref().or(expected))?;
+        self.expr(right, left_type.as_ref().or(expected))
+    }
+
+    /// A positional argument takes the type of the parameter it binds to.
+    /// A generic parameter of the function's generic return type takes the
+    /// type analysis inferred for the call.
+    fn call(&mut self, call: &Function, call_type: Option<&TypeName>) -> Result<(), ()> {
+        let context = self.context;
+        let signature = context.functions().get(&call.name);
+        let return_type = signature
+            .and_then(|sig| sig.return_type.as_ref())
+            .map(|return_type| return_type.to_type_name());
+        let mut parameters = signature.into_iter().flat_map(|sig| sig.input_parameters());
+        for argument in &call.param_assignment {
+            let ParamAssignmentKind::PositionalInput(input) = argument else {
+                argument.recurse_visit(self)?;
+                continue;
+            };
+            let expected = parameters
+                .next()
+                .filter(|param| !param.is_reference)
+                .and_then(|param| {
+                    if !is_generic(&param.param_type) {
+                        Some(param.param_type.clone())
+                    } else if return_type.as_ref() == Some(&param.param_type) {
+                        call_type.and_then(concrete_type)
+                    } else {
+                        None
+                    }
+                });
+            self.expr(&input.expr, expected.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Walks a POU with its name as the scope for variable names.
+    fn in_scope(
+        &mut self,
+        name: Id,
+        walk: impl FnOnce(&mut Self) -> Result<(), ()>,
+    ) -> Result<(), ()> {
+        self.scope = ScopeKind::Named(name);
+        let result = walk(self);
+        self.scope = ScopeKind::Global;
+        result
+    }
+}
+
+impl Visitor<()> for UntypedLiteralFinder<'_> {
+    type Value = ();
+
+    fn visit_program_declaration(&mut self, node: &ProgramDeclaration) -> Result<(), ()> {
+        self.in_scope(node.name.clone(), |this| node.recurse_visit(this))
+    }
+
+    fn visit_function_block_declaration(
+        &mut self,
+        node: &FunctionBlockDeclaration,
+    ) -> Result<(), ()> {
+        let name = Id::from(node.name.to_string().as_str());
+        self.in_scope(name, |this| node.recurse_visit(this))
+    }
+
+    fn visit_function_declaration(&mut self, node: &FunctionDeclaration) -> Result<(), ()> {
+        self.in_scope(node.name.clone(), |this| node.recurse_visit(this))
+    }
+
+    fn visit_expr(&mut self, node: &Expr) -> Result<(), ()> {
+        self.expr(node, None)
+    }
+
+    fn visit_assignment(&mut self, node: &Assignment) -> Result<(), ()> {
+        let target_type = match &node.target {
+            Variable::Symbolic(SymbolicVariableKind::Named(named)) if !node.deref => {
+                self.variable_type(&named.name, &self.scope)
+            }
+            _ => None,
+        };
+        self.visit_variable(&node.target)?;
+        self.expr(&node.value, target_type.as_ref())
+    }
+
+    fn visit_fb_call(&mut self, node: &FbCall) -> Result<(), ()> {
+        // The inputs of a user function block are variables in its scope.
+        let fb_scope = self
+            .variable_type(&node.var_name, &self.scope)
+            .map(|type_name| ScopeKind::Named(Id::from(type_name.to_string().as_str())));
+        for param in &node.params {
+            match (param, &fb_scope) {
+                (ParamAssignmentKind::NamedInput(input), Some(fb_scope)) => {
+                    let expected = self.variable_type(&input.name, fb_scope);
+                    self.expr(&input.expr, expected.as_ref())?;
+                }
+                _ => param.recurse_visit(self)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::parse_only;
+
+    fn analyzed(program: &str) -> Library {
+        let library = parse_only(program);
+        let (library, _context) = crate::stages::analyze(
+            &[&library],
+            &ironplc_parser::options::CompilerOptions::default(),
+        )
+        .unwrap();
+        library
+    }
+
+    #[test]
+    fn literal_at_when_bare_integer_then_generic_integer_type() {
+        let program = "PROGRAM Main
+VAR
+    Count : INT;
+END_VAR
+    IF Count > 5 THEN
+        Count := 0;
+    END_IF;
+END_PROGRAM";
+        let library = analyzed(program);
+        let offset = program.find('5').unwrap();
+
+        let literal = literal_at(&library, &FileId::default(), offset).unwrap();
+
+        assert_eq!(&program[literal.span.start..literal.span.end], "5");
+        assert_eq!(literal.type_name, TypeName::from("ANY_INT"));
+    }
+
+    #[test]
+    fn literal_at_when_typed_literal_then_declared_type() {
+        let program = "PROGRAM Main
+VAR
+    Count : DINT;
+END_VAR
+    Count := DINT#7;
+END_PROGRAM";
+        let library = analyzed(program);
+        let offset = program.find("DINT#7").unwrap() + 5;
+
+        let literal = literal_at(&library, &FileId::default(), offset).unwrap();
+
+        assert_eq!(literal.type_name, TypeName::from("DINT"));
+    }
+
+    #[test]
+    fn literal_at_when_offset_on_variable_then_none() {
+        let program = "PROGRAM Main
+VAR
+    Count : INT;
+END_VAR
+    Count := 1;
+END_PROGRAM";
+        let library = analyzed(program);
+        let offset = program.rfind("Count").unwrap();
+
+        assert!(literal_at(&library, &FileId::default(), offset).is_none());
+    }
+
+    fn untyped(program: &str) -> Vec<(String, String)> {
+        let library = parse_only(program);
+        let (library, context) = crate::stages::analyze(
+            &[&library],
+            &ironplc_parser::options::CompilerOptions::default(),
+        )
+        .unwrap();
+        untyped_literals(&library, &context, &FileId::default())
+            .into_iter()
+            .map(|literal| {
+                (
+                    program[literal.span.start..literal.span.end].to_string(),
+                    literal.type_name.to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn pair(text: &str, type_name: &str) -> (String, String) {
+        (text.to_string(), TypeName::from(type_name).to_string())
+    }
+
+    #[test]
+    fn untyped_literals_when_assigned_and_compared_then_type_of_variable() {
+        let program = "PROGRAM Main
+VAR
+    Count : INT;
+    Level : REAL;
+END_VAR
+    Level := 2;
+    IF Count > 5 THEN
+        Count := DINT#0;
+    END_IF;
+END_PROGRAM";
+
+        assert_eq!(untyped(program), vec![pair("2", "REAL"), pair("5", "INT")]);
+    }
+
+    #[test]
+    fn untyped_literals_when_arguments_then_type_of_parameter() {
+        let program = "FUNCTION Scale : REAL
+VAR_INPUT
+    Value : REAL;
+    Factor : INT;
+END_VAR
+    Scale := Value * Factor;
+END_FUNCTION
+FUNCTION_BLOCK Counter
+VAR_INPUT
+    Delta : DINT;
+END_VAR
+END_FUNCTION_BLOCK
+PROGRAM Main
+VAR
+    Result : REAL;
+    Tally : Counter;
+END_VAR
+    Result := Scale(Factor := 3, Value := 2);
+    Tally(Delta := 7);
+END_PROGRAM";
+
+        assert_eq!(
+            untyped(program),
+            vec![pair("2", "REAL"), pair("3", "INT"), pair("7", "DINT")]
+        );
+    }
+}
@@ -25,7 +25,7 @@
 //! ```
 use ironplc_dsl::{
     core::Located,
-    diagnostic::{Diagnostic, Label},
+    diagnostic::{Diagnostic, FixKind, Label},
     textual::{Case, CaseSelectionKind},
     visitor::Visitor,
 };
@@ -65,10 +65,18 @@ impl Visitor<Diagnostic> for RuleCaseBitStringLabel {
         for group in &node.statement_groups {
             for selector in &group.selectors {
                 if let CaseSelectionKind::BitStringLiteral(lit) = selector {
-                    self.diagnostics.push(Diagnostic::problem(
-                        Problem::BitStringCaseLabelNotAllowed,
-                        Label::span(lit.value.span(), "Bit-string literal CASE label"),
-                    ));
+                    self.diagnostics.push(
+                        Diagnostic::problem(
+                            Problem::BitStringCaseLabelNotAllowed,
+                            Label::span(lit.value.span(), "Bit-string literal CASE label"),
+                        )
+                        .with_fix(
+                            "Enable --allow-bit-string-case-labels",
+                            FixKind::EnableOption {
+                                option_key: String::from("allow_bit_string_case_labels"),
+                            },
+                        ),
+                    );
                 }
             }
         }
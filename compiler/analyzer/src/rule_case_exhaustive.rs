@@ -0,0 +1,508 @@
+//! Semantic rule (opt-in with `--check-case-exhaustive`) that reports `CASE`
+//! statements whose labels leave values unhandled or select a value twice.
+//!
+//! Each label of a `CASE` selects a range of values: a signed integer or
+//! bit-string literal selects one value, a subrange selects `start..end` and
+//! an enumerated value selects its member's ordinal (as resolved by
+//! `intermediates::enumeration`). The rule reports:
+//!
+//! * P4051 when the selector has an enumerated type, the `CASE` has no
+//!   `ELSE` and some member of the enumeration is selected by no label;
+//! * P4052 when a label selects a value that an earlier label already
+//!   selects. The earlier label always wins, so a label whose every value
+//!   is selected earlier (or an empty subrange) is unreachable.
+//!
+//! An `ELSE` with no statements is the same as no `ELSE`. A selector whose
+//! enumeration is declared inline in a `VAR` block has no named type and is
+//! only checked for repeated labels.
+//!
+//! ## Passes
+//!
+//! ```ignore
+//! TYPE
+//!     MODE : (IDLE, RUNNING, FAULT);
+//! END_TYPE
+//!
+//! FUNCTION_BLOCK FB_Example
+//! VAR
+//!     m : MODE;
+//!     y : INT;
+//! END_VAR
+//! CASE m OF
+//!     IDLE: y := 0;
+//!     RUNNING, FAULT: y := 1;
+//! END_CASE;
+//! END_FUNCTION_BLOCK
+//! ```
+//!
+//! ## Fails
+//!
+//! ```ignore
+//! TYPE
+//!     MODE : (IDLE, RUNNING, FAULT);
+//! END_TYPE
+//!
+//! FUNCTION_BLOCK FB_Example
+//! VAR
+//!     m : MODE;
+//!     y : INT;
+//! END_VAR
+//! CASE m OF
+//!     IDLE: y := 0;
+//!     RUNNING: y := 1;
+//! END_CASE;
+//! END_FUNCTION_BLOCK
+//! ```
+use std::collections::HashMap;
+
+use ironplc_dsl::{
+    common::{
+        DataTypeDeclarationKind, EnumerationDeclaration, Library, LibraryElementKind,
+        SpecificationKind, TypeName,
+    },
+    core::{Id, Located, SourceSpan},
+    diagnostic::{Diagnostic, Label},
+    textual::{Case, CaseSelectionKind},
+    visitor::Visitor,
+};
+use ironplc_parser::options::CompilerOptions;
+use ironplc_problems::Problem;
+
+use crate::{
+    intermediates::enumeration::resolve_ordinal_values, result::SemanticResult,
+    semantic_context::SemanticContext,
+};
+
+pub fn apply(
+    lib: &Library,
+    _context: &SemanticContext,
+    options: &CompilerOptions,
+) -> SemanticResult {
+    if !options.check_case_exhaustive {
+        return Ok(());
+    }
+
+    let mut visitor = RuleCaseExhaustive {
+        enumerations: Enumerations::from_library(lib),
+        diagnostics: Vec::new(),
+    };
+    visitor.walk(lib).map_err(|e| vec![e])?;
+
+    if !visitor.diagnostics.is_empty() {
+        return Err(visitor.diagnostics);
+    }
+    Ok(())
+}
+
+/// The members of each enumerated data type with their resolved ordinals,
+/// in declaration order.
+struct Enumerations {
+    declarations: HashMap<TypeName, EnumerationDeclaration>,
+}
+
+impl Enumerations {
+    fn from_library(lib: &Library) -> Self {
+        let declarations = lib
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                LibraryElementKind::DataTypeDeclaration(DataTypeDeclarationKind::Enumeration(
+                    decl,
+                )) => Some((decl.type_name.clone(), decl.clone())),
+                _ => None,
+            })
+            .collect();
+        Enumerations { declarations }
+    }
+
+    /// Returns the members of `type_name`, following aliases such as
+    /// `LEVEL_ALIAS : LEVEL;`, or `None` when it is not an enumeration.
+    fn members(&self, type_name: &TypeName) -> Option<Vec<(Id, i128)>> {
+        let mut current = type_name;
+        // Bounded by the number of declarations so that a cyclic alias
+        // (reported elsewhere) cannot loop forever.
+        for _ in 0..=self.declarations.len() {
+            let decl = self.declarations.get(current)?;
+            match &decl.spec_init.spec {
+                SpecificationKind::Named(base) => current = base,
+                SpecificationKind::Inline(spec) => {
+                    let ordinals = resolve_ordinal_values(&spec.values);
+                    return Some(
+                        spec.values
+                            .iter()
+                            .zip(ordinals)
+                            .map(|(value, ordinal)| (value.value.clone(), i128::from(ordinal)))
+                            .collect(),
+                    );
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The values that one `CASE` label selects, `min..=max`.
+struct LabelRange {
+    min: i128,
+    max: i128,
+    span: SourceSpan,
+}
+
+struct RuleCaseExhaustive {
+    enumerations: Enumerations,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl RuleCaseExhaustive {
+    /// Returns the values selected by `label`, or `None` when they are not
+    /// known (such as a subrange bound given by a constant or an enumerated
+    /// value that is not a member of the selector's type).
+    fn label_range(
+        label: &CaseSelectionKind,
+        members: Option<&[(Id, i128)]>,
+    ) -> Option<LabelRange> {
+        let (min, max, span) = match label {
+            CaseSelectionKind::SignedInteger(si) => {
+                let value = i128::try_from(si.clone()).ok()?;
+                (value, value, si.value.span())
+            }
+            CaseSelectionKind::Subrange(sr) => {
+                let start = sr.start.as_signed_integer()?;
+                let end = sr.end.as_signed_integer()?;
+                (
+                    i128::try_from(start.clone()).ok()?,
+                    i128::try_from(end.clone()).ok()?,
+                    SourceSpan::join(&start.value.span, &end.value.span),
+                )
+            }
+            CaseSelectionKind::EnumeratedValue(ev) => {
+                let ordinal = members?
+                    .iter()
+                    .find(|(name, _)| *name == ev.value)
+                    .map(|(_, ordinal)| *ordinal)?;
+                (ordinal, ordinal, ev.span())
+            }
+            CaseSelectionKind::BitStringLiteral(lit) => {
+                let value = i128::try_from(lit.value.value).ok()?;
+                (value, value, lit.value.span())
+            }
+        };
+        Some(LabelRange { min, max, span })
+    }
+
+    /// Reports each label that selects a value an earlier label already
+    /// selects and returns the ranges of all labels.
+    fn check_overlaps(&mut self, node: &Case, members: Option<&[(Id, i128)]>) -> Vec<LabelRange> {
+        let mut earlier: Vec<LabelRange> = Vec::new();
+        for label in node.statement_groups.iter().flat_map(|g| &g.selectors) {
+            let Some(range) = Self::label_range(label, members) else {
+                continue;
+            };
+            if range.min > range.max {
+                self.diagnostics.push(Diagnostic::problem(
+                    Problem::CaseLabelOverlap,
+                    Label::span(
+                        range.span.clone(),
+                        "CASE label is unreachable: the subrange selects no values",
+                    ),
+                ));
+                continue;
+            }
+            let overlapping: Vec<&LabelRange> = earlier
+                .iter()
+                .filter(|e| e.min <= range.max && e.max >= range.min)
+                .collect();
+            if let Some(first) = overlapping.first() {
+                let message = if uncovered(&range, &overlapping).is_none() {
+                    String::from("CASE label is unreachable: earlier labels select all its values")
+                } else {
+                    let min = range.min.max(first.min);
+                    let max = range.max.min(first.max);
+                    let values = if min == max {
+                        format!("value {min} is")
+                    } else {
+                        format!("values {min}..{max} are")
+                    };
+                    format!(
+                        "CASE label selects values of an earlier label: {values} selected there"
+                    )
+                };
+                self.diagnostics.push(
+                    Diagnostic::problem(
+                        Problem::CaseLabelOverlap,
+                        Label::span(range.span.clone(), message),
+                    )
+                    .with_secondary(Label::span(first.span.clone(), "Earlier label")),
+                );
+            }
+            earlier.push(range);
+        }
+        earlier
+    }
+}
+
+/// Returns a value of `range` that none of `others` selects, if any.
+fn uncovered(range: &LabelRange, others: &[&LabelRange]) -> Option<i128> {
+    let mut sorted: Vec<&&LabelRange> = others.iter().collect();
+    sorted.sort_by_key(|r| r.min);
+    let mut next = range.min;
+    for other in sorted {
+        if other.min > next {
+            break;
+        }
+        if other.max >= next {
+            next = other.max.checked_add(1)?;
+        }
+        if next > range.max {
+            return None;
+        }
+    }
+    Some(next)
+}
+
+impl Visitor<Diagnostic> for RuleCaseExhaustive {
+    type Value = ();
+
+    fn visit_case(&mut self, node: &Case) -> Result<Self::Value, Diagnostic> {
+        let enumeration = node
+            .selector
+            .resolved_type
+            .as_ref()
+            .and_then(|type_name| Some((type_name, self.enumerations.members(type_name)?)));
+        let members = enumeration.as_ref().map(|(_, members)| members.as_slice());
+
+        let ranges = self.check_overlaps(node, members);
+
+        if let Some((type_name, members)) = &enumeration {
+            if node.else_body.is_empty() {
+                let missing: Vec<String> = members
+                    .iter()
+                    .filter(|(_, ordinal)| {
+                        !ranges
+                            .iter()
+                            .any(|r| r.min <= *ordinal && *ordinal <= r.max)
+                    })
+                    .map(|(name, _)| name.to_string())
+                    .collect();
+                if !missing.is_empty() {
+                    self.diagnostics.push(
+                        Diagnostic::problem(
+                            Problem::CaseNotExhaustive,
+                            Label::span(
+                                node.selector.span(),
+                                format!(
+                                    "CASE over {} does not handle {}",
+                                    type_name,
+                                    missing.join(", ")
+                                ),
+                            ),
+                        )
+                        .with_help("Add a label for each missing value or add an ELSE"),
+                    );
+                }
+            }
+        }
+
+        node.recurse_visit(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ironplc_dsl::diagnostic::Diagnostic;
+    use ironplc_parser::options::CompilerOptions;
+    use ironplc_problems::Problem;
+
+    use crate::test_helpers::parse_and_resolve_types_with_context;
+
+    fn check(program: &str) -> Vec<Diagnostic> {
+        let (library, context) = parse_and_resolve_types_with_context(program);
+        let options = CompilerOptions {
+            check_case_exhaustive: true,
+            ..CompilerOptions::default()
+        };
+        match super::apply(&library, &context, &options) {
+            Ok(()) => vec![],
+            Err(diagnostics) => diagnostics,
+        }
+    }
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<String> {
+        diagnostics.iter().map(|d| d.code.clone()).collect()
+    }
+
+    fn function_block(types: &str, selector_type: &str, body: &str) -> String {
+        format!(
+            "
+{types}
+FUNCTION_BLOCK FB_Example
+VAR
+    m : {selector_type};
+    y : INT;
+END_VAR
+CASE m OF
+{body}
+END_CASE;
+END_FUNCTION_BLOCK"
+        )
+    }
+
+    const MODE: &str = "
+TYPE
+    MODE : (IDLE, RUNNING, FAULT);
+END_TYPE";
+
+    #[test]
+    fn apply_when_enum_case_handles_every_value_then_ok() {
+        let program = function_block(MODE, "MODE", "IDLE: y := 0; RUNNING, FAULT: y := 1;");
+        assert!(check(&program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_enum_case_misses_value_then_error_names_value() {
+        let program = function_block(MODE, "MODE", "IDLE: y := 0; RUNNING: y := 1;");
+        let diagnostics = check(&program);
+        assert_eq!(codes(&diagnostics), vec![Problem::CaseNotExhaustive.code()]);
+        assert!(diagnostics[0].primary.message.contains("FAULT"));
+        assert!(!diagnostics[0].primary.message.contains("IDLE"));
+    }
+
+    #[test]
+    fn apply_when_enum_case_misses_value_and_has_else_then_ok() {
+        let program = function_block(MODE, "MODE", "IDLE: y := 0; ELSE y := 1;");
+        assert!(check(&program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_enum_case_misses_value_and_check_disabled_then_ok() {
+        let program = function_block(MODE, "MODE", "IDLE: y := 0;");
+        let (library, context) = parse_and_resolve_types_with_context(&program);
+        assert!(super::apply(&library, &context, &CompilerOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn apply_when_enum_case_with_qualified_labels_then_ok() {
+        let program = function_block(
+            MODE,
+            "MODE",
+            "MODE#IDLE: y := 0; MODE#RUNNING: y := 1; MODE#FAULT: y := 2;",
+        );
+        assert!(check(&program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_enum_case_through_alias_misses_value_then_error() {
+        let types = "
+TYPE
+    MODE : (IDLE, RUNNING, FAULT);
+    MODE_ALIAS : MODE;
+END_TYPE";
+        let program = function_block(types, "MODE_ALIAS", "IDLE: y := 0; RUNNING: y := 1;");
+        assert_eq!(
+            codes(&check(&program)),
+            vec![Problem::CaseNotExhaustive.code()]
+        );
+    }
+
+    #[test]
+    fn apply_when_enum_case_covers_values_with_explicit_ordinal_subrange_then_ok() {
+        let types = "
+TYPE
+    MODE : (IDLE := 1, RUNNING, FAULT := 10);
+END_TYPE";
+        let program = function_block(types, "MODE", "1..2: y := 0; FAULT: y := 1;");
+        assert!(check(&program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_enum_label_repeated_then_error() {
+        let program = function_block(
+            MODE,
+            "MODE",
+            "IDLE: y := 0; RUNNING, FAULT: y := 1; IDLE: y := 2;",
+        );
+        let diagnostics = check(&program);
+        assert_eq!(codes(&diagnostics), vec![Problem::CaseLabelOverlap.code()]);
+        assert!(diagnostics[0].primary.message.contains("unreachable"));
+    }
+
+    #[test]
+    fn apply_when_enum_members_share_ordinal_then_error() {
+        let types = "
+TYPE
+    MODE : (IDLE, STOPPED := 0, RUNNING);
+END_TYPE";
+        let program = function_block(
+            types,
+            "MODE",
+            "IDLE: y := 0; STOPPED: y := 1; RUNNING: y := 2;",
+        );
+        assert_eq!(
+            codes(&check(&program)),
+            vec![Problem::CaseLabelOverlap.code()]
+        );
+    }
+
+    #[test]
+    fn apply_when_integer_label_inside_earlier_subrange_then_unreachable() {
+        let program = function_block("", "INT", "1..10: y := 0; 5: y := 1;");
+        let diagnostics = check(&program);
+        assert_eq!(codes(&diagnostics), vec![Problem::CaseLabelOverlap.code()]);
+        assert!(diagnostics[0].primary.message.contains("unreachable"));
+        assert_eq!(diagnostics[0].secondary.len(), 1);
+    }
+
+    #[test]
+    fn apply_when_subrange_covered_by_several_earlier_labels_then_unreachable() {
+        let program = function_block("", "INT", "1..4: y := 0; 5..8, 9: y := 1; 2..9: y := 2;");
+        let diagnostics = check(&program);
+        assert_eq!(codes(&diagnostics), vec![Problem::CaseLabelOverlap.code()]);
+        assert!(diagnostics[0].primary.message.contains("unreachable"));
+    }
+
+    #[test]
+    fn apply_when_subranges_partially_overlap_then_error_names_values() {
+        let program = function_block("", "INT", "1..10: y := 0; 8..20: y := 1;");
+        let diagnostics = check(&program);
+        assert_eq!(codes(&diagnostics), vec![Problem::CaseLabelOverlap.code()]);
+        assert!(diagnostics[0].primary.message.contains("8..10"));
+    }
+
+    #[test]
+    fn apply_when_subrange_is_empty_then_unreachable() {
+        let program = function_block("", "INT", "10..1: y := 0;");
+        let diagnostics = check(&program);
+        assert_eq!(codes(&diagnostics), vec![Problem::CaseLabelOverlap.code()]);
+        assert!(diagnostics[0].primary.message.contains("no values"));
+    }
+
+    #[test]
+    fn apply_when_bit_string_label_repeats_integer_label_then_error() {
+        let program = function_block("", "DINT", "16: y := 0; 16#10: y := 1;");
+        assert_eq!(
+            codes(&check(&program)),
+            vec![Problem::CaseLabelOverlap.code()]
+        );
+    }
+
+    #[test]
+    fn apply_when_integer_case_without_else_then_ok() {
+        let program = function_block("", "INT", "1: y := 0; 2..5: y := 1; -3: y := 2;");
+        assert!(check(&program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_nested_case_misses_value_then_error() {
+        let program = function_block(
+            MODE,
+            "MODE",
+            "IDLE, RUNNING, FAULT:
+    CASE m OF
+        IDLE: y := 0;
+    END_CASE;",
+        );
+        let diagnostics = check(&program);
+        assert_eq!(codes(&diagnostics), vec![Problem::CaseNotExhaustive.code()]);
+        assert!(diagnostics[0].primary.message.contains("RUNNING, FAULT"));
+    }
+}
@@ -29,7 +29,7 @@
 use ironplc_dsl::{
     common::{Library, LibraryElementKind},
     core::{Located, SourceSpan},
-    diagnostic::{Diagnostic, Label},
+    diagnostic::{Diagnostic, FixKind, Label},
 };
 use ironplc_problems::Problem;
 
@@ -66,6 +66,12 @@ pub fn apply(
                 "Move the VAR_GLOBAL block inside a CONFIGURATION (or RESOURCE) block, \
                  or select a dialect that supports top-level VAR_GLOBAL.",
             )
+            .with_fix(
+                "Enable --allow-top-level-var-global",
+                FixKind::EnableOption {
+                    option_key: String::from("allow_top_level_var_global"),
+                },
+            )
         })
         .collect();
 
@@ -16,7 +16,7 @@ use ironplc_dsl::{
     common::*,
     configuration::ResourceDeclaration,
     core::Located,
-    diagnostic::{Diagnostic, Label},
+    diagnostic::{Diagnostic, FixKind, Label},
     visitor::Visitor,
 };
 use ironplc_problems::Problem;
@@ -72,7 +72,16 @@ impl Visitor<Diagnostic> for RuleProgramTaskDefinitionExists {
                             Label::span(task_name.span(), "Reference to task configuration"),
                         )
                         .with_context_id("program", &program.name)
-                        .with_context_id("task name", task_name),
+                        .with_context_id("task name", task_name)
+                        .with_fix(
+                            format!("Add task '{task_name}'"),
+                            FixKind::InsertLineBefore {
+                                span: program.name.span(),
+                                text: format!(
+                                    "TASK {task_name}(INTERVAL := T#100ms, PRIORITY := 1);"
+                                ),
+                            },
+                        ),
                     );
                 }
             }
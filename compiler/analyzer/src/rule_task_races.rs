@@ -0,0 +1,925 @@
+//! Semantic rule (opt-in with `--check-task-races`) that reports shared
+//! `VAR_GLOBAL` variables that tasks access unsafely.
+//!
+//! Each `PROGRAM <instance> WITH <task> : <type>` in a `RESOURCE` runs the
+//! program body in that task. The rule gathers the globals that each task
+//! reads and writes, following calls into function blocks (their body and
+//! methods) and user-defined functions. Two tasks race when one can
+//! interrupt the other (different priorities) or when they run in parallel
+//! (different resources). For two racing tasks, the rule reports:
+//!
+//! * P4049 when both write the same global;
+//! * P4050 when one writes a global wider than one 32-bit word that the
+//!   other reads, because the reader can see half of an update.
+//!
+//! IronPLC schedules tasks cooperatively, so neither race happens in the
+//! VM; the check is for programs that also run on a preemptive runtime.
+//!
+//! ## Passes
+//!
+//! ```ignore
+//! PROGRAM fast
+//! VAR_EXTERNAL setpoint : INT; END_VAR
+//!     setpoint := 10;
+//! END_PROGRAM
+//!
+//! CONFIGURATION config
+//!     VAR_GLOBAL setpoint : INT; END_VAR
+//!     RESOURCE resource1 ON PLC
+//!         TASK fast_task(INTERVAL := T#10ms, PRIORITY := 1);
+//!         PROGRAM fast_instance WITH fast_task : fast;
+//!     END_RESOURCE
+//! END_CONFIGURATION
+//! ```
+//!
+//! ## Fails
+//!
+//! ```ignore
+//! PROGRAM fast
+//! VAR_EXTERNAL setpoint : INT; END_VAR
+//!     setpoint := 10;
+//! END_PROGRAM
+//!
+//! PROGRAM slow
+//! VAR_EXTERNAL setpoint : INT; END_VAR
+//!     setpoint := 20;
+//! END_PROGRAM
+//!
+//! CONFIGURATION config
+//!     VAR_GLOBAL setpoint : INT; END_VAR
+//!     RESOURCE resource1 ON PLC
+//!         TASK fast_task(INTERVAL := T#10ms, PRIORITY := 1);
+//!         TASK slow_task(INTERVAL := T#100ms, PRIORITY := 5);
+//!         PROGRAM fast_instance WITH fast_task : fast;
+//!         PROGRAM slow_instance WITH slow_task : slow;
+//!     END_RESOURCE
+//! END_CONFIGURATION
+//! ```
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use ironplc_dsl::{
+    common::*,
+    configuration::{ConfigurationDeclaration, TaskConfiguration},
+    core::{Id, Located, SourceSpan},
+    diagnostic::{Diagnostic, Label},
+    textual::*,
+    visitor::Visitor,
+};
+use ironplc_parser::options::CompilerOptions;
+use ironplc_problems::Problem;
+
+use crate::{
+    intermediate_type::IntermediateType, result::SemanticResult, semantic_context::SemanticContext,
+};
+
+/// The widest value, in bytes, that a task reads or writes in one access.
+const WORD_BYTES: u32 = 4;
+
+pub fn apply(
+    lib: &Library,
+    context: &SemanticContext,
+    options: &CompilerOptions,
+) -> SemanticResult {
+    if !options.check_task_races {
+        return Ok(());
+    }
+
+    let pous = Pous::new(lib, context);
+    let top_level_globals: Vec<&VarDecl> = lib
+        .elements
+        .iter()
+        .filter_map(|element| match element {
+            LibraryElementKind::GlobalVarDeclarations(globals) => Some(globals),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+
+    let mut diagnostics = vec![];
+    for element in &lib.elements {
+        if let LibraryElementKind::ConfigurationDeclaration(config) = element {
+            check_configuration(config, &top_level_globals, &pous, &mut diagnostics);
+        }
+    }
+
+    if !diagnostics.is_empty() {
+        return Err(diagnostics);
+    }
+    Ok(())
+}
+
+/// A global variable that tasks of one configuration can share.
+struct Global<'a> {
+    name: &'a Id,
+    multi_word: bool,
+}
+
+/// A task with the globals that the programs it runs access.
+struct Task<'a> {
+    config: &'a TaskConfiguration,
+    resource: usize,
+    /// Accesses by index into the configuration's globals.
+    accesses: Accesses<usize>,
+}
+
+impl Task<'_> {
+    /// Whether the two tasks can run one in the middle of the other.
+    fn races_with(&self, other: &Task) -> bool {
+        self.resource != other.resource || self.config.priority != other.config.priority
+    }
+
+    fn describe(&self, verb: &str) -> String {
+        format!(
+            "{verb} in task '{}' (PRIORITY := {})",
+            self.config.name, self.config.priority
+        )
+    }
+}
+
+fn check_configuration(
+    config: &ConfigurationDeclaration,
+    top_level_globals: &[&VarDecl],
+    pous: &Pous,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut summaries = Summaries::default();
+    let mut globals: Vec<Global> = vec![];
+    let mut tasks: Vec<Task> = vec![];
+
+    // Globals declared at the top level or in the configuration are shared
+    // by every resource; a resource global hides them within its resource.
+    let shared: Vec<usize> = top_level_globals
+        .iter()
+        .copied()
+        .chain(config.global_var.iter())
+        .filter_map(|decl| add_global(&mut globals, decl, pous.context))
+        .collect();
+
+    for (resource_index, resource) in config.resource_decl.iter().enumerate() {
+        let local: Vec<usize> = resource
+            .global_vars
+            .iter()
+            .filter_map(|decl| add_global(&mut globals, decl, pous.context))
+            .collect();
+        let scope = Scope {
+            local: &local,
+            shared: &shared,
+            globals: &globals,
+        };
+
+        let first_task = tasks.len();
+        for task in &resource.tasks {
+            tasks.push(Task {
+                config: task,
+                resource: resource_index,
+                accesses: Accesses::default(),
+            });
+        }
+
+        for program in &resource.programs {
+            let Some(task_name) = &program.task_name else {
+                continue;
+            };
+            let Some(task) = tasks[first_task..]
+                .iter_mut()
+                .find(|task| &task.config.name == task_name)
+            else {
+                continue;
+            };
+            let accesses = summaries.transitive(pous, &Callee::Program(program.type_name.clone()));
+            task.accesses
+                .merge_with(&accesses, |name| scope.resolve(name));
+        }
+    }
+
+    for (key, global) in globals.iter().enumerate() {
+        let mut write_conflicts: HashSet<(usize, usize)> = HashSet::new();
+
+        for (a, first) in tasks.iter().enumerate() {
+            let Some(first_write) = first.accesses.writes.get(&key) else {
+                continue;
+            };
+            for (b, second) in tasks.iter().enumerate().skip(a + 1) {
+                if !first.races_with(second) {
+                    continue;
+                }
+                let Some(second_write) = second.accesses.writes.get(&key) else {
+                    continue;
+                };
+                write_conflicts.insert((a, b));
+                diagnostics.push(
+                    Diagnostic::problem(
+                        Problem::TaskSharedVariableWriteConflict,
+                        Label::span(first_write.clone(), first.describe("Written")),
+                    )
+                    .with_secondary(Label::span(
+                        second_write.clone(),
+                        second.describe("Written"),
+                    ))
+                    .with_context_id("variable", global.name),
+                );
+            }
+        }
+
+        if !global.multi_word {
+            continue;
+        }
+        for (w, writer) in tasks.iter().enumerate() {
+            let Some(write) = writer.accesses.writes.get(&key) else {
+                continue;
+            };
+            for (r, reader) in tasks.iter().enumerate() {
+                if w == r || !writer.races_with(reader) {
+                    continue;
+                }
+                if write_conflicts.contains(&(w.min(r), w.max(r))) {
+                    continue;
+                }
+                let Some(read) = reader.accesses.reads.get(&key) else {
+                    continue;
+                };
+                diagnostics.push(
+                    Diagnostic::problem(
+                        Problem::TaskSharedVariableTornRead,
+                        Label::span(write.clone(), writer.describe("Written")),
+                    )
+                    .with_secondary(Label::span(read.clone(), reader.describe("Read")))
+                    .with_context_id("variable", global.name),
+                );
+            }
+        }
+    }
+}
+
+/// Adds a global declaration and returns its index, or `None` for a located
+/// variable without a name.
+fn add_global<'a>(
+    globals: &mut Vec<Global<'a>>,
+    decl: &'a VarDecl,
+    context: &SemanticContext,
+) -> Option<usize> {
+    let name = decl.identifier.symbolic_id()?;
+    globals.push(Global {
+        name,
+        multi_word: is_multi_word(&decl.initializer, context),
+    });
+    Some(globals.len() - 1)
+}
+
+/// Whether a variable of the declared type is wider than one word, so that
+/// reading it takes more than one access.
+fn is_multi_word(initializer: &InitialValueAssignmentKind, context: &SemanticContext) -> bool {
+    match initializer {
+        InitialValueAssignmentKind::Simple(simple) => {
+            type_is_multi_word(&simple.type_name, context)
+        }
+        InitialValueAssignmentKind::LateResolvedType(type_name) => {
+            type_is_multi_word(type_name, context)
+        }
+        InitialValueAssignmentKind::Subrange(SpecificationKind::Named(type_name)) => {
+            type_is_multi_word(type_name, context)
+        }
+        InitialValueAssignmentKind::Subrange(SpecificationKind::Inline(spec)) => {
+            type_is_multi_word(&spec.type_name.clone().into(), context)
+        }
+        InitialValueAssignmentKind::String(_)
+        | InitialValueAssignmentKind::FunctionBlock(_)
+        | InitialValueAssignmentKind::FunctionBlockCall(_)
+        | InitialValueAssignmentKind::Structure(_)
+        | InitialValueAssignmentKind::Array(_)
+        | InitialValueAssignmentKind::Reference(_) => true,
+        InitialValueAssignmentKind::None(_)
+        | InitialValueAssignmentKind::EnumeratedValues(_)
+        | InitialValueAssignmentKind::EnumeratedType(_)
+        | InitialValueAssignmentKind::SimpleExpr(_) => false,
+    }
+}
+
+fn type_is_multi_word(type_name: &TypeName, context: &SemanticContext) -> bool {
+    context.types().get(type_name).is_some_and(|attributes| {
+        let representation = &attributes.representation;
+        matches!(
+            representation,
+            IntermediateType::String { .. }
+                | IntermediateType::Structure { .. }
+                | IntermediateType::Array { .. }
+                | IntermediateType::FunctionBlock { .. }
+        ) || representation
+            .size_in_bytes()
+            .is_none_or(|size| size > WORD_BYTES)
+    })
+}
+
+/// The globals that the programs of one resource can name: the resource's
+/// own globals first, then the shared ones. Indexes are into `globals`.
+struct Scope<'a, 'b> {
+    local: &'a [usize],
+    shared: &'a [usize],
+    globals: &'a [Global<'b>],
+}
+
+impl Scope<'_, '_> {
+    fn resolve(&self, name: &Id) -> Option<usize> {
+        self.local
+            .iter()
+            .chain(self.shared.iter())
+            .copied()
+            .find(|index| self.globals[*index].name == name)
+    }
+}
+
+/// The first site of each read and write of a global, keyed by the global's
+/// name in a POU, or by its index once resolved for a task.
+#[derive(Clone)]
+struct Accesses<K> {
+    reads: HashMap<K, SourceSpan>,
+    writes: HashMap<K, SourceSpan>,
+}
+
+impl<K> Default for Accesses<K> {
+    fn default() -> Self {
+        Accesses {
+            reads: HashMap::new(),
+            writes: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> Accesses<K> {
+    fn record(&mut self, write: bool, key: K, span: SourceSpan) {
+        let sites = if write {
+            &mut self.writes
+        } else {
+            &mut self.reads
+        };
+        sites.entry(key).or_insert(span);
+    }
+
+    /// Merges `other`, keeping the sites already recorded. `key` maps the
+    /// keys of `other` and drops the ones that map to `None`.
+    fn merge_with<L>(&mut self, other: &Accesses<L>, key: impl Fn(&L) -> Option<K>) {
+        for (name, span) in &other.reads {
+            if let Some(key) = key(name) {
+                self.record(false, key, span.clone());
+            }
+        }
+        for (name, span) in &other.writes {
+            if let Some(key) = key(name) {
+                self.record(true, key, span.clone());
+            }
+        }
+    }
+
+    fn merge(&mut self, other: &Accesses<K>) {
+        self.merge_with(other, |key| Some(key.clone()));
+    }
+}
+
+/// A POU whose body runs when another POU calls it.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Callee {
+    Program(Id),
+    FunctionBlock(Id),
+    Function(Id),
+}
+
+/// The declarations of the library's POUs, by name.
+struct Pous<'a> {
+    context: &'a SemanticContext,
+    global_names: HashSet<&'a Id>,
+    programs: HashMap<&'a Id, &'a ProgramDeclaration>,
+    function_blocks: HashMap<&'a Id, &'a FunctionBlockDeclaration>,
+    functions: HashMap<&'a Id, &'a FunctionDeclaration>,
+}
+
+impl<'a> Pous<'a> {
+    fn new(lib: &'a Library, context: &'a SemanticContext) -> Self {
+        let mut pous = Pous {
+            context,
+            global_names: HashSet::new(),
+            programs: HashMap::new(),
+            function_blocks: HashMap::new(),
+            functions: HashMap::new(),
+        };
+        let mut add_globals = |decls: &'a [VarDecl]| {
+            for decl in decls {
+                if let Some(name) = decl.identifier.symbolic_id() {
+                    pous.global_names.insert(name);
+                }
+            }
+        };
+        for element in &lib.elements {
+            match element {
+                LibraryElementKind::GlobalVarDeclarations(globals) => add_globals(globals),
+                LibraryElementKind::ConfigurationDeclaration(config) => {
+                    add_globals(&config.global_var);
+                    for resource in &config.resource_decl {
+                        add_globals(&resource.global_vars);
+                    }
+                }
+                _ => {}
+            }
+        }
+        for element in &lib.elements {
+            match element {
+                LibraryElementKind::ProgramDeclaration(program) => {
+                    pous.programs.insert(&program.name, program);
+                }
+                LibraryElementKind::FunctionBlockDeclaration(function_block) => {
+                    pous.function_blocks
+                        .insert(&function_block.name.name, function_block);
+                }
+                LibraryElementKind::FunctionDeclaration(function) => {
+                    pous.functions.insert(&function.name, function);
+                }
+                _ => {}
+            }
+        }
+        pous
+    }
+
+    /// Collects the globals that one POU accesses directly, and the POUs it
+    /// calls. Returns `None` when the POU is not declared in the library
+    /// (such as a standard library function block).
+    fn summarize(&self, callee: &Callee) -> Option<(Accesses<Id>, Vec<Callee>)> {
+        let mut collector = AccessCollector::new(&self.global_names);
+        match callee {
+            Callee::Program(name) => {
+                let program = self.programs.get(name)?;
+                collector.declare(&program.variables);
+                collector
+                    .visit_function_block_body_kind(&program.body)
+                    .ok()?;
+            }
+            Callee::FunctionBlock(name) => {
+                let function_block = self.function_blocks.get(name)?;
+                collector.declare(&function_block.variables);
+                collector
+                    .visit_function_block_body_kind(&function_block.body)
+                    .ok()?;
+                for method in &function_block.methods {
+                    let mut method_collector = collector.nested();
+                    method_collector.declare(&method.variables);
+                    for stmt in &method.body {
+                        method_collector.visit_stmt_kind(stmt).ok()?;
+                    }
+                    collector.absorb(method_collector);
+                }
+            }
+            Callee::Function(name) => {
+                let function = self.functions.get(name)?;
+                collector.locals.insert(&function.name);
+                collector.declare(&function.variables);
+                for stmt in &function.body {
+                    collector.visit_stmt_kind(stmt).ok()?;
+                }
+            }
+        }
+        Some((collector.accesses, collector.calls))
+    }
+}
+
+/// Memoized accesses of each POU including everything it calls.
+#[derive(Default)]
+struct Summaries {
+    done: HashMap<Callee, Accesses<Id>>,
+    in_progress: HashSet<Callee>,
+}
+
+impl Summaries {
+    fn transitive(&mut self, pous: &Pous, callee: &Callee) -> Accesses<Id> {
+        if let Some(accesses) = self.done.get(callee) {
+            return accesses.clone();
+        }
+        // A recursive call adds nothing that the outer visit does not.
+        if !self.in_progress.insert(callee.clone()) {
+            return Accesses::default();
+        }
+
+        let mut accesses = Accesses::default();
+        if let Some((direct, calls)) = pous.summarize(callee) {
+            accesses.merge(&direct);
+            for call in &calls {
+                let called = self.transitive(pous, call);
+                accesses.merge(&called);
+            }
+        }
+
+        self.in_progress.remove(callee);
+        self.done.insert(callee.clone(), accesses.clone());
+        accesses
+    }
+}
+
+/// Visits one POU body, recording each access to a name that refers to a
+/// global (a name that the POU does not declare itself, or declares as
+/// `VAR_EXTERNAL`).
+struct AccessCollector<'a> {
+    global_names: &'a HashSet<&'a Id>,
+    locals: HashSet<&'a Id>,
+    instances: HashMap<Id, Id>,
+    writing: bool,
+    accesses: Accesses<Id>,
+    calls: Vec<Callee>,
+}
+
+impl<'a> AccessCollector<'a> {
+    fn new(global_names: &'a HashSet<&'a Id>) -> Self {
+        AccessCollector {
+            global_names,
+            locals: HashSet::new(),
+            instances: HashMap::new(),
+            writing: false,
+            accesses: Accesses::default(),
+            calls: vec![],
+        }
+    }
+
+    /// A collector for a method body, which also sees the variables of its
+    /// function block.
+    fn nested(&self) -> Self {
+        AccessCollector {
+            global_names: self.global_names,
+            locals: self.locals.clone(),
+            instances: self.instances.clone(),
+            writing: false,
+            accesses: Accesses::default(),
+            calls: vec![],
+        }
+    }
+
+    fn absorb(&mut self, other: AccessCollector) {
+        self.accesses.merge(&other.accesses);
+        self.calls.extend(other.calls);
+    }
+
+    fn declare(&mut self, variables: &'a [VarDecl]) {
+        for decl in variables {
+            let Some(name) = decl.identifier.symbolic_id() else {
+                continue;
+            };
+            if decl.var_type != VariableType::External {
+                self.locals.insert(name);
+            }
+            let fb_type = match &decl.initializer {
+                InitialValueAssignmentKind::FunctionBlock(init) => Some(&init.type_name),
+                InitialValueAssignmentKind::FunctionBlockCall(init) => Some(&init.type_name),
+                _ => None,
+            };
+            if let Some(fb_type) = fb_type {
+                self.instances.insert(name.clone(), fb_type.name.clone());
+            }
+        }
+    }
+
+    fn is_global(&self, name: &Id) -> bool {
+        self.global_names.contains(name) && !self.locals.contains(name)
+    }
+
+    fn record(&mut self, write: bool, name: &Id) {
+        if self.is_global(name) {
+            self.accesses.record(write, name.clone(), name.span());
+        }
+    }
+
+    /// Records a call of the function block instance `name`. Calling an
+    /// instance that is a global changes the global's state.
+    fn call_instance(&mut self, name: &Id) {
+        if let Some(fb_type) = self.instances.get(name) {
+            self.calls.push(Callee::FunctionBlock(fb_type.clone()));
+        }
+        self.record(true, name);
+    }
+
+    fn with_writing<T>(&mut self, writing: bool, f: impl FnOnce(&mut Self) -> T) -> T {
+        let saved = self.writing;
+        self.writing = writing;
+        let result = f(self);
+        self.writing = saved;
+        result
+    }
+}
+
+impl Visitor<()> for AccessCollector<'_> {
+    type Value = ();
+
+    fn visit_assignment(&mut self, node: &Assignment) -> Result<(), ()> {
+        self.with_writing(true, |v| v.visit_variable(&node.target))?;
+        self.with_writing(false, |v| v.visit_expr(&node.value))
+    }
+
+    fn visit_output(&mut self, node: &Output) -> Result<(), ()> {
+        self.with_writing(true, |v| v.visit_variable(&node.tgt))
+    }
+
+    fn visit_for(&mut self, node: &For) -> Result<(), ()> {
+        self.record(true, &node.control);
+        node.recurse_visit(self)
+    }
+
+    fn visit_array_variable(&mut self, node: &ArrayVariable) -> Result<(), ()> {
+        self.visit_symbolic_variable_kind(&node.subscripted_variable)?;
+        self.with_writing(false, |v| {
+            for subscript in &node.subscripts {
+                v.visit_expr(subscript)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn visit_deref_variable(&mut self, node: &DerefVariable) -> Result<(), ()> {
+        // Writing through a reference leaves the reference itself unchanged.
+        self.with_writing(false, |v| v.visit_symbolic_variable_kind(&node.variable))
+    }
+
+    fn visit_named_variable(&mut self, node: &NamedVariable) -> Result<(), ()> {
+        self.record(self.writing, &node.name);
+        Ok(())
+    }
+
+    fn visit_fb_call(&mut self, node: &FbCall) -> Result<(), ()> {
+        self.call_instance(&node.var_name);
+        node.recurse_visit(self)
+    }
+
+    fn visit_method_call(&mut self, node: &MethodCall) -> Result<(), ()> {
+        if let MethodReceiver::Instance(instance) = &node.receiver {
+            self.call_instance(instance);
+        }
+        node.recurse_visit(self)
+    }
+
+    fn visit_function(&mut self, node: &Function) -> Result<(), ()> {
+        self.calls.push(Callee::Function(node.name.clone()));
+        node.recurse_visit(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ironplc_dsl::diagnostic::Diagnostic;
+    use ironplc_parser::options::CompilerOptions;
+    use ironplc_problems::Problem;
+
+    use crate::test_helpers::parse_and_resolve_types_with_context;
+
+    fn check(program: &str) -> Vec<Diagnostic> {
+        let (library, context) = parse_and_resolve_types_with_context(program);
+        let options = CompilerOptions {
+            check_task_races: true,
+            ..CompilerOptions::default()
+        };
+        match super::apply(&library, &context, &options) {
+            Ok(()) => vec![],
+            Err(diagnostics) => diagnostics,
+        }
+    }
+
+    fn configuration(global_type: &str, fast: &str, slow: &str) -> String {
+        format!(
+            "
+PROGRAM fast
+VAR_EXTERNAL
+    shared : {global_type};
+END_VAR
+VAR
+    copy : {global_type};
+END_VAR
+{fast}
+END_PROGRAM
+
+PROGRAM slow
+VAR_EXTERNAL
+    shared : {global_type};
+END_VAR
+VAR
+    copy : {global_type};
+END_VAR
+{slow}
+END_PROGRAM
+
+CONFIGURATION config
+    VAR_GLOBAL
+        shared : {global_type};
+    END_VAR
+    RESOURCE resource1 ON PLC
+        TASK fast_task(INTERVAL := T#10ms, PRIORITY := 1);
+        TASK slow_task(INTERVAL := T#100ms, PRIORITY := 5);
+        PROGRAM fast_instance WITH fast_task : fast;
+        PROGRAM slow_instance WITH slow_task : slow;
+    END_RESOURCE
+END_CONFIGURATION"
+        )
+    }
+
+    #[test]
+    fn apply_when_check_disabled_then_ok() {
+        let program = configuration("INT", "shared := 1;", "shared := 2;");
+        let (library, context) = parse_and_resolve_types_with_context(&program);
+        assert!(super::apply(&library, &context, &CompilerOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn apply_when_two_tasks_write_global_then_write_conflict_with_both_sites() {
+        let program = configuration("INT", "shared := 1;", "shared := 2;");
+        let diagnostics = check(&program);
+
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(
+            diagnostic.code,
+            Problem::TaskSharedVariableWriteConflict.code()
+        );
+        assert!(diagnostic.primary.message.contains("fast_task"));
+        assert_eq!(diagnostic.secondary.len(), 1);
+        assert!(diagnostic.secondary[0].message.contains("slow_task"));
+        assert_ne!(
+            diagnostic.primary.location.start,
+            diagnostic.secondary[0].location.start
+        );
+    }
+
+    #[test]
+    fn apply_when_same_priority_tasks_write_global_then_ok() {
+        let program = configuration("INT", "shared := 1;", "shared := 2;")
+            .replace("PRIORITY := 5", "PRIORITY := 1");
+        assert!(check(&program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_one_task_writes_word_read_by_other_then_ok() {
+        let program = configuration("DINT", "shared := 1;", "copy := shared;");
+        assert!(check(&program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_one_task_writes_lreal_read_by_other_then_torn_read() {
+        let program = configuration("LREAL", "shared := 1.0;", "copy := shared;");
+        let diagnostics = check(&program);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Problem::TaskSharedVariableTornRead.code()
+        );
+        assert!(diagnostics[0].primary.message.contains("Written"));
+        assert!(diagnostics[0].secondary[0].message.contains("Read"));
+    }
+
+    #[test]
+    fn apply_when_one_task_writes_string_read_by_other_then_torn_read() {
+        let program = configuration("STRING", "shared := 'on';", "copy := shared;");
+        let diagnostics = check(&program);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Problem::TaskSharedVariableTornRead.code()
+        );
+    }
+
+    #[test]
+    fn apply_when_both_tasks_only_read_then_ok() {
+        let program = configuration("LREAL", "copy := shared;", "copy := shared;");
+        assert!(check(&program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_local_shadows_global_then_ok() {
+        let program = "
+PROGRAM fast
+VAR
+    shared : INT;
+END_VAR
+    shared := 1;
+END_PROGRAM
+
+PROGRAM slow
+VAR_EXTERNAL
+    shared : INT;
+END_VAR
+    shared := 2;
+END_PROGRAM
+
+CONFIGURATION config
+    VAR_GLOBAL
+        shared : INT;
+    END_VAR
+    RESOURCE resource1 ON PLC
+        TASK fast_task(INTERVAL := T#10ms, PRIORITY := 1);
+        TASK slow_task(INTERVAL := T#100ms, PRIORITY := 5);
+        PROGRAM fast_instance WITH fast_task : fast;
+        PROGRAM slow_instance WITH slow_task : slow;
+    END_RESOURCE
+END_CONFIGURATION";
+        assert!(check(program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_write_inside_function_block_then_write_conflict_at_function_block_site() {
+        let program = "
+FUNCTION_BLOCK setter
+VAR_EXTERNAL
+    shared : INT;
+END_VAR
+    shared := 1;
+END_FUNCTION_BLOCK
+
+PROGRAM fast
+VAR
+    instance : setter;
+END_VAR
+    instance();
+END_PROGRAM
+
+PROGRAM slow
+VAR_EXTERNAL
+    shared : INT;
+END_VAR
+    shared := 2;
+END_PROGRAM
+
+CONFIGURATION config
+    VAR_GLOBAL
+        shared : INT;
+    END_VAR
+    RESOURCE resource1 ON PLC
+        TASK fast_task(INTERVAL := T#10ms, PRIORITY := 1);
+        TASK slow_task(INTERVAL := T#100ms, PRIORITY := 5);
+        PROGRAM fast_instance WITH fast_task : fast;
+        PROGRAM slow_instance WITH slow_task : slow;
+    END_RESOURCE
+END_CONFIGURATION";
+        let diagnostics = check(program);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Problem::TaskSharedVariableWriteConflict.code()
+        );
+    }
+
+    #[test]
+    fn apply_when_read_inside_nested_function_block_then_torn_read() {
+        let program = "
+FUNCTION_BLOCK reader
+VAR_EXTERNAL
+    shared : LREAL;
+END_VAR
+VAR
+    copy : LREAL;
+END_VAR
+    copy := shared;
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK outer
+VAR
+    inner : reader;
+END_VAR
+    inner();
+END_FUNCTION_BLOCK
+
+PROGRAM fast
+VAR_EXTERNAL
+    shared : LREAL;
+END_VAR
+    shared := 1.0;
+END_PROGRAM
+
+PROGRAM slow
+VAR
+    instance : outer;
+END_VAR
+    instance();
+END_PROGRAM
+
+CONFIGURATION config
+    VAR_GLOBAL
+        shared : LREAL;
+    END_VAR
+    RESOURCE resource1 ON PLC
+        TASK fast_task(INTERVAL := T#10ms, PRIORITY := 1);
+        TASK slow_task(INTERVAL := T#100ms, PRIORITY := 5);
+        PROGRAM fast_instance WITH fast_task : fast;
+        PROGRAM slow_instance WITH slow_task : slow;
+    END_RESOURCE
+END_CONFIGURATION";
+        let diagnostics = check(program);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Problem::TaskSharedVariableTornRead.code()
+        );
+    }
+
+    #[test]
+    fn apply_when_program_without_task_then_ok() {
+        let program = configuration("INT", "shared := 1;", "shared := 2;").replace(
+            "PROGRAM slow_instance WITH slow_task : slow;",
+            "PROGRAM slow_instance : slow;",
+        );
+        assert!(check(&program).is_empty());
+    }
+}
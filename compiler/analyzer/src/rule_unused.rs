@@ -0,0 +1,944 @@
+//! Semantic rule (opt-in with `--check-unused`) that reports declarations
+//! nothing uses.
+//!
+//! The rule reports:
+//!
+//! * P4054 when a variable or data type is never used. The variables are
+//!   `VAR`, `VAR_TEMP` and `VAR_EXTERNAL` declarations of programs, function
+//!   blocks, functions and methods, and `VAR_GLOBAL` declarations. A global
+//!   is used when a POU names it, directly or through `VAR_EXTERNAL`, or a
+//!   configuration connects it to a program;
+//! * P4055 when a variable is assigned but never read. A located variable
+//!   (`AT %Q*`) is read by the hardware, so it is never reported;
+//! * P4056 when a function or function block is not reachable from any
+//!   program (the reachable set that `xform_toposort_declarations` builds),
+//!   or when a program is not run by any configuration.
+//!
+//! Interface variables (`VAR_INPUT`, `VAR_OUTPUT`, `VAR_IN_OUT`) belong to
+//! the POU's callers and are not checked. Neither are `CONSTANT` variables,
+//! because analysis substitutes their values into types and initializers
+//! before this rule runs. A library with no program declares POUs for other
+//! projects, so none of its POUs are reported as unreachable.
+//!
+//! ## Passes
+//!
+//! ```ignore
+//! PROGRAM main
+//! VAR
+//!     count : INT;
+//! END_VAR
+//!     count := count + 1;
+//! END_PROGRAM
+//! ```
+//!
+//! ## Fails
+//!
+//! ```ignore
+//! PROGRAM main
+//! VAR
+//!     count : INT;
+//!     last : INT;
+//! END_VAR
+//!     last := count;
+//! END_PROGRAM
+//! ```
+use std::collections::{HashMap, HashSet};
+
+use ironplc_dsl::{
+    common::*,
+    core::{Id, Located, SourceSpan},
+    diagnostic::{Diagnostic, Label},
+    textual::*,
+    visitor::Visitor,
+};
+use ironplc_parser::options::CompilerOptions;
+use ironplc_problems::Problem;
+
+use crate::{
+    result::SemanticResult, semantic_context::SemanticContext, symbol_environment::SymbolKind,
+};
+
+pub fn apply(
+    lib: &Library,
+    context: &SemanticContext,
+    options: &CompilerOptions,
+) -> SemanticResult {
+    if !options.check_unused {
+        return Ok(());
+    }
+
+    let mut diagnostics = vec![];
+    check_variables(lib, &mut diagnostics);
+    check_types(lib, context, &mut diagnostics);
+    check_pous(lib, context, &mut diagnostics);
+
+    if !diagnostics.is_empty() {
+        return Err(diagnostics);
+    }
+    Ok(())
+}
+
+/// The scope that declares a variable.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Owner {
+    Global,
+    Pou(Id),
+    /// A method: the function block that owns it, then the method.
+    Method(Id, Id),
+}
+
+/// How the bodies use one variable.
+#[derive(Clone, Copy, Default)]
+struct Use {
+    read: bool,
+    written: bool,
+}
+
+type Uses = HashMap<(Owner, Id), Use>;
+
+fn check_variables(lib: &Library, diagnostics: &mut Vec<Diagnostic>) {
+    let function_blocks: HashMap<&Id, &FunctionBlockDeclaration> = lib
+        .elements
+        .iter()
+        .filter_map(|element| match element {
+            LibraryElementKind::FunctionBlockDeclaration(fb) => Some((&fb.name.name, fb)),
+            _ => None,
+        })
+        .collect();
+
+    let mut uses = Uses::new();
+    let mut declared: Vec<(Owner, &VarDecl)> = vec![];
+    for element in &lib.elements {
+        match element {
+            LibraryElementKind::GlobalVarDeclarations(globals) => {
+                declared.extend(globals.iter().map(|decl| (Owner::Global, decl)));
+            }
+            LibraryElementKind::ConfigurationDeclaration(config) => {
+                declared.extend(config.global_var.iter().map(|decl| (Owner::Global, decl)));
+                for resource in &config.resource_decl {
+                    declared.extend(
+                        resource
+                            .global_vars
+                            .iter()
+                            .map(|decl| (Owner::Global, decl)),
+                    );
+                    for program in &resource.programs {
+                        record_connections(program, &mut uses);
+                    }
+                }
+            }
+            LibraryElementKind::ProgramDeclaration(program) => {
+                let owner = Owner::Pou(program.name.clone());
+                let mut collector = UseCollector::new(&function_blocks, None);
+                collector.declare(owner.clone(), &program.variables);
+                let _ = collector.visit_function_block_body_kind(&program.body);
+                uses.extend_uses(collector.uses);
+                declared.extend(program.variables.iter().map(|decl| (owner.clone(), decl)));
+            }
+            LibraryElementKind::FunctionDeclaration(function) => {
+                let owner = Owner::Pou(function.name.clone());
+                let mut collector = UseCollector::new(&function_blocks, None);
+                collector.declare(owner.clone(), &function.variables);
+                for stmt in &function.body {
+                    let _ = collector.visit_stmt_kind(stmt);
+                }
+                uses.extend_uses(collector.uses);
+                declared.extend(function.variables.iter().map(|decl| (owner.clone(), decl)));
+            }
+            LibraryElementKind::FunctionBlockDeclaration(fb) => {
+                let mut collector = UseCollector::new(&function_blocks, Some(&fb.name.name));
+                collector.declare_function_block(&fb.name.name);
+                let _ = collector.visit_function_block_body_kind(&fb.body);
+                uses.extend_uses(collector.uses);
+                declared.extend(
+                    fb.variables
+                        .iter()
+                        .map(|decl| (Owner::Pou(fb.name.name.clone()), decl)),
+                );
+
+                for method in &fb.methods {
+                    let owner = Owner::Method(fb.name.name.clone(), method.name.clone());
+                    let mut collector = UseCollector::new(&function_blocks, Some(&fb.name.name));
+                    collector.declare(owner.clone(), &method.variables);
+                    collector.declare_function_block(&fb.name.name);
+                    for stmt in &method.body {
+                        let _ = collector.visit_stmt_kind(stmt);
+                    }
+                    uses.extend_uses(collector.uses);
+                    declared.extend(method.variables.iter().map(|decl| (owner.clone(), decl)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (owner, decl) in declared {
+        if !matches!(
+            decl.var_type,
+            VariableType::Var
+                | VariableType::VarTemp
+                | VariableType::External
+                | VariableType::Global
+        ) || decl.qualifier == DeclarationQualifier::Constant
+        {
+            continue;
+        }
+        let Some(name) = decl.identifier.symbolic_id() else {
+            continue;
+        };
+        let used = uses
+            .get(&(owner, name.clone()))
+            .copied()
+            .unwrap_or_default();
+        if !used.read && !used.written {
+            let what = match decl.var_type {
+                VariableType::External => "External variable",
+                VariableType::Global => "Global variable",
+                _ => "Variable",
+            };
+            diagnostics.push(
+                Diagnostic::problem(
+                    Problem::UnusedDeclaration,
+                    Label::span(name.span(), format!("{what} '{name}' is never used")),
+                )
+                .with_context_id("variable", name),
+            );
+        } else if !used.read
+            && decl.var_type != VariableType::External
+            && matches!(decl.identifier, VariableIdentifier::Symbol(_))
+        {
+            diagnostics.push(
+                Diagnostic::problem(
+                    Problem::VariableNeverRead,
+                    Label::span(
+                        name.span(),
+                        format!("Variable '{name}' is assigned but never read"),
+                    ),
+                )
+                .with_context_id("variable", name),
+            );
+        }
+    }
+}
+
+/// Records the globals that a configuration connects to a program's inputs
+/// (read) and outputs (written).
+fn record_connections(program: &ironplc_dsl::configuration::ProgramConfiguration, uses: &mut Uses) {
+    use ironplc_dsl::configuration::{ProgramConnectionSinkKind, ProgramConnectionSourceKind};
+
+    for source in &program.sources {
+        if let ProgramConnectionSourceKind::GlobalVarReference(global) = &source.src {
+            uses.record(Owner::Global, &global.global_var_name, false);
+        }
+    }
+    for sink in &program.sinks {
+        if let ProgramConnectionSinkKind::GlobalVarReference(global) = &sink.dst {
+            uses.record(Owner::Global, &global.global_var_name, true);
+        }
+    }
+}
+
+trait RecordUse {
+    fn record(&mut self, owner: Owner, name: &Id, write: bool);
+    fn extend_uses(&mut self, other: Uses);
+}
+
+impl RecordUse for Uses {
+    fn record(&mut self, owner: Owner, name: &Id, write: bool) {
+        let used = self.entry((owner, name.clone())).or_default();
+        if write {
+            used.written = true;
+        } else {
+            used.read = true;
+        }
+    }
+
+    fn extend_uses(&mut self, other: Uses) {
+        for (key, other) in other {
+            let used = self.entry(key).or_default();
+            used.read |= other.read;
+            used.written |= other.written;
+        }
+    }
+}
+
+/// A scope that a name in a body can resolve to, with the names it declares
+/// and whether each is `VAR_EXTERNAL`.
+struct DeclaringScope<'a> {
+    owner: Owner,
+    names: HashMap<&'a Id, bool>,
+}
+
+/// Visits one body, recording each read and write of a variable against
+/// the scope that declares it.
+struct UseCollector<'a> {
+    function_blocks: &'a HashMap<&'a Id, &'a FunctionBlockDeclaration>,
+    /// The function block whose body or method this is, for `THIS^` and
+    /// `SUPER^`.
+    this: Option<&'a Id>,
+    /// Innermost first: a method, its function block, then the function
+    /// blocks it extends.
+    scopes: Vec<DeclaringScope<'a>>,
+    /// Function block instances by name, with their function block type.
+    instances: HashMap<&'a Id, &'a Id>,
+    writing: bool,
+    uses: Uses,
+}
+
+impl<'a> UseCollector<'a> {
+    fn new(
+        function_blocks: &'a HashMap<&'a Id, &'a FunctionBlockDeclaration>,
+        this: Option<&'a Id>,
+    ) -> Self {
+        UseCollector {
+            function_blocks,
+            this,
+            scopes: vec![],
+            instances: HashMap::new(),
+            writing: false,
+            uses: Uses::new(),
+        }
+    }
+
+    /// Adds the variables of `owner` as the next scope outwards. Their
+    /// initializers are read.
+    fn declare(&mut self, owner: Owner, variables: &'a [VarDecl]) {
+        let mut names = HashMap::new();
+        for decl in variables {
+            let Some(name) = decl.identifier.symbolic_id() else {
+                continue;
+            };
+            names.insert(name, decl.var_type == VariableType::External);
+            if let Some(fb_type) = function_block_type(&decl.initializer) {
+                self.instances.entry(name).or_insert(fb_type);
+            }
+        }
+        self.scopes.push(DeclaringScope { owner, names });
+        for decl in variables {
+            let _ = self.visit_initial_value_assignment_kind(&decl.initializer);
+        }
+    }
+
+    /// Adds the variables of the function block `name` and of every function
+    /// block it extends.
+    fn declare_function_block(&mut self, name: &'a Id) {
+        let mut next = Some(name);
+        let mut seen = HashSet::new();
+        while let Some(name) = next.filter(|name| seen.insert(*name)) {
+            let Some(fb) = self.function_blocks.get(name).copied() else {
+                break;
+            };
+            self.declare(Owner::Pou(fb.name.name.clone()), &fb.variables);
+            next = base_of(fb);
+        }
+    }
+
+    /// Records a use of `name`, resolved through the scopes. A name that no
+    /// scope declares is a global; a `VAR_EXTERNAL` uses both its own
+    /// declaration and the global.
+    fn record(&mut self, name: &Id, write: bool) {
+        match self
+            .scopes
+            .iter()
+            .find_map(|scope| Some((&scope.owner, *scope.names.get(name)?)))
+        {
+            Some((owner, external)) => {
+                let owner = owner.clone();
+                self.uses.record(owner, name, write);
+                if external {
+                    self.uses.record(Owner::Global, name, write);
+                }
+            }
+            None => self.uses.record(Owner::Global, name, write),
+        }
+    }
+
+    /// Records a use of `field` of a function block `fb_type`, in the
+    /// function block that declares it.
+    fn record_field(&mut self, fb_type: &'a Id, field: &Id) {
+        let mut next = Some(fb_type);
+        let mut seen = HashSet::new();
+        while let Some(name) = next.filter(|name| seen.insert(*name)) {
+            let Some(fb) = self.function_blocks.get(name).copied() else {
+                return;
+            };
+            let declares = fb
+                .variables
+                .iter()
+                .any(|decl| decl.identifier.symbolic_id() == Some(field));
+            if declares {
+                self.uses
+                    .record(Owner::Pou(fb.name.name.clone()), field, self.writing);
+                return;
+            }
+            next = base_of(fb);
+        }
+    }
+
+    fn with_writing<T>(&mut self, writing: bool, f: impl FnOnce(&mut Self) -> T) -> T {
+        let saved = self.writing;
+        self.writing = writing;
+        let result = f(self);
+        self.writing = saved;
+        result
+    }
+}
+
+fn function_block_type(initializer: &InitialValueAssignmentKind) -> Option<&Id> {
+    match initializer {
+        InitialValueAssignmentKind::FunctionBlock(init) => Some(&init.type_name.name),
+        InitialValueAssignmentKind::FunctionBlockCall(init) => Some(&init.type_name.name),
+        _ => None,
+    }
+}
+
+fn base_of(fb: &FunctionBlockDeclaration) -> Option<&Id> {
+    fb.oop
+        .as_ref()
+        .and_then(|oop| oop.base.as_ref())
+        .map(|base| &base.name)
+}
+
+impl Visitor<()> for UseCollector<'_> {
+    type Value = ();
+
+    fn visit_assignment(&mut self, node: &Assignment) -> Result<(), ()> {
+        self.with_writing(true, |v| v.visit_variable(&node.target))?;
+        self.with_writing(false, |v| v.visit_expr(&node.value))
+    }
+
+    fn visit_output(&mut self, node: &Output) -> Result<(), ()> {
+        self.with_writing(true, |v| v.visit_variable(&node.tgt))
+    }
+
+    fn visit_for(&mut self, node: &For) -> Result<(), ()> {
+        // The loop compares the control variable on every iteration.
+        self.record(&node.control, true);
+        self.record(&node.control, false);
+        node.recurse_visit(self)
+    }
+
+    fn visit_array_variable(&mut self, node: &ArrayVariable) -> Result<(), ()> {
+        self.visit_symbolic_variable_kind(&node.subscripted_variable)?;
+        self.with_writing(false, |v| {
+            for subscript in &node.subscripts {
+                v.visit_expr(subscript)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn visit_structured_variable(&mut self, node: &StructuredVariable) -> Result<(), ()> {
+        match node.record.as_ref() {
+            SymbolicVariableKind::Named(record) => {
+                let fb_type = self
+                    .scopes
+                    .iter()
+                    .find(|scope| scope.names.contains_key(&record.name))
+                    .and_then(|_| self.instances.get(&record.name).copied());
+                if let Some(fb_type) = fb_type {
+                    self.record_field(fb_type, &node.field);
+                }
+                self.visit_named_variable(record)
+            }
+            SymbolicVariableKind::SelfRef(self_ref) => {
+                let start = match self_ref.kind {
+                    SelfRefKind::This => self.this,
+                    SelfRefKind::Super => self
+                        .this
+                        .and_then(|this| self.function_blocks.get(this))
+                        .and_then(|fb| base_of(fb)),
+                };
+                if let Some(start) = start {
+                    self.record_field(start, &node.field);
+                }
+                Ok(())
+            }
+            _ => node.recurse_visit(self),
+        }
+    }
+
+    fn visit_deref_variable(&mut self, node: &DerefVariable) -> Result<(), ()> {
+        // Writing through a reference reads the reference itself.
+        self.with_writing(false, |v| v.visit_symbolic_variable_kind(&node.variable))
+    }
+
+    fn visit_named_variable(&mut self, node: &NamedVariable) -> Result<(), ()> {
+        self.record(&node.name, self.writing);
+        Ok(())
+    }
+
+    fn visit_fb_call(&mut self, node: &FbCall) -> Result<(), ()> {
+        self.record(&node.var_name, false);
+        self.with_writing(false, |v| node.recurse_visit(v))
+    }
+
+    fn visit_method_call(&mut self, node: &MethodCall) -> Result<(), ()> {
+        if let MethodReceiver::Instance(instance) = &node.receiver {
+            self.record(instance, false);
+        }
+        self.with_writing(false, |v| node.recurse_visit(v))
+    }
+}
+
+/// Reports user data types that no declaration or expression names.
+fn check_types(lib: &Library, context: &SemanticContext, diagnostics: &mut Vec<Diagnostic>) {
+    let mut collector = TypeNameCollector::default();
+    let _ = collector.walk(lib);
+
+    let mut unused: Vec<(&Id, &SourceSpan)> = context
+        .symbols()
+        .get_global_symbols()
+        .iter()
+        .filter(|(_, info)| info.kind == SymbolKind::Type)
+        .filter(|(name, info)| {
+            !collector
+                .names
+                .get(*name)
+                .is_some_and(|spans| spans.iter().any(|span| !same_place(span, &info.span)))
+        })
+        .map(|(name, info)| (name, &info.span))
+        .collect();
+    unused.sort_by_key(|(_, span)| (span.file_id.to_string(), span.start));
+
+    for (name, span) in unused {
+        diagnostics.push(
+            Diagnostic::problem(
+                Problem::UnusedDeclaration,
+                Label::span(span.clone(), format!("Type '{name}' is never used")),
+            )
+            .with_context_id("type", name),
+        );
+    }
+}
+
+/// `SourceSpan` equality ignores positions, so compare them here.
+fn same_place(a: &SourceSpan, b: &SourceSpan) -> bool {
+    a.file_id == b.file_id && a.start == b.start && a.end == b.end
+}
+
+/// Collects every place that names a type, including the type's own
+/// declaration, which `check_types` tells apart by its span.
+#[derive(Default)]
+struct TypeNameCollector {
+    names: HashMap<Id, Vec<SourceSpan>>,
+}
+
+impl Visitor<()> for TypeNameCollector {
+    type Value = ();
+
+    fn visit_type_name(&mut self, node: &TypeName) -> Result<(), ()> {
+        self.names
+            .entry(node.name.clone())
+            .or_default()
+            .push(node.span());
+        Ok(())
+    }
+}
+
+/// Reports functions and function blocks that no program reaches and
+/// programs that no configuration runs.
+fn check_pous(lib: &Library, context: &SemanticContext, diagnostics: &mut Vec<Diagnostic>) {
+    let has_program = lib
+        .elements
+        .iter()
+        .any(|element| matches!(element, LibraryElementKind::ProgramDeclaration(_)));
+    if !has_program {
+        return;
+    }
+
+    let run: HashSet<&Id> = lib
+        .elements
+        .iter()
+        .filter_map(|element| match element {
+            LibraryElementKind::ConfigurationDeclaration(config) => Some(config),
+            _ => None,
+        })
+        .flat_map(|config| &config.resource_decl)
+        .flat_map(|resource| &resource.programs)
+        .map(|program| &program.type_name)
+        .collect();
+    let has_configuration = lib
+        .elements
+        .iter()
+        .any(|element| matches!(element, LibraryElementKind::ConfigurationDeclaration(_)));
+
+    let reachable = context.reachable();
+    for element in &lib.elements {
+        let message = match element {
+            LibraryElementKind::FunctionDeclaration(function)
+                if !reachable.contains(&function.name) =>
+            {
+                (
+                    &function.name,
+                    format!("Function '{}' is not called by any program", function.name),
+                )
+            }
+            LibraryElementKind::FunctionBlockDeclaration(fb)
+                if !reachable.contains(&fb.name.name) =>
+            {
+                (
+                    &fb.name.name,
+                    format!("Function block '{}' is not used by any program", fb.name),
+                )
+            }
+            LibraryElementKind::ProgramDeclaration(program)
+                if has_configuration && !run.contains(&program.name) =>
+            {
+                (
+                    &program.name,
+                    format!("Program '{}' is not run by any configuration", program.name),
+                )
+            }
+            _ => continue,
+        };
+        let (name, message) = message;
+        diagnostics.push(
+            Diagnostic::problem(Problem::UnreachablePou, Label::span(name.span(), message))
+                .with_context_id("pou", name),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ironplc_dsl::diagnostic::Diagnostic;
+    use ironplc_parser::options::CompilerOptions;
+    use ironplc_problems::Problem;
+
+    use crate::test_helpers::{
+        parse_and_resolve_types_with_context, parse_and_resolve_types_with_options,
+    };
+
+    fn options() -> CompilerOptions {
+        CompilerOptions {
+            check_unused: true,
+            ..CompilerOptions::default()
+        }
+    }
+
+    fn check(program: &str) -> Vec<Diagnostic> {
+        let (library, context) = parse_and_resolve_types_with_context(program);
+        match super::apply(&library, &context, &options()) {
+            Ok(()) => vec![],
+            Err(diagnostics) => diagnostics,
+        }
+    }
+
+    fn messages(diagnostics: &[Diagnostic], problem: Problem) -> Vec<String> {
+        diagnostics
+            .iter()
+            .filter(|d| d.code == problem.code())
+            .map(|d| d.primary.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn apply_when_check_disabled_then_ok() {
+        let program = "
+PROGRAM main
+VAR
+    unused : INT;
+END_VAR
+END_PROGRAM";
+        let (library, context) = parse_and_resolve_types_with_context(program);
+        assert!(super::apply(&library, &context, &CompilerOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn apply_when_every_variable_read_then_ok() {
+        let program = "
+PROGRAM main
+VAR
+    count : INT;
+    i : INT;
+END_VAR
+FOR i := 1 TO 10 DO
+    count := count + 1;
+END_FOR;
+END_PROGRAM";
+        assert!(check(program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_variable_never_used_then_unused_declaration() {
+        let program = "
+PROGRAM main
+VAR
+    count : INT;
+    spare : INT;
+END_VAR
+count := count + 1;
+END_PROGRAM";
+        let diagnostics = check(program);
+        assert_eq!(
+            messages(&diagnostics, Problem::UnusedDeclaration),
+            vec!["Variable 'spare' is never used"]
+        );
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn apply_when_variable_only_assigned_then_never_read() {
+        let program = "
+PROGRAM main
+VAR
+    count : INT;
+    last : INT;
+END_VAR
+count := count + 1;
+last := count;
+END_PROGRAM";
+        let diagnostics = check(program);
+        assert_eq!(
+            messages(&diagnostics, Problem::VariableNeverRead),
+            vec!["Variable 'last' is assigned but never read"]
+        );
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn apply_when_array_element_assigned_with_index_then_index_read() {
+        let program = "
+PROGRAM main
+VAR
+    i : INT;
+    values : ARRAY[1..4] OF INT;
+    total : INT;
+END_VAR
+i := 2;
+values[i] := 1;
+total := values[1] + total;
+END_PROGRAM";
+        assert!(check(program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_constant_unused_then_ok() {
+        let program = "
+PROGRAM main
+VAR CONSTANT
+    LIMIT : INT := 10;
+END_VAR
+END_PROGRAM";
+        assert!(check(program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_interface_variables_unused_then_ok() {
+        let program = "
+FUNCTION_BLOCK fb
+VAR_INPUT
+    a : INT;
+END_VAR
+VAR_OUTPUT
+    b : INT;
+END_VAR
+END_FUNCTION_BLOCK
+
+PROGRAM main
+VAR
+    instance : fb;
+END_VAR
+instance();
+END_PROGRAM";
+        assert!(check(program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_global_read_through_external_then_ok() {
+        let program = "
+PROGRAM main
+VAR_EXTERNAL
+    setpoint : INT;
+END_VAR
+VAR
+    copy : INT;
+END_VAR
+copy := setpoint + copy;
+END_PROGRAM
+
+CONFIGURATION config
+    VAR_GLOBAL
+        setpoint : INT;
+    END_VAR
+    RESOURCE resource1 ON PLC
+        TASK plc_task(INTERVAL := T#10ms, PRIORITY := 1);
+        PROGRAM plc_instance WITH plc_task : main;
+    END_RESOURCE
+END_CONFIGURATION";
+        assert!(check(program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_global_and_external_never_used_then_both_unused() {
+        let program = "
+PROGRAM main
+VAR_EXTERNAL
+    setpoint : INT;
+END_VAR
+END_PROGRAM
+
+CONFIGURATION config
+    VAR_GLOBAL
+        setpoint : INT;
+    END_VAR
+    RESOURCE resource1 ON PLC
+        TASK plc_task(INTERVAL := T#10ms, PRIORITY := 1);
+        PROGRAM plc_instance WITH plc_task : main;
+    END_RESOURCE
+END_CONFIGURATION";
+        let diagnostics = check(program);
+        assert_eq!(
+            messages(&diagnostics, Problem::UnusedDeclaration),
+            vec![
+                "Global variable 'setpoint' is never used",
+                "External variable 'setpoint' is never used"
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_when_function_block_local_read_by_method_then_ok() {
+        let program = "
+FUNCTION_BLOCK counter
+VAR
+    count : INT;
+END_VAR
+METHOD increment
+    count := count + 1;
+END_METHOD
+END_FUNCTION_BLOCK
+
+PROGRAM main
+VAR
+    c : counter;
+END_VAR
+c.increment();
+END_PROGRAM";
+        let options = CompilerOptions {
+            allow_fb_inheritance: true,
+            ..options()
+        };
+        let (library, context) = parse_and_resolve_types_with_options(program, &options);
+        assert!(super::apply(&library, &context, &options).is_ok());
+    }
+
+    #[test]
+    fn apply_when_base_local_read_by_derived_then_ok() {
+        let program = "
+FUNCTION_BLOCK base
+VAR
+    count : INT;
+END_VAR
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK derived EXTENDS base
+count := count + 1;
+END_FUNCTION_BLOCK
+
+PROGRAM main
+VAR
+    d : derived;
+END_VAR
+d();
+END_PROGRAM";
+        let options = CompilerOptions {
+            allow_fb_inheritance: true,
+            ..options()
+        };
+        let (library, context) = parse_and_resolve_types_with_options(program, &options);
+        assert!(super::apply(&library, &context, &options).is_ok());
+    }
+
+    #[test]
+    fn apply_when_function_never_called_then_unreachable() {
+        let program = "
+FUNCTION helper : INT
+VAR_INPUT
+    x : INT;
+END_VAR
+helper := x;
+END_FUNCTION
+
+PROGRAM main
+END_PROGRAM";
+        let diagnostics = check(program);
+        assert_eq!(
+            messages(&diagnostics, Problem::UnreachablePou),
+            vec!["Function 'helper' is not called by any program"]
+        );
+    }
+
+    #[test]
+    fn apply_when_function_block_never_instantiated_then_unreachable() {
+        let program = "
+FUNCTION_BLOCK orphan
+END_FUNCTION_BLOCK
+
+PROGRAM main
+END_PROGRAM";
+        let diagnostics = check(program);
+        assert_eq!(
+            messages(&diagnostics, Problem::UnreachablePou),
+            vec!["Function block 'orphan' is not used by any program"]
+        );
+    }
+
+    #[test]
+    fn apply_when_no_program_then_pous_not_reported() {
+        let program = "
+FUNCTION helper : INT
+VAR_INPUT
+    x : INT;
+END_VAR
+helper := x;
+END_FUNCTION";
+        assert!(check(program).is_empty());
+    }
+
+    #[test]
+    fn apply_when_program_not_in_configuration_then_unreachable() {
+        let program = "
+PROGRAM main
+END_PROGRAM
+
+PROGRAM spare
+END_PROGRAM
+
+CONFIGURATION config
+    RESOURCE resource1 ON PLC
+        TASK plc_task(INTERVAL := T#10ms, PRIORITY := 1);
+        PROGRAM plc_instance WITH plc_task : main;
+    END_RESOURCE
+END_CONFIGURATION";
+        let diagnostics = check(program);
+        assert_eq!(
+            messages(&diagnostics, Problem::UnreachablePou),
+            vec!["Program 'spare' is not run by any configuration"]
+        );
+    }
+
+    #[test]
+    fn apply_when_type_never_used_then_unused_declaration() {
+        let program = "
+TYPE
+    MODE : (IDLE, RUNNING);
+    SPEED : INT := 0;
+END_TYPE
+
+PROGRAM main
+VAR
+    m : MODE;
+END_VAR
+IF m = IDLE THEN
+    m := RUNNING;
+END_IF;
+END_PROGRAM";
+        let diagnostics = check(program);
+        assert_eq!(
+            messages(&diagnostics, Problem::UnusedDeclaration),
+            vec!["Type 'SPEED' is never used"]
+        );
+    }
+}
@@ -42,14 +42,16 @@ use std::collections::HashMap;
 
 use ironplc_dsl::{
     common::*,
-    core::{Id, Located},
-    diagnostic::{Diagnostic, Label},
+    core::{Id, Located, SourceSpan},
+    diagnostic::{Diagnostic, FixKind, Label},
+    textual::{Assignment, SymbolicVariableKind, Variable},
     visitor::Visitor,
 };
 use ironplc_problems::Problem;
 
 use crate::{
     intermediates::inherited_fields::collect_inherited_fields,
+    literal_types::concrete_type,
     result::SemanticResult,
     scoped_table::{self, Key, ScopedTable, Value},
     semantic_context::SemanticContext,
@@ -65,6 +67,8 @@ pub fn apply(
     let mut checker = SymbolScopeChecker {
         table: scoped_table::ScopedTable::new(),
         inherited_fields: collect_inherited_fields(lib),
+        pou: None,
+        assigned: None,
     };
 
     // Seed implicit system globals so direct references don't trigger P4007.
@@ -94,6 +98,12 @@ impl Key for TypeName {}
 struct SymbolScopeChecker<'a> {
     table: ScopedTable<'a, Id, DummyNode>,
     inherited_fields: HashMap<TypeName, Vec<VarDecl>>,
+    /// The name of the POU being checked, where an undefined variable can
+    /// be declared.
+    pou: Option<SourceSpan>,
+    /// The variable the assignment being checked writes and the type of the
+    /// value it writes, when analysis resolved a type a variable can have.
+    assigned: Option<(Id, TypeName)>,
 }
 
 impl Visitor<Diagnostic> for SymbolScopeChecker<'_> {
@@ -101,6 +111,7 @@ impl Visitor<Diagnostic> for SymbolScopeChecker<'_> {
 
     fn visit_function_declaration(&mut self, node: &FunctionDeclaration) -> Result<(), Diagnostic> {
         self.table.enter();
+        self.pou = Some(node.name.span());
 
         self.table.add(&node.name, DummyNode {});
         let ret = node.recurse_visit(self);
@@ -110,6 +121,7 @@ impl Visitor<Diagnostic> for SymbolScopeChecker<'_> {
 
     fn visit_program_declaration(&mut self, node: &ProgramDeclaration) -> Result<(), Diagnostic> {
         self.table.enter();
+        self.pou = Some(node.name.span());
         self.table.add(&node.name, DummyNode {});
         let ret = node.recurse_visit(self);
         self.table.exit();
@@ -121,6 +133,7 @@ impl Visitor<Diagnostic> for SymbolScopeChecker<'_> {
         node: &FunctionBlockDeclaration,
     ) -> Result<(), Diagnostic> {
         self.table.enter();
+        self.pou = Some(node.name.span());
         self.table.add(&node.name.name, DummyNode {});
         if let Some(fields) = self.inherited_fields.get(&node.name).cloned() {
             for field in &fields {
@@ -139,6 +152,18 @@ impl Visitor<Diagnostic> for SymbolScopeChecker<'_> {
         node.recurse_visit(self)
     }
 
+    fn visit_assignment(&mut self, node: &Assignment) -> Result<(), Diagnostic> {
+        self.assigned = match (&node.target, &node.value.resolved_type) {
+            (Variable::Symbolic(SymbolicVariableKind::Named(target)), Some(value_type)) => {
+                concrete_type(value_type).map(|value_type| (target.name.clone(), value_type))
+            }
+            _ => None,
+        };
+        let ret = node.recurse_visit(self);
+        self.assigned = None;
+        ret
+    }
+
     fn visit_named_variable(
         &mut self,
         node: &ironplc_dsl::textual::NamedVariable,
@@ -159,7 +184,30 @@ impl Visitor<Diagnostic> for SymbolScopeChecker<'_> {
                 )
                 .with_context_id("variable", &node.name);
                 if let Some(suggestion) = suggestion {
-                    diagnostic = diagnostic.with_context("did you mean", &suggestion);
+                    diagnostic = diagnostic
+                        .with_context("did you mean", &suggestion)
+                        .with_fix(
+                            format!("Change to '{suggestion}'"),
+                            FixKind::Replace {
+                                span: node.name.span(),
+                                text: suggestion,
+                            },
+                        );
+                }
+                if let Some(pou) = &self.pou {
+                    let type_name = self
+                        .assigned
+                        .as_ref()
+                        .filter(|(target, _)| *target == node.name)
+                        .map(|(_, value_type)| value_type.to_string());
+                    diagnostic = diagnostic.with_fix(
+                        format!("Declare '{}' in VAR", node.name.original()),
+                        FixKind::DeclareVariable {
+                            pou: pou.clone(),
+                            name: node.name.original().to_string(),
+                            type_name,
+                        },
+                    );
                 }
                 Err(diagnostic)
             }
@@ -274,6 +322,79 @@ END_FUNCTION_BLOCK";
         assert!(error.described.contains(&"did you mean=counter".to_owned()));
     }
 
+    #[test]
+    fn apply_when_typo_in_variable_name_then_fixes_replace_and_declare() {
+        let program = "
+FUNCTION_BLOCK LOGGER
+VAR
+counter : INT;
+END_VAR
+
+conter := 1;
+END_FUNCTION_BLOCK";
+
+        let library = parse_and_resolve_types(program);
+        let context = SemanticContextBuilder::new().build().unwrap();
+        let errors = apply(&library, &context, &CompilerOptions::default()).unwrap_err();
+        let fixes = &errors.first().unwrap().fixes;
+
+        assert_eq!(fixes.len(), 2);
+        assert!(matches!(
+            &fixes[0].kind,
+            FixKind::Replace { text, .. } if text == "counter"
+        ));
+        assert!(matches!(
+            &fixes[1].kind,
+            FixKind::DeclareVariable { name, pou, type_name: None }
+                if name == "conter" && pou.start == 16
+        ));
+    }
+
+    #[test]
+    fn apply_when_undefined_variable_assigned_typed_value_then_declares_that_type() {
+        let program = "
+PROGRAM Main
+VAR
+Count : DINT;
+END_VAR
+
+Total := Count;
+Flag := TRUE;
+END_PROGRAM";
+
+        let library = parse_and_resolve_types(program);
+        let context = SemanticContextBuilder::new().build().unwrap();
+        let errors = apply(&library, &context, &CompilerOptions::default()).unwrap_err();
+        let fixes = &errors.first().unwrap().fixes;
+
+        assert!(matches!(
+            &fixes.last().unwrap().kind,
+            FixKind::DeclareVariable { name, type_name: Some(type_name), .. }
+                if name == "Total" && type_name == "DINT"
+        ));
+    }
+
+    #[test]
+    fn apply_when_undefined_variable_assigned_untyped_literal_then_type_unknown() {
+        let program = "
+PROGRAM Main
+Total := 5;
+END_PROGRAM";
+
+        let library = parse_and_resolve_types(program);
+        let context = SemanticContextBuilder::new().build().unwrap();
+        let errors = apply(&library, &context, &CompilerOptions::default()).unwrap_err();
+        let fixes = &errors.first().unwrap().fixes;
+
+        assert!(matches!(
+            &fixes.last().unwrap().kind,
+            FixKind::DeclareVariable {
+                type_name: None,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn apply_when_no_similar_variable_then_no_suggestion() {
         let program = "
@@ -15,16 +15,16 @@ use crate::{
     ironplc_dsl::common::Library,
     result::SemanticResult,
     rule_abstract_not_instantiated, rule_bit_access_range, rule_case_bit_string_label,
-    rule_decl_struct_element_unique_names, rule_decl_subrange_limits,
+    rule_case_exhaustive, rule_decl_struct_element_unique_names, rule_decl_subrange_limits,
     rule_enumeration_values_unique, rule_extends_field_duplicated,
     rule_function_block_call_unsupported, rule_function_block_invocation,
     rule_function_call_declared, rule_function_call_type_check, rule_method_call_declared,
     rule_mixed_located_var_declarations, rule_no_top_level_var_global, rule_pou_hierarchy,
     rule_program_task_definition_exists, rule_ref_to, rule_stdlib_type_redefinition,
     rule_string_encoding_compat, rule_struct_initializer_expression_allowed,
-    rule_task_names_unique, rule_unsupported_extension, rule_unsupported_stdlib_type,
-    rule_use_declared_enumerated_value, rule_use_declared_symbolic_var,
-    rule_var_decl_const_initialized, rule_var_decl_const_not_fb,
+    rule_task_names_unique, rule_task_races, rule_unsupported_extension,
+    rule_unsupported_stdlib_type, rule_unused, rule_use_declared_enumerated_value,
+    rule_use_declared_symbolic_var, rule_var_decl_const_initialized, rule_var_decl_const_not_fb,
     rule_var_decl_global_const_requires_external_const, rule_var_decl_initializer_type_compat,
     semantic_context::SemanticContext,
     symbol_environment::{ScopeKind, SymbolEnvironment, SymbolKind},
@@ -347,6 +347,7 @@ pub(crate) fn semantic(
         rule_program_task_definition_exists::apply,
         rule_no_top_level_var_global::apply,
         rule_task_names_unique::apply,
+        rule_task_races::apply,
         rule_stdlib_type_redefinition::apply,
         rule_string_encoding_compat::apply,
         rule_struct_initializer_expression_allowed::apply,
@@ -362,6 +363,8 @@ pub(crate) fn semantic(
         rule_pou_hierarchy::apply,
         rule_bit_access_range::apply,
         rule_case_bit_string_label::apply,
+        rule_case_exhaustive::apply,
+        rule_unused::apply,
         rule_ref_to::apply,
     ];
 
@@ -187,10 +187,14 @@ impl SymbolEnvironment {
         kind: SymbolKind,
         scope: &ScopeKind,
         variable_type: VariableType,
+        data_type: Option<String>,
         address: Option<String>,
     ) -> Result<(), Diagnostic> {
         let mut symbol_info = SymbolInfo::new(kind, scope.clone(), name.span())
             .with_variable_type(variable_type.clone());
+        if let Some(data_type) = data_type {
+            symbol_info = symbol_info.with_data_type(data_type);
+        }
         if let Some(addr) = address {
             symbol_info = symbol_info.with_address(addr);
         }
@@ -0,0 +1,147 @@
+//! The calls between POUs and methods, for the call hierarchy.
+
+use ironplc_dsl::core::SourceSpan;
+
+use super::{PouKind, SymbolId, SymbolIndex};
+
+/// A call written in a POU or method: a function call, an invocation of a
+/// function block instance, or a method call.
+#[derive(Debug, Clone)]
+pub struct Call {
+    /// The program, function block, function or method the call is in.
+    pub caller: SymbolId,
+    /// The function, function block or method called.
+    pub callee: SymbolId,
+    /// Where the callee's name, or the instance's, is written.
+    pub span: SourceSpan,
+}
+
+impl SymbolIndex {
+    /// Whether the symbol is in the call hierarchy: a program, function
+    /// block, function or method.
+    pub fn is_callable(&self, symbol: &SymbolId) -> bool {
+        match symbol {
+            SymbolId::Pou(name) => self
+                .pou_kind(name)
+                .is_some_and(|kind| kind != PouKind::Interface),
+            SymbolId::Method(..) => true,
+            _ => false,
+        }
+    }
+
+    /// The POUs and methods that call the symbol, each with the places it
+    /// calls from, in the order the calls are written.
+    pub fn incoming_calls(&self, callee: &SymbolId) -> Vec<(&SymbolId, Vec<&SourceSpan>)> {
+        group(
+            self.calls
+                .iter()
+                .filter(|call| call.callee == *callee)
+                .map(|call| (&call.caller, &call.span)),
+        )
+    }
+
+    /// The POUs and methods the symbol calls, each with the places it calls
+    /// them, in the order the calls are written.
+    pub fn outgoing_calls(&self, caller: &SymbolId) -> Vec<(&SymbolId, Vec<&SourceSpan>)> {
+        group(
+            self.calls
+                .iter()
+                .filter(|call| call.caller == *caller)
+                .map(|call| (&call.callee, &call.span)),
+        )
+    }
+}
+
+fn group<'a>(
+    calls: impl Iterator<Item = (&'a SymbolId, &'a SourceSpan)>,
+) -> Vec<(&'a SymbolId, Vec<&'a SourceSpan>)> {
+    let mut grouped: Vec<(&SymbolId, Vec<&SourceSpan>)> = vec![];
+    for (symbol, span) in calls {
+        match grouped.iter_mut().find(|(other, _)| *other == symbol) {
+            Some((_, spans)) => spans.push(span),
+            None => grouped.push((symbol, vec![span])),
+        }
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol_index::tests::index;
+    use ironplc_dsl::core::{FileId, Id};
+
+    #[test]
+    fn incoming_calls_when_function_then_calling_program() {
+        let index = index();
+
+        let calls = index.incoming_calls(&SymbolId::Pou(Id::from("Twice")));
+
+        assert_eq!(calls.len(), 1);
+        assert_eq!(*calls[0].0, SymbolId::Pou(Id::from("Main")));
+        assert_eq!(calls[0].1.len(), 1);
+    }
+
+    #[test]
+    fn outgoing_calls_when_program_then_instance_and_function() {
+        let index = index();
+
+        let calls = index.outgoing_calls(&SymbolId::Pou(Id::from("Main")));
+        let callees: Vec<&SymbolId> = calls.iter().map(|(callee, _)| *callee).collect();
+
+        assert_eq!(
+            callees,
+            vec![
+                &SymbolId::Pou(Id::from("Counter")),
+                &SymbolId::Pou(Id::from("Twice"))
+            ]
+        );
+    }
+
+    #[test]
+    fn outgoing_calls_when_method_calls_function_then_from_method() {
+        let source = "
+FUNCTION Clamp : INT
+VAR_INPUT
+    N : INT;
+END_VAR
+    Clamp := N;
+END_FUNCTION
+FUNCTION_BLOCK Motor
+VAR
+    Speed : INT;
+END_VAR
+METHOD Start
+    Speed := Clamp(N := 5);
+END_METHOD
+END_FUNCTION_BLOCK
+PROGRAM Main
+VAR
+    M : Motor;
+END_VAR
+    M.Start();
+END_PROGRAM";
+        let options = ironplc_parser::options::CompilerOptions {
+            allow_fb_inheritance: true,
+            ..Default::default()
+        };
+        let library = ironplc_parser::parse_program(source, &FileId::default(), &options).unwrap();
+        let index = SymbolIndex::build(&[&library]);
+        let start = SymbolId::Method(Id::from("Motor"), Id::from("Start"));
+
+        let outgoing = index.outgoing_calls(&start);
+        let incoming = index.incoming_calls(&start);
+
+        assert_eq!(*outgoing[0].0, SymbolId::Pou(Id::from("Clamp")));
+        assert_eq!(*incoming[0].0, SymbolId::Pou(Id::from("Main")));
+        assert!(index.is_callable(&start));
+    }
+
+    #[test]
+    fn is_callable_when_type_or_variable_then_false() {
+        let index = index();
+
+        assert!(!index.is_callable(&SymbolId::Type(Id::from("Point"))));
+        assert!(index.is_callable(&SymbolId::Pou(Id::from("Counter"))));
+    }
+}
@@ -0,0 +1,397 @@
+//! Collection of every declaration in the project, before any reference is
+//! resolved, and the scoped lookups that resolution and the rename conflict
+//! check use.
+
+use std::collections::{HashMap, HashSet};
+
+use ironplc_dsl::common::*;
+use ironplc_dsl::configuration::ConfigurationDeclaration;
+use ironplc_dsl::core::{Id, Located, SourceSpan};
+
+use super::{PouKind, Scope, SymbolId};
+
+/// The declared type of a variable or field, as far as member access needs.
+#[derive(Debug, Clone)]
+pub(super) enum Shape {
+    /// A named type: elementary, user-defined, or a function block.
+    Named(Id),
+    /// An inline array of the named element type.
+    ArrayOf(Id),
+}
+
+/// Every declaration in the project, collected before any reference is
+/// resolved so that declaration order does not matter.
+#[derive(Default)]
+pub(super) struct Declarations {
+    pub(super) definitions: HashMap<SymbolId, SourceSpan>,
+    pub(super) pous: HashSet<Id>,
+    pub(super) pou_kinds: HashMap<Id, PouKind>,
+    pub(super) functions: HashSet<Id>,
+    pub(super) types: HashSet<Id>,
+    /// `TYPE A : B; END_TYPE` and similar: the type `A` names.
+    pub(super) aliases: HashMap<Id, Id>,
+    /// Element type of each named array type.
+    pub(super) array_elements: HashMap<Id, Id>,
+    /// The enumeration types that declare each value name.
+    pub(super) enum_values: HashMap<Id, Vec<Id>>,
+    pub(super) bases: HashMap<Id, Id>,
+    pub(super) variables: HashMap<Scope, HashMap<Id, (Option<Shape>, bool)>>,
+    pub(super) shapes: HashMap<SymbolId, Shape>,
+}
+
+impl Declarations {
+    pub(super) fn define(&mut self, symbol: SymbolId, span: SourceSpan) {
+        self.definitions.entry(symbol).or_insert(span);
+    }
+
+    pub(super) fn collect(&mut self, library: &Library) {
+        for element in &library.elements {
+            match element {
+                LibraryElementKind::DataTypeDeclaration(decl) => self.collect_type(decl),
+                LibraryElementKind::FunctionDeclaration(decl) => {
+                    self.collect_pou(&decl.name, PouKind::Function);
+                    self.functions.insert(decl.name.clone());
+                    self.collect_variables(Scope::Pou(decl.name.clone()), &decl.variables);
+                    self.collect_edge_variables(
+                        Scope::Pou(decl.name.clone()),
+                        &decl.edge_variables,
+                    );
+                }
+                LibraryElementKind::FunctionBlockDeclaration(decl) => {
+                    let name = &decl.name.name;
+                    self.collect_pou(name, PouKind::FunctionBlock);
+                    if let Some(base) = decl.oop.as_ref().and_then(|oop| oop.base.as_ref()) {
+                        self.bases.insert(name.clone(), base.name.clone());
+                    }
+                    self.collect_variables(Scope::Pou(name.clone()), &decl.variables);
+                    self.collect_edge_variables(Scope::Pou(name.clone()), &decl.edge_variables);
+                    for method in &decl.methods {
+                        self.define(
+                            SymbolId::Method(name.clone(), method.name.clone()),
+                            method.name.span(),
+                        );
+                        let scope = Scope::Method(name.clone(), method.name.clone());
+                        self.collect_variables(scope.clone(), &method.variables);
+                        self.collect_edge_variables(scope, &method.edge_variables);
+                    }
+                }
+                LibraryElementKind::ProgramDeclaration(decl) => {
+                    self.collect_pou(&decl.name, PouKind::Program);
+                    self.collect_variables(Scope::Pou(decl.name.clone()), &decl.variables);
+                }
+                LibraryElementKind::ConfigurationDeclaration(decl) => {
+                    self.collect_configuration(decl)
+                }
+                LibraryElementKind::GlobalVarDeclarations(vars) => {
+                    self.collect_variables(Scope::Global, vars)
+                }
+                LibraryElementKind::InterfaceDeclaration(decl) => {
+                    self.collect_pou(&decl.name, PouKind::Interface)
+                }
+            }
+        }
+    }
+
+    fn collect_pou(&mut self, name: &Id, kind: PouKind) {
+        self.pous.insert(name.clone());
+        self.pou_kinds.insert(name.clone(), kind);
+        self.define(SymbolId::Pou(name.clone()), name.span());
+    }
+
+    fn collect_type(&mut self, decl: &DataTypeDeclarationKind) {
+        let type_name = match decl {
+            DataTypeDeclarationKind::Enumeration(decl) => {
+                match &decl.spec_init.spec {
+                    SpecificationKind::Inline(values) => {
+                        for value in &values.values {
+                            self.collect_enum_value(&decl.type_name.name, &value.value);
+                        }
+                    }
+                    SpecificationKind::Named(base) => {
+                        self.aliases
+                            .insert(decl.type_name.name.clone(), base.name.clone());
+                    }
+                }
+                &decl.type_name
+            }
+            DataTypeDeclarationKind::Subrange(decl) => &decl.type_name,
+            DataTypeDeclarationKind::Simple(decl) => {
+                if let Some(Shape::Named(base)) = shape_of(&decl.spec_and_init) {
+                    self.aliases.insert(decl.type_name.name.clone(), base);
+                }
+                &decl.type_name
+            }
+            DataTypeDeclarationKind::Array(decl) => {
+                match &decl.spec {
+                    SpecificationKind::Inline(subranges) => {
+                        if let ArrayElementType::Named(element) = &subranges.type_name {
+                            self.array_elements
+                                .insert(decl.type_name.name.clone(), element.name.clone());
+                        }
+                    }
+                    SpecificationKind::Named(base) => {
+                        self.aliases
+                            .insert(decl.type_name.name.clone(), base.name.clone());
+                    }
+                }
+                &decl.type_name
+            }
+            DataTypeDeclarationKind::Structure(decl) => {
+                for element in &decl.elements {
+                    let field = SymbolId::Field(decl.type_name.name.clone(), element.name.clone());
+                    if let Some(shape) = shape_of(&element.init) {
+                        self.shapes.insert(field.clone(), shape);
+                    }
+                    self.define(field, element.name.span());
+                }
+                &decl.type_name
+            }
+            DataTypeDeclarationKind::StructureInitialization(decl) => &decl.type_name,
+            DataTypeDeclarationKind::String(decl) => &decl.type_name,
+            DataTypeDeclarationKind::Reference(decl) => &decl.type_name,
+            DataTypeDeclarationKind::LateBound(decl) => {
+                self.aliases.insert(
+                    decl.data_type_name.name.clone(),
+                    decl.base_type_name.name.clone(),
+                );
+                &decl.data_type_name
+            }
+        };
+        self.types.insert(type_name.name.clone());
+        self.define(SymbolId::Type(type_name.name.clone()), type_name.span());
+    }
+
+    fn collect_enum_value(&mut self, type_name: &Id, value: &Id) {
+        self.enum_values
+            .entry(value.clone())
+            .or_default()
+            .push(type_name.clone());
+        self.define(
+            SymbolId::EnumValue(type_name.clone(), value.clone()),
+            value.span(),
+        );
+    }
+
+    fn collect_configuration(&mut self, decl: &ConfigurationDeclaration) {
+        self.collect_variables(Scope::Global, &decl.global_var);
+        for resource in &decl.resource_decl {
+            self.collect_variables(Scope::Global, &resource.global_vars);
+            for task in &resource.tasks {
+                self.define(SymbolId::Task(task.name.clone()), task.name.span());
+            }
+        }
+    }
+
+    fn collect_variables(&mut self, scope: Scope, vars: &[VarDecl]) {
+        for var in vars {
+            let Some(name) = var.identifier.symbolic_id() else {
+                continue;
+            };
+            let scope = match var.var_type {
+                VariableType::Global => Scope::Global,
+                _ => scope.clone(),
+            };
+            let is_external = var.var_type == VariableType::External;
+            let shape = shape_of(&var.initializer);
+            if !is_external {
+                let symbol = SymbolId::Variable(scope.clone(), name.clone());
+                if let Some(shape) = &shape {
+                    self.shapes.insert(symbol.clone(), shape.clone());
+                }
+                self.define(symbol, name.span());
+            }
+            self.variables
+                .entry(scope)
+                .or_default()
+                .insert(name.clone(), (shape, is_external));
+        }
+    }
+
+    fn collect_edge_variables(&mut self, scope: Scope, vars: &[EdgeVarDecl]) {
+        for var in vars {
+            self.shapes.insert(
+                SymbolId::Variable(scope.clone(), var.identifier.clone()),
+                Shape::Named(Id::from("BOOL")),
+            );
+            self.define(
+                SymbolId::Variable(scope.clone(), var.identifier.clone()),
+                var.identifier.span(),
+            );
+            self.variables
+                .entry(scope.clone())
+                .or_default()
+                .insert(var.identifier.clone(), (None, false));
+        }
+    }
+
+    /// An external declaration whose global is undeclared stands in for
+    /// the global, so it is the definition.
+    pub(super) fn define_orphan_externals(&mut self) {
+        let globals = self
+            .variables
+            .get(&Scope::Global)
+            .cloned()
+            .unwrap_or_default();
+        let mut orphans = vec![];
+        for (scope, vars) in &self.variables {
+            for (name, (_, is_external)) in vars {
+                if *is_external && !globals.contains_key(name) {
+                    orphans.push(SymbolId::Variable(scope.clone(), name.clone()));
+                }
+            }
+        }
+        for orphan in orphans {
+            let span = orphan.name().span();
+            self.define(orphan, span);
+        }
+    }
+
+    /// The function block and its ancestors, nearest first.
+    pub(super) fn lineage(&self, fb: &Id) -> Vec<Id> {
+        let mut chain = vec![fb.clone()];
+        while let Some(base) = self.bases.get(chain.last().unwrap()) {
+            if chain.contains(base) {
+                break;
+            }
+            chain.push(base.clone());
+        }
+        chain
+    }
+
+    pub(super) fn unalias(&self, name: &Id) -> Id {
+        let mut seen = vec![name.clone()];
+        while let Some(base) = self.aliases.get(seen.last().unwrap()) {
+            if seen.contains(base) {
+                break;
+            }
+            seen.push(base.clone());
+        }
+        seen.pop().unwrap()
+    }
+
+    /// The symbol a type name refers to, if it is declared in the project.
+    pub(super) fn type_symbol(&self, name: &Id) -> Option<SymbolId> {
+        if self.types.contains(name) {
+            Some(SymbolId::Type(name.clone()))
+        } else if self.pous.contains(name) {
+            Some(SymbolId::Pou(name.clone()))
+        } else {
+            None
+        }
+    }
+
+    /// A variable declared in `scope`, resolving an external declaration
+    /// to the global it names.
+    pub(super) fn variable_in(
+        &self,
+        scope: &Scope,
+        name: &Id,
+    ) -> Option<(SymbolId, Option<Shape>)> {
+        let (shape, is_external) = self.variables.get(scope)?.get(name)?;
+        if *is_external {
+            if let Some(global) = self.variable_in(&Scope::Global, name) {
+                return Some(global);
+            }
+        }
+        Some((
+            SymbolId::Variable(scope.clone(), name.clone()),
+            shape.clone(),
+        ))
+    }
+
+    /// A name written on its own in `scope`: a variable of the method,
+    /// then of the POU and its ancestors, then a global.
+    pub(super) fn lookup(&self, scope: &Scope, name: &Id) -> Option<(SymbolId, Option<Shape>)> {
+        let pou = match scope {
+            Scope::Global => None,
+            Scope::Pou(pou) => Some(pou),
+            Scope::Method(pou, _) => {
+                if let Some(found) = self.variable_in(scope, name) {
+                    return Some(found);
+                }
+                Some(pou)
+            }
+        };
+        if let Some(pou) = pou {
+            if let Some(found) = self
+                .lineage(pou)
+                .iter()
+                .find_map(|owner| self.variable_in(&Scope::Pou(owner.clone()), name))
+            {
+                return Some(found);
+            }
+            // Assigning to a function's name sets its return value.
+            if matches!(scope, Scope::Pou(_)) && pou == name && self.functions.contains(name) {
+                return Some((SymbolId::Pou(name.clone()), None));
+            }
+        }
+        self.variable_in(&Scope::Global, name)
+    }
+
+    /// A member of a value of the given shape: a structure field or a
+    /// function block variable, possibly inherited.
+    pub(super) fn member(&self, shape: &Shape, name: &Id) -> Option<(SymbolId, Option<Shape>)> {
+        let Shape::Named(type_name) = shape else {
+            return None;
+        };
+        let type_name = self.unalias(type_name);
+        let field = SymbolId::Field(type_name.clone(), name.clone());
+        if self.definitions.contains_key(&field) {
+            let shape = self.shapes.get(&field).cloned();
+            return Some((field, shape));
+        }
+        self.lineage(&type_name)
+            .iter()
+            .find_map(|fb| self.variable_in(&Scope::Pou(fb.clone()), name))
+    }
+
+    pub(super) fn method(&self, fb: &Id, name: &Id) -> Option<SymbolId> {
+        self.lineage(&self.unalias(fb))
+            .into_iter()
+            .map(|owner| SymbolId::Method(owner, name.clone()))
+            .find(|symbol| self.definitions.contains_key(symbol))
+    }
+
+    pub(super) fn element(&self, shape: &Shape) -> Option<Shape> {
+        match shape {
+            Shape::ArrayOf(element) => Some(Shape::Named(element.clone())),
+            Shape::Named(type_name) => self
+                .array_elements
+                .get(&self.unalias(type_name))
+                .map(|element| Shape::Named(element.clone())),
+        }
+    }
+}
+
+/// The declared type of a variable or structure element.
+pub(super) fn shape_of(init: &InitialValueAssignmentKind) -> Option<Shape> {
+    match init {
+        InitialValueAssignmentKind::Simple(init) => Some(Shape::Named(init.type_name.name.clone())),
+        InitialValueAssignmentKind::EnumeratedType(init) => {
+            Some(Shape::Named(init.type_name.name.clone()))
+        }
+        InitialValueAssignmentKind::FunctionBlock(init) => {
+            Some(Shape::Named(init.type_name.name.clone()))
+        }
+        InitialValueAssignmentKind::FunctionBlockCall(init) => {
+            Some(Shape::Named(init.type_name.name.clone()))
+        }
+        InitialValueAssignmentKind::Subrange(SpecificationKind::Named(type_name)) => {
+            Some(Shape::Named(type_name.name.clone()))
+        }
+        InitialValueAssignmentKind::Structure(init) => {
+            Some(Shape::Named(init.type_name.name.clone()))
+        }
+        InitialValueAssignmentKind::Array(init) => match &init.spec {
+            SpecificationKind::Named(type_name) => Some(Shape::Named(type_name.name.clone())),
+            SpecificationKind::Inline(subranges) => match &subranges.type_name {
+                ArrayElementType::Named(element) => Some(Shape::ArrayOf(element.name.clone())),
+                _ => None,
+            },
+        },
+        InitialValueAssignmentKind::LateResolvedType(type_name) => {
+            Some(Shape::Named(type_name.name.clone()))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,477 @@
+//! An index of every named declaration in a project and every reference to
+//! it, for editor navigation (go to definition, find references, rename).
+//!
+//! The index walks libraries as parsed, before analysis lowers named call
+//! arguments to positional ones and folds constants away, so every name the
+//! user wrote is still present with its source span. Resolution follows the
+//! scoping the analyzer applies: a name in a POU body is the POU's own
+//! variable, then a field inherited through `EXTENDS`, then a global. A
+//! `VAR_EXTERNAL` declaration is a reference to the global it names, so
+//! renaming the global renames every redeclaration with it.
+//!
+//! Names the index cannot resolve (elementary types, standard library
+//! functions, undeclared variables) have no occurrence; callers treat the
+//! position as having no symbol.
+//!
+//! The index also records every call from one POU or method to another, for
+//! the call hierarchy, and searches the project's top-level names, for
+//! workspace symbols.
+
+mod calls;
+mod declarations;
+mod rename;
+mod resolver;
+mod search;
+
+use std::collections::{HashMap, HashSet};
+
+use ironplc_dsl::common::*;
+use ironplc_dsl::core::{FileId, Id, SourceSpan};
+use ironplc_dsl::visitor::Visitor;
+
+pub use calls::Call;
+use declarations::{Declarations, Shape};
+use resolver::Resolver;
+
+/// The scope that declares a variable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// `VAR_GLOBAL`, at the top level or in a configuration or resource.
+    Global,
+    /// A program, function block or function.
+    Pou(Id),
+    /// A method: the function block that owns it, then the method.
+    Method(Id, Id),
+}
+
+/// A named declaration that occurrences refer to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SymbolId {
+    /// A program, function block, function or interface.
+    Pou(Id),
+    /// A user-defined data type.
+    Type(Id),
+    /// A variable declared in the scope.
+    Variable(Scope, Id),
+    /// A value of the named enumeration type.
+    EnumValue(Id, Id),
+    /// A field of the named structure type.
+    Field(Id, Id),
+    /// A method of the named function block.
+    Method(Id, Id),
+    /// A task declared in a resource.
+    Task(Id),
+}
+
+impl SymbolId {
+    /// The name as it is written at the declaration.
+    pub fn name(&self) -> &Id {
+        match self {
+            SymbolId::Pou(name) | SymbolId::Type(name) | SymbolId::Task(name) => name,
+            SymbolId::Variable(_, name)
+            | SymbolId::EnumValue(_, name)
+            | SymbolId::Field(_, name)
+            | SymbolId::Method(_, name) => name,
+        }
+    }
+}
+
+/// The kind of POU a [`SymbolId::Pou`] names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PouKind {
+    Program,
+    FunctionBlock,
+    Function,
+    Interface,
+}
+
+/// One place in source where a symbol's name is written.
+#[derive(Debug, Clone)]
+pub struct Occurrence {
+    pub symbol: SymbolId,
+    pub span: SourceSpan,
+}
+
+/// Declarations and references across a set of libraries.
+pub struct SymbolIndex {
+    declarations: Declarations,
+    occurrences: Vec<Occurrence>,
+    lookups: HashMap<SymbolId, HashSet<Scope>>,
+    calls: Vec<Call>,
+}
+
+impl SymbolIndex {
+    /// Indexes the libraries, which together form one project: a reference
+    /// in one may resolve to a declaration in another.
+    pub fn build(libraries: &[&Library]) -> Self {
+        let mut declarations = Declarations::default();
+        for library in libraries {
+            declarations.collect(library);
+        }
+        declarations.define_orphan_externals();
+
+        let mut resolver = Resolver {
+            declarations: &declarations,
+            pou: None,
+            method: None,
+            enum_type: None,
+            struct_type: None,
+            seen: HashSet::new(),
+            occurrences: vec![],
+            lookups: HashMap::new(),
+            calls: vec![],
+        };
+        for library in libraries {
+            let _ = resolver.walk(library);
+        }
+        let occurrences = resolver.occurrences;
+        let lookups = resolver.lookups;
+        let calls = resolver.calls;
+
+        SymbolIndex {
+            declarations,
+            occurrences,
+            lookups,
+            calls,
+        }
+    }
+
+    /// Every occurrence, declarations included, in the order the libraries
+    /// were walked.
+    pub fn occurrences(&self) -> &[Occurrence] {
+        &self.occurrences
+    }
+
+    /// The occurrence at the byte offset in the file, if the offset is on
+    /// (or immediately after) a name the index resolved.
+    pub fn occurrence_at(&self, file_id: &FileId, offset: usize) -> Option<&Occurrence> {
+        self.occurrences
+            .iter()
+            .filter(|occurrence| {
+                occurrence.span.file_id == *file_id
+                    && occurrence.span.start <= offset
+                    && offset <= occurrence.span.end
+            })
+            .min_by_key(|occurrence| occurrence.span.end - occurrence.span.start)
+    }
+
+    /// The symbol at the byte offset in the file.
+    pub fn symbol_at(&self, file_id: &FileId, offset: usize) -> Option<&SymbolId> {
+        self.occurrence_at(file_id, offset)
+            .map(|occurrence| &occurrence.symbol)
+    }
+
+    /// Where the symbol is declared.
+    pub fn definition(&self, symbol: &SymbolId) -> Option<&SourceSpan> {
+        self.declarations.definitions.get(symbol)
+    }
+
+    /// The type of a variable, field or enumeration value, or the symbol
+    /// itself for a type or POU. `None` when the type is not declared in
+    /// the project (an elementary type, for example).
+    pub fn type_definition(&self, symbol: &SymbolId) -> Option<SymbolId> {
+        match symbol {
+            SymbolId::Variable(..) | SymbolId::Field(..) => {
+                match self.declarations.shapes.get(symbol)? {
+                    Shape::Named(name) | Shape::ArrayOf(name) => {
+                        self.declarations.type_symbol(name)
+                    }
+                }
+            }
+            SymbolId::EnumValue(type_name, _) => Some(SymbolId::Type(type_name.clone())),
+            SymbolId::Type(_) | SymbolId::Pou(_) => Some(symbol.clone()),
+            SymbolId::Method(..) | SymbolId::Task(_) => None,
+        }
+    }
+
+    /// Every place the symbol's name is written, declaration included.
+    pub fn references(&self, symbol: &SymbolId) -> Vec<&SourceSpan> {
+        self.occurrences
+            .iter()
+            .filter(|occurrence| occurrence.symbol == *symbol)
+            .map(|occurrence| &occurrence.span)
+            .collect()
+    }
+
+    /// The kind of the POU with the name, if the project declares it.
+    pub fn pou_kind(&self, name: &Id) -> Option<PouKind> {
+        self.declarations.pou_kinds.get(name).copied()
+    }
+
+    /// Whether the span is the symbol's declaration.
+    pub fn is_definition(&self, symbol: &SymbolId, span: &SourceSpan) -> bool {
+        self.definition(symbol)
+            .is_some_and(|definition| same_span(definition, span))
+    }
+}
+
+/// `SourceSpan` equality deliberately ignores position, so compare fields.
+fn same_span(a: &SourceSpan, b: &SourceSpan) -> bool {
+    a.file_id == b.file_id && a.start == b.start && a.end == b.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::parse_only;
+
+    fn offset_of(source: &str, needle: &str, nth: usize) -> usize {
+        source.match_indices(needle).nth(nth).unwrap().0
+    }
+
+    fn texts<'a>(source: &'a str, spans: &[&SourceSpan]) -> Vec<&'a str> {
+        spans
+            .iter()
+            .map(|span| &source[span.start..span.end])
+            .collect()
+    }
+
+    const PROGRAM: &str = "
+TYPE
+    Color : (Red, Green);
+    Point : STRUCT
+        X : INT;
+        Y : INT;
+    END_STRUCT;
+END_TYPE
+
+FUNCTION_BLOCK Counter
+VAR_INPUT
+    Delta : INT;
+END_VAR
+VAR_OUTPUT
+    Value : INT;
+END_VAR
+    Value := Value + Delta;
+END_FUNCTION_BLOCK
+
+FUNCTION Twice : INT
+VAR_INPUT
+    N : INT;
+END_VAR
+    Twice := N * 2;
+END_FUNCTION
+
+PROGRAM Main
+VAR_EXTERNAL
+    Limit : INT;
+END_VAR
+VAR
+    Tally : Counter;
+    Shade : Color := Color#Green;
+    Where : Point;
+    Total : INT;
+END_VAR
+    Tally(Delta := Twice(N := Limit));
+    Total := Tally.Value;
+    Where.X := Total;
+    IF Shade = Red THEN
+        Shade := Green;
+    END_IF;
+END_PROGRAM
+
+CONFIGURATION Plant
+VAR_GLOBAL
+    Limit : INT := 10;
+END_VAR
+RESOURCE Cpu ON PLC
+    TASK Fast(INTERVAL := T#10ms, PRIORITY := 1);
+    PROGRAM Instance WITH Fast : Main;
+END_RESOURCE
+END_CONFIGURATION
+";
+
+    pub(super) fn index() -> SymbolIndex {
+        SymbolIndex::build(&[&parse_only(PROGRAM)])
+    }
+
+    #[test]
+    fn symbol_at_when_local_variable_use_then_resolves_to_declaration() {
+        let index = index();
+        let symbol = index
+            .symbol_at(&FileId::default(), offset_of(PROGRAM, "Total", 2))
+            .unwrap();
+
+        assert_eq!(
+            *symbol,
+            SymbolId::Variable(Scope::Pou(Id::from("Main")), Id::from("Total"))
+        );
+        let definition = index.definition(symbol).unwrap();
+        assert_eq!(definition.start, offset_of(PROGRAM, "Total", 0));
+    }
+
+    #[test]
+    fn references_when_fb_input_then_includes_declaration_body_and_named_argument() {
+        let index = index();
+        let symbol = SymbolId::Variable(Scope::Pou(Id::from("Counter")), Id::from("Delta"));
+
+        let references = index.references(&symbol);
+
+        assert_eq!(texts(PROGRAM, &references), vec!["Delta", "Delta", "Delta"]);
+    }
+
+    #[test]
+    fn references_when_global_then_includes_external_redeclaration() {
+        let index = index();
+        let symbol = SymbolId::Variable(Scope::Global, Id::from("Limit"));
+
+        let references = index.references(&symbol);
+
+        assert_eq!(references.len(), 3);
+        assert_eq!(
+            index.definition(&symbol).unwrap().start,
+            offset_of(PROGRAM, "Limit", 2)
+        );
+    }
+
+    #[test]
+    fn symbol_at_when_fb_output_member_then_resolves_to_fb_variable() {
+        let index = index();
+        let symbol = index
+            .symbol_at(&FileId::default(), offset_of(PROGRAM, "Value", 3))
+            .unwrap();
+
+        assert_eq!(
+            *symbol,
+            SymbolId::Variable(Scope::Pou(Id::from("Counter")), Id::from("Value"))
+        );
+    }
+
+    #[test]
+    fn symbol_at_when_struct_field_then_resolves_to_field() {
+        let index = index();
+        let symbol = index
+            .symbol_at(&FileId::default(), offset_of(PROGRAM, "Where.X", 0) + 6)
+            .unwrap();
+
+        assert_eq!(*symbol, SymbolId::Field(Id::from("Point"), Id::from("X")));
+    }
+
+    #[test]
+    fn references_when_enum_value_then_includes_bare_and_qualified_uses() {
+        let index = index();
+        let symbol = SymbolId::EnumValue(Id::from("Color"), Id::from("Green"));
+
+        let references = index.references(&symbol);
+
+        assert_eq!(texts(PROGRAM, &references), vec!["Green", "Green", "Green"]);
+    }
+
+    #[test]
+    fn references_when_program_then_includes_configuration_binding() {
+        let index = index();
+
+        let references = index.references(&SymbolId::Pou(Id::from("Main")));
+
+        assert_eq!(references.len(), 2);
+    }
+
+    #[test]
+    fn references_when_task_then_includes_program_binding() {
+        let index = index();
+
+        let references = index.references(&SymbolId::Task(Id::from("Fast")));
+
+        assert_eq!(references.len(), 2);
+    }
+
+    #[test]
+    fn references_when_function_then_includes_call_and_return_assignment() {
+        let index = index();
+
+        let references = index.references(&SymbolId::Pou(Id::from("Twice")));
+
+        assert_eq!(texts(PROGRAM, &references), vec!["Twice", "Twice", "Twice"]);
+    }
+
+    #[test]
+    fn type_definition_when_fb_instance_then_function_block() {
+        let index = index();
+        let symbol = SymbolId::Variable(Scope::Pou(Id::from("Main")), Id::from("Tally"));
+
+        assert_eq!(
+            index.type_definition(&symbol),
+            Some(SymbolId::Pou(Id::from("Counter")))
+        );
+    }
+
+    #[test]
+    fn type_definition_when_elementary_type_then_none() {
+        let index = index();
+        let symbol = SymbolId::Variable(Scope::Pou(Id::from("Main")), Id::from("Total"));
+
+        assert_eq!(index.type_definition(&symbol), None);
+    }
+
+    #[test]
+    fn symbol_at_when_reference_in_other_library_then_resolves_across_libraries() {
+        let base = "FUNCTION_BLOCK Base\nVAR\n    Speed : INT;\nEND_VAR\nEND_FUNCTION_BLOCK";
+        let derived = "FUNCTION_BLOCK Derived EXTENDS Base\n    Speed := 1;\nEND_FUNCTION_BLOCK";
+        let base_file = FileId::from_string("base.st");
+        let derived_file = FileId::from_string("derived.st");
+        let options = ironplc_parser::options::CompilerOptions {
+            allow_fb_inheritance: true,
+            ..Default::default()
+        };
+        let base_library = ironplc_parser::parse_program(base, &base_file, &options).unwrap();
+        let derived_library =
+            ironplc_parser::parse_program(derived, &derived_file, &options).unwrap();
+        let index = SymbolIndex::build(&[&base_library, &derived_library]);
+
+        let symbol = index
+            .symbol_at(&derived_file, offset_of(derived, "Speed", 0))
+            .unwrap();
+
+        assert_eq!(
+            *symbol,
+            SymbolId::Variable(Scope::Pou(Id::from("Base")), Id::from("Speed"))
+        );
+        assert_eq!(index.definition(symbol).unwrap().file_id, base_file);
+        let base_at = index.symbol_at(&derived_file, offset_of(derived, "Base", 0));
+        assert_eq!(base_at, Some(&SymbolId::Pou(Id::from("Base"))));
+    }
+
+    #[test]
+    fn symbol_at_when_method_call_then_resolves_to_method() {
+        let source = "
+FUNCTION_BLOCK Motor
+VAR
+    Running : BOOL;
+END_VAR
+METHOD Start
+    Running := TRUE;
+END_METHOD
+END_FUNCTION_BLOCK
+PROGRAM Main
+VAR
+    M : Motor;
+END_VAR
+    M.Start();
+END_PROGRAM";
+        let options = ironplc_parser::options::CompilerOptions {
+            allow_fb_inheritance: true,
+            ..Default::default()
+        };
+        let library = ironplc_parser::parse_program(source, &FileId::default(), &options).unwrap();
+        let index = SymbolIndex::build(&[&library]);
+
+        let symbol = index
+            .symbol_at(&FileId::default(), offset_of(source, "Start", 1))
+            .unwrap();
+
+        assert_eq!(
+            *symbol,
+            SymbolId::Method(Id::from("Motor"), Id::from("Start"))
+        );
+        assert_eq!(index.references(symbol).len(), 2);
+    }
+
+    #[test]
+    fn symbol_at_when_elementary_type_then_none() {
+        let index = index();
+
+        assert!(index
+            .symbol_at(&FileId::default(), offset_of(PROGRAM, "INT", 0))
+            .is_none());
+    }
+}
@@ -0,0 +1,128 @@
+//! The check that a rename keeps every occurrence referring to the same
+//! symbol.
+
+use ironplc_dsl::core::Id;
+
+use super::declarations::Shape;
+use super::{Scope, SymbolId, SymbolIndex};
+
+impl SymbolIndex {
+    /// The declared symbol that `new_name` already names where the symbol
+    /// is declared or used, so that renaming the symbol to it would change
+    /// what some occurrence refers to. `None` when the rename is safe.
+    pub fn rename_conflict(&self, symbol: &SymbolId, new_name: &Id) -> Option<SymbolId> {
+        let decls = &self.declarations;
+        let other = |found: Option<SymbolId>| found.filter(|found| found != symbol);
+
+        let scopes = self.lookups.get(symbol).into_iter().flatten();
+        for scope in scopes {
+            if let Some(found) = other(decls.lookup(scope, new_name).map(|(found, _)| found)) {
+                return Some(found);
+            }
+        }
+
+        match symbol {
+            SymbolId::Pou(_) | SymbolId::Type(_) => other(decls.type_symbol(new_name)),
+            SymbolId::Variable(Scope::Pou(pou), _) => other(
+                decls
+                    .member(&Shape::Named(pou.clone()), new_name)
+                    .map(|(found, _)| found),
+            ),
+            SymbolId::Variable(..) => None,
+            SymbolId::Field(type_name, _) => other(
+                decls
+                    .member(&Shape::Named(type_name.clone()), new_name)
+                    .map(|(found, _)| found),
+            ),
+            SymbolId::EnumValue(..) => other(
+                decls
+                    .enum_values
+                    .get(new_name)
+                    .and_then(|types| types.first())
+                    .map(|type_name| SymbolId::EnumValue(type_name.clone(), new_name.clone())),
+            ),
+            SymbolId::Method(fb, _) => other(decls.method(fb, new_name)),
+            SymbolId::Task(_) => other(
+                Some(SymbolId::Task(new_name.clone()))
+                    .filter(|task| decls.definitions.contains_key(task)),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol_index::tests::index;
+
+    #[test]
+    fn rename_conflict_when_global_renamed_to_local_of_using_pou_then_local() {
+        let index = index();
+        let symbol = SymbolId::Variable(Scope::Global, Id::from("Limit"));
+
+        let conflict = index.rename_conflict(&symbol, &Id::from("Total"));
+
+        assert_eq!(
+            conflict,
+            Some(SymbolId::Variable(
+                Scope::Pou(Id::from("Main")),
+                Id::from("Total")
+            ))
+        );
+    }
+
+    #[test]
+    fn rename_conflict_when_local_renamed_to_global_in_scope_then_global() {
+        let index = index();
+        let symbol = SymbolId::Variable(Scope::Pou(Id::from("Main")), Id::from("Total"));
+
+        let conflict = index.rename_conflict(&symbol, &Id::from("Limit"));
+
+        assert_eq!(
+            conflict,
+            Some(SymbolId::Variable(Scope::Global, Id::from("Limit")))
+        );
+    }
+
+    #[test]
+    fn rename_conflict_when_fb_input_renamed_to_other_member_then_member() {
+        let index = index();
+        let symbol = SymbolId::Variable(Scope::Pou(Id::from("Counter")), Id::from("Delta"));
+
+        let conflict = index.rename_conflict(&symbol, &Id::from("Value"));
+
+        assert_eq!(
+            conflict,
+            Some(SymbolId::Variable(
+                Scope::Pou(Id::from("Counter")),
+                Id::from("Value")
+            ))
+        );
+    }
+
+    #[test]
+    fn rename_conflict_when_pou_renamed_to_type_then_type() {
+        let index = index();
+
+        let conflict =
+            index.rename_conflict(&SymbolId::Pou(Id::from("Counter")), &Id::from("Point"));
+
+        assert_eq!(conflict, Some(SymbolId::Type(Id::from("Point"))));
+    }
+
+    #[test]
+    fn rename_conflict_when_name_unused_then_none() {
+        let index = index();
+        let symbol = SymbolId::Variable(Scope::Pou(Id::from("Counter")), Id::from("Delta"));
+
+        assert_eq!(index.rename_conflict(&symbol, &Id::from("Amount")), None);
+    }
+
+    #[test]
+    fn rename_conflict_when_only_case_changes_then_none() {
+        let index = index();
+        let symbol = SymbolId::Variable(Scope::Global, Id::from("Limit"));
+
+        assert_eq!(index.rename_conflict(&symbol, &Id::from("LIMIT")), None);
+    }
+}
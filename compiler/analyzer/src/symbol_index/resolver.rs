@@ -0,0 +1,456 @@
+//! Resolution of each name written in a POU body or declaration to the
+//! symbol it refers to, recording an occurrence for it.
+
+use std::collections::{HashMap, HashSet};
+
+use ironplc_dsl::common::*;
+use ironplc_dsl::configuration::ProgramConfiguration;
+use ironplc_dsl::core::{FileId, Id, Located, SourceSpan};
+use ironplc_dsl::textual::*;
+use ironplc_dsl::visitor::Visitor;
+
+use super::declarations::{shape_of, Declarations, Shape};
+use super::{Call, Occurrence, PouKind, Scope, SymbolId};
+
+/// Walks POU bodies and declarations, resolving each name in the scope it
+/// appears in.
+pub(super) struct Resolver<'a> {
+    pub(super) declarations: &'a Declarations,
+    pub(super) pou: Option<Id>,
+    pub(super) method: Option<Id>,
+    /// The enumeration whose values are being declared or assigned.
+    pub(super) enum_type: Option<Id>,
+    /// The structure whose elements are being declared.
+    pub(super) struct_type: Option<Id>,
+    pub(super) seen: HashSet<(FileId, usize, usize)>,
+    pub(super) occurrences: Vec<Occurrence>,
+    /// The scopes in which a name written on its own resolved to the symbol.
+    pub(super) lookups: HashMap<SymbolId, HashSet<Scope>>,
+    /// Every call from the POU or method being walked.
+    pub(super) calls: Vec<Call>,
+}
+
+impl Resolver<'_> {
+    fn record(&mut self, symbol: SymbolId, span: SourceSpan) {
+        if span.file_id.is_builtin() {
+            return;
+        }
+        if self
+            .seen
+            .insert((span.file_id.clone(), span.start, span.end))
+        {
+            self.occurrences.push(Occurrence { symbol, span });
+        }
+    }
+
+    /// Records a call to `callee` written at the span, from the POU or
+    /// method being walked.
+    fn record_call(&mut self, callee: SymbolId, span: SourceSpan) {
+        let caller = match (&self.pou, &self.method) {
+            (Some(pou), Some(method)) => SymbolId::Method(pou.clone(), method.clone()),
+            (Some(pou), None) => SymbolId::Pou(pou.clone()),
+            _ => return,
+        };
+        if !span.file_id.is_builtin() {
+            self.calls.push(Call {
+                caller,
+                callee,
+                span,
+            });
+        }
+    }
+
+    fn scope(&self) -> Option<Scope> {
+        match (&self.pou, &self.method) {
+            (Some(pou), Some(method)) => Some(Scope::Method(pou.clone(), method.clone())),
+            (Some(pou), None) => Some(Scope::Pou(pou.clone())),
+            _ => None,
+        }
+    }
+
+    /// A name used as a variable in the current scope. The scope is noted
+    /// so that a rename can tell whether the new name would resolve to
+    /// something else there.
+    fn resolve_name(&mut self, name: &Id) -> Option<(SymbolId, Option<Shape>)> {
+        let scope = self.scope().unwrap_or(Scope::Global);
+        let found = self.declarations.lookup(&scope, name)?;
+        self.note_lookup(found.0.clone(), scope);
+        Some(found)
+    }
+
+    fn note_lookup(&mut self, symbol: SymbolId, scope: Scope) {
+        self.lookups.entry(symbol).or_default().insert(scope);
+    }
+
+    /// An enumeration value written without its type.
+    fn resolve_enum_value(&self, value: &Id) -> Option<SymbolId> {
+        let types = self.declarations.enum_values.get(value)?;
+        let type_name = match &self.enum_type {
+            Some(hint) if types.contains(hint) => hint.clone(),
+            _ if types.len() == 1 => types[0].clone(),
+            _ => return None,
+        };
+        Some(SymbolId::EnumValue(type_name, value.clone()))
+    }
+
+    /// Records every name in the variable and returns its shape.
+    fn resolve_variable(&mut self, variable: &SymbolicVariableKind) -> Option<Shape> {
+        match variable {
+            SymbolicVariableKind::Named(named) => {
+                let (symbol, shape) = self.resolve_name(&named.name)?;
+                self.record(symbol, named.name.span());
+                shape
+            }
+            SymbolicVariableKind::Array(array) => {
+                for subscript in &array.subscripts {
+                    let _ = self.visit_expr(subscript);
+                }
+                let shape = self.resolve_variable(&array.subscripted_variable)?;
+                self.declarations.element(&shape)
+            }
+            SymbolicVariableKind::Structured(structured) => {
+                let record = self.resolve_variable(&structured.record)?;
+                let (symbol, shape) = self.declarations.member(&record, &structured.field)?;
+                self.record(symbol, structured.field.span());
+                shape
+            }
+            SymbolicVariableKind::BitAccess(access) => {
+                self.resolve_variable(&access.variable);
+                None
+            }
+            SymbolicVariableKind::PartialAccess(access) => {
+                self.resolve_variable(&access.variable);
+                None
+            }
+            SymbolicVariableKind::Deref(deref) => {
+                self.resolve_variable(&deref.variable);
+                None
+            }
+            SymbolicVariableKind::SelfRef(self_ref) => {
+                let pou = self.pou.clone()?;
+                match self_ref.kind {
+                    SelfRefKind::This => Some(Shape::Named(pou)),
+                    SelfRefKind::Super => self
+                        .declarations
+                        .bases
+                        .get(&pou)
+                        .map(|base| Shape::Named(base.clone())),
+                }
+            }
+        }
+    }
+
+    /// Records the parameter names and visits the argument expressions of
+    /// a call to the POU of the given shape.
+    fn resolve_arguments(&mut self, callee: Option<&Shape>, params: &[ParamAssignmentKind]) {
+        for param in params {
+            match param {
+                ParamAssignmentKind::PositionalInput(input) => {
+                    let _ = self.visit_expr(&input.expr);
+                }
+                ParamAssignmentKind::NamedInput(input) => {
+                    self.resolve_parameter(callee, &input.name);
+                    let _ = self.visit_expr(&input.expr);
+                }
+                ParamAssignmentKind::Output(output) => {
+                    self.resolve_parameter(callee, &output.src);
+                    let _ = self.visit_variable(&output.tgt);
+                }
+            }
+        }
+    }
+
+    fn resolve_parameter(&mut self, callee: Option<&Shape>, name: &Id) {
+        if let Some((symbol, _)) = callee.and_then(|shape| self.declarations.member(shape, name)) {
+            self.record(symbol, name.span());
+        }
+    }
+
+    /// Records element names of a structure or function block initializer.
+    fn resolve_element_inits(&mut self, shape: &Shape, inits: &[StructureElementInit]) {
+        for init in inits {
+            let member = self.declarations.member(shape, &init.name);
+            if let Some((symbol, _)) = &member {
+                self.record(symbol.clone(), init.name.span());
+            }
+            match &init.init {
+                StructInitialValueAssignmentKind::Structure(nested) => {
+                    if let Some(shape) = member.and_then(|(_, shape)| shape) {
+                        self.resolve_element_inits(&shape, nested);
+                    }
+                }
+                StructInitialValueAssignmentKind::EnumeratedValue(value) => {
+                    let hint = match member.and_then(|(_, shape)| shape) {
+                        Some(Shape::Named(type_name)) => Some(type_name),
+                        _ => None,
+                    };
+                    self.with_enum_type(hint, |this| this.visit_enumerated_value(value));
+                }
+                other => {
+                    let _ = other.recurse_visit(self);
+                }
+            }
+        }
+    }
+
+    fn with_enum_type(
+        &mut self,
+        enum_type: Option<Id>,
+        f: impl FnOnce(&mut Self) -> Result<(), ()>,
+    ) {
+        let outer = std::mem::replace(&mut self.enum_type, enum_type);
+        let _ = f(self);
+        self.enum_type = outer;
+    }
+
+    fn in_pou(&mut self, pou: &Id, f: impl FnOnce(&mut Self) -> Result<(), ()>) -> Result<(), ()> {
+        self.pou = Some(pou.clone());
+        let result = f(self);
+        self.pou = None;
+        result
+    }
+}
+
+impl Visitor<()> for Resolver<'_> {
+    type Value = ();
+
+    fn visit_function_declaration(&mut self, node: &FunctionDeclaration) -> Result<(), ()> {
+        self.record(SymbolId::Pou(node.name.clone()), node.name.span());
+        self.in_pou(&node.name, |this| node.recurse_visit(this))
+    }
+
+    fn visit_function_block_declaration(
+        &mut self,
+        node: &FunctionBlockDeclaration,
+    ) -> Result<(), ()> {
+        self.in_pou(&node.name.name, |this| node.recurse_visit(this))
+    }
+
+    fn visit_method_declaration(&mut self, node: &MethodDeclaration) -> Result<(), ()> {
+        if let Some(pou) = &self.pou {
+            self.record(
+                SymbolId::Method(pou.clone(), node.name.clone()),
+                node.name.span(),
+            );
+        }
+        self.method = Some(node.name.clone());
+        let result = node.recurse_visit(self);
+        self.method = None;
+        result
+    }
+
+    fn visit_program_declaration(&mut self, node: &ProgramDeclaration) -> Result<(), ()> {
+        self.record(SymbolId::Pou(node.name.clone()), node.name.span());
+        self.in_pou(&node.name, |this| node.recurse_visit(this))
+    }
+
+    fn visit_interface_declaration(&mut self, node: &InterfaceDeclaration) -> Result<(), ()> {
+        self.record(SymbolId::Pou(node.name.clone()), node.name.span());
+        node.recurse_visit(self)
+    }
+
+    fn visit_var_decl(&mut self, node: &VarDecl) -> Result<(), ()> {
+        if let Some(name) = node.identifier.symbolic_id() {
+            let scope = match (node.var_type == VariableType::Global, self.scope()) {
+                (false, Some(scope)) => scope,
+                _ => Scope::Global,
+            };
+            if let Some((symbol, _)) = self.declarations.variable_in(&scope, name) {
+                self.note_lookup(symbol.clone(), scope);
+                self.record(symbol, name.span());
+            }
+        }
+        let hint = match shape_of(&node.initializer) {
+            Some(Shape::Named(type_name)) => Some(type_name),
+            _ => None,
+        };
+        self.with_enum_type(hint, |this| node.initializer.recurse_visit(this));
+        Ok(())
+    }
+
+    fn visit_edge_var_decl(&mut self, node: &EdgeVarDecl) -> Result<(), ()> {
+        if let Some(scope) = self.scope() {
+            self.record(
+                SymbolId::Variable(scope, node.identifier.clone()),
+                node.identifier.span(),
+            );
+        }
+        Ok(())
+    }
+
+    fn visit_type_name(&mut self, node: &TypeName) -> Result<(), ()> {
+        if let Some(symbol) = self.declarations.type_symbol(&node.name) {
+            self.record(symbol, node.name.span());
+        }
+        Ok(())
+    }
+
+    fn visit_enumeration_declaration(&mut self, node: &EnumerationDeclaration) -> Result<(), ()> {
+        let type_name = Some(node.type_name.name.clone());
+        self.with_enum_type(type_name, |this| node.recurse_visit(this));
+        Ok(())
+    }
+
+    fn visit_enumerated_value(&mut self, node: &EnumeratedValue) -> Result<(), ()> {
+        let symbol = match &node.type_name {
+            Some(type_name) => {
+                self.visit_type_name(type_name)?;
+                let symbol = SymbolId::EnumValue(
+                    self.declarations.unalias(&type_name.name),
+                    node.value.clone(),
+                );
+                self.declarations
+                    .definitions
+                    .contains_key(&symbol)
+                    .then_some(symbol)
+            }
+            None => self.resolve_enum_value(&node.value),
+        };
+        if let Some(symbol) = symbol {
+            self.record(symbol, node.value.span());
+        }
+        Ok(())
+    }
+
+    fn visit_structure_declaration(&mut self, node: &StructureDeclaration) -> Result<(), ()> {
+        self.struct_type = Some(node.type_name.name.clone());
+        let result = node.recurse_visit(self);
+        self.struct_type = None;
+        result
+    }
+
+    fn visit_structure_element_declaration(
+        &mut self,
+        node: &StructureElementDeclaration,
+    ) -> Result<(), ()> {
+        if let Some(struct_type) = &self.struct_type {
+            self.record(
+                SymbolId::Field(struct_type.clone(), node.name.clone()),
+                node.name.span(),
+            );
+        }
+        let hint = match shape_of(&node.init) {
+            Some(Shape::Named(type_name)) => Some(type_name),
+            _ => None,
+        };
+        self.with_enum_type(hint, |this| node.init.recurse_visit(this));
+        Ok(())
+    }
+
+    fn visit_structure_initialization_declaration(
+        &mut self,
+        node: &StructureInitializationDeclaration,
+    ) -> Result<(), ()> {
+        self.visit_type_name(&node.type_name)?;
+        let shape = Shape::Named(node.type_name.name.clone());
+        self.resolve_element_inits(&shape, &node.elements_init);
+        Ok(())
+    }
+
+    fn visit_function_block_initial_value_assignment(
+        &mut self,
+        node: &FunctionBlockInitialValueAssignment,
+    ) -> Result<(), ()> {
+        self.visit_type_name(&node.type_name)?;
+        let shape = Shape::Named(node.type_name.name.clone());
+        self.resolve_element_inits(&shape, &node.init);
+        Ok(())
+    }
+
+    fn visit_function_block_call_initializer(
+        &mut self,
+        node: &FunctionBlockCallInitializer,
+    ) -> Result<(), ()> {
+        self.visit_type_name(&node.type_name)?;
+        let shape = Shape::Named(node.type_name.name.clone());
+        self.resolve_arguments(Some(&shape), &node.params);
+        Ok(())
+    }
+
+    fn visit_symbolic_variable_kind(&mut self, node: &SymbolicVariableKind) -> Result<(), ()> {
+        self.resolve_variable(node);
+        Ok(())
+    }
+
+    fn visit_late_bound(&mut self, node: &LateBound) -> Result<(), ()> {
+        if let Some((symbol, _)) = self.resolve_name(&node.value) {
+            self.record(symbol, node.value.span());
+        } else if let Some(symbol) = self.resolve_enum_value(&node.value) {
+            self.note_lookup(symbol.clone(), self.scope().unwrap_or(Scope::Global));
+            self.record(symbol, node.value.span());
+        }
+        Ok(())
+    }
+
+    fn visit_fb_call(&mut self, node: &FbCall) -> Result<(), ()> {
+        let shape = self
+            .resolve_name(&node.var_name)
+            .and_then(|(symbol, shape)| {
+                self.record(symbol, node.var_name.span());
+                shape
+            });
+        if let Some(Shape::Named(type_name)) = &shape {
+            let fb = self.declarations.unalias(type_name);
+            if self.declarations.pou_kinds.get(&fb) == Some(&PouKind::FunctionBlock) {
+                self.record_call(SymbolId::Pou(fb), node.var_name.span());
+            }
+        }
+        self.resolve_arguments(shape.as_ref(), &node.params);
+        Ok(())
+    }
+
+    fn visit_method_call(&mut self, node: &MethodCall) -> Result<(), ()> {
+        let shape = match &node.receiver {
+            MethodReceiver::Instance(name) => {
+                self.resolve_name(name).and_then(|(symbol, shape)| {
+                    self.record(symbol, name.span());
+                    shape
+                })
+            }
+            MethodReceiver::SelfRef(self_ref) => {
+                self.resolve_variable(&SymbolicVariableKind::SelfRef(self_ref.clone()))
+            }
+        };
+        if let Some(Shape::Named(fb)) = &shape {
+            if let Some(symbol) = self.declarations.method(fb, &node.method) {
+                self.record(symbol.clone(), node.method.span());
+                self.record_call(symbol, node.method.span());
+            }
+        }
+        self.resolve_arguments(None, &node.params);
+        Ok(())
+    }
+
+    fn visit_function(&mut self, node: &Function) -> Result<(), ()> {
+        let callee = if self.declarations.functions.contains(&node.name) {
+            self.record(SymbolId::Pou(node.name.clone()), node.name.span());
+            self.record_call(SymbolId::Pou(node.name.clone()), node.name.span());
+            Some(Shape::Named(node.name.clone()))
+        } else {
+            None
+        };
+        self.resolve_arguments(callee.as_ref(), &node.param_assignment);
+        Ok(())
+    }
+
+    fn visit_program_configuration(&mut self, node: &ProgramConfiguration) -> Result<(), ()> {
+        if let Some(task_name) = &node.task_name {
+            let task = SymbolId::Task(task_name.clone());
+            if self.declarations.definitions.contains_key(&task) {
+                self.record(task, task_name.span());
+            }
+        }
+        if self.declarations.pous.contains(&node.type_name) {
+            self.record(SymbolId::Pou(node.type_name.clone()), node.type_name.span());
+        }
+        node.recurse_visit(self)
+    }
+
+    fn visit_task_configuration(
+        &mut self,
+        node: &ironplc_dsl::configuration::TaskConfiguration,
+    ) -> Result<(), ()> {
+        self.record(SymbolId::Task(node.name.clone()), node.name.span());
+        node.recurse_visit(self)
+    }
+}
@@ -0,0 +1,113 @@
+//! Search over the project's top-level names, for workspace symbols.
+
+use ironplc_dsl::core::SourceSpan;
+
+use super::{Scope, SymbolId, SymbolIndex};
+
+impl SymbolIndex {
+    /// The POUs, data types, global variables and tasks whose names match
+    /// the query, best match first. A name matches when it contains the
+    /// query's characters in order, ignoring case; a name that starts with
+    /// the query ranks before one that contains it, and that before one that
+    /// only has its characters spread out. An empty query matches every
+    /// name.
+    pub fn search(&self, query: &str) -> Vec<(&SymbolId, &SourceSpan)> {
+        let query = query.to_lowercase();
+        let mut found: Vec<(Rank, String, &SymbolId, &SourceSpan)> = self
+            .declarations
+            .definitions
+            .iter()
+            .filter(|(symbol, _)| is_top_level(symbol))
+            .filter_map(|(symbol, span)| {
+                let name = symbol.name().original().to_lowercase();
+                let rank = rank(&name, &query)?;
+                Some((rank, name, symbol, span))
+            })
+            .collect();
+        found.sort_by(|a, b| (a.0, a.1.len(), &a.1).cmp(&(b.0, b.1.len(), &b.1)));
+        found
+            .into_iter()
+            .map(|(_, _, symbol, span)| (symbol, span))
+            .collect()
+    }
+}
+
+/// How well a name matches a query, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Rank {
+    Prefix,
+    Substring,
+    Subsequence,
+}
+
+fn rank(name: &str, query: &str) -> Option<Rank> {
+    if name.starts_with(query) {
+        return Some(Rank::Prefix);
+    }
+    if name.contains(query) {
+        return Some(Rank::Substring);
+    }
+    let mut chars = name.chars();
+    query
+        .chars()
+        .all(|wanted| chars.any(|c| c == wanted))
+        .then_some(Rank::Subsequence)
+}
+
+fn is_top_level(symbol: &SymbolId) -> bool {
+    matches!(
+        symbol,
+        SymbolId::Pou(_)
+            | SymbolId::Type(_)
+            | SymbolId::Task(_)
+            | SymbolId::Variable(Scope::Global, _)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol_index::tests::index;
+    use ironplc_dsl::core::Id;
+
+    fn names(index: &SymbolIndex, query: &str) -> Vec<String> {
+        index
+            .search(query)
+            .into_iter()
+            .map(|(symbol, _)| symbol.name().original().clone())
+            .collect()
+    }
+
+    #[test]
+    fn search_when_empty_query_then_every_top_level_name() {
+        let index = index();
+
+        let mut found = names(&index, "");
+        found.sort();
+
+        assert_eq!(
+            found,
+            vec!["Color", "Counter", "Fast", "Limit", "Main", "Point", "Twice"]
+        );
+    }
+
+    #[test]
+    fn search_when_query_then_prefix_before_substring_before_subsequence() {
+        let index = index();
+
+        assert_eq!(names(&index, "co"), vec!["Color", "Counter"]);
+        assert_eq!(names(&index, "in"), vec!["Main", "Point"]);
+        assert_eq!(names(&index, "CNT"), vec!["Counter"]);
+    }
+
+    #[test]
+    fn search_when_no_name_matches_then_empty() {
+        let index = index();
+
+        assert!(index.search("xyz").is_empty());
+        assert!(!index
+            .search("tw")
+            .iter()
+            .any(|(symbol, _)| **symbol == SymbolId::Pou(Id::from("Main"))));
+    }
+}
@@ -0,0 +1,450 @@
+//! Conservative value-range analysis over integer expressions.
+//!
+//! Computes an inclusive `[min, max]` interval that every value of an
+//! expression is guaranteed to lie in, or `None` when nothing is known.
+//! The analysis is sound but deliberately shallow: it understands integer
+//! literals, unary negation, parentheses, `+`, `-`, `*`, `/` and `MOD`, and
+//! the `LIMIT`, `MIN` and `MAX` standard functions, and defers every variable
+//! and enumerated value to a caller-supplied lookup (the caller knows declared
+//! subranges, enumeration ordinals and storage widths; this module does not).
+//!
+//! Arithmetic is exact, so a result is only known while it stays within the
+//! caller's domain, the values the operation is computed in. Outside it the
+//! overflow policy decides the value, and the analysis gives up.
+//!
+//! Code generation uses it to elide runtime range checks that cannot fail.
+
+use ironplc_dsl::common::*;
+use ironplc_dsl::textual::*;
+
+/// An inclusive interval of integer values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueRange {
+    pub min: i128,
+    pub max: i128,
+}
+
+impl ValueRange {
+    /// Creates the interval `[min, max]`.
+    pub fn new(min: i128, max: i128) -> Self {
+        Self { min, max }
+    }
+
+    /// Creates the single-value interval `[value, value]`.
+    pub fn exact(value: i128) -> Self {
+        Self::new(value, value)
+    }
+
+    /// Returns the interval holding every value of `subrange`, or `None`
+    /// when the type is not a subrange.
+    pub fn of_subrange(subrange: &crate::IntermediateType) -> Option<Self> {
+        match subrange {
+            crate::IntermediateType::Subrange {
+                min_value,
+                max_value,
+                ..
+            } => Some(Self::new(*min_value, *max_value)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` when every value of `other` is also in `self`.
+    pub fn contains(&self, other: &ValueRange) -> bool {
+        self.min <= other.min && other.max <= self.max
+    }
+}
+
+/// Returns the range of `expr`, or `None` when it cannot be bounded.
+///
+/// `domain` holds the values the operations of `expr` are computed in (for
+/// example `DINT` for an array subscript); the result of a negation or
+/// arithmetic operation outside it is unknown. `leaf` is consulted for
+/// variables and enumerated values; it returns the range the caller knows
+/// for that expression (for example the declared subrange of a variable) or
+/// `None`.
+pub fn expr_range(
+    expr: &Expr,
+    domain: ValueRange,
+    leaf: &dyn Fn(&Expr) -> Option<ValueRange>,
+) -> Option<ValueRange> {
+    match &expr.kind {
+        ExprKind::Const(ConstantKind::IntegerLiteral(lit)) => {
+            Some(ValueRange::exact(signed_integer_value(&lit.value)))
+        }
+        ExprKind::UnaryOp(unary) if unary.op == UnaryOp::Neg => {
+            let inner = expr_range(&unary.term, domain, leaf)?;
+            Some(ValueRange::new(-inner.max, -inner.min)).filter(|range| domain.contains(range))
+        }
+        ExprKind::BinaryOp(binary) => {
+            let left = expr_range(&binary.left, domain, leaf)?;
+            let right = expr_range(&binary.right, domain, leaf)?;
+            binary_range(&binary.op, left, right).filter(|range| domain.contains(range))
+        }
+        ExprKind::Expression(inner) => expr_range(inner, domain, leaf),
+        ExprKind::Variable(_) | ExprKind::EnumeratedValue(_) => leaf(expr),
+        ExprKind::Function(function) => function_range(function, domain, leaf),
+        _ => None,
+    }
+}
+
+/// Exact range of `a op b`, or `None` when it is unknown or does not fit in
+/// an `i128`.
+///
+/// Division truncates toward zero and `MOD` takes the sign of the dividend,
+/// as the runtime does. Both are unknown when the divisor may be zero.
+fn binary_range(op: &Operator, a: ValueRange, b: ValueRange) -> Option<ValueRange> {
+    let may_be_zero = b.min <= 0 && b.max >= 0;
+    match op {
+        Operator::Add => Some(ValueRange::new(
+            a.min.checked_add(b.min)?,
+            a.max.checked_add(b.max)?,
+        )),
+        Operator::Sub => Some(ValueRange::new(
+            a.min.checked_sub(b.max)?,
+            a.max.checked_sub(b.min)?,
+        )),
+        Operator::Mul => corners(a, b, i128::checked_mul),
+        // With the divisor's sign fixed, the quotient is monotonic in each
+        // operand, so its extremes are at the corners.
+        Operator::Div if !may_be_zero => corners(a, b, i128::checked_div),
+        Operator::Mod if !may_be_zero => {
+            // |a MOD b| < |b|, and the result is never further from zero
+            // than the dividend.
+            let limit = b.min.checked_abs()?.max(b.max.checked_abs()?) - 1;
+            let min = if a.min < 0 { a.min.max(-limit) } else { 0 };
+            let max = if a.max > 0 { a.max.min(limit) } else { 0 };
+            Some(ValueRange::new(min, max))
+        }
+        _ => None,
+    }
+}
+
+/// Hull of `f` applied to each pair of bounds of `a` and `b`.
+fn corners(a: ValueRange, b: ValueRange, f: fn(i128, i128) -> Option<i128>) -> Option<ValueRange> {
+    let values = [
+        f(a.min, b.min)?,
+        f(a.min, b.max)?,
+        f(a.max, b.min)?,
+        f(a.max, b.max)?,
+    ];
+    Some(ValueRange::new(
+        *values.iter().min()?,
+        *values.iter().max()?,
+    ))
+}
+
+/// Range of a call to one of the standard selection functions.
+fn function_range(
+    function: &Function,
+    domain: ValueRange,
+    leaf: &dyn Fn(&Expr) -> Option<ValueRange>,
+) -> Option<ValueRange> {
+    let args = positional_args(function)?;
+    let name = function.name.to_string().to_uppercase();
+    match name.as_str() {
+        // LIMIT(MN, IN, MX) is within [MN, MX] whatever IN is.
+        "LIMIT" if args.len() == 3 => {
+            let mn = expr_range(args[0], domain, leaf)?;
+            let mx = expr_range(args[2], domain, leaf)?;
+            if mn.max > mx.min {
+                // The bounds may cross; LIMIT's result is then not confined.
+                return None;
+            }
+            Some(ValueRange::new(mn.min, mx.max))
+        }
+        "MIN" if !args.is_empty() => fold_args(&args, domain, leaf, |a, b| {
+            ValueRange::new(a.min.min(b.min), a.max.min(b.max))
+        }),
+        "MAX" if !args.is_empty() => fold_args(&args, domain, leaf, |a, b| {
+            ValueRange::new(a.min.max(b.min), a.max.max(b.max))
+        }),
+        _ => None,
+    }
+}
+
+/// Combines the ranges of every argument; `None` if any is unbounded.
+fn fold_args(
+    args: &[&Expr],
+    domain: ValueRange,
+    leaf: &dyn Fn(&Expr) -> Option<ValueRange>,
+    combine: impl Fn(ValueRange, ValueRange) -> ValueRange,
+) -> Option<ValueRange> {
+    let mut ranges = args.iter().map(|arg| expr_range(arg, domain, leaf));
+    let first = ranges.next()??;
+    ranges.try_fold(first, |acc, next| Some(combine(acc, next?)))
+}
+
+/// Returns the argument expressions in order when every argument is a
+/// positional input. Named arguments have already been rewritten to
+/// positional form for standard functions by the time code is generated.
+fn positional_args(function: &Function) -> Option<Vec<&Expr>> {
+    function
+        .param_assignment
+        .iter()
+        .map(|param| match param {
+            ParamAssignmentKind::PositionalInput(input) => Some(&input.expr),
+            _ => None,
+        })
+        .collect()
+}
+
+fn signed_integer_value(value: &SignedInteger) -> i128 {
+    let magnitude = value.value.value as i128;
+    if value.is_neg {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironplc_dsl::core::Id;
+
+    /// The values of a `DINT`, which array subscripts are computed in.
+    const DINT: ValueRange = ValueRange {
+        min: i32::MIN as i128,
+        max: i32::MAX as i128,
+    };
+
+    fn no_leaf(_: &Expr) -> Option<ValueRange> {
+        None
+    }
+
+    fn call(name: &str, args: Vec<ExprKind>) -> Expr {
+        Expr::new(ExprKind::Function(Function {
+            name: Id::from(name),
+            param_assignment: args
+                .into_iter()
+                .map(ParamAssignmentKind::positional)
+                .collect(),
+        }))
+    }
+
+    #[test]
+    fn expr_range_when_integer_literal_then_exact() {
+        let expr = Expr::new(ExprKind::integer_literal("42"));
+        assert_eq!(
+            expr_range(&expr, DINT, &no_leaf),
+            Some(ValueRange::exact(42))
+        );
+    }
+
+    #[test]
+    fn expr_range_when_negated_literal_then_negative() {
+        let expr = Expr::new(ExprKind::unary(
+            UnaryOp::Neg,
+            ExprKind::integer_literal("7"),
+        ));
+        assert_eq!(
+            expr_range(&expr, DINT, &no_leaf),
+            Some(ValueRange::exact(-7))
+        );
+    }
+
+    #[test]
+    fn expr_range_when_variable_then_uses_leaf() {
+        let expr = Expr::new(ExprKind::named_variable("x"));
+        let leaf = |_: &Expr| Some(ValueRange::new(1, 10));
+        assert_eq!(expr_range(&expr, DINT, &leaf), Some(ValueRange::new(1, 10)));
+        assert_eq!(expr_range(&expr, DINT, &no_leaf), None);
+    }
+
+    #[test]
+    fn expr_range_when_limit_with_constant_bounds_then_bounds() {
+        let expr = call(
+            "LIMIT",
+            vec![
+                ExprKind::integer_literal("0"),
+                ExprKind::named_variable("x"),
+                ExprKind::integer_literal("100"),
+            ],
+        );
+        assert_eq!(
+            expr_range(&expr, DINT, &no_leaf),
+            Some(ValueRange::new(0, 100))
+        );
+    }
+
+    #[test]
+    fn expr_range_when_limit_bounds_cross_then_none() {
+        let expr = call(
+            "LIMIT",
+            vec![
+                ExprKind::integer_literal("10"),
+                ExprKind::named_variable("x"),
+                ExprKind::integer_literal("5"),
+            ],
+        );
+        assert_eq!(expr_range(&expr, DINT, &no_leaf), None);
+    }
+
+    #[test]
+    fn expr_range_when_min_of_bounded_args_then_smaller_bounds() {
+        let expr = call(
+            "MIN",
+            vec![
+                ExprKind::named_variable("x"),
+                ExprKind::integer_literal("5"),
+            ],
+        );
+        let leaf = |_: &Expr| Some(ValueRange::new(0, 100));
+        assert_eq!(expr_range(&expr, DINT, &leaf), Some(ValueRange::new(0, 5)));
+    }
+
+    #[test]
+    fn expr_range_when_max_with_unbounded_arg_then_none() {
+        let expr = call(
+            "MAX",
+            vec![
+                ExprKind::named_variable("x"),
+                ExprKind::integer_literal("5"),
+            ],
+        );
+        assert_eq!(expr_range(&expr, DINT, &no_leaf), None);
+    }
+
+    fn binary(op: Operator, left: ExprKind, right: ExprKind) -> Expr {
+        Expr::new(ExprKind::binary(op, left, right))
+    }
+
+    /// A leaf that bounds every variable to `0..=126`.
+    fn loop_leaf(_: &Expr) -> Option<ValueRange> {
+        Some(ValueRange::new(0, 126))
+    }
+
+    #[test]
+    fn expr_range_when_variable_plus_literal_then_shifted() {
+        let expr = binary(
+            Operator::Add,
+            ExprKind::named_variable("i"),
+            ExprKind::integer_literal("1"),
+        );
+        assert_eq!(
+            expr_range(&expr, DINT, &loop_leaf),
+            Some(ValueRange::new(1, 127))
+        );
+    }
+
+    #[test]
+    fn expr_range_when_literal_minus_variable_then_reversed() {
+        let expr = binary(
+            Operator::Sub,
+            ExprKind::integer_literal("127"),
+            ExprKind::named_variable("i"),
+        );
+        assert_eq!(
+            expr_range(&expr, DINT, &loop_leaf),
+            Some(ValueRange::new(1, 127))
+        );
+    }
+
+    #[test]
+    fn expr_range_when_multiplied_by_negative_then_corners() {
+        let expr = binary(
+            Operator::Mul,
+            ExprKind::named_variable("i"),
+            ExprKind::unary(UnaryOp::Neg, ExprKind::integer_literal("2")),
+        );
+        assert_eq!(
+            expr_range(&expr, DINT, &loop_leaf),
+            Some(ValueRange::new(-252, 0))
+        );
+    }
+
+    #[test]
+    fn expr_range_when_divided_by_positive_then_truncated() {
+        let expr = binary(
+            Operator::Div,
+            ExprKind::named_variable("i"),
+            ExprKind::integer_literal("4"),
+        );
+        assert_eq!(
+            expr_range(&expr, DINT, &loop_leaf),
+            Some(ValueRange::new(0, 31))
+        );
+    }
+
+    #[test]
+    fn expr_range_when_divisor_may_be_zero_then_none() {
+        let div = binary(
+            Operator::Div,
+            ExprKind::integer_literal("100"),
+            ExprKind::named_variable("i"),
+        );
+        let modulo = binary(
+            Operator::Mod,
+            ExprKind::integer_literal("100"),
+            ExprKind::named_variable("i"),
+        );
+        assert_eq!(expr_range(&div, DINT, &loop_leaf), None);
+        assert_eq!(expr_range(&modulo, DINT, &loop_leaf), None);
+    }
+
+    #[test]
+    fn expr_range_when_mod_then_below_divisor() {
+        let expr = binary(
+            Operator::Mod,
+            ExprKind::named_variable("i"),
+            ExprKind::integer_literal("8"),
+        );
+        assert_eq!(
+            expr_range(&expr, DINT, &loop_leaf),
+            Some(ValueRange::new(0, 7))
+        );
+    }
+
+    #[test]
+    fn expr_range_when_negative_dividend_mod_then_sign_of_dividend() {
+        let expr = binary(
+            Operator::Mod,
+            ExprKind::named_variable("i"),
+            ExprKind::integer_literal("8"),
+        );
+        let leaf = |_: &Expr| Some(ValueRange::new(-3, 20));
+        assert_eq!(expr_range(&expr, DINT, &leaf), Some(ValueRange::new(-3, 7)));
+    }
+
+    #[test]
+    fn expr_range_when_result_leaves_domain_then_none() {
+        let expr = binary(
+            Operator::Add,
+            ExprKind::named_variable("i"),
+            ExprKind::integer_literal("1"),
+        );
+        let leaf = |_: &Expr| Some(DINT);
+        assert_eq!(expr_range(&expr, DINT, &leaf), None);
+    }
+
+    #[test]
+    fn binary_range_when_i128_overflows_then_none() {
+        let huge = ValueRange::exact(i128::MAX);
+        assert_eq!(binary_range(&Operator::Add, huge, huge), None);
+        assert_eq!(binary_range(&Operator::Mul, huge, huge), None);
+        assert_eq!(
+            binary_range(
+                &Operator::Div,
+                ValueRange::exact(i128::MIN),
+                ValueRange::exact(-1)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn expr_range_when_power_then_none() {
+        let expr = binary(
+            Operator::Pow,
+            ExprKind::integer_literal("2"),
+            ExprKind::integer_literal("3"),
+        );
+        assert_eq!(expr_range(&expr, DINT, &no_leaf), None);
+    }
+
+    #[test]
+    fn contains_when_inner_range_then_true() {
+        assert!(ValueRange::new(0, 10).contains(&ValueRange::new(2, 10)));
+        assert!(!ValueRange::new(0, 10).contains(&ValueRange::new(-1, 5)));
+    }
+}
@@ -113,10 +113,7 @@ impl Fold<Diagnostic> for NamedToPositionalResolver<'_> {
         // 4. Walk declared input parameters in order, removing from the map
         //    to produce positional arguments in declaration order.
         let mut positional_args: Vec<ParamAssignmentKind> = vec![];
-        for param in &signature.parameters {
-            if !param.is_input_compatible() {
-                continue;
-            }
+        for param in signature.input_parameters() {
             if let Some(ni) = named_map.remove(&param.name) {
                 let folded_expr = self.fold_expr(ni.expr)?;
                 positional_args.push(ParamAssignmentKind::PositionalInput(PositionalInput {
@@ -11,7 +11,7 @@
 use ironplc_dsl::{
     common::{
         AddressAssignment, InitialValueAssignmentKind, Library, LocationPrefix, SizePrefix,
-        TypeReference, VariableType,
+        StringType, TypeReference, VariableType,
     },
     core::{Id, Located},
     diagnostic::Diagnostic,
@@ -62,6 +62,37 @@ struct EnvironmentResolver<'a> {
     scope: Option<Id>,
 }
 
+/// The name of the type a variable declaration states, when it names one.
+///
+/// Declarations that define their type inline (enumerated values, arrays,
+/// subranges and references) have no name to record.
+fn declared_type_name(initializer: &InitialValueAssignmentKind) -> Option<String> {
+    let type_name = match initializer {
+        InitialValueAssignmentKind::Simple(init) => &init.type_name,
+        InitialValueAssignmentKind::EnumeratedType(init) => &init.type_name,
+        InitialValueAssignmentKind::FunctionBlock(init) => &init.type_name,
+        InitialValueAssignmentKind::FunctionBlockCall(init) => &init.type_name,
+        InitialValueAssignmentKind::Structure(init) => &init.type_name,
+        InitialValueAssignmentKind::LateResolvedType(type_name) => type_name,
+        InitialValueAssignmentKind::String(init) => {
+            return Some(
+                match init.width {
+                    StringType::String => "STRING",
+                    StringType::WString => "WSTRING",
+                }
+                .to_string(),
+            )
+        }
+        InitialValueAssignmentKind::None(_)
+        | InitialValueAssignmentKind::EnumeratedValues(_)
+        | InitialValueAssignmentKind::Subrange(_)
+        | InitialValueAssignmentKind::Array(_)
+        | InitialValueAssignmentKind::Reference(_)
+        | InitialValueAssignmentKind::SimpleExpr(_) => return None,
+    };
+    Some(type_name.name.original().to_string())
+}
+
 impl<'a> EnvironmentResolver<'a> {
     fn current_scope(&self) -> ScopeKind {
         match &self.scope {
@@ -94,6 +125,7 @@ impl<'a> Visitor<Diagnostic> for EnvironmentResolver<'a> {
                     symbol_kind,
                     &self.current_scope(),
                     node.var_type.clone(),
+                    declared_type_name(&node.initializer),
                     None,
                 )?;
             }
@@ -105,6 +137,7 @@ impl<'a> Visitor<Diagnostic> for EnvironmentResolver<'a> {
                         symbol_kind,
                         &self.current_scope(),
                         node.var_type.clone(),
+                        declared_type_name(&node.initializer),
                         Some(address),
                     )?;
                 }
@@ -440,6 +473,31 @@ END_FUNCTION_BLOCK";
         assert_eq!(counter_symbol.kind, SymbolKind::FunctionBlock);
     }
 
+    #[test]
+    fn apply_when_variable_names_type_then_records_data_type() {
+        let program = "
+FUNCTION_BLOCK Counter
+VAR_INPUT
+    Count : INT;
+    Ticks : ARRAY[1..2] OF INT;
+END_VAR
+END_FUNCTION_BLOCK";
+
+        let library = parse_and_resolve_types(program);
+        let mut symbol_env = SymbolEnvironment::new();
+        let mut function_env = FunctionEnvironment::new();
+        apply_impl(&library, &mut symbol_env, &mut function_env).unwrap();
+
+        let scope = ScopeKind::Named(Id::from("Counter"));
+        let count = symbol_env.get(&Id::from("Count"), &scope).unwrap();
+        assert_eq!(
+            count.data_type.as_deref().map(TypeName::from),
+            Some(TypeName::from("INT"))
+        );
+        let ticks = symbol_env.get(&Id::from("Ticks"), &scope).unwrap();
+        assert_eq!(ticks.data_type, None);
+    }
+
     #[test]
     fn apply_when_function_declaration_then_populates_function_environment() {
         let program = "
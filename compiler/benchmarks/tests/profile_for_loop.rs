@@ -115,6 +115,8 @@ fn opcode_name(op: u8) -> &'static str {
         opcode::JMP_IF_NOT => "JMP_IF_NOT",
         opcode::CMP_BR_I32 => "CMP_BR_I32",
         opcode::CMP_BR_I64 => "CMP_BR_I64",
+        opcode::RANGE_CHECK_I32 => "RANGE_CHECK_I32",
+        opcode::RANGE_CHECK_I64 => "RANGE_CHECK_I64",
         opcode::CALL => "CALL",
         opcode::RET => "RET",
         opcode::RET_VOID => "RET_VOID",
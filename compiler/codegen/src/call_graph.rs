@@ -10,6 +10,11 @@
 //! reject containers that wouldn't fit in the embedder's frame buffer
 //! before any init code runs (`Trap::ProgramExceedsCallDepth`).
 //!
+//! The same walk orders functions callee-first for the worst-case
+//! execution time analysis in [`crate::wcet`], which costs each function
+//! after the functions it calls, and finds the deepest call chain that
+//! [`crate::target`] names when a program exceeds a target's call depth.
+//!
 //! Cycles are forbidden by IEC 61131-3 and rejected earlier by semantic
 //! analysis (`Problem::RecursiveCycle`). This module's 3-color DFS is
 //! a defensive backstop: if a cycle ever slips through, the longest-path
@@ -33,6 +38,63 @@ pub(crate) fn compute_max_call_depth(
     graph: &HashMap<FunctionId, HashSet<FunctionId>>,
     entry: FunctionId,
 ) -> Result<u16, Diagnostic> {
+    let depth = call_depths(graph, entry)?;
+    Ok(*depth.get(&entry).unwrap_or(&1))
+}
+
+/// Returns the longest call chain from `entry`, starting with `entry`
+/// itself: the path whose length [`compute_max_call_depth`] returns. Of
+/// callees equally deep, the one with the lowest id is followed.
+///
+/// Returns `InternalError` on cycle, as [`compute_max_call_depth`] does.
+pub(crate) fn deepest_call_chain(
+    graph: &HashMap<FunctionId, HashSet<FunctionId>>,
+    entry: FunctionId,
+) -> Result<Vec<FunctionId>, Diagnostic> {
+    let depth = call_depths(graph, entry)?;
+    let mut chain = vec![entry];
+    let mut node = entry;
+    while let Some(next) = graph
+        .get(&node)
+        .into_iter()
+        .flatten()
+        .copied()
+        .max_by_key(|child| (depth[child], std::cmp::Reverse(child.raw())))
+    {
+        chain.push(next);
+        node = next;
+    }
+    Ok(chain)
+}
+
+/// Computes the call depth of `entry` and every function reachable from
+/// it, counting the function itself.
+fn call_depths(
+    graph: &HashMap<FunctionId, HashSet<FunctionId>>,
+    entry: FunctionId,
+) -> Result<HashMap<FunctionId, u16>, Diagnostic> {
+    let mut depth: HashMap<FunctionId, u16> = HashMap::new();
+    for node in callees_first(graph, entry)? {
+        let max_child_depth = graph
+            .get(&node)
+            .into_iter()
+            .flatten()
+            .map(|child| depth[child])
+            .max()
+            .unwrap_or(0);
+        depth.insert(node, max_child_depth.saturating_add(1));
+    }
+    Ok(depth)
+}
+
+/// Lists `entry` and every function reachable from it, each after all of
+/// its callees, so a bottom-up analysis can visit them in order.
+///
+/// Returns `InternalError` on cycle, as [`compute_max_call_depth`] does.
+pub(crate) fn callees_first(
+    graph: &HashMap<FunctionId, HashSet<FunctionId>>,
+    entry: FunctionId,
+) -> Result<Vec<FunctionId>, Diagnostic> {
     // 3-color DFS: White (not visited), Gray (on current path),
     // Black (fully explored).
     #[derive(Clone, Copy, PartialEq, Eq)]
@@ -42,30 +104,34 @@ pub(crate) fn compute_max_call_depth(
         Black,
     }
 
-    /// Per-node DFS frame: the node, its enumerated children, the
-    /// next-child cursor, and the deepest depth seen so far among
-    /// children already finalized.
+    /// Per-node DFS frame: the node, its enumerated children, and the
+    /// next-child cursor.
     struct Frame {
         node: FunctionId,
         children: Vec<FunctionId>,
         next_child: usize,
-        max_child_depth: u16,
     }
 
+    let children_of = |node: &FunctionId| -> Vec<FunctionId> {
+        let mut children: Vec<FunctionId> = graph
+            .get(node)
+            .map(|s| s.iter().copied().collect())
+            .unwrap_or_default();
+        // Sort so the order does not depend on hash iteration order.
+        children.sort_by_key(|child| child.raw());
+        children
+    };
+
     let mut color: HashMap<FunctionId, Color> = HashMap::new();
-    let mut depth: HashMap<FunctionId, u16> = HashMap::new();
+    let mut order: Vec<FunctionId> = Vec::new();
     // Iterative DFS with an explicit work stack so a deep call graph
     // doesn't blow the Rust thread stack.
     let mut stack: Vec<Frame> = Vec::new();
     color.insert(entry, Color::Gray);
     stack.push(Frame {
         node: entry,
-        children: graph
-            .get(&entry)
-            .map(|s| s.iter().copied().collect())
-            .unwrap_or_default(),
+        children: children_of(&entry),
         next_child: 0,
-        max_child_depth: 0,
     });
 
     while let Some(top) = stack.last_mut() {
@@ -86,42 +152,26 @@ pub(crate) fn compute_max_call_depth(
                         ),
                     )));
                 }
-                Color::Black => {
-                    let d = depth[&child];
-                    if d > top.max_child_depth {
-                        top.max_child_depth = d;
-                    }
-                }
+                Color::Black => {}
                 Color::White => {
                     color.insert(child, Color::Gray);
-                    let next_children: Vec<FunctionId> = graph
-                        .get(&child)
-                        .map(|s| s.iter().copied().collect())
-                        .unwrap_or_default();
                     stack.push(Frame {
                         node: child,
-                        children: next_children,
+                        children: children_of(&child),
                         next_child: 0,
-                        max_child_depth: 0,
                     });
                 }
             }
         } else {
             // All children explored; finalize this node.
-            let d = top.max_child_depth.saturating_add(1);
             let node = top.node;
-            depth.insert(node, d);
             color.insert(node, Color::Black);
+            order.push(node);
             stack.pop();
-            if let Some(parent) = stack.last_mut() {
-                if d > parent.max_child_depth {
-                    parent.max_child_depth = d;
-                }
-            }
         }
     }
 
-    Ok(*depth.get(&entry).unwrap_or(&1))
+    Ok(order)
 }
 
 #[cfg(test)]
@@ -196,4 +246,55 @@ mod tests {
         let err = compute_max_call_depth(&g, FunctionId::new(1)).unwrap_err();
         assert_eq!(err.code, "P9998");
     }
+
+    #[test]
+    fn callees_first_when_diamond_then_every_callee_before_its_callers() {
+        // 1 -> {2, 3}, 2 -> 4, 3 -> 4
+        let g = graph(&[(1, 2), (1, 3), (2, 4), (3, 4)]);
+        let order = callees_first(&g, FunctionId::new(1)).unwrap();
+        let position = |id: u16| {
+            order
+                .iter()
+                .position(|f| *f == FunctionId::new(id))
+                .unwrap()
+        };
+        assert_eq!(order.len(), 4);
+        assert!(position(4) < position(2));
+        assert!(position(4) < position(3));
+        assert!(position(2) < position(1));
+        assert!(position(3) < position(1));
+    }
+
+    #[test]
+    fn callees_first_when_cycle_then_internal_error() {
+        let g = graph(&[(1, 2), (2, 1)]);
+        let err = callees_first(&g, FunctionId::new(1)).unwrap_err();
+        assert_eq!(err.code, "P9998");
+    }
+
+    #[test]
+    fn deepest_call_chain_when_entry_has_no_callees_then_entry_only() {
+        let g = graph(&[]);
+        let chain = deepest_call_chain(&g, FunctionId::new(1)).unwrap();
+        assert_eq!(chain, vec![FunctionId::new(1)]);
+    }
+
+    #[test]
+    fn deepest_call_chain_when_two_chains_then_follows_longer() {
+        // 1 -> 2, 1 -> 3 -> 4
+        let g = graph(&[(1, 2), (1, 3), (3, 4)]);
+        let chain = deepest_call_chain(&g, FunctionId::new(1)).unwrap();
+        assert_eq!(
+            chain,
+            vec![FunctionId::new(1), FunctionId::new(3), FunctionId::new(4)]
+        );
+    }
+
+    #[test]
+    fn deepest_call_chain_when_tie_then_follows_lowest_id() {
+        // 1 -> {3, 2}, both leaves
+        let g = graph(&[(1, 3), (1, 2)]);
+        let chain = deepest_call_chain(&g, FunctionId::new(1)).unwrap();
+        assert_eq!(chain, vec![FunctionId::new(1), FunctionId::new(2)]);
+    }
 }
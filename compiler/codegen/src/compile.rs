@@ -53,10 +53,11 @@ use ironplc_dsl::common::{
     LibraryElementKind, ProgramDeclaration, StringType, VarDecl, VariableType,
 };
 use ironplc_dsl::configuration::{ConfigurationDeclaration, TaskConfiguration};
-use ironplc_dsl::core::{FileId, Id, Located};
+use ironplc_dsl::core::{FileId, Id, Located, SourceSpan};
 use ironplc_dsl::diagnostic::{Diagnostic, Label};
 use ironplc_problems::Problem;
 
+use ironplc_analyzer::value_range::ValueRange;
 use ironplc_analyzer::{FunctionEnvironment, SemanticContext, TypeEnvironment};
 
 use crate::emit::Emitter;
@@ -204,11 +205,19 @@ pub(crate) fn emit_string_literal_load(
 /// Returns an error if no program is found or if the program contains
 /// unsupported constructs.
 /// Options that affect code generation.
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
 pub struct CodegenOptions {
     /// When `true`, inject `__SYSTEM_UP_TIME` (TIME) and `__SYSTEM_UP_LTIME`
     /// (LTIME) as implicit globals at the start of the variable table.
     pub system_uptime_global: bool,
+    /// When `true`, omit the runtime range check on a store into a subrange
+    /// or enumeration target when value-range analysis proves the stored
+    /// value is in range, and the array bounds check on an element access
+    /// whose subscripts are proven to be in bounds.
+    pub elide_proven_range_checks: bool,
+    /// When set, reject a program that needs more resources than the
+    /// target profile allows.
+    pub target: Option<crate::target::TargetProfile>,
 }
 
 pub fn compile(
@@ -273,7 +282,7 @@ pub fn compile(
 
     let enum_map = crate::compile_enum::build_enum_ordinal_map(library);
 
-    let mut container = compile_program_with_functions(
+    let (mut container, contributors) = compile_program_with_functions(
         ProgramInputs {
             program,
             func_decls: &func_decls,
@@ -283,6 +292,7 @@ pub fn compile(
         context.functions(),
         context.types(),
         enum_map,
+        options,
         sources,
     )?;
 
@@ -294,6 +304,10 @@ pub fn compile(
         apply_task_configuration(&mut container, config, &program.name)?;
     }
 
+    if let Some(target) = &options.target {
+        crate::target::check_fits(&container, target, &program.name, &contributors)?;
+    }
+
     Ok(container)
 }
 
@@ -458,6 +472,29 @@ fn add_line_map_entries(
     builder
 }
 
+/// Pushes a function's loops (already remapped through the optimizer) into
+/// the container builder, paired with the function's `FunctionId`.
+fn add_loop_bounds(
+    mut builder: ContainerBuilder,
+    function_id: FunctionId,
+    loops: &[crate::emit::EmittedLoop],
+) -> ContainerBuilder {
+    for lp in loops {
+        let (file_id, source_line, source_column) = lp.position.unwrap_or_default();
+        builder = builder.add_loop_bound(ironplc_container::LoopBoundEntry {
+            function_id,
+            start_offset: lp.start_offset,
+            end_offset: lp.end_offset,
+            kind: lp.kind,
+            max_iterations: lp.max_iterations,
+            file_id,
+            source_line,
+            source_column,
+        });
+    }
+    builder
+}
+
 /// Holds the compiled bytecode and metadata for a user-defined function.
 pub(crate) struct CompiledFunction {
     pub(crate) function_id: FunctionId,
@@ -471,6 +508,8 @@ pub(crate) struct CompiledFunction {
     /// entry with this function's `FunctionId` before pushing to the
     /// container builder.
     pub(crate) line_map: Vec<crate::emit::EmittedLineMapEntry>,
+    /// Loops with offsets already remapped through the optimizer.
+    pub(crate) loops: Vec<crate::emit::EmittedLoop>,
 }
 
 /// Holds the finalized bytecode and stack depth for a single emitted function.
@@ -489,6 +528,8 @@ pub(crate) struct FinalizedFunction {
     /// elided snap forward to the next surviving instruction; entries
     /// past the new end-of-function are dropped.
     pub(crate) line_map: Vec<crate::emit::EmittedLineMapEntry>,
+    /// Loops with offsets remapped through the same table.
+    pub(crate) loops: Vec<crate::emit::EmittedLoop>,
 }
 
 /// Finalizes an emitter into ready-to-store bytecode plus stack depth.
@@ -497,14 +538,17 @@ pub(crate) struct FinalizedFunction {
 /// peephole optimizer (run inside `bytecode()`) may increase max_stack_depth.
 pub(crate) fn finalize_function(emitter: &mut Emitter, ctx: &CompileContext) -> FinalizedFunction {
     let raw_line_map = emitter.take_line_map();
+    let raw_loops = emitter.take_loops();
     let (bytecode, offset_map) = crate::optimize::optimize(emitter.bytecode(), &ctx.constants);
     let max_stack_depth = emitter.max_stack_depth();
     let line_map =
         crate::optimize::remap_line_map(raw_line_map, &offset_map, bytecode.len() as u16);
+    let loops = crate::optimize::remap_loops(raw_loops, &offset_map);
     FinalizedFunction {
         bytecode,
         max_stack_depth,
         line_map,
+        loops,
     }
 }
 
@@ -533,8 +577,9 @@ fn compile_program_with_functions(
     functions: &FunctionEnvironment,
     types: &TypeEnvironment,
     enum_map: crate::compile_enum::EnumOrdinalMap,
+    options: &CodegenOptions,
     sources: &dyn crate::source_lookup::SourceLookup,
-) -> Result<Container, Diagnostic> {
+) -> Result<(Container, crate::target::Contributors), Diagnostic> {
     let ProgramInputs {
         program,
         func_decls,
@@ -543,6 +588,7 @@ fn compile_program_with_functions(
     } = inputs;
     let mut ctx = CompileContext::new();
     ctx.enum_map = enum_map;
+    ctx.elide_proven_range_checks = options.elide_proven_range_checks;
     let mut builder = ContainerBuilder::new();
 
     // Register every top-level POU's source file with the debug
@@ -564,6 +610,7 @@ fn compile_program_with_functions(
     // Assign global variable indices first (indices 0..G).
     assign_variables(&mut ctx, &mut builder, global_vars, types)?;
     let num_globals = ctx.variables.len() as u16;
+    ctx.num_globals = num_globals;
 
     // Pre-scan user-defined FB declarations to register type metadata
     // (field indices, field op types, type IDs) before assign_variables runs.
@@ -576,6 +623,7 @@ fn compile_program_with_functions(
         let fb_name = fb_decl.name.name.to_string().to_uppercase();
         let mut field_indices: HashMap<String, u8> = HashMap::new();
         let mut field_op_types: HashMap<String, OpType> = HashMap::new();
+        let mut field_ranges = HashMap::new();
         let mut field_decls_tmp: Vec<&VarDecl> = Vec::new();
 
         for decl in &fb_decl.variables {
@@ -597,6 +645,9 @@ fn compile_program_with_functions(
             if let Some(id) = decl.identifier.symbolic_id() {
                 let name = id.to_string().to_lowercase();
                 field_indices.insert(name.clone(), i as u8);
+                if let Some(range) = crate::compile_range::decl_constraint(&ctx, types, decl) {
+                    field_ranges.insert(name.clone(), range);
+                }
                 if let InitialValueAssignmentKind::Simple(simple) = &decl.initializer {
                     if let Some(vti) = resolve_type_name(&simple.type_name.name) {
                         field_op_types.insert(name, (vti.op_width, vti.signedness));
@@ -620,6 +671,7 @@ fn compile_program_with_functions(
                 function_id: FunctionId::new(next_function_id),
                 var_offset: 0, // updated after program vars are assigned
                 field_op_types,
+                field_ranges,
             },
         );
     }
@@ -759,6 +811,7 @@ fn compile_program_with_functions(
         0,
     );
     builder = add_line_map_entries(builder, FunctionId::INIT, &init.line_map);
+    builder = add_loop_bounds(builder, FunctionId::INIT, &init.loops);
 
     let scan = finalize_function(&mut scan_emitter, &ctx);
     builder = builder.add_function(
@@ -772,6 +825,7 @@ fn compile_program_with_functions(
         0,
     );
     builder = add_line_map_entries(builder, FunctionId::SCAN, &scan.line_map);
+    builder = add_loop_bounds(builder, FunctionId::SCAN, &scan.loops);
 
     // Add user-defined function block bodies.
     for compiled in &compiled_fb_bodies {
@@ -783,6 +837,7 @@ fn compile_program_with_functions(
             compiled.num_params,
         );
         builder = add_line_map_entries(builder, compiled.function_id, &compiled.line_map);
+        builder = add_loop_bounds(builder, compiled.function_id, &compiled.loops);
     }
 
     // Add user FB type descriptors to the container.
@@ -805,6 +860,7 @@ fn compile_program_with_functions(
             compiled.num_params,
         );
         builder = add_line_map_entries(builder, compiled.function_id, &compiled.line_map);
+        builder = add_loop_bounds(builder, compiled.function_id, &compiled.loops);
     }
 
     // Add the SOURCE_FILE_TABLE (tag 6). `ctx.debug_source_files`
@@ -875,7 +931,11 @@ fn compile_program_with_functions(
     // `specs/design/bytecode-verifier-rules.md` for the rules it enforces.
     crate::stack_balance::verify_container(&container)?;
 
-    Ok(container)
+    let contributors = crate::target::Contributors {
+        data: ctx.data_allocations,
+        call_graph: ctx.call_graph,
+    };
+    Ok((container, contributors))
 }
 
 #[derive(Clone)]
@@ -951,6 +1011,9 @@ pub(crate) struct UserFbTypeInfo {
     pub(crate) var_offset: u16,
     /// Maps field name (lowercase) to its op type for codegen at call sites.
     pub(crate) field_op_types: HashMap<String, OpType>,
+    /// Maps field name (lowercase) to its declared range, for fields of a
+    /// subrange or enumeration type.
+    pub(crate) field_ranges: HashMap<String, crate::compile_range::RangeConstraint>,
 }
 
 pub(crate) struct CompileContext {
@@ -973,8 +1036,22 @@ pub(crate) struct CompileContext {
     pub(crate) struct_vars: HashMap<Id, crate::compile_struct::StructVarInfo>,
     /// Pre-computed ordinal mappings for named enumeration types.
     pub(crate) enum_map: crate::compile_enum::EnumOrdinalMap,
+    /// Declared range of each subrange- or enumeration-typed variable, keyed
+    /// by variable index so it survives the per-POU scope swaps. For an
+    /// array variable the range applies to its elements.
+    pub(crate) var_ranges: HashMap<VarIndex, crate::compile_range::RangeConstraint>,
+    /// Mirrors `CodegenOptions::elide_proven_range_checks`.
+    pub(crate) elide_proven_range_checks: bool,
+    /// Range of each `FOR` control variable whose loop body is being
+    /// compiled, when the body cannot change it.
+    pub(crate) loop_ranges: HashMap<VarIndex, ValueRange>,
+    /// Number of global variables; they occupy the first variable indices.
+    pub(crate) num_globals: u16,
     /// Next available byte offset in the data region.
     pub(crate) data_region_offset: u32,
+    /// Reservations in the data region, for naming the largest when the
+    /// program does not fit a target profile.
+    pub(crate) data_allocations: Vec<crate::target::DataAllocation>,
     /// Maximum string capacity across all STRING variables (for temp buffer sizing).
     pub(crate) max_string_capacity: u16,
     /// True when any WSTRING (wide) string is declared. Temp buffers are then
@@ -1043,6 +1120,7 @@ impl CompileContext {
             array_vars: HashMap::new(),
             struct_vars: HashMap::new(),
             data_region_offset: 0,
+            data_allocations: Vec::new(),
             max_string_capacity: 0,
             has_wide_string: false,
             num_temp_bufs: 0,
@@ -1053,6 +1131,10 @@ impl CompileContext {
             user_fb_types: HashMap::new(),
             next_user_fb_type_id: 0x1000,
             enum_map: crate::compile_enum::EnumOrdinalMap::default(),
+            var_ranges: HashMap::new(),
+            elide_proven_range_checks: false,
+            loop_ranges: HashMap::new(),
+            num_globals: 0,
             current_function_return: None,
             current_function_id: None,
             call_graph: HashMap::new(),
@@ -1076,6 +1158,25 @@ impl CompileContext {
         }
     }
 
+    /// Reserves `bytes` of the data region for `owner` (`None` for a compiler
+    /// temporary) declared at `span`. Returns the offset of the reservation,
+    /// or `None` when the data region would overflow.
+    pub(crate) fn allocate_data(
+        &mut self,
+        bytes: u32,
+        owner: Option<&Id>,
+        span: &SourceSpan,
+    ) -> Option<u32> {
+        let offset = self.data_region_offset;
+        self.data_region_offset = offset.checked_add(bytes)?;
+        self.data_allocations.push(crate::target::DataAllocation {
+            owner: owner.cloned(),
+            span: span.clone(),
+            bytes,
+        });
+        Some(offset)
+    }
+
     /// Looks up a variable index by identifier, using the provided span for error reporting.
     pub(crate) fn var_index(&self, name: &Id) -> Result<VarIndex, Diagnostic> {
         self.variables.get(name).copied().ok_or_else(|| {
@@ -5,7 +5,7 @@
 //! keep module sizes within the 1000-line guideline.
 
 use ironplc_dsl::common::{
-    ArrayInitialElementKind, ConstantKind, ReferenceInitializer, ReferenceTarget,
+    ArrayInitialElementKind, ConstantKind, ReferenceInitializer, ReferenceTarget, TypeName,
 };
 use ironplc_dsl::core::{Id, Located, SourceSpan};
 use ironplc_dsl::diagnostic::{Diagnostic, Label};
@@ -13,9 +13,12 @@ use ironplc_dsl::textual::{Expr, ExprKind, SymbolicVariableKind, UnaryOp, Variab
 use ironplc_problems::Problem;
 
 use ironplc_analyzer::intermediate_type::{ArrayDimension, ByteSized, IntermediateType};
+use ironplc_analyzer::value_range::ValueRange;
+use ironplc_analyzer::TypeEnvironment;
 use ironplc_container::{CharWidth, ContainerBuilder, SlotIndex, VarIndex};
 
 use super::compile::{CompileContext, OpType, OpWidth, Signedness, VarTypeInfo};
+use super::compile_bounds::subscripts_in_bounds;
 use super::compile_expr::compile_expr;
 use crate::emit::Emitter;
 
@@ -36,6 +39,9 @@ pub(crate) struct ArraySpec {
     /// `Narrow` for STRING, `Wide` for WSTRING. `None` for non-string
     /// element types.
     pub string_char_width: Option<CharWidth>,
+    /// For subrange element types, the declared range of each element.
+    /// `element_type_name` then names the subrange's base type.
+    pub element_range: Option<ValueRange>,
 }
 
 /// Metadata for a single dimension of an array, used for index computation.
@@ -520,9 +526,29 @@ pub(crate) fn array_spec_from_inline(
         ref_to: subranges.ref_to.is_some(),
         string_max_len,
         string_char_width,
+        element_range: None,
     })
 }
 
+/// Rewrites an inline array spec whose element type names a subrange type
+/// to use the subrange's base type, recording the declared element range.
+pub(crate) fn resolve_subrange_element(
+    spec: &mut ArraySpec,
+    types: &TypeEnvironment,
+) -> Result<(), Diagnostic> {
+    if spec.ref_to || spec.string_max_len.is_some() {
+        return Ok(());
+    }
+    let type_name = TypeName::from_id(&spec.element_type_name);
+    if let Some(subrange @ IntermediateType::Subrange { base_type, .. }) =
+        types.resolve_subrange_type(&type_name)
+    {
+        spec.element_type_name = intermediate_type_to_name(base_type)?;
+        spec.element_range = ValueRange::of_subrange(subrange);
+    }
+    Ok(())
+}
+
 /// Converts a named array type (from the TypeEnvironment) to a normalized ArraySpec.
 pub(crate) fn array_spec_from_named(
     element_type: &IntermediateType,
@@ -535,7 +561,13 @@ pub(crate) fn array_spec_from_named(
     } else {
         element_type
     };
-    let element_type_name = intermediate_type_to_name(inner_type)?;
+    let (element_type_name, element_range) = match inner_type {
+        IntermediateType::Subrange { base_type, .. } => (
+            intermediate_type_to_name(base_type)?,
+            ValueRange::of_subrange(inner_type),
+        ),
+        _ => (intermediate_type_to_name(inner_type)?, None),
+    };
     let (string_max_len, string_char_width) = match inner_type {
         IntermediateType::String {
             max_len,
@@ -554,6 +586,7 @@ pub(crate) fn array_spec_from_named(
         ref_to,
         string_max_len,
         string_char_width,
+        element_range,
     })
 }
 
@@ -707,7 +740,6 @@ pub(crate) fn register_array_variable(
     }
 
     // 5. Allocate data region space
-    let data_offset = ctx.data_region_offset;
     let total_bytes = if is_string {
         // STRING/WSTRING elements: each element is [max_len:u16][cur_len:u16][data]
         let element_stride = super::compile::string_region_size(string_max_len, string_char_width);
@@ -717,9 +749,8 @@ pub(crate) fn register_array_variable(
     } else {
         total_elements * 8
     };
-    ctx.data_region_offset = ctx
-        .data_region_offset
-        .checked_add(total_bytes)
+    let data_offset = ctx
+        .allocate_data(total_bytes, Some(id), span)
         .ok_or_else(|| {
             Diagnostic::not_implemented(Label::span(span.clone(), "Data region overflow"))
         })?;
@@ -754,6 +785,12 @@ pub(crate) fn register_array_variable(
     }
 
     // 9. Store in context
+    if let Some(range) = spec.element_range {
+        ctx.var_ranges.insert(
+            var_index,
+            crate::compile_range::RangeConstraint::subrange(range),
+        );
+    }
     ctx.array_vars.insert(
         id.clone(),
         ArrayVarInfo {
@@ -895,13 +932,16 @@ pub(crate) fn flatten_array_initial_values(
 /// For constant subscripts, computes the flat index at compile time.
 /// For variable subscripts, emits i64 arithmetic:
 ///   `(s_0 - l_0) * stride_0 + (s_1 - l_1) * stride_1 + ...`
+///
+/// Returns whether every subscript is proven to be in bounds, and `Err`
+/// when one is proven to be out of bounds.
 pub(crate) fn emit_flat_index(
     emitter: &mut Emitter,
     ctx: &mut CompileContext,
     subscripts: &[&Expr],
     dimensions: &[DimensionInfo],
     span: &SourceSpan,
-) -> Result<(), Diagnostic> {
+) -> Result<bool, Diagnostic> {
     if subscripts.len() != dimensions.len() {
         return Err(Diagnostic::not_implemented(Label::span(
             span.clone(),
@@ -913,8 +953,9 @@ pub(crate) fn emit_flat_index(
     if let Some(flat_index) = try_constant_flat_index(subscripts, dimensions, span)? {
         let const_index = ctx.add_i32_constant(flat_index);
         emitter.emit_load_const_i32(const_index);
-        return Ok(());
+        return Ok(true);
     }
+    let in_bounds = subscripts_in_bounds(ctx, subscripts, dimensions)?;
 
     // Variable case: emit runtime computation using i64 arithmetic.
     let subscript_op_type = (OpWidth::W32, Signedness::Signed);
@@ -934,7 +975,7 @@ pub(crate) fn emit_flat_index(
             emitter.emit_add_i64();
         }
     }
-    Ok(())
+    Ok(in_bounds)
 }
 
 /// Tries to compute the flat index at compile time when all subscripts are literals.
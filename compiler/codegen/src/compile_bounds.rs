@@ -0,0 +1,262 @@
+//! Compile-time array bounds proofs.
+//!
+//! The value-range analysis bounds each array subscript by the values it can
+//! take: literals, `LIMIT`, `MIN` and `MAX`, the declared subrange or storage
+//! width of a variable, and the range of a `FOR` control variable inside its
+//! loop. A subscript whose range lies entirely outside its dimension can
+//! never index the array and is reported as `ArrayIndexOutOfBounds` (P2027).
+//! When every subscript lies inside its dimension the access cannot trap,
+//! and with `CodegenOptions::elide_proven_range_checks` it is emitted as
+//! `LOAD_ARRAY_UNCHECKED` or `STORE_ARRAY_UNCHECKED`.
+//!
+//! See `specs/plans/2026-10-18-array-bounds-proofs.md`.
+
+use ironplc_analyzer::value_range::ValueRange;
+use ironplc_container::VarIndex;
+use ironplc_dsl::core::{Id, Located};
+use ironplc_dsl::diagnostic::{Diagnostic, Label};
+use ironplc_dsl::textual::*;
+use ironplc_dsl::visitor::Visitor;
+use ironplc_problems::Problem;
+
+use super::compile::{CompileContext, OpWidth, Signedness};
+use super::compile_array::DimensionInfo;
+use super::compile_range::proven_range;
+use crate::emit::Emitter;
+
+/// Returns whether every subscript is proven to lie within its dimension.
+///
+/// Returns `Err` when a subscript is proven to lie outside its dimension.
+pub(crate) fn subscripts_in_bounds(
+    ctx: &CompileContext,
+    subscripts: &[&Expr],
+    dimensions: &[DimensionInfo],
+) -> Result<bool, Diagnostic> {
+    let mut in_bounds = true;
+    for (subscript, dim) in subscripts.iter().zip(dimensions) {
+        let bounds = ValueRange::new(
+            dim.lower_bound.into(),
+            i128::from(dim.lower_bound) + i128::from(dim.size) - 1,
+        );
+        let Some(range) = proven_range(ctx, subscript, (OpWidth::W32, Signedness::Signed)) else {
+            in_bounds = false;
+            continue;
+        };
+        if range.max < bounds.min || range.min > bounds.max {
+            let values = if range.min == range.max {
+                format!("{}", range.min)
+            } else {
+                format!("in {}..{}", range.min, range.max)
+            };
+            return Err(Diagnostic::problem(
+                Problem::ArrayIndexOutOfBounds,
+                Label::span(
+                    subscript.span(),
+                    format!(
+                        "Array index is always {values}, outside the bounds {}..{}",
+                        bounds.min, bounds.max
+                    ),
+                ),
+            ));
+        }
+        in_bounds &= bounds.contains(&range);
+    }
+    Ok(in_bounds)
+}
+
+/// Emits the load of an array element whose flat index is on the stack.
+pub(crate) fn emit_load_array_element(
+    emitter: &mut Emitter,
+    ctx: &CompileContext,
+    var_index: VarIndex,
+    desc_index: u16,
+    in_bounds: bool,
+) {
+    if in_bounds && ctx.elide_proven_range_checks {
+        emitter.emit_load_array_unchecked(var_index, desc_index);
+    } else {
+        emitter.emit_load_array(var_index, desc_index);
+    }
+}
+
+/// Emits the store of the value below the flat index on the stack into an
+/// array element.
+pub(crate) fn emit_store_array_element(
+    emitter: &mut Emitter,
+    ctx: &CompileContext,
+    var_index: VarIndex,
+    desc_index: u16,
+    in_bounds: bool,
+) {
+    if in_bounds && ctx.elide_proven_range_checks {
+        emitter.emit_store_array_unchecked(var_index, desc_index);
+    } else {
+        emitter.emit_store_array(var_index, desc_index);
+    }
+}
+
+/// Returns the range the control variable of `for_stmt` stays in while the
+/// loop body runs, or `None` when it cannot be bounded or the body may
+/// change it.
+///
+/// The body runs only while the control variable has not passed `TO`, so
+/// with a positive step it lies between the smallest `FROM` and the largest
+/// `TO`, and the reverse with a negative step. An empty loop has no range.
+pub(crate) fn for_control_range(ctx: &CompileContext, for_stmt: &For) -> Option<ValueRange> {
+    let op_type = ctx.var_op_type(&for_stmt.control);
+    let from = proven_range(ctx, &for_stmt.from, op_type)?;
+    let to = proven_range(ctx, &for_stmt.to, op_type)?;
+    let step = match &for_stmt.step {
+        Some(step) => proven_range(ctx, step, op_type),
+        None => Some(ValueRange::exact(1)),
+    };
+    let range = match step {
+        Some(step) if step.min > 0 => ValueRange::new(from.min, to.max),
+        Some(step) if step.max < 0 => ValueRange::new(to.min, from.max),
+        _ => ValueRange::new(from.min.min(to.min), from.max.max(to.max)),
+    };
+    if range.min > range.max || body_may_write(ctx, for_stmt) {
+        return None;
+    }
+    Some(range)
+}
+
+/// Returns whether the body of `for_stmt` may change its control variable.
+pub(crate) fn body_may_write(ctx: &CompileContext, for_stmt: &For) -> bool {
+    let is_global = ctx
+        .variables
+        .get(&for_stmt.control)
+        .is_none_or(|var_index| var_index.raw() < ctx.num_globals);
+    let mut finder = ControlWrites {
+        ctx,
+        control: &for_stmt.control,
+        is_global,
+        writing: false,
+        in_user_call: false,
+        found: false,
+    };
+    for stmt in &for_stmt.body {
+        let _ = finder.visit_stmt_kind(stmt);
+    }
+    finder.found
+}
+
+/// Looks for a statement that may write the control variable: an
+/// assignment or output to it, a nested loop over it, a reference to it,
+/// passing it to a user function or function block (which may take it
+/// `VAR_IN_OUT`) and, for a global, any user call at all.
+struct ControlWrites<'a> {
+    ctx: &'a CompileContext,
+    control: &'a Id,
+    is_global: bool,
+    writing: bool,
+    in_user_call: bool,
+    found: bool,
+}
+
+impl ControlWrites<'_> {
+    fn with_writing(&mut self, writing: bool, f: impl FnOnce(&mut Self) -> Result<(), ()>) {
+        let saved = self.writing;
+        self.writing = writing;
+        let _ = f(self);
+        self.writing = saved;
+    }
+
+    fn with_user_call(&mut self, user_call: bool, f: impl FnOnce(&mut Self) -> Result<(), ()>) {
+        if user_call && self.is_global {
+            self.found = true;
+        }
+        let saved = self.in_user_call;
+        self.in_user_call = user_call;
+        let _ = f(self);
+        self.in_user_call = saved;
+    }
+
+    fn argument(&mut self, expr: &Expr) {
+        if let ExprKind::Variable(Variable::Symbolic(SymbolicVariableKind::Named(named))) =
+            &expr.kind
+        {
+            if self.in_user_call && named.name == *self.control {
+                self.found = true;
+            }
+        }
+    }
+}
+
+impl Visitor<()> for ControlWrites<'_> {
+    type Value = ();
+
+    fn visit_assignment(&mut self, node: &Assignment) -> Result<(), ()> {
+        self.with_writing(true, |v| v.visit_variable(&node.target));
+        self.visit_expr(&node.value)
+    }
+
+    fn visit_output(&mut self, node: &Output) -> Result<(), ()> {
+        self.with_writing(true, |v| v.visit_variable(&node.tgt));
+        Ok(())
+    }
+
+    fn visit_for(&mut self, node: &For) -> Result<(), ()> {
+        if node.control == *self.control {
+            self.found = true;
+        }
+        node.recurse_visit(self)
+    }
+
+    fn visit_array_variable(&mut self, node: &ArrayVariable) -> Result<(), ()> {
+        self.visit_symbolic_variable_kind(&node.subscripted_variable)?;
+        self.with_writing(false, |v| {
+            for subscript in &node.subscripts {
+                v.visit_expr(subscript)?;
+            }
+            Ok(())
+        });
+        Ok(())
+    }
+
+    fn visit_named_variable(&mut self, node: &NamedVariable) -> Result<(), ()> {
+        if self.writing && node.name == *self.control {
+            self.found = true;
+        }
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, node: &Expr) -> Result<(), ()> {
+        match &node.kind {
+            ExprKind::Ref(variable) => {
+                self.with_writing(true, |v| v.visit_variable(variable));
+                Ok(())
+            }
+            _ => {
+                self.with_writing(false, |v| node.recurse_visit(v));
+                Ok(())
+            }
+        }
+    }
+
+    fn visit_fb_call(&mut self, node: &FbCall) -> Result<(), ()> {
+        self.with_user_call(true, |v| node.recurse_visit(v));
+        Ok(())
+    }
+
+    fn visit_method_call(&mut self, node: &MethodCall) -> Result<(), ()> {
+        self.with_user_call(true, |v| node.recurse_visit(v));
+        Ok(())
+    }
+
+    fn visit_function(&mut self, node: &Function) -> Result<(), ()> {
+        let user_call = self.ctx.user_functions.contains_key(node.name.lower_case());
+        self.with_user_call(user_call, |v| node.recurse_visit(v));
+        Ok(())
+    }
+
+    fn visit_positional_input(&mut self, node: &PositionalInput) -> Result<(), ()> {
+        self.argument(&node.expr);
+        node.recurse_visit(self)
+    }
+
+    fn visit_named_input(&mut self, node: &NamedInput) -> Result<(), ()> {
+        self.argument(&node.expr);
+        node.recurse_visit(self)
+    }
+}
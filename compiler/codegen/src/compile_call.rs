@@ -6,7 +6,7 @@
 
 use std::collections::HashMap;
 
-use ironplc_container::opcode;
+use ironplc_container::{opcode, VarIndex};
 use ironplc_dsl::core::{Id, Located};
 use ironplc_dsl::diagnostic::Diagnostic;
 use ironplc_dsl::textual::{
@@ -17,8 +17,8 @@ use super::compile::{
     CompileContext, OpType, OpWidth, Signedness, UserFunctionInfo, VarTypeInfo, DEFAULT_OP_TYPE,
 };
 use super::compile_expr::{
-    compile_expr, emit_add, emit_and, emit_div, emit_eq, emit_ge, emit_gt, emit_le, emit_lt,
-    emit_mod, emit_mul, emit_ne, emit_or, emit_sub, emit_truncation, emit_xor, op_type,
+    compile_expr, emit_add, emit_and, emit_bit_mask, emit_div, emit_eq, emit_ge, emit_gt, emit_le,
+    emit_lt, emit_mod, emit_mul, emit_ne, emit_or, emit_sub, emit_truncation, emit_xor, op_type,
     op_type_from_expr, storage_bits,
 };
 use super::compile_setup::resolve_type_name;
@@ -179,9 +179,9 @@ pub(crate) fn compile_function_call(
         }
         "mux" => compile_mux(emitter, ctx, func, op_type),
         // Arithmetic function forms (equivalent to +, -, *, /, MOD operators)
-        "add" => compile_two_arg_operator(emitter, ctx, func, op_type, emit_add),
-        "sub" => compile_two_arg_operator(emitter, ctx, func, op_type, emit_sub),
-        "mul" => compile_two_arg_operator(emitter, ctx, func, op_type, emit_mul),
+        "add" => compile_two_arg_arith(emitter, ctx, func, op_type, emit_add),
+        "sub" => compile_two_arg_arith(emitter, ctx, func, op_type, emit_sub),
+        "mul" => compile_two_arg_arith(emitter, ctx, func, op_type, emit_mul),
         "div" => compile_two_arg_operator(emitter, ctx, func, op_type, emit_div),
         "mod" => compile_two_arg_operator(emitter, ctx, func, op_type, emit_mod),
         // Comparison function forms (equivalent to >, >=, =, <=, <, <> operators)
@@ -216,14 +216,14 @@ pub(crate) fn compile_function_call(
         "mid" => compile_mid(emitter, ctx, func),
         "concat" => compile_concat(emitter, ctx, func),
         // Time functions — Group 1: direct i32 operations (same units)
-        "add_time" | "add_tod_time" => compile_two_arg_operator(
+        "add_time" | "add_tod_time" => compile_two_arg_arith(
             emitter,
             ctx,
             func,
             (OpWidth::W32, Signedness::Signed),
             emit_add,
         ),
-        "sub_time" | "sub_tod_time" | "sub_tod_tod" => compile_two_arg_operator(
+        "sub_time" | "sub_tod_time" | "sub_tod_tod" => compile_two_arg_arith(
             emitter,
             ctx,
             func,
@@ -335,6 +335,15 @@ fn compile_user_function_call(
             } else {
                 compile_expr(emitter, ctx, arg, param_op_type)?;
             }
+            // Parameters occupy the first slots of the function's region.
+            let param_index = VarIndex::new(func_info.var_offset.raw() + i as u16);
+            crate::compile_range::emit_var_range_check(
+                emitter,
+                ctx,
+                param_index,
+                param_op_type,
+                Some(arg),
+            );
         }
     }
 
@@ -433,6 +442,22 @@ fn compile_two_arg_operator(
     Ok(())
 }
 
+/// Compiles the ADD, SUB and MUL function forms, whose result has the
+/// width of the first argument.
+fn compile_two_arg_arith(
+    emitter: &mut Emitter,
+    ctx: &mut CompileContext,
+    func: &Function,
+    op_type: OpType,
+    emit_fn: fn(&mut Emitter, OpType, u8),
+) -> Result<(), Diagnostic> {
+    let (in1, in2) = extract_two_positional_args(func)?;
+    compile_expr(emitter, ctx, in1, op_type)?;
+    compile_expr(emitter, ctx, in2, op_type)?;
+    emit_fn(emitter, op_type, storage_bits(in1).unwrap_or(32));
+    Ok(())
+}
+
 /// Extracts two positional input arguments from a function call.
 fn extract_two_positional_args(func: &Function) -> Result<(&Expr, &Expr), Diagnostic> {
     let args: Vec<&Expr> = func
@@ -459,7 +484,7 @@ fn compile_dt_time_add_sub(
     emitter: &mut Emitter,
     ctx: &mut CompileContext,
     func: &Function,
-    emit_fn: fn(&mut Emitter, OpType),
+    emit_fn: fn(&mut Emitter, OpType, u8),
 ) -> Result<(), Diagnostic> {
     let (in1, in2) = extract_two_positional_args(func)?;
     let op_type = (OpWidth::W32, Signedness::Signed);
@@ -468,7 +493,7 @@ fn compile_dt_time_add_sub(
     let pool_idx = ctx.add_i32_constant(1000);
     emitter.emit_load_const_i32(pool_idx);
     emit_div(emitter, op_type);
-    emit_fn(emitter, op_type);
+    emit_fn(emitter, op_type, 32);
     Ok(())
 }
 
@@ -485,10 +510,10 @@ fn compile_sub_to_time(
     let op_type = (OpWidth::W32, Signedness::Signed);
     compile_expr(emitter, ctx, in1, op_type)?;
     compile_expr(emitter, ctx, in2, op_type)?;
-    emit_sub(emitter, op_type);
+    emit_sub(emitter, op_type, 32);
     let pool_idx = ctx.add_i32_constant(1000);
     emitter.emit_load_const_i32(pool_idx);
-    emit_mul(emitter, op_type);
+    emit_mul(emitter, op_type, 32);
     Ok(())
 }
 
@@ -526,7 +551,7 @@ fn compile_dt_to_date(
     // Stack: IN, (IN % 86400)
     emit_mod(emitter, op_type);
     // Stack: IN - (IN % 86400)
-    emit_sub(emitter, op_type);
+    emit_sub(emitter, op_type, 32);
     Ok(())
 }
 
@@ -564,7 +589,7 @@ fn compile_dt_to_tod(
     let ms_per_sec = ctx.add_i32_constant(1000);
     emitter.emit_load_const_i32(ms_per_sec);
     // Stack: (IN % 86400) * 1000
-    emit_mul(emitter, op_type);
+    emit_mul(emitter, op_type, 32);
     Ok(())
 }
 
@@ -590,7 +615,7 @@ fn compile_mul_div_time(
             compile_expr(emitter, ctx, in1, time_op)?;
             compile_expr(emitter, ctx, in2, time_op)?;
             if is_mul {
-                emit_mul(emitter, time_op);
+                emit_mul(emitter, time_op, 32);
             } else {
                 emit_div(emitter, time_op);
             }
@@ -601,7 +626,7 @@ fn compile_mul_div_time(
             compile_expr(emitter, ctx, in2, (OpWidth::F32, Signedness::Signed))?;
             let f32_op = (OpWidth::F32, Signedness::Signed);
             if is_mul {
-                emit_mul(emitter, f32_op);
+                emit_mul(emitter, f32_op, 32);
             } else {
                 emit_div(emitter, f32_op);
             }
@@ -613,7 +638,7 @@ fn compile_mul_div_time(
             compile_expr(emitter, ctx, in2, (OpWidth::F64, Signedness::Signed))?;
             let f64_op = (OpWidth::F64, Signedness::Signed);
             if is_mul {
-                emit_mul(emitter, f64_op);
+                emit_mul(emitter, f64_op, 32);
             } else {
                 emit_div(emitter, f64_op);
             }
@@ -628,7 +653,7 @@ fn compile_mul_div_time(
             emitter.emit_builtin(opcode::builtin::CONV_I64_TO_F64);
             let f64_op = (OpWidth::F64, Signedness::Signed);
             if is_mul {
-                emit_mul(emitter, f64_op);
+                emit_mul(emitter, f64_op, 32);
             } else {
                 emit_div(emitter, f64_op);
             }
@@ -1117,6 +1142,11 @@ fn compile_shift_rotate(
     };
 
     emitter.emit_builtin(func_id);
+    // SHL_I32 shifts bits of a BYTE or WORD past its width; they are dropped,
+    // not an overflow.
+    if name == "shl" && op_type == (OpWidth::W32, Signedness::Unsigned) {
+        emit_bit_mask(emitter, ctx, bits);
+    }
     Ok(())
 }
 
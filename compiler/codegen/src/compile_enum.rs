@@ -135,6 +135,36 @@ pub(crate) fn resolve_enum_default_ordinal(map: &EnumOrdinalMap, type_name: &str
     map.defaults.get(&type_upper).copied().unwrap_or(0)
 }
 
+/// Returns the smallest and largest ordinal of a named enumeration type, or
+/// `None` when the type is unknown.
+///
+/// For enumerations with explicit values the interval may contain ordinals
+/// that name no value; range checks built on it reject only values outside
+/// the declared extremes.
+pub(crate) fn enum_ordinal_range(map: &EnumOrdinalMap, type_name: &str) -> Option<(i32, i32)> {
+    let type_upper = type_name.to_uppercase();
+    map.ordinals
+        .iter()
+        .filter(|((ty, _), _)| *ty == type_upper)
+        .map(|(_, ordinal)| *ordinal)
+        .fold(None, |acc, ordinal| match acc {
+            None => Some((ordinal, ordinal)),
+            Some((lo, hi)) => Some((lo.min(ordinal), hi.max(ordinal))),
+        })
+}
+
+/// Returns the enumeration type name (uppercase) that an `EnumeratedValue`
+/// belongs to, or `None` when the value is unknown.
+pub(crate) fn enum_value_type(map: &EnumOrdinalMap, ev: &EnumeratedValue) -> Option<String> {
+    match &ev.type_name {
+        Some(type_name) => Some(type_name.to_string().to_uppercase()),
+        None => map
+            .value_lookup
+            .get(&ev.value.to_string().to_uppercase())
+            .map(|(type_name, _)| type_name.clone()),
+    }
+}
+
 /// Returns the `VarTypeInfo` for an enumeration variable.
 ///
 /// All enumerations use DINT (W32, Signed, 32-bit) at the codegen level,
@@ -211,6 +241,18 @@ mod tests {
         assert_eq!(map.ordinals.get(&("LEVEL".into(), "HIGH".into())), Some(&1));
     }
 
+    #[test]
+    fn enum_ordinal_range_when_declared_then_spans_ordinals() {
+        let lib = parse_library(
+            "TYPE COLOR : (RED, GREEN, BLUE) := RED; END_TYPE
+             PROGRAM main END_PROGRAM",
+        );
+        let map = build_enum_ordinal_map(&lib);
+
+        assert_eq!(enum_ordinal_range(&map, "color"), Some((0, 2)));
+        assert_eq!(enum_ordinal_range(&map, "LEVEL"), None);
+    }
+
     #[test]
     fn resolve_enum_ordinal_when_unqualified_then_finds_value() {
         let lib = parse_library(
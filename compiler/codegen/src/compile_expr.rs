@@ -135,10 +135,12 @@ pub(crate) fn compile_expr(
         ExprKind::BinaryOp(binary) => {
             compile_expr(emitter, ctx, &binary.left, op_type)?;
             compile_expr(emitter, ctx, &binary.right, op_type)?;
+            // Without a resolved type, assume a full-width result.
+            let bits = storage_bits(expr).unwrap_or(32);
             match binary.op {
-                Operator::Add => emit_add(emitter, op_type),
-                Operator::Sub => emit_sub(emitter, op_type),
-                Operator::Mul => emit_mul(emitter, op_type),
+                Operator::Add => emit_add(emitter, op_type, bits),
+                Operator::Sub => emit_sub(emitter, op_type, bits),
+                Operator::Mul => emit_mul(emitter, op_type, bits),
                 Operator::Div => emit_div(emitter, op_type),
                 Operator::Mod => emit_mod(emitter, op_type),
                 Operator::Pow => emit_pow(emitter, op_type),
@@ -156,11 +158,7 @@ pub(crate) fn compile_expr(
                 match op_type {
                     (OpWidth::W32, Signedness::Unsigned) => {
                         emitter.emit_bit_not_32();
-                        match storage_bits(&unary.term)? {
-                            8 => emitter.emit_trunc_u8(),
-                            16 => emitter.emit_trunc_u16(),
-                            _ => {}
-                        }
+                        emit_bit_mask(emitter, ctx, storage_bits(&unary.term)?);
                     }
                     (OpWidth::W64, Signedness::Unsigned) => emitter.emit_bit_not_64(),
                     _ => emitter.emit_bool_not(),
@@ -738,7 +736,7 @@ pub(crate) fn compile_variable_read(
                         })
                         .collect();
                     let span = variable_span(variable);
-                    crate::compile_array::emit_flat_index(
+                    let in_bounds = crate::compile_array::emit_flat_index(
                         emitter,
                         ctx,
                         &subscripts,
@@ -749,7 +747,13 @@ pub(crate) fn compile_variable_read(
                         ctx.num_temp_bufs += 1;
                         emitter.emit_str_load_array_elem(arr_var_index, arr_desc_index);
                     } else {
-                        emitter.emit_load_array(arr_var_index, arr_desc_index);
+                        crate::compile_bounds::emit_load_array_element(
+                            emitter,
+                            ctx,
+                            arr_var_index,
+                            arr_desc_index,
+                            in_bounds,
+                        );
                     }
                 }
                 crate::compile_array::ResolvedAccess::DerefArrayElement { info, subscripts } => {
@@ -1768,6 +1772,21 @@ pub(crate) fn emit_truncation(emitter: &mut Emitter, type_info: VarTypeInfo) {
     }
 }
 
+/// Keeps the low `bits` bits of a bit-string result such as `NOT` or `SHL`
+/// on a BYTE or WORD. Unlike `TRUNC_U8`/`TRUNC_U16`, the mask ignores the
+/// overflow policy: dropping the high bits is what the bitwise operation
+/// means, not an overflow.
+pub(crate) fn emit_bit_mask(emitter: &mut Emitter, ctx: &mut CompileContext, bits: u8) {
+    let mask = match bits {
+        8 => u8::MAX as i32,
+        16 => u16::MAX as i32,
+        _ => return,
+    };
+    let pool_index = ctx.add_i32_constant(mask);
+    emitter.emit_load_const_i32(pool_index);
+    emitter.emit_bit_and_32();
+}
+
 pub(crate) fn emit_load_var(emitter: &mut Emitter, var_index: VarIndex, op_type: OpType) {
     match op_type.0 {
         OpWidth::W32 => emitter.emit_load_var_i32(var_index),
@@ -1831,6 +1850,32 @@ macro_rules! emit_signed_op {
     };
 }
 
+/// Generates an arithmetic dispatch `emit_<stem>` for ADD/SUB/MUL whose
+/// result type is `storage_bits` wide. UDINT, ULINT, DWORD and LWORD emit
+/// the unsigned builtins (`emit_<stem>_{u32,u64}`) so that the overflow
+/// policy applies unsigned limits. Every other type emits the width opcode:
+/// a BYTE, USINT, WORD or UINT result computed in 32-bit signed arithmetic
+/// either fits or is out of range of its type anyway, and the store's
+/// `TRUNC_U8`/`TRUNC_U16` applies the policy.
+macro_rules! emit_arith_op {
+    ($stem:ident) => {
+        paste! {
+            pub(crate) fn [<emit_ $stem>](emitter: &mut Emitter, op_type: OpType, storage_bits: u8) {
+                match op_type {
+                    (OpWidth::W32, Signedness::Unsigned) if storage_bits == 32 => {
+                        emitter.[<emit_ $stem _u32>]()
+                    }
+                    (OpWidth::W64, Signedness::Unsigned) => emitter.[<emit_ $stem _u64>](),
+                    (OpWidth::W32, _) => emitter.[<emit_ $stem _i32>](),
+                    (OpWidth::W64, _) => emitter.[<emit_ $stem _i64>](),
+                    (OpWidth::F32, _) => emitter.[<emit_ $stem _f32>](),
+                    (OpWidth::F64, _) => emitter.[<emit_ $stem _f64>](),
+                }
+            }
+        }
+    };
+}
+
 /// Generates a logical/bitwise dispatch `emit_<stem>` for AND/OR/XOR. Unsigned
 /// integer operands emit the bitwise opcode (`emit_bit_<stem>_32/_64`); every
 /// other type (BOOL and signed integers) emits `emit_bool_<stem>`.
@@ -1848,13 +1893,14 @@ macro_rules! emit_logical_op {
     };
 }
 
-emit_width_op!(add);
-emit_width_op!(sub);
-emit_width_op!(mul);
 emit_width_op!(neg);
 emit_width_op!(eq);
 emit_width_op!(ne);
 
+emit_arith_op!(add);
+emit_arith_op!(sub);
+emit_arith_op!(mul);
+
 emit_signed_op!(div);
 emit_signed_op!(lt);
 emit_signed_op!(le);
@@ -127,6 +127,7 @@ pub(crate) fn compile_user_function(
         if let Some(id) = decl.identifier.symbolic_id() {
             ctx.variables.insert(id.clone(), current_index);
             push_local_var_name(ctx, current_index, function_id, decl, id);
+            crate::compile_range::register_var_range(ctx, types, current_index, decl);
             match &decl.initializer {
                 InitialValueAssignmentKind::Simple(simple) => {
                     if let Some(type_info) = resolve_type_name(&simple.type_name.name) {
@@ -137,11 +138,9 @@ pub(crate) fn compile_user_function(
                     let max_length = resolve_string_max_length(string_init)?;
                     let char_width = char_width_for_string_type(&string_init.width);
 
-                    let data_offset = ctx.data_region_offset;
                     let total_bytes = string_region_size(max_length, char_width);
-                    ctx.data_region_offset = ctx
-                        .data_region_offset
-                        .checked_add(total_bytes)
+                    let data_offset = ctx
+                        .allocate_data(total_bytes, Some(id), &decl.identifier.span())
                         .ok_or_else(|| {
                             Diagnostic::not_implemented(Label::span(
                                 string_init.span(),
@@ -194,6 +193,7 @@ pub(crate) fn compile_user_function(
         if let Some(id) = decl.identifier.symbolic_id() {
             ctx.variables.insert(id.clone(), current_index);
             push_local_var_name(ctx, current_index, function_id, decl, id);
+            crate::compile_range::register_var_range(ctx, types, current_index, decl);
             match &decl.initializer {
                 InitialValueAssignmentKind::Simple(simple) => {
                     if let Some(type_info) = resolve_type_name(&simple.type_name.name) {
@@ -204,11 +204,9 @@ pub(crate) fn compile_user_function(
                     let max_length = resolve_string_max_length(string_init)?;
                     let char_width = char_width_for_string_type(&string_init.width);
 
-                    let data_offset = ctx.data_region_offset;
                     let total_bytes = string_region_size(max_length, char_width);
-                    ctx.data_region_offset = ctx
-                        .data_region_offset
-                        .checked_add(total_bytes)
+                    let data_offset = ctx
+                        .allocate_data(total_bytes, Some(id), &decl.identifier.span())
                         .ok_or_else(|| {
                             Diagnostic::not_implemented(Label::span(
                                 string_init.span(),
@@ -280,11 +278,9 @@ pub(crate) fn compile_user_function(
                 _ => NARROW_CHAR_WIDTH,
             };
 
-            let data_offset = ctx.data_region_offset;
             let total_bytes = string_region_size(max_length, char_width);
-            ctx.data_region_offset = ctx
-                .data_region_offset
-                .checked_add(total_bytes)
+            let data_offset = ctx
+                .allocate_data(total_bytes, Some(&return_id), &func_decl.name.span())
                 .ok_or_else(|| Diagnostic::todo())?;
 
             if max_length > ctx.max_string_capacity {
@@ -477,6 +473,7 @@ pub(crate) fn compile_user_function(
         num_params,
         name: func_name.to_string(),
         line_map: finalized.line_map,
+        loops: finalized.loops,
     })
 }
 
@@ -494,7 +491,7 @@ pub(crate) fn compile_user_function_block(
     var_offset: u16,
     ctx: &mut CompileContext,
     builder: &mut ContainerBuilder,
-    _types: &TypeEnvironment,
+    types: &TypeEnvironment,
     num_globals: u16,
 ) -> Result<CompiledFunction, Diagnostic> {
     let fb_name = fb_decl.name.name.to_string().to_uppercase();
@@ -563,6 +560,7 @@ pub(crate) fn compile_user_function_block(
         if let Some(id) = decl.identifier.symbolic_id() {
             ctx.variables.insert(id.clone(), current_index);
             push_local_var_name(ctx, current_index, function_id, decl, id);
+            crate::compile_range::register_var_range(ctx, types, current_index, decl);
             match &decl.initializer {
                 InitialValueAssignmentKind::Simple(simple) => {
                     if let Some(vti) = resolve_type_name(&simple.type_name.name) {
@@ -590,11 +588,9 @@ pub(crate) fn compile_user_function_block(
                     let max_length = resolve_string_max_length(string_init)?;
                     let char_width = char_width_for_string_type(&string_init.width);
 
-                    let data_offset = ctx.data_region_offset;
                     let total_bytes = string_region_size(max_length, char_width);
-                    ctx.data_region_offset = ctx
-                        .data_region_offset
-                        .checked_add(total_bytes)
+                    let data_offset = ctx
+                        .allocate_data(total_bytes, Some(id), &decl.identifier.span())
                         .ok_or_else(|| {
                             Diagnostic::not_implemented(Label::span(
                                 string_init.span(),
@@ -654,5 +650,6 @@ pub(crate) fn compile_user_function_block(
         num_params: 0,
         name: fb_name,
         line_map: finalized.line_map,
+        loops: finalized.loops,
     })
 }
@@ -0,0 +1,139 @@
+//! Iteration bounds for loop statements.
+//!
+//! Codegen records every `FOR`, `WHILE` and `REPEAT` loop in the debug
+//! section's LOOP_BOUND table (Tag 10) so that the worst-case execution
+//! time analysis in [`crate::wcet`] can tell how often each loop body runs.
+//!
+//! A `FOR` loop is bounded by its own header: the value-range analysis
+//! bounds `FROM` and `TO`, and the step is a constant. The bound is dropped
+//! when the body may change the control variable, or when stepping past
+//! `TO` would leave the control variable's type, because the increment then
+//! wraps and the loop need not end.
+//!
+//! `WHILE` and `REPEAT` loops carry no bound of their own. The programmer
+//! states one with a pragma directly before the statement:
+//!
+//! ```text
+//! {attribute 'loop_bound' := '16'}
+//! WHILE NOT done DO
+//!     ...
+//! END_WHILE;
+//! ```
+//!
+//! Pragmas require a dialect or `--allow-pragmas` that accepts them.
+//!
+//! See `specs/plans/2026-10-18-wcet-estimate.md`.
+
+use ironplc_dsl::core::SourceSpan;
+use ironplc_dsl::textual::For;
+
+use super::compile::CompileContext;
+use super::compile_bounds::body_may_write;
+use super::compile_range::{proven_range, storage_range};
+
+/// The pragma attribute that states a loop's iteration bound.
+const LOOP_BOUND_ATTRIBUTE: &str = "loop_bound";
+
+/// Returns the most times the body of `for_stmt` runs, or `None` when the
+/// loop cannot be bounded.
+pub(crate) fn for_iteration_bound(ctx: &CompileContext, for_stmt: &For) -> Option<u64> {
+    let op_type = ctx.var_op_type(&for_stmt.control);
+    let from = proven_range(ctx, &for_stmt.from, op_type)?;
+    let to = proven_range(ctx, &for_stmt.to, op_type)?;
+    let step = match &for_stmt.step {
+        Some(step) => {
+            proven_range(ctx, step, op_type)
+                .filter(|range| range.min == range.max)?
+                .min
+        }
+        None => 1,
+    };
+    if step == 0 || body_may_write(ctx, for_stmt) {
+        return None;
+    }
+
+    // The loop ends when the control variable steps past TO. If that step
+    // leaves the variable's type, the increment wraps and the loop goes on.
+    let storage = storage_range(ctx.var_type_info(&for_stmt.control)?)?;
+    let distance = if step > 0 {
+        if to.max + step > storage.max {
+            return None;
+        }
+        to.max - from.min
+    } else {
+        if to.min + step < storage.min {
+            return None;
+        }
+        from.max - to.min
+    };
+    if distance < 0 {
+        return Some(0);
+    }
+    u64::try_from(distance / step.abs() + 1).ok()
+}
+
+/// Returns the bound a `{attribute 'loop_bound' := 'N'}` pragma directly
+/// before the statement at `span` states, if there is one.
+pub(crate) fn pragma_iteration_bound(ctx: &CompileContext, span: &SourceSpan) -> Option<u64> {
+    let bytes = ctx.debug_source_files.source_bytes(&span.file_id)?;
+    let text = std::str::from_utf8(bytes).ok()?;
+    let before = text.get(..span.start)?.trim_end();
+    let pragma = before.strip_suffix('}')?;
+    let open = pragma.rfind('{')?;
+    parse_loop_bound_pragma(&pragma[open + 1..])
+}
+
+/// Parses the inside of a `{attribute 'loop_bound' := 'N'}` pragma.
+fn parse_loop_bound_pragma(pragma: &str) -> Option<u64> {
+    let rest = pragma.trim().strip_prefix("attribute")?.trim_start();
+    let rest = rest
+        .strip_prefix('\'')?
+        .strip_prefix(LOOP_BOUND_ATTRIBUTE)?
+        .strip_prefix('\'')?
+        .trim_start();
+    let value = rest.strip_prefix(":=")?.trim();
+    let value = value.strip_prefix('\'')?.strip_suffix('\'')?;
+    value.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_loop_bound_pragma_when_well_formed_then_bound() {
+        assert_eq!(
+            parse_loop_bound_pragma("attribute 'loop_bound' := '16'"),
+            Some(16)
+        );
+    }
+
+    #[test]
+    fn parse_loop_bound_pragma_when_extra_whitespace_then_bound() {
+        assert_eq!(
+            parse_loop_bound_pragma("  attribute  'loop_bound':='8'  "),
+            Some(8)
+        );
+    }
+
+    #[test]
+    fn parse_loop_bound_pragma_when_other_attribute_then_none() {
+        assert_eq!(
+            parse_loop_bound_pragma("attribute 'pack_mode' := '1'"),
+            None
+        );
+        assert_eq!(parse_loop_bound_pragma("attribute 'qualified_only'"), None);
+    }
+
+    #[test]
+    fn parse_loop_bound_pragma_when_value_not_a_count_then_none() {
+        assert_eq!(
+            parse_loop_bound_pragma("attribute 'loop_bound' := 'many'"),
+            None
+        );
+        assert_eq!(
+            parse_loop_bound_pragma("attribute 'loop_bound' := '-1'"),
+            None
+        );
+    }
+}
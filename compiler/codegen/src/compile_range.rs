@@ -0,0 +1,262 @@
+//! Runtime range checks for subrange and enumeration stores.
+//!
+//! A store into a variable, structure field or array element whose declared
+//! type is a subrange (`TYPE SMALL : INT (0..100); END_TYPE`) or an
+//! enumeration is preceded by a `RANGE_CHECK_*` instruction, emitted after
+//! any narrowing truncation. An out-of-range value then traps with
+//! `RangeViolation` (V4007) instead of being written.
+//!
+//! Checks whose value the value-range analysis proves to be in range are
+//! elided when `CodegenOptions::elide_proven_range_checks` is set.
+//!
+//! See `specs/plans/2026-10-18-runtime-range-checks.md`.
+
+use ironplc_analyzer::intermediate_type::IntermediateType;
+use ironplc_analyzer::value_range::{expr_range, ValueRange};
+use ironplc_analyzer::TypeEnvironment;
+use ironplc_container::{opcode, VarIndex};
+use ironplc_dsl::common::{InitialValueAssignmentKind, SpecificationKind, TypeName, VarDecl};
+use ironplc_dsl::textual::{Expr, ExprKind, SymbolicVariableKind, Variable};
+
+use super::compile::{CompileContext, OpType, OpWidth, Signedness, VarTypeInfo};
+use super::compile_enum::{enum_ordinal_range, enum_value_type, resolve_enum_ordinal};
+use crate::emit::Emitter;
+
+/// The declared range of a store target.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RangeConstraint {
+    /// Inclusive bounds every stored value must lie in.
+    pub(crate) range: ValueRange,
+    /// Uppercase enumeration type name when the target is an enumeration;
+    /// `None` for subranges.
+    pub(crate) enum_type: Option<String>,
+}
+
+impl RangeConstraint {
+    pub(crate) fn subrange(range: ValueRange) -> Self {
+        RangeConstraint {
+            range,
+            enum_type: None,
+        }
+    }
+
+    fn enumeration(ctx: &CompileContext, type_name: &TypeName) -> Option<Self> {
+        let type_upper = type_name.to_string().to_uppercase();
+        let (min, max) = enum_ordinal_range(&ctx.enum_map, &type_upper)?;
+        Some(RangeConstraint {
+            range: ValueRange::new(min.into(), max.into()),
+            enum_type: Some(type_upper),
+        })
+    }
+}
+
+/// Returns the constraint of a structure field or array element type.
+pub(crate) fn type_constraint(ty: &IntermediateType) -> Option<RangeConstraint> {
+    ValueRange::of_subrange(ty).map(RangeConstraint::subrange)
+}
+
+/// Returns the constraint declared by the type of `decl`, if any.
+pub(crate) fn decl_constraint(
+    ctx: &CompileContext,
+    types: &TypeEnvironment,
+    decl: &VarDecl,
+) -> Option<RangeConstraint> {
+    match &decl.initializer {
+        // Named types arrive as `Simple` from some declaration forms; the
+        // type environment tells a subrange or enumeration apart.
+        InitialValueAssignmentKind::Simple(simple) => {
+            if let Some(subrange) = types.resolve_subrange_type(&simple.type_name) {
+                type_constraint(subrange)
+            } else if types.is_enumeration(&simple.type_name) {
+                RangeConstraint::enumeration(ctx, &simple.type_name)
+            } else {
+                None
+            }
+        }
+        InitialValueAssignmentKind::Subrange(spec) => match spec {
+            SpecificationKind::Named(type_name) => {
+                type_constraint(types.resolve_subrange_type(type_name)?)
+            }
+            SpecificationKind::Inline(inline) => {
+                let min = inline.subrange.start.as_signed_integer()?;
+                let max = inline.subrange.end.as_signed_integer()?;
+                Some(RangeConstraint::subrange(ValueRange::new(
+                    signed_value(min.is_neg, min.value.value),
+                    signed_value(max.is_neg, max.value.value),
+                )))
+            }
+        },
+        InitialValueAssignmentKind::EnumeratedType(enum_init) => {
+            RangeConstraint::enumeration(ctx, &enum_init.type_name)
+        }
+        _ => None,
+    }
+}
+
+/// Records the constraint of `decl` (if any) for the variable at `var_index`.
+pub(crate) fn register_var_range(
+    ctx: &mut CompileContext,
+    types: &TypeEnvironment,
+    var_index: VarIndex,
+    decl: &VarDecl,
+) {
+    if let Some(constraint) = decl_constraint(ctx, types, decl) {
+        ctx.var_ranges.insert(var_index, constraint);
+    }
+}
+
+/// Emits the range check for storing the value on top of the stack into a
+/// target with `constraint`. `var_index` identifies the target in the trap.
+///
+/// `value` is the source expression when there is one. It lets the check
+/// be skipped when it cannot fail: an enumeration target assigned a value of
+/// its own type, or (with elision enabled) any value whose range is proven
+/// to lie inside the target's.
+pub(crate) fn emit_range_check(
+    emitter: &mut Emitter,
+    ctx: &mut CompileContext,
+    var_index: VarIndex,
+    constraint: &RangeConstraint,
+    op_type: OpType,
+    value: Option<&Expr>,
+) {
+    if let (Some(enum_type), Some(expr)) = (&constraint.enum_type, value) {
+        if expr_enum_type(ctx, expr).as_deref() == Some(enum_type.as_str()) {
+            return;
+        }
+    }
+    if ctx.elide_proven_range_checks {
+        if let Some(proven) = value.and_then(|expr| proven_range(ctx, expr, op_type)) {
+            if constraint.range.contains(&proven) {
+                return;
+            }
+        }
+    }
+
+    let range = constraint.range;
+    let (op, min_const, max_const) = match op_type {
+        (OpWidth::W32, Signedness::Signed) => (
+            opcode::RANGE_CHECK_I32,
+            ctx.add_i32_constant(range.min as i32),
+            ctx.add_i32_constant(range.max as i32),
+        ),
+        (OpWidth::W32, Signedness::Unsigned) => (
+            opcode::RANGE_CHECK_U32,
+            ctx.add_i32_constant(range.min as u32 as i32),
+            ctx.add_i32_constant(range.max as u32 as i32),
+        ),
+        (OpWidth::W64, Signedness::Signed) => (
+            opcode::RANGE_CHECK_I64,
+            ctx.add_i64_constant(range.min as i64),
+            ctx.add_i64_constant(range.max as i64),
+        ),
+        (OpWidth::W64, Signedness::Unsigned) => (
+            opcode::RANGE_CHECK_U64,
+            ctx.add_i64_constant(range.min as u64 as i64),
+            ctx.add_i64_constant(range.max as u64 as i64),
+        ),
+        // Subranges and enumerations are integers; nothing to check.
+        (OpWidth::F32, _) | (OpWidth::F64, _) => return,
+    };
+    emitter.emit_range_check(op, var_index, min_const, max_const);
+}
+
+/// Emits the range check for a store into the variable at `var_index` when
+/// that variable (or, for an array, its element type) has a declared range.
+pub(crate) fn emit_var_range_check(
+    emitter: &mut Emitter,
+    ctx: &mut CompileContext,
+    var_index: VarIndex,
+    op_type: OpType,
+    value: Option<&Expr>,
+) {
+    if let Some(constraint) = ctx.var_ranges.get(&var_index).cloned() {
+        emit_range_check(emitter, ctx, var_index, &constraint, op_type, value);
+    }
+}
+
+/// Returns the range every value of `expr` lies in when it is computed as
+/// `op_type`, using the ranges of the enclosing `FOR` control variables and
+/// the declared ranges and storage widths of the variables it reads.
+pub(crate) fn proven_range(
+    ctx: &CompileContext,
+    expr: &Expr,
+    op_type: OpType,
+) -> Option<ValueRange> {
+    let domain = op_domain(op_type)?;
+    expr_range(expr, domain, &|leaf| match &leaf.kind {
+        ExprKind::EnumeratedValue(ev) => resolve_enum_ordinal(&ctx.enum_map, ev)
+            .ok()
+            .map(|ordinal| ValueRange::exact(ordinal.into())),
+        ExprKind::Variable(Variable::Symbolic(SymbolicVariableKind::Named(named))) => {
+            let var_index = ctx.variables.get(&named.name)?;
+            if let Some(range) = ctx.loop_ranges.get(var_index) {
+                return Some(*range);
+            }
+            if let Some(constraint) = ctx.var_ranges.get(var_index) {
+                return Some(constraint.range);
+            }
+            storage_range(ctx.var_type_info(&named.name)?)
+        }
+        _ => None,
+    })
+}
+
+/// The values an integer operation of `op_type` can produce without
+/// overflowing; `None` for floating point.
+fn op_domain(op_type: OpType) -> Option<ValueRange> {
+    let bits = match op_type.0 {
+        OpWidth::W32 => 32,
+        OpWidth::W64 => 64,
+        OpWidth::F32 | OpWidth::F64 => return None,
+    };
+    match op_type.1 {
+        Signedness::Signed => {
+            let half = 1i128 << (bits - 1);
+            Some(ValueRange::new(-half, half - 1))
+        }
+        Signedness::Unsigned => Some(ValueRange::new(0, (1i128 << bits) - 1)),
+    }
+}
+
+/// Returns the enumeration type (uppercase) of `expr` when it is known to
+/// be a value of a single enumeration.
+fn expr_enum_type(ctx: &CompileContext, expr: &Expr) -> Option<String> {
+    match &expr.kind {
+        ExprKind::EnumeratedValue(ev) => enum_value_type(&ctx.enum_map, ev),
+        ExprKind::Expression(inner) => expr_enum_type(ctx, inner),
+        ExprKind::Variable(Variable::Symbolic(SymbolicVariableKind::Named(named))) => ctx
+            .variables
+            .get(&named.name)
+            .and_then(|var_index| ctx.var_ranges.get(var_index))
+            .and_then(|constraint| constraint.enum_type.clone()),
+        _ => None,
+    }
+}
+
+/// The values an integer variable of the given type can hold.
+pub(crate) fn storage_range(type_info: VarTypeInfo) -> Option<ValueRange> {
+    if !matches!(type_info.op_width, OpWidth::W32 | OpWidth::W64) {
+        return None;
+    }
+    match (type_info.signedness, type_info.storage_bits) {
+        (_, 1) => Some(ValueRange::new(0, 1)),
+        (Signedness::Signed, bits @ (8 | 16 | 32 | 64)) => {
+            let half = 1i128 << (bits - 1);
+            Some(ValueRange::new(-half, half - 1))
+        }
+        (Signedness::Unsigned, bits @ (8 | 16 | 32 | 64)) => {
+            Some(ValueRange::new(0, (1i128 << bits) - 1))
+        }
+        _ => None,
+    }
+}
+
+fn signed_value(is_neg: bool, magnitude: u128) -> i128 {
+    let magnitude = magnitude as i128;
+    if is_neg {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
@@ -38,6 +38,7 @@ pub(crate) fn assign_variables(
         if let Some(id) = decl.identifier.symbolic_id() {
             let index = VarIndex::new(ctx.variables.len() as u16);
             ctx.variables.insert(id.clone(), index);
+            crate::compile_range::register_var_range(ctx, types, index, decl);
 
             // Resolve type info and collect debug metadata.
             let (type_tag, type_name_str) = match &decl.initializer {
@@ -83,11 +84,9 @@ pub(crate) fn assign_variables(
                     let char_width = char_width_for_string_type(&string_init.width);
 
                     // Allocate space in the data region: [max_length: u16][cur_length: u16][data]
-                    let data_offset = ctx.data_region_offset;
                     let total_bytes = string_region_size(max_length, char_width);
-                    ctx.data_region_offset = ctx
-                        .data_region_offset
-                        .checked_add(total_bytes)
+                    let data_offset = ctx
+                        .allocate_data(total_bytes, Some(id), &decl.identifier.span())
                         .ok_or_else(|| {
                             Diagnostic::not_implemented(Label::span(
                                 string_init.span(),
@@ -124,10 +123,8 @@ pub(crate) fn assign_variables(
                     if let Some((type_id, num_fields, field_map)) = resolve_fb_type(&fb_name) {
                         // Standard library function block.
                         let instance_size = num_fields as u32 * 8;
-                        let data_offset = ctx.data_region_offset;
-                        ctx.data_region_offset = ctx
-                            .data_region_offset
-                            .checked_add(instance_size)
+                        let data_offset = ctx
+                            .allocate_data(instance_size, Some(id), &decl.identifier.span())
                             .ok_or_else(|| {
                                 Diagnostic::not_implemented(Label::span(
                                     decl.identifier.span(),
@@ -147,10 +144,10 @@ pub(crate) fn assign_variables(
                     } else if let Some(user_fb) = ctx.user_fb_types.get(&fb_name) {
                         // User-defined function block.
                         let instance_size = user_fb.num_fields as u32 * 8;
-                        let data_offset = ctx.data_region_offset;
-                        ctx.data_region_offset = ctx
-                            .data_region_offset
-                            .checked_add(instance_size)
+                        let type_id = user_fb.type_id;
+                        let field_indices = user_fb.field_indices.clone();
+                        let data_offset = ctx
+                            .allocate_data(instance_size, Some(id), &decl.identifier.span())
                             .ok_or_else(|| {
                                 Diagnostic::not_implemented(Label::span(
                                     decl.identifier.span(),
@@ -162,9 +159,9 @@ pub(crate) fn assign_variables(
                             id.clone(),
                             FbInstanceInfo {
                                 var_index: index,
-                                type_id: user_fb.type_id,
+                                type_id,
                                 data_offset,
-                                field_indices: user_fb.field_indices.clone(),
+                                field_indices,
                             },
                         );
                     }
@@ -173,10 +170,12 @@ pub(crate) fn assign_variables(
                 InitialValueAssignmentKind::Array(array_init) => {
                     let spec = match &array_init.spec {
                         SpecificationKind::Inline(array_subranges) => {
-                            crate::compile_array::array_spec_from_inline(
+                            let mut spec = crate::compile_array::array_spec_from_inline(
                                 array_subranges,
                                 &decl.identifier.span(),
-                            )?
+                            )?;
+                            crate::compile_array::resolve_subrange_element(&mut spec, types)?;
+                            spec
                         }
                         SpecificationKind::Named(type_name) => {
                             let array_type =
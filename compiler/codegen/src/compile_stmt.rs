@@ -20,6 +20,7 @@ use super::compile::{
     emit_string_literal_load, CompileContext, CurrentFunctionReturn, OpType, OpWidth, Signedness,
     VarTypeInfo, DEFAULT_OP_TYPE, DEFAULT_STRING_MAX_LENGTH_U16,
 };
+use super::compile_bounds::{emit_store_array_element, for_control_range};
 use super::compile_expr::{
     compile_bit_access_assignment, compile_expr, compile_partial_access_assignment,
     condition_op_type, emit_add, emit_classified_cmp_br, emit_ge, emit_le, emit_load_var,
@@ -27,7 +28,12 @@ use super::compile_expr::{
     op_type, resolve_variable, resolve_variable_name, signed_integer_to_i64, try_classify_cmp,
     variable_span, ClassifiedCmp,
 };
+use super::compile_loop_bound::{for_iteration_bound, pragma_iteration_bound};
+use super::compile_range::{
+    emit_range_check, emit_var_range_check, type_constraint, RangeConstraint,
+};
 use crate::emit::Emitter;
+use ironplc_container::debug_section::loop_kind;
 use ironplc_container::opcode;
 
 /// Compiles a function block body.
@@ -177,6 +183,16 @@ fn compile_statement(
                         let op_type = resolve_fb_field_op_type(ctx, type_id, &field_name);
                         emitter.emit_fb_load_instance(var_index);
                         compile_expr(emitter, ctx, &assignment.value, op_type)?;
+                        if let Some(range) = resolve_fb_field_range(ctx, type_id, &field_name) {
+                            emit_range_check(
+                                emitter,
+                                ctx,
+                                var_index,
+                                &range,
+                                op_type,
+                                Some(&assignment.value),
+                            );
+                        }
                         emitter.emit_fb_store_param(field_idx);
                         emitter.emit_pop();
                         return Ok(());
@@ -213,6 +229,16 @@ fn compile_statement(
                     crate::compile_struct::resolve_struct_field_access(ctx, structured)?;
                 compile_expr(emitter, ctx, &assignment.value, op_type)?;
                 crate::compile_struct::emit_truncation_for_field(emitter, &field_type);
+                if let Some(range) = type_constraint(&field_type) {
+                    emit_range_check(
+                        emitter,
+                        ctx,
+                        var_index,
+                        &range,
+                        op_type,
+                        Some(&assignment.value),
+                    );
+                }
                 let idx_const = ctx.add_i32_constant(slot_offset.raw() as i32);
                 emitter.emit_load_const_i32(idx_const);
                 emitter.emit_store_array(var_index, desc_index);
@@ -284,6 +310,13 @@ fn compile_statement(
                         if let Some(ti) = type_info {
                             emit_truncation(emitter, ti);
                         }
+                        emit_var_range_check(
+                            emitter,
+                            ctx,
+                            var_index,
+                            op_type,
+                            Some(&assignment.value),
+                        );
                         emit_store_var(emitter, var_index, op_type);
                     }
                     crate::compile_array::ResolvedAccess::ArrayElement { info, subscripts } => {
@@ -334,10 +367,18 @@ fn compile_statement(
                             let element_op_type = (element_vti.op_width, element_vti.signedness);
                             // 1. Compile the RHS value.
                             compile_expr(emitter, ctx, &assignment.value, element_op_type)?;
-                            // 2. Truncate for sub-32-bit types.
+                            // 2. Truncate for sub-32-bit types, then check
+                            //    subrange element types.
                             emit_truncation(emitter, element_vti);
+                            emit_var_range_check(
+                                emitter,
+                                ctx,
+                                arr_var_index,
+                                element_op_type,
+                                Some(&assignment.value),
+                            );
                             // 3. Compute the flat index.
-                            crate::compile_array::emit_flat_index(
+                            let in_bounds = crate::compile_array::emit_flat_index(
                                 emitter,
                                 ctx,
                                 &subscripts,
@@ -345,7 +386,13 @@ fn compile_statement(
                                 &target_span,
                             )?;
                             // Stack: [..., value, index]. STORE_ARRAY pops both.
-                            emitter.emit_store_array(arr_var_index, arr_desc_index);
+                            emit_store_array_element(
+                                emitter,
+                                ctx,
+                                arr_var_index,
+                                arr_desc_index,
+                                in_bounds,
+                            );
                         }
                     }
                     crate::compile_array::ResolvedAccess::DerefArrayElement {
@@ -390,6 +437,16 @@ fn compile_statement(
                         let target_span = variable_span(&assignment.target);
                         compile_expr(emitter, ctx, &assignment.value, element_op_type)?;
                         crate::compile_struct::emit_truncation_for_field(emitter, element_type);
+                        if let Some(range) = type_constraint(element_type) {
+                            emit_range_check(
+                                emitter,
+                                ctx,
+                                var_index,
+                                &range,
+                                element_op_type,
+                                Some(&assignment.value),
+                            );
+                        }
                         crate::compile_array::emit_flat_index(
                             emitter,
                             ctx,
@@ -500,6 +557,20 @@ fn resolve_fb_field_op_type(ctx: &CompileContext, type_id: u16, field_name: &str
     fb_field_op_type(field_name)
 }
 
+/// Returns the declared range of a user-defined FB field, if the field has
+/// a subrange or enumeration type.
+fn resolve_fb_field_range(
+    ctx: &CompileContext,
+    type_id: u16,
+    field_name: &str,
+) -> Option<RangeConstraint> {
+    ctx.user_fb_types
+        .values()
+        .find(|user_fb| user_fb.type_id == type_id)
+        .and_then(|user_fb| user_fb.field_ranges.get(field_name))
+        .cloned()
+}
+
 /// Compiles a function block invocation: stores inputs, calls FB, reads outputs.
 fn compile_fb_call(
     emitter: &mut Emitter,
@@ -526,6 +597,9 @@ fn compile_fb_call(
                 .ok_or_else(|| Diagnostic::todo_with_span(input.name.span()))?;
             let op_type = resolve_fb_field_op_type(ctx, type_id, &field_name);
             compile_expr(emitter, ctx, &input.expr, op_type)?;
+            if let Some(range) = resolve_fb_field_range(ctx, type_id, &field_name) {
+                emit_range_check(emitter, ctx, var_index, &range, op_type, Some(&input.expr));
+            }
             emitter.emit_fb_store_param(*field_idx);
         }
     }
@@ -552,6 +626,7 @@ fn compile_fb_call(
             emitter.emit_fb_load_param(*field_idx);
             let target_index = resolve_variable(ctx, &output.tgt)?;
             let op_type = resolve_fb_field_op_type(ctx, type_id, &field_name);
+            emit_var_range_check(emitter, ctx, target_index, op_type, None);
             emit_store_var(emitter, target_index, op_type);
         }
     }
@@ -902,6 +977,9 @@ fn compile_while(
     ctx: &mut CompileContext,
     while_stmt: &ironplc_dsl::textual::While,
 ) -> Result<(), Diagnostic> {
+    let position = emitter.source_position();
+    let bound = pragma_iteration_bound(ctx, &while_stmt.span);
+
     // Fast path: when the condition is a fusable `var <cmp> const`, emit a
     // do-while shape so the per-iteration overhead collapses to a single
     // `CMP_BR_*` (zero-trip head + back-edge tail).
@@ -923,6 +1001,7 @@ fn compile_while(
         ctx.loop_exit_labels.pop();
         emit_classified_cmp_br(emitter, classified, true, body_label);
         emitter.bind_label(end_label);
+        emitter.record_loop(body_label, end_label, loop_kind::WHILE, bound, position);
         return Ok(());
     }
 
@@ -939,6 +1018,7 @@ fn compile_while(
     ctx.loop_exit_labels.pop();
     emitter.emit_jmp(loop_label);
     emitter.bind_label(end_label);
+    emitter.record_loop(loop_label, end_label, loop_kind::WHILE, bound, position);
 
     Ok(())
 }
@@ -956,6 +1036,8 @@ fn compile_repeat(
     ctx: &mut CompileContext,
     repeat_stmt: &ironplc_dsl::textual::Repeat,
 ) -> Result<(), Diagnostic> {
+    let position = emitter.source_position();
+    let bound = pragma_iteration_bound(ctx, &repeat_stmt.span);
     let loop_label = emitter.create_label();
     let end_label = emitter.create_label();
 
@@ -976,6 +1058,7 @@ fn compile_repeat(
         emitter.emit_jmp_if_not(loop_label);
     }
     emitter.bind_label(end_label);
+    emitter.record_loop(loop_label, end_label, loop_kind::REPEAT, bound, position);
 
     Ok(())
 }
@@ -1160,6 +1243,8 @@ fn compile_for(
     let var_index = ctx.var_index(&for_stmt.control)?;
     let op_type = ctx.var_op_type(&for_stmt.control);
     let type_info = ctx.var_type_info(&for_stmt.control);
+    let position = emitter.source_position();
+    let bound = for_iteration_bound(ctx, for_stmt);
 
     // Determine step sign.
     let step_sign = match &for_stmt.step {
@@ -1184,13 +1269,15 @@ fn compile_for(
         None => true,
     };
 
-    // Initialize: compile(from), STORE_VAR control
+    // Initialize: compile(from), STORE_VAR control. Only the initial value
+    // is range-checked: the final increment legitimately steps past TO.
     compile_expr(emitter, ctx, &for_stmt.from, op_type)?;
     if let Some(ti) = type_info {
         if !elide_trunc {
             emit_truncation(emitter, ti);
         }
     }
+    emit_var_range_check(emitter, ctx, var_index, op_type, Some(&for_stmt.from));
     emit_store_var(emitter, var_index, op_type);
 
     let loop_label = emitter.create_label();
@@ -1215,10 +1302,20 @@ fn compile_for(
         emitter.emit_jmp_if_not(end_label);
     }
 
-    // BODY:
+    // BODY: while it runs the control variable stays between FROM and TO,
+    // which lets array accesses indexed by it be proven in bounds.
+    let control_range = for_control_range(ctx, for_stmt);
+    let outer_range = match control_range {
+        Some(range) => ctx.loop_ranges.insert(var_index, range),
+        None => ctx.loop_ranges.remove(&var_index),
+    };
     ctx.loop_exit_labels.push(end_label);
     compile_stmts(emitter, ctx, &for_stmt.body)?;
     ctx.loop_exit_labels.pop();
+    match outer_range {
+        Some(range) => ctx.loop_ranges.insert(var_index, range),
+        None => ctx.loop_ranges.remove(&var_index),
+    };
 
     // Increment: LOAD_VAR control, compile(step), ADD, truncate, STORE_VAR control
     emit_load_var(emitter, var_index, op_type);
@@ -1243,7 +1340,7 @@ fn compile_for(
             }
         },
     }
-    emit_add(emitter, op_type);
+    emit_add(emitter, op_type, type_info.map_or(32, |ti| ti.storage_bits));
     if let Some(ti) = type_info {
         if !elide_trunc {
             emit_truncation(emitter, ti);
@@ -1254,6 +1351,7 @@ fn compile_for(
 
     // END:
     emitter.bind_label(end_label);
+    emitter.record_loop(loop_label, end_label, loop_kind::FOR, bound, position);
 
     Ok(())
 }
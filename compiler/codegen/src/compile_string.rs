@@ -118,11 +118,9 @@ pub(crate) fn resolve_string_arg(
             // Complex variable (e.g., struct field array subscript): fall
             // through to the general expression path below.
             let max_length = DEFAULT_STRING_MAX_LENGTH_U16;
-            let data_offset = ctx.data_region_offset;
             let total_bytes = string_region_size(max_length, NARROW_CHAR_WIDTH);
-            ctx.data_region_offset = ctx
-                .data_region_offset
-                .checked_add(total_bytes)
+            let data_offset = ctx
+                .allocate_data(total_bytes, None, func_span)
                 .ok_or_else(|| Diagnostic::todo_with_span(func_span.clone()))?;
 
             if max_length > ctx.max_string_capacity {
@@ -141,11 +139,9 @@ pub(crate) fn resolve_string_arg(
             // Allocate space in the data region for this string literal.
             let bytes = encode_string_literal(&lit.value, NARROW_CHAR_WIDTH);
             let max_length = DEFAULT_STRING_MAX_LENGTH_U16;
-            let data_offset = ctx.data_region_offset;
             let total_bytes = string_region_size(max_length, NARROW_CHAR_WIDTH);
-            ctx.data_region_offset = ctx
-                .data_region_offset
-                .checked_add(total_bytes)
+            let data_offset = ctx
+                .allocate_data(total_bytes, None, func_span)
                 .ok_or_else(|| Diagnostic::todo_with_span(func_span.clone()))?;
 
             if max_length > ctx.max_string_capacity {
@@ -167,11 +163,9 @@ pub(crate) fn resolve_string_arg(
             // Compile the expression (pushes buf_idx), then store into a
             // temporary data region slot so the caller gets a data_offset.
             let max_length = DEFAULT_STRING_MAX_LENGTH_U16;
-            let data_offset = ctx.data_region_offset;
             let total_bytes = string_region_size(max_length, NARROW_CHAR_WIDTH);
-            ctx.data_region_offset = ctx
-                .data_region_offset
-                .checked_add(total_bytes)
+            let data_offset = ctx
+                .allocate_data(total_bytes, None, func_span)
                 .ok_or_else(|| Diagnostic::todo_with_span(func_span.clone()))?;
 
             if max_length > ctx.max_string_capacity {
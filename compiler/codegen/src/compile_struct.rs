@@ -584,16 +584,14 @@ pub(crate) fn allocate_struct_variable(
     }
 
     // Allocate data region space
-    let data_offset = ctx.data_region_offset;
     let total_bytes = total_slots.checked_mul(8).ok_or_else(|| {
         Diagnostic::not_implemented(Label::span(
             span.clone(),
             "Structure size overflows (slots * 8)",
         ))
     })?;
-    ctx.data_region_offset = ctx
-        .data_region_offset
-        .checked_add(total_bytes)
+    let data_offset = ctx
+        .allocate_data(total_bytes, Some(id), span)
         .ok_or_else(|| {
             Diagnostic::not_implemented(Label::span(span.clone(), "Data region overflow"))
         })?;
@@ -27,6 +27,23 @@ pub struct EmittedLineMapEntry {
     pub source_column: SourceColumn,
 }
 
+/// A loop recorded by the [`Emitter`], in the shape of
+/// [`ironplc_container::LoopBoundEntry`] minus the `FunctionId` the codegen
+/// driver pairs it with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmittedLoop {
+    /// Offset of the instruction the loop's back edges jump to.
+    pub start_offset: u16,
+    /// Offset just past the loop's last instruction.
+    pub end_offset: u16,
+    /// One of the [`ironplc_container::debug_section::loop_kind`] values.
+    pub kind: u8,
+    /// The most times the body runs, or `None` when no bound is known.
+    pub max_iterations: Option<u64>,
+    /// Source position of the loop statement, when one was set.
+    pub position: Option<(SourceFileId, SourceLine, SourceColumn)>,
+}
+
 /// An opaque forward reference to a bytecode position, used for jump targets.
 #[derive(Clone, Copy)]
 pub struct Label(usize);
@@ -61,6 +78,8 @@ pub struct Emitter {
     /// entries in emission order — therefore monotonically non-decreasing
     /// in offset.
     line_map: Vec<EmittedLineMapEntry>,
+    /// Loops recorded by [`Self::record_loop`] in the order they closed.
+    loops: Vec<EmittedLoop>,
 }
 
 /// Records the last emitted load instruction for DUP optimization.
@@ -138,6 +157,15 @@ macro_rules! emit_binop {
     };
 }
 
+/// Emit a two-argument BUILTIN that stands in for a binary op.
+macro_rules! emit_builtin_binop {
+    ($name:ident, $func_id:expr) => {
+        pub fn $name(&mut self) {
+            self.emit_builtin($func_id);
+        }
+    };
+}
+
 /// Emit a no-operand unary op (pops one, pushes one = no stack change).
 macro_rules! emit_unaryop {
     ($name:ident, $opcode:expr) => {
@@ -159,6 +187,7 @@ impl Emitter {
             last_store: None,
             current_position: None,
             line_map: Vec::new(),
+            loops: Vec::new(),
         }
     }
 
@@ -202,6 +231,40 @@ impl Emitter {
         core::mem::take(&mut self.line_map)
     }
 
+    /// Returns the source position set by [`Self::set_source_position`],
+    /// if any.
+    pub fn source_position(&self) -> Option<(SourceFileId, SourceLine, SourceColumn)> {
+        self.current_position
+    }
+
+    /// Records a loop whose back edges jump to `start` and whose exits land
+    /// at `end`. Both labels must already be bound.
+    pub fn record_loop(
+        &mut self,
+        start: Label,
+        end: Label,
+        kind: u8,
+        max_iterations: Option<u64>,
+        position: Option<(SourceFileId, SourceLine, SourceColumn)>,
+    ) {
+        let offset = |label: Label| {
+            self.labels[label.0].expect("loop labels must be bound before recording") as u16
+        };
+        self.loops.push(EmittedLoop {
+            start_offset: offset(start),
+            end_offset: offset(end),
+            kind,
+            max_iterations,
+            position,
+        });
+    }
+
+    /// Takes ownership of the recorded loops, leaving the Emitter's
+    /// internal vec empty.
+    pub fn take_loops(&mut self) -> Vec<EmittedLoop> {
+        core::mem::take(&mut self.loops)
+    }
+
     /// Records a line_map entry for the next opcode about to be appended
     /// to `bytecode`, when a current source position is set and differs
     /// from the previously recorded entry's `(line, column)`. Call this
@@ -366,6 +429,13 @@ impl Emitter {
     emit_binop!(emit_mod_u32, opcode::MOD_U32);
     emit_binop!(emit_div_u64, opcode::DIV_U64);
     emit_binop!(emit_mod_u64, opcode::MOD_U64);
+    // UDINT and ULINT arithmetic checks overflow against unsigned bounds.
+    emit_builtin_binop!(emit_add_u32, opcode::builtin::ADD_U32);
+    emit_builtin_binop!(emit_sub_u32, opcode::builtin::SUB_U32);
+    emit_builtin_binop!(emit_mul_u32, opcode::builtin::MUL_U32);
+    emit_builtin_binop!(emit_add_u64, opcode::builtin::ADD_U64);
+    emit_builtin_binop!(emit_sub_u64, opcode::builtin::SUB_U64);
+    emit_builtin_binop!(emit_mul_u64, opcode::builtin::MUL_U64);
     emit_binop!(emit_add_f32, opcode::ADD_F32);
     emit_binop!(emit_sub_f32, opcode::SUB_F32);
     emit_binop!(emit_mul_f32, opcode::MUL_F32);
@@ -468,6 +538,25 @@ impl Emitter {
         self.pop_stack(2);
     }
 
+    /// Emits LOAD_ARRAY_UNCHECKED with var_index and desc_index operands.
+    /// Only for a flat index proven to be in bounds. Net: 0.
+    pub fn emit_load_array_unchecked(&mut self, var_index: VarIndex, desc_index: u16) {
+        self.emit_opcode(opcode::LOAD_ARRAY_UNCHECKED);
+        self.bytecode.extend_from_slice(&var_index.to_le_bytes());
+        self.bytecode.extend_from_slice(&desc_index.to_le_bytes());
+        self.pop_stack(1);
+        self.push_stack(1);
+    }
+
+    /// Emits STORE_ARRAY_UNCHECKED with var_index and desc_index operands.
+    /// Only for a flat index proven to be in bounds. Net: -2.
+    pub fn emit_store_array_unchecked(&mut self, var_index: VarIndex, desc_index: u16) {
+        self.emit_opcode(opcode::STORE_ARRAY_UNCHECKED);
+        self.bytecode.extend_from_slice(&var_index.to_le_bytes());
+        self.bytecode.extend_from_slice(&desc_index.to_le_bytes());
+        self.pop_stack(2);
+    }
+
     /// Emits LOAD_ARRAY_DEREF with ref_var_index and desc_index operands.
     /// Pops 1 (flat index), pushes 1 (element value). Net: 0.
     pub fn emit_load_array_deref(&mut self, ref_var_index: VarIndex, desc_index: u16) {
@@ -632,6 +721,27 @@ impl Emitter {
         // Stack effect: 0 (no pushes, no pops).
     }
 
+    /// Emits a `RANGE_CHECK_*` opcode (`op` selects the family) guarding the
+    /// value on top of the stack against `[const_pool[min_const],
+    /// const_pool[max_const]]`. `var_index` names the store target for the
+    /// trap. Stack effect: 0 (the value is peeked, not popped).
+    pub fn emit_range_check(
+        &mut self,
+        op: u8,
+        var_index: VarIndex,
+        min_const: u16,
+        max_const: u16,
+    ) {
+        debug_assert!(
+            opcode::decode_opcode(op).0 == opcode::OP_CLASS_RANGE_CHECK,
+            "emit_range_check requires a RANGE_CHECK_* opcode"
+        );
+        self.emit_opcode(op);
+        self.bytecode.extend_from_slice(&var_index.to_le_bytes());
+        self.bytecode.extend_from_slice(&min_const.to_le_bytes());
+        self.bytecode.extend_from_slice(&max_const.to_le_bytes());
+    }
+
     /// Emits STR_INIT with data_offset, max_length, and char_width operands.
     /// Initializes a STRING/WSTRING variable's header in the data region. The
     /// `char_width` (1 = narrow STRING, 2 = wide WSTRING) is written into the
@@ -891,6 +1001,7 @@ impl Default for Emitter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ironplc_container::debug_section::loop_kind;
 
     #[test]
     fn emitter_when_load_const_then_correct_bytecode() {
@@ -1738,6 +1849,20 @@ mod tests {
         assert_eq!(em.max_stack_depth(), 2);
     }
 
+    #[test]
+    fn emitter_when_store_array_unchecked_then_correct_bytecode_and_stack_depth() {
+        let mut em = Emitter::new();
+        em.emit_load_const_i32(0); // stack: 1 (value)
+        em.emit_load_const_i32(1); // stack: 2 (flat index)
+        em.emit_store_array_unchecked(VarIndex::new(5), 2); // stack: 0 (pops 2)
+
+        assert_eq!(
+            &em.bytecode()[6..],
+            &[opcode::STORE_ARRAY_UNCHECKED, 0x05, 0x00, 0x02, 0x00]
+        );
+        assert_eq!(em.max_stack_depth(), 2);
+    }
+
     #[test]
     fn emitter_when_emit_dup_then_emits_dup_opcode_and_increments_stack() {
         let mut em = Emitter::new();
@@ -1925,4 +2050,24 @@ mod tests {
         assert_eq!(second.len(), 1);
         assert_eq!(second[0].source_line.raw(), 2);
     }
+
+    #[test]
+    fn emitter_record_loop_when_labels_bound_then_records_their_offsets() {
+        let mut em = Emitter::new();
+        let start = em.create_label();
+        let end = em.create_label();
+        em.emit_load_const_i32(0);
+        em.emit_pop();
+        em.bind_label(start);
+        em.emit_jmp(start);
+        em.bind_label(end);
+        em.record_loop(start, end, loop_kind::WHILE, Some(3), None);
+
+        let loops = em.take_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].start_offset, 4);
+        assert_eq!(loops[0].end_offset, 7);
+        assert_eq!(loops[0].max_iterations, Some(3));
+        assert!(em.take_loops().is_empty());
+    }
 }
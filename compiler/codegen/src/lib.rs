@@ -31,10 +31,13 @@
 mod call_graph;
 mod compile;
 mod compile_array;
+mod compile_bounds;
 mod compile_call;
 mod compile_enum;
 mod compile_expr;
 mod compile_fn;
+mod compile_loop_bound;
+mod compile_range;
 mod compile_setup;
 mod compile_stmt;
 mod compile_string;
@@ -43,6 +46,8 @@ mod emit;
 mod optimize;
 mod source_lookup;
 mod stack_balance;
+pub mod target;
+pub mod wcet;
 
 pub use compile::{compile, CodegenOptions};
 pub use source_lookup::{EmptyLookup, SourceLookup};
@@ -74,6 +74,29 @@ pub(crate) fn remap_line_map(
     out
 }
 
+/// Remaps emitter loops through the optimizer's old→new offset table.
+///
+/// A loop's start is the target of its back edge, so the optimizer never
+/// removes it; its end snaps forward like a line-map entry and may be the
+/// one-past-the-end offset. A loop whose offsets are not in the map is
+/// dropped for the same reason [`remap_line_map`] drops such entries.
+pub(crate) fn remap_loops(
+    raw: Vec<crate::emit::EmittedLoop>,
+    offset_map: &OffsetMap,
+) -> Vec<crate::emit::EmittedLoop> {
+    raw.into_iter()
+        .filter_map(|lp| {
+            let start = *offset_map.get(&(lp.start_offset as usize))?;
+            let end = *offset_map.get(&(lp.end_offset as usize))?;
+            Some(crate::emit::EmittedLoop {
+                start_offset: start as u16,
+                end_offset: end as u16,
+                ..lp
+            })
+        })
+        .collect()
+}
+
 /// A decoded instruction: its original byte offset and raw bytes.
 struct Instruction {
     offset: usize,
@@ -527,6 +527,7 @@ fn enum_spec_req_en_063_unknown_tags_skippable() {
             type_name: "X".into(),
             values: vec!["A".into()],
         }],
+        loop_bounds: vec![],
     };
     let mut buf = Vec::new();
     section.write_to(&mut buf).unwrap();
@@ -0,0 +1,388 @@
+//! Target profiles and resource usage.
+//!
+//! A container's [`FileHeader`](ironplc_container::FileHeader) records the
+//! resources the VM must reserve before the program runs: operand stack,
+//! call frames, the data region and temporary string buffers. A
+//! [`TargetProfile`] sets a limit for each of these and for the number of
+//! tasks, so a program that does not fit a controller is rejected at
+//! compile time rather than when it is loaded.
+//!
+//! When a program does not fit, the diagnostic names what uses the most:
+//! the largest data region reservations, the deepest call chain and the
+//! functions with the deepest operand stacks. Codegen collects the data
+//! region reservations as it allocates them (see
+//! `CompileContext::allocate_data`).
+//!
+//! A profile also carries the nanoseconds per cost unit that turn the
+//! [`crate::wcet`] estimate into time on that target.
+//!
+//! See `specs/plans/2026-10-18-target-profiles.md`.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use ironplc_container::{Container, FunctionId};
+use ironplc_dsl::core::{Id, Located, SourceSpan};
+use ironplc_dsl::diagnostic::{Diagnostic, Label};
+use ironplc_problems::Problem;
+
+use crate::call_graph::deepest_call_chain;
+use crate::wcet::DEFAULT_NS_PER_COST_UNIT;
+
+/// How many of the largest contributors a diagnostic names.
+const MAX_CONTRIBUTORS: usize = 3;
+
+/// Resource limits of a class of controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetProfile {
+    /// Name given to `--target`.
+    pub name: &'static str,
+    /// The class of controller the profile describes.
+    pub description: &'static str,
+    /// Most operand stack slots.
+    pub max_stack_depth: u16,
+    /// Most call frames, counting the program itself.
+    pub max_call_depth: u16,
+    /// Most bytes of data region (strings, arrays, structures and function
+    /// block instances).
+    pub max_data_region_bytes: u32,
+    /// Most temporary string buffers.
+    pub max_temp_bufs: u16,
+    /// Most bytes in each temporary string buffer.
+    pub max_temp_buf_bytes: u32,
+    /// Most tasks.
+    pub max_tasks: u16,
+    /// Nanoseconds one worst-case execution time cost unit takes.
+    pub ns_per_cost_unit: u64,
+}
+
+/// The target profiles, smallest first.
+pub const TARGET_PROFILES: [TargetProfile; 3] = [
+    TargetProfile {
+        name: "embedded-small",
+        description: "Microcontroller with tens of kilobytes of RAM",
+        max_stack_depth: 128,
+        max_call_depth: 8,
+        max_data_region_bytes: 16 * 1024,
+        max_temp_bufs: 16,
+        max_temp_buf_bytes: 512,
+        max_tasks: 2,
+        ns_per_cost_unit: 400,
+    },
+    TargetProfile {
+        name: "embedded-large",
+        description: "Microcontroller or small PLC with hundreds of kilobytes of RAM",
+        max_stack_depth: 512,
+        max_call_depth: 32,
+        max_data_region_bytes: 256 * 1024,
+        max_temp_bufs: 64,
+        max_temp_buf_bytes: 4 * 1024,
+        max_tasks: 8,
+        ns_per_cost_unit: 100,
+    },
+    TargetProfile {
+        name: "desktop",
+        description: "Industrial or desktop PC",
+        max_stack_depth: u16::MAX,
+        max_call_depth: 1024,
+        max_data_region_bytes: 64 * 1024 * 1024,
+        max_temp_bufs: u16::MAX,
+        max_temp_buf_bytes: 1024 * 1024,
+        max_tasks: 64,
+        ns_per_cost_unit: DEFAULT_NS_PER_COST_UNIT,
+    },
+];
+
+/// Returns the target profile named `name`.
+pub fn find_target_profile(name: &str) -> Option<&'static TargetProfile> {
+    TARGET_PROFILES.iter().find(|profile| profile.name == name)
+}
+
+/// The resources a container needs, from its header and task table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceUsage {
+    pub max_stack_depth: u16,
+    pub max_call_depth: u16,
+    pub data_region_bytes: u32,
+    pub num_temp_bufs: u16,
+    pub max_temp_buf_bytes: u32,
+    pub num_tasks: u16,
+}
+
+impl ResourceUsage {
+    /// Reads the resource usage of `container`.
+    pub fn of(container: &Container) -> Self {
+        let header = &container.header;
+        ResourceUsage {
+            max_stack_depth: header.max_stack_depth,
+            max_call_depth: header.max_call_depth,
+            data_region_bytes: header.data_region_bytes,
+            num_temp_bufs: header.num_temp_bufs,
+            max_temp_buf_bytes: header.max_temp_buf_bytes,
+            num_tasks: u16::try_from(container.task_table.tasks.len()).unwrap_or(u16::MAX),
+        }
+    }
+
+    /// Renders the usage as text, next to the limits of `target` when given.
+    pub fn render(&self, target: Option<&TargetProfile>) -> String {
+        let mut out = String::new();
+        let _ = match target {
+            Some(target) => writeln!(out, "Resource usage (target {}):", target.name),
+            None => writeln!(out, "Resource usage:"),
+        };
+        for measure in self.measures(target) {
+            let _ = write!(out, "  {}: {}", measure.name, measure.used);
+            if let Some(limit) = measure.limit {
+                let _ = write!(out, " of {limit}");
+            }
+            let _ = write!(out, " {}", measure.unit);
+            if measure.exceeded() {
+                let _ = write!(out, " (exceeds target)");
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    fn measures(&self, target: Option<&TargetProfile>) -> [Measure; 6] {
+        let limit = |f: fn(&TargetProfile) -> u64| target.map(f);
+        [
+            Measure {
+                resource: Resource::Stack,
+                name: "operand stack depth",
+                unit: "slots",
+                used: self.max_stack_depth.into(),
+                limit: limit(|t| t.max_stack_depth.into()),
+            },
+            Measure {
+                resource: Resource::CallDepth,
+                name: "call depth",
+                unit: "frames",
+                used: self.max_call_depth.into(),
+                limit: limit(|t| t.max_call_depth.into()),
+            },
+            Measure {
+                resource: Resource::DataRegion,
+                name: "data region",
+                unit: "bytes",
+                used: self.data_region_bytes.into(),
+                limit: limit(|t| t.max_data_region_bytes.into()),
+            },
+            Measure {
+                resource: Resource::TempBufs,
+                name: "temporary string buffers",
+                unit: "buffers",
+                used: self.num_temp_bufs.into(),
+                limit: limit(|t| t.max_temp_bufs.into()),
+            },
+            Measure {
+                resource: Resource::TempBufBytes,
+                name: "temporary string buffer size",
+                unit: "bytes",
+                used: self.max_temp_buf_bytes.into(),
+                limit: limit(|t| t.max_temp_buf_bytes.into()),
+            },
+            Measure {
+                resource: Resource::Tasks,
+                name: "tasks",
+                unit: "tasks",
+                used: self.num_tasks.into(),
+                limit: limit(|t| t.max_tasks.into()),
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Resource {
+    Stack,
+    CallDepth,
+    DataRegion,
+    TempBufs,
+    TempBufBytes,
+    Tasks,
+}
+
+/// One resource: how much the container uses and what the target allows.
+struct Measure {
+    resource: Resource,
+    name: &'static str,
+    unit: &'static str,
+    used: u64,
+    limit: Option<u64>,
+}
+
+impl Measure {
+    fn exceeded(&self) -> bool {
+        self.limit.is_some_and(|limit| self.used > limit)
+    }
+}
+
+/// A reservation in the data region.
+#[derive(Clone, Debug)]
+pub(crate) struct DataAllocation {
+    /// The variable the reservation holds; `None` for a compiler temporary.
+    pub(crate) owner: Option<Id>,
+    pub(crate) span: SourceSpan,
+    pub(crate) bytes: u32,
+}
+
+/// What codegen knows about where the resources go, beyond the container.
+#[derive(Default)]
+pub(crate) struct Contributors {
+    pub(crate) data: Vec<DataAllocation>,
+    pub(crate) call_graph: HashMap<FunctionId, HashSet<FunctionId>>,
+}
+
+/// Checks that `container` fits `target`.
+///
+/// Returns a [`Problem::TargetLimitExceeded`] diagnostic at the program's
+/// name listing every limit the container exceeds and its largest
+/// contributors.
+pub(crate) fn check_fits(
+    container: &Container,
+    target: &TargetProfile,
+    program: &Id,
+    contributors: &Contributors,
+) -> Result<(), Diagnostic> {
+    let usage = ResourceUsage::of(container);
+    let exceeded: Vec<Measure> = usage
+        .measures(Some(target))
+        .into_iter()
+        .filter(Measure::exceeded)
+        .collect();
+    if exceeded.is_empty() {
+        return Ok(());
+    }
+
+    let mut diagnostic = Diagnostic::problem(
+        Problem::TargetLimitExceeded,
+        Label::span(
+            program.span(),
+            format!(
+                "Program '{program}' does not fit target profile '{}'",
+                target.name
+            ),
+        ),
+    );
+    for measure in &exceeded {
+        diagnostic.described.push(format!(
+            "{} needs {} {}, target allows {}",
+            measure.name,
+            measure.used,
+            measure.unit,
+            measure.limit.unwrap_or_default()
+        ));
+        diagnostic = match measure.resource {
+            Resource::DataRegion => {
+                let mut largest: Vec<&DataAllocation> = contributors.data.iter().collect();
+                largest.sort_by_key(|allocation| std::cmp::Reverse(allocation.bytes));
+                largest.into_iter().take(MAX_CONTRIBUTORS).fold(
+                    diagnostic,
+                    |diagnostic, allocation| {
+                        let owner = match &allocation.owner {
+                            Some(id) => format!("'{id}'"),
+                            None => String::from("A temporary string"),
+                        };
+                        diagnostic.with_secondary(Label::span(
+                            allocation.span.clone(),
+                            format!("{owner} takes {} bytes of data region", allocation.bytes),
+                        ))
+                    },
+                )
+            }
+            Resource::CallDepth => {
+                let chain = deepest_call_chain(&contributors.call_graph, FunctionId::SCAN)?;
+                let names: Vec<String> =
+                    chain.iter().map(|f| function_name(container, *f)).collect();
+                diagnostic.with_help(format!("Deepest call chain: {}", names.join(" -> ")))
+            }
+            Resource::Stack => {
+                let mut deepest: Vec<_> = container.code.functions.iter().collect();
+                deepest.sort_by_key(|f| std::cmp::Reverse(f.max_stack_depth));
+                let names: Vec<String> = deepest
+                    .into_iter()
+                    .take(MAX_CONTRIBUTORS)
+                    .map(|f| {
+                        format!(
+                            "{} ({} slots)",
+                            function_name(container, f.function_id),
+                            f.max_stack_depth
+                        )
+                    })
+                    .collect();
+                diagnostic.with_help(format!("Deepest operand stacks: {}", names.join(", ")))
+            }
+            Resource::TempBufs => diagnostic.with_help(
+                "Each string expression that needs an intermediate value takes a temporary buffer",
+            ),
+            Resource::TempBufBytes => diagnostic.with_help(
+                "Every temporary buffer is as long as the longest STRING or WSTRING declared; \
+                 declaring shorter strings, such as STRING[80], makes them smaller",
+            ),
+            Resource::Tasks => diagnostic,
+        };
+    }
+    Err(diagnostic)
+}
+
+fn function_name(container: &Container, function_id: FunctionId) -> String {
+    container
+        .debug_section
+        .iter()
+        .flat_map(|debug| &debug.func_names)
+        .find(|entry| entry.function_id == function_id)
+        .map(|entry| entry.name.clone())
+        .unwrap_or_else(|| format!("function {function_id}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage() -> ResourceUsage {
+        ResourceUsage {
+            max_stack_depth: 12,
+            max_call_depth: 3,
+            data_region_bytes: 20_000,
+            num_temp_bufs: 2,
+            max_temp_buf_bytes: 260,
+            num_tasks: 1,
+        }
+    }
+
+    #[test]
+    fn find_target_profile_when_known_name_then_profile() {
+        let profile = find_target_profile("embedded-small").unwrap();
+        assert_eq!(profile.name, "embedded-small");
+    }
+
+    #[test]
+    fn find_target_profile_when_unknown_name_then_none() {
+        assert!(find_target_profile("mainframe").is_none());
+    }
+
+    #[test]
+    fn target_profiles_when_listed_then_limits_grow() {
+        for pair in TARGET_PROFILES.windows(2) {
+            assert!(pair[0].max_data_region_bytes < pair[1].max_data_region_bytes);
+            assert!(pair[0].max_call_depth < pair[1].max_call_depth);
+            assert!(pair[0].ns_per_cost_unit > pair[1].ns_per_cost_unit);
+        }
+    }
+
+    #[test]
+    fn render_when_no_target_then_usage_only() {
+        let text = usage().render(None);
+        assert!(text.starts_with("Resource usage:\n"));
+        assert!(text.contains("  data region: 20000 bytes\n"));
+    }
+
+    #[test]
+    fn render_when_target_exceeded_then_marks_resource() {
+        let text = usage().render(find_target_profile("embedded-small"));
+        assert!(text.starts_with("Resource usage (target embedded-small):\n"));
+        assert!(text.contains("  data region: 20000 of 16384 bytes (exceeds target)\n"));
+        assert!(text.contains("  call depth: 3 of 8 frames\n"));
+    }
+}
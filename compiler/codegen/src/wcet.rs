@@ -0,0 +1,606 @@
+//! Worst-case execution time estimate per task.
+//!
+//! The estimate is a static analysis over the bytecode that ships in a
+//! [`Container`], so it describes exactly what the VM runs. Each
+//! instruction has a cost in abstract cost units from [`opcode_cost`]; a
+//! unit is roughly one dispatch of a simple instruction, and a target's
+//! nanoseconds per unit turn units into time
+//! ([`DEFAULT_NS_PER_COST_UNIT`] when no target says otherwise).
+//!
+//! Within a function the cost is the longest path through its control-flow
+//! graph. Loops come from the debug section's LOOP_BOUND table (Tag 10),
+//! which codegen fills for every `FOR`, `WHILE` and `REPEAT` (see
+//! `compile_loop_bound`). A loop is costed on its own and then stands in
+//! its enclosing code as a single step:
+//!
+//! ```text
+//! loop cost = back edges × (longest pass from the head back to the head)
+//!           + (longest path from the head out of the loop)
+//! ```
+//!
+//! where the back edges are the bound for `FOR` and `WHILE` and one fewer
+//! for `REPEAT`, whose first pass is entered from above. A loop with no
+//! bound, or a backward jump no recorded loop explains, makes every cost
+//! that contains it [`Cost::Unbounded`] and is listed in
+//! [`WcetReport::unbounded_loops`].
+//!
+//! Calls add the cost of the callee. Functions are costed callee-first in
+//! the order [`crate::call_graph::callees_first`] gives, over the call
+//! graph that the `CALL` and `FB_CALL` instructions in the bytecode form.
+//! A task's cost is the sum of the scan functions of the programs it runs.
+//!
+//! The estimate is an upper bound under the cost table, not a measurement:
+//! string instructions, for example, cost a fixed amount whatever the
+//! string length.
+//!
+//! See `specs/plans/2026-10-18-wcet-estimate.md`.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+use ironplc_container::debug_section::loop_kind;
+use ironplc_container::opcode::{self, Opcode};
+use ironplc_container::{Container, FunctionId, LoopBoundEntry, TaskId};
+use ironplc_dsl::diagnostic::Diagnostic;
+
+use crate::call_graph::callees_first;
+
+/// Nanoseconds one cost unit takes when no target profile says otherwise.
+pub const DEFAULT_NS_PER_COST_UNIT: u64 = 50;
+
+/// Cost of entering and leaving a function or function block body,
+/// on top of the body itself.
+const CALL_COST: u64 = 4;
+
+/// Cost of running an intrinsic function block such as `TON` or `CTU`.
+const INTRINSIC_FB_COST: u64 = 8;
+
+/// Cost of a standard-library function run by `BUILTIN`.
+const BUILTIN_COST: u64 = 8;
+
+/// Cost of a string instruction, which copies up to a whole string.
+const STRING_COST: u64 = 16;
+
+/// A worst-case cost in cost units.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cost {
+    /// At most this many cost units.
+    Bounded(u64),
+    /// No bound: some path runs a loop whose iterations are not bounded.
+    Unbounded,
+}
+
+impl Cost {
+    const ZERO: Cost = Cost::Bounded(0);
+
+    fn plus(self, other: Cost) -> Cost {
+        match (self, other) {
+            (Cost::Bounded(a), Cost::Bounded(b)) => Cost::Bounded(a.saturating_add(b)),
+            _ => Cost::Unbounded,
+        }
+    }
+
+    fn times(self, n: u64) -> Cost {
+        match self {
+            Cost::Bounded(a) => Cost::Bounded(a.saturating_mul(n)),
+            Cost::Unbounded => Cost::Unbounded,
+        }
+    }
+
+    fn max(self, other: Cost) -> Cost {
+        match (self, other) {
+            (Cost::Bounded(a), Cost::Bounded(b)) => Cost::Bounded(a.max(b)),
+            _ => Cost::Unbounded,
+        }
+    }
+
+    /// The cost in microseconds, rounded up, at `ns_per_unit` nanoseconds
+    /// per cost unit; `None` when unbounded.
+    pub fn micros(self, ns_per_unit: u64) -> Option<u64> {
+        match self {
+            Cost::Bounded(units) => Some(units.saturating_mul(ns_per_unit).div_ceil(1000)),
+            Cost::Unbounded => None,
+        }
+    }
+}
+
+/// The estimate for one task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskWcet {
+    pub task_id: TaskId,
+    /// The task's name from the configuration; `None` until
+    /// [`WcetReport::name_tasks`] supplies it, since the container does not
+    /// record task names.
+    pub name: Option<String>,
+    /// Names of the programs the task runs, in program-table order.
+    pub programs: Vec<String>,
+    /// The task's `INTERVAL` in microseconds; 0 when it has none.
+    pub interval_us: u64,
+    /// The task's watchdog in microseconds; 0 when it has none.
+    pub watchdog_us: u64,
+    /// The worst-case cost of one scan of the task.
+    pub cost: Cost,
+}
+
+/// A loop with no iteration bound.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnboundedLoop {
+    /// Name of the function containing the loop.
+    pub function: String,
+    /// `FOR`, `WHILE` or `REPEAT`; `None` for a backward jump no recorded
+    /// loop explains.
+    pub kind: Option<&'static str>,
+    /// Path of the source file, when known.
+    pub file: Option<String>,
+    /// Source line (1-based; 0 = unknown).
+    pub line: u16,
+    /// Source column (1-based; 0 = unknown).
+    pub column: u16,
+}
+
+/// The worst-case execution time estimate for a container.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WcetReport {
+    /// One entry per task, in task-table order.
+    pub tasks: Vec<TaskWcet>,
+    /// Loops on some task's path with no iteration bound.
+    pub unbounded_loops: Vec<UnboundedLoop>,
+}
+
+impl WcetReport {
+    /// Names each task after the `TASK` that runs its programs.
+    ///
+    /// `task_of` maps a program type name to the name of the task its
+    /// instance is bound to with `WITH`; the first program that has one
+    /// names the task.
+    pub fn name_tasks(&mut self, task_of: impl Fn(&str) -> Option<String>) {
+        for task in &mut self.tasks {
+            task.name = task.programs.iter().find_map(|program| task_of(program));
+        }
+    }
+
+    /// Renders the report as text, converting cost units to time at
+    /// `ns_per_unit` nanoseconds per unit.
+    pub fn render(&self, ns_per_unit: u64) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Worst-case execution time ({ns_per_unit} ns per cost unit):"
+        );
+        for task in &self.tasks {
+            let estimate = match task.cost {
+                Cost::Bounded(units) => format!(
+                    "{units} units, {} us",
+                    task.cost.micros(ns_per_unit).unwrap_or(u64::MAX)
+                ),
+                Cost::Unbounded => String::from("unbounded"),
+            };
+            let name = match &task.name {
+                Some(name) => name.clone(),
+                None => task.task_id.to_string(),
+            };
+            let _ = write!(
+                out,
+                "  task {name} ({}): {estimate}; interval {}, watchdog {}",
+                task.programs.join(", "),
+                describe_us(task.interval_us),
+                describe_us(task.watchdog_us),
+            );
+            if task.interval_us > 0
+                && task
+                    .cost
+                    .micros(ns_per_unit)
+                    .is_none_or(|us| us > task.interval_us)
+            {
+                let _ = write!(out, " (may exceed INTERVAL)");
+            }
+            out.push('\n');
+        }
+        if !self.unbounded_loops.is_empty() {
+            let _ = writeln!(out, "Unbounded loops:");
+            for lp in &self.unbounded_loops {
+                let location = match &lp.file {
+                    Some(file) => format!(" at {file}:{}:{}", lp.line, lp.column),
+                    None => String::new(),
+                };
+                let _ = writeln!(
+                    out,
+                    "  {} in {}{location}",
+                    lp.kind.unwrap_or("loop"),
+                    lp.function
+                );
+            }
+        }
+        out
+    }
+}
+
+fn describe_us(us: u64) -> String {
+    if us == 0 {
+        String::from("none")
+    } else {
+        format!("{us} us")
+    }
+}
+
+/// The cost of one instruction in cost units, excluding the body a call
+/// runs.
+pub fn opcode_cost(op: Opcode) -> u64 {
+    use opcode::*;
+
+    match decode_opcode(op).0 {
+        OP_CLASS_MUL => 2,
+        OP_CLASS_DIV_S | OP_CLASS_DIV_U | OP_CLASS_MOD_S | OP_CLASS_MOD_U => 4,
+        OP_CLASS_LOAD_INDIRECT
+        | OP_CLASS_STORE_INDIRECT
+        | OP_CLASS_RANGE_CHECK
+        | OP_CLASS_ARRAY_UNCHECKED => 2,
+        OP_CLASS_LOAD_ARRAY
+        | OP_CLASS_STORE_ARRAY
+        | OP_CLASS_LOAD_ARRAY_DEREF
+        | OP_CLASS_STORE_ARRAY_DEREF => 3,
+        OP_CLASS_CALL | OP_CLASS_FB_CALL => CALL_COST,
+        OP_CLASS_BUILTIN => BUILTIN_COST,
+        OP_CLASS_LOAD_CONST_STR | OP_CLASS_STR_INIT..=OP_CLASS_STR_STORE_ARRAY_ELEM => STRING_COST,
+        _ => 1,
+    }
+}
+
+/// Estimates the worst-case execution time of each task in `container`.
+///
+/// Returns `InternalError` if the bytecode's call graph has a cycle, which
+/// semantic analysis rules out for compiled programs.
+pub fn estimate(container: &Container) -> Result<WcetReport, Diagnostic> {
+    let mut analyzer = Analyzer::new(container);
+    let graph = analyzer.call_graph();
+    for program in &container.task_table.programs {
+        for function_id in callees_first(&graph, program.entry_function_id)? {
+            if !analyzer.costs.contains_key(&function_id) {
+                let cost = analyzer.function_cost(function_id);
+                analyzer.costs.insert(function_id, cost);
+            }
+        }
+    }
+
+    let tasks = container
+        .task_table
+        .tasks
+        .iter()
+        .map(|task| {
+            let programs: Vec<FunctionId> = container
+                .task_table
+                .programs
+                .iter()
+                .filter(|program| program.task_id == task.task_id)
+                .map(|program| program.entry_function_id)
+                .collect();
+            TaskWcet {
+                task_id: task.task_id,
+                name: None,
+                programs: programs.iter().map(|f| analyzer.name(*f)).collect(),
+                interval_us: task.interval_us,
+                watchdog_us: task.watchdog_us,
+                cost: programs
+                    .iter()
+                    .fold(Cost::ZERO, |sum, f| sum.plus(analyzer.costs[f])),
+            }
+        })
+        .collect();
+
+    Ok(WcetReport {
+        tasks,
+        unbounded_loops: analyzer.unbounded_loops,
+    })
+}
+
+/// Where control can go after an instruction.
+enum Flow {
+    Next,
+    Jump(isize),
+    Branch(isize),
+    Return,
+}
+
+fn flow_of(op: Opcode, operands: &[u8]) -> Flow {
+    let relative = |index: usize| i16::from_le_bytes([operands[index], operands[index + 1]]);
+    match op {
+        opcode::JMP => Flow::Jump(relative(0) as isize),
+        opcode::JMP_IF_NOT => Flow::Branch(relative(0) as isize),
+        // CMP_BR: [cmp_op u8][var u16][const u16][target i16]
+        opcode::CMP_BR_I32 | opcode::CMP_BR_I64 => Flow::Branch(relative(5) as isize),
+        opcode::RET | opcode::RET_VOID => Flow::Return,
+        _ => Flow::Next,
+    }
+}
+
+/// Decodes the instruction at `pc`: its opcode, operands and size.
+fn decode(bytecode: &[u8], pc: usize) -> Option<(Opcode, &[u8], usize)> {
+    let op = *bytecode.get(pc)?;
+    let size = opcode::instruction_size(op);
+    let operands = bytecode.get(pc + 1..pc + size)?;
+    Some((op, operands, size))
+}
+
+/// The costs of a region of a function body: the whole body, or a loop.
+#[derive(Default)]
+struct Region {
+    /// Longest pass from the region start back to it (loops only).
+    iteration: Option<Cost>,
+    /// Longest path from the region start out of it.
+    exit: Option<Cost>,
+    /// Offsets outside the region that paths leave to; a return leaves to
+    /// none.
+    exit_targets: BTreeSet<usize>,
+    /// Whether some path returns from the function.
+    returns: bool,
+    /// Whether some path takes a backward jump no recorded loop explains.
+    unknown_loop: bool,
+}
+
+/// The bounds of a region: `[lo, hi)`, and whether it is a loop whose
+/// back edges target `lo`.
+#[derive(Clone, Copy)]
+struct Span {
+    lo: usize,
+    hi: usize,
+    is_loop: bool,
+}
+
+/// One function body under analysis.
+struct Body<'a> {
+    function_id: FunctionId,
+    bytecode: &'a [u8],
+    /// The function's loops, sorted so an enclosing loop comes before the
+    /// loops it contains.
+    loops: Vec<&'a LoopBoundEntry>,
+}
+
+impl Body<'_> {
+    /// The outermost loop that starts at `pc` inside the region
+    /// `[lo, hi)`, other than the region itself.
+    fn child_at(&self, pc: usize, lo: usize, hi: usize) -> Option<&LoopBoundEntry> {
+        self.loops.iter().copied().find(|lp| {
+            let (start, end) = (lp.start_offset as usize, lp.end_offset as usize);
+            start == pc && end > start && end <= hi && (start, end) != (lo, hi)
+        })
+    }
+}
+
+struct Analyzer<'a> {
+    container: &'a Container,
+    /// Function body of each user function block type, by type id.
+    user_fbs: HashMap<u16, FunctionId>,
+    /// Worst-case cost of each function analyzed so far.
+    costs: HashMap<FunctionId, Cost>,
+    unbounded_loops: Vec<UnboundedLoop>,
+}
+
+impl<'a> Analyzer<'a> {
+    fn new(container: &'a Container) -> Self {
+        let user_fbs = container
+            .type_section
+            .iter()
+            .flat_map(|section| &section.user_fb_types)
+            .map(|fb| (fb.type_id.raw(), fb.function_id))
+            .collect();
+        Analyzer {
+            container,
+            user_fbs,
+            costs: HashMap::new(),
+            unbounded_loops: Vec::new(),
+        }
+    }
+
+    fn name(&self, function_id: FunctionId) -> String {
+        self.container
+            .debug_section
+            .iter()
+            .flat_map(|debug| &debug.func_names)
+            .find(|entry| entry.function_id == function_id)
+            .map(|entry| entry.name.clone())
+            .unwrap_or_else(|| format!("function {function_id}"))
+    }
+
+    fn bytecode(&self, function_id: FunctionId) -> &'a [u8] {
+        self.container
+            .code
+            .get_function_bytecode(function_id)
+            .unwrap_or_default()
+    }
+
+    /// The function a `CALL` or `FB_CALL` runs, if it runs one.
+    fn callee(&self, op: Opcode, operands: &[u8]) -> Option<FunctionId> {
+        let operand = u16::from_le_bytes([*operands.first()?, *operands.get(1)?]);
+        match op {
+            opcode::CALL => Some(FunctionId::new(operand)),
+            opcode::FB_CALL => self.user_fbs.get(&operand).copied(),
+            _ => None,
+        }
+    }
+
+    /// The call graph formed by the `CALL` and user `FB_CALL` instructions.
+    fn call_graph(&self) -> HashMap<FunctionId, HashSet<FunctionId>> {
+        let mut graph: HashMap<FunctionId, HashSet<FunctionId>> = HashMap::new();
+        for entry in &self.container.code.functions {
+            let bytecode = self.bytecode(entry.function_id);
+            let mut pc = 0;
+            while let Some((op, operands, size)) = decode(bytecode, pc) {
+                if let Some(callee) = self.callee(op, operands) {
+                    graph.entry(entry.function_id).or_default().insert(callee);
+                }
+                pc += size;
+            }
+        }
+        graph
+    }
+
+    /// The cost of one instruction, including the body it calls.
+    fn instruction_cost(&self, op: Opcode, operands: &[u8]) -> Cost {
+        let own = Cost::Bounded(opcode_cost(op));
+        if let Some(callee) = self.callee(op, operands) {
+            return own.plus(*self.costs.get(&callee).unwrap_or(&Cost::Unbounded));
+        }
+        if op == opcode::FB_CALL {
+            return own.plus(Cost::Bounded(INTRINSIC_FB_COST));
+        }
+        own
+    }
+
+    fn function_cost(&mut self, function_id: FunctionId) -> Cost {
+        let mut loops: Vec<&LoopBoundEntry> = self
+            .container
+            .debug_section
+            .iter()
+            .flat_map(|debug| &debug.loop_bounds)
+            .filter(|lp| lp.function_id == function_id)
+            .collect();
+        loops.sort_by_key(|lp| (lp.start_offset, std::cmp::Reverse(lp.end_offset)));
+        let body = Body {
+            function_id,
+            bytecode: self.bytecode(function_id),
+            loops,
+        };
+        let region = self.region(&body, 0, body.bytecode.len(), false);
+        if region.unknown_loop {
+            return Cost::Unbounded;
+        }
+        region.exit.unwrap_or(Cost::ZERO)
+    }
+
+    /// Costs the region `[lo, hi)` of `body`, which is a loop when
+    /// `is_loop` and the whole function otherwise.
+    fn region(&mut self, body: &Body, lo: usize, hi: usize, is_loop: bool) -> Region {
+        let mut region = Region::default();
+        // Longest path from `lo` to each instruction reached so far. The
+        // region has no cycles once its inner loops are collapsed, so one
+        // pass in offset order settles every instruction before it is used.
+        let mut longest: HashMap<usize, Cost> = HashMap::from([(lo, Cost::ZERO)]);
+        let mut pc = lo;
+        while pc < hi {
+            let child = body.child_at(pc, lo, hi);
+            let Some(&before) = longest.get(&pc) else {
+                // Unreachable: nothing to cost.
+                pc = match child {
+                    Some(lp) => lp.end_offset as usize,
+                    None => match decode(body.bytecode, pc) {
+                        Some((_, _, size)) => pc + size,
+                        None => break,
+                    },
+                };
+                continue;
+            };
+
+            if let Some(lp) = child {
+                let (start, end) = (lp.start_offset as usize, lp.end_offset as usize);
+                let inner = self.region(body, start, end, true);
+                let back_edges = match lp.kind {
+                    loop_kind::REPEAT => lp.max_iterations.map(|n| n.saturating_sub(1)),
+                    _ => lp.max_iterations,
+                };
+                let total = match back_edges {
+                    Some(n) if !inner.unknown_loop => inner
+                        .iteration
+                        .unwrap_or(Cost::ZERO)
+                        .times(n)
+                        .plus(inner.exit.unwrap_or(Cost::ZERO)),
+                    _ => {
+                        if lp.max_iterations.is_none() {
+                            self.report_unbounded(body, Some(lp), start);
+                        }
+                        Cost::Unbounded
+                    }
+                };
+                let after = before.plus(total);
+                let span = Span { lo, hi, is_loop };
+                for target in inner.exit_targets {
+                    self.follow(body, span, &mut region, &mut longest, pc, target, after);
+                }
+                if inner.returns {
+                    region.returns = true;
+                    region.exit = Some(region.exit.map_or(after, |exit| exit.max(after)));
+                }
+                pc = end;
+                continue;
+            }
+
+            let Some((op, operands, size)) = decode(body.bytecode, pc) else {
+                break;
+            };
+            let after = before.plus(self.instruction_cost(op, operands));
+            let next = pc + size;
+            let target = |relative: isize| (next as isize + relative).max(0) as usize;
+            let mut successors = Vec::with_capacity(2);
+            match flow_of(op, operands) {
+                Flow::Next => successors.push(next),
+                Flow::Jump(relative) => successors.push(target(relative)),
+                Flow::Branch(relative) => successors.extend([target(relative), next]),
+                Flow::Return => {
+                    region.returns = true;
+                    region.exit = Some(region.exit.map_or(after, |exit| exit.max(after)));
+                }
+            }
+            let span = Span { lo, hi, is_loop };
+            for successor in successors {
+                self.follow(body, span, &mut region, &mut longest, pc, successor, after);
+            }
+            pc = next;
+        }
+        region
+    }
+
+    /// Records that a path of cost `cost` from the region start reaches
+    /// `target` from the instruction (or inner loop) at `pc`.
+    #[allow(clippy::too_many_arguments)]
+    fn follow(
+        &mut self,
+        body: &Body,
+        span: Span,
+        region: &mut Region,
+        longest: &mut HashMap<usize, Cost>,
+        pc: usize,
+        target: usize,
+        cost: Cost,
+    ) {
+        if span.is_loop && target == span.lo {
+            region.iteration = Some(region.iteration.map_or(cost, |it| it.max(cost)));
+        } else if target < span.lo || target >= span.hi {
+            region.exit = Some(region.exit.map_or(cost, |exit| exit.max(cost)));
+            region.exit_targets.insert(target);
+        } else if target <= pc {
+            // A backward jump inside the region that is not this loop's
+            // back edge: a loop the debug section does not describe.
+            region.unknown_loop = true;
+            self.report_unbounded(body, None, pc);
+        } else {
+            let entry = longest.entry(target).or_insert(cost);
+            *entry = entry.max(cost);
+        }
+    }
+
+    fn report_unbounded(&mut self, body: &Body, lp: Option<&LoopBoundEntry>, pc: usize) {
+        let debug = self.container.debug_section.as_ref();
+        let (file_id, line, column) = match lp {
+            Some(lp) => (lp.file_id, lp.source_line, lp.source_column),
+            None => match debug
+                .and_then(|debug| debug.lookup_source_location(body.function_id, pc as u16))
+            {
+                Some(entry) => (entry.file_id, entry.source_line, entry.source_column),
+                None => Default::default(),
+            },
+        };
+        let file = if line.raw() == 0 {
+            None
+        } else {
+            debug
+                .and_then(|debug| debug.source_files.get(file_id.raw() as usize))
+                .map(|file| file.path.clone())
+        };
+        self.unbounded_loops.push(UnboundedLoop {
+            function: self.name(body.function_id),
+            kind: lp.and_then(|lp| loop_kind::keyword(lp.kind)),
+            file,
+            line: line.raw(),
+            column: column.raw(),
+        });
+    }
+}
@@ -394,6 +394,23 @@ pub mod bc {
         ]
     }
 
+    /// RANGE_CHECK_I32 (trap unless min <= TOS <= max, signed).
+    /// Operands: var index (u16), min and max constant pool indices (u16).
+    pub fn range_check_i32(var_idx: u16, min_const: u16, max_const: u16) -> Vec<u8> {
+        let v = var_idx.to_le_bytes();
+        let mn = min_const.to_le_bytes();
+        let mx = max_const.to_le_bytes();
+        vec![
+            opcode::RANGE_CHECK_I32,
+            v[0],
+            v[1],
+            mn[0],
+            mn[1],
+            mx[0],
+            mx[1],
+        ]
+    }
+
     /// CMP_BR_I64 (fused compare-and-branch on 64-bit signed integers).
     /// See `cmp_br_i32` for operand layout.
     pub fn cmp_br_i64(cmp_op_byte: u8, var_idx: u16, const_idx: u16, offset: i16) -> Vec<u8> {
@@ -583,10 +600,21 @@ pub fn parse_and_compile(source: &str, options: &CompilerOptions) -> Container {
 pub fn try_parse_and_compile(
     source: &str,
     options: &CompilerOptions,
+) -> Result<Container, Diagnostic> {
+    try_parse_and_compile_with_codegen(source, options, &ironplc_codegen::CodegenOptions::default())
+}
+
+/// Like [`try_parse_and_compile`], with explicit code generation options.
+/// `system_uptime_global` is still taken from `options`.
+pub fn try_parse_and_compile_with_codegen(
+    source: &str,
+    options: &CompilerOptions,
+    codegen_options: &ironplc_codegen::CodegenOptions,
 ) -> Result<Container, Diagnostic> {
     let (library, context) = parse(source, options);
     let codegen_options = ironplc_codegen::CodegenOptions {
         system_uptime_global: options.allow_system_uptime_global,
+        ..codegen_options.clone()
     };
     compile(
         &library,
@@ -612,6 +640,7 @@ pub fn parse_and_try_run(
     let (library, context) = parse(source, options);
     let codegen_options = ironplc_codegen::CodegenOptions {
         system_uptime_global: options.allow_system_uptime_global,
+        ..ironplc_codegen::CodegenOptions::default()
     };
     let container = compile(
         &library,
@@ -664,6 +693,7 @@ pub fn parse_and_run_rounds(
     let (library, context) = parse(source, options);
     let codegen_options = ironplc_codegen::CodegenOptions {
         system_uptime_global: options.allow_system_uptime_global,
+        ..ironplc_codegen::CodegenOptions::default()
     };
     let container = compile(
         &library,
@@ -0,0 +1,345 @@
+//! Bytecode-level integration tests for compile-time array bounds proofs.
+
+#![allow(clippy::result_large_err)]
+
+use ironplc_codegen::CodegenOptions;
+use ironplc_container::{opcode, FunctionId};
+use ironplc_dsl::diagnostic::Diagnostic;
+use ironplc_parser::options::CompilerOptions;
+use ironplc_problems::Problem;
+use ironplc_vm::test_support::load_and_start;
+
+use crate::common::{try_parse_and_compile_with_codegen, VmBuffers};
+
+fn compile(source: &str, elide: bool) -> Result<Vec<u8>, Diagnostic> {
+    let codegen_options = CodegenOptions {
+        elide_proven_range_checks: elide,
+        ..CodegenOptions::default()
+    };
+    let container =
+        try_parse_and_compile_with_codegen(source, &CompilerOptions::default(), &codegen_options)?;
+    Ok(container
+        .code
+        .get_function_bytecode(FunctionId::new(1))
+        .unwrap()
+        .to_vec())
+}
+
+fn scan_bytecode(source: &str) -> Vec<u8> {
+    compile(source, true).unwrap()
+}
+
+fn has(bytecode: &[u8], op: u8) -> bool {
+    bytecode.contains(&op)
+}
+
+fn program(body: &str) -> String {
+    format!(
+        "
+FUNCTION touch : INT
+  VAR_INPUT
+    v : INT;
+  END_VAR
+  touch := v;
+END_FUNCTION
+
+PROGRAM main
+  VAR
+    a : ARRAY[1..5] OF INT;
+    i : INT;
+    n : INT;
+    total : INT;
+  END_VAR
+{body}
+END_PROGRAM
+"
+    )
+}
+
+#[test]
+fn compile_when_for_loop_within_array_bounds_then_unchecked_access() {
+    let bytecode = scan_bytecode(&program(
+        "
+  FOR i := 1 TO 5 DO
+    a[i] := a[i] + 1;
+  END_FOR;",
+    ));
+    assert!(has(&bytecode, opcode::LOAD_ARRAY_UNCHECKED));
+    assert!(has(&bytecode, opcode::STORE_ARRAY_UNCHECKED));
+    assert!(!has(&bytecode, opcode::LOAD_ARRAY));
+    assert!(!has(&bytecode, opcode::STORE_ARRAY));
+}
+
+#[test]
+fn compile_when_for_loop_within_bounds_and_no_elision_then_checked_access() {
+    let bytecode = compile(
+        &program(
+            "
+  FOR i := 1 TO 5 DO
+    a[i] := 0;
+  END_FOR;",
+        ),
+        false,
+    )
+    .unwrap();
+    assert!(has(&bytecode, opcode::STORE_ARRAY));
+    assert!(!has(&bytecode, opcode::STORE_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_negative_step_within_bounds_then_unchecked_access() {
+    let bytecode = scan_bytecode(&program(
+        "
+  FOR i := 5 TO 1 BY -1 DO
+    total := total + a[i];
+  END_FOR;",
+    ));
+    assert!(has(&bytecode, opcode::LOAD_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_for_loop_index_plus_offset_within_bounds_then_unchecked_access() {
+    let bytecode = scan_bytecode(&program(
+        "
+  FOR i := 1 TO 4 DO
+    a[i + 1] := a[i];
+  END_FOR;",
+    ));
+    assert!(has(&bytecode, opcode::LOAD_ARRAY_UNCHECKED));
+    assert!(has(&bytecode, opcode::STORE_ARRAY_UNCHECKED));
+    assert!(!has(&bytecode, opcode::LOAD_ARRAY));
+    assert!(!has(&bytecode, opcode::STORE_ARRAY));
+}
+
+#[test]
+fn compile_when_for_loop_constant_minus_index_within_bounds_then_unchecked_access() {
+    let bytecode = scan_bytecode(&program(
+        "
+  FOR i := 0 TO 4 DO
+    total := total + a[5 - i];
+  END_FOR;",
+    ));
+    assert!(has(&bytecode, opcode::LOAD_ARRAY_UNCHECKED));
+    assert!(!has(&bytecode, opcode::LOAD_ARRAY));
+}
+
+#[test]
+fn compile_when_for_loop_overlaps_array_bounds_then_checked_access() {
+    let bytecode = scan_bytecode(&program(
+        "
+  FOR i := 0 TO 5 DO
+    a[i] := 0;
+  END_FOR;",
+    ));
+    assert!(has(&bytecode, opcode::STORE_ARRAY));
+    assert!(!has(&bytecode, opcode::STORE_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_access_after_for_loop_then_checked_access() {
+    // After the loop the control variable is one past TO.
+    let bytecode = scan_bytecode(&program(
+        "
+  FOR i := 1 TO 5 DO
+    total := total + 1;
+  END_FOR;
+  total := a[i];",
+    ));
+    assert!(has(&bytecode, opcode::LOAD_ARRAY));
+    assert!(!has(&bytecode, opcode::LOAD_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_body_assigns_control_variable_then_checked_access() {
+    let bytecode = scan_bytecode(&program(
+        "
+  FOR i := 1 TO 5 DO
+    a[i] := 0;
+    i := i + 1;
+  END_FOR;",
+    ));
+    assert!(has(&bytecode, opcode::STORE_ARRAY));
+    assert!(!has(&bytecode, opcode::STORE_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_control_variable_passed_to_user_function_then_checked_access() {
+    let bytecode = scan_bytecode(&program(
+        "
+  FOR i := 1 TO 5 DO
+    a[i] := touch(i);
+  END_FOR;",
+    ));
+    assert!(has(&bytecode, opcode::STORE_ARRAY));
+    assert!(!has(&bytecode, opcode::STORE_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_control_variable_passed_to_standard_function_then_unchecked_access() {
+    let bytecode = scan_bytecode(&program(
+        "
+  FOR i := 1 TO 5 DO
+    a[i] := ABS(i);
+  END_FOR;",
+    ));
+    assert!(has(&bytecode, opcode::STORE_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_index_is_limited_then_unchecked_access() {
+    let bytecode = scan_bytecode(&program("  total := a[LIMIT(1, n, 5)];"));
+    assert!(has(&bytecode, opcode::LOAD_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_index_is_unbounded_then_checked_access() {
+    let bytecode = scan_bytecode(&program("  total := a[n];"));
+    assert!(has(&bytecode, opcode::LOAD_ARRAY));
+    assert!(!has(&bytecode, opcode::LOAD_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_index_has_subrange_type_within_bounds_then_unchecked_access() {
+    let source = "
+TYPE
+  SLOT : INT (1..5);
+END_TYPE
+
+PROGRAM main
+  VAR
+    a : ARRAY[1..5] OF INT;
+    s : SLOT := 1;
+  END_VAR
+  a[s] := 7;
+END_PROGRAM
+";
+    let bytecode = scan_bytecode(source);
+    assert!(has(&bytecode, opcode::STORE_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_global_control_variable_and_user_call_in_body_then_checked_access() {
+    let source = "
+FUNCTION touch : INT
+  VAR_INPUT
+    v : INT;
+  END_VAR
+  touch := v;
+END_FUNCTION
+
+CONFIGURATION config
+  VAR_GLOBAL
+    g : INT;
+  END_VAR
+  RESOURCE res ON PLC
+    TASK t (INTERVAL := T#100ms, PRIORITY := 1);
+    PROGRAM inst WITH t : main;
+  END_RESOURCE
+END_CONFIGURATION
+
+PROGRAM main
+  VAR_EXTERNAL
+    g : INT;
+  END_VAR
+  VAR
+    a : ARRAY[1..5] OF INT;
+  END_VAR
+  FOR g := 1 TO 5 DO
+    a[g] := touch(1);
+  END_FOR;
+END_PROGRAM
+";
+    let bytecode = scan_bytecode(source);
+    assert!(has(&bytecode, opcode::STORE_ARRAY));
+    assert!(!has(&bytecode, opcode::STORE_ARRAY_UNCHECKED));
+}
+
+#[test]
+fn compile_when_for_loop_entirely_outside_array_bounds_then_error() {
+    let err = compile(
+        &program(
+            "
+  FOR i := 6 TO 10 DO
+    a[i] := 0;
+  END_FOR;",
+        ),
+        false,
+    )
+    .unwrap_err();
+    assert_eq!(err.code, Problem::ArrayIndexOutOfBounds.code());
+    assert!(err.primary.message.contains("6..10"));
+}
+
+#[test]
+fn compile_when_for_loop_index_offset_outside_array_bounds_then_error() {
+    let err = compile(
+        &program(
+            "
+  FOR i := 1 TO 5 DO
+    a[i + 200] := 0;
+  END_FOR;",
+        ),
+        false,
+    )
+    .unwrap_err();
+    assert_eq!(err.code, Problem::ArrayIndexOutOfBounds.code());
+    assert!(err.primary.message.contains("201..205"));
+}
+
+#[test]
+fn compile_when_index_type_cannot_reach_array_bounds_then_error() {
+    let source = "
+PROGRAM main
+  VAR
+    a : ARRAY[200..300] OF INT;
+    small : SINT;
+    total : INT;
+  END_VAR
+  total := a[small];
+END_PROGRAM
+";
+    let err = compile(source, false).unwrap_err();
+    assert_eq!(err.code, Problem::ArrayIndexOutOfBounds.code());
+}
+
+#[test]
+fn compile_when_empty_for_loop_outside_array_bounds_then_ok() {
+    // The body never runs, so its accesses cannot fail.
+    let bytecode = scan_bytecode(&program(
+        "
+  FOR i := 10 TO 6 DO
+    a[i] := 0;
+  END_FOR;",
+    ));
+    assert!(has(&bytecode, opcode::STORE_ARRAY));
+}
+
+#[test]
+fn run_when_unchecked_loop_then_sums_elements() {
+    let source = program(
+        "
+  FOR i := 1 TO 5 DO
+    a[i] := i * 10;
+  END_FOR;
+  FOR i := 5 TO 1 BY -1 DO
+    total := total + a[i];
+  END_FOR;",
+    );
+    let codegen_options = CodegenOptions {
+        elide_proven_range_checks: true,
+        ..CodegenOptions::default()
+    };
+    let container =
+        try_parse_and_compile_with_codegen(&source, &CompilerOptions::default(), &codegen_options)
+            .unwrap();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+    vm.run_round(0).unwrap();
+
+    // a = var:0, i = var:1, n = var:2, total = var:3
+    assert_eq!(
+        vm.read_variable(ironplc_container::VarIndex::new(3))
+            .unwrap(),
+        150
+    );
+}
@@ -0,0 +1,184 @@
+//! Bytecode-level integration tests for runtime range checks on subrange
+//! and enumeration stores.
+
+use ironplc_codegen::CodegenOptions;
+use ironplc_container::{opcode, FunctionId};
+use ironplc_parser::options::CompilerOptions;
+
+use crate::common::{bc, parse_and_compile, try_parse_and_compile_with_codegen};
+
+fn scan_bytecode(source: &str, elide: bool) -> Vec<u8> {
+    let codegen_options = CodegenOptions {
+        elide_proven_range_checks: elide,
+        ..CodegenOptions::default()
+    };
+    let container =
+        try_parse_and_compile_with_codegen(source, &CompilerOptions::default(), &codegen_options)
+            .unwrap();
+    container
+        .code
+        .get_function_bytecode(FunctionId::new(1))
+        .unwrap()
+        .to_vec()
+}
+
+fn range_check_count(bytecode: &[u8]) -> usize {
+    bytecode
+        .iter()
+        .filter(|b| **b == opcode::RANGE_CHECK_I32)
+        .count()
+}
+
+#[test]
+fn compile_when_store_to_subrange_then_emits_range_check_before_store() {
+    let source = "
+TYPE
+  PERCENT : INT (0..100);
+END_TYPE
+
+PROGRAM main
+  VAR
+    x : PERCENT;
+    y : INT;
+  END_VAR
+  x := y;
+END_PROGRAM
+";
+    let container = parse_and_compile(source, &CompilerOptions::default());
+    let bytecode = container
+        .code
+        .get_function_bytecode(FunctionId::new(1))
+        .unwrap();
+
+    // x=var:0, y=var:1, constants: pool:0=0, pool:1=100
+    assert_bytecode!(
+        bytecode,
+        [
+            bc::load_var_i32(1),
+            bc::trunc_i16(),
+            bc::range_check_i32(0, 0, 1),
+            bc::store_var_i32(0),
+            bc::ret_void(),
+        ]
+    );
+}
+
+#[test]
+fn compile_when_store_to_plain_int_then_no_range_check() {
+    let source = "
+PROGRAM main
+  VAR
+    x : INT;
+    y : INT;
+  END_VAR
+  x := y;
+END_PROGRAM
+";
+    assert_eq!(range_check_count(&scan_bytecode(source, false)), 0);
+}
+
+#[test]
+fn compile_when_enum_assigned_own_value_then_no_range_check() {
+    let source = "
+TYPE
+  COLOR : (RED, GREEN, BLUE);
+END_TYPE
+
+PROGRAM main
+  VAR
+    c : COLOR;
+  END_VAR
+  c := GREEN;
+END_PROGRAM
+";
+    assert_eq!(range_check_count(&scan_bytecode(source, false)), 0);
+}
+
+#[test]
+fn compile_when_constant_in_range_and_elision_disabled_then_keeps_check() {
+    let source = "
+TYPE
+  PERCENT : INT (0..100);
+END_TYPE
+
+PROGRAM main
+  VAR
+    x : PERCENT;
+  END_VAR
+  x := 50;
+END_PROGRAM
+";
+    assert_eq!(range_check_count(&scan_bytecode(source, false)), 1);
+}
+
+#[test]
+fn compile_when_constant_in_range_and_elision_enabled_then_omits_check() {
+    let source = "
+TYPE
+  PERCENT : INT (0..100);
+END_TYPE
+
+PROGRAM main
+  VAR
+    x : PERCENT;
+  END_VAR
+  x := 50;
+END_PROGRAM
+";
+    assert_eq!(range_check_count(&scan_bytecode(source, true)), 0);
+}
+
+#[test]
+fn compile_when_limit_within_range_and_elision_enabled_then_omits_check() {
+    let source = "
+TYPE
+  PERCENT : INT (0..100);
+END_TYPE
+
+PROGRAM main
+  VAR
+    x : PERCENT;
+    y : INT;
+  END_VAR
+  x := LIMIT(0, y, 100);
+END_PROGRAM
+";
+    assert_eq!(range_check_count(&scan_bytecode(source, true)), 0);
+}
+
+#[test]
+fn compile_when_value_not_proven_and_elision_enabled_then_keeps_check() {
+    let source = "
+TYPE
+  PERCENT : INT (0..100);
+END_TYPE
+
+PROGRAM main
+  VAR
+    x : PERCENT;
+    y : INT;
+  END_VAR
+  x := MIN(y, 100);
+END_PROGRAM
+";
+    assert_eq!(range_check_count(&scan_bytecode(source, true)), 1);
+}
+
+#[test]
+fn compile_when_narrower_subrange_assigned_and_elision_enabled_then_omits_check() {
+    let source = "
+TYPE
+  PERCENT : INT (0..100);
+  DIGIT : INT (0..9);
+END_TYPE
+
+PROGRAM main
+  VAR
+    x : PERCENT;
+    d : DIGIT;
+  END_VAR
+  x := d;
+END_PROGRAM
+";
+    assert_eq!(range_check_count(&scan_bytecode(source, true)), 0);
+}
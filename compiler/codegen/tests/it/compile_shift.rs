@@ -22,7 +22,8 @@ END_PROGRAM
     assert_eq!(container.header.num_variables, 2);
 
     // x := BYTE#16#0F: LOAD_CONST_I32 pool:0, TRUNC_U8, STORE_VAR_I32 var:0
-    // y := SHL(x, 4):  LOAD_VAR_I32 var:0, LOAD_CONST_I32 pool:1, BUILTIN SHL_I32, TRUNC_U8, STORE_VAR_I32 var:1
+    // y := SHL(x, 4):  LOAD_VAR_I32 var:0, LOAD_CONST_I32 pool:1, BUILTIN SHL_I32,
+    //                  LOAD_CONST_I32 pool:2, BIT_AND_32, TRUNC_U8, STORE_VAR_I32 var:1
     // RET_VOID
     let bytecode = container
         .code
@@ -37,6 +38,8 @@ END_PROGRAM
             bc::store_var_i32(0),                  // var:0
             bc::load_const_i32(1),                 // pool:1 (4)
             bc::builtin(opcode::builtin::SHL_I32), // SHL_I32 (0x0348)
+            bc::load_const_i32(2),                 // pool:2 (0xFF)
+            bc::bit_and_32(),
             bc::trunc_u8(),
             bc::store_var_i32(1), // var:1
             bc::ret_void(),
@@ -124,3 +124,66 @@ END_PROGRAM
         bytecode
     );
 }
+
+#[test]
+fn compile_when_uint_add_then_produces_add_i32() {
+    let source = "
+PROGRAM main
+  VAR
+    a : UINT;
+    b : UINT;
+    x : UINT;
+  END_VAR
+  x := a + b;
+END_PROGRAM
+";
+    let container = parse_and_compile(source, &CompilerOptions::default());
+
+    // LOAD_VAR_I32 var:0, LOAD_VAR_I32 var:1, ADD_I32, TRUNC_U16, STORE_VAR_I32 var:2, RET_VOID
+    let bytecode = container
+        .code
+        .get_function_bytecode(ironplc_container::FunctionId::new(1))
+        .unwrap();
+    assert_bytecode!(
+        bytecode,
+        [
+            bc::load_var_i32(0), // var:0
+            bc::load_var_i32(1), // var:1
+            bc::add_i32(),
+            bc::trunc_u16(),
+            bc::store_var_i32(2), // var:2
+            bc::ret_void(),
+        ]
+    );
+}
+
+#[test]
+fn compile_when_udint_add_then_produces_add_u32_builtin() {
+    let source = "
+PROGRAM main
+  VAR
+    a : UDINT;
+    b : UDINT;
+    x : UDINT;
+  END_VAR
+  x := a + b;
+END_PROGRAM
+";
+    let container = parse_and_compile(source, &CompilerOptions::default());
+
+    // LOAD_VAR_I32 var:0, LOAD_VAR_I32 var:1, BUILTIN ADD_U32, STORE_VAR_I32 var:2, RET_VOID
+    let bytecode = container
+        .code
+        .get_function_bytecode(ironplc_container::FunctionId::new(1))
+        .unwrap();
+    assert_bytecode!(
+        bytecode,
+        [
+            bc::load_var_i32(0), // var:0
+            bc::load_var_i32(1), // var:1
+            bc::builtin(ironplc_container::opcode::builtin::ADD_U32),
+            bc::store_var_i32(2), // var:2
+            bc::ret_void(),
+        ]
+    );
+}
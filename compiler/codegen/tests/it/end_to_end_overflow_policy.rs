@@ -0,0 +1,210 @@
+//! End-to-end tests for the integer overflow policy (ADR-0002) on unsigned
+//! integer and bit string types.
+
+use crate::common::{parse, VmBuffers};
+use ironplc_codegen::compile;
+use ironplc_container::{InstanceId, TaskId};
+use ironplc_parser::options::CompilerOptions;
+use ironplc_vm::error::Trap;
+use ironplc_vm::{OverflowPolicy, Vm};
+
+/// Compiles the program and runs one scan cycle under the policy, returning
+/// the variables or the trap.
+fn run(source: &str, policy: OverflowPolicy) -> Result<VmBuffers, Trap> {
+    let (library, context) = parse(source, &CompilerOptions::default());
+    let container = compile(
+        &library,
+        &context,
+        &ironplc_codegen::CodegenOptions::default(),
+        &ironplc_codegen::EmptyLookup,
+    )
+    .unwrap();
+    let mut bufs = VmBuffers::from_container(&container);
+    {
+        let mut vm = Vm::new()
+            .overflow_policy(policy)
+            .load(&container, &mut bufs)
+            .start()
+            .map_err(|ctx| ctx.trap)?;
+        vm.run_round(0).map_err(|ctx| ctx.trap)?;
+    }
+    Ok(bufs)
+}
+
+fn overflow() -> Trap {
+    Trap::IntegerOverflow {
+        task_id: TaskId::DEFAULT,
+        instance_id: InstanceId::DEFAULT,
+    }
+}
+
+const UDINT_ADD_IN_RANGE: &str = "
+PROGRAM main
+  VAR a : UDINT := 2000000000; x : UDINT; END_VAR
+  x := a + a;
+END_PROGRAM";
+
+const UDINT_ADD_OUT_OF_RANGE: &str = "
+PROGRAM main
+  VAR a : UDINT := 4000000000; x : UDINT; END_VAR
+  x := a + a;
+END_PROGRAM";
+
+const UDINT_SUB_BELOW_ZERO: &str = "
+PROGRAM main
+  VAR a : UDINT := 1; b : UDINT := 2; x : UDINT; END_VAR
+  x := a - b;
+END_PROGRAM";
+
+const ULINT_MUL_OUT_OF_RANGE: &str = "
+PROGRAM main
+  VAR a : ULINT := 10000000000000000000; x : ULINT; END_VAR
+  x := a * 2;
+END_PROGRAM";
+
+const UINT_MUL_OUT_OF_RANGE: &str = "
+PROGRAM main
+  VAR a : UINT := 65535; x : UINT; END_VAR
+  x := a * a;
+END_PROGRAM";
+
+const USINT_SUB_BELOW_ZERO: &str = "
+PROGRAM main
+  VAR a : USINT := 1; b : USINT := 2; x : USINT; END_VAR
+  x := a - b;
+END_PROGRAM";
+
+const BYTE_NOT: &str = "
+PROGRAM main
+  VAR b : BYTE := 16#0F; x : BYTE; END_VAR
+  x := NOT b;
+END_PROGRAM";
+
+const BYTE_SHL: &str = "
+PROGRAM main
+  VAR b : BYTE := 16#81; x : BYTE; END_VAR
+  x := SHL(b, 1);
+END_PROGRAM";
+
+const WORD_NOT: &str = "
+PROGRAM main
+  VAR w : WORD := 16#00FF; x : WORD; END_VAR
+  x := NOT w;
+END_PROGRAM";
+
+#[test]
+fn end_to_end_when_saturate_and_udint_sum_above_dint_max_then_exact() {
+    let bufs = run(UDINT_ADD_IN_RANGE, OverflowPolicy::Saturate).unwrap();
+    assert_eq!(bufs.vars[1].as_i32() as u32, 4_000_000_000);
+}
+
+#[test]
+fn end_to_end_when_fault_and_udint_sum_above_dint_max_then_exact() {
+    let bufs = run(UDINT_ADD_IN_RANGE, OverflowPolicy::Fault).unwrap();
+    assert_eq!(bufs.vars[1].as_i32() as u32, 4_000_000_000);
+}
+
+#[test]
+fn end_to_end_when_saturate_and_udint_sum_above_udint_max_then_udint_max() {
+    let bufs = run(UDINT_ADD_OUT_OF_RANGE, OverflowPolicy::Saturate).unwrap();
+    assert_eq!(bufs.vars[1].as_i32() as u32, u32::MAX);
+}
+
+#[test]
+fn end_to_end_when_fault_and_udint_sum_above_udint_max_then_integer_overflow() {
+    let result = run(UDINT_ADD_OUT_OF_RANGE, OverflowPolicy::Fault);
+    assert_eq!(result.err(), Some(overflow()));
+}
+
+#[test]
+fn end_to_end_when_wrap_and_udint_sum_above_udint_max_then_low_bits() {
+    let bufs = run(UDINT_ADD_OUT_OF_RANGE, OverflowPolicy::Wrap).unwrap();
+    assert_eq!(
+        bufs.vars[1].as_i32() as u32,
+        4_000_000_000u32.wrapping_mul(2)
+    );
+}
+
+#[test]
+fn end_to_end_when_saturate_and_udint_difference_below_zero_then_zero() {
+    let bufs = run(UDINT_SUB_BELOW_ZERO, OverflowPolicy::Saturate).unwrap();
+    assert_eq!(bufs.vars[2].as_i32() as u32, 0);
+}
+
+#[test]
+fn end_to_end_when_fault_and_udint_difference_below_zero_then_integer_overflow() {
+    let result = run(UDINT_SUB_BELOW_ZERO, OverflowPolicy::Fault);
+    assert_eq!(result.err(), Some(overflow()));
+}
+
+#[test]
+fn end_to_end_when_saturate_and_ulint_product_above_ulint_max_then_ulint_max() {
+    let bufs = run(ULINT_MUL_OUT_OF_RANGE, OverflowPolicy::Saturate).unwrap();
+    assert_eq!(bufs.vars[1].as_u64(), u64::MAX);
+}
+
+#[test]
+fn end_to_end_when_fault_and_ulint_product_above_ulint_max_then_integer_overflow() {
+    let result = run(ULINT_MUL_OUT_OF_RANGE, OverflowPolicy::Fault);
+    assert_eq!(result.err(), Some(overflow()));
+}
+
+#[test]
+fn end_to_end_when_wrap_and_uint_product_above_uint_max_then_low_bits() {
+    let bufs = run(UINT_MUL_OUT_OF_RANGE, OverflowPolicy::Wrap).unwrap();
+    assert_eq!(bufs.vars[1].as_i32(), 1);
+}
+
+#[test]
+fn end_to_end_when_saturate_and_uint_product_above_uint_max_then_uint_max() {
+    let bufs = run(UINT_MUL_OUT_OF_RANGE, OverflowPolicy::Saturate).unwrap();
+    assert_eq!(bufs.vars[1].as_i32(), u16::MAX as i32);
+}
+
+#[test]
+fn end_to_end_when_fault_and_uint_product_above_uint_max_then_integer_overflow() {
+    let result = run(UINT_MUL_OUT_OF_RANGE, OverflowPolicy::Fault);
+    assert_eq!(result.err(), Some(overflow()));
+}
+
+#[test]
+fn end_to_end_when_saturate_and_usint_difference_below_zero_then_zero() {
+    let bufs = run(USINT_SUB_BELOW_ZERO, OverflowPolicy::Saturate).unwrap();
+    assert_eq!(bufs.vars[2].as_i32(), 0);
+}
+
+#[test]
+fn end_to_end_when_fault_and_usint_difference_below_zero_then_integer_overflow() {
+    let result = run(USINT_SUB_BELOW_ZERO, OverflowPolicy::Fault);
+    assert_eq!(result.err(), Some(overflow()));
+}
+
+#[test]
+fn end_to_end_when_saturate_and_not_byte_then_complement() {
+    let bufs = run(BYTE_NOT, OverflowPolicy::Saturate).unwrap();
+    assert_eq!(bufs.vars[1].as_i32(), 0xF0);
+}
+
+#[test]
+fn end_to_end_when_fault_and_not_byte_then_complement() {
+    let bufs = run(BYTE_NOT, OverflowPolicy::Fault).unwrap();
+    assert_eq!(bufs.vars[1].as_i32(), 0xF0);
+}
+
+#[test]
+fn end_to_end_when_fault_and_not_word_then_complement() {
+    let bufs = run(WORD_NOT, OverflowPolicy::Fault).unwrap();
+    assert_eq!(bufs.vars[1].as_i32(), 0xFF00);
+}
+
+#[test]
+fn end_to_end_when_saturate_and_shl_byte_then_high_bit_dropped() {
+    let bufs = run(BYTE_SHL, OverflowPolicy::Saturate).unwrap();
+    assert_eq!(bufs.vars[1].as_i32(), 0x02);
+}
+
+#[test]
+fn end_to_end_when_fault_and_shl_byte_then_high_bit_dropped() {
+    let bufs = run(BYTE_SHL, OverflowPolicy::Fault).unwrap();
+    assert_eq!(bufs.vars[1].as_i32(), 0x02);
+}
@@ -0,0 +1,192 @@
+//! End-to-end integration tests for runtime range checks on subrange stores.
+
+use ironplc_container::VarIndex;
+use ironplc_parser::options::CompilerOptions;
+use ironplc_vm::error::Trap;
+
+use crate::common::parse_and_try_run;
+
+fn assert_range_violation(source: &str, value: i128, min: i128, max: i128) {
+    let err = parse_and_try_run(source, &CompilerOptions::default()).unwrap_err();
+    match err.trap {
+        Trap::RangeViolation {
+            value: v,
+            min: mn,
+            max: mx,
+            ..
+        } => assert_eq!((v, mn, mx), (value, min, max)),
+        other => panic!("expected RangeViolation, got {other:?}"),
+    }
+}
+
+e2e_i32!(
+    end_to_end_when_subrange_store_at_upper_bound_then_stores_value,
+    "
+TYPE
+  PERCENT : INT (0..100);
+END_TYPE
+
+PROGRAM main
+  VAR
+    x : PERCENT;
+    y : INT := 100;
+  END_VAR
+  x := y;
+END_PROGRAM
+",
+    &[(0, 100)],
+);
+
+#[test]
+fn end_to_end_when_subrange_store_above_range_then_traps() {
+    let source = "
+TYPE
+  PERCENT : INT (0..100);
+END_TYPE
+
+PROGRAM main
+  VAR
+    x : PERCENT;
+    y : INT := 101;
+  END_VAR
+  x := y;
+END_PROGRAM
+";
+    let err = parse_and_try_run(source, &CompilerOptions::default()).unwrap_err();
+    assert_eq!(
+        err.trap,
+        Trap::RangeViolation {
+            var_index: VarIndex::new(0),
+            value: 101,
+            min: 0,
+            max: 100,
+        }
+    );
+}
+
+#[test]
+fn end_to_end_when_signed_subrange_store_below_range_then_traps() {
+    let source = "
+TYPE
+  OFFSET : INT (-10..10);
+END_TYPE
+
+PROGRAM main
+  VAR
+    x : OFFSET;
+    y : INT := -11;
+  END_VAR
+  x := y;
+END_PROGRAM
+";
+    assert_range_violation(source, -11, -10, 10);
+}
+
+#[test]
+fn end_to_end_when_subrange_array_element_out_of_range_then_traps() {
+    let source = "
+TYPE
+  DIGIT : INT (0..9);
+END_TYPE
+
+PROGRAM main
+  VAR
+    digits : ARRAY[1..3] OF DIGIT;
+    y : INT := 12;
+  END_VAR
+  digits[2] := y;
+END_PROGRAM
+";
+    assert_range_violation(source, 12, 0, 9);
+}
+
+#[test]
+fn end_to_end_when_function_block_input_out_of_range_then_traps() {
+    let source = "
+TYPE
+  PERCENT : INT (0..100);
+END_TYPE
+
+FUNCTION_BLOCK VALVE
+  VAR_INPUT
+    opening : PERCENT;
+  END_VAR
+END_FUNCTION_BLOCK
+
+PROGRAM main
+  VAR
+    v : VALVE;
+    y : INT := 150;
+  END_VAR
+  v(opening := y);
+END_PROGRAM
+";
+    assert_range_violation(source, 150, 0, 100);
+}
+
+#[test]
+fn end_to_end_when_function_argument_out_of_range_then_traps() {
+    let source = "
+TYPE
+  PERCENT : INT (0..100);
+END_TYPE
+
+FUNCTION SCALE : INT
+  VAR_INPUT
+    p : PERCENT;
+  END_VAR
+  SCALE := p * 2;
+END_FUNCTION
+
+PROGRAM main
+  VAR
+    r : INT;
+    y : INT := -1;
+  END_VAR
+  r := SCALE(y);
+END_PROGRAM
+";
+    assert_range_violation(source, -1, 0, 100);
+}
+
+#[test]
+fn end_to_end_when_struct_subrange_field_out_of_range_then_traps() {
+    let source = "
+TYPE
+  PERCENT : INT (0..100);
+  SETPOINT : STRUCT
+    level : PERCENT;
+  END_STRUCT;
+END_TYPE
+
+PROGRAM main
+  VAR
+    s : SETPOINT;
+    y : INT := 200;
+  END_VAR
+  s.level := y;
+END_PROGRAM
+";
+    assert_range_violation(source, 200, 0, 100);
+}
+
+#[test]
+fn end_to_end_when_for_start_out_of_range_then_traps() {
+    let source = "
+TYPE
+  DIGIT : INT (0..9);
+END_TYPE
+
+PROGRAM main
+  VAR
+    i : DIGIT;
+    start : INT := -1;
+    n : INT;
+  END_VAR
+  FOR i := start TO 5 DO
+    n := n + 1;
+  END_FOR;
+END_PROGRAM
+";
+    assert_range_violation(source, -1, 0, 9);
+}
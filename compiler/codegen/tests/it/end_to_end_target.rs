@@ -0,0 +1,119 @@
+//! End-to-end tests for checking a program against a target profile.
+//!
+//! The report rendering and the profile table are unit-tested in
+//! `codegen/src/target.rs`. These tests cover the source -> compile ->
+//! diagnostic wiring: that codegen attributes data region reservations to
+//! their variables and names the deepest call chain.
+
+use ironplc_codegen::target::find_target_profile;
+use ironplc_codegen::CodegenOptions;
+use ironplc_parser::options::CompilerOptions;
+
+use crate::common::try_parse_and_compile_with_codegen;
+
+fn options_for(target: &str) -> CodegenOptions {
+    CodegenOptions {
+        target: find_target_profile(target).copied(),
+        ..CodegenOptions::default()
+    }
+}
+
+#[test]
+fn compile_when_program_fits_target_then_ok() {
+    let source = "
+PROGRAM main
+  VAR x : DINT; END_VAR
+  x := x + 1;
+END_PROGRAM";
+    let result = try_parse_and_compile_with_codegen(
+        source,
+        &CompilerOptions::default(),
+        &options_for("embedded-small"),
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn compile_when_array_exceeds_data_region_then_names_array() {
+    // 4096 elements of 8 bytes is 32 KiB, twice what embedded-small allows.
+    let source = "
+PROGRAM main
+  VAR
+    small : ARRAY[1..4] OF DINT;
+    big : ARRAY[1..4096] OF DINT;
+  END_VAR
+  big[1] := small[1];
+END_PROGRAM";
+    let err = try_parse_and_compile_with_codegen(
+        source,
+        &CompilerOptions::default(),
+        &options_for("embedded-small"),
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, "P4053");
+    assert!(err.described.iter().any(|d| d.starts_with("data region")));
+    assert!(err.secondary[0]
+        .message
+        .starts_with("'big' takes 32768 bytes"));
+}
+
+#[test]
+fn compile_when_program_fits_larger_target_then_ok() {
+    let source = "
+PROGRAM main
+  VAR big : ARRAY[1..4096] OF DINT; END_VAR
+  big[1] := 1;
+END_PROGRAM";
+    let result = try_parse_and_compile_with_codegen(
+        source,
+        &CompilerOptions::default(),
+        &options_for("embedded-large"),
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn compile_when_call_chain_exceeds_call_depth_then_names_chain() {
+    // main -> F1 -> ... -> F8 is nine frames, one more than embedded-small allows.
+    let mut source = String::from(
+        "
+FUNCTION F8 : INT
+  VAR_INPUT n : INT; END_VAR
+  F8 := n;
+END_FUNCTION
+",
+    );
+    for level in (1..8).rev() {
+        source.push_str(&format!(
+            "
+FUNCTION F{level} : INT
+  VAR_INPUT n : INT; END_VAR
+  F{level} := F{next}(n := n);
+END_FUNCTION
+",
+            next = level + 1
+        ));
+    }
+    source.push_str(
+        "
+PROGRAM main
+  VAR x : INT; END_VAR
+  x := F1(n := x);
+END_PROGRAM",
+    );
+
+    let err = try_parse_and_compile_with_codegen(
+        &source,
+        &CompilerOptions::default(),
+        &options_for("embedded-small"),
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, "P4053");
+    assert!(err
+        .described
+        .contains(&String::from("call depth needs 9 frames, target allows 8")));
+    assert!(err.help[0].starts_with("Deepest call chain: "));
+    assert!(err.help[0].ends_with("-> f7 -> f8"));
+}
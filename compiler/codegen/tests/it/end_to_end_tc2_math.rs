@@ -39,6 +39,7 @@ fn run_with_tc2_math(source: &str) -> VmBuffers {
 
     let codegen_options = ironplc_codegen::CodegenOptions {
         system_uptime_global: false,
+        ..ironplc_codegen::CodegenOptions::default()
     };
     let container = compile(
         &analyzed,
@@ -0,0 +1,206 @@
+//! End-to-end tests for the worst-case execution time estimate and the
+//! debug section's LOOP_BOUND table (tag 10) it reads.
+
+use std::collections::HashMap;
+
+use ironplc_analyzer::stages::resolve_types;
+use ironplc_codegen::wcet::{estimate, Cost};
+use ironplc_codegen::{compile, CodegenOptions, SourceLookup};
+use ironplc_container::debug_section::loop_kind;
+use ironplc_container::{Container, FunctionId};
+use ironplc_dsl::core::FileId;
+use ironplc_parser::options::CompilerOptions;
+use ironplc_parser::parse_program;
+
+struct MapLookup(HashMap<FileId, Vec<u8>>);
+
+impl SourceLookup for MapLookup {
+    fn source_bytes(&self, file_id: &FileId) -> Option<&[u8]> {
+        self.0.get(file_id).map(Vec::as_slice)
+    }
+}
+
+/// Parses and compiles `source` with pragmas allowed, so that loop bound
+/// pragmas are read from the source bytes.
+fn compile_with_source(source: &str) -> Container {
+    let file_id = FileId::from_string("test.st");
+    let options = CompilerOptions {
+        allow_pragmas: true,
+        ..CompilerOptions::default()
+    };
+    let library = parse_program(source, &file_id, &options).unwrap();
+    let (analyzed, ctx) = resolve_types(&[&library], &options).unwrap();
+    let lookup = MapLookup(HashMap::from([(file_id, source.as_bytes().to_vec())]));
+    compile(&analyzed, &ctx, &CodegenOptions::default(), &lookup).unwrap()
+}
+
+/// The cost of the only task in `container`.
+fn task_cost(container: &Container) -> Cost {
+    let report = estimate(container).unwrap();
+    assert_eq!(report.tasks.len(), 1);
+    report.tasks[0].cost
+}
+
+fn units(cost: Cost) -> u64 {
+    match cost {
+        Cost::Bounded(units) => units,
+        Cost::Unbounded => panic!("expected a bounded cost"),
+    }
+}
+
+#[test]
+fn wcet_when_for_loop_has_constant_bounds_then_loop_bound_recorded() {
+    let source = "PROGRAM main
+  VAR i : DINT; x : DINT; END_VAR
+  FOR i := 1 TO 10 DO
+    x := x + i;
+  END_FOR;
+END_PROGRAM";
+    let container = compile_with_source(source);
+    let debug = container.debug_section.as_ref().unwrap();
+
+    let loops: Vec<_> = debug
+        .loop_bounds
+        .iter()
+        .filter(|lp| lp.function_id == FunctionId::SCAN)
+        .collect();
+    assert_eq!(loops.len(), 1);
+    assert_eq!(loops[0].kind, loop_kind::FOR);
+    assert_eq!(loops[0].max_iterations, Some(10));
+    assert_eq!(loops[0].source_line.raw(), 3);
+    assert!(loops[0].start_offset < loops[0].end_offset);
+}
+
+#[test]
+fn wcet_when_for_loop_runs_more_iterations_then_costs_more() {
+    let short = "PROGRAM main
+  VAR i : DINT; x : DINT; END_VAR
+  FOR i := 1 TO 10 DO
+    x := x + i;
+  END_FOR;
+END_PROGRAM";
+    let long = short.replace("TO 10", "TO 100");
+
+    let short_cost = units(task_cost(&compile_with_source(short)));
+    let long_cost = units(task_cost(&compile_with_source(&long)));
+
+    assert!(long_cost > short_cost);
+    // Ninety more passes through the body and increment, each at least
+    // a few cost units.
+    assert!(long_cost - short_cost >= 90 * 3);
+}
+
+#[test]
+fn wcet_when_for_loop_body_writes_control_variable_then_unbounded() {
+    let source = "PROGRAM main
+  VAR i : DINT; END_VAR
+  FOR i := 1 TO 10 DO
+    i := 1;
+  END_FOR;
+END_PROGRAM";
+    let container = compile_with_source(source);
+    let report = estimate(&container).unwrap();
+
+    assert_eq!(report.tasks[0].cost, Cost::Unbounded);
+    assert_eq!(report.unbounded_loops.len(), 1);
+    assert_eq!(report.unbounded_loops[0].kind, Some("FOR"));
+    assert_eq!(report.unbounded_loops[0].line, 3);
+}
+
+#[test]
+fn wcet_when_while_loop_has_no_bound_then_reported_unbounded() {
+    let source = "PROGRAM main
+  VAR x : DINT; END_VAR
+  WHILE x < 10 DO
+    x := x + 1;
+  END_WHILE;
+END_PROGRAM";
+    let container = compile_with_source(source);
+    let report = estimate(&container).unwrap();
+
+    assert_eq!(report.tasks[0].cost, Cost::Unbounded);
+    assert_eq!(report.unbounded_loops.len(), 1);
+    let unbounded = &report.unbounded_loops[0];
+    assert_eq!(unbounded.kind, Some("WHILE"));
+    assert_eq!(unbounded.file.as_deref(), Some("test.st"));
+    assert_eq!(unbounded.line, 3);
+}
+
+#[test]
+fn wcet_when_while_loop_has_bound_pragma_then_bounded() {
+    let source = "PROGRAM main
+  VAR x : DINT; END_VAR
+  {attribute 'loop_bound' := '16'}
+  WHILE x < 10 DO
+    x := x + 1;
+  END_WHILE;
+END_PROGRAM";
+    let container = compile_with_source(source);
+    let report = estimate(&container).unwrap();
+
+    assert!(matches!(report.tasks[0].cost, Cost::Bounded(_)));
+    assert!(report.unbounded_loops.is_empty());
+}
+
+#[test]
+fn wcet_when_repeat_loop_has_bound_pragma_then_bounded() {
+    let source = "PROGRAM main
+  VAR x : DINT; END_VAR
+  {attribute 'loop_bound' := '4'}
+  REPEAT
+    x := x + 1;
+  UNTIL x > 3
+  END_REPEAT;
+END_PROGRAM";
+    let container = compile_with_source(source);
+    let debug = container.debug_section.as_ref().unwrap();
+
+    assert_eq!(debug.loop_bounds.len(), 1);
+    assert_eq!(debug.loop_bounds[0].kind, loop_kind::REPEAT);
+    assert_eq!(debug.loop_bounds[0].max_iterations, Some(4));
+    assert!(matches!(task_cost(&container), Cost::Bounded(_)));
+}
+
+#[test]
+fn wcet_when_program_calls_function_then_includes_callee_cost() {
+    let without_call = "PROGRAM main
+  VAR x : DINT; END_VAR
+  x := x + 1;
+END_PROGRAM";
+    let with_call = "FUNCTION work : DINT
+  VAR_INPUT n : DINT; END_VAR
+  VAR i : DINT; END_VAR
+  work := 0;
+  FOR i := 1 TO 50 DO
+    work := work + n;
+  END_FOR;
+END_FUNCTION
+
+PROGRAM main
+  VAR x : DINT; END_VAR
+  x := work(n := x);
+END_PROGRAM";
+
+    let base = units(task_cost(&compile_with_source(without_call)));
+    let called = units(task_cost(&compile_with_source(with_call)));
+
+    // The callee's fifty iterations dominate the caller's own cost.
+    assert!(called > base + 50 * 3);
+}
+
+#[test]
+fn wcet_when_rendered_then_lists_tasks_and_unbounded_loops() {
+    let source = "PROGRAM main
+  VAR x : DINT; END_VAR
+  WHILE x < 10 DO
+    x := x + 1;
+  END_WHILE;
+END_PROGRAM";
+    let container = compile_with_source(source);
+    let text = estimate(&container).unwrap().render(50);
+
+    assert!(text.starts_with("Worst-case execution time (50 ns per cost unit):\n"));
+    assert!(text.contains("unbounded"));
+    assert!(text.contains("Unbounded loops:\n  WHILE in "));
+    assert!(text.contains("at test.st:3:"));
+}
@@ -14,6 +14,7 @@ mod common;
 
 mod codegen_max_call_depth;
 mod compile_array;
+mod compile_array_bounds;
 mod compile_bool;
 mod compile_case;
 mod compile_cmp;
@@ -23,6 +24,7 @@ mod compile_func_forms;
 mod compile_if;
 mod compile_loops;
 mod compile_mux;
+mod compile_range_check;
 mod compile_shift;
 mod compile_struct;
 mod compile_this_super;
@@ -114,8 +116,10 @@ mod end_to_end_mux_float;
 mod end_to_end_mux_lint;
 mod end_to_end_neg;
 mod end_to_end_nested;
+mod end_to_end_overflow_policy;
 mod end_to_end_partial_access;
 mod end_to_end_pow;
+mod end_to_end_range_check;
 mod end_to_end_ref;
 mod end_to_end_ref_to_array;
 mod end_to_end_reference_to;
@@ -133,6 +137,7 @@ mod end_to_end_struct;
 mod end_to_end_sub;
 mod end_to_end_subrange;
 mod end_to_end_system_uptime;
+mod end_to_end_target;
 mod end_to_end_tc2_math;
 mod end_to_end_tc2_utilities;
 mod end_to_end_time_function;
@@ -144,6 +149,7 @@ mod end_to_end_types;
 mod end_to_end_user_fb;
 mod end_to_end_user_function;
 mod end_to_end_var_temp;
+mod end_to_end_wcet;
 mod end_to_end_write_variable_raw;
 mod end_to_end_wstring;
 mod wire_format;
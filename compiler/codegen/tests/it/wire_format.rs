@@ -209,6 +209,20 @@ fn opcode_constants_when_stack_op_family_then_pinned_bytes() {
     assert_eq!(opcode::SWAP, 0x92);
 }
 
+#[test]
+fn opcode_constants_when_range_check_family_then_pinned_bytes() {
+    assert_eq!(opcode::RANGE_CHECK_I32, 0xF8);
+    assert_eq!(opcode::RANGE_CHECK_I64, 0xF9);
+    assert_eq!(opcode::RANGE_CHECK_U32, 0xFA);
+    assert_eq!(opcode::RANGE_CHECK_U64, 0xFB);
+}
+
+#[test]
+fn opcode_constants_when_array_unchecked_family_then_pinned_bytes() {
+    assert_eq!(opcode::LOAD_ARRAY_UNCHECKED, 0xFC);
+    assert_eq!(opcode::STORE_ARRAY_UNCHECKED, 0xFD);
+}
+
 #[test]
 fn opcode_constants_when_builtin_then_pinned_byte() {
     assert_eq!(opcode::BUILTIN, 0x94);
@@ -225,6 +239,16 @@ fn builtin_func_ids_when_unnamed_arithmetic_builtins_then_pinned_values() {
     assert_eq!(opcode::builtin::MOD_F32, 0x03A6);
 }
 
+#[test]
+fn builtin_func_ids_when_unsigned_arithmetic_builtins_then_pinned_values() {
+    assert_eq!(opcode::builtin::ADD_U32, 0x03A7);
+    assert_eq!(opcode::builtin::SUB_U32, 0x03A8);
+    assert_eq!(opcode::builtin::MUL_U32, 0x03A9);
+    assert_eq!(opcode::builtin::ADD_U64, 0x03AA);
+    assert_eq!(opcode::builtin::SUB_U64, 0x03AB);
+    assert_eq!(opcode::builtin::MUL_U64, 0x03AC);
+}
+
 #[test]
 fn opcode_constants_when_fb_family_then_pinned_bytes() {
     assert_eq!(opcode::FB_LOAD_INSTANCE, 0x98);
@@ -831,6 +855,8 @@ fn opcode_pins_when_compared_to_assigned_set_then_complete() {
         opcode::STORE_ARRAY,
         opcode::LOAD_ARRAY_DEREF,
         opcode::STORE_ARRAY_DEREF,
+        opcode::LOAD_ARRAY_UNCHECKED,
+        opcode::STORE_ARRAY_UNCHECKED,
         opcode::STR_INIT_ARRAY,
         opcode::STR_LOAD_ARRAY_ELEM,
         opcode::STR_STORE_ARRAY_ELEM,
@@ -844,6 +870,10 @@ fn opcode_pins_when_compared_to_assigned_set_then_complete() {
         opcode::STR_INIT,
         opcode::CMP_BR_I32,
         opcode::CMP_BR_I64,
+        opcode::RANGE_CHECK_I32,
+        opcode::RANGE_CHECK_I64,
+        opcode::RANGE_CHECK_U32,
+        opcode::RANGE_CHECK_U64,
         opcode::FIND_STR,
         opcode::REPLACE_STR,
         opcode::INSERT_STR,
@@ -7,8 +7,8 @@ use crate::const_type::ConstType;
 use crate::constant_pool::{ConstEntry, ConstantPool};
 use crate::container::Container;
 use crate::debug_section::{
-    DebugSection, EnumDefEntry, FuncNameEntry, LineMapEntry, SourceFileEntry, StringLayoutEntry,
-    VarNameEntry,
+    DebugSection, EnumDefEntry, FuncNameEntry, LineMapEntry, LoopBoundEntry, SourceFileEntry,
+    StringLayoutEntry, VarNameEntry,
 };
 use crate::header::FileHeader;
 use crate::id_types::{FunctionId, InstanceId, TaskId, VarIndex};
@@ -42,6 +42,7 @@ pub struct ContainerBuilder {
     debug_string_layouts: Vec<StringLayoutEntry>,
     debug_source_files: Vec<SourceFileEntry>,
     debug_enum_defs: Vec<EnumDefEntry>,
+    debug_loop_bounds: Vec<LoopBoundEntry>,
 }
 
 impl ContainerBuilder {
@@ -71,6 +72,7 @@ impl ContainerBuilder {
             debug_string_layouts: Vec::new(),
             debug_source_files: Vec::new(),
             debug_enum_defs: Vec::new(),
+            debug_loop_bounds: Vec::new(),
         }
     }
 
@@ -261,6 +263,12 @@ impl ContainerBuilder {
         self
     }
 
+    /// Adds a loop extent and iteration bound to the debug section.
+    pub fn add_loop_bound(mut self, entry: LoopBoundEntry) -> Self {
+        self.debug_loop_bounds.push(entry);
+        self
+    }
+
     /// Adds a STRING variable layout entry to the debug section.
     pub fn add_string_layout(mut self, entry: StringLayoutEntry) -> Self {
         self.debug_string_layouts.push(entry);
@@ -372,6 +380,7 @@ impl ContainerBuilder {
             && self.debug_string_layouts.is_empty()
             && self.debug_source_files.is_empty()
             && self.debug_enum_defs.is_empty()
+            && self.debug_loop_bounds.is_empty()
         {
             None
         } else {
@@ -384,6 +393,7 @@ impl ContainerBuilder {
                 string_layouts: self.debug_string_layouts,
                 source_files: self.debug_source_files,
                 enum_defs: self.debug_enum_defs,
+                loop_bounds: self.debug_loop_bounds,
             })
         };
 
@@ -575,6 +585,31 @@ mod tests {
         assert_eq!(debug.line_map[0], entry);
     }
 
+    #[test]
+    fn builder_when_add_loop_bound_then_included_in_debug_section() {
+        use crate::debug_section::{loop_kind, LoopBoundEntry};
+
+        let entry = LoopBoundEntry {
+            function_id: FunctionId::SCAN,
+            start_offset: 0,
+            end_offset: 8,
+            kind: loop_kind::FOR,
+            max_iterations: Some(5),
+            file_id: SourceFileId::new(0),
+            source_line: SourceLine::new(3),
+            source_column: SourceColumn::new(5),
+        };
+
+        let container = ContainerBuilder::new()
+            .num_variables(0)
+            .add_function(FunctionId::INIT, &[0x8C], 0, 0, 0)
+            .add_loop_bound(entry)
+            .build();
+
+        let debug = container.debug_section.unwrap();
+        assert_eq!(debug.loop_bounds, vec![entry]);
+    }
+
     #[test]
     fn builder_when_line_map_entries_unsorted_then_build_sorts_them() {
         use crate::debug_section::LineMapEntry;
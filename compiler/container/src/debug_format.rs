@@ -6,8 +6,10 @@
 //! `specs/design/vm-cli.md` so the CLI dump remains spec-compliant.
 //! Tools such as the playground that need richer rendering (decimal
 //! seconds, `D#YYYY-MM-DD`, enum value names) can still wrap or replace
-//! these helpers locally.
+//! these helpers locally. [`parse_variable_value`] is the inverse of
+//! [`format_variable_value`], for tools that set variables from text.
 
+use core::fmt;
 use std::collections::HashMap;
 use std::format;
 use std::string::String;
@@ -75,6 +77,153 @@ pub fn format_variable_value(raw: u64, tag: u8) -> String {
     }
 }
 
+/// Reasons text could not be turned into a variable's raw slot value.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ValueParseError {
+    /// The text is not a literal of the variable's type.
+    Invalid,
+    /// The literal does not fit the variable's type.
+    OutOfRange,
+    /// Values of the variable's type cannot be set from text (strings, dates,
+    /// and types without an elementary tag).
+    Unsupported,
+}
+
+impl fmt::Display for ValueParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueParseError::Invalid => write!(f, "not a literal of the variable's type"),
+            ValueParseError::OutOfRange => write!(f, "out of range for the variable's type"),
+            ValueParseError::Unsupported => write!(f, "values of this type cannot be set"),
+        }
+    }
+}
+
+/// Parses `text` as a value of the IEC type `tag` and returns the raw 64-bit
+/// slot the VM would hold for it.
+///
+/// Accepts what [`format_variable_value`] renders — `TRUE`, `-5`, `1.5`,
+/// `16#ABCD`, `T#250ms` — plus `2#`/`8#`/`16#` integers, `_` digit
+/// separators, and durations built from `d`, `h`, `m`, `s` and `ms` parts. Values
+/// of 32 bits or fewer are sign-extended, as the VM's 32-bit stores do, so a
+/// parsed value compares equal to one the program computed.
+pub fn parse_variable_value(text: &str, tag: u8) -> Result<u64, ValueParseError> {
+    let text = text.trim();
+    match tag {
+        iec_type_tag::BOOL => match text.to_ascii_uppercase().as_str() {
+            "TRUE" | "1" => Ok(1),
+            "FALSE" | "0" => Ok(0),
+            _ => Err(ValueParseError::Invalid),
+        },
+        iec_type_tag::SINT => parse_in_range(text, i8::MIN as i128, i8::MAX as i128),
+        iec_type_tag::INT => parse_in_range(text, i16::MIN as i128, i16::MAX as i128),
+        iec_type_tag::DINT => parse_in_range(text, i32::MIN as i128, i32::MAX as i128),
+        iec_type_tag::LINT => parse_in_range(text, i64::MIN as i128, i64::MAX as i128),
+        iec_type_tag::USINT | iec_type_tag::BYTE => parse_in_range(text, 0, u8::MAX as i128),
+        iec_type_tag::UINT | iec_type_tag::WORD => parse_in_range(text, 0, u16::MAX as i128),
+        iec_type_tag::UDINT | iec_type_tag::DWORD => {
+            parse_in_range(text, 0, u32::MAX as i128).map(|raw| raw as u32 as i32 as i64 as u64)
+        }
+        iec_type_tag::ULINT | iec_type_tag::LWORD => parse_in_range(text, 0, u64::MAX as i128),
+        iec_type_tag::REAL => text
+            .parse::<f32>()
+            .map(|v| v.to_bits() as u64)
+            .map_err(|_| ValueParseError::Invalid),
+        iec_type_tag::LREAL => text
+            .parse::<f64>()
+            .map(f64::to_bits)
+            .map_err(|_| ValueParseError::Invalid),
+        iec_type_tag::TIME => {
+            let ms = parse_duration_ms(text, &["T#", "TIME#"])?;
+            i32::try_from(ms)
+                .map(|v| v as i64 as u64)
+                .map_err(|_| ValueParseError::OutOfRange)
+        }
+        iec_type_tag::LTIME => {
+            let ms = parse_duration_ms(text, &["LTIME#", "LT#"])?;
+            i64::try_from(ms)
+                .map(|v| v as u64)
+                .map_err(|_| ValueParseError::OutOfRange)
+        }
+        _ => Err(ValueParseError::Unsupported),
+    }
+}
+
+/// Parses an integer literal and checks it lies in `min..=max`, returning it
+/// sign-extended to 64 bits.
+fn parse_in_range(text: &str, min: i128, max: i128) -> Result<u64, ValueParseError> {
+    let value = parse_integer(text)?;
+    if value < min || value > max {
+        return Err(ValueParseError::OutOfRange);
+    }
+    Ok(value as i64 as u64)
+}
+
+/// Parses a decimal or `2#`/`8#`/`16#` integer literal with an optional sign
+/// and `_` separators.
+fn parse_integer(text: &str) -> Result<i128, ValueParseError> {
+    let digits: String = text.chars().filter(|c| *c != '_').collect();
+    let (negative, unsigned) = match digits.as_bytes().first() {
+        Some(b'-') => (true, &digits[1..]),
+        Some(b'+') => (false, &digits[1..]),
+        _ => (false, digits.as_str()),
+    };
+    let (radix, body) = match unsigned.split_once('#') {
+        Some(("2", body)) => (2, body),
+        Some(("8", body)) => (8, body),
+        Some(("16", body)) => (16, body),
+        Some(_) => return Err(ValueParseError::Invalid),
+        None => (10, unsigned),
+    };
+    if body.is_empty() || body.starts_with(['+', '-']) {
+        return Err(ValueParseError::Invalid);
+    }
+    let magnitude = i128::from_str_radix(body, radix).map_err(|_| ValueParseError::Invalid)?;
+    Ok(if negative { -magnitude } else { magnitude })
+}
+
+/// Parses a duration in milliseconds: a bare integer count of milliseconds,
+/// or one of `prefixes` (case-insensitive) followed by `d`/`h`/`m`/`s`/`ms`
+/// parts such as `1m30s`, optionally negative.
+fn parse_duration_ms(text: &str, prefixes: &[&str]) -> Result<i128, ValueParseError> {
+    let upper = text.to_ascii_uppercase();
+    let Some(body) = prefixes.iter().find_map(|p| upper.strip_prefix(p)) else {
+        return parse_integer(text);
+    };
+    let (negative, mut rest) = match body.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, body),
+    };
+    if rest.is_empty() {
+        return Err(ValueParseError::Invalid);
+    }
+    let mut total: i128 = 0;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '_')
+            .ok_or(ValueParseError::Invalid)?;
+        let count = parse_integer(&rest[..digits])?;
+        let units = &rest[digits..];
+        let (scale, len) = if units.starts_with("MS") {
+            (1, 2)
+        } else {
+            match units.as_bytes()[0] {
+                b'D' => (86_400_000, 1),
+                b'H' => (3_600_000, 1),
+                b'M' => (60_000, 1),
+                b'S' => (1_000, 1),
+                _ => return Err(ValueParseError::Invalid),
+            }
+        };
+        total = count
+            .checked_mul(scale)
+            .and_then(|ms| total.checked_add(ms))
+            .ok_or(ValueParseError::OutOfRange)?;
+        rest = &units[len..];
+    }
+    Ok(if negative { -total } else { total })
+}
+
 /// Reasons a STRING variable's bytes could not be read from the data region.
 ///
 /// Distinguished (rather than collapsed to a sentinel string) so a caller
@@ -329,4 +478,79 @@ mod tests {
             "'$00$01$FF'"
         );
     }
+
+    #[rstest::rstest]
+    #[case::bool(iec_type_tag::BOOL, 1)]
+    #[case::sint(iec_type_tag::SINT, -5i64 as u64)]
+    #[case::dint(iec_type_tag::DINT, -1i64 as u64)]
+    #[case::udint(iec_type_tag::UDINT, 0xFFFF_FFFF_FFFF_FFFF)]
+    #[case::real(iec_type_tag::REAL, 1.5f32.to_bits() as u64)]
+    #[case::lreal(iec_type_tag::LREAL, 0.25f64.to_bits())]
+    #[case::word(iec_type_tag::WORD, 0xABCD)]
+    #[case::time(iec_type_tag::TIME, 250)]
+    fn parse_variable_value_when_formatted_value_then_round_trips(
+        #[case] tag: u8,
+        #[case] raw: u64,
+    ) {
+        let text = format_variable_value(raw, tag);
+        assert_eq!(parse_variable_value(&text, tag), Ok(raw));
+    }
+
+    #[test]
+    fn parse_variable_value_when_based_or_separated_integer_then_parses() {
+        assert_eq!(parse_variable_value("2#1010", iec_type_tag::BYTE), Ok(10));
+        assert_eq!(parse_variable_value("8#17", iec_type_tag::INT), Ok(15));
+        assert_eq!(
+            parse_variable_value("1_000_000", iec_type_tag::DINT),
+            Ok(1_000_000)
+        );
+    }
+
+    #[test]
+    fn parse_variable_value_when_out_of_range_then_error() {
+        assert_eq!(
+            parse_variable_value("128", iec_type_tag::SINT),
+            Err(ValueParseError::OutOfRange)
+        );
+        assert_eq!(
+            parse_variable_value("-1", iec_type_tag::UINT),
+            Err(ValueParseError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn parse_variable_value_when_not_a_literal_then_invalid() {
+        assert_eq!(
+            parse_variable_value("maybe", iec_type_tag::BOOL),
+            Err(ValueParseError::Invalid)
+        );
+        assert_eq!(
+            parse_variable_value("3#12", iec_type_tag::DINT),
+            Err(ValueParseError::Invalid)
+        );
+        assert_eq!(
+            parse_variable_value("T#5x", iec_type_tag::TIME),
+            Err(ValueParseError::Invalid)
+        );
+    }
+
+    #[test]
+    fn parse_variable_value_when_compound_duration_then_milliseconds() {
+        assert_eq!(
+            parse_variable_value("t#1m30s", iec_type_tag::TIME),
+            Ok(90_000)
+        );
+        assert_eq!(
+            parse_variable_value("LTIME#1d", iec_type_tag::LTIME),
+            Ok(86_400_000)
+        );
+    }
+
+    #[test]
+    fn parse_variable_value_when_string_then_unsupported() {
+        assert_eq!(
+            parse_variable_value("'hi'", iec_type_tag::STRING),
+            Err(ValueParseError::Unsupported)
+        );
+    }
 }
@@ -13,6 +13,7 @@ const TAG_FUNC_NAME: u16 = 3;
 const TAG_STRING_LAYOUT: u16 = 4;
 const TAG_SOURCE_FILE: u16 = 6;
 const TAG_ENUM_DEF: u16 = 9;
+const TAG_LOOP_BOUND: u16 = 10;
 
 /// Size of each StringLayoutEntry on disk: var_index(2) + data_offset(4) + max_length(2) = 8 bytes.
 const STRING_LAYOUT_ENTRY_SIZE: u32 = 8;
@@ -21,6 +22,11 @@ const STRING_LAYOUT_ENTRY_SIZE: u32 = 8;
 /// + file_id(2) + source_line(2) + source_column(2) = 10 bytes.
 const LINE_MAP_ENTRY_SIZE: u32 = 10;
 
+/// Size of each LoopBoundEntry on disk: function_id(2) + start_offset(2)
+/// + end_offset(2) + kind(1) + bounded(1) + max_iterations(8) + file_id(2)
+/// + source_line(2) + source_column(2) = 22 bytes.
+const LOOP_BOUND_ENTRY_SIZE: u32 = 22;
+
 /// BLAKE3 digest size in bytes (32, default output length).
 pub const SOURCE_FILE_HASH_LEN: usize = 32;
 
@@ -75,6 +81,44 @@ pub mod var_section {
     pub const VAR_IN_OUT: u8 = 4;
     pub const VAR_EXTERNAL: u8 = 5;
     pub const VAR_GLOBAL: u8 = 6;
+
+    /// The IEC 61131-3 keyword for a section, e.g. `"VAR_INPUT"`; `None` for
+    /// a value outside the encoding.
+    pub fn keyword(section: u8) -> Option<&'static str> {
+        match section {
+            VAR => Some("VAR"),
+            VAR_TEMP => Some("VAR_TEMP"),
+            VAR_INPUT => Some("VAR_INPUT"),
+            VAR_OUTPUT => Some("VAR_OUTPUT"),
+            VAR_IN_OUT => Some("VAR_IN_OUT"),
+            VAR_EXTERNAL => Some("VAR_EXTERNAL"),
+            VAR_GLOBAL => Some("VAR_GLOBAL"),
+            _ => None,
+        }
+    }
+
+    /// The section a [`keyword`] names.
+    pub fn from_keyword(keyword: &str) -> Option<u8> {
+        (VAR..=VAR_GLOBAL).find(|&section| self::keyword(section) == Some(keyword))
+    }
+}
+
+/// Loop statement encoding for [`LoopBoundEntry::kind`].
+pub mod loop_kind {
+    pub const FOR: u8 = 0;
+    pub const WHILE: u8 = 1;
+    pub const REPEAT: u8 = 2;
+
+    /// The IEC 61131-3 keyword for a loop kind, e.g. `"WHILE"`; `None` for
+    /// a value outside the encoding.
+    pub fn keyword(kind: u8) -> Option<&'static str> {
+        match kind {
+            FOR => Some("FOR"),
+            WHILE => Some("WHILE"),
+            REPEAT => Some("REPEAT"),
+            _ => None,
+        }
+    }
 }
 
 /// A variable name entry (debug section Tag 2).
@@ -160,6 +204,33 @@ pub struct EnumDefEntry {
     pub values: Vec<String>,
 }
 
+/// The bytecode extent and iteration bound of a loop (debug section Tag 10).
+///
+/// Every back edge of the loop targets `start_offset` and every exit lands
+/// at or after `end_offset`, so a timing analysis can treat the bytes in
+/// between as one region that runs at most `max_iterations` times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoopBoundEntry {
+    /// Function containing the loop.
+    pub function_id: FunctionId,
+    /// Offset of the instruction the loop's back edges jump to.
+    pub start_offset: u16,
+    /// Offset just past the loop's last instruction.
+    pub end_offset: u16,
+    /// The loop statement, one of the [`loop_kind`] values.
+    pub kind: u8,
+    /// The most times the body runs, or `None` when the compiler knows no
+    /// bound.
+    pub max_iterations: Option<u64>,
+    /// Index into the SOURCE_FILE_TABLE (debug section Tag 6) of the file
+    /// that declares the loop.
+    pub file_id: SourceFileId,
+    /// Source line of the loop statement (1-based; 0 = unknown).
+    pub source_line: SourceLine,
+    /// Source column of the loop statement (1-based; 0 = unknown).
+    pub source_column: SourceColumn,
+}
+
 /// The debug section of a bytecode container.
 #[derive(Clone, Debug, Default)]
 pub struct DebugSection {
@@ -184,6 +255,8 @@ pub struct DebugSection {
     /// Enumeration type definitions (debug section Tag 9).
     /// Maps enum type names to their value names in ordinal order.
     pub enum_defs: Vec<EnumDefEntry>,
+    /// Loop extents and iteration bounds (debug section Tag 10).
+    pub loop_bounds: Vec<LoopBoundEntry>,
 }
 
 /// Sorts a line map by `(function_id, bytecode_offset)` to satisfy the
@@ -205,6 +278,7 @@ impl DebugSection {
             + self.string_layout_payload_size()
             + self.source_file_payload_size()
             + self.enum_def_payload_size()
+            + self.loop_bound_payload_size()
     }
 
     /// Writes the debug section to the given writer.
@@ -243,6 +317,11 @@ impl DebugSection {
             w.write_all(&0u16.to_le_bytes())?; // reserved
             w.write_all(&self.enum_def_payload_size().to_le_bytes())?;
         }
+        if !self.loop_bounds.is_empty() {
+            w.write_all(&TAG_LOOP_BOUND.to_le_bytes())?;
+            w.write_all(&0u16.to_le_bytes())?; // reserved
+            w.write_all(&self.loop_bound_payload_size().to_le_bytes())?;
+        }
 
         // Write payloads in directory order.
         if !self.line_map.is_empty() {
@@ -263,6 +342,9 @@ impl DebugSection {
         if !self.enum_defs.is_empty() {
             self.write_enum_defs(w)?;
         }
+        if !self.loop_bounds.is_empty() {
+            self.write_loop_bounds(w)?;
+        }
 
         Ok(())
     }
@@ -290,6 +372,7 @@ impl DebugSection {
         let mut string_layouts = Vec::new();
         let mut source_files = Vec::new();
         let mut enum_defs = Vec::new();
+        let mut loop_bounds = Vec::new();
 
         // Read payloads in directory order, skipping unknown tags.
         for (tag, size) in &directory {
@@ -312,6 +395,9 @@ impl DebugSection {
                 TAG_ENUM_DEF => {
                     enum_defs = Self::read_enum_defs(r)?;
                 }
+                TAG_LOOP_BOUND => {
+                    loop_bounds = Self::read_loop_bounds(r, *size)?;
+                }
                 _ => {
                     // Skip unknown tags by reading and discarding their payload.
                     let mut skip_buf = vec![0u8; *size as usize];
@@ -327,6 +413,7 @@ impl DebugSection {
             string_layouts,
             source_files,
             enum_defs,
+            loop_bounds,
         })
     }
 
@@ -350,6 +437,9 @@ impl DebugSection {
         if !self.enum_defs.is_empty() {
             count += 1;
         }
+        if !self.loop_bounds.is_empty() {
+            count += 1;
+        }
         count
     }
 
@@ -423,6 +513,15 @@ impl DebugSection {
         }
     }
 
+    /// Returns the name of the variable at `var_index`, or `None` when the
+    /// variable table has no entry for it.
+    pub fn var_name(&self, var_index: VarIndex) -> Option<&str> {
+        self.var_names
+            .iter()
+            .find(|e| e.var_index == var_index)
+            .map(|e| e.name.as_str())
+    }
+
     fn var_name_payload_size(&self) -> u32 {
         if self.var_names.is_empty() {
             return 0;
@@ -695,6 +794,61 @@ impl DebugSection {
         }
         Ok(entries)
     }
+
+    fn loop_bound_payload_size(&self) -> u32 {
+        if self.loop_bounds.is_empty() {
+            return 0;
+        }
+        // count(2) + entries
+        2 + self.loop_bounds.len() as u32 * LOOP_BOUND_ENTRY_SIZE
+    }
+
+    fn write_loop_bounds(&self, w: &mut impl Write) -> Result<(), ContainerError> {
+        w.write_all(&(self.loop_bounds.len() as u16).to_le_bytes())?;
+        for entry in &self.loop_bounds {
+            w.write_all(&entry.function_id.to_le_bytes())?;
+            w.write_all(&entry.start_offset.to_le_bytes())?;
+            w.write_all(&entry.end_offset.to_le_bytes())?;
+            w.write_all(&[entry.kind, u8::from(entry.max_iterations.is_some())])?;
+            w.write_all(&entry.max_iterations.unwrap_or(0).to_le_bytes())?;
+            w.write_all(&entry.file_id.to_le_bytes())?;
+            w.write_all(&entry.source_line.to_le_bytes())?;
+            w.write_all(&entry.source_column.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn read_loop_bounds(
+        r: &mut impl Read,
+        _size: u32,
+    ) -> Result<Vec<LoopBoundEntry>, ContainerError> {
+        let mut buf2 = [0u8; 2];
+        r.read_exact(&mut buf2)?;
+        let count = u16::from_le_bytes(buf2) as usize;
+
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            let mut b = [0u8; LOOP_BOUND_ENTRY_SIZE as usize];
+            r.read_exact(&mut b)?;
+            let mut max_iterations = [0u8; 8];
+            max_iterations.copy_from_slice(&b[8..16]);
+            entries.push(LoopBoundEntry {
+                function_id: FunctionId::new(u16::from_le_bytes([b[0], b[1]])),
+                start_offset: u16::from_le_bytes([b[2], b[3]]),
+                end_offset: u16::from_le_bytes([b[4], b[5]]),
+                kind: b[6],
+                max_iterations: match b[7] {
+                    0 => None,
+                    1 => Some(u64::from_le_bytes(max_iterations)),
+                    _ => return Err(ContainerError::InvalidDebugSection),
+                },
+                file_id: SourceFileId::new(u16::from_le_bytes([b[16], b[17]])),
+                source_line: SourceLine::new(u16::from_le_bytes([b[18], b[19]])),
+                source_column: SourceColumn::new(u16::from_le_bytes([b[20], b[21]])),
+            });
+        }
+        Ok(entries)
+    }
 }
 
 #[cfg(test)]
@@ -728,6 +882,7 @@ mod tests {
             string_layouts: vec![],
             source_files: vec![],
             enum_defs: vec![],
+            loop_bounds: vec![],
         };
 
         let mut buf = Vec::new();
@@ -761,6 +916,7 @@ mod tests {
             string_layouts: vec![],
             source_files: vec![],
             enum_defs: vec![],
+            loop_bounds: vec![],
         };
 
         let mut buf = Vec::new();
@@ -794,6 +950,7 @@ mod tests {
             string_layouts: vec![],
             source_files: vec![],
             enum_defs: vec![],
+            loop_bounds: vec![],
         };
 
         let mut buf = Vec::new();
@@ -875,6 +1032,7 @@ mod tests {
             string_layouts: vec![],
             source_files: vec![],
             enum_defs: vec![],
+            loop_bounds: vec![],
         };
 
         let mut buf = Vec::new();
@@ -905,6 +1063,7 @@ mod tests {
                 },
             ],
             enum_defs: vec![],
+            loop_bounds: vec![],
         };
 
         let mut buf = Vec::new();
@@ -939,6 +1098,7 @@ mod tests {
             string_layouts: vec![],
             source_files: vec![],
             enum_defs: vec![],
+            loop_bounds: vec![],
         };
 
         let mut buf = Vec::new();
@@ -999,6 +1159,24 @@ mod tests {
         assert_eq!(decoded.source_files[0].content_hash, hash);
     }
 
+    #[test]
+    fn debug_section_var_name_when_index_in_table_then_name_else_none() {
+        let section = DebugSection {
+            var_names: vec![VarNameEntry {
+                var_index: VarIndex::new(3),
+                function_id: FunctionId::GLOBAL_SCOPE,
+                var_section: var_section::VAR,
+                iec_type_tag: iec_type_tag::INT,
+                name: "level".into(),
+                type_name: "PERCENT".into(),
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(section.var_name(VarIndex::new(3)), Some("level"));
+        assert_eq!(section.var_name(VarIndex::new(4)), None);
+    }
+
     #[test]
     fn debug_section_lookup_source_location_when_offset_between_entries_then_returns_lower() {
         let section = DebugSection {
@@ -1032,6 +1210,7 @@ mod tests {
             string_layouts: vec![],
             source_files: vec![],
             enum_defs: vec![],
+            loop_bounds: vec![],
         };
 
         // Exact match
@@ -1077,6 +1256,7 @@ mod tests {
             string_layouts: vec![],
             source_files: vec![],
             enum_defs: vec![],
+            loop_bounds: vec![],
         };
 
         let mut buf = Vec::new();
@@ -1123,6 +1303,7 @@ mod tests {
             string_layouts: vec![],
             source_files: vec![],
             enum_defs: vec![],
+            loop_bounds: vec![],
         };
 
         // Offset 0: matches the first entry exactly.
@@ -1151,4 +1332,82 @@ mod tests {
             .unwrap();
         assert_eq!(hit.source_line.raw(), 30);
     }
+
+    #[test]
+    fn debug_section_write_read_when_loop_bounds_then_roundtrips() {
+        let section = DebugSection {
+            loop_bounds: vec![
+                LoopBoundEntry {
+                    function_id: FunctionId::SCAN,
+                    start_offset: 7,
+                    end_offset: 42,
+                    kind: loop_kind::FOR,
+                    max_iterations: Some(10),
+                    file_id: SourceFileId::new(0),
+                    source_line: SourceLine::new(4),
+                    source_column: SourceColumn::new(3),
+                },
+                LoopBoundEntry {
+                    function_id: FunctionId::new(2),
+                    start_offset: 0,
+                    end_offset: 12,
+                    kind: loop_kind::WHILE,
+                    max_iterations: None,
+                    file_id: SourceFileId::new(1),
+                    source_line: SourceLine::new(9),
+                    source_column: SourceColumn::new(1),
+                },
+            ],
+            ..DebugSection::default()
+        };
+
+        let mut buf = Vec::new();
+        section.write_to(&mut buf).unwrap();
+        assert_eq!(section.section_size(), buf.len() as u32);
+
+        let decoded = DebugSection::read_from(&mut Cursor::new(&buf)).unwrap();
+        assert_eq!(decoded.loop_bounds, section.loop_bounds);
+    }
+
+    #[test]
+    fn debug_section_read_when_loop_bound_flag_invalid_then_error() {
+        let section = DebugSection {
+            loop_bounds: vec![LoopBoundEntry {
+                function_id: FunctionId::SCAN,
+                start_offset: 0,
+                end_offset: 4,
+                kind: loop_kind::REPEAT,
+                max_iterations: Some(3),
+                file_id: SourceFileId::new(0),
+                source_line: SourceLine::new(1),
+                source_column: SourceColumn::new(1),
+            }],
+            ..DebugSection::default()
+        };
+        let mut buf = Vec::new();
+        section.write_to(&mut buf).unwrap();
+        // header(2) + directory entry(8) + count(2) + the bounded flag at 7.
+        buf[2 + 8 + 2 + 7] = 2;
+
+        let result = DebugSection::read_from(&mut Cursor::new(&buf));
+        assert!(matches!(result, Err(ContainerError::InvalidDebugSection)));
+    }
+
+    #[test]
+    fn var_section_keyword_when_known_then_round_trips() {
+        assert_eq!(
+            var_section::keyword(var_section::VAR_IN_OUT),
+            Some("VAR_IN_OUT")
+        );
+        assert_eq!(
+            var_section::from_keyword("VAR_GLOBAL"),
+            Some(var_section::VAR_GLOBAL)
+        );
+    }
+
+    #[test]
+    fn var_section_keyword_when_unknown_then_none() {
+        assert_eq!(var_section::keyword(7), None);
+        assert_eq!(var_section::from_keyword("VAR_ACCESS"), None);
+    }
 }
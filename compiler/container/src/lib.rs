@@ -60,13 +60,13 @@ pub use constant_pool::{ConstEntry, ConstantPool};
 pub use container::Container;
 #[cfg(feature = "std")]
 pub use debug_format::{
-    build_var_debug_map, format_iec_string_literal, format_variable_value, read_string_value,
-    StringReadError, VarDebugInfo,
+    build_var_debug_map, format_iec_string_literal, format_variable_value, parse_variable_value,
+    read_string_value, StringReadError, ValueParseError, VarDebugInfo,
 };
 #[cfg(feature = "std")]
 pub use debug_section::{
-    DebugSection, EnumDefEntry, FuncNameEntry, LineMapEntry, SourceFileEntry, StringLayoutEntry,
-    VarNameEntry, SOURCE_FILE_HASH_LEN,
+    DebugSection, EnumDefEntry, FuncNameEntry, LineMapEntry, LoopBoundEntry, SourceFileEntry,
+    StringLayoutEntry, VarNameEntry, SOURCE_FILE_HASH_LEN,
 };
 #[cfg(feature = "std")]
 pub use task_table::{ProgramInstanceEntry, TaskEntry, TaskTable};
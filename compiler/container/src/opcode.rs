@@ -178,7 +178,12 @@ pub const OP_CLASS_STR_STORE_ARRAY_ELEM: u8 = 0x3C;
 /// (`T_I32`/`T_I64`; floats reserved). The comparison operator is encoded
 /// as a 1-byte operand (`cmp_op` enum). See `vm-performance.md` §11.
 pub const OP_CLASS_CMP_BR: u8 = 0x3D;
-// 0x3E..0x3F free (2 op-class slots reserved for future use).
+/// Op class: range check on the top of stack. Type tag selects the
+/// comparison family (I32/I64/U32/U64), like `OP_CLASS_TRUNC`.
+pub const OP_CLASS_RANGE_CHECK: u8 = 0x3E;
+/// Op class: array element access whose index codegen proved in bounds.
+/// Type tag selects: 0 = LOAD_ARRAY_UNCHECKED, 1 = STORE_ARRAY_UNCHECKED.
+pub const OP_CLASS_ARRAY_UNCHECKED: u8 = 0x3F;
 
 /// Decompose a primary opcode byte into `(op_class, type_tag)`.
 #[inline]
@@ -477,6 +482,17 @@ pub const LOAD_ARRAY_DEREF: Opcode = encode_opcode(OP_CLASS_LOAD_ARRAY_DEREF, 0)
 /// Pops 2 (value, flat index). Net stack: -2.
 pub const STORE_ARRAY_DEREF: Opcode = encode_opcode(OP_CLASS_STORE_ARRAY_DEREF, 0);
 
+/// Load a value from an array element without comparing the index to the
+/// array's element count. Codegen emits it only for an index that it proved
+/// to be in bounds; the data-region bounds check still applies.
+/// Operands and stack effect are those of `LOAD_ARRAY`.
+pub const LOAD_ARRAY_UNCHECKED: Opcode = encode_opcode(OP_CLASS_ARRAY_UNCHECKED, 0);
+
+/// Store a value to an array element without comparing the index to the
+/// array's element count. See `LOAD_ARRAY_UNCHECKED`.
+/// Operands and stack effect are those of `STORE_ARRAY`.
+pub const STORE_ARRAY_UNCHECKED: Opcode = encode_opcode(OP_CLASS_ARRAY_UNCHECKED, 1);
+
 // --- Truncation opcodes ---
 
 /// Truncate i32 to i8 range, then sign-extend back to i32.
@@ -770,6 +786,35 @@ pub const CMP_BR_I32: Opcode = encode_opcode(OP_CLASS_CMP_BR, T_I32);
 /// See `CMP_BR_I32` for operand layout and semantics.
 pub const CMP_BR_I64: Opcode = encode_opcode(OP_CLASS_CMP_BR, T_I64);
 
+// --- Range-check opcodes ---
+
+/// Range check on a 32-bit signed integer.
+///
+/// Operands:
+/// - `var_idx:u16` — variable index of the store target (for diagnostics).
+/// - `min_const:u16` — constant pool index of the inclusive lower bound.
+/// - `max_const:u16` — constant pool index of the inclusive upper bound.
+///
+/// Semantics: peek the top of stack. If it lies outside `[min, max]`, trap
+/// with `RangeViolation`; otherwise leave the stack unchanged.
+///
+/// Stack effect: 0 (pops 1, pushes 1).
+pub const RANGE_CHECK_I32: Opcode = encode_opcode(OP_CLASS_RANGE_CHECK, 0);
+
+/// Range check on a 64-bit signed integer.
+/// See `RANGE_CHECK_I32` for operand layout and semantics.
+pub const RANGE_CHECK_I64: Opcode = encode_opcode(OP_CLASS_RANGE_CHECK, 1);
+
+/// Range check on a 32-bit unsigned integer. The bounds are stored as
+/// `i32` constants and compared as `u32`.
+/// See `RANGE_CHECK_I32` for operand layout and semantics.
+pub const RANGE_CHECK_U32: Opcode = encode_opcode(OP_CLASS_RANGE_CHECK, 2);
+
+/// Range check on a 64-bit unsigned integer. The bounds are stored as
+/// `i64` constants and compared as `u64`.
+/// See `RANGE_CHECK_I32` for operand layout and semantics.
+pub const RANGE_CHECK_U64: Opcode = encode_opcode(OP_CLASS_RANGE_CHECK, 3);
+
 /// Comparison-operator codes used as the first operand of `CMP_BR_*`.
 ///
 /// Negation pairs (used by codegen to emit a "branch if false" predicate
@@ -867,8 +912,16 @@ fn instruction_size_opt(op: Opcode) -> Option<usize> {
         | JMP_IF_NOT | BUILTIN => 3,
 
         // 5-byte: opcode + u16 + u16.
-        CALL | LOAD_ARRAY | STORE_ARRAY | LOAD_ARRAY_DEREF | STORE_ARRAY_DEREF | STR_INIT_ARRAY
-        | STR_LOAD_ARRAY_ELEM | STR_STORE_ARRAY_ELEM => 5,
+        CALL
+        | LOAD_ARRAY
+        | STORE_ARRAY
+        | LOAD_ARRAY_DEREF
+        | STORE_ARRAY_DEREF
+        | LOAD_ARRAY_UNCHECKED
+        | STORE_ARRAY_UNCHECKED
+        | STR_INIT_ARRAY
+        | STR_LOAD_ARRAY_ELEM
+        | STR_STORE_ARRAY_ELEM => 5,
 
         // 5-byte: opcode + u32.
         STR_LOAD_VAR | STR_STORE_VAR | LEN_STR | DELETE_STR | LEFT_STR | RIGHT_STR | MID_STR => 5,
@@ -879,6 +932,9 @@ fn instruction_size_opt(op: Opcode) -> Option<usize> {
         // 8-byte: opcode + u8 + u16 + u16 + i16.
         CMP_BR_I32 | CMP_BR_I64 => 8,
 
+        // 7-byte: opcode + u16 + u16 + u16.
+        RANGE_CHECK_I32 | RANGE_CHECK_I64 | RANGE_CHECK_U32 | RANGE_CHECK_U64 => 7,
+
         // 9-byte: opcode + u32 + u32.
         FIND_STR | REPLACE_STR | INSERT_STR | CONCAT_STR => 9,
 
@@ -1238,6 +1294,34 @@ pub mod builtin {
     /// variant of [`MOD_F64`] (`x % 0.0` is NaN, not a trap).
     pub const MOD_F32: u16 = 0x03A6;
 
+    // =========================================================================
+    // Unsigned integer arithmetic builtins
+    //
+    // UDINT and ULINT share the I32/I64 slots with the signed types, so the
+    // wrapped result of ADD/SUB/MUL is the same bits, but a result out of the
+    // unsigned range is not. These are dispatched inline in the VM main loop
+    // (not via builtin::dispatch) because they apply the overflow policy
+    // (ADR-0002) with unsigned bounds.
+    // =========================================================================
+
+    /// Unsigned 32-bit addition: pops b then a, pushes a + b.
+    pub const ADD_U32: u16 = 0x03A7;
+
+    /// Unsigned 32-bit subtraction: pops b then a, pushes a - b.
+    pub const SUB_U32: u16 = 0x03A8;
+
+    /// Unsigned 32-bit multiplication: pops b then a, pushes a * b.
+    pub const MUL_U32: u16 = 0x03A9;
+
+    /// Unsigned 64-bit addition: pops b then a, pushes a + b.
+    pub const ADD_U64: u16 = 0x03AA;
+
+    /// Unsigned 64-bit subtraction: pops b then a, pushes a - b.
+    pub const SUB_U64: u16 = 0x03AB;
+
+    /// Unsigned 64-bit multiplication: pops b then a, pushes a * b.
+    pub const MUL_U64: u16 = 0x03AC;
+
     // =========================================================================
     // MUX (multiplexer) range-based opcodes
     //
@@ -1317,7 +1401,7 @@ pub mod builtin {
             | MIN_U32 | MIN_U64 | MAX_I32 | MAX_F32 | MAX_F64 | MAX_I64 | MAX_U32 | MAX_U64
             | SHL_I32 | SHL_I64 | SHR_I32 | SHR_I64 | ROL_I32 | ROL_I64 | ROR_I32 | ROR_I64
             | ROL_U8 | ROL_U16 | ROR_U8 | ROR_U16 | ATAN2_F32 | ATAN2_F64 | CMP_STR | MOD_F64
-            | MOD_F32 => 2,
+            | MOD_F32 | ADD_U32 | SUB_U32 | MUL_U32 | ADD_U64 | SUB_U64 | MUL_U64 => 2,
             LIMIT_I32 | LIMIT_F32 | LIMIT_F64 | LIMIT_I64 | LIMIT_U32 | LIMIT_U64 | SEL_I32
             | SEL_F32 | SEL_F64 | SEL_I64 => 3,
             id if is_mux(id) => {
@@ -1400,4 +1484,18 @@ mod tests {
         assert_eq!(builtin::arg_count(builtin::TRUNC_F32), 1);
         assert_eq!(builtin::arg_count(builtin::MOD_F32), 2);
     }
+
+    #[test]
+    fn arg_count_when_unsigned_arithmetic_builtins_then_two() {
+        for id in [
+            builtin::ADD_U32,
+            builtin::SUB_U32,
+            builtin::MUL_U32,
+            builtin::ADD_U64,
+            builtin::SUB_U64,
+            builtin::MUL_U64,
+        ] {
+            assert_eq!(builtin::arg_count(id), 2);
+        }
+    }
 }
@@ -202,6 +202,7 @@ fn container_spec_req_en_061_enum_def_payload_roundtrips() {
             type_name: "COLOR".into(),
             values: vec!["RED".into(), "GREEN".into(), "BLUE".into()],
         }],
+        loop_bounds: vec![],
     };
     let mut buf = Vec::new();
     section.write_to(&mut buf).unwrap();
@@ -540,93 +540,99 @@ fn effect_of(
 ) -> Result<Effect, StackImbalance> {
     use opcode::*;
 
-    let effect = match op {
-        // --- Push one, pop nothing ---
-        LOAD_CONST_I32 | LOAD_CONST_I64 | LOAD_CONST_F32 | LOAD_CONST_F64 | LOAD_CONST_STR
-        | LOAD_VAR_I32 | LOAD_VAR_I64 | LOAD_VAR_F32 | LOAD_VAR_F64 | LOAD_TRUE | LOAD_FALSE
-        | DUP | FB_LOAD_INSTANCE | STR_LOAD_VAR | LEN_STR | FIND_STR | CONCAT_STR => {
-            Effect::new(0, 1)
-        }
-
-        // FB_LOAD_PARAM reads a field through the fb_ref it leaves in place.
-        FB_LOAD_PARAM => Effect::new(0, 1),
-
-        // --- Pop one, push nothing ---
-        STORE_VAR_I32 | STORE_VAR_I64 | STORE_VAR_F32 | STORE_VAR_F64 | POP | JMP_IF_NOT
-        | STR_STORE_VAR => Effect::new(1, 0),
-
-        // FB_STORE_PARAM consumes the value; the fb_ref below it survives.
-        FB_STORE_PARAM => Effect::new(1, 0),
-
-        // --- Pop two, push nothing ---
-        STORE_INDIRECT | STORE_ARRAY | STORE_ARRAY_DEREF | STR_STORE_ARRAY_ELEM => {
-            Effect::new(2, 0)
-        }
-
-        // --- Pop one, push one (net zero) ---
-        NEG_I32 | NEG_I64 | NEG_F32 | NEG_F64 | BOOL_NOT | BIT_NOT_32 | BIT_NOT_64 | TRUNC_I8
-        | TRUNC_U8 | TRUNC_I16 | TRUNC_U16 | LOAD_INDIRECT | LOAD_ARRAY | LOAD_ARRAY_DEREF
-        | STR_LOAD_ARRAY_ELEM | INSERT_STR | LEFT_STR | RIGHT_STR => Effect::new(1, 1),
-
-        // --- Pop two, push one (net pop one) ---
-        ADD_I32 | SUB_I32 | MUL_I32 | DIV_I32 | MOD_I32 | ADD_I64 | SUB_I64 | MUL_I64 | DIV_I64
-        | MOD_I64 | DIV_U32 | MOD_U32 | DIV_U64 | MOD_U64 | ADD_F32 | SUB_F32 | MUL_F32
-        | DIV_F32 | ADD_F64 | SUB_F64 | MUL_F64 | DIV_F64 | EQ_I32 | NE_I32 | LT_I32 | LE_I32
-        | GT_I32 | GE_I32 | EQ_I64 | NE_I64 | LT_I64 | LE_I64 | GT_I64 | GE_I64 | LT_U32
-        | LE_U32 | GT_U32 | GE_U32 | LT_U64 | LE_U64 | GT_U64 | GE_U64 | EQ_F32 | NE_F32
-        | LT_F32 | LE_F32 | GT_F32 | GE_F32 | EQ_F64 | NE_F64 | LT_F64 | LE_F64 | GT_F64
-        | GE_F64 | BOOL_AND | BOOL_OR | BOOL_XOR | BIT_AND_32 | BIT_OR_32 | BIT_XOR_32
-        | BIT_AND_64 | BIT_OR_64 | BIT_XOR_64 | REPLACE_STR | DELETE_STR | MID_STR => {
-            Effect::new(2, 1)
-        }
+    let effect =
+        match op {
+            // --- Push one, pop nothing ---
+            LOAD_CONST_I32 | LOAD_CONST_I64 | LOAD_CONST_F32 | LOAD_CONST_F64 | LOAD_CONST_STR
+            | LOAD_VAR_I32 | LOAD_VAR_I64 | LOAD_VAR_F32 | LOAD_VAR_F64 | LOAD_TRUE
+            | LOAD_FALSE | DUP | FB_LOAD_INSTANCE | STR_LOAD_VAR | LEN_STR | FIND_STR
+            | CONCAT_STR => Effect::new(0, 1),
+
+            // FB_LOAD_PARAM reads a field through the fb_ref it leaves in place.
+            FB_LOAD_PARAM => Effect::new(0, 1),
+
+            // --- Pop one, push nothing ---
+            STORE_VAR_I32 | STORE_VAR_I64 | STORE_VAR_F32 | STORE_VAR_F64 | POP | JMP_IF_NOT
+            | STR_STORE_VAR => Effect::new(1, 0),
+
+            // FB_STORE_PARAM consumes the value; the fb_ref below it survives.
+            FB_STORE_PARAM => Effect::new(1, 0),
+
+            // --- Pop two, push nothing ---
+            STORE_INDIRECT
+            | STORE_ARRAY
+            | STORE_ARRAY_DEREF
+            | STORE_ARRAY_UNCHECKED
+            | STR_STORE_ARRAY_ELEM => Effect::new(2, 0),
+
+            // --- Pop one, push one (net zero) ---
+            NEG_I32 | NEG_I64 | NEG_F32 | NEG_F64 | BOOL_NOT | BIT_NOT_32 | BIT_NOT_64
+            | TRUNC_I8 | TRUNC_U8 | TRUNC_I16 | TRUNC_U16 | LOAD_INDIRECT | LOAD_ARRAY
+            | LOAD_ARRAY_DEREF | LOAD_ARRAY_UNCHECKED | STR_LOAD_ARRAY_ELEM | INSERT_STR
+            | LEFT_STR | RIGHT_STR => Effect::new(1, 1),
+
+            // RANGE_CHECK peeks the value it guards and leaves it in place.
+            RANGE_CHECK_I32 | RANGE_CHECK_I64 | RANGE_CHECK_U32 | RANGE_CHECK_U64 => {
+                Effect::new(1, 1)
+            }
 
-        // --- No stack effect ---
-        //
-        // SWAP reorders the top two slots; it needs two present, which the
-        // pop/push pair below models without changing the depth. JMP,
-        // CMP_BR, STR_INIT, STR_INIT_ARRAY and the returns move control or
-        // touch only memory. FB_CALL runs the body as its own frame and
-        // leaves the caller's fb_ref where it was.
-        SWAP => Effect::new(2, 2),
-        JMP | CMP_BR_I32 | CMP_BR_I64 | STR_INIT | STR_INIT_ARRAY | FB_CALL | RET | RET_VOID => {
-            Effect::NONE
-        }
+            // --- Pop two, push one (net pop one) ---
+            ADD_I32 | SUB_I32 | MUL_I32 | DIV_I32 | MOD_I32 | ADD_I64 | SUB_I64 | MUL_I64
+            | DIV_I64 | MOD_I64 | DIV_U32 | MOD_U32 | DIV_U64 | MOD_U64 | ADD_F32 | SUB_F32
+            | MUL_F32 | DIV_F32 | ADD_F64 | SUB_F64 | MUL_F64 | DIV_F64 | EQ_I32 | NE_I32
+            | LT_I32 | LE_I32 | GT_I32 | GE_I32 | EQ_I64 | NE_I64 | LT_I64 | LE_I64 | GT_I64
+            | GE_I64 | LT_U32 | LE_U32 | GT_U32 | GE_U32 | LT_U64 | LE_U64 | GT_U64 | GE_U64
+            | EQ_F32 | NE_F32 | LT_F32 | LE_F32 | GT_F32 | GE_F32 | EQ_F64 | NE_F64 | LT_F64
+            | LE_F64 | GT_F64 | GE_F64 | BOOL_AND | BOOL_OR | BOOL_XOR | BIT_AND_32 | BIT_OR_32
+            | BIT_XOR_32 | BIT_AND_64 | BIT_OR_64 | BIT_XOR_64 | REPLACE_STR | DELETE_STR
+            | MID_STR => Effect::new(2, 1),
+
+            // --- No stack effect ---
+            //
+            // SWAP reorders the top two slots; it needs two present, which the
+            // pop/push pair below models without changing the depth. JMP,
+            // CMP_BR, STR_INIT, STR_INIT_ARRAY and the returns move control or
+            // touch only memory. FB_CALL runs the body as its own frame and
+            // leaves the caller's fb_ref where it was.
+            SWAP => Effect::new(2, 2),
+            JMP | CMP_BR_I32 | CMP_BR_I64 | STR_INIT | STR_INIT_ARRAY | FB_CALL | RET
+            | RET_VOID => Effect::NONE,
+
+            // --- Variable-effect instructions ---
+            BUILTIN => {
+                let builtin_id = u16_at(operands, 0);
+                let args = opcode::builtin::arg_count_opt(builtin_id).ok_or(
+                    StackImbalance::UnknownBuiltin {
+                        function_id,
+                        offset,
+                        builtin_id,
+                    },
+                )?;
+                // Every builtin consumes its arguments and produces one result.
+                Effect::new(args, 1)
+            }
+            CALL => {
+                let callee = FunctionId::new(u16_at(operands, 0));
+                let entry = code
+                    .get_function(callee)
+                    .ok_or(StackImbalance::UnknownCallee {
+                        function_id,
+                        offset,
+                        callee,
+                    })?;
+                // Arguments are popped into the callee's parameter slots; the
+                // callee's RET leaves exactly one value behind.
+                Effect::new(entry.num_params, RET_DEPTH)
+            }
 
-        // --- Variable-effect instructions ---
-        BUILTIN => {
-            let builtin_id = u16_at(operands, 0);
-            let args = opcode::builtin::arg_count_opt(builtin_id).ok_or(
-                StackImbalance::UnknownBuiltin {
+            _ => {
+                return Err(StackImbalance::UnknownOpcode {
                     function_id,
                     offset,
-                    builtin_id,
-                },
-            )?;
-            // Every builtin consumes its arguments and produces one result.
-            Effect::new(args, 1)
-        }
-        CALL => {
-            let callee = FunctionId::new(u16_at(operands, 0));
-            let entry = code
-                .get_function(callee)
-                .ok_or(StackImbalance::UnknownCallee {
-                    function_id,
-                    offset,
-                    callee,
-                })?;
-            // Arguments are popped into the callee's parameter slots; the
-            // callee's RET leaves exactly one value behind.
-            Effect::new(entry.num_params, RET_DEPTH)
-        }
-
-        _ => {
-            return Err(StackImbalance::UnknownOpcode {
-                function_id,
-                offset,
-                byte: op,
-            })
-        }
-    };
+                    byte: op,
+                })
+            }
+        };
     Ok(effect)
 }
 
@@ -14,7 +14,7 @@
 //! (especially one that works for both command line and language server
 //! protocol).
 use ironplc_problems::Problem;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 use crate::common::TypeName;
 use crate::core::{FileId, Id, Located, SourceSpan};
@@ -56,6 +56,28 @@ impl LineColumn {
         }
         LineColumn { line, column }
     }
+
+    /// Converts this line and column to a byte offset in `source`; the
+    /// inverse of [`LineColumn::from_offset`].
+    ///
+    /// A column past the end of its line is clamped to the end of the line,
+    /// and a line past the end of `source` to the end of `source`.
+    pub fn to_offset(&self, source: &str) -> usize {
+        let mut line_start = 0;
+        for _ in 0..self.line {
+            match source[line_start..].find('\n') {
+                Some(newline) => line_start += newline + 1,
+                None => return source.len(),
+            }
+        }
+        let line = &source[line_start..];
+        let line_end = line.find('\n').unwrap_or(line.len());
+        let column = line[..line_end]
+            .char_indices()
+            .nth(self.column as usize)
+            .map_or(line_end, |(offset, _)| offset);
+        line_start + column
+    }
 }
 
 /// A label that refers to some range in a file and possibly associated
@@ -109,6 +131,17 @@ impl Label {
     }
 }
 
+/// How seriously a diagnostic is reported.
+///
+/// Every diagnostic is an error (ADR-0039). A diagnostic is a warning only
+/// because the user demoted its problem code through [`Demotions`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+}
+
 /// A diagnostic. Diagnostic have a code that is indicative of the category,
 /// a primary location and possibly non-zero set of secondary location.
 #[derive(Debug, Clone)]
@@ -116,6 +149,9 @@ pub struct Diagnostic {
     /// A normally unique value describing the type of diagnostic.
     pub code: String,
 
+    /// Whether the diagnostic fails the command that reported it.
+    pub severity: Severity,
+
     description: String,
 
     /// The primary or first diagnostic.
@@ -133,6 +169,10 @@ pub struct Diagnostic {
     /// Additional information about the diagnostic.
     pub secondary: Vec<Label>,
 
+    /// Changes to the source that resolve the problem. The language server
+    /// offers these as quick fixes.
+    pub fixes: Vec<Fix>,
+
     /// Rust source file that produced this diagnostic (from `file!()` macro).
     pub source_file: Option<String>,
 
@@ -140,6 +180,38 @@ pub struct Diagnostic {
     pub source_line: Option<u32>,
 }
 
+/// A change that resolves a diagnostic.
+///
+/// Like help notes, fixes describe *how to fix* the problem, but as a change
+/// a tool can apply rather than as text for the user to read.
+#[derive(Debug, Clone)]
+pub struct Fix {
+    /// Short description of the change, such as "Change to 'Count'".
+    pub title: String,
+    pub kind: FixKind,
+}
+
+/// The change that a [`Fix`] makes.
+#[derive(Debug, Clone)]
+pub enum FixKind {
+    /// Replaces the text of the span.
+    Replace { span: SourceSpan, text: String },
+    /// Inserts a line before the line that contains the span, indented like
+    /// that line.
+    InsertLineBefore { span: SourceSpan, text: String },
+    /// Declares a variable in the `VAR` section of the POU whose name is at
+    /// the span, adding the section when there is none. The type is `None`
+    /// when it cannot be known from how the variable is used.
+    DeclareVariable {
+        pou: SourceSpan,
+        name: String,
+        type_name: Option<String>,
+    },
+    /// Enables the compiler option with the key: the name of the
+    /// `CompilerOptions` flag, such as `allow_top_level_var_global`.
+    EnableOption { option_key: String },
+}
+
 /// Formats the label message for a "not implemented" diagnostic from the
 /// compiler location that produced it.
 fn not_implemented_message(caller: &std::panic::Location<'_>) -> String {
@@ -154,11 +226,13 @@ impl Diagnostic {
     pub fn problem(problem: Problem, primary: Label) -> Self {
         Self {
             code: problem.code().to_string(),
+            severity: Severity::Error,
             description: problem.message().to_string(),
             primary,
             described: vec![],
             help: vec![],
             secondary: vec![],
+            fixes: vec![],
             source_file: None,
             source_line: None,
         }
@@ -357,6 +431,20 @@ impl Diagnostic {
         self
     }
 
+    /// Adds a change that resolves the problem.
+    pub fn with_fix(mut self, title: impl Into<String>, kind: FixKind) -> Self {
+        self.fixes.push(Fix {
+            title: title.into(),
+            kind,
+        });
+        self
+    }
+
+    /// Returns `true` unless the diagnostic was demoted to a warning.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
     /// Returns the help notes describing how to resolve the problem.
     pub fn help(&self) -> &[String] {
         &self.help
@@ -394,6 +482,58 @@ impl Diagnostic {
     }
 }
 
+/// The problem codes a user chose to report as warnings (ADR-0039).
+///
+/// Demotion is opt-in and per code. A code whose problem is safety-relevant
+/// (see [`Problem::is_safety_relevant`]) is refused, so it stays an error.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Demotions {
+    codes: BTreeSet<String>,
+}
+
+impl Demotions {
+    /// Demotes the problem with `code`, such as `P4041`.
+    ///
+    /// Returns the reason when the code names no problem or a problem that
+    /// cannot be demoted; the set is then unchanged.
+    pub fn demote(&mut self, code: &str) -> Result<(), String> {
+        let code = code.trim().to_uppercase();
+        let problem =
+            Problem::from_code(&code).ok_or_else(|| format!("{code} is not a problem code"))?;
+        if problem.is_safety_relevant() {
+            return Err(format!(
+                "{code} is safety-relevant and cannot be demoted to a warning"
+            ));
+        }
+        self.codes.insert(code);
+        Ok(())
+    }
+
+    /// Adds every code demoted in `other`.
+    pub fn extend(&mut self, other: &Demotions) {
+        self.codes.extend(other.codes.iter().cloned());
+    }
+
+    /// Returns `true` when no code is demoted.
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Returns the demoted codes in order.
+    pub fn codes(&self) -> impl Iterator<Item = &str> {
+        self.codes.iter().map(String::as_str)
+    }
+
+    /// Makes every diagnostic with a demoted code a warning.
+    pub fn apply(&self, diagnostics: &mut [Diagnostic]) {
+        for diagnostic in diagnostics {
+            if self.codes.contains(&diagnostic.code) {
+                diagnostic.severity = Severity::Warning;
+            }
+        }
+    }
+}
+
 /// The www.ironplc.com reference section that documents a problem code, derived
 /// from its leading letter: `P####` → `compiler`, `V####` → `runtime`,
 /// `E####` → `editor`.
@@ -533,6 +673,68 @@ mod tests {
         assert_eq!(lc, LineColumn { line: 0, column: 0 });
     }
 
+    #[test]
+    fn line_column_to_offset_when_on_later_line_then_returns_byte_offset() {
+        let offset = LineColumn { line: 2, column: 1 }.to_offset("abc\ndef\nghi");
+        assert_eq!(offset, 9);
+    }
+
+    #[test]
+    fn line_column_to_offset_when_multibyte_characters_then_counts_code_points() {
+        let offset = LineColumn { line: 0, column: 2 }.to_offset("äöx");
+        assert_eq!(offset, 4);
+    }
+
+    #[test]
+    fn line_column_to_offset_when_column_past_end_of_line_then_clamps_to_line_end() {
+        let offset = LineColumn {
+            line: 0,
+            column: 10,
+        }
+        .to_offset("abc\ndef");
+        assert_eq!(offset, 3);
+    }
+
+    #[test]
+    fn line_column_to_offset_when_line_past_end_then_clamps_to_end() {
+        let offset = LineColumn { line: 5, column: 0 }.to_offset("abc\ndef");
+        assert_eq!(offset, 7);
+    }
+
+    #[test]
+    fn demote_when_portability_code_then_diagnostic_becomes_warning() {
+        let mut demotions = Demotions::default();
+        demotions.demote("p4041").unwrap();
+        let mut diagnostics = vec![
+            Diagnostic::problem(
+                Problem::BitStringCaseLabelNotAllowed,
+                Label::file(FileId::default(), "label"),
+            ),
+            Diagnostic::problem(Problem::SyntaxError, Label::file(FileId::default(), "x")),
+        ];
+
+        demotions.apply(&mut diagnostics);
+
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(!diagnostics[0].is_error());
+        assert!(diagnostics[1].is_error());
+    }
+
+    #[test]
+    fn demote_when_safety_relevant_code_then_refused() {
+        let mut demotions = Demotions::default();
+        let err = demotions.demote("P4035").unwrap_err();
+        assert!(err.contains("safety-relevant"));
+        assert!(demotions.is_empty());
+    }
+
+    #[test]
+    fn demote_when_unknown_code_then_refused() {
+        let mut demotions = Demotions::default();
+        let err = demotions.demote("P0000").unwrap_err();
+        assert_eq!(err, "P0000 is not a problem code");
+    }
+
     #[test]
     fn file_ids_when_has_secondary_then_returns_all_file_ids() {
         let primary_file = FileId::from_string("file1");
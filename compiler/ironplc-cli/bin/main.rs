@@ -5,7 +5,10 @@ use clap::Parser;
 use ironplc_cli::cli;
 use ironplc_cli::logger;
 use ironplc_cli::lsp;
+use ironplc_codegen::target::{TargetProfile, TARGET_PROFILES};
+use ironplc_codegen::CodegenOptions;
 use ironplc_parser::options::{describe_dialects, CompilerOptions, Dialect};
+use ironplc_plc2plc::formatter::{FormatOptions, Indent, KeywordCase};
 use ironplc_sources::LibraryName;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -57,6 +60,52 @@ impl clap::ValueEnum for ClapDialect {
     }
 }
 
+/// Target profile choices for `compile --target`.
+///
+/// Wraps a [`TargetProfile`] for the same orphan-rule reason as
+/// [`ClapDialect`].
+#[derive(Clone, Copy, Debug)]
+struct ClapTarget(&'static TargetProfile);
+
+impl clap::ValueEnum for ClapTarget {
+    fn value_variants<'a>() -> &'a [Self] {
+        // Mirrors `TARGET_PROFILES`. A test below guards the two against drift.
+        const VARIANTS: [ClapTarget; 3] = [
+            ClapTarget(&TARGET_PROFILES[0]),
+            ClapTarget(&TARGET_PROFILES[1]),
+            ClapTarget(&TARGET_PROFILES[2]),
+        ];
+        &VARIANTS
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(clap::builder::PossibleValue::new(self.0.name).help(self.0.description))
+    }
+}
+
+/// Keyword case choices for `fmt --keyword-case`.
+///
+/// Mirrors [`KeywordCase`] for the same orphan-rule reason as [`ClapDialect`].
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum ClapKeywordCase {
+    /// Writes keywords in upper case (`END_IF`).
+    Upper,
+    /// Writes keywords in lower case (`end_if`).
+    Lower,
+    /// Leaves keywords as written.
+    Preserve,
+}
+
+impl From<ClapKeywordCase> for KeywordCase {
+    fn from(c: ClapKeywordCase) -> Self {
+        match c {
+            ClapKeywordCase::Upper => KeywordCase::Upper,
+            ClapKeywordCase::Lower => KeywordCase::Lower,
+            ClapKeywordCase::Preserve => KeywordCase::Preserve,
+        }
+    }
+}
+
 /// Shared arguments for commands that operate on source files.
 #[derive(clap::Args, Debug)]
 struct FileArgs {
@@ -217,6 +266,25 @@ struct FileArgs {
     /// extension not part of the IEC 61131-3 standard.
     #[arg(long)]
     allow_fb_inheritance: bool,
+
+    /// Report shared VAR_GLOBAL variables that tasks of different priorities
+    /// access unsafely: written by more than one task, or written by one task
+    /// while another reads a value wider than one 32-bit word. Off by default.
+    #[arg(long)]
+    check_task_races: bool,
+
+    /// Report CASE statements over an enumerated type that miss a value and
+    /// have no ELSE, and CASE labels that select a value an earlier label
+    /// already selects. Off by default.
+    #[arg(long)]
+    check_case_exhaustive: bool,
+
+    /// Report variables and data types that are never used, variables that
+    /// are assigned but never read, functions and function blocks that no
+    /// program reaches, and programs that no configuration runs. Off by
+    /// default.
+    #[arg(long, visible_alias = "unused")]
+    check_unused: bool,
 }
 
 impl FileArgs {
@@ -251,6 +319,9 @@ impl FileArgs {
         options.allow_paren_string_length |= self.allow_paren_string_length;
         options.allow_struct_initializer_expressions |= self.allow_struct_initializer_expressions;
         options.allow_fb_inheritance |= self.allow_fb_inheritance;
+        options.check_task_races |= self.check_task_races;
+        options.check_case_exhaustive |= self.check_case_exhaustive;
+        options.check_unused |= self.check_unused;
         options
     }
 }
@@ -273,6 +344,12 @@ enum Action {
         /// automatically.
         #[arg(long = "library")]
         libraries: Vec<LibraryName>,
+
+        /// Report the problem code as a warning instead of an error
+        /// (repeatable), e.g. `--demote P4041`. Only portability and style
+        /// codes can be demoted.
+        #[arg(long = "demote", value_name = "CODE")]
+        demote: Vec<String>,
     },
     /// Compiles source files into a bytecode container (.iplc) file.
     ///
@@ -290,6 +367,28 @@ enum Action {
         /// `--library Tc2_System`. See `check --library`.
         #[arg(long = "library")]
         libraries: Vec<LibraryName>,
+
+        /// Omit the runtime range check on a store into a subrange or
+        /// enumeration variable when the stored value is proven to be in
+        /// range at compile time.
+        #[arg(long)]
+        elide_range_checks: bool,
+
+        /// Report the problem code as a warning instead of an error
+        /// (repeatable). See `check --demote`.
+        #[arg(long = "demote", value_name = "CODE")]
+        demote: Vec<String>,
+
+        /// Print the resources the program needs and a worst-case
+        /// execution time estimate for each task next to its INTERVAL and
+        /// watchdog, and list loops with no iteration bound.
+        #[arg(long)]
+        report: bool,
+
+        /// Fail when the program needs more stack, call depth, data region,
+        /// temporary buffers or tasks than the target profile allows.
+        #[arg(long, value_name = "PROFILE")]
+        target: Option<ClapTarget>,
     },
     /// The echo action reads (parses) the libraries and writes the context to the
     /// standard output.
@@ -309,6 +408,43 @@ enum Action {
         #[command(flatten)]
         file_args: FileArgs,
     },
+    /// Formats Structured Text source files in place.
+    ///
+    /// Formatting keeps comments, pragmas and blank-line grouping and only
+    /// changes whitespace and keyword case.
+    Fmt {
+        #[command(flatten)]
+        file_args: FileArgs,
+
+        /// Report files that are not formatted instead of rewriting them.
+        #[arg(long)]
+        check: bool,
+
+        /// Number of spaces per indentation level, at least 1.
+        #[arg(
+            long,
+            default_value_t = 4,
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+        )]
+        indent_width: usize,
+
+        /// Indent with tabs rather than spaces.
+        #[arg(long)]
+        indent_tabs: bool,
+
+        /// Case used for keywords.
+        #[arg(long, value_enum, default_value = "upper")]
+        keyword_case: ClapKeywordCase,
+
+        /// Do not align the colons of consecutive declarations.
+        #[arg(long)]
+        no_align: bool,
+
+        /// Report the problem code as a warning instead of an error
+        /// (repeatable). See `check --demote`.
+        #[arg(long = "demote", value_name = "CODE")]
+        demote: Vec<String>,
+    },
     /// Run in Language Server Protocol mode to integrate with development tools.
     Lsp {
         #[arg(long)]
@@ -331,21 +467,34 @@ pub fn main() -> Result<(), String> {
         Action::Check {
             file_args,
             libraries,
+            demote,
         } => cli::check(
             &file_args.files,
             file_args.compiler_options(),
             &libraries,
+            &demote,
             false,
         ),
         Action::Compile {
             file_args,
             output,
             libraries,
+            elide_range_checks,
+            demote,
+            report,
+            target,
         } => cli::compile(
             &file_args.files,
             &output,
             file_args.compiler_options(),
+            &CodegenOptions {
+                elide_proven_range_checks: elide_range_checks,
+                target: target.map(|t| *t.0),
+                ..CodegenOptions::default()
+            },
             &libraries,
+            &demote,
+            report,
             false,
         ),
         Action::Echo { file_args } => {
@@ -354,6 +503,33 @@ pub fn main() -> Result<(), String> {
         Action::Tokenize { file_args } => {
             cli::tokenize(&file_args.files, file_args.compiler_options(), false)
         }
+        Action::Fmt {
+            file_args,
+            check,
+            indent_width,
+            indent_tabs,
+            keyword_case,
+            no_align,
+            demote,
+        } => {
+            let format_options = FormatOptions {
+                indent: if indent_tabs {
+                    Indent::Tab
+                } else {
+                    Indent::Spaces(indent_width)
+                },
+                keyword_case: keyword_case.into(),
+                align_declarations: !no_align,
+            };
+            cli::format(
+                &file_args.files,
+                file_args.compiler_options(),
+                &format_options,
+                check,
+                &demote,
+                false,
+            )
+        }
         Action::Dialects => {
             print!("{}", describe_dialects());
             Ok(())
@@ -370,6 +546,15 @@ mod tests {
     use super::*;
     use clap::ValueEnum;
 
+    #[test]
+    fn fmt_when_indent_width_zero_then_rejected() {
+        let zero = Args::try_parse_from(["ironplcc", "fmt", "--indent-width", "0", "main.st"]);
+        let one = Args::try_parse_from(["ironplcc", "fmt", "--indent-width", "1", "main.st"]);
+
+        assert!(zero.is_err());
+        assert!(one.is_ok());
+    }
+
     #[test]
     fn clap_dialect_value_variants_when_compared_then_matches_dialect_all() {
         let clap_variants: Vec<Dialect> =
@@ -377,6 +562,13 @@ mod tests {
         assert_eq!(clap_variants.as_slice(), Dialect::ALL);
     }
 
+    #[test]
+    fn clap_target_value_variants_when_compared_then_matches_target_profiles() {
+        let clap_variants: Vec<TargetProfile> =
+            ClapTarget::value_variants().iter().map(|c| *c.0).collect();
+        assert_eq!(clap_variants.as_slice(), TARGET_PROFILES);
+    }
+
     /// Guards the hand-maintained `FileArgs` flag list against drifting out of
     /// sync with the compiler's `FEATURE_DESCRIPTORS`: every dialect flag must be
     /// reachable via its `--allow-*` CLI form and wired through to
@@ -408,6 +600,34 @@ mod tests {
         }
     }
 
+    /// Same guard as above for the opt-in analysis checks in
+    /// `CHECK_DESCRIPTORS`.
+    #[test]
+    fn file_args_when_each_check_cli_form_passed_then_option_enabled() {
+        #[derive(Parser)]
+        struct TestCli {
+            #[command(flatten)]
+            file_args: FileArgs,
+        }
+
+        for cd in CompilerOptions::CHECK_DESCRIPTORS {
+            let cli = TestCli::try_parse_from(["ironplcc", cd.cli_flag]).unwrap_or_else(|e| {
+                panic!(
+                    "CLI does not accept `{}` (for CompilerOptions.{}): {e}",
+                    cd.cli_flag, cd.option_key
+                )
+            });
+            let options = cli.file_args.compiler_options();
+            assert_eq!(
+                options.get_flag_by_key(cd.option_key),
+                Some(true),
+                "`{}` parsed but did not enable CompilerOptions.{}",
+                cd.cli_flag,
+                cd.option_key
+            );
+        }
+    }
+
     /// Guards the hand-maintained VS Code `ironplc.dialect` settings schema
     /// against drifting out of sync with `Dialect::ALL`. JSON cannot read the
     /// Rust enum, so this test parses `integrations/vscode/package.json` and
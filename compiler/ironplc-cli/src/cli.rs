@@ -10,8 +10,9 @@ use codespan_reporting::{
 };
 use ironplc_dsl::{
     core::FileId,
-    diagnostic::{Diagnostic, Label},
+    diagnostic::{Demotions, Diagnostic, Label, Severity as DiagnosticSeverity},
 };
+use ironplc_plc2plc::formatter::FormatOptions;
 use ironplc_plc2plc::write_to_string;
 use ironplc_problems::Problem;
 use log::{error, trace};
@@ -22,9 +23,13 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use ironplc_codegen::target::ResourceUsage;
+use ironplc_codegen::{wcet, CodegenOptions};
 use ironplc_parser::options::CompilerOptions;
 use ironplc_sources::discovery::DiscoveredProject;
-use ironplc_sources::LibraryName;
+use ironplc_sources::naming::NamingConventions;
+use ironplc_sources::settings::{demotions_from, ProjectSettings};
+use ironplc_sources::{FileType, LibraryName};
 
 use ironplc_project::tokenizer;
 use ironplc_project::{FileBackedProject, Project};
@@ -34,9 +39,10 @@ pub fn check(
     paths: &[PathBuf],
     compiler_options: CompilerOptions,
     libraries: &[LibraryName],
+    demote: &[String],
     suppress_output: bool,
 ) -> Result<(), String> {
-    let (mut project, mut diagnostics) = create_project(paths, compiler_options, libraries);
+    let (mut project, mut diagnostics) = create_project(paths, compiler_options, libraries, demote);
 
     // Analyze the set
     diagnostics.extend(project.semantic());
@@ -50,7 +56,7 @@ pub fn echo(
     suppress_output: bool,
 ) -> Result<(), String> {
     // Echo renders parsed source; it runs no analysis, so no library activation.
-    let (mut project, mut diagnostics) = create_project(paths, compiler_options, &[]);
+    let (mut project, mut diagnostics) = create_project(paths, compiler_options, &[], &[]);
 
     // Collect the results and output after because getting the results may change
     // the project itself
@@ -78,7 +84,7 @@ pub fn tokenize(
     suppress_output: bool,
 ) -> Result<(), String> {
     // Tokenize only lexes each source; library activation is irrelevant here.
-    let (project, mut diagnostics) = create_project(paths, compiler_options, &[]);
+    let (project, mut diagnostics) = create_project(paths, compiler_options, &[], &[]);
 
     for src in project.sources() {
         diagnostics.extend(tokenizer::tokenize_source(src));
@@ -87,18 +93,78 @@ pub fn tokenize(
     finish("Tokenize", diagnostics, Some(&project), suppress_output)
 }
 
+/// Formats Structured Text source files in place.
+///
+/// With `check`, files are left as they are and each file that formatting
+/// would change is reported instead. Files in other formats (PLCopen XML,
+/// TwinCAT) are skipped because their Structured Text is embedded in XML.
+pub fn format(
+    paths: &[PathBuf],
+    compiler_options: CompilerOptions,
+    format_options: &FormatOptions,
+    check: bool,
+    demote: &[String],
+    suppress_output: bool,
+) -> Result<(), String> {
+    // Formatting works on tokens; library activation is irrelevant here.
+    let (project, mut diagnostics) = create_project(paths, compiler_options, &[], demote);
+
+    for src in project.sources() {
+        if src.file_type() != FileType::StructuredText {
+            trace!("Skipping formatting {}", src.file_id());
+            continue;
+        }
+        let formatted = match ironplc_plc2plc::formatter::format(
+            src.as_string(),
+            src.file_id(),
+            &compiler_options,
+            format_options,
+        ) {
+            Ok(formatted) => formatted.text(),
+            Err(errs) => {
+                diagnostics.extend(errs);
+                continue;
+            }
+        };
+        if formatted == src.as_string() {
+            continue;
+        }
+
+        let path = PathBuf::from(src.file_id().to_string());
+        if check {
+            diagnostics.extend(diagnostic(
+                Problem::FileNotFormatted,
+                &path,
+                String::from("Run ironplcc fmt to format the file"),
+            ));
+        } else if let Err(e) = std::fs::write(&path, formatted) {
+            diagnostics.extend(diagnostic(Problem::CannotWriteFile, &path, e.to_string()));
+        }
+    }
+    project.demotions().apply(&mut diagnostics);
+
+    finish("Format", diagnostics, Some(&project), suppress_output)
+}
+
 /// Compiles source files into a bytecode container (.iplc) file.
 ///
 /// Parses the source files, runs full analysis (type resolution + semantic
-/// checks), and generates bytecode.
+/// checks), and generates bytecode. With `report`, also prints the resources
+/// the container needs and the worst-case execution time estimate of each
+/// task, against the limits of the target profile in `codegen_options` when
+/// there is one.
+#[allow(clippy::too_many_arguments)]
 pub fn compile(
     paths: &[PathBuf],
     output: &Path,
     compiler_options: CompilerOptions,
+    codegen_options: &CodegenOptions,
     libraries: &[LibraryName],
+    demote: &[String],
+    report: bool,
     suppress_output: bool,
 ) -> Result<(), String> {
-    let (mut project, mut diagnostics) = create_project(paths, compiler_options, libraries);
+    let (mut project, mut diagnostics) = create_project(paths, compiler_options, libraries, demote);
 
     // Refuse to write the container over a loaded source file. `File::create`
     // truncates immediately, so this must be diagnosed before codegen runs:
@@ -121,8 +187,13 @@ pub fn compile(
     // Handing it the diagnostics collected so far is what makes a
     // discovery-time problem suppress the container while still letting
     // analysis run against the files that did resolve.
-    let output_result =
-        ironplc_project::compile(&mut project, &compiler_options, &source_lookup, diagnostics);
+    let mut output_result = ironplc_project::compile(
+        &mut project,
+        &compiler_options,
+        codegen_options,
+        &source_lookup,
+        diagnostics,
+    );
 
     if let Some(container) = output_result.container {
         // Write the container to the output file
@@ -131,6 +202,22 @@ pub fn compile(
         container
             .write_to(&mut out_file)
             .map_err(|e| format!("Failed to write output file: {e}"))?;
+
+        if report {
+            let target = codegen_options.target.as_ref();
+            let ns_per_unit = target.map_or(wcet::DEFAULT_NS_PER_COST_UNIT, |t| t.ns_per_cost_unit);
+            match wcet::estimate(&container) {
+                Ok(mut estimate) if !suppress_output => {
+                    if let Some(library) = project.analyzed_library() {
+                        estimate.name_tasks(|program| bound_task_name(library, program));
+                    }
+                    print!("{}", ResourceUsage::of(&container).render(target));
+                    print!("{}", estimate.render(ns_per_unit));
+                }
+                Ok(_) => {}
+                Err(diagnostic) => output_result.diagnostics.push(diagnostic),
+            }
+        }
     }
 
     finish(
@@ -141,6 +228,25 @@ pub fn compile(
     )
 }
 
+/// Returns the name of the `TASK` that a configuration in `library` binds
+/// the program type `program` to with `WITH`.
+fn bound_task_name(library: &ironplc_dsl::common::Library, program: &str) -> Option<String> {
+    library.elements.iter().find_map(|element| {
+        let ironplc_dsl::common::LibraryElementKind::ConfigurationDeclaration(config) = element
+        else {
+            return None;
+        };
+        config.resource_decl.iter().find_map(|resource| {
+            resource
+                .programs
+                .iter()
+                .find(|instance| instance.type_name.to_string().eq_ignore_ascii_case(program))
+                .and_then(|instance| instance.task_name.as_ref())
+                .map(|task| task.to_string())
+        })
+    })
+}
+
 /// Codegen [`SourceLookup`](ironplc_codegen::SourceLookup) backed by an
 /// in-memory map populated from the project's loaded sources. The map
 /// owns the bytes so the lookup can outlive any borrow on the project.
@@ -165,8 +271,9 @@ impl ironplc_codegen::SourceLookup for HashMapSourceLookup {
 }
 
 /// Prints every diagnostic a command collected and derives the command's
-/// final result from that same collection: `Ok` when there are none,
-/// otherwise `Err` naming the command and the problem count.
+/// final result from that same collection: `Ok` when none is an error,
+/// otherwise `Err` naming the command and the error count. Diagnostics
+/// demoted to warnings (ADR-0039) are printed but do not fail the command.
 ///
 /// This is the single fold point from diagnostics (data) to exit status
 /// (control flow). Commands accumulate diagnostics from every stage —
@@ -182,12 +289,13 @@ fn finish(
     if diagnostics.is_empty() {
         return Ok(());
     }
-    trace!("Errors {diagnostics:?}");
+    trace!("Diagnostics {diagnostics:?}");
     handle_diagnostics(&diagnostics, project, suppress_output);
-    Err(format!(
-        "{command} failed with {} problem(s)",
-        diagnostics.len()
-    ))
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    if errors == 0 {
+        return Ok(());
+    }
+    Err(format!("{command} failed with {errors} problem(s)"))
 }
 
 /// Builds a project from `paths`, running discovery and loading every
@@ -201,10 +309,15 @@ fn finish(
 /// against it — real diagnostics in the resolvable files must not be
 /// hidden behind a discovery-time problem — and fold the returned
 /// diagnostics into their final result via [`finish`].
+///
+/// `demote` are the problem codes given with `--demote`; the project reports
+/// them, and those its `ironplc.toml` lists, as warnings. A code that cannot
+/// be demoted is a diagnostic like any other discovery problem.
 fn create_project(
     paths: &[PathBuf],
     compiler_options: CompilerOptions,
     libraries: &[LibraryName],
+    demote: &[String],
 ) -> (FileBackedProject, Vec<Diagnostic>) {
     trace!("Reading paths {paths:?}");
     let mut files: Vec<PathBuf> = vec![];
@@ -212,22 +325,29 @@ fn create_project(
     // files reference. The explicit set is applied first so it takes
     // precedence in ordering; discovered libraries are appended, deduplicated.
     let mut activated_libraries: Vec<LibraryName> = libraries.to_vec();
-    let mut diagnostics: Vec<Diagnostic> = vec![];
+    let (mut demotions, mut diagnostics) =
+        demotions_from(demote.iter().map(String::as_str), &FileId::default());
+    let mut naming = NamingConventions::default();
 
     for path in paths {
-        let (mut resolved, discovered_libraries, path_diagnostics) = enumerate_files(path);
+        let (mut resolved, discovered_libraries, settings, path_diagnostics) =
+            enumerate_files(path);
         files.append(&mut resolved);
         for library in discovered_libraries {
             if !activated_libraries.contains(&library) {
                 activated_libraries.push(library);
             }
         }
+        demotions.extend(&settings.demotions);
+        naming.extend(&settings.naming);
         diagnostics.extend(path_diagnostics);
     }
 
     // Create the project
     let mut project = FileBackedProject::with_options(compiler_options);
     project.set_activated_libraries(activated_libraries);
+    project.set_demotions(demotions);
+    project.set_naming_conventions(naming);
 
     for file_path in files {
         if let Err(err) = project.push(FileId::from_path(&file_path)) {
@@ -252,7 +372,7 @@ fn create_project(
 /// files DID resolve, rather than aborting enumeration entirely -- but
 /// they are still genuine errors: the caller must still fail the overall
 /// command if this returns any diagnostics.
-fn enumerate_files(path: &PathBuf) -> (Vec<PathBuf>, Vec<LibraryName>, Vec<Diagnostic>) {
+fn enumerate_files(path: &PathBuf) -> EnumeratedFiles {
     // Get the canonical path so that error messages are unambiguous
     let path = match canonicalize(path) {
         Ok(path) => path,
@@ -260,6 +380,7 @@ fn enumerate_files(path: &PathBuf) -> (Vec<PathBuf>, Vec<LibraryName>, Vec<Diagn
             return (
                 vec![],
                 vec![],
+                EnumeratedSettings::default(),
                 diagnostic(
                     Problem::CannotCanonicalizePath,
                     path,
@@ -276,6 +397,7 @@ fn enumerate_files(path: &PathBuf) -> (Vec<PathBuf>, Vec<LibraryName>, Vec<Diagn
             return (
                 vec![],
                 vec![],
+                EnumeratedSettings::default(),
                 diagnostic(Problem::CannotReadMetadata, &path, e.to_string()),
             );
         }
@@ -284,6 +406,7 @@ fn enumerate_files(path: &PathBuf) -> (Vec<PathBuf>, Vec<LibraryName>, Vec<Diagn
         return (
             vec![],
             vec![],
+            EnumeratedSettings::default(),
             diagnostic(Problem::SymlinkUnsupported, &path, String::from("")),
         );
     }
@@ -291,15 +414,29 @@ fn enumerate_files(path: &PathBuf) -> (Vec<PathBuf>, Vec<LibraryName>, Vec<Diagn
     enumerate_project(ironplc_sources::discovery::discover(&path))
 }
 
-/// Flattens a discovered project into the files, libraries, and problems
-/// [`enumerate_files`] reports.
+/// The demotions and naming rules of a project's `ironplc.toml`.
+#[derive(Default)]
+struct EnumeratedSettings {
+    demotions: Demotions,
+    naming: NamingConventions,
+}
+
+/// The files, libraries, settings and problems of one path.
+type EnumeratedFiles = (
+    Vec<PathBuf>,
+    Vec<LibraryName>,
+    EnumeratedSettings,
+    Vec<Diagnostic>,
+);
+
+/// Flattens a discovered project into the files, libraries, settings and
+/// problems [`enumerate_files`] reports.
 ///
 /// Auto-activates the libraries a discovered project file references,
 /// alongside any files it declares. Referenced but unshipped libraries
-/// contribute a diagnostic naming them.
-fn enumerate_project(
-    discovered: Result<DiscoveredProject, Diagnostic>,
-) -> (Vec<PathBuf>, Vec<LibraryName>, Vec<Diagnostic>) {
+/// contribute a diagnostic naming them. The project's `ironplc.toml`, if
+/// any, supplies the demotions and naming rules.
+fn enumerate_project(discovered: Result<DiscoveredProject, Diagnostic>) -> EnumeratedFiles {
     match discovered {
         Ok(project) => {
             let (libraries, library_diagnostics) =
@@ -307,9 +444,23 @@ fn enumerate_project(
                     .resolve_references(&project.library_references);
             let mut diagnostics = project.errors;
             diagnostics.extend(library_diagnostics);
-            (project.files, libraries, diagnostics)
+            let settings = match ProjectSettings::load(&project.root_dir) {
+                Ok(settings) => {
+                    let (demotions, refused) = settings.demotions();
+                    diagnostics.extend(refused);
+                    EnumeratedSettings {
+                        demotions,
+                        naming: settings.naming,
+                    }
+                }
+                Err(e) => {
+                    diagnostics.push(e);
+                    EnumeratedSettings::default()
+                }
+            };
+            (project.files, libraries, settings, diagnostics)
         }
-        Err(e) => (vec![], vec![], vec![e]),
+        Err(e) => (vec![], vec![], EnumeratedSettings::default(), vec![e]),
     }
 }
 
@@ -397,28 +548,38 @@ fn map_diagnostic(
 ) -> CodeSpanDiagnostic<usize> {
     let description = diagnostic.description();
 
-    // Set the primary labels
-    let mut labels = vec![map_label(
-        &diagnostic.primary,
-        LabelStyle::Primary,
-        file_to_id,
-    )];
-
-    // Add any secondary labels
-    labels.extend(
+    // A label on no file (such as a problem with a command-line argument)
+    // has no position to show, so its message becomes a note rather than a
+    // label at an empty `:1:1`.
+    let no_file = FileId::default();
+    let mut labels = vec![];
+    let mut notes = vec![];
+    let all_labels = std::iter::once((&diagnostic.primary, LabelStyle::Primary)).chain(
         diagnostic
             .secondary
             .iter()
-            .map(|lbl| map_label(lbl, LabelStyle::Secondary, file_to_id)),
+            .map(|lbl| (lbl, LabelStyle::Secondary)),
     );
+    for (label, style) in all_labels {
+        if label.file_id == no_file {
+            notes.push(label.message.clone());
+        } else {
+            labels.push(map_label(label, style, file_to_id));
+        }
+    }
 
     // Existing help notes, then a trailing link to the problem-code docs so a
     // CLI user can follow the same reference page the editor and playground link
     // to (and so that follow-through is attributable to the CLI in analytics).
-    let mut notes = diagnostic.help().to_vec();
+    notes.extend(diagnostic.help().iter().cloned());
     notes.push(format!("Learn more: {}", problem_help_url(diagnostic)));
 
-    CodeSpanDiagnostic::new(Severity::Error)
+    let severity = match diagnostic.severity {
+        DiagnosticSeverity::Error => Severity::Error,
+        DiagnosticSeverity::Warning => Severity::Warning,
+    };
+
+    CodeSpanDiagnostic::new(severity)
         .with_code(diagnostic.code.clone())
         .with_message(description)
         .with_labels(labels)
@@ -465,6 +626,7 @@ fn diagnostic(problem: Problem, path: &Path, message: String) -> Vec<Diagnostic>
 mod tests {
     use ironplc_test::shared_resource_path;
 
+    use ironplc_codegen::CodegenOptions;
     use ironplc_parser::options::CompilerOptions;
 
     use crate::{cli::check, cli::compile, cli::tokenize, test_helpers::resource_path};
@@ -512,7 +674,7 @@ mod tests {
     #[test]
     fn check_first_steps_dir_when_valid_syntax_then_ok() {
         let paths = vec![resource_path("set")];
-        let result = check(&paths, CompilerOptions::default(), &[], true);
+        let result = check(&paths, CompilerOptions::default(), &[], &[], true);
         assert!(result.is_ok())
     }
 
@@ -534,7 +696,7 @@ mod tests {
         .unwrap();
 
         let paths = vec![dir.path().to_path_buf()];
-        let result = check(&paths, CompilerOptions::default(), &[], true);
+        let result = check(&paths, CompilerOptions::default(), &[], &[], true);
         assert!(result.is_err())
     }
 
@@ -560,7 +722,7 @@ mod tests {
         .unwrap();
 
         let paths = vec![dir.path().join("project.plcproj")];
-        let err = check(&paths, CompilerOptions::default(), &[], true).unwrap_err();
+        let err = check(&paths, CompilerOptions::default(), &[], &[], true).unwrap_err();
 
         // Exactly the semantic error in A.st: the .plcproj resolved to
         // its sources instead of being parsed as Structured Text, which
@@ -593,12 +755,13 @@ mod tests {
             &[dir.path().to_path_buf()],
             CompilerOptions::default(),
             &[],
+            &[],
             true
         )
         .is_ok());
 
         let named = vec![dir.path().join("ZZZ.plcproj")];
-        let err = check(&named, CompilerOptions::default(), &[], true).unwrap_err();
+        let err = check(&named, CompilerOptions::default(), &[], &[], true).unwrap_err();
         assert_eq!(problem_count(&err), 1, "{err}");
     }
 
@@ -631,7 +794,7 @@ mod tests {
         .unwrap();
 
         let paths = vec![dir.path().to_path_buf()];
-        let result = check(&paths, CompilerOptions::default(), &[], true);
+        let result = check(&paths, CompilerOptions::default(), &[], &[], true);
         let err = result.unwrap_err();
         assert!(
             problem_count(&err) >= 2,
@@ -663,7 +826,7 @@ mod tests {
         .unwrap();
 
         let paths = vec![dir.path().to_path_buf()];
-        let result = check(&paths, CompilerOptions::default(), &[], true);
+        let result = check(&paths, CompilerOptions::default(), &[], &[], true);
         let err = result.unwrap_err();
         assert!(
             problem_count(&err) >= 2,
@@ -706,7 +869,16 @@ mod tests {
 
         let paths = vec![dir.path().to_path_buf()];
         let output = tempfile::NamedTempFile::new().unwrap();
-        let result = compile(&paths, output.path(), CompilerOptions::default(), &[], true);
+        let result = compile(
+            &paths,
+            output.path(),
+            CompilerOptions::default(),
+            &CodegenOptions::default(),
+            &[],
+            &[],
+            false,
+            true,
+        );
 
         assert!(result.is_err());
         assert_eq!(output.path().metadata().unwrap().len(), 0);
@@ -716,7 +888,17 @@ mod tests {
     fn compile_when_output_is_valid_container_then_roundtrips() {
         let paths = vec![shared_resource_path("steel_thread.st")];
         let output = tempfile::NamedTempFile::new().unwrap();
-        compile(&paths, output.path(), CompilerOptions::default(), &[], true).unwrap();
+        compile(
+            &paths,
+            output.path(),
+            CompilerOptions::default(),
+            &CodegenOptions::default(),
+            &[],
+            &[],
+            false,
+            true,
+        )
+        .unwrap();
 
         // Verify the output is a valid container by reading it back
         let mut file = std::fs::File::open(output.path()).unwrap();
@@ -756,7 +938,10 @@ mod tests {
             &[source.path().to_path_buf()],
             output.path(),
             CompilerOptions::default(),
+            &CodegenOptions::default(),
+            &[],
             &[],
+            false,
             true,
         );
         assert!(result.is_err());
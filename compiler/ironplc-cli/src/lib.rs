@@ -7,8 +7,14 @@ extern crate ironplc_parser;
 pub mod cli;
 pub mod logger;
 pub mod lsp;
+pub mod lsp_code_action;
+pub mod lsp_completion;
+pub mod lsp_hover;
+pub mod lsp_monitor;
 pub mod lsp_project;
 pub mod lsp_runner;
+pub mod lsp_signature;
+pub mod lsp_symbol;
 
 #[cfg(test)]
 mod test_helpers;
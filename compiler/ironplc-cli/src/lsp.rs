@@ -1,26 +1,39 @@
 //! Implements the language server protocol for integration with an IDE such
 //! as Visual Studio Code.
 
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use ironplc_parser::options::{CompilerOptions, Dialect};
-use log::{debug, trace};
-use lsp_server::{Connection, ExtractError, Message, RequestId};
+use log::{debug, trace, warn};
+use lsp_server::{Connection, ErrorCode, ExtractError, Message, RequestId};
 use lsp_types::{
-    notification::{self, Notification, PublishDiagnostics},
+    notification::{self, Cancel, Notification, PublishDiagnostics},
     request::{self, Request},
-    InitializeParams, OneOf, PublishDiagnosticsParams, SemanticTokens, SemanticTokensFullOptions,
-    SemanticTokensLegend, SemanticTokensOptions, SemanticTokensResult,
-    SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentSyncCapability,
-    TextDocumentSyncKind, Uri, WorkDoneProgressOptions, WorkspaceFoldersServerCapabilities,
-    WorkspaceServerCapabilities,
+    CallHierarchyServerCapability, CancelParams, CodeActionKind, CodeActionOptions,
+    CodeActionProviderCapability, CompletionOptions, CompletionResponse, ExecuteCommandOptions,
+    GotoDefinitionResponse, HoverProviderCapability, InitializeParams, NumberOrString, OneOf,
+    PositionEncodingKind, PrepareRenameResponse, PublishDiagnosticsParams, RenameOptions,
+    SemanticTokens, SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
+    SemanticTokensResult, SemanticTokensServerCapabilities, ServerCapabilities,
+    SignatureHelpOptions, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TypeDefinitionProviderCapability, Uri, WorkDoneProgressOptions,
+    WorkspaceFoldersServerCapabilities, WorkspaceServerCapabilities, WorkspaceSymbolResponse,
 };
 use serde::{de::DeserializeOwned, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
+use std::time::Duration;
 
+use crate::lsp_code_action::{self, ENABLE_OPTION_COMMAND};
+use crate::lsp_monitor::Monitor;
 use crate::lsp_project::{LspProject, UriKey, TOKEN_TYPE_LEGEND};
+use ironplc_dsl::diagnostic::Demotions;
 use ironplc_project::disassemble;
-use ironplc_project::FileBackedProject;
+use ironplc_project::{FileBackedProject, Project};
+
+/// How long the server waits after an edit, for more edits, before it
+/// analyzes the workspace and publishes diagnostics. Typing then costs one
+/// analysis per pause rather than one per keystroke.
+const DIAGNOSTICS_DEBOUNCE: Duration = Duration::from_millis(200);
 
 /// Start the LSP server.
 ///
@@ -87,12 +100,39 @@ fn extract_compiler_options(initialize_params: &InitializeParams) -> CompilerOpt
                 options.set_flag_by_key(fd.option_key, true);
             }
         }
+        // Opt-in analysis checks use the same key form (`checkTaskRaces`).
+        for cd in CompilerOptions::CHECK_DESCRIPTORS {
+            let key = to_lower_camel_case(cd.option_key);
+            if opts.get(&key).and_then(|v| v.as_bool()).unwrap_or(false) {
+                options.set_flag_by_key(cd.option_key, true);
+            }
+        }
         options
     } else {
         CompilerOptions::default()
     }
 }
 
+/// Extract the problem codes to report as warnings from LSP initialization
+/// options.
+///
+/// Reads `"demote"`, an array of problem codes (e.g. `["P4041"]`). A code
+/// that cannot be demoted is logged and stays an error (ADR-0039).
+fn extract_demotions(initialize_params: &InitializeParams) -> Demotions {
+    let mut demotions = Demotions::default();
+    let codes = initialize_params
+        .initialization_options
+        .as_ref()
+        .and_then(|opts| opts.get("demote"))
+        .and_then(|v| v.as_array());
+    for code in codes.into_iter().flatten().filter_map(|v| v.as_str()) {
+        if let Err(reason) = demotions.demote(code) {
+            warn!("Ignoring demote option: {reason}");
+        }
+    }
+    demotions
+}
+
 /// Start the LSP server using the connection for communication.
 ///
 /// When `project_override` is `None`, the project is constructed from
@@ -121,10 +161,9 @@ fn start_with_connection(
         Some(project) => project,
         None => {
             let compiler_options = extract_compiler_options(&initialize_params);
-            LspProject::with_options(
-                Box::new(FileBackedProject::with_options(compiler_options)),
-                compiler_options,
-            )
+            let mut project = FileBackedProject::with_options(compiler_options);
+            project.set_demotions(extract_demotions(&initialize_params));
+            LspProject::with_options(Box::new(project), compiler_options)
         }
     };
 
@@ -160,6 +199,9 @@ struct LspServer<'a> {
     /// rather than `Uri` so the set is independent of `lsp_types::Uri`'s
     /// interior `Cell`s.
     published_uris: HashSet<UriKey>,
+    /// Documents edited since diagnostics were last published, each with
+    /// the version of its latest edit.
+    pending: Vec<(Uri, Option<i32>)>,
 }
 
 impl<'a> LspServer<'a> {
@@ -169,7 +211,12 @@ impl<'a> LspServer<'a> {
     /// do.
     fn server_capabilities() -> ServerCapabilities {
         ServerCapabilities {
-            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            // Every position the server receives or sends counts columns in
+            // UTF-16 code units.
+            position_encoding: Some(PositionEncodingKind::UTF16),
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                TextDocumentSyncKind::INCREMENTAL,
+            )),
             semantic_tokens_provider: Some(
                 SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
                     // We don't report progress in generating tokens so
@@ -193,6 +240,35 @@ impl<'a> LspServer<'a> {
                 file_operations: None,
             }),
             document_symbol_provider: Some(OneOf::Left(true)),
+            definition_provider: Some(OneOf::Left(true)),
+            type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
+            references_provider: Some(OneOf::Left(true)),
+            workspace_symbol_provider: Some(OneOf::Left(true)),
+            call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            completion_provider: Some(CompletionOptions {
+                trigger_characters: Some(vec![".".to_string(), "#".to_string()]),
+                ..CompletionOptions::default()
+            }),
+            rename_provider: Some(OneOf::Right(RenameOptions {
+                prepare_provider: Some(true),
+                work_done_progress_options: WorkDoneProgressOptions::default(),
+            })),
+            code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                ..CodeActionOptions::default()
+            })),
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: vec![ENABLE_OPTION_COMMAND.to_string()],
+                ..ExecuteCommandOptions::default()
+            }),
+            document_formatting_provider: Some(OneOf::Left(true)),
+            document_range_formatting_provider: Some(OneOf::Left(true)),
+            signature_help_provider: Some(SignatureHelpOptions {
+                trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+                ..SignatureHelpOptions::default()
+            }),
+            inlay_hint_provider: Some(OneOf::Left(true)),
             ..ServerCapabilities::default()
         }
     }
@@ -202,6 +278,7 @@ impl<'a> LspServer<'a> {
             sender,
             project,
             published_uris: HashSet::new(),
+            pending: vec![],
         }
     }
 
@@ -209,12 +286,16 @@ impl<'a> LspServer<'a> {
     /// diagnostics for every affected file. Sends an empty-clear
     /// notification for every URI that previously had diagnostics
     /// but no longer does. The version field is attached only to the
-    /// notification for `edited_uri` (the document that triggered
-    /// this round of analysis); other URIs receive `version: None`,
-    /// which the LSP spec permits and signals "out of band" to the
-    /// client.
-    fn publish_workspace_diagnostics(&mut self, edited_uri: &Uri, edited_version: Option<i32>) {
-        let edited_key = UriKey::from_uri(edited_uri);
+    /// notifications for `edited` (the documents that triggered this
+    /// round of analysis); other URIs receive `version: None`, which
+    /// the LSP spec permits and signals "out of band" to the client.
+    fn publish_workspace_diagnostics(&mut self, edited: &[(Uri, Option<i32>)]) {
+        let edited_version = |key: &UriKey| {
+            edited
+                .iter()
+                .find(|(uri, _)| UriKey::from_uri(uri) == *key)
+                .and_then(|(_, version)| *version)
+        };
         let by_key = self.project.semantic_all();
         let mut new_published: HashSet<UriKey> = HashSet::new();
 
@@ -222,11 +303,7 @@ impl<'a> LspServer<'a> {
             if diagnostics.is_empty() {
                 continue;
             }
-            let version = if key == edited_key {
-                edited_version
-            } else {
-                None
-            };
+            let version = edited_version(&key);
             let uri = key.to_uri();
             new_published.insert(key);
             self.send_notification::<PublishDiagnostics>(PublishDiagnosticsParams {
@@ -245,11 +322,7 @@ impl<'a> LspServer<'a> {
             .cloned()
             .collect();
         for key in stale {
-            let version = if key == edited_key {
-                edited_version
-            } else {
-                None
-            };
+            let version = edited_version(&key);
             self.send_notification::<PublishDiagnostics>(PublishDiagnosticsParams {
                 uri: key.to_uri(),
                 diagnostics: vec![],
@@ -257,40 +330,107 @@ impl<'a> LspServer<'a> {
             });
         }
 
-        // The edited URI must always receive a notification, even
+        // An edited URI must always receive a notification, even
         // when it is currently error-free and was never previously
         // published. Without this the editor would never see the
         // initial "no problems" state for a freshly-opened file and
         // the LSP test harness — which expects one notification per
-        // edit — would block.
-        if !new_published.contains(&edited_key) && !self.published_uris.contains(&edited_key) {
-            self.send_notification::<PublishDiagnostics>(PublishDiagnosticsParams {
-                uri: edited_uri.clone(),
-                diagnostics: vec![],
-                version: edited_version,
-            });
+        // analyzed edit — would block.
+        for (uri, version) in edited {
+            let key = UriKey::from_uri(uri);
+            if !new_published.contains(&key) && !self.published_uris.contains(&key) {
+                self.send_notification::<PublishDiagnostics>(PublishDiagnosticsParams {
+                    uri: uri.clone(),
+                    diagnostics: vec![],
+                    version: *version,
+                });
+            }
         }
 
         self.published_uris = new_published;
     }
 
+    /// Records an edit whose diagnostics are published once the edits
+    /// pause, or before a request that reads the analysis.
+    fn edited(&mut self, uri: Uri, version: i32) {
+        self.pending.retain(|(pending, _)| pending != &uri);
+        self.pending.push((uri, Some(version)));
+    }
+
+    /// Analyzes and publishes the diagnostics of the pending edits.
+    fn flush_diagnostics(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let edited = std::mem::take(&mut self.pending);
+        self.publish_workspace_diagnostics(&edited);
+    }
+
     /// The main event loop. The event loop receives messages from the other
     /// end of the channel.
+    ///
+    /// Diagnostics for edits are published once no message has arrived for
+    /// [`DIAGNOSTICS_DEBOUNCE`]. Messages that queued up while the server
+    /// was busy are read together, so that a request the client cancelled,
+    /// or one about a document edited after it was sent, is answered with
+    /// an error rather than computed.
     fn run(&mut self, receiver: &Receiver<Message>) -> Result<lsp_server::Request, String> {
-        for msg in receiver {
-            match msg {
-                lsp_server::Message::Request(req) => {
-                    if req.method == request::Shutdown::METHOD {
-                        return Ok(req);
+        loop {
+            let msg = if self.pending.is_empty() {
+                match receiver.recv() {
+                    Ok(msg) => msg,
+                    Err(_) => break,
+                }
+            } else {
+                match receiver.recv_timeout(DIAGNOSTICS_DEBOUNCE) {
+                    Ok(msg) => msg,
+                    Err(RecvTimeoutError::Timeout) => {
+                        self.flush_diagnostics();
+                        continue;
                     }
-                    self.handle_request(req);
+                    Err(RecvTimeoutError::Disconnected) => break,
                 }
-                lsp_server::Message::Response(_) => {
-                    // LSP responses are typically handled by the client, not the server
-                    // For now, we just ignore them
+            };
+            // Reading stops at a shutdown request: the exit notification
+            // behind it is for `Connection::handle_shutdown` to receive.
+            let mut queued = vec![msg];
+            while !queued.last().is_some_and(is_shutdown) {
+                match receiver.try_recv() {
+                    Ok(msg) => queued.push(msg),
+                    Err(_) => break,
                 }
-                lsp_server::Message::Notification(notification) => {
-                    self.handle_notification(notification);
+            }
+            let mut refused = refused_requests(&queued);
+
+            for msg in queued {
+                match msg {
+                    lsp_server::Message::Request(req) => {
+                        if req.method == request::Shutdown::METHOD {
+                            return Ok(req);
+                        }
+                        match refused.remove(&req.id) {
+                            Some(ErrorCode::RequestCanceled) => self.send_error_code(
+                                req.id,
+                                ErrorCode::RequestCanceled,
+                                "The request was cancelled".to_owned(),
+                            ),
+                            Some(code) => self.send_error_code(
+                                req.id,
+                                code,
+                                "The document changed after the request".to_owned(),
+                            ),
+                            None => {
+                                self.handle_request(req);
+                            }
+                        }
+                    }
+                    lsp_server::Message::Response(_) => {
+                        // LSP responses are typically handled by the client, not the server
+                        // For now, we just ignore them
+                    }
+                    lsp_server::Message::Notification(notification) => {
+                        self.handle_notification(notification);
+                    }
                 }
             }
         }
@@ -300,6 +440,19 @@ impl<'a> LspServer<'a> {
 
     fn handle_request(&mut self, req: lsp_server::Request) -> &'static str {
         let req_id = req.id.clone();
+        // What the request reads comes from the tokens or the completion
+        // catalog, or needs the analysis of the pending edits.
+        if !matches!(
+            req.method.as_str(),
+            request::SemanticTokensFullRequest::METHOD
+                | request::Completion::METHOD
+                | request::SignatureHelpRequest::METHOD
+                | request::CodeActionRequest::METHOD
+                | request::Formatting::METHOD
+                | request::RangeFormatting::METHOD
+        ) {
+            self.flush_diagnostics();
+        }
         let req = match Self::cast_request::<request::Shutdown>(req) {
             Ok(_params) => {
                 return request::Shutdown::METHOD;
@@ -332,9 +485,10 @@ impl<'a> LspServer<'a> {
             }
             Err(req) => req,
         };
-        let _req = match Self::cast_request::<request::DocumentSymbolRequest>(req) {
+        let req = match Self::cast_request::<request::DocumentSymbolRequest>(req) {
             Ok(params) => {
                 let uri = params.text_document.uri;
+                self.project.ensure_analyzed(&uri);
                 let symbols = self.project.document_symbols(&uri);
 
                 trace!("DocumentSymbolRequest Response {symbols:?}");
@@ -344,6 +498,270 @@ impl<'a> LspServer<'a> {
             }
             Err(req) => req,
         };
+        let req = match Self::cast_request::<request::GotoDefinition>(req) {
+            Ok(params) => {
+                let position = params.text_document_position_params;
+                let location = self
+                    .project
+                    .definition(&position.text_document.uri, position.position);
+
+                trace!("GotoDefinition Response {location:?}");
+                self.send_response::<request::GotoDefinition>(
+                    req_id,
+                    location.map(GotoDefinitionResponse::Scalar),
+                );
+
+                return request::GotoDefinition::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::GotoTypeDefinition>(req) {
+            Ok(params) => {
+                let position = params.text_document_position_params;
+                let location = self
+                    .project
+                    .type_definition(&position.text_document.uri, position.position);
+
+                trace!("GotoTypeDefinition Response {location:?}");
+                self.send_response::<request::GotoTypeDefinition>(
+                    req_id,
+                    location.map(GotoDefinitionResponse::Scalar),
+                );
+
+                return request::GotoTypeDefinition::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::References>(req) {
+            Ok(params) => {
+                let position = params.text_document_position;
+                let locations = self.project.references(
+                    &position.text_document.uri,
+                    position.position,
+                    params.context.include_declaration,
+                );
+
+                trace!("References Response {locations:?}");
+                self.send_response::<request::References>(req_id, Some(locations));
+
+                return request::References::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::WorkspaceSymbolRequest>(req) {
+            Ok(params) => {
+                let symbols = self.project.workspace_symbols(&params.query);
+
+                trace!("WorkspaceSymbolRequest Response {} symbols", symbols.len());
+                self.send_response::<request::WorkspaceSymbolRequest>(
+                    req_id,
+                    Some(WorkspaceSymbolResponse::Nested(symbols)),
+                );
+
+                return request::WorkspaceSymbolRequest::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::CallHierarchyPrepare>(req) {
+            Ok(params) => {
+                let position = params.text_document_position_params;
+                let items = self
+                    .project
+                    .prepare_call_hierarchy(&position.text_document.uri, position.position);
+
+                trace!("CallHierarchyPrepare Response {items:?}");
+                self.send_response::<request::CallHierarchyPrepare>(req_id, items);
+
+                return request::CallHierarchyPrepare::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::CallHierarchyIncomingCalls>(req) {
+            Ok(params) => {
+                let calls = self.project.incoming_calls(&params.item);
+
+                trace!("CallHierarchyIncomingCalls Response {} calls", calls.len());
+                self.send_response::<request::CallHierarchyIncomingCalls>(req_id, Some(calls));
+
+                return request::CallHierarchyIncomingCalls::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::CallHierarchyOutgoingCalls>(req) {
+            Ok(params) => {
+                let calls = self.project.outgoing_calls(&params.item);
+
+                trace!("CallHierarchyOutgoingCalls Response {} calls", calls.len());
+                self.send_response::<request::CallHierarchyOutgoingCalls>(req_id, Some(calls));
+
+                return request::CallHierarchyOutgoingCalls::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::HoverRequest>(req) {
+            Ok(params) => {
+                let position = params.text_document_position_params;
+                self.project.ensure_analyzed(&position.text_document.uri);
+                let hover = self
+                    .project
+                    .hover(&position.text_document.uri, position.position);
+
+                trace!("Hover Response {hover:?}");
+                self.send_response::<request::HoverRequest>(req_id, hover);
+
+                return request::HoverRequest::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::Completion>(req) {
+            Ok(params) => {
+                let position = params.text_document_position;
+                let items = self
+                    .project
+                    .completion(&position.text_document.uri, position.position);
+
+                trace!("Completion Response {} items", items.len());
+                self.send_response::<request::Completion>(
+                    req_id,
+                    Some(CompletionResponse::Array(items)),
+                );
+
+                return request::Completion::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::SignatureHelpRequest>(req) {
+            Ok(params) => {
+                let position = params.text_document_position_params;
+                let help = self
+                    .project
+                    .signature_help(&position.text_document.uri, position.position);
+
+                trace!("SignatureHelp Response {help:?}");
+                self.send_response::<request::SignatureHelpRequest>(req_id, help);
+
+                return request::SignatureHelpRequest::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::InlayHintRequest>(req) {
+            Ok(params) => {
+                self.project.ensure_analyzed(&params.text_document.uri);
+                let hints = self
+                    .project
+                    .inlay_hints(&params.text_document.uri, params.range);
+
+                trace!("InlayHint Response {} hints", hints.len());
+                self.send_response::<request::InlayHintRequest>(req_id, Some(hints));
+
+                return request::InlayHintRequest::METHOD;
+            }
+            Err(req) => req,
+        };
+
+        let req = match Self::cast_request::<request::PrepareRenameRequest>(req) {
+            Ok(params) => {
+                let range = self
+                    .project
+                    .prepare_rename(&params.text_document.uri, params.position);
+
+                trace!("PrepareRenameRequest Response {range:?}");
+                self.send_response::<request::PrepareRenameRequest>(
+                    req_id,
+                    range.map(PrepareRenameResponse::Range),
+                );
+
+                return request::PrepareRenameRequest::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::Rename>(req) {
+            Ok(params) => {
+                let position = params.text_document_position;
+                match self.project.rename(
+                    &position.text_document.uri,
+                    position.position,
+                    &params.new_name,
+                ) {
+                    Ok(edit) => {
+                        trace!("Rename Response {edit:?}");
+                        self.send_response::<request::Rename>(req_id, Some(edit));
+                    }
+                    Err(message) => {
+                        trace!("Rename Refused {message}");
+                        self.send_error(req_id, message);
+                    }
+                }
+
+                return request::Rename::METHOD;
+            }
+            Err(req) => req,
+        };
+
+        let req = match Self::cast_request::<request::CodeActionRequest>(req) {
+            Ok(params) => {
+                let actions = lsp_code_action::requested(&params);
+
+                trace!("CodeActionRequest Response {} actions", actions.len());
+                self.send_response::<request::CodeActionRequest>(req_id, Some(actions));
+
+                return request::CodeActionRequest::METHOD;
+            }
+            Err(req) => req,
+        };
+        let req = match Self::cast_request::<request::ExecuteCommand>(req) {
+            Ok(params) => {
+                let option_key = params.arguments.first().and_then(|arg| arg.as_str());
+                let uri = params
+                    .arguments
+                    .get(1)
+                    .and_then(|arg| arg.as_str())
+                    .and_then(|uri| Uri::from_str(uri).ok());
+                match (params.command.as_str(), option_key, uri) {
+                    (ENABLE_OPTION_COMMAND, Some(option_key), Some(uri))
+                        if self.project.enable_option(option_key) =>
+                    {
+                        self.send_response::<request::ExecuteCommand>(req_id, None);
+                        self.publish_workspace_diagnostics(&[(uri, None)]);
+                    }
+                    _ => {
+                        self.send_error(req_id, format!("Unknown command {}", params.command));
+                    }
+                }
+
+                return request::ExecuteCommand::METHOD;
+            }
+            Err(req) => req,
+        };
+
+        let req = match Self::cast_request::<request::Formatting>(req) {
+            Ok(params) => {
+                let edits = self
+                    .project
+                    .format(&params.text_document.uri, &params.options, None);
+
+                trace!("Formatting Response {} edits", edits.len());
+                self.send_response::<request::Formatting>(req_id, Some(edits));
+
+                return request::Formatting::METHOD;
+            }
+            Err(req) => req,
+        };
+        let _req = match Self::cast_request::<request::RangeFormatting>(req) {
+            Ok(params) => {
+                let edits = self.project.format(
+                    &params.text_document.uri,
+                    &params.options,
+                    Some(params.range),
+                );
+
+                trace!("RangeFormatting Response {} edits", edits.len());
+                self.send_response::<request::RangeFormatting>(req_id, Some(edits));
+
+                return request::RangeFormatting::METHOD;
+            }
+            Err(req) => req,
+        };
 
         // Handle custom requests by method name
         if _req.method == "ironplc/disassemble" {
@@ -370,8 +788,11 @@ impl<'a> LspServer<'a> {
             let params: serde_json::Value = serde_json::from_value(_req.params).unwrap_or_default();
             let source = params["source"].as_str().unwrap_or("");
             let cycle_time_us = params["cycleTimeUs"].as_u64().unwrap_or(100_000);
+            let monitored = params["uri"]
+                .as_str()
+                .and_then(|uri| lsp_types::Uri::from_str(uri).ok());
 
-            let result = self.project.run_load(source, cycle_time_us);
+            let result = self.project.run_load(source, cycle_time_us, monitored);
             let response = lsp_server::Response::new_ok(req_id, result);
             self.sender
                 .send(lsp_server::Message::Response(response))
@@ -384,10 +805,14 @@ impl<'a> LspServer<'a> {
             let scans = params["scans"].as_u64().unwrap_or(1) as u32;
 
             let result = self.project.run_step(scans);
+            let monitor = self.project.monitor(&result);
             let response = lsp_server::Response::new_ok(req_id, result);
             self.sender
                 .send(lsp_server::Message::Response(response))
                 .unwrap();
+            if let Some(params) = monitor {
+                self.send_notification::<Monitor>(params);
+            }
             return "ironplc/step";
         }
 
@@ -427,6 +852,17 @@ impl<'a> LspServer<'a> {
         self.sender.send(Message::Response(response)).unwrap()
     }
 
+    /// Answers the request with an error the editor shows to the user.
+    fn send_error(&self, request_id: RequestId, message: String) {
+        self.send_error_code(request_id, ErrorCode::RequestFailed, message);
+    }
+
+    fn send_error_code(&self, request_id: RequestId, code: ErrorCode, message: String) {
+        trace!("Error response {message}");
+        let response = lsp_server::Response::new_err(request_id, code as i32, message);
+        self.sender.send(Message::Response(response)).unwrap()
+    }
+
     fn handle_notification(&mut self, notification: lsp_server::Notification) -> &'static str {
         let notification = match Self::cast_notification::<notification::Exit>(notification) {
             Ok(_params) => {
@@ -445,7 +881,7 @@ impl<'a> LspServer<'a> {
 
                     self.project
                         .change_text_document(&uri, contents.as_str().to_string());
-                    self.publish_workspace_diagnostics(&uri, Some(version));
+                    self.edited(uri, version);
 
                     return notification::DidOpenTextDocument::METHOD;
                 }
@@ -459,13 +895,11 @@ impl<'a> LspServer<'a> {
                         "DidChangeTextDocument {}",
                         params.text_document.uri.as_str()
                     );
-                    let contents = params.content_changes.into_iter().next().unwrap().text;
                     let uri = params.text_document.uri;
                     let version = params.text_document.version;
 
-                    self.project
-                        .change_text_document(&uri, contents.as_str().to_string());
-                    self.publish_workspace_diagnostics(&uri, Some(version));
+                    self.project.apply_changes(&uri, params.content_changes);
+                    self.edited(uri, version);
 
                     return notification::DidChangeTextDocument::METHOD;
                 }
@@ -500,6 +934,49 @@ impl<'a> LspServer<'a> {
     }
 }
 
+fn is_shutdown(msg: &Message) -> bool {
+    matches!(msg, Message::Request(req) if req.method == request::Shutdown::METHOD)
+}
+
+/// The requests among queued messages that are answered with an error
+/// instead of a result: those the client cancelled, and those about a
+/// document a later message edits, since the client no longer wants an
+/// answer computed from the text the request was sent for.
+fn refused_requests(queued: &[Message]) -> HashMap<RequestId, ErrorCode> {
+    let mut refused = HashMap::new();
+    for (index, msg) in queued.iter().enumerate() {
+        match msg {
+            Message::Request(req) => {
+                let Some(uri) = req.params["textDocument"]["uri"].as_str() else {
+                    continue;
+                };
+                let edited_later = queued[index + 1..].iter().any(|msg| {
+                    matches!(msg, Message::Notification(notification)
+                        if notification.method == notification::DidChangeTextDocument::METHOD
+                            && notification.params["textDocument"]["uri"].as_str() == Some(uri))
+                });
+                if edited_later {
+                    refused.insert(req.id.clone(), ErrorCode::ContentModified);
+                }
+            }
+            Message::Notification(notification) if notification.method == Cancel::METHOD => {
+                let Ok(params) =
+                    serde_json::from_value::<CancelParams>(notification.params.clone())
+                else {
+                    continue;
+                };
+                let id = match params.id {
+                    NumberOrString::Number(id) => RequestId::from(id),
+                    NumberOrString::String(id) => RequestId::from(id),
+                };
+                refused.insert(id, ErrorCode::RequestCanceled);
+            }
+            _ => {}
+        }
+    }
+    refused
+}
+
 /// Converts a `file:` URI to a filesystem path string.
 ///
 /// On Windows, file URIs have the form `file:///C:/path` where the URI path
@@ -518,25 +995,36 @@ fn uri_to_file_path(uri: &lsp_types::Uri) -> String {
 #[cfg(test)]
 mod test {
     use core::time::Duration;
-    use lsp_server::{Connection, Message, RequestId};
+    use lsp_server::{Connection, ErrorCode, Message, RequestId};
     use lsp_server::{Notification, Response};
     use lsp_types::DidChangeTextDocumentParams;
     use lsp_types::Uri;
     use lsp_types::VersionedTextDocumentIdentifier;
     use lsp_types::{notification, WorkDoneProgressParams};
     use lsp_types::{
-        request, ClientCapabilities, InitializeParams, InitializeResult, InitializedParams,
-        PublishDiagnosticsParams, TextDocumentContentChangeEvent,
+        request, CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+        CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams,
+        CancelParams, ClientCapabilities, CodeActionContext, CodeActionOrCommand, CodeActionParams,
+        CompletionParams, CompletionResponse, DocumentFormattingParams,
+        DocumentRangeFormattingParams, ExecuteCommandParams, FormattingOptions,
+        GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+        InitializeParams, InitializeResult, InitializedParams, InlayHint, InlayHintLabel,
+        InlayHintParams, Location, NumberOrString, PartialResultParams, Position,
+        PublishDiagnosticsParams, Range, ReferenceContext, ReferenceParams, RenameParams,
+        SignatureHelp, SignatureHelpParams, TextDocumentContentChangeEvent, TextDocumentIdentifier,
+        TextDocumentPositionParams, TextEdit, WorkspaceEdit, WorkspaceSymbolParams,
+        WorkspaceSymbolResponse,
     };
     use serde::de::DeserializeOwned;
     use serde::Serialize;
     use std::collections::HashMap;
     use std::str::FromStr;
 
+    use crate::lsp_monitor::MonitorParams;
     use crate::lsp_project::{LspProject, UriKey};
     use ironplc_project::{FileBackedProject, Project};
 
-    use super::start_with_connection;
+    use super::{refused_requests, start_with_connection};
 
     struct TestServer {
         server_thread: Option<std::thread::JoinHandle<()>>,
@@ -803,6 +1291,42 @@ mod test {
         }
     }
 
+    #[test]
+    fn extract_compiler_options_when_each_check_key_set_then_check_enabled() {
+        for cd in ironplc_parser::options::CompilerOptions::CHECK_DESCRIPTORS {
+            let key = super::to_lower_camel_case(cd.option_key);
+            let params = params_with_init_options(serde_json::json!({ key.clone(): true }));
+            let options = super::extract_compiler_options(&params);
+            assert_eq!(
+                options.get_flag_by_key(cd.option_key),
+                Some(true),
+                "LSP key `{key}` did not enable CompilerOptions.{}",
+                cd.option_key
+            );
+        }
+    }
+
+    #[test]
+    fn extract_demotions_when_demote_listed_then_demotable_codes_only() {
+        let params = params_with_init_options(serde_json::json!({
+            "demote": ["P4041", "p4028", "P4035"]
+        }));
+
+        let demotions = super::extract_demotions(&params);
+
+        assert_eq!(
+            demotions.codes().collect::<Vec<_>>(),
+            vec!["P4028", "P4041"]
+        );
+    }
+
+    #[test]
+    fn extract_demotions_when_no_options_then_empty() {
+        let params = params_with_init_options(serde_json::json!({}));
+
+        assert!(super::extract_demotions(&params).is_empty());
+    }
+
     #[test]
     fn to_lower_camel_case_when_multi_segment_then_camel_cased() {
         assert_eq!(super::to_lower_camel_case("allow_ref_to"), "allowRefTo");
@@ -1282,6 +1806,45 @@ mod test {
         assert_eq!(vars[0]["value"], "42");
     }
 
+    #[test]
+    fn step_request_when_document_monitored_then_sends_monitor_notification() {
+        let proj = Box::new(FileBackedProject::default());
+        let mut server = TestServer::new(proj);
+        let source = "PROGRAM main\nVAR\nx : DINT;\nEND_VAR\nx := 42;\nEND_PROGRAM";
+        let uri = Uri::from_str("file://example.net/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(
+            DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier {
+                    uri: uri.clone(),
+                    version: 1,
+                },
+                content_changes: vec![TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: String::from(source),
+                }],
+            },
+        );
+        let _ = server.receive_notification::<PublishDiagnosticsParams>();
+
+        let params = serde_json::json!({
+            "source": source,
+            "cycleTimeUs": 100000,
+            "uri": uri.as_str(),
+        });
+        let req_id = server.send_raw_request("ironplc/run", params);
+        let _: serde_json::Value = server.receive_response(req_id);
+        let step_id = server.send_raw_request("ironplc/step", serde_json::json!({"scans": 1}));
+        let _: serde_json::Value = server.receive_response(step_id);
+        let monitor = server.receive_notification::<MonitorParams>();
+
+        assert_eq!(monitor.uri, uri);
+        assert_eq!(monitor.total_scans, 1);
+        assert_eq!(monitor.values[0].name, "x");
+        assert_eq!(monitor.values[0].value, "42");
+        assert_eq!(monitor.values[0].ranges.len(), 2);
+    }
+
     #[test]
     fn step_request_when_no_program_then_returns_error() {
         let proj = Box::new(FileBackedProject::default());
@@ -1539,4 +2102,648 @@ mod test {
         assert!(!n[&broken_key].diagnostics.is_empty());
         assert!(n[&clean_key].diagnostics.is_empty());
     }
+
+    #[test]
+    fn text_document_changed_when_range_edit_then_applies_to_text() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        let key = UriKey::from_uri(&uri);
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            "PROGRAM Main\nVAR\n  x : BOOL;\nEND_VAR\n  x := TRUE;\nEND_PROGRAM",
+        ));
+        let n = server.receive_publishes(1);
+        assert!(n[&key].diagnostics.is_empty());
+
+        // Replace `x :=` in the body with `y :=`, which is not declared.
+        server.send_notification::<notification::DidChangeTextDocument>(
+            DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier {
+                    uri: uri.clone(),
+                    version: 2,
+                },
+                content_changes: vec![TextDocumentContentChangeEvent {
+                    range: Some(Range::new(Position::new(4, 2), Position::new(4, 3))),
+                    range_length: None,
+                    text: "y".to_string(),
+                }],
+            },
+        );
+        let n = server.receive_publishes(1);
+
+        assert!(!n[&key].diagnostics.is_empty());
+        assert_eq!(n[&key].version, Some(2));
+    }
+
+    #[test]
+    fn text_document_changed_when_edits_in_quick_succession_then_one_publish_for_last() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        let key = UriKey::from_uri(&uri);
+
+        // Both edits arrive well within the debounce, so they are analyzed
+        // together.
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            "this is not valid",
+        ));
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            2,
+            "PROGRAM Main\nVAR\n  x : BOOL;\nEND_VAR\nEND_PROGRAM",
+        ));
+        let n = server.receive_publishes(1);
+        assert_eq!(n[&key].version, Some(2));
+        assert!(n[&key].diagnostics.is_empty());
+
+        // Nothing else was published: the next message is the response.
+        let id = server.send_request::<request::Formatting>(DocumentFormattingParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            options: formatting_options(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        server.receive_response::<Option<Vec<TextEdit>>>(id);
+    }
+
+    #[test]
+    fn hover_when_edit_pending_then_publishes_before_response() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            NAVIGATION_SOURCE,
+        ));
+
+        let id = server.send_request::<request::HoverRequest>(HoverParams {
+            text_document_position_params: text_document_position(&uri, 4, 14),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        server.receive_publishes(1);
+        let hover = server.receive_response::<Option<Hover>>(id);
+
+        assert!(hover.is_some());
+    }
+
+    fn queued_request(id: i32, uri: &Uri) -> Message {
+        Message::Request(lsp_server::Request::new(
+            RequestId::from(id),
+            <request::HoverRequest as request::Request>::METHOD.to_string(),
+            HoverParams {
+                text_document_position_params: text_document_position(uri, 0, 0),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            },
+        ))
+    }
+
+    fn queued_notification<N>(params: N::Params) -> Message
+    where
+        N: lsp_types::notification::Notification,
+        N::Params: Serialize,
+    {
+        Message::Notification(Notification::new(N::METHOD.to_string(), params))
+    }
+
+    #[test]
+    fn refused_requests_when_cancelled_then_request_canceled() {
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        let queued = vec![
+            queued_request(1, &uri),
+            queued_request(2, &uri),
+            queued_notification::<notification::Cancel>(CancelParams {
+                id: NumberOrString::Number(1),
+            }),
+        ];
+
+        let refused = refused_requests(&queued);
+
+        assert_eq!(refused.len(), 1);
+        assert!(matches!(
+            refused.get(&RequestId::from(1)),
+            Some(ErrorCode::RequestCanceled)
+        ));
+    }
+
+    #[test]
+    fn refused_requests_when_document_edited_after_request_then_content_modified() {
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        let other = Uri::from_str("file:///workspace/other.st").unwrap();
+        let queued = vec![
+            queued_request(1, &uri),
+            queued_request(2, &other),
+            queued_notification::<notification::DidChangeTextDocument>(change_doc(&uri, 2, "")),
+            queued_request(3, &uri),
+        ];
+
+        let refused = refused_requests(&queued);
+
+        assert_eq!(refused.len(), 1);
+        assert!(matches!(
+            refused.get(&RequestId::from(1)),
+            Some(ErrorCode::ContentModified)
+        ));
+    }
+
+    const NAVIGATION_SOURCE: &str = "PROGRAM Main
+VAR
+    Count : INT;
+END_VAR
+    Count := Count + 1;
+END_PROGRAM";
+
+    fn text_document_position(uri: &Uri, line: u32, character: u32) -> TextDocumentPositionParams {
+        TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            position: Position::new(line, character),
+        }
+    }
+
+    #[test]
+    fn goto_definition_when_variable_use_then_returns_declaration() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            NAVIGATION_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: text_document_position(&uri, 4, 14),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        });
+        let response = server.receive_response::<Option<GotoDefinitionResponse>>(id);
+
+        let location = match response {
+            Some(GotoDefinitionResponse::Scalar(location)) => location,
+            other => panic!("expected a single location, got {other:?}"),
+        };
+        assert_eq!(location.uri, uri);
+        assert_eq!(location.range.start, Position::new(2, 4));
+    }
+
+    #[test]
+    fn references_when_variable_then_returns_declaration_and_uses() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            NAVIGATION_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::References>(ReferenceParams {
+            text_document_position: text_document_position(&uri, 2, 4),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+            context: ReferenceContext {
+                include_declaration: true,
+            },
+        });
+        let locations = server
+            .receive_response::<Option<Vec<Location>>>(id)
+            .unwrap();
+
+        assert_eq!(locations.len(), 3);
+    }
+
+    #[test]
+    fn workspace_symbol_when_query_then_returns_matching_pou() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            NAVIGATION_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::WorkspaceSymbolRequest>(WorkspaceSymbolParams {
+            query: "mn".to_string(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        });
+        let response = server.receive_response::<Option<WorkspaceSymbolResponse>>(id);
+
+        // A workspace symbol with a full location reads back as the flat form.
+        let names: Vec<String> = match response {
+            Some(WorkspaceSymbolResponse::Nested(symbols)) => {
+                symbols.into_iter().map(|symbol| symbol.name).collect()
+            }
+            Some(WorkspaceSymbolResponse::Flat(symbols)) => {
+                symbols.into_iter().map(|symbol| symbol.name).collect()
+            }
+            None => panic!("expected workspace symbols"),
+        };
+        assert_eq!(names, vec!["Main"]);
+    }
+
+    #[test]
+    fn call_hierarchy_when_program_calls_function_then_outgoing_and_incoming() {
+        let source = "FUNCTION Twice : INT
+VAR_INPUT
+    N : INT;
+END_VAR
+    Twice := N * 2;
+END_FUNCTION
+PROGRAM Main
+VAR
+    Count : INT;
+END_VAR
+    Count := Twice(N := Count);
+END_PROGRAM";
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server
+            .send_notification::<notification::DidChangeTextDocument>(change_doc(&uri, 1, source));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::CallHierarchyPrepare>(CallHierarchyPrepareParams {
+            text_document_position_params: text_document_position(&uri, 6, 8),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        let items = server
+            .receive_response::<Option<Vec<CallHierarchyItem>>>(id)
+            .unwrap();
+        assert_eq!(items[0].name, "Main");
+
+        let id = server.send_request::<request::CallHierarchyOutgoingCalls>(
+            CallHierarchyOutgoingCallsParams {
+                item: items[0].clone(),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: PartialResultParams::default(),
+            },
+        );
+        let outgoing = server
+            .receive_response::<Option<Vec<CallHierarchyOutgoingCall>>>(id)
+            .unwrap();
+        assert_eq!(outgoing.len(), 1);
+        assert_eq!(outgoing[0].to.name, "Twice");
+        assert_eq!(outgoing[0].from_ranges[0].start, Position::new(10, 13));
+
+        let id = server.send_request::<request::CallHierarchyIncomingCalls>(
+            CallHierarchyIncomingCallsParams {
+                item: outgoing[0].to.clone(),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: PartialResultParams::default(),
+            },
+        );
+        let incoming = server
+            .receive_response::<Option<Vec<CallHierarchyIncomingCall>>>(id)
+            .unwrap();
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(incoming[0].from.name, "Main");
+    }
+
+    #[test]
+    fn hover_when_variable_use_then_returns_declaration() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            NAVIGATION_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::HoverRequest>(HoverParams {
+            text_document_position_params: text_document_position(&uri, 4, 14),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        let hover = server.receive_response::<Option<Hover>>(id).unwrap();
+
+        match hover.contents {
+            HoverContents::Markup(markup) => assert!(markup.value.contains("VAR Count : INT")),
+            other => panic!("expected markdown, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn completion_when_in_body_then_returns_variables() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            NAVIGATION_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::Completion>(CompletionParams {
+            text_document_position: text_document_position(&uri, 4, 4),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+            context: None,
+        });
+        let response = server.receive_response::<Option<CompletionResponse>>(id);
+
+        let items = match response {
+            Some(CompletionResponse::Array(items)) => items,
+            other => panic!("expected completion items, got {other:?}"),
+        };
+        assert!(items.iter().any(|item| item.label == "Count"));
+    }
+
+    const CALL_SOURCE: &str = "FUNCTION Scale : INT
+VAR_INPUT
+    Value : INT;
+    Factor : INT;
+END_VAR
+    Scale := Value * Factor;
+END_FUNCTION
+PROGRAM Main
+VAR
+    Count : INT;
+END_VAR
+    Count := Scale(Count, 2);
+END_PROGRAM";
+
+    #[test]
+    fn signature_help_when_second_argument_then_second_input_active() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            CALL_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::SignatureHelpRequest>(SignatureHelpParams {
+            context: None,
+            text_document_position_params: text_document_position(&uri, 11, 26),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        let help = server
+            .receive_response::<Option<SignatureHelp>>(id)
+            .unwrap();
+
+        assert_eq!(
+            help.signatures[0].label,
+            "Scale(Value : INT, Factor : INT) : INT"
+        );
+        assert_eq!(help.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn inlay_hint_when_positional_call_then_parameter_names_and_literal_type() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            CALL_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::InlayHintRequest>(InlayHintParams {
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            range: Range::new(Position::new(0, 0), Position::new(13, 0)),
+        });
+        let hints = server
+            .receive_response::<Option<Vec<InlayHint>>>(id)
+            .unwrap();
+
+        let labels: Vec<(u32, u32, String)> = hints
+            .into_iter()
+            .map(|hint| match hint.label {
+                InlayHintLabel::String(label) => {
+                    (hint.position.line, hint.position.character, label)
+                }
+                other => panic!("expected a plain label, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                (11, 19, "Value :=".to_string()),
+                (11, 26, "Factor :=".to_string()),
+                (11, 26, "INT#".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_when_variable_then_edits_declaration_and_uses() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            NAVIGATION_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::Rename>(RenameParams {
+            text_document_position: text_document_position(&uri, 4, 4),
+            new_name: "Ticks".to_string(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        let edit = server
+            .receive_response::<Option<WorkspaceEdit>>(id)
+            .unwrap();
+
+        let Some(lsp_types::DocumentChanges::Edits(documents)) = edit.document_changes else {
+            panic!("expected document edits");
+        };
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].edits.len(), 3);
+    }
+
+    #[test]
+    fn rename_when_keyword_then_error_response() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            NAVIGATION_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::Rename>(RenameParams {
+            text_document_position: text_document_position(&uri, 4, 4),
+            new_name: "WHILE".to_string(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        server.receive();
+
+        let response = server.responses.get(&id).unwrap();
+        assert!(response.response_result.is_err());
+    }
+
+    const UNFORMATTED_SOURCE: &str = "PROGRAM Main
+VAR
+Count : INT;
+END_VAR
+Count := Count + 1;
+END_PROGRAM
+";
+
+    fn formatting_options() -> FormattingOptions {
+        FormattingOptions {
+            tab_size: 2,
+            insert_spaces: true,
+            ..FormattingOptions::default()
+        }
+    }
+
+    #[test]
+    fn formatting_when_unformatted_then_replaces_document() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            UNFORMATTED_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::Formatting>(DocumentFormattingParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            options: formatting_options(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        let edits = server
+            .receive_response::<Option<Vec<TextEdit>>>(id)
+            .unwrap();
+
+        assert_eq!(edits.len(), 1);
+        assert_eq!(
+            edits[0].new_text,
+            "PROGRAM Main\nVAR\n  Count : INT;\nEND_VAR\n  Count := Count + 1;\nEND_PROGRAM\n"
+        );
+    }
+
+    #[test]
+    fn range_formatting_when_one_line_then_replaces_only_that_line() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            UNFORMATTED_SOURCE,
+        ));
+        server.receive_publishes(1);
+
+        let id = server.send_request::<request::RangeFormatting>(DocumentRangeFormattingParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            range: Range::new(Position::new(4, 0), Position::new(4, 5)),
+            options: formatting_options(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        let edits = server
+            .receive_response::<Option<Vec<TextEdit>>>(id)
+            .unwrap();
+
+        assert_eq!(edits.len(), 1);
+        assert_eq!(
+            edits[0].range,
+            Range::new(Position::new(4, 0), Position::new(5, 0))
+        );
+        assert_eq!(edits[0].new_text, "  Count := Count + 1;\n");
+    }
+
+    fn code_action_params(uri: &Uri, diagnostics: Vec<lsp_types::Diagnostic>) -> CodeActionParams {
+        CodeActionParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            range: diagnostics[0].range,
+            context: CodeActionContext {
+                diagnostics,
+                only: None,
+                trigger_kind: None,
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        }
+    }
+
+    #[test]
+    fn code_action_when_misspelled_variable_then_offers_suggestion_and_declaration() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            "PROGRAM Main
+VAR
+    Count : INT;
+END_VAR
+    Count := Cont + 1;
+END_PROGRAM",
+        ));
+        let published = server.receive_publishes(1);
+        let diagnostics = published[&UriKey::from_uri(&uri)].diagnostics.clone();
+
+        let id = server
+            .send_request::<request::CodeActionRequest>(code_action_params(&uri, diagnostics));
+        let actions = server
+            .receive_response::<Option<Vec<CodeActionOrCommand>>>(id)
+            .unwrap();
+
+        let titles: Vec<String> = actions
+            .iter()
+            .map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => action.title.clone(),
+                CodeActionOrCommand::Command(command) => command.title.clone(),
+            })
+            .collect();
+        assert_eq!(titles, vec!["Change to 'Count'", "Declare 'Cont' in VAR"]);
+        let CodeActionOrCommand::CodeAction(change) = &actions[0] else {
+            panic!("expected a code action");
+        };
+        let edits = &change.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(edits[0].new_text, "Count");
+        assert_eq!(
+            edits[0].range,
+            Range::new(Position::new(4, 13), Position::new(4, 17))
+        );
+    }
+
+    #[test]
+    fn execute_command_when_enable_option_then_diagnostic_cleared() {
+        let mut server = TestServer::new(Box::new(FileBackedProject::default()));
+        let uri = Uri::from_str("file:///workspace/main.st").unwrap();
+        server.send_notification::<notification::DidChangeTextDocument>(change_doc(
+            &uri,
+            1,
+            "VAR_GLOBAL
+    Count : INT;
+END_VAR
+PROGRAM Main
+END_PROGRAM",
+        ));
+        let published = server.receive_publishes(1);
+        let diagnostics = published[&UriKey::from_uri(&uri)].diagnostics.clone();
+        assert_eq!(
+            diagnostics[0].code,
+            Some(lsp_types::NumberOrString::String("P4028".to_string()))
+        );
+
+        let id = server
+            .send_request::<request::CodeActionRequest>(code_action_params(&uri, diagnostics));
+        let actions = server
+            .receive_response::<Option<Vec<CodeActionOrCommand>>>(id)
+            .unwrap();
+        let CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+            panic!("expected a code action");
+        };
+        let command = action.command.clone().unwrap();
+
+        let id = server.send_request::<request::ExecuteCommand>(ExecuteCommandParams {
+            command: command.command,
+            arguments: command.arguments.unwrap(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        server.receive_response::<Option<serde_json::Value>>(id);
+        let published = server.receive_publishes(1);
+
+        assert!(published[&UriKey::from_uri(&uri)].diagnostics.is_empty());
+    }
 }
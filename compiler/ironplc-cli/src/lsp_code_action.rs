@@ -0,0 +1,320 @@
+//! Turns the fixes the compiler attaches to diagnostics into code actions.
+//!
+//! The actions are built when the diagnostics are published, while the text
+//! they were computed from is at hand, and travel in the `data` of the
+//! diagnostic. A code action request then returns the actions of the
+//! diagnostics the editor sends back, so the edits always match the text the
+//! editor showed the diagnostic for.
+
+use std::collections::HashMap;
+
+use ironplc_dsl::core::{FileId, SourceSpan};
+use ironplc_dsl::diagnostic::{Fix, FixKind};
+use ironplc_parser::token::{Token, TokenType};
+use ironplc_project::Project;
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, Command, Range, TextEdit,
+    Uri, WorkspaceEdit,
+};
+
+use crate::lsp_project::{lsp_position, UriKey};
+
+/// The command that enables a compiler option for the rest of the session.
+/// The arguments are the option key and the URI of the document the
+/// diagnostic is in.
+pub(crate) const ENABLE_OPTION_COMMAND: &str = "ironplc.enableOption";
+
+/// The type a variable declared by a quick fix gets when its use does not
+/// tell; it names no type, so the declaration stays in error until the user
+/// writes one.
+const TYPE_PLACEHOLDER: &str = "TYPE_NAME";
+
+/// Builds the code actions for the fixes of a diagnostic in the file. A fix
+/// whose location is not in a project source is left out.
+pub(crate) fn actions(fixes: &[Fix], file_id: &FileId, project: &dyn Project) -> Vec<CodeAction> {
+    fixes
+        .iter()
+        .filter_map(|fix| action(fix, file_id, project))
+        .collect()
+}
+
+/// The actions carried by the diagnostics of a code action request, each
+/// linked to the diagnostic it fixes.
+pub(crate) fn requested(params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+    if params
+        .context
+        .only
+        .as_ref()
+        .is_some_and(|only| !only.contains(&CodeActionKind::QUICKFIX))
+    {
+        return vec![];
+    }
+    params
+        .context
+        .diagnostics
+        .iter()
+        .flat_map(|diagnostic| {
+            let actions: Vec<CodeAction> = diagnostic
+                .data
+                .clone()
+                .and_then(|data| serde_json::from_value(data).ok())
+                .unwrap_or_default();
+            let mut fixed = diagnostic.clone();
+            fixed.data = None;
+            actions.into_iter().map(move |action| {
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    diagnostics: Some(vec![fixed.clone()]),
+                    ..action
+                })
+            })
+        })
+        .collect()
+}
+
+fn action(fix: &Fix, file_id: &FileId, project: &dyn Project) -> Option<CodeAction> {
+    let quick_fix = |edit: Option<WorkspaceEdit>, command: Option<Command>| CodeAction {
+        title: fix.title.clone(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: None,
+        edit,
+        command,
+        is_preferred: Some(matches!(fix.kind, FixKind::Replace { .. })),
+        disabled: None,
+        data: None,
+    };
+
+    match &fix.kind {
+        FixKind::Replace { span, text } => {
+            let source = project.find(&span.file_id)?.as_string();
+            let edit = TextEdit::new(range(source, span.start, span.end), text.clone());
+            Some(quick_fix(Some(workspace_edit(&span.file_id, edit)?), None))
+        }
+        FixKind::InsertLineBefore { span, text } => {
+            let source = project.find(&span.file_id)?.as_string();
+            let start = line_start(source, span.start);
+            let indent = indentation(&source[start..]);
+            let edit = TextEdit::new(range(source, start, start), format!("{indent}{text}\n"));
+            Some(quick_fix(Some(workspace_edit(&span.file_id, edit)?), None))
+        }
+        FixKind::DeclareVariable {
+            pou,
+            name,
+            type_name,
+        } => {
+            let source = project.find(&pou.file_id)?.as_string();
+            let (tokens, _) = project.tokenize(&pou.file_id);
+            let type_name = type_name.as_deref().unwrap_or(TYPE_PLACEHOLDER);
+            let (at, text) = declaration(source, &tokens, pou, name, type_name)?;
+            let edit = TextEdit::new(range(source, at, at), text);
+            Some(quick_fix(Some(workspace_edit(&pou.file_id, edit)?), None))
+        }
+        FixKind::EnableOption { option_key } => {
+            let uri = UriKey::from_file_id(file_id)?.to_uri();
+            let command = Command {
+                title: fix.title.clone(),
+                command: ENABLE_OPTION_COMMAND.to_string(),
+                arguments: Some(vec![
+                    serde_json::Value::String(option_key.clone()),
+                    serde_json::Value::String(uri.as_str().to_string()),
+                ]),
+            };
+            Some(quick_fix(None, Some(command)))
+        }
+    }
+}
+
+fn workspace_edit(file_id: &FileId, edit: TextEdit) -> Option<WorkspaceEdit> {
+    let uri: Uri = UriKey::from_file_id(file_id)?.to_uri();
+    Some(WorkspaceEdit {
+        changes: Some(HashMap::from([(uri, vec![edit])])),
+        ..WorkspaceEdit::default()
+    })
+}
+
+/// Where to declare a variable of the type in the POU whose name is at
+/// `pou`, and the text to insert: a line after the line that opens the first
+/// plain `VAR` section, or a new `VAR` section after the POU's other sections.
+fn declaration(
+    source: &str,
+    tokens: &[Token],
+    pou: &SourceSpan,
+    name: &str,
+    type_name: &str,
+) -> Option<(usize, String)> {
+    let start = tokens.iter().position(|tok| tok.span.start == pou.start)?;
+    let header_line = tokens[start].line;
+    let pou_indent = indentation(&source[line_start(source, pou.start)..]).to_string();
+    let declaration = format!("{name} : {type_name};");
+
+    let mut after_sections = line_end(source, tokens[start].span.end);
+    let mut index = start + 1;
+    while let Some(tok) = tokens.get(index) {
+        if is_trivia(&tok.token_type) || tok.line == header_line {
+            index += 1;
+            continue;
+        }
+        if !is_section(&tok.token_type) {
+            break;
+        }
+        let opens_line =
+            next_significant(tokens, index + 1).is_none_or(|next| tokens[next].line != tok.line);
+        if tok.token_type == TokenType::Var && opens_line {
+            let at = line_end(source, tok.span.end);
+            let indent = next_significant(tokens, index + 1)
+                .filter(|next| tokens[*next].token_type != TokenType::EndVar)
+                .map(|next| indentation(&source[line_start(source, tokens[next].span.start)..]))
+                .map_or_else(|| format!("{pou_indent}    "), str::to_string);
+            return Some((at, format!("{indent}{declaration}\n")));
+        }
+        let end = tokens[index..]
+            .iter()
+            .position(|tok| tok.token_type == TokenType::EndVar)?;
+        index += end;
+        after_sections = line_end(source, tokens[index].span.end);
+        index += 1;
+    }
+
+    Some((
+        after_sections,
+        format!("{pou_indent}VAR\n{pou_indent}    {declaration}\n{pou_indent}END_VAR\n"),
+    ))
+}
+
+fn next_significant(tokens: &[Token], from: usize) -> Option<usize> {
+    (from..tokens.len()).find(|index| !is_trivia(&tokens[*index].token_type))
+}
+
+fn is_trivia(token_type: &TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Whitespace | TokenType::Newline | TokenType::Comment | TokenType::Pragma
+    )
+}
+
+fn is_section(token_type: &TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Var
+            | TokenType::VarInput
+            | TokenType::VarOutput
+            | TokenType::VarInOut
+            | TokenType::VarTemp
+            | TokenType::VarExternal
+            | TokenType::VarGlobal
+    )
+}
+
+/// The offset of the start of the line that contains `offset`.
+fn line_start(source: &str, offset: usize) -> usize {
+    source[..offset]
+        .rfind('\n')
+        .map_or(0, |newline| newline + 1)
+}
+
+/// The offset just past the line break of the line that contains `offset`.
+fn line_end(source: &str, offset: usize) -> usize {
+    source[offset..]
+        .find('\n')
+        .map_or(source.len(), |newline| offset + newline + 1)
+}
+
+/// The whitespace a line starts with.
+fn indentation(line: &str) -> &str {
+    let end = line
+        .find(|c: char| c != ' ' && c != '\t')
+        .unwrap_or(line.len());
+    &line[..end]
+}
+
+fn range(source: &str, start: usize, end: usize) -> Range {
+    Range::new(lsp_position(source, start), lsp_position(source, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironplc_parser::options::CompilerOptions;
+
+    fn declare(source: &str, pou: &str, name: &str, type_name: &str) -> String {
+        let file_id = FileId::default();
+        let (tokens, _) =
+            ironplc_parser::tokenize_program(source, &file_id, &CompilerOptions::default(), 0, 0);
+        let start = source.find(pou).unwrap();
+        let span = SourceSpan::range(start, start + pou.len());
+        let (at, text) = declaration(source, &tokens, &span, name, type_name).unwrap();
+        format!("{}{}{}", &source[..at], text, &source[at..])
+    }
+
+    #[test]
+    fn declaration_when_var_section_then_adds_line_to_it() {
+        let source = "PROGRAM Main
+VAR_INPUT
+    Go : BOOL;
+END_VAR
+VAR
+  Count : INT;
+END_VAR
+    Total := Count;
+END_PROGRAM
+";
+
+        assert_eq!(
+            declare(source, "Main", "Total", "INT"),
+            "PROGRAM Main
+VAR_INPUT
+    Go : BOOL;
+END_VAR
+VAR
+  Total : INT;
+  Count : INT;
+END_VAR
+    Total := Count;
+END_PROGRAM
+"
+        );
+    }
+
+    #[test]
+    fn declaration_when_only_other_sections_then_adds_var_section_after_them() {
+        let source = "FUNCTION_BLOCK Counter
+VAR CONSTANT
+    Limit : INT := 10;
+END_VAR
+    Total := Limit;
+END_FUNCTION_BLOCK
+";
+
+        assert_eq!(
+            declare(source, "Counter", "Total", "INT"),
+            "FUNCTION_BLOCK Counter
+VAR CONSTANT
+    Limit : INT := 10;
+END_VAR
+VAR
+    Total : INT;
+END_VAR
+    Total := Limit;
+END_FUNCTION_BLOCK
+"
+        );
+    }
+
+    #[test]
+    fn declaration_when_no_sections_then_adds_var_section_after_header() {
+        let source = "FUNCTION Twice : INT
+    Twice := Total * 2;
+END_FUNCTION
+";
+
+        assert_eq!(
+            declare(source, "Twice", "Total", TYPE_PLACEHOLDER),
+            "FUNCTION Twice : INT
+VAR
+    Total : TYPE_NAME;
+END_VAR
+    Twice := Total * 2;
+END_FUNCTION
+"
+        );
+    }
+}
@@ -0,0 +1,734 @@
+//! Builds completion items for the language server.
+//!
+//! Completion reads where the cursor is from the tokens of the document, which
+//! exist even when the document does not parse, and what to offer from the
+//! catalog of the last complete analysis. Variables the document declares are
+//! also read from its tokens so that a name declared since that analysis is
+//! offered too.
+use ironplc_analyzer::completion_catalog::CompletionCatalog;
+use ironplc_analyzer::extractors::TypeSymbolKind;
+use ironplc_analyzer::FunctionSignature;
+use ironplc_dsl::common::TypeName;
+use ironplc_dsl::core::{FileId, Id};
+use ironplc_parser::options::CompilerOptions;
+use ironplc_parser::token::{Token, TokenType};
+use lsp_types::{CompletionItem, CompletionItemKind, InsertTextFormat};
+
+use crate::lsp_hover::signature_label;
+
+/// Keywords that start or belong to a statement.
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "IF",
+    "THEN",
+    "ELSIF",
+    "ELSE",
+    "END_IF",
+    "CASE",
+    "OF",
+    "END_CASE",
+    "FOR",
+    "TO",
+    "BY",
+    "DO",
+    "END_FOR",
+    "WHILE",
+    "END_WHILE",
+    "REPEAT",
+    "UNTIL",
+    "END_REPEAT",
+    "EXIT",
+    "RETURN",
+    "TRUE",
+    "FALSE",
+    "AND",
+    "AND_THEN",
+    "OR",
+    "XOR",
+    "NOT",
+    "MOD",
+    "THIS",
+    "SUPER",
+    "REF",
+    "NULL",
+];
+
+/// Keywords that open or close a section of a POU.
+const SECTION_KEYWORDS: &[&str] = &[
+    "VAR",
+    "VAR_INPUT",
+    "VAR_OUTPUT",
+    "VAR_IN_OUT",
+    "VAR_TEMP",
+    "VAR_EXTERNAL",
+    "END_VAR",
+    "CONSTANT",
+    "RETAIN",
+    "METHOD",
+    "END_METHOD",
+    "END_PROGRAM",
+    "END_FUNCTION_BLOCK",
+    "END_FUNCTION",
+];
+
+/// Keywords that declare something at the top level of a file.
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "PROGRAM",
+    "FUNCTION_BLOCK",
+    "FUNCTION",
+    "INTERFACE",
+    "TYPE",
+    "END_TYPE",
+    "VAR_GLOBAL",
+    "CONFIGURATION",
+    "RESOURCE",
+    "TASK",
+];
+
+/// Elementary types and the keywords that start a type specification.
+const TYPE_KEYWORDS: &[&str] = &[
+    "BOOL",
+    "SINT",
+    "INT",
+    "DINT",
+    "LINT",
+    "USINT",
+    "UINT",
+    "UDINT",
+    "ULINT",
+    "REAL",
+    "LREAL",
+    "BYTE",
+    "WORD",
+    "DWORD",
+    "LWORD",
+    "STRING",
+    "WSTRING",
+    "TIME",
+    "LTIME",
+    "DATE",
+    "LDATE",
+    "TIME_OF_DAY",
+    "LTIME_OF_DAY",
+    "DATE_AND_TIME",
+    "LDATE_AND_TIME",
+    "ARRAY",
+    "REF_TO",
+    "POINTER",
+    "REFERENCE",
+];
+
+/// Completion items for the cursor at the byte offset of the document.
+///
+/// `catalog` is from the last complete analysis, if there was one.
+pub(crate) fn complete(
+    tokens: &[Token],
+    offset: usize,
+    catalog: Option<&CompletionCatalog>,
+    options: &CompilerOptions,
+) -> Vec<CompletionItem> {
+    let cursor = Cursor::new(tokens, offset);
+    if cursor.in_comment {
+        return vec![];
+    }
+    let pou = enclosing_pou(tokens, cursor.start);
+    let locals = pou
+        .as_ref()
+        .map(|pou| declared_variables(tokens, pou.range.clone()))
+        .unwrap_or_default();
+    let empty = CompletionCatalog::default();
+    let catalog = catalog.unwrap_or(&empty);
+    let scope = Scope {
+        catalog,
+        pou: pou.as_ref().map(|pou| &pou.name),
+        locals: &locals,
+    };
+
+    let before = cursor.before(tokens);
+    match before.map(|index| &tokens[index].token_type) {
+        Some(TokenType::Period) => member_items(&scope, &receiver(tokens, before.unwrap())),
+        Some(TokenType::Hash) => {
+            let enum_type = previous_code(tokens, before.unwrap())
+                .map(|index| TypeName::from(tokens[index].text.as_str()));
+            enum_value_items(catalog, enum_type.as_ref())
+        }
+        Some(token_type) if in_declarations(tokens, cursor.start) => {
+            if matches!(
+                token_type,
+                TokenType::Colon | TokenType::Of | TokenType::RefTo | TokenType::To
+            ) {
+                type_items(catalog, options)
+            } else if *token_type == TokenType::Assignment {
+                value_items(&scope, options)
+            } else {
+                // The user is naming a new variable.
+                vec![]
+            }
+        }
+        _ if pou.is_some() => {
+            let mut items = value_items(&scope, options);
+            items.extend(function_block_call_items(&scope));
+            items.extend(function_items(catalog));
+            items.extend(keyword_items(SECTION_KEYWORDS, options));
+            items
+        }
+        _ => keyword_items(DECLARATION_KEYWORDS, options),
+    }
+}
+
+/// Where the cursor is in the tokens.
+struct Cursor {
+    /// Offset where the word being typed starts, or the cursor offset.
+    start: usize,
+    in_comment: bool,
+}
+
+impl Cursor {
+    fn new(tokens: &[Token], offset: usize) -> Self {
+        let at = tokens
+            .iter()
+            .find(|tok| tok.span.start < offset && offset <= tok.span.end);
+        match at {
+            Some(tok) if tok.token_type == TokenType::Comment => Cursor {
+                start: offset,
+                in_comment: !(tok.text.ends_with('\n') && offset == tok.span.end),
+            },
+            Some(tok) if is_word(tok) => Cursor {
+                start: tok.span.start,
+                in_comment: false,
+            },
+            _ => Cursor {
+                start: offset,
+                in_comment: false,
+            },
+        }
+    }
+
+    /// The index of the last code token before the word being typed.
+    fn before(&self, tokens: &[Token]) -> Option<usize> {
+        tokens
+            .iter()
+            .rposition(|tok| tok.span.end <= self.start && !is_trivia(tok))
+    }
+}
+
+/// What a name can refer to at the cursor.
+struct Scope<'a> {
+    catalog: &'a CompletionCatalog,
+    pou: Option<&'a Id>,
+    locals: &'a [(Id, Option<TypeName>)],
+}
+
+impl Scope<'_> {
+    /// The variables in scope with their types, declarations in the
+    /// document first.
+    fn variables(&self) -> Vec<(Id, Option<TypeName>)> {
+        let mut variables: Vec<(Id, Option<TypeName>)> = self.locals.to_vec();
+        for entry in self.catalog.variables(self.pou) {
+            if !variables.iter().any(|(name, _)| *name == entry.name) {
+                variables.push((entry.name.clone(), entry.type_name.clone()));
+            }
+        }
+        variables
+    }
+
+    fn type_of(&self, name: &Id) -> Option<TypeName> {
+        self.variables()
+            .into_iter()
+            .find(|(variable, _)| variable == name)
+            .and_then(|(_, type_name)| type_name)
+    }
+}
+
+/// The POU that contains a position: its name and the range of its tokens.
+pub(crate) struct Pou {
+    pub(crate) name: Id,
+    pub(crate) range: std::ops::Range<usize>,
+}
+
+pub(crate) fn enclosing_pou(tokens: &[Token], offset: usize) -> Option<Pou> {
+    let keyword = tokens.iter().rposition(|tok| {
+        tok.span.end <= offset
+            && matches!(
+                tok.token_type,
+                TokenType::Program
+                    | TokenType::FunctionBlock
+                    | TokenType::Function
+                    | TokenType::EndProgram
+                    | TokenType::EndFunctionBlock
+                    | TokenType::EndFunction
+            )
+    })?;
+    if matches!(
+        tokens[keyword].token_type,
+        TokenType::EndProgram | TokenType::EndFunctionBlock | TokenType::EndFunction
+    ) {
+        return None;
+    }
+    let name = next_code(tokens, keyword).filter(|index| is_word(&tokens[*index]))?;
+    let end = tokens[keyword..]
+        .iter()
+        .position(|tok| {
+            matches!(
+                tok.token_type,
+                TokenType::EndProgram | TokenType::EndFunctionBlock | TokenType::EndFunction
+            )
+        })
+        .map_or(tokens.len(), |end| keyword + end);
+    Some(Pou {
+        name: Id::from(tokens[name].text.as_str()),
+        range: keyword..end,
+    })
+}
+
+/// Whether the offset is in a variable block or a type declaration.
+fn in_declarations(tokens: &[Token], offset: usize) -> bool {
+    tokens
+        .iter()
+        .rev()
+        .filter(|tok| tok.span.end <= offset)
+        .find_map(|tok| match tok.token_type {
+            TokenType::EndVar | TokenType::EndType => Some(false),
+            _ if is_section(&tok.token_type) || tok.token_type == TokenType::Type => Some(true),
+            _ => None,
+        })
+        .unwrap_or(false)
+}
+
+/// The variables declared in the variable blocks of the token range, with
+/// the type written for each.
+fn declared_variables(
+    tokens: &[Token],
+    range: std::ops::Range<usize>,
+) -> Vec<(Id, Option<TypeName>)> {
+    let mut variables = vec![];
+    let mut in_block = false;
+    let mut pending: Vec<Id> = vec![];
+    let mut expect_name = false;
+    // Commas inside an initializer such as `[1, 2]` do not separate names.
+    let mut depth = 0usize;
+    let mut index = range.start;
+    while index < range.end {
+        let tok = &tokens[index];
+        index += 1;
+        if is_trivia(tok) {
+            continue;
+        }
+        match tok.token_type {
+            ref token_type if is_section(token_type) => {
+                in_block = true;
+                expect_name = true;
+            }
+            TokenType::EndVar => in_block = false,
+            TokenType::LeftParen | TokenType::LeftBracket => depth += 1,
+            TokenType::RightParen | TokenType::RightBracket => depth = depth.saturating_sub(1),
+            TokenType::Semicolon | TokenType::Comma if in_block && depth == 0 => expect_name = true,
+            TokenType::Constant | TokenType::Retain | TokenType::NonRetain => {}
+            TokenType::Identifier if in_block && expect_name => {
+                pending.push(Id::from(tok.text.as_str()));
+                expect_name = false;
+            }
+            TokenType::Colon if in_block && !pending.is_empty() => {
+                let type_name = next_code(tokens, index - 1)
+                    .filter(|index| is_word(&tokens[*index]))
+                    .map(|index| TypeName::from(tokens[index].text.as_str()));
+                variables.extend(pending.drain(..).map(|name| (name, type_name.clone())));
+            }
+            _ => expect_name = false,
+        }
+    }
+    variables
+}
+
+/// The names before the `.` at `period`, outermost first: `a.b.` is
+/// `[a, b]`.
+fn receiver(tokens: &[Token], period: usize) -> Vec<Id> {
+    let mut names = vec![];
+    let mut index = period;
+    while let Some(name) = previous_code(tokens, index).filter(|i| is_word(&tokens[*i])) {
+        names.push(Id::from(tokens[name].text.as_str()));
+        match previous_code(tokens, name) {
+            Some(dot) if tokens[dot].token_type == TokenType::Period => index = dot,
+            _ => break,
+        }
+    }
+    names.reverse();
+    names
+}
+
+fn member_items(scope: &Scope, receiver: &[Id]) -> Vec<CompletionItem> {
+    let Some((first, rest)) = receiver.split_first() else {
+        return vec![];
+    };
+    let mut type_name = scope.type_of(first);
+    for name in rest {
+        type_name = type_name.and_then(|type_name| {
+            scope
+                .catalog
+                .members(&type_name)
+                .iter()
+                .find(|member| member.name == *name)
+                .and_then(|member| member.type_name.clone())
+        });
+    }
+    let Some(type_name) = type_name else {
+        return vec![];
+    };
+    scope
+        .catalog
+        .members(&type_name)
+        .iter()
+        .map(|member| CompletionItem {
+            label: member.name.to_string(),
+            kind: Some(CompletionItemKind::FIELD),
+            detail: member.type_name.as_ref().map(ToString::to_string),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+/// Names that hold a value: variables, enumeration values and the
+/// literal keywords.
+fn value_items(scope: &Scope, options: &CompilerOptions) -> Vec<CompletionItem> {
+    let mut items: Vec<CompletionItem> = scope
+        .variables()
+        .into_iter()
+        .map(|(name, type_name)| CompletionItem {
+            label: name.to_string(),
+            kind: Some(CompletionItemKind::VARIABLE),
+            detail: type_name.map(|type_name| type_name.to_string()),
+            ..CompletionItem::default()
+        })
+        .collect();
+    items.extend(enum_value_items(scope.catalog, None));
+    items.extend(keyword_items(STATEMENT_KEYWORDS, options));
+    items
+}
+
+fn enum_value_items(
+    catalog: &CompletionCatalog,
+    type_name: Option<&TypeName>,
+) -> Vec<CompletionItem> {
+    catalog
+        .enum_values(type_name)
+        .map(|(enum_type, value)| CompletionItem {
+            label: value.to_string(),
+            kind: Some(CompletionItemKind::ENUM_MEMBER),
+            detail: Some(enum_type.to_string()),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+/// A call of each function block instance in scope, with a placeholder
+/// for each input.
+fn function_block_call_items(scope: &Scope) -> Vec<CompletionItem> {
+    scope
+        .variables()
+        .into_iter()
+        .filter_map(|(name, type_name)| {
+            let type_name = type_name.filter(|ty| scope.catalog.is_function_block(ty))?;
+            let inputs: Vec<String> = scope
+                .catalog
+                .members(&type_name)
+                .iter()
+                .filter(|member| {
+                    member.variable_type != Some(ironplc_dsl::common::VariableType::Output)
+                })
+                .enumerate()
+                .map(|(i, member)| format!("{} := ${}", member.name, i + 1))
+                .collect();
+            Some(CompletionItem {
+                label: format!("{name}(...)"),
+                kind: Some(CompletionItemKind::METHOD),
+                detail: Some(format!("call {type_name}")),
+                filter_text: Some(name.to_string()),
+                insert_text: Some(format!("{}({})", name, inputs.join(", "))),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..CompletionItem::default()
+            })
+        })
+        .collect()
+}
+
+/// Each function, inserted as a call with a placeholder for each input.
+fn function_items(catalog: &CompletionCatalog) -> Vec<CompletionItem> {
+    catalog
+        .functions()
+        .iter()
+        .map(|signature| CompletionItem {
+            label: signature.name.to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some(signature_label(signature)),
+            insert_text: Some(call_snippet(signature)),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+fn call_snippet(signature: &FunctionSignature) -> String {
+    let inputs: Vec<String> = signature
+        .parameters
+        .iter()
+        .filter(|param| param.is_input || param.is_inout)
+        .enumerate()
+        .map(|(i, param)| format!("${{{}:{}}}", i + 1, param.name))
+        .collect();
+    format!("{}({})", signature.name, inputs.join(", "))
+}
+
+/// Types a declaration can name: the elementary types the dialect has, then
+/// the declared types.
+fn type_items(catalog: &CompletionCatalog, options: &CompilerOptions) -> Vec<CompletionItem> {
+    let mut items = keyword_items(TYPE_KEYWORDS, options);
+    items.extend(catalog.types().iter().map(|ty| CompletionItem {
+        label: ty.name.to_string(),
+        kind: Some(match ty.kind {
+            TypeSymbolKind::FunctionBlock => CompletionItemKind::CLASS,
+            TypeSymbolKind::Structure => CompletionItemKind::STRUCT,
+            TypeSymbolKind::Enumeration => CompletionItemKind::ENUM,
+            _ => CompletionItemKind::TYPE_PARAMETER,
+        }),
+        detail: Some(ty.kind.as_str().replace('_', " ")),
+        ..CompletionItem::default()
+    }));
+    items
+}
+
+/// The words that are keywords in the dialect. A word gated by a dialect
+/// option is lexed as an identifier when the option is off, so lexing each
+/// word applies the same rules as the parser.
+fn keyword_items(words: &[&str], options: &CompilerOptions) -> Vec<CompletionItem> {
+    words
+        .iter()
+        .filter(|word| {
+            let (tokens, _) =
+                ironplc_parser::tokenize_program(word, &FileId::default(), options, 0, 0);
+            tokens
+                .first()
+                .is_some_and(|tok| tok.token_type != TokenType::Identifier)
+        })
+        .map(|word| CompletionItem {
+            label: word.to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+fn is_section(token_type: &TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Var
+            | TokenType::VarInput
+            | TokenType::VarOutput
+            | TokenType::VarInOut
+            | TokenType::VarTemp
+            | TokenType::VarExternal
+            | TokenType::VarGlobal
+            | TokenType::VarAccess
+            | TokenType::VarConfig
+    )
+}
+
+fn previous_code(tokens: &[Token], index: usize) -> Option<usize> {
+    tokens[..index].iter().rposition(|tok| !is_trivia(tok))
+}
+
+fn next_code(tokens: &[Token], index: usize) -> Option<usize> {
+    tokens[index + 1..]
+        .iter()
+        .position(|tok| !is_trivia(tok))
+        .map(|next| index + 1 + next)
+}
+
+/// An identifier, or a keyword being typed that is a prefix of a name.
+fn is_word(tok: &Token) -> bool {
+    tok.text
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && tok
+            .text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_trivia(tok: &Token) -> bool {
+    matches!(
+        tok.token_type,
+        TokenType::Whitespace | TokenType::Newline | TokenType::Comment
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "TYPE
+    Mode : (Idle, Busy);
+END_TYPE
+FUNCTION_BLOCK Counter
+VAR_INPUT
+    Delta : INT;
+END_VAR
+VAR_OUTPUT
+    Total : INT;
+END_VAR
+    Total := Total + Delta;
+END_FUNCTION_BLOCK
+PROGRAM Main
+VAR
+    Tally : Counter;
+    Timer : TON;
+    State : Mode;
+END_VAR
+    Tally(Delta := 1);
+END_PROGRAM";
+
+    fn catalog() -> CompletionCatalog {
+        let options = CompilerOptions::default();
+        let library = ironplc_parser::parse_program(PROGRAM, &FileId::default(), &options).unwrap();
+        let (_, context) = ironplc_analyzer::stages::analyze(&[&library], &options).unwrap();
+        CompletionCatalog::from(&context)
+    }
+
+    /// Completes at the `|` in the text, which is removed first.
+    fn complete_at(text: &str, options: &CompilerOptions) -> Vec<CompletionItem> {
+        let offset = text.find('|').unwrap();
+        let text = text.replace('|', "");
+        let (tokens, _) =
+            ironplc_parser::tokenize_program(&text, &FileId::default(), options, 0, 0);
+        complete(&tokens, offset, Some(&catalog()), options)
+    }
+
+    fn labels(items: &[CompletionItem]) -> Vec<&str> {
+        items.iter().map(|item| item.label.as_str()).collect()
+    }
+
+    fn in_main(body: &str) -> String {
+        PROGRAM.replace("    Tally(Delta := 1);", body)
+    }
+
+    #[test]
+    fn complete_when_in_body_then_variables_functions_and_keywords() {
+        let items = complete_at(&in_main("    Ta|"), &CompilerOptions::default());
+
+        let labels = labels(&items);
+        assert!(labels.contains(&"Tally"));
+        assert!(labels.contains(&"State"));
+        assert!(labels.contains(&"Busy"));
+        assert!(labels.contains(&"IF"));
+        assert!(!labels.contains(&"Delta"));
+    }
+
+    #[test]
+    fn complete_when_standard_function_then_snippet_with_inputs() {
+        let items = complete_at(&in_main("    |"), &CompilerOptions::default());
+
+        let limit = items.iter().find(|item| item.label == "LIMIT").unwrap();
+        assert_eq!(
+            limit.insert_text.as_deref(),
+            Some("LIMIT(${1:MN}, ${2:IN}, ${3:MX})")
+        );
+        assert_eq!(limit.insert_text_format, Some(InsertTextFormat::SNIPPET));
+    }
+
+    #[test]
+    fn complete_when_function_block_instance_then_call_snippet_with_inputs() {
+        let items = complete_at(&in_main("    |"), &CompilerOptions::default());
+
+        let call = items
+            .iter()
+            .find(|item| item.label == "Tally(...)")
+            .unwrap();
+        assert_eq!(call.insert_text.as_deref(), Some("Tally(Delta := $1)"));
+    }
+
+    #[test]
+    fn complete_when_after_period_then_members_of_instance_type() {
+        let items = complete_at(&in_main("    Tally.|"), &CompilerOptions::default());
+
+        assert_eq!(labels(&items), vec!["Delta", "Total"]);
+    }
+
+    #[test]
+    fn complete_when_after_period_on_standard_function_block_then_members() {
+        let items = complete_at(&in_main("    Timer.E|"), &CompilerOptions::default());
+
+        assert_eq!(labels(&items), vec!["IN", "PT", "Q", "ET"]);
+    }
+
+    #[test]
+    fn complete_when_after_type_prefix_then_values_of_enumeration() {
+        let items = complete_at(&in_main("    State := Mode#|"), &CompilerOptions::default());
+
+        assert_eq!(labels(&items), vec!["Busy", "Idle"]);
+    }
+
+    #[test]
+    fn complete_when_type_position_in_var_block_then_types() {
+        let text = PROGRAM.replace("    State : Mode;", "    Other : |");
+
+        let items = complete_at(&text, &CompilerOptions::default());
+
+        let labels = labels(&items);
+        assert!(labels.contains(&"INT"));
+        assert!(labels.contains(&"Counter"));
+        assert!(labels.contains(&"TON"));
+        assert!(labels.contains(&"Mode"));
+        assert!(!labels.contains(&"LTIME"));
+        assert!(!labels.contains(&"Tally"));
+    }
+
+    #[test]
+    fn complete_when_dialect_has_long_time_types_then_offers_them() {
+        let text = PROGRAM.replace("    State : Mode;", "    Other : |");
+        let options = CompilerOptions {
+            allow_long_time_types: true,
+            ..CompilerOptions::default()
+        };
+
+        let items = complete_at(&text, &options);
+
+        assert!(labels(&items).contains(&"LTIME"));
+    }
+
+    #[test]
+    fn complete_when_naming_variable_then_empty() {
+        let text = PROGRAM.replace("    State : Mode;", "    Oth|");
+
+        assert!(complete_at(&text, &CompilerOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn complete_when_document_does_not_parse_then_offers_new_declaration() {
+        let text = PROGRAM
+            .replace(
+                "    State : Mode;",
+                "    State : Mode;\n    Fresh : Counter;",
+            )
+            .replace("    Tally(Delta := 1);", "    IF Fresh. THEN\n    Fresh.|");
+
+        let items = complete_at(&text, &CompilerOptions::default());
+
+        assert_eq!(labels(&items), vec!["Delta", "Total"]);
+    }
+
+    #[test]
+    fn complete_when_in_comment_then_empty() {
+        let items = complete_at(&in_main("    (* Ta| *)"), &CompilerOptions::default());
+
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn complete_when_outside_pou_then_declaration_keywords() {
+        let text = format!("{PROGRAM}\n|");
+
+        let items = complete_at(&text, &CompilerOptions::default());
+
+        assert!(labels(&items).contains(&"FUNCTION_BLOCK"));
+        assert!(!labels(&items).contains(&"IF"));
+    }
+}
@@ -0,0 +1,479 @@
+//! Builds hover content for the language server: what a name declares, the
+//! signature of a standard library function, or the type analysis resolved
+//! for a literal.
+//!
+//! Declarations are described from their source tokens rather than from the
+//! parsed library so that the hover shows the type and initial value as the
+//! user wrote them, along with the comment that documents the declaration.
+use ironplc_analyzer::literal_types::ResolvedLiteral;
+use ironplc_analyzer::symbol_index::SymbolId;
+use ironplc_analyzer::FunctionSignature;
+use ironplc_parser::token::{Token, TokenType};
+
+/// Opens a code block highlighted as Structured Text by the editor.
+const CODE_FENCE: &str = "```61131-3-st";
+
+/// Describes the declaration whose name is the token that starts at the
+/// byte offset `start`.
+pub(crate) fn declaration(symbol: &SymbolId, tokens: &[Token], start: usize) -> Option<String> {
+    let index = tokens.iter().position(|tok| tok.span.start == start)?;
+    let (code, end) = match symbol {
+        SymbolId::Variable(..) | SymbolId::Field(..) => variable(tokens, index)?,
+        SymbolId::EnumValue(type_name, value) => {
+            (format!("{type_name}#{value}"), line_end(tokens, index))
+        }
+        _ => (line(tokens, index), line_end(tokens, index)),
+    };
+    let comment = trailing_comment(tokens, end).or_else(|| leading_comment(tokens, index));
+    Some(markdown(&code, comment.as_deref()))
+}
+
+/// Describes a standard library function by its signature.
+pub(crate) fn function(signature: &FunctionSignature) -> String {
+    markdown(
+        &signature_label(signature),
+        Some("Standard library function."),
+    )
+}
+
+/// Describes the type analysis resolved for a literal. `text` is the
+/// literal as written.
+pub(crate) fn literal(literal: &ResolvedLiteral, text: &str) -> String {
+    let code = format!("{} : {}", text, literal.type_name);
+    // Generic types such as ANY_INT are what analysis gives a literal
+    // written without a type prefix.
+    let note = literal
+        .type_name
+        .to_string()
+        .to_uppercase()
+        .starts_with("ANY")
+        .then_some("Untyped literal: takes the type that its context requires.");
+    markdown(&code, note)
+}
+
+/// The identifier at the byte offset when it names a function being called,
+/// that is, when an opening parenthesis follows it.
+pub(crate) fn called_name(tokens: &[Token], offset: usize) -> Option<&Token> {
+    let index = tokens.iter().position(|tok| {
+        tok.token_type == TokenType::Identifier
+            && tok.span.start <= offset
+            && offset <= tok.span.end
+    })?;
+    tokens[index + 1..]
+        .iter()
+        .find(|tok| !is_trivia(tok))
+        .filter(|tok| tok.token_type == TokenType::LeftParen)
+        .map(|_| &tokens[index])
+}
+
+/// Writes the signature as `NAME(IN : T, ..., OUT => T) : RESULT`.
+pub(crate) fn signature_label(signature: &FunctionSignature) -> String {
+    signature_label_with_offsets(signature).0
+}
+
+/// Writes the signature as [`signature_label`] does, with where each
+/// parameter is in the label as start and end offsets.
+pub(crate) fn signature_label_with_offsets(
+    signature: &FunctionSignature,
+) -> (String, Vec<[u32; 2]>) {
+    let mut label = format!("{}(", signature.name);
+    let mut offsets = Vec::with_capacity(signature.parameters.len());
+    for (index, param) in signature.parameters.iter().enumerate() {
+        if index > 0 {
+            label.push_str(", ");
+        }
+        let assign = if param.is_output && !param.is_inout {
+            "=>"
+        } else {
+            ":"
+        };
+        let reference = if param.is_reference { "REF_TO " } else { "" };
+        let start = label.len() as u32;
+        label.push_str(&format!(
+            "{} {} {}{}",
+            param.name, assign, reference, param.param_type
+        ));
+        offsets.push([start, label.len() as u32]);
+    }
+    if signature.is_extensible {
+        if !signature.parameters.is_empty() {
+            label.push_str(", ");
+        }
+        label.push_str("...");
+    }
+    label.push(')');
+    if let Some(return_type) = &signature.return_type {
+        label.push_str(&format!(" : {}", return_type.to_type_name()));
+    }
+    (label, offsets)
+}
+
+fn markdown(code: &str, comment: Option<&str>) -> String {
+    let mut value = format!("{CODE_FENCE}\n{code}\n```");
+    if let Some(comment) = comment {
+        value.push_str("\n\n");
+        value.push_str(comment);
+    }
+    value
+}
+
+/// Describes a variable or field as `SECTION name : TYPE := VALUE` and
+/// returns the index of the token that ends the declaration.
+fn variable(tokens: &[Token], index: usize) -> Option<(String, usize)> {
+    let colon = index
+        + tokens[index..]
+            .iter()
+            .position(|tok| matches!(tok.token_type, TokenType::Colon | TokenType::Semicolon))?;
+    if tokens[colon].token_type != TokenType::Colon {
+        return None;
+    }
+    let type_end = until_depth_zero(tokens, colon + 1, |tok| {
+        matches!(tok, TokenType::Assignment | TokenType::Semicolon)
+    });
+    let mut code = String::new();
+    if let Some(section) = section(tokens, index) {
+        code.push_str(&section);
+        code.push(' ');
+    }
+    code.push_str(&format!(
+        "{} : {}",
+        tokens[index].text,
+        join(&tokens[colon + 1..type_end])
+    ));
+    let end = match tokens.get(type_end) {
+        Some(tok) if tok.token_type == TokenType::Assignment => {
+            let value_end = until_depth_zero(tokens, type_end + 1, |tok| {
+                matches!(tok, TokenType::Semicolon)
+            });
+            code.push_str(&format!(" := {}", join(&tokens[type_end + 1..value_end])));
+            value_end
+        }
+        _ => type_end,
+    };
+    Some((code, end))
+}
+
+/// The index of the first token from `start` that matches `stop` outside of
+/// any parentheses or brackets, or the end of the tokens.
+fn until_depth_zero(tokens: &[Token], start: usize, stop: impl Fn(&TokenType) -> bool) -> usize {
+    let mut depth = 0usize;
+    for (index, tok) in tokens.iter().enumerate().skip(start) {
+        match tok.token_type {
+            TokenType::LeftParen | TokenType::LeftBracket => depth += 1,
+            TokenType::RightParen | TokenType::RightBracket => depth = depth.saturating_sub(1),
+            TokenType::EndVar | TokenType::EndStruct => return index,
+            ref token_type if depth == 0 && stop(token_type) => return index,
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+/// The variable section keyword, with any qualifiers, of the block that
+/// declares the token at `index`.
+fn section(tokens: &[Token], index: usize) -> Option<String> {
+    let keyword = tokens[..index].iter().rposition(|tok| {
+        matches!(
+            tok.token_type,
+            TokenType::Var
+                | TokenType::VarInput
+                | TokenType::VarOutput
+                | TokenType::VarInOut
+                | TokenType::VarTemp
+                | TokenType::VarExternal
+                | TokenType::VarAccess
+                | TokenType::VarConfig
+                | TokenType::VarGlobal
+                | TokenType::EndVar
+        )
+    })?;
+    if tokens[keyword].token_type == TokenType::EndVar {
+        return None;
+    }
+    let qualifiers = tokens[keyword + 1..]
+        .iter()
+        .filter(|tok| !is_trivia(tok))
+        .take_while(|tok| {
+            matches!(
+                tok.token_type,
+                TokenType::Constant | TokenType::Retain | TokenType::NonRetain
+            )
+        });
+    let words: Vec<String> = std::iter::once(&tokens[keyword])
+        .chain(qualifiers)
+        .map(|tok| tok.text.to_uppercase())
+        .collect();
+    Some(words.join(" "))
+}
+
+/// The tokens of the line that contains the token at `index`.
+fn line(tokens: &[Token], index: usize) -> String {
+    join(&tokens[line_start(tokens, index)..=line_end(tokens, index)])
+}
+
+/// The index of the first token on the line of the token at `index`.
+fn line_start(tokens: &[Token], index: usize) -> usize {
+    tokens[..index]
+        .iter()
+        .rposition(ends_line)
+        .map_or(0, |end| end + 1)
+}
+
+/// The index of the token that ends the line of the token at `index`.
+fn line_end(tokens: &[Token], index: usize) -> usize {
+    tokens[index..]
+        .iter()
+        .position(ends_line)
+        .map_or(tokens.len() - 1, |end| index + end)
+}
+
+/// A comment that follows the token at `end` on the same line. When `end`
+/// ends a line, the comment is the last thing on that line.
+fn trailing_comment(tokens: &[Token], end: usize) -> Option<String> {
+    let comment = if tokens.get(end).is_some_and(ends_line) {
+        tokens[..=end]
+            .iter()
+            .rev()
+            .find(|tok| !matches!(tok.token_type, TokenType::Whitespace | TokenType::Newline))
+    } else {
+        tokens
+            .iter()
+            .skip(end + 1)
+            .find(|tok| tok.token_type != TokenType::Whitespace)
+    };
+    comment
+        .filter(|tok| tok.token_type == TokenType::Comment)
+        .map(|tok| comment_text(&tok.text))
+}
+
+/// The comments on the lines directly above the line of the token at
+/// `index`, up to a blank line or a line with code.
+fn leading_comment(tokens: &[Token], index: usize) -> Option<String> {
+    let mut comments = vec![];
+    let mut start = line_start(tokens, index);
+    while start > 0 {
+        let previous = line_start(tokens, start - 1);
+        let content: Vec<&Token> = tokens[previous..start]
+            .iter()
+            .filter(|tok| !matches!(tok.token_type, TokenType::Whitespace | TokenType::Newline))
+            .collect();
+        if content.is_empty()
+            || content
+                .iter()
+                .any(|tok| tok.token_type != TokenType::Comment)
+        {
+            break;
+        }
+        comments.extend(content.iter().rev().map(|tok| comment_text(&tok.text)));
+        start = previous;
+    }
+    comments.reverse();
+    (!comments.is_empty()).then(|| comments.join("\n"))
+}
+
+/// The text of a comment without its delimiters.
+fn comment_text(text: &str) -> String {
+    let text = text.trim();
+    let body = text
+        .strip_prefix("(*")
+        .and_then(|body| body.strip_suffix("*)"))
+        .or_else(|| {
+            text.strip_prefix("/*")
+                .and_then(|body| body.strip_suffix("*/"))
+        })
+        .or_else(|| text.strip_prefix("//"))
+        .unwrap_or(text);
+    body.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Joins the code tokens, writing a single space wherever the source
+/// separates tokens by whitespace or a comment.
+fn join(tokens: &[Token]) -> String {
+    let mut text = String::new();
+    let mut separated = false;
+    for tok in tokens {
+        if is_trivia(tok) {
+            separated = true;
+            continue;
+        }
+        if separated && !text.is_empty() {
+            text.push(' ');
+        }
+        separated = false;
+        text.push_str(&tok.text);
+    }
+    text
+}
+
+fn is_trivia(tok: &Token) -> bool {
+    matches!(
+        tok.token_type,
+        TokenType::Whitespace | TokenType::Newline | TokenType::Comment
+    )
+}
+
+/// A `//` comment token includes the newline that ends it.
+fn ends_line(tok: &Token) -> bool {
+    tok.token_type == TokenType::Newline
+        || (tok.token_type == TokenType::Comment && tok.text.ends_with('\n'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironplc_analyzer::extractors::stdlib_function;
+    use ironplc_dsl::common::TypeName;
+    use ironplc_dsl::core::{FileId, Id, SourceSpan};
+    use ironplc_parser::options::CompilerOptions;
+
+    const PROGRAM: &str = "FUNCTION_BLOCK Counter (* Counts rising edges *)
+VAR_INPUT
+    (* How much to add
+       on each edge *)
+    Delta : INT := 5;
+    Limit : INT; // Stops counting here
+END_VAR
+VAR_OUTPUT RETAIN
+    Total : ARRAY[1..2] OF INT := [1, 2];
+END_VAR
+
+(* Unrelated *)
+
+VAR
+    Ready : BOOL;
+END_VAR
+END_FUNCTION_BLOCK";
+
+    fn tokens() -> Vec<Token> {
+        ironplc_parser::tokenize_program(
+            PROGRAM,
+            &FileId::default(),
+            &CompilerOptions::default(),
+            0,
+            0,
+        )
+        .0
+    }
+
+    fn variable(name: &str) -> SymbolId {
+        SymbolId::Variable(
+            ironplc_analyzer::symbol_index::Scope::Pou(Id::from("Counter")),
+            Id::from(name),
+        )
+    }
+
+    #[test]
+    fn declaration_when_variable_with_leading_comment_then_section_type_value_and_comment() {
+        let start = PROGRAM.find("Delta").unwrap();
+
+        let hover = declaration(&variable("Delta"), &tokens(), start).unwrap();
+
+        assert_eq!(
+            hover,
+            "```61131-3-st\nVAR_INPUT Delta : INT := 5\n```\n\nHow much to add\non each edge"
+        );
+    }
+
+    #[test]
+    fn declaration_when_variable_with_trailing_line_comment_then_comment() {
+        let start = PROGRAM.find("Limit").unwrap();
+
+        let hover = declaration(&variable("Limit"), &tokens(), start).unwrap();
+
+        assert_eq!(
+            hover,
+            "```61131-3-st\nVAR_INPUT Limit : INT\n```\n\nStops counting here"
+        );
+    }
+
+    #[test]
+    fn declaration_when_qualified_section_and_array_value_then_written_as_declared() {
+        let start = PROGRAM.find("Total").unwrap();
+
+        let hover = declaration(&variable("Total"), &tokens(), start).unwrap();
+
+        assert_eq!(
+            hover,
+            "```61131-3-st\nVAR_OUTPUT RETAIN Total : ARRAY[1..2] OF INT := [1, 2]\n```"
+        );
+    }
+
+    #[test]
+    fn declaration_when_comment_separated_by_blank_line_then_no_comment() {
+        let start = PROGRAM.find("Ready").unwrap();
+
+        let hover = declaration(&variable("Ready"), &tokens(), start).unwrap();
+
+        assert_eq!(hover, "```61131-3-st\nVAR Ready : BOOL\n```");
+    }
+
+    #[test]
+    fn declaration_when_pou_then_declaration_line_and_comment() {
+        let start = PROGRAM.find("Counter").unwrap();
+
+        let hover = declaration(&SymbolId::Pou(Id::from("Counter")), &tokens(), start).unwrap();
+
+        assert_eq!(
+            hover,
+            "```61131-3-st\nFUNCTION_BLOCK Counter\n```\n\nCounts rising edges"
+        );
+    }
+
+    #[test]
+    fn function_when_standard_function_then_signature() {
+        let signature = stdlib_function(&Id::from("LIMIT")).unwrap();
+
+        let hover = function(&signature);
+
+        assert!(hover.contains("LIMIT(MN : "), "{hover}");
+    }
+
+    #[test]
+    fn called_name_when_identifier_before_parenthesis_then_identifier() {
+        let text = "x := LIMIT (0, x, 10);";
+        let tokens = ironplc_parser::tokenize_program(
+            text,
+            &FileId::default(),
+            &CompilerOptions::default(),
+            0,
+            0,
+        )
+        .0;
+
+        let name = called_name(&tokens, text.find("LIMIT").unwrap() + 1).unwrap();
+
+        assert_eq!(name.text, "LIMIT");
+        assert!(called_name(&tokens, 0).is_none());
+    }
+
+    #[test]
+    fn literal_when_generic_type_then_notes_untyped_literal() {
+        let resolved = ResolvedLiteral {
+            span: SourceSpan::default(),
+            type_name: TypeName::from("ANY_INT"),
+        };
+
+        let hover = literal(&resolved, "5");
+
+        assert_eq!(
+            hover,
+            "```61131-3-st\n5 : ANY_INT\n```\n\nUntyped literal: takes the type that its context requires."
+        );
+    }
+
+    #[test]
+    fn literal_when_typed_then_type_only() {
+        let resolved = ResolvedLiteral {
+            span: SourceSpan::default(),
+            type_name: TypeName::from("DINT"),
+        };
+
+        let hover = literal(&resolved, "DINT#7");
+
+        assert_eq!(hover, "```61131-3-st\nDINT#7 : DINT\n```");
+    }
+}
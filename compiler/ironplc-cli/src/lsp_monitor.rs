@@ -0,0 +1,183 @@
+//! The `ironplc/monitor` notification: the live values of a running
+//! program, placed on the names that refer to them in an open document so
+//! the editor can show each value next to the code.
+
+use ironplc_analyzer::symbol_index::{Scope, SymbolId, SymbolIndex};
+use ironplc_dsl::core::{FileId, Id, SourceSpan};
+use lsp_types::{Range, Uri};
+use serde::{Deserialize, Serialize};
+
+use crate::lsp_runner::VariableInfo;
+
+/// The notification the server sends after each step of a run session that
+/// monitors a document.
+pub enum Monitor {}
+
+impl lsp_types::notification::Notification for Monitor {
+    type Params = MonitorParams;
+    const METHOD: &'static str = "ironplc/monitor";
+}
+
+/// The values of the variables the monitored document refers to.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonitorParams {
+    pub uri: Uri,
+    pub total_scans: u64,
+    pub values: Vec<MonitorValue>,
+}
+
+/// The value of one variable and the ranges in the document that name it:
+/// its declaration and every use.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonitorValue {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub type_name: String,
+    pub value: String,
+    pub ranges: Vec<Range>,
+}
+
+/// The values of the variables that the file names, each with the ranges
+/// of the names. The owner gives the POU that declares a variable of the
+/// run, by index, and the range converts a span of the file. Variables
+/// without a name in the debug section, or that the file does not name,
+/// are left out.
+pub(crate) fn values<'a>(
+    index: &SymbolIndex,
+    file_id: &FileId,
+    variables: &[VariableInfo],
+    owner: impl Fn(u16) -> Option<&'a str>,
+    range: impl Fn(&SourceSpan) -> Range,
+) -> Vec<MonitorValue> {
+    variables
+        .iter()
+        .filter(|variable| !variable.name.is_empty())
+        .filter_map(|variable| {
+            let symbol = variable_symbol(index, owner(variable.index), &variable.name)?;
+            let ranges: Vec<Range> = index
+                .references(&symbol)
+                .into_iter()
+                .filter(|span| &span.file_id == file_id)
+                .map(&range)
+                .collect();
+            if ranges.is_empty() {
+                return None;
+            }
+            Some(MonitorValue {
+                name: variable.name.clone(),
+                type_name: variable.type_name.clone(),
+                value: variable.value.clone(),
+                ranges,
+            })
+        })
+        .collect()
+}
+
+/// The symbol that a variable of the debug section is. A variable in the
+/// global scope of the debug section is either a variable of the program
+/// or a global variable, so the program is tried first.
+fn variable_symbol(index: &SymbolIndex, owner: Option<&str>, name: &str) -> Option<SymbolId> {
+    let name = Id::from(name);
+    owner
+        .map(|pou| SymbolId::Variable(Scope::Pou(Id::from(pou)), name.clone()))
+        .into_iter()
+        .chain(std::iter::once(SymbolId::Variable(Scope::Global, name)))
+        .find(|symbol| index.definition(symbol).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironplc_parser::options::CompilerOptions;
+    use lsp_types::Position;
+
+    const SOURCE: &str = "
+VAR_GLOBAL
+    Limit : INT;
+END_VAR
+PROGRAM Main
+VAR
+    Count : INT;
+END_VAR
+    Count := Count + Limit;
+END_PROGRAM";
+
+    fn index() -> SymbolIndex {
+        let options = CompilerOptions::default();
+        let library = ironplc_parser::parse_program(SOURCE, &FileId::default(), &options).unwrap();
+        SymbolIndex::build(&[&library])
+    }
+
+    fn variable(index: u16, name: &str, value: &str) -> VariableInfo {
+        VariableInfo {
+            index,
+            value: value.to_string(),
+            name: name.to_string(),
+            type_name: "INT".to_string(),
+        }
+    }
+
+    /// The range of a span as its byte offsets, on line 0.
+    fn offsets(span: &SourceSpan) -> Range {
+        Range::new(
+            Position::new(0, span.start as u32),
+            Position::new(0, span.end as u32),
+        )
+    }
+
+    #[test]
+    fn values_when_program_variable_then_declaration_and_uses() {
+        let index = index();
+        let variables = [variable(0, "Count", "3")];
+
+        let values = values(
+            &index,
+            &FileId::default(),
+            &variables,
+            |_| Some("Main"),
+            offsets,
+        );
+
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].value, "3");
+        assert_eq!(values[0].ranges.len(), 3);
+    }
+
+    #[test]
+    fn values_when_global_variable_then_found_in_global_scope() {
+        let index = index();
+        let variables = [variable(1, "Limit", "10")];
+
+        let values = values(
+            &index,
+            &FileId::default(),
+            &variables,
+            |_| Some("Main"),
+            offsets,
+        );
+
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].ranges.len(), 2);
+    }
+
+    #[test]
+    fn values_when_other_file_then_empty() {
+        let index = index();
+        let variables = [variable(0, "Count", "3")];
+        let other = FileId::from_string("other.st");
+
+        let values = values(&index, &other, &variables, |_| Some("Main"), offsets);
+
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn values_when_unnamed_variable_then_left_out() {
+        let index = index();
+        let variables = [variable(2, "", "0")];
+
+        let values = values(&index, &FileId::default(), &variables, |_| None, offsets);
+
+        assert!(values.is_empty());
+    }
+}
@@ -4,20 +4,33 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use ironplc_analyzer::extractors::TypeSymbolKind;
+use ironplc_analyzer::completion_catalog::CompletionCatalog;
+use ironplc_analyzer::extractors::{stdlib_function, TypeSymbolKind};
+use ironplc_analyzer::literal_types::{literal_at, untyped_literals};
+use ironplc_analyzer::symbol_index::{SymbolId, SymbolIndex};
 use ironplc_analyzer::SemanticContext;
-use ironplc_dsl::core::{FileId, Located};
-use ironplc_dsl::diagnostic::LineColumn;
+use ironplc_dsl::core::{FileId, Id, Located, SourceSpan};
 use ironplc_parser::token::{Token, TokenType};
+use ironplc_plc2plc::formatter::{self, FormatOptions, Indent};
 use log::error;
 use lsp_types::{
-    CodeDescription, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, DocumentSymbol,
-    DocumentSymbolResponse, Location, NumberOrString, SemanticTokenType, SymbolKind,
-    WorkspaceFolder,
+    CallHierarchyIncomingCall, CallHierarchyItem, CallHierarchyOutgoingCall, CodeDescription,
+    CompletionItem, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, DocumentChanges,
+    DocumentSymbol, DocumentSymbolResponse, FormattingOptions, Hover, HoverContents, InlayHint,
+    InlayHintKind, InlayHintLabel, Location, MarkupContent, MarkupKind, NumberOrString, OneOf,
+    OptionalVersionedTextDocumentIdentifier, Position, SemanticTokenType, SignatureHelp,
+    SymbolKind, TextDocumentContentChangeEvent, TextDocumentEdit, TextEdit, WorkspaceEdit,
+    WorkspaceFolder, WorkspaceSymbol,
 };
 use lsp_types::{SemanticToken, Uri};
 
+use crate::lsp_code_action;
+use crate::lsp_completion;
+use crate::lsp_hover;
+use crate::lsp_monitor::{self, MonitorParams};
 use crate::lsp_runner::{RunResult, VmRunner};
+use crate::lsp_signature;
+use crate::lsp_symbol;
 use ironplc_project::Project;
 
 fn to_path_buf(uri: &Uri) -> Result<PathBuf, ()> {
@@ -90,6 +103,8 @@ pub struct LspProject {
     wrapped: Box<dyn Project + Send>,
     /// Active VM runner session for step-through execution.
     runner: Option<VmRunner>,
+    /// The document whose variables the runner session monitors.
+    monitored: Option<Uri>,
     /// Compiler options used for compilation (cached for runner).
     compiler_options: ironplc_parser::options::CompilerOptions,
     /// Key for the most recently changed text document. Used as a
@@ -97,6 +112,9 @@ pub struct LspProject {
     /// back to a URI (e.g. `BuiltIn`) still surface in the editor
     /// instead of being silently dropped.
     last_changed_uri_key: Option<UriKey>,
+    /// Names from the last analysis in which every source parsed, kept so
+    /// that completion still has them while a document has errors.
+    completion_catalog: Option<CompletionCatalog>,
 }
 
 impl LspProject {
@@ -104,8 +122,10 @@ impl LspProject {
         Self {
             wrapped: project,
             runner: None,
+            monitored: None,
             compiler_options: ironplc_parser::options::CompilerOptions::default(),
             last_changed_uri_key: None,
+            completion_catalog: None,
         }
     }
 
@@ -116,8 +136,10 @@ impl LspProject {
         Self {
             wrapped: project,
             runner: None,
+            monitored: None,
             compiler_options: options,
             last_changed_uri_key: None,
+            completion_catalog: None,
         }
     }
 
@@ -141,6 +163,20 @@ impl LspProject {
         self.wrapped.initialize_many(&refs);
     }
 
+    /// Enables a compiler option in the project, keeping the text of every
+    /// document; the sources parse again with the new options at the next
+    /// analysis. The client persists the option in its settings, which come
+    /// back as initialization options in the next session.
+    ///
+    /// Returns whether the key names an option.
+    pub(crate) fn enable_option(&mut self, option_key: &str) -> bool {
+        if !self.compiler_options.set_flag_by_key(option_key, true) {
+            return false;
+        }
+        self.wrapped.set_compiler_options(self.compiler_options);
+        true
+    }
+
     pub(crate) fn change_text_document(&mut self, uri: &Uri, content: String) {
         let path = to_path_buf(uri);
         if let Ok(path) = path {
@@ -152,6 +188,26 @@ impl LspProject {
         }
     }
 
+    /// Applies the edits of a change notification to the text of a document
+    /// in order. An edit without a range replaces the whole text.
+    pub(crate) fn apply_changes(
+        &mut self,
+        uri: &Uri,
+        changes: Vec<TextDocumentContentChangeEvent>,
+    ) {
+        let Ok(path) = to_path_buf(uri) else {
+            error!("URL must be convertible to a file path {}", uri.as_str());
+            return;
+        };
+        let file_id = FileId::from_path(&path);
+        let text = self
+            .wrapped
+            .find(&file_id)
+            .map(|source| source.as_string().to_string())
+            .unwrap_or_default();
+        self.change_text_document(uri, apply_changes(text, changes));
+    }
+
     pub(crate) fn tokenize(&self, uri: &Uri) -> Result<Vec<SemanticToken>, Vec<Diagnostic>> {
         let path = to_path_buf(uri);
         if let Ok(path) = path {
@@ -167,14 +223,30 @@ impl LspProject {
                     .collect());
             }
 
-            // The conversion produces tokens with absolute (line, col) values
-            // stored in `delta_line` / `delta_start`. The LSP protocol requires
+            // The tokens hold absolute (line, col) values in `delta_line` /
+            // `delta_start`, with the column and length in UTF-16 code units
+            // like every position the server sends. The LSP protocol requires
             // these fields to be encoded as deltas relative to the previous
             // emitted token, so fold over the sequence to convert them.
+            let text = self
+                .wrapped
+                .find(&file_id)
+                .map(|source| source.as_string())
+                .unwrap_or_default();
             let absolute: Vec<SemanticToken> = result
                 .0
                 .into_iter()
-                .filter_map(|tok| LspTokenType(tok).into())
+                .filter_map(|tok| {
+                    let start = lsp_position(text, tok.span.start);
+                    let length = tok.text.encode_utf16().count() as u32;
+                    let token: Option<SemanticToken> = LspTokenType(tok).into();
+                    token.map(|token| SemanticToken {
+                        delta_line: start.line,
+                        delta_start: start.character,
+                        length,
+                        ..token
+                    })
+                })
                 .collect();
 
             return Ok(to_deltas(absolute));
@@ -187,7 +259,9 @@ impl LspProject {
 
     /// Run semantic analysis on the whole workspace and return all
     /// resulting diagnostics grouped by the URI key that should
-    /// display each one.
+    /// display each one. Only the sources that changed since the last
+    /// analysis, and those that depend on them, are analyzed again; see
+    /// `Project::semantic_incremental`.
     ///
     /// Every diagnostic appears under each URI that its primary or
     /// secondary labels reference, so a cross-file diagnostic shows
@@ -200,7 +274,8 @@ impl LspProject {
     /// emitting empty notifications to clear URIs that previously
     /// had diagnostics but no longer do.
     pub(crate) fn semantic_all(&mut self) -> HashMap<UriKey, Vec<lsp_types::Diagnostic>> {
-        let diagnostics = self.wrapped.semantic();
+        let diagnostics = self.wrapped.semantic_incremental();
+        self.update_catalog();
         if diagnostics.is_empty() {
             return HashMap::new();
         }
@@ -237,6 +312,43 @@ impl LspProject {
         by_key
     }
 
+    /// Analyzes the whole workspace when the last analysis did not cover
+    /// the document, so that what is read from the analysis describes it.
+    pub(crate) fn ensure_analyzed(&mut self, uri: &Uri) {
+        let Ok(path) = to_path_buf(uri) else {
+            return;
+        };
+        if !self.wrapped.is_analyzed(&FileId::from_path(&path)) {
+            self.wrapped.semantic();
+            self.update_catalog();
+        }
+    }
+
+    /// Takes the names of the last analysis into the completion catalog.
+    /// An analysis of part of the sources updates the names of that part.
+    fn update_catalog(&mut self) {
+        // An analysis that left out a source that failed to parse is missing
+        // that source's names, so keep the previous catalog instead.
+        let all_parsed = self
+            .wrapped
+            .sources()
+            .iter()
+            .all(|source| source.parsed_library().is_some());
+        let Some(context) = self.wrapped.semantic_context().filter(|_| all_parsed) else {
+            return;
+        };
+        let catalog = CompletionCatalog::from(context);
+        let complete = self
+            .wrapped
+            .sources()
+            .iter()
+            .all(|source| self.wrapped.is_analyzed(source.file_id()));
+        match (&mut self.completion_catalog, complete) {
+            (Some(previous), false) => previous.update(catalog),
+            _ => self.completion_catalog = Some(catalog),
+        }
+    }
+
     /// Run semantic analysis and return only the diagnostics that
     /// should be shown for `uri`. Retained as a thin wrapper over
     /// `semantic_all` for tests and callers that only need a single
@@ -355,13 +467,471 @@ impl LspProject {
         DocumentSymbolResponse::Nested(symbols)
     }
 
+    /// Returns where the symbol at the position is declared.
+    ///
+    /// The declaration may be in another file of the workspace or in an
+    /// activated compatibility library.
+    pub(crate) fn definition(&self, uri: &Uri, position: Position) -> Option<Location> {
+        let index = self.wrapped.symbol_index()?;
+        let symbol = self.symbol_at(index, uri, position)?;
+        let span = index.definition(symbol)?;
+        self.locations([span]).pop()
+    }
+
+    /// Returns where the type of the symbol at the position is declared: the
+    /// function block of an instance, the structure of a variable, the
+    /// enumeration of a value. Elementary types have no declaration.
+    pub(crate) fn type_definition(&self, uri: &Uri, position: Position) -> Option<Location> {
+        let index = self.wrapped.symbol_index()?;
+        let symbol = self.symbol_at(index, uri, position)?;
+        let type_symbol = index.type_definition(symbol)?;
+        let span = index.definition(&type_symbol)?;
+        self.locations([span]).pop()
+    }
+
+    /// Returns the POUs, data types, global variables and tasks of the
+    /// workspace whose names match the query, best match first.
+    pub(crate) fn workspace_symbols(&self, query: &str) -> Vec<WorkspaceSymbol> {
+        let Some(index) = self.wrapped.symbol_index() else {
+            return vec![];
+        };
+        index
+            .search(query)
+            .into_iter()
+            .filter_map(|(symbol, span)| {
+                let location = self.locations([span]).pop()?;
+                Some(lsp_symbol::workspace_symbol(index, symbol, location))
+            })
+            .collect()
+    }
+
+    /// Returns the call hierarchy item for the program, function block,
+    /// function or method at the position.
+    pub(crate) fn prepare_call_hierarchy(
+        &self,
+        uri: &Uri,
+        position: Position,
+    ) -> Option<Vec<CallHierarchyItem>> {
+        let index = self.wrapped.symbol_index()?;
+        let symbol = self
+            .symbol_at(index, uri, position)
+            .filter(|symbol| index.is_callable(symbol))?;
+        let location = self.locations([index.definition(symbol)?]).pop()?;
+        Some(vec![lsp_symbol::call_hierarchy_item(
+            index, symbol, location,
+        )])
+    }
+
+    /// Returns the POUs and methods that call the item, with where each
+    /// calls it from.
+    pub(crate) fn incoming_calls(
+        &self,
+        item: &CallHierarchyItem,
+    ) -> Vec<CallHierarchyIncomingCall> {
+        let Some(index) = self.wrapped.symbol_index() else {
+            return vec![];
+        };
+        let Some(symbol) = self.symbol_at(index, &item.uri, item.selection_range.start) else {
+            return vec![];
+        };
+        index
+            .incoming_calls(symbol)
+            .into_iter()
+            .filter_map(|(caller, spans)| {
+                let location = self.locations([index.definition(caller)?]).pop()?;
+                Some(CallHierarchyIncomingCall {
+                    from: lsp_symbol::call_hierarchy_item(index, caller, location),
+                    from_ranges: self.ranges(spans),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the POUs and methods the item calls, with where it calls
+    /// each of them.
+    pub(crate) fn outgoing_calls(
+        &self,
+        item: &CallHierarchyItem,
+    ) -> Vec<CallHierarchyOutgoingCall> {
+        let Some(index) = self.wrapped.symbol_index() else {
+            return vec![];
+        };
+        let Some(symbol) = self.symbol_at(index, &item.uri, item.selection_range.start) else {
+            return vec![];
+        };
+        index
+            .outgoing_calls(symbol)
+            .into_iter()
+            .filter_map(|(callee, spans)| {
+                let location = self.locations([index.definition(callee)?]).pop()?;
+                Some(CallHierarchyOutgoingCall {
+                    to: lsp_symbol::call_hierarchy_item(index, callee, location),
+                    from_ranges: self.ranges(spans),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns every place the symbol at the position is written, across
+    /// the workspace, optionally leaving out its declaration.
+    pub(crate) fn references(
+        &self,
+        uri: &Uri,
+        position: Position,
+        include_declaration: bool,
+    ) -> Vec<Location> {
+        let Some(index) = self.wrapped.symbol_index() else {
+            return vec![];
+        };
+        let Some(symbol) = self.symbol_at(index, uri, position) else {
+            return vec![];
+        };
+        let spans = index
+            .references(symbol)
+            .into_iter()
+            .filter(|span| include_declaration || !index.is_definition(symbol, span));
+        self.locations(spans)
+    }
+
+    /// Returns the range of the name at the position when it can be
+    /// renamed: the index resolves it and it is declared in a project
+    /// source rather than in a compatibility library.
+    pub(crate) fn prepare_rename(&self, uri: &Uri, position: Position) -> Option<lsp_types::Range> {
+        let (file_id, offset) = self.offset(uri, position)?;
+        let index = self.wrapped.symbol_index()?;
+        let occurrence = index.occurrence_at(&file_id, offset)?;
+        let definition = index.definition(&occurrence.symbol)?;
+        self.wrapped.find(&definition.file_id)?;
+        let text = self.wrapped.find(&file_id)?.as_string();
+        Some(span_to_range(text, &occurrence.span))
+    }
+
+    /// Renames the symbol at the position everywhere it is written. The
+    /// rename is refused, with the reason, when the new name is not an
+    /// identifier in the dialect or already names something visible where
+    /// the symbol is declared or used.
+    pub(crate) fn rename(
+        &self,
+        uri: &Uri,
+        position: Position,
+        new_name: &str,
+    ) -> Result<WorkspaceEdit, String> {
+        if !self.is_identifier(new_name) {
+            return Err(format!("'{new_name}' is not a valid identifier"));
+        }
+        let no_symbol = || "There is no symbol to rename at the position".to_string();
+        let index = self.wrapped.symbol_index().ok_or_else(no_symbol)?;
+        let symbol = self.symbol_at(index, uri, position).ok_or_else(no_symbol)?;
+        let old_name = symbol.name();
+        if index
+            .definition(symbol)
+            .is_none_or(|definition| self.wrapped.find(&definition.file_id).is_none())
+        {
+            return Err(format!(
+                "'{old_name}' is not declared in the workspace and cannot be renamed"
+            ));
+        }
+        if index.rename_conflict(symbol, &Id::from(new_name)).is_some() {
+            return Err(format!(
+                "'{new_name}' is already declared where '{old_name}' is declared or used"
+            ));
+        }
+
+        let mut edits: Vec<TextDocumentEdit> = vec![];
+        let mut documents: HashMap<UriKey, usize> = HashMap::new();
+        let spans = index
+            .references(symbol)
+            .into_iter()
+            .filter(|span| self.wrapped.find(&span.file_id).is_some());
+        for location in self.locations(spans) {
+            let document = *documents
+                .entry(UriKey::from_uri(&location.uri))
+                .or_insert_with(|| {
+                    edits.push(TextDocumentEdit {
+                        text_document: OptionalVersionedTextDocumentIdentifier {
+                            uri: location.uri.clone(),
+                            version: None,
+                        },
+                        edits: vec![],
+                    });
+                    edits.len() - 1
+                });
+            edits[document].edits.push(OneOf::Left(TextEdit::new(
+                location.range,
+                new_name.to_string(),
+            )));
+        }
+        Ok(WorkspaceEdit {
+            document_changes: Some(DocumentChanges::Edits(edits)),
+            ..WorkspaceEdit::default()
+        })
+    }
+
+    /// Describes what is at the position: the declaration of a name, the
+    /// signature of a standard library function, or the type analysis
+    /// resolved for a literal.
+    pub(crate) fn hover(&self, uri: &Uri, position: Position) -> Option<Hover> {
+        let (file_id, offset) = self.offset(uri, position)?;
+        let text = self.wrapped.find(&file_id)?.as_string();
+
+        if let Some(index) = self.wrapped.symbol_index() {
+            if let Some(occurrence) = index.occurrence_at(&file_id, offset) {
+                let definition = index.definition(&occurrence.symbol)?;
+                let tokens = self.file_tokens(&definition.file_id)?;
+                let value = lsp_hover::declaration(&occurrence.symbol, &tokens, definition.start)?;
+                return Some(to_hover(value, span_to_range(text, &occurrence.span)));
+            }
+        }
+
+        let (tokens, _) = self.wrapped.tokenize(&file_id);
+        if let Some(name) = lsp_hover::called_name(&tokens, offset) {
+            if let Some(signature) = stdlib_function(&Id::from(name.text.as_str())) {
+                let value = lsp_hover::function(&signature);
+                return Some(to_hover(value, span_to_range(text, &name.span)));
+            }
+        }
+
+        let literal = literal_at(self.wrapped.analyzed_library()?, &file_id, offset)?;
+        let value = lsp_hover::literal(&literal, text.get(literal.span.start..literal.span.end)?);
+        Some(to_hover(value, span_to_range(text, &literal.span)))
+    }
+
+    /// Returns what can be written at the position. The document may have
+    /// errors: the position is read from its tokens and the names come from
+    /// the last analysis in which everything parsed.
+    pub(crate) fn completion(&self, uri: &Uri, position: Position) -> Vec<CompletionItem> {
+        let Some((file_id, offset)) = self.offset(uri, position) else {
+            return vec![];
+        };
+        let (tokens, _) = self.wrapped.tokenize(&file_id);
+        lsp_completion::complete(
+            &tokens,
+            offset,
+            self.completion_catalog.as_ref(),
+            &self.compiler_options,
+        )
+    }
+
+    /// Describes the call whose arguments the position is in. Like
+    /// completion, this works while the document has errors.
+    pub(crate) fn signature_help(&self, uri: &Uri, position: Position) -> Option<SignatureHelp> {
+        let (file_id, offset) = self.offset(uri, position)?;
+        let (tokens, _) = self.wrapped.tokenize(&file_id);
+        lsp_signature::signature_help(&tokens, offset, self.completion_catalog.as_ref()?)
+    }
+
+    /// Hints for the range of the document: the parameter each positional
+    /// argument binds to, written before the argument as `NAME :=`, and the
+    /// type each untyped literal takes from its context, written before the
+    /// literal as a type prefix such as `INT#`.
+    pub(crate) fn inlay_hints(&self, uri: &Uri, range: lsp_types::Range) -> Vec<InlayHint> {
+        let Some((file_id, start)) = self.offset(uri, range.start) else {
+            return vec![];
+        };
+        let Some((_, end)) = self.offset(uri, range.end) else {
+            return vec![];
+        };
+        let Some(text) = self.wrapped.find(&file_id).map(|source| source.as_string()) else {
+            return vec![];
+        };
+        let position = |offset| lsp_position(text, offset);
+
+        let mut hints = vec![];
+        if let Some(catalog) = self.completion_catalog.as_ref() {
+            let (tokens, _) = self.wrapped.tokenize(&file_id);
+            hints.extend(
+                lsp_signature::parameter_hints(&tokens, start..end, catalog)
+                    .into_iter()
+                    .map(|(offset, name)| InlayHint {
+                        position: position(offset),
+                        label: InlayHintLabel::String(format!("{name} :=")),
+                        kind: Some(InlayHintKind::PARAMETER),
+                        text_edits: None,
+                        tooltip: None,
+                        padding_left: None,
+                        padding_right: Some(true),
+                        data: None,
+                    }),
+            );
+        }
+        if let (Some(library), Some(context)) = (
+            self.wrapped.analyzed_library(),
+            self.wrapped.semantic_context(),
+        ) {
+            hints.extend(
+                untyped_literals(library, context, &file_id)
+                    .into_iter()
+                    .filter(|literal| start <= literal.span.start && literal.span.start < end)
+                    .map(|literal| InlayHint {
+                        position: position(literal.span.start),
+                        label: InlayHintLabel::String(format!("{}#", literal.type_name)),
+                        kind: Some(InlayHintKind::TYPE),
+                        text_edits: None,
+                        tooltip: None,
+                        padding_left: None,
+                        padding_right: None,
+                        data: None,
+                    }),
+            );
+        }
+        // A literal passed positionally has both hints: the parameter name
+        // goes first.
+        hints.sort_by_key(|hint| (hint.position, hint.kind != Some(InlayHintKind::PARAMETER)));
+        hints
+    }
+
+    /// Formats the document, or only the lines the range touches, with the
+    /// editor's indentation. Returns no edits when the document does not
+    /// parse or is already formatted.
+    pub(crate) fn format(
+        &self,
+        uri: &Uri,
+        options: &FormattingOptions,
+        range: Option<lsp_types::Range>,
+    ) -> Vec<TextEdit> {
+        let Some(file_id) = to_path_buf(uri).ok().map(|path| FileId::from_path(&path)) else {
+            return vec![];
+        };
+        let Some(source) = self.wrapped.find(&file_id) else {
+            return vec![];
+        };
+        let text = source.as_string();
+        let format_options = FormatOptions {
+            indent: if options.insert_spaces {
+                Indent::Spaces(options.tab_size as usize)
+            } else {
+                Indent::Tab
+            },
+            ..FormatOptions::default()
+        };
+        let Ok(formatted) =
+            formatter::format(text, &file_id, &self.compiler_options, &format_options)
+        else {
+            return vec![];
+        };
+
+        let whole = span_to_range(text, &SourceSpan::range(0, text.len()));
+        let (replaced, new_text) = match range {
+            None => (whole, formatted.text()),
+            Some(range) => {
+                // A selection that ends at the start of a line does not
+                // include that line.
+                let last = if range.end.character == 0 && range.end.line > range.start.line {
+                    range.end.line - 1
+                } else {
+                    range.end.line
+                };
+                let (lines, new_text) = formatted.lines(range.start.line as usize, last as usize);
+                let start = Position::new(lines.start as u32, 0);
+                let end = if lines.end as u32 > whole.end.line {
+                    whole.end
+                } else {
+                    Position::new(lines.end as u32, 0)
+                };
+                (lsp_types::Range::new(start, end), new_text)
+            }
+        };
+
+        let offset = |position: Position| position_offset(text, position);
+        let old_text = text
+            .get(offset(replaced.start)..offset(replaced.end))
+            .unwrap_or_default();
+        if old_text == new_text {
+            return vec![];
+        }
+        vec![TextEdit::new(replaced, new_text)]
+    }
+
+    /// Whether the text is a single identifier under the compiler options,
+    /// so neither a keyword of the dialect nor anything that is not a name.
+    fn is_identifier(&self, text: &str) -> bool {
+        let (tokens, diagnostics) = ironplc_parser::tokenize_program(
+            text,
+            &FileId::default(),
+            &self.compiler_options,
+            0,
+            0,
+        );
+        diagnostics.is_empty()
+            && matches!(tokens.as_slice(), [token] if token.token_type == TokenType::Identifier && token.text == text)
+    }
+
+    fn symbol_at<'a>(
+        &self,
+        index: &'a SymbolIndex,
+        uri: &Uri,
+        position: Position,
+    ) -> Option<&'a SymbolId> {
+        let (file_id, offset) = self.offset(uri, position)?;
+        index.symbol_at(&file_id, offset)
+    }
+
+    /// The file and byte offset of a position in a project document.
+    fn offset(&self, uri: &Uri, position: Position) -> Option<(FileId, usize)> {
+        let path = to_path_buf(uri).ok()?;
+        let file_id = FileId::from_path(&path);
+        let source = self.wrapped.find(&file_id)?;
+        let offset = position_offset(source.as_string(), position);
+        Some((file_id, offset))
+    }
+
+    /// Tokens of a file, which is either a project source or an activated
+    /// compatibility library read from disk.
+    fn file_tokens(&self, file_id: &FileId) -> Option<Vec<Token>> {
+        if self.wrapped.find(file_id).is_some() {
+            return Some(self.wrapped.tokenize(file_id).0);
+        }
+        let text = std::fs::read_to_string(file_id.to_string()).ok()?;
+        Some(ironplc_parser::tokenize_program(&text, file_id, &self.compiler_options, 0, 0).0)
+    }
+
+    /// Converts spans in one file to ranges.
+    fn ranges(&self, spans: Vec<&SourceSpan>) -> Vec<lsp_types::Range> {
+        self.locations(spans)
+            .into_iter()
+            .map(|location| location.range)
+            .collect()
+    }
+
+    /// Converts spans to locations. A compatibility library is not a
+    /// project source, so its text is read from disk, once per file.
+    fn locations<'a>(&self, spans: impl IntoIterator<Item = &'a SourceSpan>) -> Vec<Location> {
+        let mut library_texts: HashMap<FileId, Option<String>> = HashMap::new();
+        spans
+            .into_iter()
+            .filter_map(|span| {
+                let uri = UriKey::from_file_id(&span.file_id)?.to_uri();
+                let range = match self.wrapped.find(&span.file_id) {
+                    Some(source) => span_to_range(source.as_string(), span),
+                    None => {
+                        let text = library_texts
+                            .entry(span.file_id.clone())
+                            .or_insert_with(|| {
+                                std::fs::read_to_string(span.file_id.to_string()).ok()
+                            })
+                            .as_deref()?;
+                        span_to_range(text, span)
+                    }
+                };
+                Some(Location::new(uri, range))
+            })
+            .collect()
+    }
+
     /// Compile source and start a VM execution session.
     ///
     /// Returns initial metadata. Subsequent calls to `step()` execute
-    /// scan cycles and return variable values.
-    pub(crate) fn run_load(&mut self, source: &str, cycle_time_us: u64) -> RunResult {
+    /// scan cycles and return variable values. When the session monitors a
+    /// document, each step also yields the values placed in that document.
+    pub(crate) fn run_load(
+        &mut self,
+        source: &str,
+        cycle_time_us: u64,
+        monitored: Option<Uri>,
+    ) -> RunResult {
         // Tear down any existing session
         self.runner = None;
+        self.monitored = monitored;
 
         match VmRunner::load(source, cycle_time_us, &self.compiler_options) {
             Ok((runner, result)) => {
@@ -385,9 +955,33 @@ impl LspProject {
         }
     }
 
+    /// The values of a step placed on the names in the monitored document,
+    /// using the symbol index of the last analysis, or `None` when the
+    /// session monitors no document of the project or nothing was analyzed.
+    pub(crate) fn monitor(&self, result: &RunResult) -> Option<MonitorParams> {
+        let runner = self.runner.as_ref()?;
+        let uri = self.monitored.as_ref()?;
+        let file_id = FileId::from_path(&to_path_buf(uri).ok()?);
+        let text = self.wrapped.find(&file_id)?.as_string();
+        let index = self.wrapped.symbol_index()?;
+        let values = lsp_monitor::values(
+            index,
+            &file_id,
+            &result.variables,
+            |var_index| runner.owner(var_index),
+            |span| span_to_range(text, span),
+        );
+        Some(MonitorParams {
+            uri: uri.clone(),
+            total_scans: result.total_scans,
+            values,
+        })
+    }
+
     /// Stop the current execution session and release resources.
     pub(crate) fn run_stop(&mut self) -> RunResult {
         self.runner = None;
+        self.monitored = None;
         RunResult {
             ok: true,
             variables: vec![],
@@ -397,6 +991,60 @@ impl LspProject {
     }
 }
 
+/// Applies change events to the text.
+fn apply_changes(mut text: String, changes: Vec<TextDocumentContentChangeEvent>) -> String {
+    for change in changes {
+        match change.range {
+            Some(range) => {
+                let start = position_offset(&text, range.start);
+                let end = position_offset(&text, range.end).max(start);
+                text.replace_range(start..end, &change.text);
+            }
+            None => text = change.text,
+        }
+    }
+    text
+}
+
+/// The byte offset of a position. A position past the end of its line is at
+/// the end of the line.
+///
+/// The server counts columns in UTF-16 code units, the encoding the protocol
+/// uses unless the client and server agree on another, for every position it
+/// receives and sends.
+pub(crate) fn position_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(newline) => line_start += newline + 1,
+            None => return text.len(),
+        }
+    }
+    let line = &text[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    let mut units = 0;
+    for (offset, c) in line.char_indices() {
+        if units >= position.character as usize {
+            return line_start + offset;
+        }
+        units += c.len_utf16();
+    }
+    line_start + line.len()
+}
+
+/// The position of a byte offset, with its column in UTF-16 code units like
+/// [`position_offset`]. An offset past the end of `text` is at the end.
+pub(crate) fn lsp_position(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let line_start = text[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+    let line = text[..line_start].matches('\n').count() as u32;
+    let character = text[line_start..offset]
+        .chars()
+        .map(char::len_utf16)
+        .sum::<usize>() as u32;
+    Position::new(line, character)
+}
+
 /// Map the analyzer's neutral `TypeSymbolKind` to LSP's `SymbolKind`.
 fn type_kind_to_symbol_kind(kind: TypeSymbolKind) -> SymbolKind {
     match kind {
@@ -412,13 +1060,21 @@ fn type_kind_to_symbol_kind(kind: TypeSymbolKind) -> SymbolKind {
     }
 }
 
+fn to_hover(markdown: String, range: lsp_types::Range) -> Hover {
+    Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: markdown,
+        }),
+        range: Some(range),
+    }
+}
+
 /// Convert a SourceSpan to an LSP Range using file contents for line/column calculation.
-fn span_to_range(contents: &str, span: &ironplc_dsl::core::SourceSpan) -> lsp_types::Range {
-    let start = LineColumn::from_offset(contents, span.start);
-    let end = LineColumn::from_offset(contents, span.end);
+fn span_to_range(contents: &str, span: &SourceSpan) -> lsp_types::Range {
     lsp_types::Range::new(
-        lsp_types::Position::new(start.line, start.column),
-        lsp_types::Position::new(end.line, end.column),
+        lsp_position(contents, span.start),
+        lsp_position(contents, span.end),
     )
 }
 
@@ -710,16 +1366,30 @@ fn map_diagnostic(
         message.push_str(&format!("\n{note}"));
     }
 
+    // The quick fixes travel with the diagnostic so that a code action
+    // request answers from the text the diagnostic was computed for.
+    let actions = lsp_code_action::actions(&diagnostic.fixes, &diagnostic.primary.file_id, project);
+    let data = if actions.is_empty() {
+        None
+    } else {
+        serde_json::to_value(actions).ok()
+    };
+
+    let severity = match diagnostic.severity {
+        ironplc_dsl::diagnostic::Severity::Error => DiagnosticSeverity::ERROR,
+        ironplc_dsl::diagnostic::Severity::Warning => DiagnosticSeverity::WARNING,
+    };
+
     lsp_types::Diagnostic {
         range,
-        severity: Some(DiagnosticSeverity::ERROR),
+        severity: Some(severity),
         code: Some(NumberOrString::String(diagnostic.code)),
         code_description,
         source: Some("ironplc".into()),
         message,
         related_information,
         tags: None,
-        data: None,
+        data,
     }
 }
 
@@ -730,11 +1400,9 @@ fn map_label(label: &ironplc_dsl::diagnostic::Label, project: &dyn Project) -> l
 
     if let Some(contents) = contents {
         let contents = contents.as_string();
-        let start = LineColumn::from_offset(contents, label.location.start);
-        let end = LineColumn::from_offset(contents, label.location.end);
         return lsp_types::Range::new(
-            lsp_types::Position::new(start.line, start.column),
-            lsp_types::Position::new(end.line, end.column),
+            lsp_position(contents, label.location.start),
+            lsp_position(contents, label.location.end),
         );
     }
     lsp_types::Range::new(
@@ -753,11 +1421,14 @@ mod test {
     use ironplc_parser::token::{Token, TokenType};
     use ironplc_test::cast;
     use ironplc_test::read_shared_resource;
-    use lsp_types::{SemanticToken, Uri};
+    use lsp_types::{
+        DocumentChanges, Position, SemanticToken, TextDocumentContentChangeEvent, Uri,
+        WorkspaceEdit,
+    };
 
     use ironplc_project::FileBackedProject;
 
-    use super::{LspProject, LspTokenType};
+    use super::{apply_changes, LspProject, LspTokenType};
 
     #[cfg(target_os = "macos")]
     static FAKE_PATH: &str = "file:///localhost/first_steps.st";
@@ -841,6 +1512,60 @@ mod test {
         );
     }
 
+    fn change(
+        range: Option<((u32, u32), (u32, u32))>,
+        text: &str,
+    ) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: range.map(|((l1, c1), (l2, c2))| {
+                lsp_types::Range::new(Position::new(l1, c1), Position::new(l2, c2))
+            }),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn apply_changes_when_several_ranges_then_applies_each_to_result_of_last() {
+        let text = "PROGRAM Main\n  x := 1;\nEND_PROGRAM".to_string();
+
+        let changed = apply_changes(
+            text,
+            vec![
+                change(Some(((1, 7), (1, 8))), "2"),
+                change(Some(((1, 2), (1, 3))), "y"),
+            ],
+        );
+
+        assert_eq!(changed, "PROGRAM Main\n  y := 2;\nEND_PROGRAM");
+    }
+
+    #[test]
+    fn apply_changes_when_range_spans_lines_then_joins_lines() {
+        let text = "PROGRAM Main\n  x := 1;\n  y := 2;\nEND_PROGRAM".to_string();
+
+        let changed = apply_changes(text, vec![change(Some(((1, 9), (2, 9))), "")]);
+
+        assert_eq!(changed, "PROGRAM Main\n  x := 1;\nEND_PROGRAM");
+    }
+
+    #[test]
+    fn apply_changes_when_no_range_then_replaces_text() {
+        let changed = apply_changes("old".to_string(), vec![change(None, "new")]);
+
+        assert_eq!(changed, "new");
+    }
+
+    #[test]
+    fn apply_changes_when_character_outside_basic_plane_then_counts_utf16_units() {
+        // The emoji is two UTF-16 code units, so `x` is at column 9.
+        let text = "(* \u{1F600} *) x := 1;".to_string();
+
+        let changed = apply_changes(text, vec![change(Some(((0, 9), (0, 10))), "y")]);
+
+        assert_eq!(changed, "(* \u{1F600} *) y := 1;");
+    }
+
     #[test]
     fn tokenize_when_no_document_then_error() {
         let proj = new_empty_project();
@@ -1282,6 +2007,27 @@ INVALID_SYNTAX"
         assert!(lsp_diag.related_information.is_none());
     }
 
+    #[test]
+    fn map_diagnostic_when_warning_then_warning_severity() {
+        use ironplc_dsl::diagnostic::{
+            Diagnostic as DslDiagnostic, Label as DslLabel, Severity as DslSeverity,
+        };
+
+        let proj = new_empty_project();
+        let mut diag = DslDiagnostic::problem(
+            ironplc_problems::Problem::BitStringCaseLabelNotAllowed,
+            DslLabel::span(SourceSpan::default(), "bit-string label"),
+        );
+        diag.severity = DslSeverity::Warning;
+
+        let lsp_diag = super::map_diagnostic(diag, proj.wrapped.as_ref());
+
+        assert_eq!(
+            lsp_diag.severity,
+            Some(lsp_types::DiagnosticSeverity::WARNING)
+        );
+    }
+
     #[test]
     fn map_diagnostic_when_problem_then_url_has_version_only() {
         use ironplc_dsl::core::FileId;
@@ -1489,6 +2235,36 @@ INVALID_SYNTAX"
         assert_eq!(range.end.character, 8);
     }
 
+    #[test]
+    fn span_to_range_when_character_outside_basic_plane_then_counts_utf16_units() {
+        use super::span_to_range;
+
+        // The emoji is four bytes and two UTF-16 code units, so `x` starts
+        // at byte 11 and column 9.
+        let contents = "(* \u{1F600} *) x := 1;";
+        let span = SourceSpan {
+            start: 11,
+            end: 12,
+            file_id: ironplc_dsl::core::FileId::default(),
+        };
+
+        let range = span_to_range(contents, &span);
+
+        assert_eq!(range.start, Position::new(0, 9));
+        assert_eq!(range.end, Position::new(0, 10));
+    }
+
+    #[test]
+    fn position_offset_when_lsp_position_then_same_offset() {
+        use super::{lsp_position, position_offset};
+
+        let contents = "(* \u{1F600} *)\nx := \u{E9};";
+        for offset in [0, 3, 7, 11, 12, 16, 18, 19] {
+            let position = lsp_position(contents, offset);
+            assert_eq!(position_offset(contents, position), offset);
+        }
+    }
+
     #[test]
     fn document_symbols_when_semantic_errors_then_returns_symbols() {
         let mut proj = new_empty_project();
@@ -1569,11 +2345,430 @@ INVALID_SYNTAX"
         );
     }
 
+    // -----------------------------------------------------------------
+    // Navigation: definition, type definition, references.
+    // See specs/plans/2026-10-18-lsp-navigation.md.
+    // -----------------------------------------------------------------
+
+    const NAVIGATION_FB: &str = "TYPE
+    Mode : (Idle, Busy);
+END_TYPE
+FUNCTION_BLOCK Counter
+VAR_INPUT
+    Delta : INT;
+END_VAR
+VAR_OUTPUT
+    Total : INT;
+    State : Mode;
+END_VAR
+    Total := Total + Delta;
+    State := Busy;
+END_FUNCTION_BLOCK";
+
+    const NAVIGATION_PROGRAM: &str = "PROGRAM Main
+VAR
+    Tally : Counter;
+    Seen : INT;
+END_VAR
+    Tally(Delta := 1);
+    Seen := Tally.Total;
+END_PROGRAM";
+
+    /// A project with the function block in one file and the program that
+    /// uses it in another, analyzed so the parsed libraries are cached.
+    fn navigation_project() -> (LspProject, Uri, Uri) {
+        let mut proj = new_empty_project();
+        let url_fb = Uri::from_str(FAKE_PATH).unwrap();
+        let url_main = Uri::from_str(FAKE_PATH_2).unwrap();
+        proj.change_text_document(&url_fb, NAVIGATION_FB.to_owned());
+        proj.change_text_document(&url_main, NAVIGATION_PROGRAM.to_owned());
+        let _ = proj.semantic_all();
+        (proj, url_fb, url_main)
+    }
+
+    fn position(line: u32, character: u32) -> lsp_types::Position {
+        lsp_types::Position::new(line, character)
+    }
+
+    #[test]
+    fn definition_when_fb_type_in_other_file_then_location_in_that_file() {
+        let (proj, url_fb, url_main) = navigation_project();
+
+        let location = proj.definition(&url_main, position(2, 14)).unwrap();
+
+        assert_eq!(location.uri, url_fb);
+        assert_eq!(location.range.start, position(3, 15));
+        assert_eq!(location.range.end, position(3, 22));
+    }
+
+    #[test]
+    fn definition_when_fb_member_access_then_output_declaration() {
+        let (proj, url_fb, url_main) = navigation_project();
+
+        let location = proj.definition(&url_main, position(6, 20)).unwrap();
+
+        assert_eq!(location.uri, url_fb);
+        assert_eq!(location.range.start, position(8, 4));
+    }
+
+    #[test]
+    fn definition_when_named_argument_then_input_declaration() {
+        let (proj, url_fb, url_main) = navigation_project();
+
+        let location = proj.definition(&url_main, position(5, 11)).unwrap();
+
+        assert_eq!(location.uri, url_fb);
+        assert_eq!(location.range.start, position(5, 4));
+    }
+
+    #[test]
+    fn definition_when_enum_value_then_value_in_type_declaration() {
+        let (proj, url_fb, _) = navigation_project();
+
+        let location = proj.definition(&url_fb, position(12, 14)).unwrap();
+
+        assert_eq!(location.range.start, position(1, 18));
+    }
+
+    #[test]
+    fn definition_when_elementary_type_then_none() {
+        let (proj, _, url_main) = navigation_project();
+
+        assert!(proj.definition(&url_main, position(3, 11)).is_none());
+    }
+
+    #[test]
+    fn definition_when_not_analyzed_then_none() {
+        let mut proj = new_empty_project();
+        let url = Uri::from_str(FAKE_PATH).unwrap();
+        proj.change_text_document(&url, NAVIGATION_FB.to_owned());
+
+        assert!(proj.definition(&url, position(11, 6)).is_none());
+    }
+
+    #[test]
+    fn type_definition_when_fb_instance_then_function_block() {
+        let (proj, url_fb, url_main) = navigation_project();
+
+        let location = proj.type_definition(&url_main, position(6, 14)).unwrap();
+
+        assert_eq!(location.uri, url_fb);
+        assert_eq!(location.range.start, position(3, 15));
+    }
+
+    #[test]
+    fn type_definition_when_enum_variable_then_enumeration() {
+        let (proj, url_fb, _) = navigation_project();
+
+        let location = proj.type_definition(&url_fb, position(12, 6)).unwrap();
+
+        assert_eq!(location.range.start, position(1, 4));
+    }
+
+    #[test]
+    fn references_when_fb_output_then_locations_in_both_files() {
+        let (proj, url_fb, url_main) = navigation_project();
+
+        let locations = proj.references(&url_fb, position(8, 4), true);
+
+        let in_fb = locations.iter().filter(|l| l.uri == url_fb).count();
+        let in_main = locations.iter().filter(|l| l.uri == url_main).count();
+        assert_eq!(in_fb, 3);
+        assert_eq!(in_main, 1);
+    }
+
+    #[test]
+    fn references_when_exclude_declaration_then_only_uses() {
+        let (proj, url_fb, _) = navigation_project();
+
+        let locations = proj.references(&url_fb, position(5, 4), false);
+
+        assert_eq!(locations.len(), 2);
+        assert!(locations.iter().all(|l| l.range.start != position(5, 4)));
+    }
+
+    const MONITORED_PROGRAM: &str = "PROGRAM main
+VAR
+    x : DINT;
+END_VAR
+    x := x + 2;
+END_PROGRAM";
+
+    #[test]
+    fn monitor_when_document_monitored_then_values_on_names() {
+        let mut proj = new_empty_project();
+        let url = Uri::from_str(FAKE_PATH).unwrap();
+        proj.change_text_document(&url, MONITORED_PROGRAM.to_owned());
+        let _ = proj.semantic_all();
+        proj.run_load(MONITORED_PROGRAM, 100_000, Some(url.clone()));
+
+        let result = proj.run_step(1);
+        let params = proj.monitor(&result).unwrap();
+
+        assert_eq!(params.uri, url);
+        assert_eq!(params.total_scans, 1);
+        assert_eq!(params.values.len(), 1);
+        assert_eq!(params.values[0].value, "2");
+        let starts: Vec<lsp_types::Position> = params.values[0]
+            .ranges
+            .iter()
+            .map(|range| range.start)
+            .collect();
+        assert_eq!(starts, vec![position(2, 4), position(4, 4), position(4, 9)]);
+    }
+
+    #[test]
+    fn monitor_when_no_document_monitored_then_none() {
+        let mut proj = new_empty_project();
+        proj.run_load(MONITORED_PROGRAM, 100_000, None);
+
+        let result = proj.run_step(1);
+
+        assert!(proj.monitor(&result).is_none());
+    }
+
+    #[test]
+    fn workspace_symbols_when_query_then_symbols_across_files() {
+        let (proj, url_fb, url_main) = navigation_project();
+
+        let symbols = proj.workspace_symbols("m");
+        let found: Vec<(&str, &Uri)> = symbols
+            .iter()
+            .map(|symbol| match &symbol.location {
+                lsp_types::OneOf::Left(location) => (symbol.name.as_str(), &location.uri),
+                lsp_types::OneOf::Right(_) => panic!("expected a location"),
+            })
+            .collect();
+
+        assert_eq!(found, vec![("Main", &url_main), ("Mode", &url_fb)]);
+    }
+
+    #[test]
+    fn prepare_call_hierarchy_when_variable_then_none() {
+        let (proj, _, url_main) = navigation_project();
+
+        assert!(proj
+            .prepare_call_hierarchy(&url_main, position(3, 4))
+            .is_none());
+    }
+
+    #[test]
+    fn incoming_calls_when_fb_invoked_in_other_file_then_calling_program() {
+        let (proj, url_fb, url_main) = navigation_project();
+        let item = proj
+            .prepare_call_hierarchy(&url_fb, position(3, 15))
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        let calls = proj.incoming_calls(&item);
+
+        assert_eq!(item.kind, lsp_types::SymbolKind::CLASS);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].from.name, "Main");
+        assert_eq!(calls[0].from.uri, url_main);
+        assert_eq!(
+            calls[0].from_ranges,
+            vec![lsp_types::Range::new(position(5, 4), position(5, 9))]
+        );
+    }
+
+    fn edit_count(edit: &WorkspaceEdit) -> Vec<usize> {
+        match &edit.document_changes {
+            Some(DocumentChanges::Edits(documents)) => documents
+                .iter()
+                .map(|document| document.edits.len())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn prepare_rename_when_variable_then_range_of_name() {
+        let (proj, url_fb, _) = navigation_project();
+
+        let range = proj.prepare_rename(&url_fb, position(5, 6)).unwrap();
+
+        assert_eq!(range.start, position(5, 4));
+        assert_eq!(range.end, position(5, 9));
+    }
+
+    #[test]
+    fn prepare_rename_when_elementary_type_then_none() {
+        let (proj, url_fb, _) = navigation_project();
+
+        assert!(proj.prepare_rename(&url_fb, position(5, 13)).is_none());
+    }
+
+    #[test]
+    fn rename_when_fb_input_then_edits_both_files_including_named_argument() {
+        let (proj, url_fb, _) = navigation_project();
+
+        let edit = proj.rename(&url_fb, position(5, 4), "Amount").unwrap();
+
+        let mut counts = edit_count(&edit);
+        counts.sort();
+        assert_eq!(counts, vec![1, 2]);
+    }
+
+    #[test]
+    fn rename_when_new_name_is_keyword_then_refused() {
+        let (proj, url_fb, _) = navigation_project();
+
+        let result = proj.rename(&url_fb, position(5, 4), "END_IF");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rename_when_new_name_is_not_identifier_then_refused() {
+        let (proj, url_fb, _) = navigation_project();
+
+        let result = proj.rename(&url_fb, position(5, 4), "New Name");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rename_when_new_name_collides_with_member_then_refused() {
+        let (proj, url_fb, _) = navigation_project();
+
+        let result = proj.rename(&url_fb, position(5, 4), "Total");
+
+        assert!(result.unwrap_err().contains("Total"));
+    }
+
+    #[test]
+    fn definition_when_compat_library_global_then_location_in_library_file() {
+        use ironplc_parser::options::CompilerOptions;
+        use ironplc_project::FileBackedProject;
+        use ironplc_sources::LibraryName;
+
+        let mut project = FileBackedProject::with_options(CompilerOptions {
+            allow_top_level_var_global: true,
+            allow_constant_initializer_expressions: true,
+            ..CompilerOptions::default()
+        });
+        project.set_activated_libraries(vec![LibraryName::from("Tc2_System")]);
+        let mut proj = LspProject::new(Box::new(project));
+        let url = Uri::from_str(FAKE_PATH).unwrap();
+        proj.change_text_document(
+            &url,
+            "FUNCTION_BLOCK FB_Angle\nVAR\n    d2r : LREAL := PI / 180.0;\nEND_VAR\nEND_FUNCTION_BLOCK"
+                .to_owned(),
+        );
+        let _ = proj.semantic_all();
+
+        let location = proj.definition(&url, position(2, 20)).unwrap();
+
+        assert_ne!(location.uri, url);
+        assert!(location.uri.as_str().ends_with(".st"));
+    }
+
     #[cfg(target_os = "windows")]
     static FAKE_PATH_2: &str = "file:///C:/second_steps.st";
     #[cfg(not(target_os = "windows"))]
     static FAKE_PATH_2: &str = "file:///localhost/second_steps.st";
 
+    // -----------------------------------------------------------------
+    // Hover. See specs/plans/2026-10-18-lsp-hover.md.
+    // -----------------------------------------------------------------
+
+    fn hover_value(hover: &lsp_types::Hover) -> &str {
+        match &hover.contents {
+            lsp_types::HoverContents::Markup(markup) => &markup.value,
+            _ => panic!("expected markdown hover"),
+        }
+    }
+
+    #[test]
+    fn hover_when_fb_member_access_then_declaration_in_other_file() {
+        let (proj, _, url_main) = navigation_project();
+
+        let hover = proj.hover(&url_main, position(6, 20)).unwrap();
+
+        assert!(hover_value(&hover).contains("VAR_OUTPUT Total : INT"));
+        assert_eq!(hover.range.unwrap().start, position(6, 18));
+    }
+
+    #[test]
+    fn hover_when_enum_value_then_qualified_value() {
+        let (proj, url_fb, _) = navigation_project();
+
+        let hover = proj.hover(&url_fb, position(12, 14)).unwrap();
+
+        assert!(hover_value(&hover).contains("Mode#Busy"));
+    }
+
+    #[test]
+    fn hover_when_keyword_then_none() {
+        let (proj, _, url_main) = navigation_project();
+
+        assert!(proj.hover(&url_main, position(0, 2)).is_none());
+    }
+
+    const HOVER_PROGRAM: &str = "PROGRAM Main
+VAR
+    Seen : INT;
+END_VAR
+    Seen := LIMIT(0, Seen, 10);
+END_PROGRAM";
+
+    fn hover_project() -> (LspProject, Uri) {
+        let mut proj = new_empty_project();
+        let url = Uri::from_str(FAKE_PATH).unwrap();
+        proj.change_text_document(&url, HOVER_PROGRAM.to_owned());
+        let _ = proj.semantic_all();
+        (proj, url)
+    }
+
+    #[test]
+    fn hover_when_standard_function_then_signature() {
+        let (proj, url) = hover_project();
+
+        let hover = proj.hover(&url, position(4, 14)).unwrap();
+
+        assert!(hover_value(&hover).contains("LIMIT(MN : "));
+        assert_eq!(hover.range.unwrap().start, position(4, 12));
+    }
+
+    #[test]
+    fn hover_when_literal_then_resolved_type() {
+        let (proj, url) = hover_project();
+
+        let hover = proj.hover(&url, position(4, 28)).unwrap();
+
+        assert!(hover_value(&hover).contains("10 : "));
+        assert_eq!(hover.range.unwrap().start, position(4, 27));
+    }
+
+    // -----------------------------------------------------------------
+    // Completion. See specs/plans/2026-10-18-lsp-completion.md.
+    // -----------------------------------------------------------------
+
+    #[test]
+    fn completion_when_document_stops_parsing_then_keeps_names_from_last_analysis() {
+        let (mut proj, _, url_main) = navigation_project();
+        proj.change_text_document(
+            &url_main,
+            NAVIGATION_PROGRAM.replace("    Seen := Tally.Total;", "    Seen := Tally."),
+        );
+        let _ = proj.semantic_all();
+
+        let items = proj.completion(&url_main, position(6, 18));
+
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["Delta", "Total", "State"]);
+    }
+
+    #[test]
+    fn completion_when_no_document_then_empty() {
+        let proj = new_empty_project();
+        let url = Uri::from_str(FAKE_PATH).unwrap();
+
+        assert!(proj.completion(&url, position(0, 0)).is_empty());
+    }
+
     #[test]
     fn semantic_all_when_no_errors_then_returns_empty_map() {
         let mut proj = new_empty_project();
@@ -1638,6 +2833,14 @@ INVALID_SYNTAX"
             vec![]
         }
 
+        fn set_compiler_options(
+            &mut self,
+            _compiler_options: ironplc_parser::options::CompilerOptions,
+        ) {
+        }
+
+        fn set_demotions(&mut self, _demotions: ironplc_dsl::diagnostic::Demotions) {}
+
         fn change_text_document(&mut self, _file_id: &FileId, _content: String) {}
 
         fn tokenize(&self, _file_id: &FileId) -> (Vec<Token>, Vec<Diagnostic>) {
@@ -1662,6 +2865,10 @@ INVALID_SYNTAX"
             None
         }
 
+        fn parsed_libraries(&self) -> Vec<&ironplc_dsl::common::Library> {
+            vec![]
+        }
+
         fn sources(&self) -> Vec<&ironplc_sources::Source> {
             vec![]
         }
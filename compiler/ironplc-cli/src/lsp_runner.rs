@@ -11,7 +11,7 @@ use std::io::Cursor;
 use ironplc_analyzer::stages::analyze;
 use ironplc_codegen::compile as codegen_compile;
 use ironplc_container::debug_format::{build_var_debug_map, format_variable_value, VarDebugInfo};
-use ironplc_container::Container;
+use ironplc_container::{Container, FunctionId};
 use ironplc_dsl::core::FileId;
 use ironplc_parser::options::CompilerOptions;
 use ironplc_sources::{parse_source, FileType};
@@ -51,6 +51,8 @@ pub struct VmRunner {
     scan_count: u64,
     cycle_time_us: u64,
     faulted: bool,
+    /// The POU that declares each variable, by variable index.
+    owners: HashMap<u16, String>,
 }
 
 impl VmRunner {
@@ -84,11 +86,15 @@ impl VmRunner {
                     ok: false,
                     variables: vec![],
                     total_scans: 0,
-                    error: Some(format!("VM init trap: {}", ctx.trap)),
+                    error: Some(format!(
+                        "VM init trap: {}",
+                        ctx.trap.with_debug(container.debug_section.as_ref())
+                    )),
                 });
             }
         }
 
+        let owners = build_owner_map(&container);
         let runner = VmRunner {
             container_bytes,
             var_buf: bufs.vars,
@@ -96,6 +102,7 @@ impl VmRunner {
             scan_count: 0,
             cycle_time_us,
             faulted: false,
+            owners,
         };
 
         let result = RunResult {
@@ -159,6 +166,40 @@ impl VmRunner {
 
         result
     }
+
+    /// The name of the POU that declares the variable at the index. A
+    /// program's variables and the global variables share the global scope
+    /// of the debug section, so both report the program.
+    pub fn owner(&self, index: u16) -> Option<&str> {
+        self.owners.get(&index).map(String::as_str)
+    }
+}
+
+/// Maps each variable index in the debug section to the name of the
+/// function that owns it; the global scope belongs to the scan function.
+fn build_owner_map(container: &Container) -> HashMap<u16, String> {
+    let Some(debug) = &container.debug_section else {
+        return HashMap::new();
+    };
+    let function_name = |function_id: FunctionId| {
+        debug
+            .func_names
+            .iter()
+            .find(|entry| entry.function_id == function_id)
+            .map(|entry| entry.name.clone())
+    };
+    debug
+        .var_names
+        .iter()
+        .filter_map(|entry| {
+            let function_id = if entry.function_id == FunctionId::GLOBAL_SCOPE {
+                FunctionId::SCAN
+            } else {
+                entry.function_id
+            };
+            Some((entry.var_index.raw(), function_name(function_id)?))
+        })
+        .collect()
 }
 
 /// Runs scan cycles on an already-prepared [`VmBuffers`], returning a
@@ -185,7 +226,7 @@ fn run_step_scans(
                 total_scans,
                 error: Some(format!(
                     "VM trap: {} (task {}, instance {})",
-                    faulted.trap(),
+                    faulted.trap().with_debug(container.debug_section.as_ref()),
                     faulted.task_id(),
                     faulted.instance_id()
                 )),
@@ -249,6 +290,7 @@ fn compile_to_bytes(source: &str, options: &CompilerOptions) -> Result<Vec<u8>,
 
     let codegen_options = ironplc_codegen::CodegenOptions {
         system_uptime_global: options.allow_system_uptime_global,
+        ..ironplc_codegen::CodegenOptions::default()
     };
     let container = codegen_compile(
         &library,
@@ -392,4 +434,13 @@ END_PROGRAM
         assert!(result.ok);
         assert_eq!(result.total_scans, 8);
     }
+
+    #[test]
+    fn owner_when_program_variable_then_program_name() {
+        let options = CompilerOptions::default();
+        let (mut runner, _) = VmRunner::load(SIMPLE_PROGRAM, 100_000, &options).unwrap();
+        let result = runner.step(1);
+
+        assert_eq!(runner.owner(result.variables[0].index), Some("main"));
+    }
 }
@@ -0,0 +1,356 @@
+//! Builds signature help and parameter name hints for calls in the language
+//! server.
+//!
+//! Both read the calls from the tokens of the document, which exist even
+//! while the call being typed does not parse, and look up what a call takes
+//! in the catalog of the last complete analysis. A positional argument binds
+//! to the inputs of the signature in the order that analysis uses when it
+//! rewrites named arguments to positional ones.
+use ironplc_analyzer::completion_catalog::CompletionCatalog;
+use ironplc_analyzer::FunctionSignature;
+use ironplc_dsl::core::Id;
+use ironplc_parser::token::{Token, TokenType};
+use lsp_types::{ParameterInformation, ParameterLabel, SignatureHelp, SignatureInformation};
+
+use crate::lsp_completion::enclosing_pou;
+use crate::lsp_hover::signature_label_with_offsets;
+
+/// The signature of the call the byte offset is in the arguments of, with
+/// the parameter the argument at the offset binds to as the active one.
+pub(crate) fn signature_help(
+    tokens: &[Token],
+    offset: usize,
+    catalog: &CompletionCatalog,
+) -> Option<SignatureHelp> {
+    let open = open_paren(tokens, offset)?;
+    let signature = called_signature(tokens, open, catalog)?;
+    let arguments = arguments(tokens, open);
+    let current = arguments
+        .iter()
+        .rposition(|argument| {
+            tokens
+                .get(argument.start)
+                .is_some_and(|tok| tok.span.start <= offset)
+        })
+        .map_or(0, |index| {
+            // The cursor may be past the comma that ends the argument.
+            let after_comma = arguments[index]
+                .end
+                .is_some_and(|end| tokens[end].span.end <= offset);
+            index + usize::from(after_comma)
+        });
+    let active = match arguments.get(current).and_then(|arg| arg.name.as_ref()) {
+        Some(name) => signature
+            .parameters
+            .iter()
+            .position(|param| param.name == *name),
+        None => positional_parameter(&signature, current),
+    };
+
+    let (label, offsets) = signature_label_with_offsets(&signature);
+    let parameters = offsets
+        .into_iter()
+        .map(|offsets| ParameterInformation {
+            label: ParameterLabel::LabelOffsets(offsets),
+            documentation: None,
+        })
+        .collect();
+    let active_parameter = active.map(|index| index as u32);
+    Some(SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation: None,
+            parameters: Some(parameters),
+            active_parameter,
+        }],
+        active_signature: Some(0),
+        active_parameter,
+    })
+}
+
+/// The name of the parameter that each positional argument of a call in the
+/// byte range binds to, with the offset where the argument starts. An
+/// argument that is a variable with the parameter's name has no hint.
+pub(crate) fn parameter_hints(
+    tokens: &[Token],
+    range: std::ops::Range<usize>,
+    catalog: &CompletionCatalog,
+) -> Vec<(usize, Id)> {
+    let mut hints = vec![];
+    for (open, tok) in tokens.iter().enumerate() {
+        if tok.token_type != TokenType::LeftParen || !range.contains(&tok.span.start) {
+            continue;
+        }
+        let Some(signature) = called_signature(tokens, open, catalog) else {
+            continue;
+        };
+        // The parameters of an extensible function past the declared ones
+        // have no names.
+        if signature.is_extensible {
+            continue;
+        }
+        for (position, argument) in arguments(tokens, open).iter().enumerate() {
+            if argument.name.is_some() {
+                continue;
+            }
+            let Some(param) = signature.input_parameters().nth(position) else {
+                break;
+            };
+            let code: Vec<&Token> = argument.code(tokens).collect();
+            let same_name = matches!(code.as_slice(), [only]
+                if only.token_type == TokenType::Identifier && Id::from(only.text.as_str()) == param.name);
+            if let (Some(first), false) = (code.first(), same_name) {
+                hints.push((first.span.start, param.name.clone()));
+            }
+        }
+    }
+    hints
+}
+
+/// An argument of a call: the index of its first token, the index of the
+/// comma or parenthesis that ends it when there is one, and the parameter
+/// it names when it is written `NAME :=` or `NAME =>`.
+struct Argument {
+    start: usize,
+    end: Option<usize>,
+    name: Option<Id>,
+}
+
+impl Argument {
+    /// The tokens of the argument other than whitespace and comments.
+    fn code<'a>(&self, tokens: &'a [Token]) -> impl Iterator<Item = &'a Token> {
+        let end = self.end.unwrap_or(tokens.len());
+        tokens[self.start..end].iter().filter(|tok| !is_trivia(tok))
+    }
+}
+
+/// The arguments of the call whose opening parenthesis is at the index,
+/// up to its closing parenthesis or the end of the statement.
+fn arguments(tokens: &[Token], open: usize) -> Vec<Argument> {
+    let mut arguments = vec![];
+    let mut start = open + 1;
+    let mut depth = 0usize;
+    let mut index = open + 1;
+    while let Some(tok) = tokens.get(index) {
+        match tok.token_type {
+            TokenType::LeftParen | TokenType::LeftBracket => depth += 1,
+            TokenType::RightParen | TokenType::RightBracket if depth > 0 => depth -= 1,
+            TokenType::Comma if depth == 0 => {
+                arguments.push(argument(tokens, start, Some(index)));
+                start = index + 1;
+            }
+            TokenType::RightParen => {
+                if tokens[start..index].iter().any(|tok| !is_trivia(tok)) {
+                    arguments.push(argument(tokens, start, Some(index)));
+                }
+                return arguments;
+            }
+            TokenType::Semicolon => break,
+            _ => {}
+        }
+        index += 1;
+    }
+    arguments.push(argument(tokens, start, None));
+    arguments
+}
+
+fn argument(tokens: &[Token], start: usize, end: Option<usize>) -> Argument {
+    let end_index = end.unwrap_or(tokens.len());
+    let code: Vec<&Token> = tokens[start..end_index]
+        .iter()
+        .filter(|tok| !is_trivia(tok))
+        .collect();
+    let names = |name: &Token, assign: &Token| {
+        name.token_type == TokenType::Identifier
+            && matches!(
+                assign.token_type,
+                TokenType::Assignment | TokenType::RightArrow
+            )
+    };
+    let name = match code.as_slice() {
+        [name, assign, ..] if names(name, assign) => Some(Id::from(name.text.as_str())),
+        // An output can be negated: `NOT NAME => variable`.
+        [not, name, assign, ..] if not.token_type == TokenType::Not && names(name, assign) => {
+            Some(Id::from(name.text.as_str()))
+        }
+        _ => None,
+    };
+    Argument { start, end, name }
+}
+
+/// The index of the parenthesis that opens the call whose arguments the
+/// byte offset is in.
+fn open_paren(tokens: &[Token], offset: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for index in (0..tokens.len()).rev() {
+        let tok = &tokens[index];
+        if tok.span.end > offset {
+            continue;
+        }
+        match tok.token_type {
+            TokenType::RightParen => depth += 1,
+            TokenType::LeftParen if depth == 0 => return Some(index),
+            TokenType::LeftParen => depth -= 1,
+            TokenType::Semicolon => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The signature of the function or function block instance named just
+/// before the opening parenthesis.
+fn called_signature(
+    tokens: &[Token],
+    open: usize,
+    catalog: &CompletionCatalog,
+) -> Option<FunctionSignature> {
+    let name = tokens[..open].iter().rev().find(|tok| !is_trivia(tok))?;
+    if name.token_type != TokenType::Identifier {
+        return None;
+    }
+    let pou = enclosing_pou(tokens, name.span.start);
+    catalog.call_signature(
+        &Id::from(name.text.as_str()),
+        pou.as_ref().map(|pou| &pou.name),
+    )
+}
+
+/// The index in the signature of the parameter that the positional
+/// argument binds to. Arguments past the declared inputs of an extensible
+/// function bind to its last input.
+fn positional_parameter(signature: &FunctionSignature, position: usize) -> Option<usize> {
+    let inputs: Vec<usize> = signature
+        .parameters
+        .iter()
+        .enumerate()
+        .filter(|(_, param)| param.is_input_compatible())
+        .map(|(index, _)| index)
+        .collect();
+    match inputs.get(position) {
+        Some(index) => Some(*index),
+        None if signature.is_extensible => inputs.last().copied(),
+        None => None,
+    }
+}
+
+fn is_trivia(tok: &Token) -> bool {
+    matches!(
+        tok.token_type,
+        TokenType::Whitespace | TokenType::Newline | TokenType::Comment | TokenType::Pragma
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironplc_dsl::core::FileId;
+    use ironplc_parser::options::CompilerOptions;
+
+    const PROGRAM: &str = "FUNCTION_BLOCK Counter
+VAR_INPUT
+    Up : BOOL;
+    Delta : INT;
+END_VAR
+VAR_OUTPUT
+    Total : DINT;
+END_VAR
+END_FUNCTION_BLOCK
+PROGRAM Main
+VAR
+    Tally : Counter;
+    Level : INT;
+END_VAR
+    Tally(Up := TRUE, Delta := 1);
+END_PROGRAM";
+
+    fn catalog() -> CompletionCatalog {
+        let library =
+            ironplc_parser::parse_program(PROGRAM, &FileId::default(), &CompilerOptions::default())
+                .unwrap();
+        let (_library, context) =
+            ironplc_analyzer::stages::analyze(&[&library], &CompilerOptions::default()).unwrap();
+        CompletionCatalog::from(&context)
+    }
+
+    fn tokens(text: &str) -> Vec<Token> {
+        ironplc_parser::tokenize_program(
+            text,
+            &FileId::default(),
+            &CompilerOptions::default(),
+            0,
+            0,
+        )
+        .0
+    }
+
+    fn in_main(body: &str) -> String {
+        PROGRAM.replace("    Tally(Up := TRUE, Delta := 1);\n", body)
+    }
+
+    fn active_parameter(text: &str, at: &str) -> Option<String> {
+        let help = signature_help(&tokens(text), text.find(at).unwrap(), &catalog())?;
+        let signature = &help.signatures[0];
+        let index = signature.active_parameter? as usize;
+        let ParameterLabel::LabelOffsets([start, end]) =
+            signature.parameters.as_ref().unwrap()[index].label
+        else {
+            panic!("expected label offsets");
+        };
+        Some(signature.label[start as usize..end as usize].to_string())
+    }
+
+    #[test]
+    fn signature_help_when_positional_argument_then_matching_input_active() {
+        let text = in_main("    Level := LIMIT(0, Level, \n");
+
+        assert_eq!(
+            active_parameter(&text, ", \n"),
+            Some("IN : ANY_NUM".to_string())
+        );
+        assert_eq!(
+            active_parameter(&text, " \n"),
+            Some("MX : ANY_NUM".to_string())
+        );
+    }
+
+    #[test]
+    fn signature_help_when_named_argument_then_named_input_active() {
+        let text = in_main("    Tally(Delta := 2, Up := \n");
+
+        assert_eq!(
+            active_parameter(&text, " \n"),
+            Some("Up : BOOL".to_string())
+        );
+    }
+
+    #[test]
+    fn signature_help_when_outside_call_then_none() {
+        let text = in_main("    Level := 1;\n");
+
+        assert!(signature_help(&tokens(&text), text.find(":= 1").unwrap(), &catalog()).is_none());
+    }
+
+    #[test]
+    fn parameter_hints_when_positional_arguments_then_name_of_each_input() {
+        let text = in_main("    Level := LIMIT(0, Level, 100);\n    Tally(TRUE, 2);\n");
+        let tokens = tokens(&text);
+        let body = text.rfind("END_VAR").unwrap();
+
+        let hints: Vec<(String, String)> = parameter_hints(&tokens, body..text.len(), &catalog())
+            .into_iter()
+            .map(|(offset, name)| (text[offset..offset + 1].to_string(), name.to_string()))
+            .collect();
+
+        assert_eq!(
+            hints,
+            vec![
+                ("0".to_string(), "MN".to_string()),
+                ("L".to_string(), "IN".to_string()),
+                ("1".to_string(), "MX".to_string()),
+                ("T".to_string(), "Up".to_string()),
+                ("2".to_string(), "Delta".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Describes the symbols of the index to the editor, as workspace symbols
+//! and call hierarchy items: the kind of symbol and the name of what
+//! contains it.
+
+use ironplc_analyzer::symbol_index::{PouKind, Scope, SymbolId, SymbolIndex};
+use lsp_types::{CallHierarchyItem, Location, OneOf, SymbolKind, WorkspaceSymbol};
+
+/// The kind an editor shows for the symbol. A POU has the kind its document
+/// symbol has.
+pub(crate) fn kind(index: &SymbolIndex, symbol: &SymbolId) -> SymbolKind {
+    match symbol {
+        SymbolId::Pou(name) => match index.pou_kind(name) {
+            Some(PouKind::FunctionBlock) => SymbolKind::CLASS,
+            Some(PouKind::Function) => SymbolKind::FUNCTION,
+            Some(PouKind::Interface) => SymbolKind::INTERFACE,
+            Some(PouKind::Program) | None => SymbolKind::MODULE,
+        },
+        SymbolId::Type(_) => SymbolKind::STRUCT,
+        SymbolId::Variable(Scope::Global, _) => SymbolKind::VARIABLE,
+        SymbolId::Variable(..) => SymbolKind::FIELD,
+        SymbolId::EnumValue(..) => SymbolKind::ENUM_MEMBER,
+        SymbolId::Field(..) => SymbolKind::FIELD,
+        SymbolId::Method(..) => SymbolKind::METHOD,
+        SymbolId::Task(_) => SymbolKind::EVENT,
+    }
+}
+
+/// The name of the function block that declares a method, or the structure
+/// or enumeration that declares a field or value.
+pub(crate) fn container(symbol: &SymbolId) -> Option<String> {
+    match symbol {
+        SymbolId::Method(owner, _) | SymbolId::Field(owner, _) | SymbolId::EnumValue(owner, _) => {
+            Some(owner.original().clone())
+        }
+        SymbolId::Variable(Scope::Pou(pou), _) => Some(pou.original().clone()),
+        _ => None,
+    }
+}
+
+/// The workspace symbol for a symbol declared at the location.
+pub(crate) fn workspace_symbol(
+    index: &SymbolIndex,
+    symbol: &SymbolId,
+    location: Location,
+) -> WorkspaceSymbol {
+    WorkspaceSymbol {
+        name: symbol.name().original().clone(),
+        kind: kind(index, symbol),
+        tags: None,
+        container_name: container(symbol),
+        location: OneOf::Left(location),
+        data: None,
+    }
+}
+
+/// The call hierarchy item for a symbol declared at the location. The item
+/// spans the declared name, where the symbol is found again when the editor
+/// asks for its calls.
+pub(crate) fn call_hierarchy_item(
+    index: &SymbolIndex,
+    symbol: &SymbolId,
+    location: Location,
+) -> CallHierarchyItem {
+    CallHierarchyItem {
+        name: symbol.name().original().clone(),
+        kind: kind(index, symbol),
+        tags: None,
+        detail: container(symbol),
+        uri: location.uri,
+        range: location.range,
+        selection_range: location.range,
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironplc_dsl::core::{FileId, Id};
+    use ironplc_parser::options::CompilerOptions;
+
+    const SOURCE: &str = "
+FUNCTION_BLOCK Motor
+VAR
+    Running : BOOL;
+END_VAR
+METHOD Start
+    Running := TRUE;
+END_METHOD
+END_FUNCTION_BLOCK
+FUNCTION Twice : INT
+    Twice := 2;
+END_FUNCTION
+PROGRAM Main
+END_PROGRAM";
+
+    fn index() -> SymbolIndex {
+        let options = CompilerOptions {
+            allow_fb_inheritance: true,
+            ..Default::default()
+        };
+        let library = ironplc_parser::parse_program(SOURCE, &FileId::default(), &options).unwrap();
+        SymbolIndex::build(&[&library])
+    }
+
+    #[test]
+    fn kind_when_pous_then_matches_document_symbols() {
+        let index = index();
+
+        assert_eq!(
+            kind(&index, &SymbolId::Pou(Id::from("Motor"))),
+            SymbolKind::CLASS
+        );
+        assert_eq!(
+            kind(&index, &SymbolId::Pou(Id::from("Twice"))),
+            SymbolKind::FUNCTION
+        );
+        assert_eq!(
+            kind(&index, &SymbolId::Pou(Id::from("Main"))),
+            SymbolKind::MODULE
+        );
+    }
+
+    #[test]
+    fn container_when_method_then_function_block() {
+        let start = SymbolId::Method(Id::from("Motor"), Id::from("Start"));
+
+        assert_eq!(container(&start), Some("Motor".to_string()));
+        assert_eq!(container(&SymbolId::Pou(Id::from("Main"))), None);
+    }
+}
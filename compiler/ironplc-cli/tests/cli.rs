@@ -190,6 +190,109 @@ fn compile_when_short_output_flag_then_creates_output() -> Result<(), Box<dyn st
     Ok(())
 }
 
+#[test]
+fn compile_when_elide_range_checks_flag_then_creates_output(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = NamedTempFile::new()?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("compile")
+        .arg(shared_resource_path("steel_thread.st"))
+        .arg("--elide-range-checks")
+        .arg("--output")
+        .arg(output.path());
+    cmd.assert().success().stdout(predicate::str::is_empty());
+
+    assert!(output.path().metadata()?.len() > 0);
+
+    Ok(())
+}
+
+#[test]
+fn compile_when_report_flag_then_prints_wcet_estimate() -> Result<(), Box<dyn std::error::Error>> {
+    let output = NamedTempFile::new()?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("compile")
+        .arg(shared_resource_path("steel_thread.st"))
+        .arg("--report")
+        .arg("--output")
+        .arg(output.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Worst-case execution time"))
+        .stdout(predicate::str::contains("task 0 (main): "));
+
+    assert!(output.path().metadata()?.len() > 0);
+
+    Ok(())
+}
+
+#[test]
+fn compile_when_report_flag_and_configuration_then_names_task(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = NamedTempFile::new()?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("compile")
+        .arg(shared_resource_path("task_report.st"))
+        .arg("--report")
+        .arg("--output")
+        .arg(output.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("task fast (main): "));
+
+    Ok(())
+}
+
+#[test]
+fn compile_when_report_and_target_flags_then_prints_usage_against_limits(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = NamedTempFile::new()?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("compile")
+        .arg(shared_resource_path("steel_thread.st"))
+        .arg("--report")
+        .arg("--target")
+        .arg("embedded-small")
+        .arg("--output")
+        .arg(output.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Resource usage (target embedded-small):",
+        ))
+        .stdout(predicate::str::contains("  tasks: 1 of 2 tasks"));
+
+    Ok(())
+}
+
+#[test]
+fn compile_when_program_exceeds_target_then_err() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(
+        &source,
+        "PROGRAM Main\nVAR\n    samples : ARRAY[1..4096] OF DINT;\nEND_VAR\n    samples[1] := 0;\nEND_PROGRAM\n",
+    )?;
+    let output = temp.path().join("main.iplc");
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("compile")
+        .arg(&source)
+        .arg("--target")
+        .arg("embedded-small")
+        .arg("--output")
+        .arg(&output);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("P4053").and(predicate::str::contains("'samples'")));
+
+    Ok(())
+}
+
 #[test]
 fn compile_when_output_is_input_then_fails_without_modifying_source(
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -532,3 +635,360 @@ fn version_then_ok() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn fmt_when_unformatted_then_rewrites_file() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(
+        &source,
+        "PROGRAM Main\nVAR\nx : INT; (* kept *)\nEND_VAR\nx := 1;\nEND_PROGRAM\n",
+    )?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("fmt").arg(&source);
+    cmd.assert().success();
+
+    assert_eq!(
+        std::fs::read_to_string(&source)?,
+        "PROGRAM Main\nVAR\n    x : INT; (* kept *)\nEND_VAR\n    x := 1;\nEND_PROGRAM\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn fmt_when_check_and_unformatted_then_fails_without_modifying_file(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    let original = "PROGRAM Main\nVAR\nx : INT;\nEND_VAR\nEND_PROGRAM\n";
+    std::fs::write(&source, original)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("fmt").arg("--check").arg(&source);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("P6013"));
+
+    assert_eq!(std::fs::read_to_string(&source)?, original);
+
+    Ok(())
+}
+
+const BIT_STRING_CASE_LABEL: &str = "PROGRAM Main
+VAR
+    x : DINT;
+    y : INT;
+END_VAR
+CASE x OF
+    16#D012: y := 1;
+END_CASE;
+END_PROGRAM
+";
+
+#[test]
+fn check_when_demote_code_then_warning_and_ok() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(&source, BIT_STRING_CASE_LABEL)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check").arg("--demote").arg("P4041").arg(&source);
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("warning[P4041]"));
+
+    Ok(())
+}
+
+#[test]
+fn check_when_code_not_demoted_then_err() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(&source, BIT_STRING_CASE_LABEL)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check").arg(&source);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("error[P4041]"));
+
+    Ok(())
+}
+
+#[test]
+fn check_when_demote_safety_relevant_code_then_err() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check")
+        .arg("--demote")
+        .arg("P4035")
+        .arg(shared_resource_path("first_steps.st"));
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("P6016"))
+        .stderr(predicate::str::contains(
+            "P4035 is safety-relevant and cannot be demoted to a warning",
+        ))
+        .stderr(predicate::str::contains(":1:1").not());
+
+    Ok(())
+}
+
+#[test]
+fn check_when_settings_file_demotes_code_then_warning_and_ok(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    std::fs::write(temp.path().join("main.st"), BIT_STRING_CASE_LABEL)?;
+    std::fs::write(
+        temp.path().join("ironplc.toml"),
+        "[diagnostics]\ndemote = [\"P4041\"]\n",
+    )?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check").arg(temp.path());
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("warning[P4041]"));
+
+    Ok(())
+}
+
+const NAMING_SETTINGS: &str = "
+[naming]
+function_block = { prefix = \"FB_\" }
+
+[naming.types]
+BOOL = { prefix = \"b\" }
+";
+
+const NAMING_VIOLATION: &str = "
+FUNCTION_BLOCK Motor
+VAR_INPUT
+    run : BOOL;
+END_VAR
+END_FUNCTION_BLOCK
+";
+
+#[test]
+fn check_when_settings_file_naming_rules_broken_then_err() -> Result<(), Box<dyn std::error::Error>>
+{
+    let temp = tempfile::tempdir()?;
+    std::fs::write(temp.path().join("main.st"), NAMING_VIOLATION)?;
+    std::fs::write(temp.path().join("ironplc.toml"), NAMING_SETTINGS)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check").arg(temp.path());
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("error[P4057]"))
+        .stderr(predicate::str::contains("Expected prefix 'FB_'"))
+        .stderr(predicate::str::contains("Expected prefix 'b'"));
+
+    Ok(())
+}
+
+#[test]
+fn check_when_naming_code_demoted_then_warning_and_ok() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    std::fs::write(temp.path().join("main.st"), NAMING_VIOLATION)?;
+    std::fs::write(temp.path().join("ironplc.toml"), NAMING_SETTINGS)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check")
+        .arg("--demote")
+        .arg("P4057")
+        .arg(temp.path());
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("warning[P4057]"));
+
+    Ok(())
+}
+
+#[test]
+fn compile_when_demote_code_then_creates_output() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    let output = temp.path().join("main.iplc");
+    std::fs::write(&source, BIT_STRING_CASE_LABEL)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("compile")
+        .arg("--demote")
+        .arg("P4041")
+        .arg(&source)
+        .arg("-o")
+        .arg(&output);
+    cmd.assert().success();
+
+    assert!(output.exists());
+
+    Ok(())
+}
+
+#[test]
+fn fmt_when_check_and_demote_then_warning_and_ok() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(
+        &source,
+        "PROGRAM Main\nVAR\nx : INT;\nEND_VAR\nEND_PROGRAM\n",
+    )?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("fmt")
+        .arg("--check")
+        .arg("--demote")
+        .arg("P6013")
+        .arg(&source);
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("warning[P6013]"));
+
+    Ok(())
+}
+
+const TASK_WRITE_CONFLICT: &str = "PROGRAM fast
+VAR_EXTERNAL
+    setpoint : INT;
+END_VAR
+    setpoint := 10;
+END_PROGRAM
+
+PROGRAM slow
+VAR_EXTERNAL
+    setpoint : INT;
+END_VAR
+    setpoint := 20;
+END_PROGRAM
+
+CONFIGURATION config
+    VAR_GLOBAL
+        setpoint : INT;
+    END_VAR
+    RESOURCE resource1 ON PLC
+        TASK fast_task(INTERVAL := T#10ms, PRIORITY := 1);
+        TASK slow_task(INTERVAL := T#100ms, PRIORITY := 5);
+        PROGRAM fast_instance WITH fast_task : fast;
+        PROGRAM slow_instance WITH slow_task : slow;
+    END_RESOURCE
+END_CONFIGURATION
+";
+
+#[test]
+fn check_when_task_write_conflict_then_ok() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(&source, TASK_WRITE_CONFLICT)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check").arg(&source);
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn check_when_check_task_races_and_write_conflict_then_err(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(&source, TASK_WRITE_CONFLICT)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check").arg("--check-task-races").arg(&source);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("error[P4049]"))
+        .stderr(predicate::str::contains("fast_task"))
+        .stderr(predicate::str::contains("slow_task"));
+
+    Ok(())
+}
+
+const CASE_MISSING_ENUM_VALUE: &str = "TYPE
+    MODE : (IDLE, RUNNING, FAULT);
+END_TYPE
+
+FUNCTION_BLOCK FB_Example
+VAR
+    m : MODE;
+    y : INT;
+END_VAR
+CASE m OF
+    IDLE: y := 0;
+    RUNNING: y := 1;
+END_CASE;
+END_FUNCTION_BLOCK
+";
+
+#[test]
+fn check_when_case_missing_enum_value_then_ok() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(&source, CASE_MISSING_ENUM_VALUE)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check").arg(&source);
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn check_when_check_case_exhaustive_and_missing_enum_value_then_err(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(&source, CASE_MISSING_ENUM_VALUE)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check").arg("--check-case-exhaustive").arg(&source);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("error[P4051]"))
+        .stderr(predicate::str::contains("FAULT"));
+
+    Ok(())
+}
+
+const UNUSED_VARIABLE: &str = "
+PROGRAM main
+VAR
+    count : INT;
+    spare : INT;
+END_VAR
+count := count + 1;
+END_PROGRAM
+";
+
+#[test]
+fn check_when_unused_variable_then_ok() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(&source, UNUSED_VARIABLE)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check").arg(&source);
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn check_when_unused_flag_and_unused_variable_then_err() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let source = temp.path().join("main.st");
+    std::fs::write(&source, UNUSED_VARIABLE)?;
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcc"));
+
+    cmd.arg("check").arg("--unused").arg(&source);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("error[P4054]"))
+        .stderr(predicate::str::contains("spare"));
+
+    Ok(())
+}
@@ -150,6 +150,8 @@ pub struct TaskMeta {
 #[derive(Clone, Debug)]
 pub struct ProgramMeta {
     pub name: String,
+    /// Name of the program type the instance runs.
+    pub type_name: String,
     pub task: Option<String>,
 }
 
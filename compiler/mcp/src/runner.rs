@@ -21,8 +21,9 @@ use std::collections::HashMap;
 
 use ironplc_analyzer::symbol_environment::ScopeKind;
 use ironplc_analyzer::SemanticContext;
+use ironplc_container::debug_format::parse_variable_value;
 use ironplc_container::debug_section::{iec_type_tag, DebugSection, VarNameEntry};
-use ironplc_container::Container;
+use ironplc_container::{Container, VarIndex};
 use ironplc_vm::{Vm, VmBuffers};
 use serde_json::Value;
 
@@ -152,6 +153,21 @@ pub fn value_from_raw(raw: u64, tag: u8) -> Value {
     }
 }
 
+/// Converts a JSON value to the raw 64-bit VM slot for a variable of type
+/// `tag`: the inverse of [`value_from_raw`] for the types a force can set. A
+/// string is read as an IEC 61131-3 literal, so `"T#250ms"` and `"16#FF"`
+/// work as well as `true` and `42`.
+pub fn raw_from_value(value: &Value, tag: u8) -> Result<u64, String> {
+    let text = match value {
+        Value::Bool(true) => "TRUE".to_string(),
+        Value::Bool(false) => "FALSE".to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => return Err("expected a boolean, a number or a string".to_string()),
+    };
+    parse_variable_value(&text, tag).map_err(|e| e.to_string())
+}
+
 fn finite_or_special(v: f64) -> Value {
     if v.is_nan() {
         Value::String("NaN".into())
@@ -213,11 +229,12 @@ impl TerminatedReason {
 }
 
 /// Executes a cached container for `trace_set` variables for `duration_ms` of
-/// simulated time, under `limits`.
+/// simulated time, under `limits`, with each `(variable, raw)` of `forces`
+/// held in the VM's force table for the whole run.
 ///
 /// This is the core of the `run` tool. Control flow:
 /// 1. Deserialize bytes → `Container`.
-/// 2. Build VM buffers, load, start.
+/// 2. Build VM buffers, load, start, and force `forces`.
 /// 3. Round loop: check limits, step one round, snapshot any task that
 ///    advanced its `scan_count`, append to trace.
 /// 4. On trap: capture diagnostic, drain final values from `VmFaulted`.
@@ -230,6 +247,7 @@ impl TerminatedReason {
 pub fn execute(
     cached: &CachedContainer,
     trace_set: &[ResolvedVar],
+    forces: &[(VarIndex, u64)],
     duration_ms: u64,
     limits: EffectiveLimits,
 ) -> Result<RunOutcome, String> {
@@ -251,11 +269,20 @@ pub fn execute(
                 completed_cycles: vec![],
                 terminated_reason: TerminatedReason::Error,
                 truncated: false,
-                error_message: Some(format!("VM start fault: {}", ctx.trap)),
+                error_message: Some(format!(
+                    "VM start fault: {}",
+                    ctx.trap.with_debug(container.debug_section.as_ref())
+                )),
             });
         }
     };
 
+    for &(var_index, raw) in forces {
+        running
+            .force_variable(var_index, raw)
+            .map_err(|trap| format!("force error: {trap}"))?;
+    }
+
     let wall_start = std::time::Instant::now();
     let mut simulated_us: u64 = 0;
     let mut trace: Vec<TraceSample> = Vec::new();
@@ -317,7 +344,10 @@ pub fn execute(
         let before_total = running.scan_count();
 
         if let Err(ctx) = running.run_round(current_us) {
-            let trap_msg = ctx.trap.to_string();
+            let trap_msg = ctx
+                .trap
+                .with_debug(container.debug_section.as_ref())
+                .to_string();
             let faulted = running.fault(ctx);
             let final_values =
                 read_final_values(trace_set, |idx| faulted.read_variable_raw(idx).ok());
@@ -422,6 +452,31 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn raw_from_value_when_bool_then_one_or_zero() {
+        assert_eq!(
+            raw_from_value(&Value::Bool(true), iec_type_tag::BOOL),
+            Ok(1)
+        );
+        assert_eq!(
+            raw_from_value(&Value::Bool(false), iec_type_tag::BOOL),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn raw_from_value_when_iec_literal_string_then_parsed_for_type() {
+        assert_eq!(
+            raw_from_value(&Value::from("16#FF"), iec_type_tag::WORD),
+            Ok(0xFF)
+        );
+    }
+
+    #[test]
+    fn raw_from_value_when_number_out_of_range_then_error() {
+        assert!(raw_from_value(&Value::from(300), iec_type_tag::USINT).is_err());
+    }
+
     #[test]
     fn value_from_raw_when_bool_true_then_json_true() {
         assert_eq!(value_from_raw(1, iec_type_tag::BOOL), Value::Bool(true));
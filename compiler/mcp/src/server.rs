@@ -17,6 +17,7 @@ use crate::tools::pou_lineage::PouLineageInput;
 use crate::tools::pou_scope::PouScopeInput;
 use crate::tools::run::RunInput;
 use crate::tools::symbols::SymbolsInput;
+use crate::tools::wcet::WcetInput;
 
 #[derive(Clone)]
 pub struct IronPlcMcp {
@@ -224,10 +225,25 @@ impl IronPlcMcp {
         Ok(ContentBlock::text(json))
     }
 
+    /// Estimates the worst-case execution time of each task in a compiled container.
+    #[tool(
+        name = "wcet",
+        description = "Static worst-case execution time estimate per task of a compiled container, next to each task's INTERVAL and watchdog, plus every loop with no iteration bound. The estimate is an upper bound from a cost table, not a measurement. Bound a WHILE or REPEAT loop with a `{attribute 'loop_bound' := 'N'}` pragma before the statement when the dialect allows pragmas."
+    )]
+    async fn wcet(
+        &self,
+        Parameters(input): Parameters<WcetInput>,
+    ) -> Result<ContentBlock, rmcp::ErrorData> {
+        let response = tools::wcet::build_response(&input.container_id, &self.cache);
+        let json = serde_json::to_string(&response)
+            .map_err(|e| rmcp::ErrorData::internal_error(e.to_string(), None))?;
+        Ok(ContentBlock::text(json))
+    }
+
     /// Executes a compiled container in the IronPLC VM.
     #[tool(
         name = "run",
-        description = "Executes a compiled container in the IronPLC VM for a bounded simulated duration and returns a trace of observed variables. Call `check` until it returns `ok:true`, then `compile` to obtain a `container_id`, then `run`. In this milestone the tool supports `every_cycle` trace mode without stimuli; supply `variables` (fully-qualified names) or `trace_outputs:true` to capture outputs."
+        description = "Executes a compiled container in the IronPLC VM for a bounded simulated duration and returns a trace of observed variables. Call `check` until it returns `ok:true`, then `compile` to obtain a `container_id`, then `run`. In this milestone the tool supports `every_cycle` trace mode without stimuli; supply `variables` (fully-qualified names) or `trace_outputs:true` to capture outputs. Use `forces` to hold variables (by name or located address such as `%IX0.0`) at a value the program cannot override."
     )]
     async fn run(
         &self,
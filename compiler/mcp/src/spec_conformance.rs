@@ -403,6 +403,35 @@ fn mcp_spec_req_tol_026_accepts_flag_overrides() {
     assert!(resp.ok, "flag override should enable C-style comments");
 }
 
+/// REQ-TOL-mcp-027: The `check` tool reports the codes listed in
+/// `options.demote` as warnings and refuses a code that cannot be demoted.
+#[spec_test(REQ_TOL_mcp_027)]
+fn mcp_spec_req_tol_027_demote_reports_warning() {
+    use crate::tools::common::SourceInput;
+
+    let sources = vec![SourceInput {
+        name: "main.st".into(),
+        content: "PROGRAM p VAR x : DINT; y : INT; END_VAR CASE x OF 16#D012: y := 1; END_CASE; END_PROGRAM"
+            .into(),
+    }];
+
+    let resp = tools::check::build_response(
+        &sources,
+        &serde_json::json!({"dialect": "iec61131-3-ed2", "demote": ["P4041"]}),
+    );
+    assert!(resp.ok, "a demoted code must not fail the check");
+    assert_eq!(resp.diagnostics.len(), 1);
+    assert_eq!(resp.diagnostics[0]["code"], "P4041");
+    assert_eq!(resp.diagnostics[0]["severity"], "warning");
+
+    let resp = tools::check::build_response(
+        &sources,
+        &serde_json::json!({"dialect": "iec61131-3-ed2", "demote": ["P4035"]}),
+    );
+    assert!(!resp.ok);
+    assert_eq!(resp.diagnostics[0]["code"], "P6016");
+}
+
 // ===========================================================================
 // `compile` tool (REQ-TOL-mcp-030..036)
 // ===========================================================================
@@ -620,6 +649,40 @@ fn mcp_spec_req_tol_047_resource_limits() {}
 #[ignore]
 fn mcp_spec_req_tol_048_run_returns_summary() {}
 
+/// REQ-TOL-mcp-049: a forced variable holds its value for the whole run
+/// although the program increments it every cycle, and the summary names it.
+#[spec_test(REQ_TOL_mcp_049)]
+fn mcp_spec_req_tol_049_run_holds_forced_variables() {
+    use std::sync::Mutex;
+
+    use crate::cache::ContainerCache;
+    use crate::tools::common::SourceInput;
+
+    let cache = Mutex::new(ContainerCache::new(64, 64 * 1024 * 1024));
+    let sources = vec![SourceInput {
+        name: "main.st".into(),
+        content:
+            "PROGRAM Main\nVAR\n  Counter : INT;\nEND_VAR\n  Counter := Counter + 1;\nEND_PROGRAM"
+                .into(),
+    }];
+    let options = serde_json::json!({"dialect": "iec61131-3-ed2"});
+    let compiled = tools::compile::build_response(&sources, &options, false, &cache);
+    assert!(compiled.ok, "diagnostics: {:?}", compiled.diagnostics);
+
+    let input: tools::run::RunInput = serde_json::from_value(serde_json::json!({
+        "container_id": compiled.container_id.unwrap(),
+        "duration_ms": 100,
+        "variables": ["Main.Counter"],
+        "forces": [{"variable": "Main.Counter", "value": 42}],
+    }))
+    .unwrap();
+    let resp = tools::run::build_response(&input, &cache);
+
+    assert!(resp.ok, "diagnostics: {:?}", resp.diagnostics);
+    assert_eq!(resp.summary.final_values["Main.Counter"], 42);
+    assert_eq!(resp.summary.forced, vec!["Main.Counter".to_string()]);
+}
+
 // ===========================================================================
 // `symbols` tool (REQ-TOL-mcp-050..055) — Milestone 1 (later)
 // ===========================================================================
@@ -784,6 +847,15 @@ fn mcp_spec_req_tol_063_option_ids_match_compiler_options_fields() {
             fd.option_key
         );
     }
+
+    // ... and so must every CHECK_DESCRIPTORS entry
+    for cd in CompilerOptions::CHECK_DESCRIPTORS {
+        assert!(
+            flag_ids.contains(&cd.option_key),
+            "CHECK_DESCRIPTOR key '{}' missing from list_options response",
+            cd.option_key
+        );
+    }
 }
 
 // ===========================================================================
@@ -866,6 +938,112 @@ fn mcp_spec_req_tol_150_container_drop_removes_entry() {}
 #[ignore]
 fn mcp_spec_req_tol_151_container_drop_unknown_id() {}
 
+// ===========================================================================
+// `wcet` tool (REQ-TOL-mcp-160..162) — Milestone 2
+// ===========================================================================
+
+/// REQ-TOL-mcp-160: The `wcet` tool returns one `tasks` entry per task in
+/// the container, named by the configured task, with `null` estimates when a
+/// task is unbounded.
+#[spec_test(REQ_TOL_mcp_160)]
+fn mcp_spec_req_tol_160_wcet_estimate_per_task() {
+    use std::sync::Mutex;
+
+    use crate::cache::ContainerCache;
+    use crate::tools::common::SourceInput;
+
+    let cache = Mutex::new(ContainerCache::new(64, 64 * 1024 * 1024));
+    let sources = vec![SourceInput {
+        name: "main.st".into(),
+        content: "PROGRAM Main\nVAR\n  y : INT;\nEND_VAR\n  WHILE y < 10 DO\n    y := y + 1;\n  END_WHILE;\nEND_PROGRAM\n\nCONFIGURATION config\n  RESOURCE resource1 ON PLC\n    TASK fast_task(INTERVAL := T#10ms, PRIORITY := 1);\n    TASK plc_task(INTERVAL := T#100ms, PRIORITY := 2);\n    PROGRAM program1 WITH plc_task : Main;\n  END_RESOURCE\nEND_CONFIGURATION".into(),
+    }];
+    let options = serde_json::json!({"dialect": "iec61131-3-ed2"});
+    let compiled = tools::compile::build_response(&sources, &options, false, &cache);
+    assert!(compiled.ok, "diagnostics: {:?}", compiled.diagnostics);
+
+    let resp = tools::wcet::build_response(&compiled.container_id.unwrap(), &cache);
+
+    assert!(resp.ok, "diagnostics: {:?}", resp.diagnostics);
+    assert_eq!(resp.tasks.len(), 1);
+    let task = &resp.tasks[0];
+    assert_eq!(task.task_id, 0);
+    assert_eq!(task.task.as_deref(), Some("plc_task"));
+    assert_eq!(task.programs, vec!["Main".to_string()]);
+    assert_eq!(task.interval_us, 100_000);
+    assert!(!task.bounded);
+    assert_eq!(task.wcet_units, None);
+    assert_eq!(task.wcet_us, None);
+
+    let json = serde_json::to_value(&resp).unwrap();
+    let entry = &json["tasks"][0];
+    for key in [
+        "task_id",
+        "task",
+        "programs",
+        "interval_us",
+        "watchdog_us",
+        "wcet_units",
+        "wcet_us",
+        "bounded",
+    ] {
+        assert!(entry.get(key).is_some(), "missing key {key}");
+    }
+    assert!(entry["wcet_units"].is_null());
+    assert!(entry["wcet_us"].is_null());
+}
+
+/// REQ-TOL-mcp-161: The `wcet` tool lists every loop with no iteration bound
+/// in `unbounded_loops`; a `FOR` with constant bounds is not listed.
+#[spec_test(REQ_TOL_mcp_161)]
+fn mcp_spec_req_tol_161_wcet_lists_unbounded_loops() {
+    use std::sync::Mutex;
+
+    use crate::cache::ContainerCache;
+    use crate::tools::common::SourceInput;
+
+    let cache = Mutex::new(ContainerCache::new(64, 64 * 1024 * 1024));
+    let sources = vec![SourceInput {
+        name: "main.st".into(),
+        content: "PROGRAM Main\nVAR\n  i : INT;\n  n : INT := 5;\n  x : INT;\nEND_VAR\n  FOR i := 1 TO 10 DO\n    x := x + i;\n  END_FOR;\n  FOR i := 1 TO n DO\n    x := x + i;\n  END_FOR;\n  REPEAT\n    x := x - 1;\n  UNTIL x < 0\n  END_REPEAT;\nEND_PROGRAM".into(),
+    }];
+    let options = serde_json::json!({"dialect": "iec61131-3-ed2"});
+    let compiled = tools::compile::build_response(&sources, &options, false, &cache);
+    assert!(compiled.ok, "diagnostics: {:?}", compiled.diagnostics);
+
+    let resp = tools::wcet::build_response(&compiled.container_id.unwrap(), &cache);
+
+    assert!(resp.ok, "diagnostics: {:?}", resp.diagnostics);
+    assert!(!resp.tasks[0].bounded);
+    let loops: Vec<(Option<&str>, u16)> = resp
+        .unbounded_loops
+        .iter()
+        .map(|lp| (lp.kind, lp.line))
+        .collect();
+    assert_eq!(loops, vec![(Some("FOR"), 10), (Some("REPEAT"), 13)]);
+    for lp in &resp.unbounded_loops {
+        assert!(!lp.function.is_empty());
+        assert_eq!(lp.file.as_deref(), Some("main.st"));
+    }
+}
+
+/// REQ-TOL-mcp-162: The `wcet` tool returns `ok: false`, empty `tasks` and
+/// `unbounded_loops`, and diagnostics when the `container_id` is unknown.
+#[spec_test(REQ_TOL_mcp_162)]
+fn mcp_spec_req_tol_162_wcet_unknown_id() {
+    use std::sync::Mutex;
+
+    use crate::cache::ContainerCache;
+
+    let cache = Mutex::new(ContainerCache::new(64, 64 * 1024 * 1024));
+
+    let resp = tools::wcet::build_response("c_nonexistent", &cache);
+
+    assert!(!resp.ok);
+    assert!(resp.tasks.is_empty());
+    assert!(resp.unbounded_loops.is_empty());
+    assert!(!resp.diagnostics.is_empty());
+}
+
 // ===========================================================================
 // Context tools: `project_manifest` (REQ-TOL-mcp-200..201) — Milestone 1 (later)
 // ===========================================================================
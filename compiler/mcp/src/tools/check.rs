@@ -3,10 +3,11 @@
 //! Runs parse and full semantic analysis, returning structured diagnostics.
 
 use ironplc_dsl::core::FileId;
+use ironplc_dsl::diagnostic::Diagnostic;
 use ironplc_project::project::{MemoryBackedProject, Project};
 use serde::Serialize;
 
-use super::common::{parse_options, serialize_diagnostics, validate_sources, SourceInput};
+use super::common::{parse_project_options, serialize_diagnostics, validate_sources, SourceInput};
 
 /// Response returned by the `check` tool.
 #[derive(Debug, Serialize)]
@@ -27,7 +28,7 @@ pub fn build_response(sources: &[SourceInput], options_value: &serde_json::Value
     }
 
     // Parse options
-    let options = match parse_options(options_value) {
+    let (options, demotions) = match parse_project_options(options_value) {
         Ok(opts) => opts,
         Err(errs) => {
             return CheckResponse {
@@ -39,6 +40,7 @@ pub fn build_response(sources: &[SourceInput], options_value: &serde_json::Value
 
     // Construct a fresh in-memory project (REQ-ARC-mcp-010)
     let mut project = MemoryBackedProject::new(options);
+    project.set_demotions(demotions);
 
     // Load sources (REQ-ARC-mcp-011)
     for src in sources {
@@ -47,10 +49,10 @@ pub fn build_response(sources: &[SourceInput], options_value: &serde_json::Value
     }
 
     // Run parse + full semantic analysis
-    let diagnostics = serialize_diagnostics(&project.semantic());
+    let diagnostics = project.semantic();
     CheckResponse {
-        ok: diagnostics.is_empty(),
-        diagnostics,
+        ok: !diagnostics.iter().any(Diagnostic::is_error),
+        diagnostics: serialize_diagnostics(&diagnostics),
     }
 }
 
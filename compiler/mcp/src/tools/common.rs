@@ -9,7 +9,7 @@
 use std::collections::HashSet;
 
 use ironplc_dsl::core::SourceSpan;
-use ironplc_dsl::diagnostic::{Diagnostic, Label};
+use ironplc_dsl::diagnostic::{Demotions, Diagnostic, Label, Severity};
 use ironplc_parser::options::{CompilerOptions, Dialect};
 use ironplc_problems::Problem;
 use schemars::JsonSchema;
@@ -117,7 +117,12 @@ pub fn validate_sources(sources: &[SourceInput]) -> Vec<Diagnostic> {
 // Options parsing (REQ-TOL-mcp-025, REQ-TOL-mcp-026)
 // ───────────────────────────────────────────────────────────────────
 
+/// The `options` key listing the problem codes to report as warnings.
+const DEMOTE_KEY: &str = "demote";
+
 /// Parses and validates the `options` JSON value into `CompilerOptions`.
+///
+/// The `demote` key is not a compiler option; [`parse_demotions`] reads it.
 pub fn parse_options(value: &serde_json::Value) -> Result<CompilerOptions, Vec<Diagnostic>> {
     let obj = value
         .as_object()
@@ -143,7 +148,7 @@ pub fn parse_options(value: &serde_json::Value) -> Result<CompilerOptions, Vec<D
     let mut errors = Vec::new();
 
     for (key, val) in obj {
-        if key == "dialect" {
+        if key == "dialect" || key == DEMOTE_KEY {
             continue;
         }
         match val.as_bool() {
@@ -170,6 +175,59 @@ pub fn parse_options(value: &serde_json::Value) -> Result<CompilerOptions, Vec<D
     Ok(options)
 }
 
+/// Parses the `demote` array of the `options` JSON value: the problem codes
+/// to report as warnings (REQ-TOL-mcp-027). A code that cannot be demoted is
+/// a `ProblemNotDemotable` (P6016) diagnostic.
+pub fn parse_demotions(value: &serde_json::Value) -> Result<Demotions, Vec<Diagnostic>> {
+    let mut demotions = Demotions::default();
+    let Some(codes) = value.get(DEMOTE_KEY) else {
+        return Ok(demotions);
+    };
+    let codes = codes.as_array().ok_or_else(|| {
+        vec![validation_diagnostic(
+            "options.demote must be an array of problem codes.",
+        )]
+    })?;
+
+    let mut errors = Vec::new();
+    for code in codes {
+        let Some(code) = code.as_str() else {
+            errors.push(validation_diagnostic(
+                "options.demote must be an array of problem codes.",
+            ));
+            continue;
+        };
+        if let Err(reason) = demotions.demote(code) {
+            errors.push(Diagnostic::problem(
+                Problem::ProblemNotDemotable,
+                Label::span(SourceSpan::default(), reason),
+            ));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(demotions)
+}
+
+/// Parses the `options` JSON value into the compiler options and the
+/// demotions of a tool that analyzes a project.
+pub fn parse_project_options(
+    value: &serde_json::Value,
+) -> Result<(CompilerOptions, Demotions), Vec<Diagnostic>> {
+    let options = parse_options(value);
+    let demotions = parse_demotions(value);
+    match (options, demotions) {
+        (Ok(options), Ok(demotions)) => Ok((options, demotions)),
+        (options, demotions) => {
+            let mut errors = options.err().unwrap_or_default();
+            errors.extend(demotions.err().unwrap_or_default());
+            Err(errors)
+        }
+    }
+}
+
 /// Resolves a dialect string (e.g. `"iec61131-3-ed2"`) to a `Dialect`.
 fn resolve_dialect(s: &str) -> Option<Dialect> {
     Dialect::ALL.iter().find(|d| d.to_string() == s).copied()
@@ -199,7 +257,10 @@ pub fn serialize_diagnostic(diag: &Diagnostic) -> serde_json::Value {
         "file": diag.primary.file_id.to_string(),
         "start": diag.primary.location.start,
         "end": diag.primary.location.end,
-        "severity": "error",
+        "severity": match diag.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        },
     })
 }
 
@@ -414,6 +475,41 @@ mod tests {
         assert!(opts.allow_c_style_comments);
     }
 
+    #[test]
+    fn parse_options_when_demote_then_not_unknown_key() {
+        let val = serde_json::json!({"dialect": "iec61131-3-ed2", "demote": ["P4041"]});
+        assert!(parse_options(&val).is_ok());
+    }
+
+    // -- parse_demotions tests --
+
+    #[test]
+    fn parse_demotions_when_absent_then_empty() {
+        let val = serde_json::json!({"dialect": "iec61131-3-ed2"});
+        assert!(parse_demotions(&val).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_demotions_when_demotable_code_then_demoted() {
+        let val = serde_json::json!({"dialect": "iec61131-3-ed2", "demote": ["P4041"]});
+        let demotions = parse_demotions(&val).unwrap();
+        assert_eq!(demotions.codes().collect::<Vec<_>>(), vec!["P4041"]);
+    }
+
+    #[test]
+    fn parse_demotions_when_safety_relevant_code_then_p6016() {
+        let val = serde_json::json!({"dialect": "iec61131-3-ed2", "demote": ["P4035"]});
+        let errs = parse_demotions(&val).unwrap_err();
+        assert_eq!(errs[0].code, "P6016");
+    }
+
+    #[test]
+    fn parse_demotions_when_not_array_then_p8001() {
+        let val = serde_json::json!({"dialect": "iec61131-3-ed2", "demote": "P4041"});
+        let errs = parse_demotions(&val).unwrap_err();
+        assert_eq!(errs[0].code, "P8001");
+    }
+
     // -- serialize_diagnostic tests --
 
     #[test]
@@ -4,6 +4,7 @@
 //! supplied sources and returns an opaque container handle plus task and
 //! program metadata.
 
+use std::collections::HashMap;
 use std::sync::Mutex;
 
 use base64::Engine;
@@ -18,7 +19,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::common::{
-    parse_options, serialize_diagnostic, serialize_diagnostics, validate_sources, SourceInput,
+    parse_project_options, serialize_diagnostic, serialize_diagnostics, validate_sources,
+    SourceInput,
 };
 use crate::cache::{CachedContainer, ContainerCache, InsertError, ProgramMeta, TaskMeta};
 
@@ -62,6 +64,16 @@ pub struct ProgramInfo {
     pub task: Option<String>,
 }
 
+/// Codegen [`SourceLookup`](ironplc_codegen::SourceLookup) over the
+/// sources supplied in the tool call.
+struct SourcesLookup<'a>(HashMap<FileId, &'a [u8]>);
+
+impl ironplc_codegen::SourceLookup for SourcesLookup<'_> {
+    fn source_bytes(&self, file_id: &FileId) -> Option<&[u8]> {
+        self.0.get(file_id).copied()
+    }
+}
+
 /// Builds the compile response from raw inputs.
 pub fn build_response(
     sources: &[SourceInput],
@@ -83,7 +95,7 @@ pub fn build_response(
     }
 
     // Parse options (shared infra)
-    let compiler_options = match parse_options(options_value) {
+    let (compiler_options, demotions) = match parse_project_options(options_value) {
         Ok(opts) => opts,
         Err(errs) => {
             return CompileResponse {
@@ -99,6 +111,7 @@ pub fn build_response(
 
     // Construct a fresh in-memory project (REQ-ARC-mcp-010)
     let mut project = MemoryBackedProject::new(compiler_options);
+    project.set_demotions(demotions);
 
     // Load sources (REQ-ARC-mcp-011)
     for src in sources {
@@ -106,12 +119,19 @@ pub fn build_response(
     }
 
     // Run the pipeline (parse, analysis, codegen) that `ironplc-project` owns.
-    // No sources come from disk here, so codegen gets no source bytes to hash
-    // into the container's debug section.
+    // Codegen reads the source bytes for the debug section and for loop
+    // bound pragmas that the `wcet` tool uses.
+    let source_lookup = SourcesLookup(
+        sources
+            .iter()
+            .map(|src| (FileId::from_string(&src.name), src.content.as_bytes()))
+            .collect(),
+    );
     let output = ironplc_project::compile(
         &mut project,
         &compiler_options,
-        &ironplc_codegen::EmptyLookup,
+        &ironplc_codegen::CodegenOptions::default(),
+        &source_lookup,
         vec![],
     );
     let mut diagnostics = serialize_diagnostics(&output.diagnostics);
@@ -265,6 +285,7 @@ fn extract_task_program_metadata(library: &Library) -> (Vec<TaskMeta>, Vec<Progr
             }];
             let programs = vec![ProgramMeta {
                 name: program_name.clone(),
+                type_name: program_name.clone(),
                 task: Some(program_name),
             }];
             (tasks, programs)
@@ -315,6 +336,7 @@ fn task_meta_from_config(task: &TaskConfiguration) -> TaskMeta {
 fn program_meta_from_config(prog: &ProgramConfiguration) -> ProgramMeta {
     ProgramMeta {
         name: prog.name.to_string(),
+        type_name: prog.type_name.to_string(),
         task: prog.task_name.as_ref().map(|t| t.to_string()),
     }
 }
@@ -1,6 +1,7 @@
 //! The `list_options` MCP tool.
 //!
-//! Returns the set of compiler options (dialects and feature flags) that
+//! Returns the set of compiler options (dialects, feature flags and opt-in
+//! analysis checks) that
 //! callers may pass in an `options` object to analysis, context, and
 //! execution tools.
 
@@ -46,7 +47,9 @@ pub fn build_response() -> ListOptionsResponse {
         })
         .collect();
 
-    let mut flags = Vec::with_capacity(CompilerOptions::FEATURE_DESCRIPTORS.len());
+    let mut flags = Vec::with_capacity(
+        CompilerOptions::FEATURE_DESCRIPTORS.len() + CompilerOptions::CHECK_DESCRIPTORS.len(),
+    );
 
     // All dialect-extension flags from the macro-generated descriptors.
     for fd in CompilerOptions::FEATURE_DESCRIPTORS {
@@ -59,6 +62,17 @@ pub fn build_response() -> ListOptionsResponse {
         });
     }
 
+    // Opt-in analysis checks take a boolean in the same `options` object.
+    for cd in CompilerOptions::CHECK_DESCRIPTORS {
+        flags.push(FlagInfo {
+            id: cd.option_key.to_string(),
+            flag_type: "bool".into(),
+            default: serde_json::Value::Bool(false),
+            description: cd.description.to_string(),
+            allowed_values: None,
+        });
+    }
+
     ListOptionsResponse { dialects, flags }
 }
 
@@ -83,13 +97,16 @@ mod tests {
     }
 
     #[test]
-    fn build_response_when_called_then_contains_every_feature_descriptor() {
+    fn build_response_when_called_then_contains_every_feature_and_check_descriptor() {
         // Derived from the source of truth (`FEATURE_DESCRIPTORS`) rather than a
         // hard-coded count, so adding a feature flag does not force an edit here.
         // That each flag gates real compiler behavior — not just sets a struct
         // field — is proven behaviorally in the `feature_flag_conformance` suite.
         let resp = build_response();
-        assert_eq!(resp.flags.len(), CompilerOptions::FEATURE_DESCRIPTORS.len());
+        assert_eq!(
+            resp.flags.len(),
+            CompilerOptions::FEATURE_DESCRIPTORS.len() + CompilerOptions::CHECK_DESCRIPTORS.len()
+        );
     }
 
     #[test]
@@ -113,6 +130,24 @@ mod tests {
         assert!(resp.flags.iter().any(|f| f.id == "allow_c_style_comments"));
     }
 
+    #[test]
+    fn build_response_when_called_then_contains_task_races_check() {
+        let resp = build_response();
+        assert!(resp.flags.iter().any(|f| f.id == "check_task_races"));
+    }
+
+    #[test]
+    fn build_response_when_called_then_contains_case_exhaustive_check() {
+        let resp = build_response();
+        assert!(resp.flags.iter().any(|f| f.id == "check_case_exhaustive"));
+    }
+
+    #[test]
+    fn build_response_when_called_then_contains_unused_check() {
+        let resp = build_response();
+        assert!(resp.flags.iter().any(|f| f.id == "check_unused"));
+    }
+
     #[test]
     fn build_response_when_serialized_then_valid_json() {
         let resp = build_response();
@@ -14,3 +14,4 @@ pub mod symbols;
 #[cfg(test)]
 pub mod test_support;
 pub mod types_all;
+pub mod wcet;
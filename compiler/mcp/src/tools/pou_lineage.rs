@@ -18,7 +18,7 @@ use ironplc_project::project::{MemoryBackedProject, Project};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use super::common::{parse_options, serialize_diagnostics, validate_sources, SourceInput};
+use super::common::{parse_project_options, serialize_diagnostics, validate_sources, SourceInput};
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct PouLineageInput {
@@ -68,7 +68,7 @@ pub fn build_response(
         );
     }
 
-    let options = match parse_options(options_value) {
+    let (options, demotions) = match parse_project_options(options_value) {
         Ok(opts) => opts,
         Err(errs) => {
             return PouLineageResponse::empty(
@@ -81,6 +81,7 @@ pub fn build_response(
     };
 
     let mut project = MemoryBackedProject::new(options);
+    project.set_demotions(demotions);
     for src in sources {
         project.add_source(FileId::from_string(&src.name), src.content.clone());
     }
@@ -11,7 +11,7 @@ use ironplc_dsl::core::FileId;
 use ironplc_project::project::{MemoryBackedProject, Project};
 use serde::Serialize;
 
-use super::common::{parse_options, serialize_diagnostics, validate_sources, SourceInput};
+use super::common::{parse_project_options, serialize_diagnostics, validate_sources, SourceInput};
 
 /// A single input or output entry (REQ-TOL-mcp-212).
 #[derive(Debug, Clone, Serialize)]
@@ -52,7 +52,7 @@ pub fn build_response(
         return ProjectIoResponse::empty(false, serialize_diagnostics(&source_errors));
     }
 
-    let options = match parse_options(options_value) {
+    let (options, demotions) = match parse_project_options(options_value) {
         Ok(opts) => opts,
         Err(errs) => {
             return ProjectIoResponse::empty(false, serialize_diagnostics(&errs));
@@ -60,6 +60,7 @@ pub fn build_response(
     };
 
     let mut project = MemoryBackedProject::new(options);
+    project.set_demotions(demotions);
     for src in sources {
         project.add_source(FileId::from_string(&src.name), src.content.clone());
     }
@@ -9,7 +9,7 @@ use ironplc_dsl::core::FileId;
 use ironplc_project::project::{MemoryBackedProject, Project};
 use serde::Serialize;
 
-use super::common::{parse_options, serialize_diagnostics, validate_sources, SourceInput};
+use super::common::{parse_project_options, serialize_diagnostics, validate_sources, SourceInput};
 
 /// Response returned by the `project_manifest` tool.
 #[derive(Debug, Serialize)]
@@ -63,7 +63,7 @@ pub fn build_response(
         );
     }
 
-    let options = match parse_options(options_value) {
+    let (options, demotions) = match parse_project_options(options_value) {
         Ok(opts) => opts,
         Err(errs) => {
             return ProjectManifestResponse::empty(false, vec![], serialize_diagnostics(&errs));
@@ -73,6 +73,7 @@ pub fn build_response(
     let files: Vec<String> = sources.iter().map(|s| s.name.clone()).collect();
 
     let mut project = MemoryBackedProject::new(options);
+    project.set_demotions(demotions);
     for src in sources {
         project.add_source(FileId::from_string(&src.name), src.content.clone());
     }
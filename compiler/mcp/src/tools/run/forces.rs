@@ -0,0 +1,166 @@
+//! Resolution of the `run` tool's `forces` (REQ-TOL-mcp-049).
+//!
+//! Each force names a variable, like a trace name or by its located address,
+//! and a value. Both are checked against the loaded container before the VM
+//! starts, so a force that cannot apply fails the run with a diagnostic
+//! rather than being dropped.
+
+use ironplc_dsl::diagnostic::Diagnostic;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::cache::{ResolvedVar, VariableSymbolMap};
+use crate::runner;
+
+use super::{resolve_name, validation, NameError};
+
+/// One entry of `run`'s `forces`.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ForceInput {
+    /// A variable name, resolved like the trace names, or a located
+    /// address such as `%IX0.0`.
+    pub variable: String,
+    /// The value to hold the variable at: a boolean, a number, or an IEC
+    /// 61131-3 literal such as `"T#250ms"` or `"16#FF"`.
+    #[schemars(schema_with = "force_value_schema")]
+    pub value: Value,
+}
+
+/// schemars schema function for a force's `value`, which would otherwise
+/// render as the boolean schema `true` (see `stimuli_schema`).
+fn force_value_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({
+        "type": ["boolean", "number", "string"],
+        "description": "The value to hold the variable at: a boolean, a number, or an IEC 61131-3 literal."
+    })
+}
+
+/// Resolves every force to its variable and the raw slot value to hold it at.
+/// A located address (`%IX0.0`, `%QW3`) names the variable declared `AT` it;
+/// anything else resolves like a trace name (REQ-ARC-mcp-020).
+pub(super) fn resolve_forces(
+    forces: &[ForceInput],
+    symbols: &VariableSymbolMap,
+) -> Result<Vec<(ResolvedVar, u64)>, Vec<Diagnostic>> {
+    let mut out = Vec::new();
+    let mut errors = Vec::new();
+
+    for force in forces {
+        let resolved = if force.variable.starts_with('%') {
+            symbols
+                .iter()
+                .find(|v| {
+                    v.address
+                        .as_deref()
+                        .is_some_and(|a| a.eq_ignore_ascii_case(&force.variable))
+                })
+                .cloned()
+                .ok_or(NameError::Unresolved)
+        } else {
+            resolve_name(symbols, &force.variable)
+        };
+        let var = match resolved {
+            Ok(var) => var,
+            Err(NameError::Unresolved) => {
+                errors.push(validation(&format!(
+                    "Forced variable '{}' does not resolve against the loaded container.",
+                    force.variable
+                )));
+                continue;
+            }
+            Err(NameError::Ambiguous(candidates)) => {
+                errors.push(validation(&format!(
+                    "Forced variable '{}' is ambiguous; candidates: [{}]. Qualify the name (e.g. 'Program.{}').",
+                    force.variable,
+                    candidates.join(", "),
+                    force.variable
+                )));
+                continue;
+            }
+        };
+        match runner::raw_from_value(&force.value, var.iec_type_tag) {
+            Ok(raw) => out.push((var, raw)),
+            Err(reason) => errors.push(validation(&format!(
+                "Cannot force '{}' to {}: {reason}.",
+                force.variable, force.value
+            ))),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(out)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::build_response;
+    use super::super::tests::{base_input, compile_into, make_cache, COUNTER_PROGRAM};
+    use super::*;
+
+    const LOCATED_INPUT_PROGRAM: &str = r#"
+PROGRAM Main
+VAR
+  Start AT %IX0.0 : BOOL;
+  Running : BOOL;
+END_VAR
+  Running := Start;
+END_PROGRAM
+"#;
+
+    #[test]
+    fn build_response_when_located_input_forced_by_address_then_program_sees_it() {
+        let cache = make_cache();
+        let id = compile_into(&cache, LOCATED_INPUT_PROGRAM);
+        let mut input = base_input(id);
+        input.variables = vec!["Main.Running".into()];
+        input.forces = vec![ForceInput {
+            variable: "%IX0.0".into(),
+            value: Value::Bool(true),
+        }];
+        let resp = build_response(&input, &cache);
+
+        assert!(resp.ok, "diagnostics: {:?}", resp.diagnostics);
+        assert_eq!(resp.summary.final_values["Main.Running"], Value::Bool(true));
+        assert_eq!(resp.summary.forced, vec!["Main.Start".to_string()]);
+    }
+
+    #[test]
+    fn build_response_when_force_value_not_of_type_then_ok_false() {
+        let cache = make_cache();
+        let id = compile_into(&cache, COUNTER_PROGRAM);
+        let mut input = base_input(id);
+        input.forces = vec![ForceInput {
+            variable: "Main.Counter".into(),
+            value: Value::from("fast"),
+        }];
+        let resp = build_response(&input, &cache);
+
+        assert!(!resp.ok);
+        assert!(resp.diagnostics.iter().any(|d| d["message"]
+            .as_str()
+            .unwrap_or("")
+            .contains("Cannot force 'Main.Counter'")));
+    }
+
+    #[test]
+    fn build_response_when_forced_address_not_declared_then_ok_false() {
+        let cache = make_cache();
+        let id = compile_into(&cache, COUNTER_PROGRAM);
+        let mut input = base_input(id);
+        input.forces = vec![ForceInput {
+            variable: "%IX9.9".into(),
+            value: Value::Bool(true),
+        }];
+        let resp = build_response(&input, &cache);
+
+        assert!(!resp.ok);
+        assert!(resp
+            .diagnostics
+            .iter()
+            .any(|d| d["message"].as_str().unwrap_or("").contains("%IX9.9")));
+    }
+}
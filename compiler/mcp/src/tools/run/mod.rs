@@ -10,8 +10,11 @@
 //! ingestion, full IEC value codec for STRING/DATE/struct/array) return
 //! `ok: false` with a diagnostic directing the caller to the follow-up.
 //!
+//! `forces` hold variables at a value for the whole run through the VM's
+//! force table (REQ-TOL-mcp-049); their resolution lives in `forces`.
+//!
 //! Design references: `specs/design/mcp-server.md` §`run`
-//! (REQ-TOL-mcp-040..048), §Variable Naming (REQ-ARC-mcp-020..021), §VM
+//! (REQ-TOL-mcp-040..049), §Variable Naming (REQ-ARC-mcp-020..021), §VM
 //! Sandboxing (REQ-ARC-mcp-030..035).
 
 use std::sync::Mutex;
@@ -23,10 +26,17 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
+use ironplc_container::VarIndex;
+
 use crate::cache::{ContainerCache, ResolvedVar, VariableSymbolMap};
 use crate::runner::{self, EffectiveLimits, RunOutcome, TerminatedReason};
 use crate::tools::common::{serialize_diagnostic, serialize_diagnostics};
 
+mod forces;
+
+use forces::resolve_forces;
+pub use forces::ForceInput;
+
 /// Combined input accepted by `run`.
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct RunInput {
@@ -59,6 +69,10 @@ pub struct RunInput {
     /// Restrict trace emission to cycles from named tasks. Phase 11 feature.
     #[serde(default)]
     pub tasks: Option<Vec<String>>,
+    /// Variables held at a value for the whole run, as when commissioning a
+    /// machine: the program cannot override them.
+    #[serde(default)]
+    pub forces: Vec<ForceInput>,
 }
 
 /// schemars schema function for the free-form `stimuli` array.
@@ -122,6 +136,8 @@ pub struct RunSummary {
     pub final_values: Map<String, Value>,
     pub completed_cycles: Map<String, Value>,
     pub terminated_reason: String,
+    /// The canonical names of the variables `forces` held, in request order.
+    pub forced: Vec<String>,
 }
 
 /// Entry point. Validates input, resolves the trace set, runs the VM,
@@ -206,6 +222,12 @@ pub fn build_response(input: &RunInput, cache: &Mutex<ContainerCache>) -> RunRes
         Err(diags) => return fail(diags),
     };
 
+    // --- Force resolution (REQ-TOL-mcp-049) ---
+    let forces = match resolve_forces(&input.forces, &cached.symbols) {
+        Ok(forces) => forces,
+        Err(diags) => return fail(diags),
+    };
+
     // --- Apply a caller-supplied `trace.max_samples` as a tightening cap ---
     let effective_samples = input
         .trace
@@ -225,9 +247,14 @@ pub fn build_response(input: &RunInput, cache: &Mutex<ContainerCache>) -> RunRes
     drop(guard);
 
     // --- Execute ---
+    let raw_forces: Vec<(VarIndex, u64)> = forces
+        .iter()
+        .map(|(var, raw)| (var.var_index, *raw))
+        .collect();
     let outcome = match runner::execute(
         &cached_snapshot,
         &trace_set,
+        &raw_forces,
         input.duration_ms,
         effective_limits,
     ) {
@@ -238,11 +265,16 @@ pub fn build_response(input: &RunInput, cache: &Mutex<ContainerCache>) -> RunRes
         }
     };
 
-    build_success_response(outcome, input.duration_ms, effective_limits)
+    let forced = forces
+        .into_iter()
+        .map(|(var, _)| var.canonical_name)
+        .collect();
+    build_success_response(outcome, forced, input.duration_ms, effective_limits)
 }
 
 fn build_success_response(
     outcome: RunOutcome,
+    forced: Vec<String>,
     requested_duration_ms: u64,
     limits: EffectiveLimits,
 ) -> RunResponse {
@@ -303,6 +335,7 @@ fn build_success_response(
             final_values,
             completed_cycles,
             terminated_reason: reason_str,
+            forced,
         },
         diagnostics,
     }
@@ -453,13 +486,16 @@ fn is_observable_output(v: &ResolvedVar) -> bool {
 }
 
 #[derive(Debug)]
-enum NameError {
+pub(super) enum NameError {
     Unresolved,
     Ambiguous(Vec<String>),
 }
 
 /// Resolves a fully-qualified or bare name per REQ-ARC-mcp-020.
-fn resolve_name(symbols: &VariableSymbolMap, requested: &str) -> Result<ResolvedVar, NameError> {
+pub(super) fn resolve_name(
+    symbols: &VariableSymbolMap,
+    requested: &str,
+) -> Result<ResolvedVar, NameError> {
     // Qualified lookup first.
     if requested.contains('.') {
         return symbols
@@ -487,7 +523,7 @@ fn resolve_name(symbols: &VariableSymbolMap, requested: &str) -> Result<Resolved
     Err(NameError::Ambiguous(names))
 }
 
-fn validation(message: &str) -> Diagnostic {
+pub(super) fn validation(message: &str) -> Diagnostic {
     Diagnostic::problem(
         Problem::McpInputValidation,
         Label::span(SourceSpan::default(), message),
@@ -504,6 +540,7 @@ fn fail(diags: Vec<Diagnostic>) -> RunResponse {
             final_values: Map::new(),
             completed_cycles: Map::new(),
             terminated_reason: "error".into(),
+            forced: vec![],
         },
         diagnostics: serialize_diagnostics(&diags),
     }
@@ -531,13 +568,13 @@ mod tests {
     use crate::tools::common::SourceInput;
     use crate::tools::test_support::ed2_options;
 
-    fn make_cache() -> Mutex<ContainerCache> {
+    pub(super) fn make_cache() -> Mutex<ContainerCache> {
         Mutex::new(ContainerCache::new(64, 64 * 1024 * 1024))
     }
 
     /// Compile a program via the `compile` tool to populate the cache with
     /// a fresh container and symbol map; returns the container_id.
-    fn compile_into(cache: &Mutex<ContainerCache>, source: &str) -> String {
+    pub(super) fn compile_into(cache: &Mutex<ContainerCache>, source: &str) -> String {
         let sources = vec![SourceInput {
             name: "main.st".into(),
             content: source.into(),
@@ -547,7 +584,7 @@ mod tests {
         resp.container_id.unwrap()
     }
 
-    const COUNTER_PROGRAM: &str = r#"
+    pub(super) const COUNTER_PROGRAM: &str = r#"
 PROGRAM Main
 VAR
   Counter : INT;
@@ -563,7 +600,7 @@ CONFIGURATION config
 END_CONFIGURATION
 "#;
 
-    fn base_input(container_id: String) -> RunInput {
+    pub(super) fn base_input(container_id: String) -> RunInput {
         RunInput {
             container_id: Some(container_id),
             container_base64: None,
@@ -574,6 +611,7 @@ END_CONFIGURATION
             trace: None,
             limits: None,
             tasks: None,
+            forces: vec![],
         }
     }
 
@@ -604,6 +642,7 @@ END_CONFIGURATION
             trace: None,
             limits: None,
             tasks: None,
+            forces: vec![],
         };
         let resp = build_response(&input, &cache);
         assert!(!resp.ok);
@@ -623,6 +662,7 @@ END_CONFIGURATION
             trace: None,
             limits: None,
             tasks: None,
+            forces: vec![],
         };
         let resp = build_response(&input, &cache);
         assert!(!resp.ok);
@@ -641,6 +681,7 @@ END_CONFIGURATION
             trace: None,
             limits: None,
             tasks: None,
+            forces: vec![],
         };
         let resp = build_response(&input, &cache);
         assert!(!resp.ok);
@@ -7,7 +7,7 @@ use ironplc_project::project::{MemoryBackedProject, Project};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use super::common::{parse_options, serialize_diagnostics, validate_sources, SourceInput};
+use super::common::{parse_project_options, serialize_diagnostics, validate_sources, SourceInput};
 
 const MAX_RESPONSE_BYTES: usize = 256 * 1024;
 
@@ -79,7 +79,7 @@ pub fn build_response(
         return empty_response(false, None, false, serialize_diagnostics(&source_errors));
     }
 
-    let options = match parse_options(options_value) {
+    let (options, demotions) = match parse_project_options(options_value) {
         Ok(opts) => opts,
         Err(errs) => {
             return empty_response(false, None, false, serialize_diagnostics(&errs));
@@ -87,6 +87,7 @@ pub fn build_response(
     };
 
     let mut project = MemoryBackedProject::new(options);
+    project.set_demotions(demotions);
     for src in sources {
         project.add_source(FileId::from_string(&src.name), src.content.clone());
     }
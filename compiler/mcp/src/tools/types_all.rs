@@ -10,7 +10,7 @@ use ironplc_dsl::core::FileId;
 use ironplc_project::project::{MemoryBackedProject, Project};
 use serde::Serialize;
 
-use super::common::{parse_options, serialize_diagnostics, validate_sources, SourceInput};
+use super::common::{parse_project_options, serialize_diagnostics, validate_sources, SourceInput};
 
 /// A single entry in the `types` array.
 #[derive(Debug, Clone, Serialize)]
@@ -84,7 +84,7 @@ pub fn build_response(
         return TypesAllResponse::empty(false, serialize_diagnostics(&source_errors));
     }
 
-    let options = match parse_options(options_value) {
+    let (options, demotions) = match parse_project_options(options_value) {
         Ok(opts) => opts,
         Err(errs) => {
             return TypesAllResponse::empty(false, serialize_diagnostics(&errs));
@@ -92,6 +92,7 @@ pub fn build_response(
     };
 
     let mut project = MemoryBackedProject::new(options);
+    project.set_demotions(demotions);
     for src in sources {
         project.add_source(FileId::from_string(&src.name), src.content.clone());
     }
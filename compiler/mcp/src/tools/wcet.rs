@@ -0,0 +1,236 @@
+//! The `wcet` MCP tool.
+//!
+//! Estimates the worst-case execution time of each task in a previously
+//! compiled container and lists the loops that have no iteration bound.
+
+use std::sync::Mutex;
+
+use ironplc_codegen::wcet::{self, Cost};
+use ironplc_container::Container;
+use ironplc_dsl::core::SourceSpan;
+use ironplc_dsl::diagnostic::{Diagnostic, Label};
+use ironplc_problems::Problem;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::common::serialize_diagnostics;
+use crate::cache::{ContainerCache, ProgramMeta};
+
+/// Input for the `wcet` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct WcetInput {
+    /// The opaque container ID returned by `compile`.
+    pub container_id: String,
+}
+
+/// Response for the `wcet` tool.
+#[derive(Debug, Serialize)]
+pub struct WcetResponse {
+    pub ok: bool,
+    pub ns_per_cost_unit: u64,
+    pub tasks: Vec<TaskEstimate>,
+    pub unbounded_loops: Vec<UnboundedLoopInfo>,
+    pub diagnostics: Vec<serde_json::Value>,
+}
+
+/// The estimate for one task.
+#[derive(Debug, Serialize)]
+pub struct TaskEstimate {
+    pub task_id: u16,
+    /// Task name from the configuration, when the container has one.
+    pub task: Option<String>,
+    pub programs: Vec<String>,
+    pub interval_us: u64,
+    pub watchdog_us: u64,
+    /// Worst-case cost units of one scan; `null` when unbounded.
+    pub wcet_units: Option<u64>,
+    /// `wcet_units` in microseconds; `null` when unbounded.
+    pub wcet_us: Option<u64>,
+    pub bounded: bool,
+}
+
+/// A loop with no iteration bound.
+#[derive(Debug, Serialize)]
+pub struct UnboundedLoopInfo {
+    pub function: String,
+    pub kind: Option<&'static str>,
+    pub file: Option<String>,
+    pub line: u16,
+    pub column: u16,
+}
+
+/// Builds the wcet response.
+pub fn build_response(container_id: &str, cache: &Mutex<ContainerCache>) -> WcetResponse {
+    let (bytes, program_metas) = {
+        let mut guard = cache.lock().unwrap();
+        match guard.get(container_id) {
+            Some(cached) => (cached.iplc_bytes.clone(), cached.programs.clone()),
+            None => {
+                return fail(Diagnostic::problem(
+                    Problem::McpInputValidation,
+                    Label::span(
+                        SourceSpan::default(),
+                        format!("unknown container_id '{container_id}'"),
+                    ),
+                ));
+            }
+        }
+    };
+
+    let container = match Container::read_from(&mut bytes.as_slice()) {
+        Ok(container) => container,
+        Err(e) => {
+            return fail(Diagnostic::internal_error_at(Label::span(
+                SourceSpan::default(),
+                format!("container read error: {e}"),
+            )));
+        }
+    };
+    let mut report = match wcet::estimate(&container) {
+        Ok(report) => report,
+        Err(diagnostic) => return fail(diagnostic),
+    };
+    report.name_tasks(|program| task_name(&program_metas, program));
+
+    let ns = wcet::DEFAULT_NS_PER_COST_UNIT;
+    let tasks = report
+        .tasks
+        .into_iter()
+        .map(|task| {
+            let wcet_units = match task.cost {
+                Cost::Bounded(units) => Some(units),
+                Cost::Unbounded => None,
+            };
+            TaskEstimate {
+                task_id: task.task_id.raw(),
+                task: task.name,
+                programs: task.programs,
+                interval_us: task.interval_us,
+                watchdog_us: task.watchdog_us,
+                wcet_units,
+                wcet_us: task.cost.micros(ns),
+                bounded: wcet_units.is_some(),
+            }
+        })
+        .collect();
+    let unbounded_loops = report
+        .unbounded_loops
+        .into_iter()
+        .map(|lp| UnboundedLoopInfo {
+            function: lp.function,
+            kind: lp.kind,
+            file: lp.file,
+            line: lp.line,
+            column: lp.column,
+        })
+        .collect();
+
+    WcetResponse {
+        ok: true,
+        ns_per_cost_unit: ns,
+        tasks,
+        unbounded_loops,
+        diagnostics: vec![],
+    }
+}
+
+/// Returns the configured name of the task that runs `program`.
+///
+/// The container's task table holds only the tasks of the compiled
+/// programs, so its task IDs do not index the configuration's task list;
+/// the name comes from the `WITH` clause of a program instance instead.
+fn task_name(program_metas: &[ProgramMeta], program: &str) -> Option<String> {
+    program_metas
+        .iter()
+        .find(|meta| meta.type_name.eq_ignore_ascii_case(program))
+        .and_then(|meta| meta.task.clone())
+}
+
+fn fail(diagnostic: Diagnostic) -> WcetResponse {
+    WcetResponse {
+        ok: false,
+        ns_per_cost_unit: wcet::DEFAULT_NS_PER_COST_UNIT,
+        tasks: vec![],
+        unbounded_loops: vec![],
+        diagnostics: serialize_diagnostics(&[diagnostic]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::common::SourceInput;
+    use crate::tools::test_support::ed2_options;
+
+    fn compile_into(cache: &Mutex<ContainerCache>, content: &str) -> String {
+        let sources = vec![SourceInput {
+            name: "main.st".into(),
+            content: content.into(),
+        }];
+        let resp = crate::tools::compile::build_response(&sources, &ed2_options(), false, cache);
+        assert!(resp.ok, "compile failed: {:?}", resp.diagnostics);
+        resp.container_id.unwrap()
+    }
+
+    fn new_cache() -> Mutex<ContainerCache> {
+        Mutex::new(ContainerCache::new(64, 64 * 1024 * 1024))
+    }
+
+    #[test]
+    fn build_response_when_bounded_program_then_task_estimate() {
+        let cache = new_cache();
+        let id = compile_into(
+            &cache,
+            "PROGRAM main
+  VAR i : DINT; x : DINT; END_VAR
+  FOR i := 1 TO 10 DO
+    x := x + i;
+  END_FOR;
+END_PROGRAM",
+        );
+
+        let resp = build_response(&id, &cache);
+
+        assert!(resp.ok);
+        assert_eq!(resp.tasks.len(), 1);
+        assert!(resp.tasks[0].bounded);
+        assert!(resp.tasks[0].wcet_units.unwrap() > 10);
+        assert_eq!(resp.tasks[0].programs, vec!["main".to_string()]);
+        assert!(resp.unbounded_loops.is_empty());
+    }
+
+    #[test]
+    fn build_response_when_while_loop_then_lists_unbounded_loop() {
+        let cache = new_cache();
+        let id = compile_into(
+            &cache,
+            "PROGRAM main
+  VAR x : DINT; END_VAR
+  WHILE x < 10 DO
+    x := x + 1;
+  END_WHILE;
+END_PROGRAM",
+        );
+
+        let resp = build_response(&id, &cache);
+
+        assert!(resp.ok);
+        assert!(!resp.tasks[0].bounded);
+        assert_eq!(resp.tasks[0].wcet_us, None);
+        assert_eq!(resp.unbounded_loops.len(), 1);
+        assert_eq!(resp.unbounded_loops[0].kind, Some("WHILE"));
+        assert_eq!(resp.unbounded_loops[0].file.as_deref(), Some("main.st"));
+        assert_eq!(resp.unbounded_loops[0].line, 3);
+    }
+
+    #[test]
+    fn build_response_when_unknown_container_then_not_ok() {
+        let cache = new_cache();
+
+        let resp = build_response("c_nonexistent", &cache);
+
+        assert!(!resp.ok);
+        assert!(resp.tasks.is_empty());
+        assert!(!resp.diagnostics.is_empty());
+    }
+}
@@ -140,11 +140,29 @@ pub struct FeatureDescriptor {
     pub dialects: &'static [Dialect],
 }
 
-/// Declares [`CompilerOptions`] with a set of dialect-extension boolean flags.
+/// Metadata for a single opt-in analysis check.
+///
+/// A check is off in every dialect. Turning it on makes the analyzer report
+/// an additional class of error, so unlike a feature it never accepts more
+/// code.
+pub struct CheckDescriptor {
+    /// The CLI flag name (e.g. `"--check-task-races"`).
+    pub cli_flag: &'static str,
+    /// The option key used in the MCP `options` object (e.g. `"check_task_races"`).
+    /// Matches the corresponding [`CompilerOptions`] field name.
+    pub option_key: &'static str,
+    /// A short human-readable description.
+    pub description: &'static str,
+}
+
+/// Declares [`CompilerOptions`] with a set of dialect-extension boolean flags
+/// followed by a `checks { ... }` set of opt-in analysis checks.
 ///
 /// Each field carries a description string and a list of [`Dialect`] variants
-/// that enable it.  The macro auto-generates the struct, its `Default` impl,
-/// [`CompilerOptions::from_dialect`], and [`CompilerOptions::FEATURE_DESCRIPTORS`].
+/// that enable it; each check carries a description string only.  The macro
+/// auto-generates the struct, its `Default` impl,
+/// [`CompilerOptions::from_dialect`], [`CompilerOptions::FEATURE_DESCRIPTORS`],
+/// and [`CompilerOptions::CHECK_DESCRIPTORS`].
 macro_rules! define_compiler_options {
     (
         $(
@@ -153,17 +171,26 @@ macro_rules! define_compiler_options {
             [$($dialect:ident),* $(,)?],
             $flag_field:ident
         ),* $(,)?
+        checks {
+            $(
+                $check_desc:literal,
+                $check_cli_flag:literal,
+                $check_field:ident
+            ),* $(,)?
+        }
     ) => {
         #[derive(Debug, Default, Clone, Copy)]
         pub struct CompilerOptions {
             $(pub $flag_field: bool,)*
+            $(pub $check_field: bool,)*
         }
 
         impl CompilerOptions {
             /// Build a [`CompilerOptions`] from a [`Dialect`] preset.
             ///
             /// Individual flags can be set to `true` afterwards to layer
-            /// additional extensions on top of the dialect.
+            /// additional extensions on top of the dialect. No dialect turns
+            /// on a check.
             pub fn from_dialect(dialect: Dialect) -> Self {
                 let mut opts = Self::default();
                 $(
@@ -186,8 +213,19 @@ macro_rules! define_compiler_options {
                 )*
             ];
 
-            /// Set a dialect-extension feature flag by its `option_key` (the
-            /// field name from [`FeatureDescriptor`]).
+            /// Metadata for every opt-in analysis check.
+            pub const CHECK_DESCRIPTORS: &[CheckDescriptor] = &[
+                $(
+                    CheckDescriptor {
+                        cli_flag: $check_cli_flag,
+                        option_key: stringify!($check_field),
+                        description: $check_desc,
+                    },
+                )*
+            ];
+
+            /// Set a feature flag or check by its `option_key` (the field
+            /// name from [`FeatureDescriptor`] or [`CheckDescriptor`]).
             ///
             /// Returns `true` if the key matched a known flag.
             pub fn set_flag_by_key(&mut self, key: &str, value: bool) -> bool {
@@ -198,12 +236,18 @@ macro_rules! define_compiler_options {
                             true
                         }
                     )*
+                    $(
+                        stringify!($check_field) => {
+                            self.$check_field = value;
+                            true
+                        }
+                    )*
                     _ => false,
                 }
             }
 
-            /// Get a dialect-extension feature flag by its `option_key` (the
-            /// field name from [`FeatureDescriptor`]).
+            /// Get a feature flag or check by its `option_key` (the field
+            /// name from [`FeatureDescriptor`] or [`CheckDescriptor`]).
             ///
             /// Returns `None` if the key does not match a known flag.
             pub fn get_flag_by_key(&self, key: &str) -> Option<bool> {
@@ -211,6 +255,9 @@ macro_rules! define_compiler_options {
                     $(
                         stringify!($flag_field) => Some(self.$flag_field),
                     )*
+                    $(
+                        stringify!($check_field) => Some(self.$check_field),
+                    )*
                     _ => None,
                 }
             }
@@ -353,6 +400,20 @@ define_compiler_options! {
     "--allow-fb-inheritance",
     [Rusty, Codesys, TwinCat],
     allow_fb_inheritance,
+
+    checks {
+        "Report shared VAR_GLOBAL accesses that race between tasks of different priorities",
+        "--check-task-races",
+        check_task_races,
+
+        "Report enumeration CASE statements that miss a value and CASE labels that repeat a value",
+        "--check-case-exhaustive",
+        check_case_exhaustive,
+
+        "Report variables and types that are never used, variables that are never read, and unreachable POUs",
+        "--check-unused",
+        check_unused,
+    }
 }
 
 /// Format a human-readable summary of all dialects and which features each
@@ -659,6 +720,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn check_descriptors_when_called_then_all_option_keys_start_with_check() {
+        for cd in CompilerOptions::CHECK_DESCRIPTORS {
+            assert!(
+                cd.option_key.starts_with("check_"),
+                "option_key {} does not start with check_",
+                cd.option_key
+            );
+        }
+    }
+
+    #[test]
+    fn from_dialect_when_any_dialect_then_no_check_enabled() {
+        for dialect in Dialect::ALL {
+            let options = CompilerOptions::from_dialect(*dialect);
+            for cd in CompilerOptions::CHECK_DESCRIPTORS {
+                assert_eq!(options.get_flag_by_key(cd.option_key), Some(false));
+            }
+        }
+    }
+
+    #[test]
+    fn set_flag_by_key_when_check_key_then_enables_check() {
+        let mut options = CompilerOptions::default();
+        assert!(options.set_flag_by_key("check_task_races", true));
+        assert!(options.check_task_races);
+    }
+
     #[test]
     fn dialect_display_name_when_ed2_then_human_readable() {
         assert_eq!(Dialect::Iec61131_3Ed2.display_name(), "IEC 61131-3 Ed. 2");
@@ -4,7 +4,7 @@
 //! `--allow-partial-access-syntax` (implied by the `rusty` and
 //! `iec61131-3-ed3` dialects).
 
-use dsl::diagnostic::{Diagnostic, Label};
+use dsl::diagnostic::{Diagnostic, FixKind, Label};
 
 use crate::{
     options::CompilerOptions,
@@ -35,6 +35,12 @@ pub fn apply(tokens: &[Token], options: &CompilerOptions) -> Result<(), Vec<Diag
                 ironplc_problems::Problem::PartialAccessSyntaxDisabled,
                 Label::span(t.span.clone(), "partial-access selector"),
             )
+            .with_fix(
+                "Enable --allow-partial-access-syntax",
+                FixKind::EnableOption {
+                    option_key: String::from("allow_partial_access_syntax"),
+                },
+            )
         })
         .collect();
 
@@ -1,6 +1,7 @@
 //! Numeric, real, duration and long-date/time literal parsing.
 
 use super::common::*;
+use dsl::core::Located;
 
 #[test]
 fn parse_program_when_complex_bit_string_then_ok() {
@@ -239,3 +240,30 @@ END_PROGRAM",
     let prog = cast!(&lib.elements[0], LibraryElementKind::ProgramDeclaration);
     assert_eq!(prog.variables.len(), 1);
 }
+
+#[test]
+fn parse_program_when_integer_literals_then_spans_cover_digits() {
+    let program = "
+FUNCTION fun : INT
+VAR
+    a : INT := -42;
+    b : WORD := 16#FF;
+END_VAR
+fun := DINT#7;
+END_FUNCTION";
+    let res = parse_text(program);
+
+    let function = cast!(&res.elements[0], LibraryElementKind::FunctionDeclaration);
+    let a = cast!(
+        &function.variables[0].initializer,
+        InitialValueAssignmentKind::Simple
+    );
+    let b = cast!(
+        &function.variables[1].initializer,
+        InitialValueAssignmentKind::Simple
+    );
+    let a_span = a.initial_value.as_ref().unwrap().span();
+    let b_span = b.initial_value.as_ref().unwrap().span();
+    assert_eq!(&program[a_span.start..a_span.end], "42");
+    assert_eq!(&program[b_span.start..b_span.end], "16#FF");
+}
@@ -591,6 +591,7 @@ fn compile_inner(source: &str, dialect: &str, allows: &str, libraries: &str) ->
     let output = ironplc_project::compile(
         &mut project,
         &options,
+        &ironplc_codegen::CodegenOptions::default(),
         &ironplc_codegen::EmptyLookup,
         vec![],
     );
@@ -683,7 +684,9 @@ fn run_bytes(bytes: &[u8], scans: u32) -> RunResult {
                 error: Some(RunError {
                     message: format!(
                         "VM trap during init: {} (task {}, instance {})",
-                        ctx.trap, ctx.task_id, ctx.instance_id
+                        ctx.trap.with_debug(container.debug_section.as_ref()),
+                        ctx.task_id,
+                        ctx.instance_id
                     ),
                     code: Some(ctx.trap.v_code().to_string()),
                     ..Default::default()
@@ -717,7 +720,7 @@ fn run_bytes(bytes: &[u8], scans: u32) -> RunResult {
                 error: Some(RunError {
                     message: format!(
                         "VM trap: {} (task {}, instance {})",
-                        faulted.trap(),
+                        faulted.trap().with_debug(container.debug_section.as_ref()),
                         faulted.task_id(),
                         faulted.instance_id()
                     ),
@@ -935,7 +938,10 @@ fn load_program_inner(
                 variables: vec![],
                 total_scans: 0,
                 error: Some(RunError {
-                    message: format!("VM init trap: {}", ctx.trap),
+                    message: format!(
+                        "VM init trap: {}",
+                        ctx.trap.with_debug(container.debug_section.as_ref())
+                    ),
                     code: Some(ctx.trap.v_code().to_string()),
                     ..Default::default()
                 }),
@@ -1106,7 +1112,7 @@ fn run_vm_scans(
             let error = RunError {
                 message: format!(
                     "VM trap: {} (task {}, instance {})",
-                    faulted.trap(),
+                    faulted.trap().with_debug(container.debug_section.as_ref()),
                     faulted.task_id(),
                     faulted.instance_id()
                 ),
@@ -0,0 +1,998 @@
+//! Formats Structured Text source while keeping comments, pragmas and the
+//! blank lines that group declarations and statements.
+//!
+//! The renderer writes a [`Library`](ironplc_dsl::common::Library) and so
+//! loses everything the parser does not keep. The formatter works on the
+//! tokens instead: it keeps every token in order and only decides the
+//! whitespace between them and the case of keywords. Line breaks are the
+//! author's; the formatter sets the indentation of each line from the block
+//! structure, collapses runs of blank lines to one and, optionally, aligns
+//! the `:` of neighbouring declarations.
+//!
+//! As a guard against changing what the source means, the formatted text is
+//! tokenized again and must have the same tokens as the input.
+
+use std::ops::Range;
+
+use ironplc_dsl::core::FileId;
+use ironplc_dsl::diagnostic::{Diagnostic, Label};
+use ironplc_parser::options::CompilerOptions;
+use ironplc_parser::token::{Token, TokenType};
+
+/// The case keywords are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeywordCase {
+    #[default]
+    Upper,
+    Lower,
+    /// Keywords are written as they are in the source.
+    Preserve,
+}
+
+/// What one level of indentation is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    Spaces(usize),
+    Tab,
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Indent::Spaces(4)
+    }
+}
+
+impl Indent {
+    fn unit(&self) -> String {
+        match self {
+            Indent::Spaces(width) => " ".repeat(*width),
+            Indent::Tab => "\t".to_string(),
+        }
+    }
+}
+
+/// How to lay out formatted source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    pub indent: Indent,
+    pub keyword_case: KeywordCase,
+    /// Whether the `:` of declarations on neighbouring lines in a `VAR`,
+    /// `TYPE` or `STRUCT` block line up.
+    pub align_declarations: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: Indent::default(),
+            keyword_case: KeywordCase::default(),
+            align_declarations: true,
+        }
+    }
+}
+
+/// One line of formatted output with the source lines it came from.
+#[derive(Debug, Clone)]
+struct FormattedLine {
+    /// Zero-based source lines. A line holding a block comment that spans
+    /// several source lines covers all of them.
+    source_lines: Range<usize>,
+    /// The line without its line break; empty for a blank line.
+    text: String,
+}
+
+/// Formatted source, kept by line so that part of a document can be
+/// formatted.
+#[derive(Debug, Clone)]
+pub struct Formatted {
+    lines: Vec<FormattedLine>,
+    /// The line break the source uses, which the formatted text keeps.
+    line_ending: &'static str,
+}
+
+impl Formatted {
+    /// The whole formatted source.
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| format!("{}{}", line.text, self.line_ending))
+            .collect()
+    }
+
+    /// The formatted text of the zero-based source lines `first..=last`
+    /// and the source lines it replaces. The replaced lines can extend past
+    /// `first` and `last` when a formatted line spans several source lines.
+    /// The text ends with a line break unless it is empty.
+    pub fn lines(&self, first: usize, last: usize) -> (Range<usize>, String) {
+        let mut replaced = first..last + 1;
+        let mut text = String::new();
+        for line in &self.lines {
+            if line.source_lines.end <= first || line.source_lines.start > last {
+                continue;
+            }
+            replaced.start = replaced.start.min(line.source_lines.start);
+            replaced.end = replaced.end.max(line.source_lines.end);
+            text.push_str(&line.text);
+            text.push_str(self.line_ending);
+        }
+        (replaced, text)
+    }
+}
+
+/// Formats the source. Fails with the parse diagnostics when the source does
+/// not parse, since the layout follows the block structure.
+pub fn format(
+    source: &str,
+    file_id: &FileId,
+    compiler_options: &CompilerOptions,
+    options: &FormatOptions,
+) -> Result<Formatted, Vec<Diagnostic>> {
+    ironplc_parser::parse_program(source, file_id, compiler_options).map_err(|err| vec![err])?;
+
+    let (tokens, _) = ironplc_parser::tokenize_program(source, file_id, compiler_options, 0, 0);
+    // The tokenizer drops some text (OSCAT range markers) that formatting
+    // would then lose.
+    if tokens
+        .iter()
+        .map(|tok| tok.text.as_str())
+        .collect::<String>()
+        != source
+    {
+        return Err(vec![Diagnostic::not_implemented(Label::file(
+            file_id.clone(),
+            "Formatting source with content the tokenizer removes",
+        ))]);
+    }
+
+    let formatted = Formatted {
+        lines: Layout::new(options).apply(split_lines(&tokens)),
+        line_ending: line_ending(source),
+    };
+
+    let (reformatted, _) =
+        ironplc_parser::tokenize_program(&formatted.text(), file_id, compiler_options, 0, 0);
+    if !same_tokens(&tokens, &reformatted) {
+        return Err(vec![Diagnostic::internal_error_at(Label::file(
+            file_id.clone(),
+            "Formatting changed the tokens of the source",
+        ))]);
+    }
+    Ok(formatted)
+}
+
+/// The line break of the source's first line: `\r\n` when it has one,
+/// otherwise `\n`.
+fn line_ending(source: &str) -> &'static str {
+    match source.find('\n') {
+        Some(end) if source[..end].ends_with('\r') => "\r\n",
+        _ => "\n",
+    }
+}
+
+/// A token on a source line.
+struct Piece<'a> {
+    token_type: &'a TokenType,
+    text: &'a str,
+    /// Whether whitespace separated the token from the previous one.
+    gap: bool,
+}
+
+/// A source line as the tokens on it; empty for a blank line.
+struct SourceLine<'a> {
+    pieces: Vec<Piece<'a>>,
+    lines: Range<usize>,
+}
+
+/// Splits the tokens at line breaks. A `//` comment carries the line break
+/// that ends it.
+fn split_lines(tokens: &[Token]) -> Vec<SourceLine<'_>> {
+    let mut lines = vec![];
+    let mut pieces = vec![];
+    let mut gap = false;
+    let mut start = 0;
+    let mut end = 0;
+
+    for tok in tokens {
+        match tok.token_type {
+            TokenType::Whitespace => gap = true,
+            TokenType::Newline => {
+                lines.push(SourceLine {
+                    pieces: std::mem::take(&mut pieces),
+                    lines: start..end + 1,
+                });
+                start = end + 1;
+                end = start;
+                gap = false;
+            }
+            _ if tok.text.is_empty() => {}
+            _ => {
+                let ends_line = tok.token_type == TokenType::Comment && tok.text.ends_with('\n');
+                let text = tok.text.trim_end_matches(['\r', '\n']);
+                end += text.matches('\n').count();
+                pieces.push(Piece {
+                    token_type: &tok.token_type,
+                    text,
+                    gap,
+                });
+                gap = false;
+                if ends_line {
+                    lines.push(SourceLine {
+                        pieces: std::mem::take(&mut pieces),
+                        lines: start..end + 1,
+                    });
+                    start = end + 1;
+                    end = start;
+                }
+            }
+        }
+    }
+    if !pieces.is_empty() {
+        lines.push(SourceLine {
+            pieces,
+            lines: start..end + 1,
+        });
+    }
+    lines
+}
+
+/// A block that indents the lines inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    /// A program, function, function block, method, interface, action or
+    /// SFC element.
+    Pou,
+    Var,
+    Type,
+    Struct,
+    Configuration,
+    Resource,
+    If,
+    Case,
+    For,
+    While,
+    Repeat,
+}
+
+struct Block {
+    kind: BlockKind,
+    /// The indentation of the line that opens the block.
+    level: usize,
+    /// Inside a `CASE`, whether a label has been seen, so statements are
+    /// under it.
+    after_label: bool,
+    /// Inside a `CASE`, whether `ELSE` has been seen, so no more labels
+    /// follow.
+    after_else: bool,
+}
+
+impl Block {
+    /// The indentation of the lines the block contains.
+    fn body_level(&self) -> usize {
+        match self.kind {
+            BlockKind::Case if self.after_label && !self.after_else => self.level + 2,
+            _ => self.level + 1,
+        }
+    }
+
+    fn is_declarations(&self) -> bool {
+        matches!(
+            self.kind,
+            BlockKind::Var
+                | BlockKind::Type
+                | BlockKind::Struct
+                | BlockKind::Configuration
+                | BlockKind::Resource
+        )
+    }
+}
+
+/// A line laid out but not yet indented or aligned.
+struct LaidLine {
+    level: usize,
+    content: String,
+    /// Where the declaration `:` is, to align it with its neighbours.
+    colon: Option<usize>,
+    source_lines: Range<usize>,
+}
+
+struct Layout<'o> {
+    options: &'o FormatOptions,
+    blocks: Vec<Block>,
+    /// Parentheses and brackets open at the end of the previous line.
+    depth: usize,
+}
+
+impl<'o> Layout<'o> {
+    fn new(options: &'o FormatOptions) -> Self {
+        Self {
+            options,
+            blocks: vec![],
+            depth: 0,
+        }
+    }
+
+    fn apply(mut self, source: Vec<SourceLine>) -> Vec<FormattedLine> {
+        let mut laid: Vec<Option<LaidLine>> = vec![];
+        for line in source {
+            if line.pieces.is_empty() {
+                // Keep one blank line between groups; none at the start.
+                if laid.last().is_some_and(|last| last.is_some()) {
+                    laid.push(None);
+                }
+                continue;
+            }
+            laid.push(Some(self.lay_out(&line)));
+        }
+        while laid.last().is_some_and(|last| last.is_none()) {
+            laid.pop();
+        }
+
+        if self.options.align_declarations {
+            align(&mut laid);
+        }
+
+        let unit = self.options.indent.unit();
+        let mut lines = vec![];
+        let mut next_line = 0;
+        for line in laid {
+            match line {
+                Some(line) => {
+                    next_line = line.source_lines.end;
+                    lines.push(FormattedLine {
+                        source_lines: line.source_lines,
+                        text: format!("{}{}", unit.repeat(line.level), line.content),
+                    });
+                }
+                None => lines.push(FormattedLine {
+                    source_lines: next_line..next_line + 1,
+                    text: String::new(),
+                }),
+            }
+        }
+        lines
+    }
+
+    fn lay_out(&mut self, line: &SourceLine) -> LaidLine {
+        let first = line
+            .pieces
+            .iter()
+            .position(|piece| !matches!(piece.token_type, TokenType::Comment | TokenType::Pragma));
+        let mut rest = 0;
+        let level = match first.map(|index| (index, line.pieces[index].token_type)) {
+            Some((index, token_type)) => {
+                let (level, closed) = self.line_level(token_type, line);
+                if closed {
+                    rest = index + 1;
+                }
+                level
+            }
+            None => self.body_level(),
+        } + usize::from(self.depth > 0);
+
+        let declarations = self.blocks.last().is_some_and(Block::is_declarations)
+            || first.is_some_and(|index| {
+                matches!(
+                    line.pieces[index].token_type,
+                    TokenType::Function | TokenType::FunctionBlock | TokenType::Method
+                )
+            });
+        let aligned = self.options.align_declarations
+            && matches!(
+                self.blocks.last().map(|block| block.kind),
+                Some(BlockKind::Var | BlockKind::Type | BlockKind::Struct)
+            )
+            && first.is_some_and(|index| *line.pieces[index].token_type == TokenType::Identifier);
+        let (content, colon) = self.render(&line.pieces, declarations);
+
+        for piece in &line.pieces[rest..] {
+            self.track(piece.token_type, level);
+        }
+
+        LaidLine {
+            level,
+            content,
+            colon: colon.filter(|_| aligned),
+            source_lines: line.lines.clone(),
+        }
+    }
+
+    fn body_level(&self) -> usize {
+        self.blocks.last().map_or(0, Block::body_level)
+    }
+
+    /// The indentation of a line that starts with the token, and whether the
+    /// token closed a block (so it is not tracked again).
+    fn line_level(&mut self, token_type: &TokenType, line: &SourceLine) -> (usize, bool) {
+        if let Some(kind) = closes(token_type) {
+            if let Some(index) = self.blocks.iter().rposition(|block| block.kind == kind) {
+                let level = self.blocks[index].level;
+                self.blocks.truncate(index);
+                return (level, true);
+            }
+            return (self.body_level(), true);
+        }
+
+        let Some(top) = self.blocks.last_mut() else {
+            return (0, false);
+        };
+        match (top.kind, token_type) {
+            (BlockKind::If, TokenType::Else | TokenType::Elsif) => (top.level, false),
+            (BlockKind::Case, TokenType::Else) => {
+                top.after_else = true;
+                (top.level, false)
+            }
+            (BlockKind::Repeat, TokenType::Until) => (top.level, false),
+            // Sections and methods of a POU are not indented under it; its
+            // body is.
+            (BlockKind::Pou, token_type)
+                if is_section(token_type)
+                    || matches!(token_type, TokenType::Method | TokenType::Action) =>
+            {
+                (top.level, false)
+            }
+            (BlockKind::Case, _) if !top.after_else && is_case_label(line) => {
+                top.after_label = true;
+                (top.level + 1, false)
+            }
+            _ => (top.body_level(), false),
+        }
+    }
+
+    /// Opens and closes blocks for a token on a line at `level`, and counts
+    /// open parentheses.
+    fn track(&mut self, token_type: &TokenType, level: usize) {
+        match token_type {
+            TokenType::LeftParen | TokenType::LeftBracket => self.depth += 1,
+            TokenType::RightParen | TokenType::RightBracket => {
+                self.depth = self.depth.saturating_sub(1)
+            }
+            _ => {}
+        }
+        if let Some(kind) = closes(token_type) {
+            if let Some(index) = self.blocks.iter().rposition(|block| block.kind == kind) {
+                self.blocks.truncate(index);
+            }
+            return;
+        }
+        let in_configuration = matches!(
+            self.blocks.last().map(|block| block.kind),
+            Some(BlockKind::Configuration | BlockKind::Resource)
+        );
+        let kind = match token_type {
+            // `PROGRAM name WITH task : Type;` in a resource is a statement.
+            TokenType::Program if in_configuration => return,
+            TokenType::Program
+            | TokenType::Function
+            | TokenType::FunctionBlock
+            | TokenType::Method
+            | TokenType::Interface
+            | TokenType::Action
+            | TokenType::Step
+            | TokenType::InitialStep
+            | TokenType::Transition => BlockKind::Pou,
+            TokenType::Type => BlockKind::Type,
+            TokenType::Struct => BlockKind::Struct,
+            TokenType::Configuration => BlockKind::Configuration,
+            TokenType::Resource => BlockKind::Resource,
+            TokenType::If => BlockKind::If,
+            TokenType::Case => BlockKind::Case,
+            TokenType::For => BlockKind::For,
+            TokenType::While => BlockKind::While,
+            TokenType::Repeat => BlockKind::Repeat,
+            token_type if is_section(token_type) => BlockKind::Var,
+            _ => return,
+        };
+        self.blocks.push(Block {
+            kind,
+            level,
+            after_label: false,
+            after_else: false,
+        });
+    }
+
+    /// Writes the tokens of a line with normalized spacing, returning the
+    /// text and where a declaration `:` starts.
+    fn render(&self, pieces: &[Piece], declarations: bool) -> (String, Option<usize>) {
+        let declaration_colon = declarations.then(|| first_colon(pieces)).flatten();
+        let mut content = String::new();
+        let mut colon = None;
+        for (index, piece) in pieces.iter().enumerate() {
+            if index > 0 {
+                let around_colon =
+                    declaration_colon.is_some_and(|at| at == index || at + 1 == index);
+                if around_colon || spaced(pieces[index - 1].token_type, piece) {
+                    content.push(' ');
+                }
+            }
+            if declaration_colon == Some(index) {
+                colon = Some(content.trim_end().len());
+            }
+            content.push_str(&self.keyword_case(piece));
+        }
+        // A block comment before the `:` makes the column meaningless.
+        let colon = colon.filter(|at| !content[..*at].contains('\n'));
+        (content, colon)
+    }
+
+    fn keyword_case(&self, piece: &Piece) -> String {
+        if !is_keyword(piece.token_type, piece.text) {
+            return piece.text.to_string();
+        }
+        match self.options.keyword_case {
+            KeywordCase::Upper => piece.text.to_uppercase(),
+            KeywordCase::Lower => piece.text.to_lowercase(),
+            KeywordCase::Preserve => piece.text.to_string(),
+        }
+    }
+}
+
+/// Whether a space goes between two tokens on a line. Spacing only changes
+/// next to tokens that cannot merge with a neighbour, so the tokens stay
+/// the same.
+fn spaced(previous: &TokenType, next: &Piece) -> bool {
+    match (previous, next.token_type) {
+        (_, TokenType::Comma | TokenType::Semicolon) => false,
+        (TokenType::Comma, _) => true,
+        (TokenType::Assignment | TokenType::RightArrow, _)
+        | (_, TokenType::Assignment | TokenType::RightArrow) => true,
+        _ => next.gap,
+    }
+}
+
+/// The `:` that separates the declared names from the type: the first one
+/// outside parentheses. Later ones are part of the type or initial value,
+/// such as in `TOD#14:30:00`.
+fn first_colon(pieces: &[Piece]) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, piece) in pieces.iter().enumerate() {
+        match piece.token_type {
+            TokenType::LeftParen | TokenType::LeftBracket => depth += 1,
+            TokenType::RightParen | TokenType::RightBracket => depth = depth.saturating_sub(1),
+            TokenType::Colon if depth == 0 => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Pads declarations on neighbouring lines so that their `:` line up.
+fn align(lines: &mut [Option<LaidLine>]) {
+    let mut start = 0;
+    while start < lines.len() {
+        let Some(Some(first)) = lines.get(start) else {
+            start += 1;
+            continue;
+        };
+        let level = first.level;
+        let mut end = start;
+        while let Some(Some(line)) = lines.get(end) {
+            if line.colon.is_none() || line.level != level {
+                break;
+            }
+            end += 1;
+        }
+        if end == start {
+            start += 1;
+            continue;
+        }
+        let column = lines[start..end]
+            .iter()
+            .flatten()
+            .filter_map(|line| line.colon)
+            .max()
+            .unwrap_or_default();
+        for line in lines[start..end].iter_mut().flatten() {
+            if let Some(at) = line.colon {
+                let padding = column - at;
+                line.content.insert_str(at, &" ".repeat(padding));
+            }
+        }
+        start = end;
+    }
+}
+
+/// The kind of block the token ends.
+fn closes(token_type: &TokenType) -> Option<BlockKind> {
+    match token_type {
+        TokenType::EndProgram
+        | TokenType::EndFunction
+        | TokenType::EndFunctionBlock
+        | TokenType::EndMethod
+        | TokenType::EndInterface
+        | TokenType::EndAction
+        | TokenType::EndStep
+        | TokenType::EndTransition => Some(BlockKind::Pou),
+        TokenType::EndVar => Some(BlockKind::Var),
+        TokenType::EndType => Some(BlockKind::Type),
+        TokenType::EndStruct => Some(BlockKind::Struct),
+        TokenType::EndConfiguration => Some(BlockKind::Configuration),
+        TokenType::EndResource => Some(BlockKind::Resource),
+        TokenType::EndIf => Some(BlockKind::If),
+        TokenType::EndCase => Some(BlockKind::Case),
+        TokenType::EndFor => Some(BlockKind::For),
+        TokenType::EndWhile => Some(BlockKind::While),
+        TokenType::EndRepeat => Some(BlockKind::Repeat),
+        _ => None,
+    }
+}
+
+fn is_section(token_type: &TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Var
+            | TokenType::VarInput
+            | TokenType::VarOutput
+            | TokenType::VarInOut
+            | TokenType::VarTemp
+            | TokenType::VarExternal
+            | TokenType::VarGlobal
+            | TokenType::VarAccess
+            | TokenType::VarConfig
+    )
+}
+
+/// Whether a line in a `CASE` is a label: it has a `:` outside parentheses.
+fn is_case_label(line: &SourceLine) -> bool {
+    first_colon(&line.pieces).is_some()
+}
+
+/// Whether the token is a keyword: a word the tokenizer did not lex as an
+/// identifier. Keywords a dialect does not have are lexed as identifiers.
+fn is_keyword(token_type: &TokenType, text: &str) -> bool {
+    *token_type != TokenType::Identifier
+        && !text.is_empty()
+        && text.chars().all(|c| c.is_ascii_alphabetic() || c == '_')
+}
+
+/// Whether two token streams are the same apart from whitespace, line
+/// breaks and the case of keywords.
+fn same_tokens(before: &[Token], after: &[Token]) -> bool {
+    let significant = |tokens: &[Token]| -> Vec<(TokenType, String)> {
+        tokens
+            .iter()
+            .filter(|tok| {
+                !matches!(tok.token_type, TokenType::Whitespace | TokenType::Newline)
+                    && !tok.text.is_empty()
+            })
+            .map(|tok| {
+                let text = tok.text.trim_end_matches(['\r', '\n']);
+                let text = if is_keyword(&tok.token_type, text) {
+                    text.to_uppercase()
+                } else {
+                    text.to_string()
+                };
+                (tok.token_type.clone(), text)
+            })
+            .collect()
+    };
+    significant(before) == significant(after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format_default(source: &str) -> String {
+        format_with(source, &FormatOptions::default())
+    }
+
+    fn format_with(source: &str, options: &FormatOptions) -> String {
+        format(
+            source,
+            &FileId::default(),
+            &CompilerOptions::default(),
+            options,
+        )
+        .unwrap()
+        .text()
+    }
+
+    #[test]
+    fn format_when_crlf_line_endings_then_keeps_crlf() {
+        let source =
+            "PROGRAM Main\r\nVAR\r\nx : INT; (* count *)\r\nEND_VAR\r\nx := 1;\r\nEND_PROGRAM\r\n";
+
+        assert_eq!(
+            format_default(source),
+            "PROGRAM Main\r\nVAR\r\n    x : INT; (* count *)\r\nEND_VAR\r\n    x := 1;\r\nEND_PROGRAM\r\n"
+        );
+    }
+
+    #[test]
+    fn lines_when_crlf_line_endings_then_keeps_crlf() {
+        let source = "PROGRAM Main\r\nVAR\r\nx : INT;\r\nEND_VAR\r\nEND_PROGRAM\r\n";
+        let formatted = format(
+            source,
+            &FileId::default(),
+            &CompilerOptions::default(),
+            &FormatOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            formatted.lines(2, 2),
+            (2..3, "    x : INT;\r\n".to_string())
+        );
+    }
+
+    #[test]
+    fn format_when_unindented_body_then_indents_blocks() {
+        let source = "PROGRAM Main
+VAR
+x : INT;
+END_VAR
+IF x > 0 THEN
+x := 0;
+ELSE
+x := 1;
+END_IF;
+END_PROGRAM
+";
+
+        assert_eq!(
+            format_default(source),
+            "PROGRAM Main
+VAR
+    x : INT;
+END_VAR
+    IF x > 0 THEN
+        x := 0;
+    ELSE
+        x := 1;
+    END_IF;
+END_PROGRAM
+"
+        );
+    }
+
+    #[test]
+    fn format_when_comments_and_blank_lines_then_keeps_them() {
+        let source = "PROGRAM Main
+VAR
+  (* the count *)
+  x : INT; (* trailing *)
+
+
+
+  y : INT;
+END_VAR
+  x := 1;
+END_PROGRAM
+";
+
+        assert_eq!(
+            format_default(source),
+            "PROGRAM Main
+VAR
+    (* the count *)
+    x : INT; (* trailing *)
+
+    y : INT;
+END_VAR
+    x := 1;
+END_PROGRAM
+"
+        );
+    }
+
+    #[test]
+    fn format_when_declarations_then_aligns_colons_in_group() {
+        let source = "PROGRAM Main
+VAR
+    x:INT;
+    Longer : BOOL := TRUE;
+
+    y : INT;
+END_VAR
+END_PROGRAM
+";
+
+        assert_eq!(
+            format_default(source),
+            "PROGRAM Main
+VAR
+    x      : INT;
+    Longer : BOOL := TRUE;
+
+    y : INT;
+END_VAR
+END_PROGRAM
+"
+        );
+    }
+
+    #[test]
+    fn format_when_lower_keywords_and_tab_then_applies_options() {
+        let source = "PROGRAM Main
+VAR
+    x : INT;
+END_VAR
+    x := 1;
+END_PROGRAM
+";
+        let options = FormatOptions {
+            indent: Indent::Tab,
+            keyword_case: KeywordCase::Lower,
+            align_declarations: false,
+        };
+
+        assert_eq!(
+            format_with(source, &options),
+            "program Main
+var
+\tx : int;
+end_var
+\tx := 1;
+end_program
+"
+        );
+    }
+
+    #[test]
+    fn format_when_case_then_indents_statements_under_labels() {
+        let source = "PROGRAM Main
+VAR
+    x : INT;
+END_VAR
+CASE x OF
+1:
+x := 2;
+2, 3: x := 4;
+ELSE
+x := 0;
+END_CASE;
+END_PROGRAM
+";
+
+        assert_eq!(
+            format_default(source),
+            "PROGRAM Main
+VAR
+    x : INT;
+END_VAR
+    CASE x OF
+        1:
+            x := 2;
+        2, 3: x := 4;
+    ELSE
+        x := 0;
+    END_CASE;
+END_PROGRAM
+"
+        );
+    }
+
+    #[test]
+    fn format_when_one_line_if_and_case_then_keeps_them_on_one_line() {
+        let source = "PROGRAM Main
+VAR
+    a : INT;
+END_VAR
+IF a>1 THEN a:=2; END_IF; (* set *)
+CASE a OF 1: a:=3; ELSE a:=4; END_CASE;
+a := 5;
+END_PROGRAM
+";
+
+        assert_eq!(
+            format_default(source),
+            "PROGRAM Main
+VAR
+    a : INT;
+END_VAR
+    IF a>1 THEN a := 2; END_IF; (* set *)
+    CASE a OF 1: a := 3; ELSE a := 4; END_CASE;
+    a := 5;
+END_PROGRAM
+"
+        );
+    }
+
+    #[test]
+    fn format_when_call_spans_lines_then_continuation_indented() {
+        let source = "PROGRAM Main
+VAR
+    x : INT;
+END_VAR
+x := LIMIT(0,
+x,
+10);
+END_PROGRAM
+";
+
+        assert_eq!(
+            format_default(source),
+            "PROGRAM Main
+VAR
+    x : INT;
+END_VAR
+    x := LIMIT(0,
+        x,
+        10);
+END_PROGRAM
+"
+        );
+    }
+
+    #[test]
+    fn format_when_configuration_then_indents_resource() {
+        let source = "PROGRAM Main
+END_PROGRAM
+CONFIGURATION Plant
+RESOURCE Cpu ON PLC
+TASK Fast(INTERVAL := T#10ms, PRIORITY := 1);
+PROGRAM Instance WITH Fast : Main;
+END_RESOURCE
+END_CONFIGURATION
+";
+
+        assert_eq!(
+            format_default(source),
+            "PROGRAM Main
+END_PROGRAM
+CONFIGURATION Plant
+    RESOURCE Cpu ON PLC
+        TASK Fast(INTERVAL := T#10ms, PRIORITY := 1);
+        PROGRAM Instance WITH Fast : Main;
+    END_RESOURCE
+END_CONFIGURATION
+"
+        );
+    }
+
+    #[test]
+    fn format_when_formatted_then_unchanged() {
+        let source = "TYPE
+    Point : STRUCT
+        X : INT;
+        Y : INT;
+    END_STRUCT;
+END_TYPE
+FUNCTION_BLOCK Counter
+VAR_INPUT
+    Delta : INT;
+END_VAR
+    WHILE Delta > 0 DO
+        Delta := Delta - 1;
+    END_WHILE;
+END_FUNCTION_BLOCK
+";
+
+        assert_eq!(format_default(source), source);
+    }
+
+    #[test]
+    fn format_when_syntax_error_then_err() {
+        let result = format(
+            "PROGRAM Main\n    x := ;\nEND_PROGRAM\n",
+            &FileId::default(),
+            &CompilerOptions::default(),
+            &FormatOptions::default(),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn lines_when_range_then_only_those_lines_formatted() {
+        let source = "PROGRAM Main
+VAR
+x : INT;
+END_VAR
+x := 1;
+END_PROGRAM
+";
+        let formatted = format(
+            source,
+            &FileId::default(),
+            &CompilerOptions::default(),
+            &FormatOptions::default(),
+        )
+        .unwrap();
+
+        let (replaced, text) = formatted.lines(4, 4);
+
+        assert_eq!(replaced, 4..5);
+        assert_eq!(text, "    x := 1;\n");
+    }
+}
@@ -6,6 +6,7 @@ extern crate ironplc_dsl as dsl;
 use ironplc_dsl::{common::Library, diagnostic::Diagnostic};
 use renderer::apply;
 
+pub mod formatter;
 mod renderer;
 #[cfg(test)]
 mod tests;
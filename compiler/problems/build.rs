@@ -128,6 +128,23 @@ fn create_problems() -> Result<(), Box<dyn Error>> {
         )?;
     }
     out.write_all(b"        }\n")?;
+    out.write_all(b"    }\n\n")?;
+
+    // Define from_code()
+    out.write_all(b"    /// Returns the problem with the code, such as `P4041`.\n")?;
+    out.write_all(b"    pub fn from_code(code: &str) -> Option<Problem> {\n")?;
+    out.write_all(b"        match code {\n")?;
+    for def in &defs {
+        out.write_all(
+            format!(
+                "            \"{}\" => Some(Problem::{}),\n",
+                def.code, def.name
+            )
+            .as_bytes(),
+        )?;
+    }
+    out.write_all(b"            _ => None,\n")?;
+    out.write_all(b"        }\n")?;
     out.write_all(b"    }\n")?;
 
     out.write_all(b"}\n")?;
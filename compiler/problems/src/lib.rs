@@ -1,3 +1,65 @@
 //! Provides compiler error definitions.
 
 include!(concat!(env!("OUT_DIR"), "/problems.rs"));
+
+/// The problems a user may demote to a warning (ADR-0039).
+///
+/// Each entry is stylistic or a cross-compiler portability hint, and the
+/// compiler produces the same program whether or not it is reported. Every
+/// problem not listed is safety-relevant: ignoring it could change what the
+/// program does, so it stays an error whatever the configuration asks for.
+const DEMOTABLE: &[&str] = &[
+    "P4028", // Top-level VAR_GLOBAL outside a CONFIGURATION (portability).
+    "P4036", // AT-located variable in a plain VAR block (portability).
+    "P4041", // Bit-string literal as a CASE label (portability).
+    "P4043", // General expression as a structure initializer value (portability).
+    "P4054", // Variable or data type is never used (stylistic).
+    "P4055", // Variable is assigned but never read (stylistic).
+    "P4056", // POU is not reachable from any program (stylistic).
+    "P4057", // Name does not follow the project's naming convention (stylistic).
+    "P6013", // File is not formatted (stylistic).
+];
+
+impl Problem {
+    /// Returns `true` when the problem must stay an error: demoting it to
+    /// a warning could hide something that changes what the program does.
+    pub fn is_safety_relevant(&self) -> bool {
+        !DEMOTABLE.contains(&self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_code_when_known_code_then_problem() {
+        let problem = Problem::from_code("P4041").unwrap();
+        assert_eq!(problem.code(), "P4041");
+    }
+
+    #[test]
+    fn from_code_when_unknown_code_then_none() {
+        assert!(Problem::from_code("P0000").is_none());
+    }
+
+    #[test]
+    fn is_safety_relevant_when_portability_hint_then_false() {
+        assert!(!Problem::BitStringCaseLabelNotAllowed.is_safety_relevant());
+    }
+
+    #[test]
+    fn is_safety_relevant_when_type_error_then_true() {
+        assert!(Problem::AssignmentTypeMismatch.is_safety_relevant());
+    }
+
+    #[test]
+    fn demotable_when_listed_then_every_code_exists() {
+        for code in DEMOTABLE {
+            assert!(
+                Problem::from_code(code).is_some(),
+                "{code} is not a problem"
+            );
+        }
+    }
+}
@@ -24,12 +24,13 @@ pub struct CompileOutput {
     /// Every diagnostic the pipeline collected, in stage order: the
     /// caller-supplied ones first, then parsing and analysis, then codegen.
     ///
-    /// Empty means the project is clean.
+    /// Empty means the project is clean. Diagnostics demoted to warnings
+    /// (ADR-0039) stay here but do not prevent a container.
     pub diagnostics: Vec<Diagnostic>,
 
     /// The generated container.
     ///
-    /// `Some` only when no stage reported a problem, so a caller can treat
+    /// `Some` only when no stage reported an error, so a caller can treat
     /// this as "there is something worth writing out". A failing compile never
     /// yields a container, because a failing command must not leave behind a
     /// deployable artifact.
@@ -47,13 +48,16 @@ pub struct CompileOutput {
 /// container from being produced.
 ///
 /// Analysis always runs. Codegen runs only when nothing at all -- seeded,
-/// parse, or analysis -- reported a problem.
+/// parse, or analysis -- reported an error; warnings do not block it.
 ///
 /// `source_lookup` hands codegen the exact bytes the parser saw for each file,
 /// which the container's debug section hashes so a debugger can detect drift.
 /// Callers with no source bytes to offer pass
 /// [`EmptyLookup`](ironplc_codegen::EmptyLookup).
 ///
+/// `codegen_options` carries the code generation choices that are not
+/// language options; `system_uptime_global` is taken from `compiler_options`.
+///
 /// The analyzed library and semantic context stay cached on `project`, so a
 /// caller that needs them (for task/program metadata, or to build a symbol
 /// map) reads them back through [`Project::analyzed_library`] and
@@ -61,6 +65,7 @@ pub struct CompileOutput {
 pub fn compile(
     project: &mut dyn Project,
     compiler_options: &CompilerOptions,
+    codegen_options: &CodegenOptions,
     source_lookup: &dyn SourceLookup,
     mut diagnostics: Vec<Diagnostic>,
 ) -> CompileOutput {
@@ -68,8 +73,9 @@ pub fn compile(
     // it found; it never short-circuits on the seeded diagnostics.
     diagnostics.extend(project.semantic());
 
-    if !diagnostics.is_empty() {
-        debug!("Skipping codegen, {} problem(s) found", diagnostics.len());
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    if errors > 0 {
+        debug!("Skipping codegen, {errors} error(s) found");
         return CompileOutput {
             diagnostics,
             container: None,
@@ -91,6 +97,7 @@ pub fn compile(
     // the PROGRAM root to reduce container size.
     let codegen_options = CodegenOptions {
         system_uptime_global: compiler_options.allow_system_uptime_global,
+        ..codegen_options.clone()
     };
 
     match ironplc_codegen::compile(library, context, &codegen_options, source_lookup) {
@@ -110,9 +117,9 @@ pub fn compile(
 
 #[cfg(test)]
 mod tests {
-    use ironplc_codegen::EmptyLookup;
+    use ironplc_codegen::{CodegenOptions, EmptyLookup};
     use ironplc_dsl::core::{FileId, SourceSpan};
-    use ironplc_dsl::diagnostic::{Diagnostic, Label};
+    use ironplc_dsl::diagnostic::{Demotions, Diagnostic, Label};
     use ironplc_parser::options::CompilerOptions;
     use ironplc_problems::Problem;
 
@@ -149,6 +156,7 @@ END_PROGRAM
         let output = compile(
             &mut project,
             &CompilerOptions::default(),
+            &CodegenOptions::default(),
             &EmptyLookup,
             vec![],
         );
@@ -167,6 +175,7 @@ END_PROGRAM
         let output = compile(
             &mut project,
             &CompilerOptions::default(),
+            &CodegenOptions::default(),
             &EmptyLookup,
             vec![],
         );
@@ -182,6 +191,7 @@ END_PROGRAM
         let output = compile(
             &mut project,
             &CompilerOptions::default(),
+            &CodegenOptions::default(),
             &EmptyLookup,
             vec![],
         );
@@ -201,6 +211,7 @@ END_PROGRAM
         let output = compile(
             &mut project,
             &CompilerOptions::default(),
+            &CodegenOptions::default(),
             &EmptyLookup,
             seeded_diagnostic(),
         );
@@ -220,6 +231,7 @@ END_PROGRAM
         let output = compile(
             &mut project,
             &CompilerOptions::default(),
+            &CodegenOptions::default(),
             &EmptyLookup,
             seeded_diagnostic(),
         );
@@ -237,6 +249,7 @@ END_PROGRAM
         let output = compile(
             &mut project,
             &CompilerOptions::default(),
+            &CodegenOptions::default(),
             &EmptyLookup,
             vec![],
         );
@@ -247,6 +260,89 @@ END_PROGRAM
         assert!(project.semantic_context().is_some());
     }
 
+    fn compile_demoted(content: &str, code: &str) -> super::CompileOutput {
+        let mut demotions = Demotions::default();
+        demotions.demote(code).unwrap();
+        let mut project = project_with(content);
+        project.set_demotions(demotions);
+        compile(
+            &mut project,
+            &CompilerOptions::default(),
+            &CodegenOptions::default(),
+            &EmptyLookup,
+            vec![],
+        )
+    }
+
+    fn assert_warning_only(output: &super::CompileOutput, code: &str) {
+        assert!(
+            output
+                .diagnostics
+                .iter()
+                .all(|d| d.code == code && !d.is_error()),
+            "expected only {code} warnings, got: {:?}",
+            output.diagnostics
+        );
+        assert!(!output.diagnostics.is_empty());
+        assert!(output.container.is_some());
+    }
+
+    #[test]
+    fn compile_when_bit_string_case_label_demoted_then_container_and_warning() {
+        let output = compile_demoted(
+            "PROGRAM Main VAR x : DINT; y : INT; END_VAR CASE x OF 16#D012: y := 1; END_CASE; END_PROGRAM",
+            "P4041",
+        );
+        assert_warning_only(&output, "P4041");
+    }
+
+    #[test]
+    fn compile_when_top_level_var_global_demoted_then_container_and_warning() {
+        let output = compile_demoted(
+            "VAR_GLOBAL CONSTANT LIMIT_HI : INT := 250; END_VAR PROGRAM Main VAR x : INT; END_VAR x := LIMIT_HI; END_PROGRAM",
+            "P4028",
+        );
+        assert_warning_only(&output, "P4028");
+    }
+
+    #[test]
+    fn compile_when_mixed_located_var_demoted_then_container_and_warning() {
+        let output = compile_demoted(
+            "FUNCTION_BLOCK Reader VAR sensor AT %I* : INT; x : INT; END_VAR x := sensor; END_FUNCTION_BLOCK PROGRAM Main VAR r : Reader; END_VAR r(); END_PROGRAM",
+            "P4036",
+        );
+        assert_warning_only(&output, "P4036");
+    }
+
+    /// Demotion has the effect of the extension flag and no more: code
+    /// generation does not support the construct yet either way.
+    #[test]
+    fn compile_when_struct_initializer_expression_demoted_then_warning() {
+        let output = compile_demoted(
+            "TYPE Settings : STRUCT x : INT; END_STRUCT; END_TYPE PROGRAM Main VAR base : INT := 4; s : Settings := (x := base + 1); END_VAR s.x := 1; END_PROGRAM",
+            "P4043",
+        );
+        assert!(output
+            .diagnostics
+            .iter()
+            .any(|d| d.code == "P4043" && !d.is_error()));
+        assert!(output
+            .diagnostics
+            .iter()
+            .all(|d| d.code != "P4043" || !d.is_error()));
+    }
+
+    #[test]
+    fn compile_when_demoted_warning_and_error_then_no_container() {
+        let output = compile_demoted(
+            "PROGRAM Main VAR x : DINT; y : INT; END_VAR CASE x OF 16#D012: y := undeclared_var; END_CASE; END_PROGRAM",
+            "P4041",
+        );
+        assert!(output.diagnostics.iter().any(|d| !d.is_error()));
+        assert!(output.diagnostics.iter().any(|d| d.is_error()));
+        assert!(output.container.is_none());
+    }
+
     // The "clean analysis cached no artifacts" arm has no test: it guards an
     // invariant the `Project` implementations cannot break, so reaching it
     // means a compiler defect. Proving it with a stub `Project` costs more
@@ -0,0 +1,250 @@
+//! Which sources depend on which, judged by the names that each source
+//! declares and the names that it uses.
+//!
+//! A source depends on another when it uses a name the other declares: a
+//! type, a POU, an enumeration value or a global variable. Names are
+//! compared without regard to case and without resolving scopes, so a local
+//! variable that happens to share a global's name makes its source depend on
+//! the global's. That over-approximates the dependencies, which only costs
+//! analyzing more than is needed.
+
+use std::collections::{HashMap, HashSet};
+
+use ironplc_dsl::common::{
+    DataTypeDeclarationKind, EnumeratedSpecificationValues, Library, LibraryElementKind, VarDecl,
+    VariableIdentifier, VariableType,
+};
+use ironplc_dsl::core::{FileId, Id};
+use ironplc_dsl::visitor::Visitor;
+
+/// The names a source declares for other sources to use, and every name it
+/// uses, in lower case.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SourceNames {
+    pub(crate) declared: HashSet<String>,
+    used: HashSet<String>,
+}
+
+impl SourceNames {
+    pub(crate) fn of(library: &Library) -> Self {
+        let mut collector = NameCollector {
+            declared: HashSet::new(),
+            used: HashSet::new(),
+        };
+        for element in &library.elements {
+            if let Some(name) = element_name(element) {
+                collector.declared.insert(name.lower_case().clone());
+            }
+        }
+        let _ = collector.walk(library);
+        SourceNames {
+            declared: collector.declared,
+            used: collector.used,
+        }
+    }
+
+    fn uses_any(&self, names: &HashSet<String>) -> bool {
+        !self.used.is_disjoint(names)
+    }
+}
+
+/// The changed sources and every source that depends on one of them,
+/// directly or through other sources: the sources whose analysis a change
+/// can alter.
+pub(crate) fn affected(
+    changed: &HashSet<FileId>,
+    names: &HashMap<FileId, SourceNames>,
+) -> HashSet<FileId> {
+    closure(changed, |file| {
+        let declared = &names.get(file)?.declared;
+        Some(
+            names
+                .iter()
+                .filter(|(_, other)| other.uses_any(declared))
+                .map(|(other, _)| other.clone())
+                .collect(),
+        )
+    })
+}
+
+/// The sources and every source they depend on, directly or through other
+/// sources: what analysis of the sources needs.
+pub(crate) fn with_dependencies(
+    files: &HashSet<FileId>,
+    names: &HashMap<FileId, SourceNames>,
+) -> HashSet<FileId> {
+    closure(files, |file| {
+        let own = names.get(file)?;
+        Some(
+            names
+                .iter()
+                .filter(|(_, other)| own.uses_any(&other.declared))
+                .map(|(other, _)| other.clone())
+                .collect(),
+        )
+    })
+}
+
+fn closure(
+    start: &HashSet<FileId>,
+    next: impl Fn(&FileId) -> Option<Vec<FileId>>,
+) -> HashSet<FileId> {
+    let mut reached = start.clone();
+    let mut pending: Vec<FileId> = start.iter().cloned().collect();
+    while let Some(file) = pending.pop() {
+        for other in next(&file).unwrap_or_default() {
+            if reached.insert(other.clone()) {
+                pending.push(other);
+            }
+        }
+    }
+    reached
+}
+
+/// The name of a top-level declaration.
+pub(crate) fn element_name(element: &LibraryElementKind) -> Option<Id> {
+    match element {
+        LibraryElementKind::DataTypeDeclaration(decl) => {
+            let type_name = match decl {
+                DataTypeDeclarationKind::Enumeration(decl) => &decl.type_name,
+                DataTypeDeclarationKind::Subrange(decl) => &decl.type_name,
+                DataTypeDeclarationKind::Simple(decl) => &decl.type_name,
+                DataTypeDeclarationKind::Array(decl) => &decl.type_name,
+                DataTypeDeclarationKind::Structure(decl) => &decl.type_name,
+                DataTypeDeclarationKind::StructureInitialization(decl) => &decl.type_name,
+                DataTypeDeclarationKind::String(decl) => &decl.type_name,
+                DataTypeDeclarationKind::Reference(decl) => &decl.type_name,
+                DataTypeDeclarationKind::LateBound(decl) => &decl.data_type_name,
+            };
+            Some(type_name.name.clone())
+        }
+        LibraryElementKind::FunctionDeclaration(decl) => Some(decl.name.clone()),
+        LibraryElementKind::FunctionBlockDeclaration(decl) => Some(decl.name.name.clone()),
+        LibraryElementKind::ProgramDeclaration(decl) => Some(decl.name.clone()),
+        LibraryElementKind::ConfigurationDeclaration(decl) => Some(decl.name.clone()),
+        LibraryElementKind::InterfaceDeclaration(decl) => Some(decl.name.clone()),
+        // The variables are collected with the other globals.
+        LibraryElementKind::GlobalVarDeclarations(_) => None,
+    }
+}
+
+struct NameCollector {
+    declared: HashSet<String>,
+    used: HashSet<String>,
+}
+
+impl Visitor<()> for NameCollector {
+    type Value = ();
+
+    fn visit_id(&mut self, node: &Id) -> Result<(), ()> {
+        self.used.insert(node.lower_case().clone());
+        Ok(())
+    }
+
+    fn visit_enumerated_specification_values(
+        &mut self,
+        node: &EnumeratedSpecificationValues,
+    ) -> Result<(), ()> {
+        for value in &node.values {
+            self.declared.insert(value.value.lower_case().clone());
+        }
+        node.recurse_visit(self)
+    }
+
+    fn visit_var_decl(&mut self, node: &VarDecl) -> Result<(), ()> {
+        if node.var_type == VariableType::Global {
+            let name = match &node.identifier {
+                VariableIdentifier::Symbol(name) => Some(name),
+                VariableIdentifier::Direct(direct) => direct.name.as_ref(),
+            };
+            if let Some(name) = name {
+                self.declared.insert(name.lower_case().clone());
+            }
+        }
+        node.recurse_visit(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironplc_parser::options::CompilerOptions;
+
+    fn names(sources: &[(&str, &str)]) -> HashMap<FileId, SourceNames> {
+        sources
+            .iter()
+            .map(|(name, text)| {
+                let file_id = FileId::from_string(name);
+                let library =
+                    ironplc_parser::parse_program(text, &file_id, &CompilerOptions::default())
+                        .unwrap();
+                (file_id, SourceNames::of(&library))
+            })
+            .collect()
+    }
+
+    fn files(names: &[&str]) -> HashSet<FileId> {
+        names.iter().map(|name| FileId::from_string(name)).collect()
+    }
+
+    const TYPES: &str = "TYPE
+    Mode : (Idle, Busy);
+END_TYPE";
+    const COUNTER: &str = "FUNCTION_BLOCK Counter
+VAR_INPUT
+    Start : Mode;
+END_VAR
+END_FUNCTION_BLOCK";
+    const MAIN: &str = "PROGRAM Main
+VAR
+    Tally : Counter;
+END_VAR
+    Tally(Start := Idle);
+END_PROGRAM";
+    const OTHER: &str = "PROGRAM Other
+VAR
+    Level : INT;
+END_VAR
+    Level := 1;
+END_PROGRAM";
+
+    #[test]
+    fn of_when_enumeration_then_declares_type_and_values() {
+        let names = names(&[("types.st", TYPES)]);
+
+        let declared = &names[&FileId::from_string("types.st")].declared;
+
+        assert_eq!(
+            declared,
+            &HashSet::from(["mode".to_string(), "idle".to_string(), "busy".to_string()])
+        );
+    }
+
+    #[test]
+    fn affected_when_declarations_change_then_users_through_other_sources() {
+        let names = names(&[
+            ("types.st", TYPES),
+            ("counter.st", COUNTER),
+            ("main.st", MAIN),
+            ("other.st", OTHER),
+        ]);
+
+        let affected = affected(&files(&["types.st"]), &names);
+
+        assert_eq!(affected, files(&["types.st", "counter.st", "main.st"]));
+    }
+
+    #[test]
+    fn with_dependencies_when_program_then_declarations_it_uses() {
+        let names = names(&[
+            ("types.st", TYPES),
+            ("counter.st", COUNTER),
+            ("main.st", MAIN),
+            ("other.st", OTHER),
+        ]);
+
+        let needed = with_dependencies(&files(&["main.st"]), &names);
+
+        assert_eq!(needed, files(&["types.st", "counter.st", "main.st"]));
+    }
+}
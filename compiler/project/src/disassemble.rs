@@ -514,6 +514,12 @@ fn decode_instructions(bytecode: &[u8], container: &Container) -> Vec<Value> {
                     opcode::builtin::MOD_F64 => format!("MOD_F64 (0x{:04X})", func_id),
                     opcode::builtin::TRUNC_F32 => format!("TRUNC_F32 (0x{:04X})", func_id),
                     opcode::builtin::MOD_F32 => format!("MOD_F32 (0x{:04X})", func_id),
+                    opcode::builtin::ADD_U32 => format!("ADD_U32 (0x{:04X})", func_id),
+                    opcode::builtin::SUB_U32 => format!("SUB_U32 (0x{:04X})", func_id),
+                    opcode::builtin::MUL_U32 => format!("MUL_U32 (0x{:04X})", func_id),
+                    opcode::builtin::ADD_U64 => format!("ADD_U64 (0x{:04X})", func_id),
+                    opcode::builtin::SUB_U64 => format!("SUB_U64 (0x{:04X})", func_id),
+                    opcode::builtin::MUL_U64 => format!("MUL_U64 (0x{:04X})", func_id),
                     id if opcode::builtin::is_mux(id) => {
                         let n = opcode::builtin::mux_info(id).unwrap();
                         let width = if id >= opcode::builtin::MUX_F64_BASE {
@@ -636,6 +642,47 @@ fn decode_instructions(bytecode: &[u8], container: &Container) -> Vec<Value> {
                 }));
                 pc += 8;
             }
+            opcode::RANGE_CHECK_I32
+            | opcode::RANGE_CHECK_I64
+            | opcode::RANGE_CHECK_U32
+            | opcode::RANGE_CHECK_U64 => {
+                let var_idx = read_u16(bytecode, pc + 1);
+                let min_idx = read_u16(bytecode, pc + 3);
+                let max_idx = read_u16(bytecode, pc + 5);
+                let mnemonic = match opcode_byte {
+                    opcode::RANGE_CHECK_I32 => "RANGE_CHECK_I32",
+                    opcode::RANGE_CHECK_I64 => "RANGE_CHECK_I64",
+                    opcode::RANGE_CHECK_U32 => "RANGE_CHECK_U32",
+                    _ => "RANGE_CHECK_U64",
+                };
+                instructions.push(json!({
+                    "offset": offset,
+                    "opcode": mnemonic,
+                    "operands": format!("var[{}], const[{}], const[{}]", var_idx, min_idx, max_idx),
+                    "comment": "",
+                }));
+                pc += 7;
+            }
+            opcode::LOAD_ARRAY
+            | opcode::STORE_ARRAY
+            | opcode::LOAD_ARRAY_UNCHECKED
+            | opcode::STORE_ARRAY_UNCHECKED => {
+                let var_idx = read_u16(bytecode, pc + 1);
+                let desc_idx = read_u16(bytecode, pc + 3);
+                let mnemonic = match opcode_byte {
+                    opcode::LOAD_ARRAY => "LOAD_ARRAY",
+                    opcode::STORE_ARRAY => "STORE_ARRAY",
+                    opcode::LOAD_ARRAY_UNCHECKED => "LOAD_ARRAY_UNCHECKED",
+                    _ => "STORE_ARRAY_UNCHECKED",
+                };
+                instructions.push(json!({
+                    "offset": offset,
+                    "opcode": mnemonic,
+                    "operands": format!("var[{}], desc[{}]", var_idx, desc_idx),
+                    "comment": "",
+                }));
+                pc += 5;
+            }
             unknown => {
                 instructions.push(json!({
                     "offset": offset,
@@ -1058,6 +1105,38 @@ mod tests {
         assert_eq!(instr["comment"], "-> 0");
     }
 
+    #[test]
+    fn decode_when_range_check_u32_then_shows_var_and_bound_constants() {
+        let bytecode = vec![
+            opcode::RANGE_CHECK_U32,
+            0x03,
+            0x00,
+            0x01,
+            0x00,
+            0x02,
+            0x00,
+            opcode::RET_VOID,
+        ];
+        let instr = first_instruction(bytecode);
+        assert_eq!(instr["opcode"], "RANGE_CHECK_U32");
+        assert_eq!(instr["operands"], "var[3], const[1], const[2]");
+    }
+
+    #[test]
+    fn decode_when_load_array_unchecked_then_shows_var_and_descriptor() {
+        let bytecode = vec![
+            opcode::LOAD_ARRAY_UNCHECKED,
+            0x02,
+            0x00,
+            0x01,
+            0x00,
+            opcode::RET_VOID,
+        ];
+        let instr = first_instruction(bytecode);
+        assert_eq!(instr["opcode"], "LOAD_ARRAY_UNCHECKED");
+        assert_eq!(instr["operands"], "var[2], desc[1]");
+    }
+
     // ---------------------------------------------------------------
     // decode_instructions: BUILTIN named sub-IDs
     // ---------------------------------------------------------------
@@ -1079,6 +1158,8 @@ mod tests {
     #[case::bcd_to_int_32(opcode::builtin::BCD_TO_INT_32, "BCD_TO_INT_32 (0x0393)")]
     #[case::int_to_bcd_64(opcode::builtin::INT_TO_BCD_64, "INT_TO_BCD_64 (0x0398)")]
     #[case::sqrt_f32(opcode::builtin::SQRT_F32, "SQRT_F32 (0x035E)")]
+    #[case::add_u32(opcode::builtin::ADD_U32, "ADD_U32 (0x03A7)")]
+    #[case::mul_u64(opcode::builtin::MUL_U64, "MUL_U64 (0x03AC)")]
     #[case::unknown_id(0x00FF, "0x00FF")]
     fn decode_when_builtin_then_operand_shows_name(
         #[case] func_id: u16,
@@ -2,7 +2,9 @@
 #![allow(clippy::result_large_err)]
 
 pub mod compile;
+mod dependencies;
 pub mod disassemble;
+mod naming;
 pub mod project;
 pub mod tokenizer;
 
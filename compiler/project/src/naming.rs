@@ -0,0 +1,616 @@
+//! Checks the names that user sources declare against the project's naming
+//! conventions (the `[naming]` table of `ironplc.toml`), reporting each name
+//! that breaks its rule as P4057.
+//!
+//! The check runs after semantic analysis, on the analyzed library, so that
+//! a variable's function block or enumeration type is already resolved.
+//! Declarations in compatibility libraries are never checked.
+//!
+//! A variable takes the first rule that the conventions have of: its type in
+//! `[naming.types]`, `function_block_instance` when it is an instance,
+//! `constant` when it is `CONSTANT`, its section (`input`, `output`,
+//! `in_out` or `global`), and `variable`.
+//!
+//! Pragmas in a source exempt names from the check:
+//!
+//! * `{attribute 'naming' := 'off'}` exempts the names that follow, up to
+//!   `{attribute 'naming' := 'on'}` or the end of the file;
+//! * `{attribute 'naming' := 'omit'}` exempts the next declaration: a whole
+//!   POU or data type when it precedes one, otherwise one variable.
+//!
+//! Pragmas are only read when `--allow-pragmas` is in effect.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use ironplc_dsl::{
+    common::*,
+    configuration::ConfigurationDeclaration,
+    core::{FileId, Id, Located},
+    diagnostic::{Diagnostic, Label},
+};
+use ironplc_parser::token::{Token, TokenType};
+use ironplc_problems::Problem;
+use ironplc_sources::naming::{NameRule, NamedKind, NamingConventions};
+
+use crate::dependencies::element_name;
+
+/// A naming pragma and where it ends in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pragma {
+    Off(usize),
+    On(usize),
+    Omit(usize),
+}
+
+/// The naming pragmas of one source, in source order.
+#[derive(Debug, Default)]
+pub(crate) struct Exemptions {
+    pragmas: Vec<Pragma>,
+}
+
+impl Exemptions {
+    /// Finds the naming pragmas among the tokens of a source.
+    pub(crate) fn from_tokens(tokens: &[Token]) -> Self {
+        let pragmas = tokens
+            .iter()
+            .filter(|token| token.token_type == TokenType::Pragma)
+            .filter_map(|token| {
+                let text: String = token
+                    .text
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .collect::<String>()
+                    .to_lowercase();
+                let end = token.span.end;
+                match text.as_str() {
+                    "{attribute'naming':='off'}" => Some(Pragma::Off(end)),
+                    "{attribute'naming':='on'}" => Some(Pragma::On(end)),
+                    "{attribute'naming':='omit'}" => Some(Pragma::Omit(end)),
+                    _ => None,
+                }
+            })
+            .collect();
+        Exemptions { pragmas }
+    }
+
+    /// Returns the positions in `names` that the pragmas exempt, judged by
+    /// the layout of the source rather than by which names have rules.
+    fn exempt(&self, names: &[&Named], layout: &Layout) -> HashSet<usize> {
+        let mut exempt = HashSet::new();
+        let mut exempt_range = |range: Range<usize>| {
+            exempt.extend(
+                names
+                    .iter()
+                    .filter(|named| range.contains(&named.name.span.start))
+                    .map(|named| named.name.span.start),
+            );
+        };
+        for (index, pragma) in self.pragmas.iter().enumerate() {
+            match pragma {
+                Pragma::Off(start) => {
+                    let end = self.pragmas[index + 1..]
+                        .iter()
+                        .find_map(|next| match next {
+                            Pragma::On(end) => Some(*end),
+                            _ => None,
+                        })
+                        .unwrap_or(usize::MAX);
+                    exempt_range(*start..end);
+                }
+                Pragma::On(_) => {}
+                Pragma::Omit(start) => {
+                    let Some(next) = layout
+                        .declared
+                        .iter()
+                        .find(|declared| declared.start > *start)
+                    else {
+                        continue;
+                    };
+                    let end = if next.pou_or_type {
+                        layout
+                            .elements
+                            .iter()
+                            .copied()
+                            .find(|element| *element > next.start)
+                            .unwrap_or(usize::MAX)
+                    } else {
+                        next.start + 1
+                    };
+                    exempt_range(next.start..end);
+                }
+            }
+        }
+        exempt
+    }
+}
+
+/// Where the declarations of a source are, whether or not a rule names
+/// them, sorted by position.
+#[derive(Default)]
+struct Layout {
+    declared: Vec<Declared>,
+    /// Where each top-level declaration, including a `VAR_GLOBAL` block,
+    /// starts.
+    elements: Vec<usize>,
+}
+
+/// Where a declared name starts.
+struct Declared {
+    start: usize,
+    /// A POU or data type, which `omit` exempts with its contents.
+    pou_or_type: bool,
+}
+
+/// A declared name and the rule that applies to it.
+struct Named<'a> {
+    name: &'a Id,
+    /// How the diagnostic describes the declaration.
+    what: &'static str,
+    /// Where the rule is in `ironplc.toml`.
+    rule_key: String,
+    rule: &'a NameRule,
+}
+
+/// Checks the names declared in `files` against `conventions`.
+pub(crate) fn check(
+    library: &Library,
+    files: &HashMap<FileId, Exemptions>,
+    conventions: &NamingConventions,
+) -> Vec<Diagnostic> {
+    let mut collector = Collector {
+        conventions,
+        names: vec![],
+        layouts: HashMap::new(),
+    };
+    for element in &library.elements {
+        collector.element(element);
+    }
+
+    let mut by_file: HashMap<&FileId, Vec<&Named>> = HashMap::new();
+    for named in &collector.names {
+        if files.contains_key(&named.name.span.file_id) {
+            by_file
+                .entry(&named.name.span.file_id)
+                .or_default()
+                .push(named);
+        }
+    }
+
+    let mut diagnostics = vec![];
+    for (file_id, mut names) in by_file {
+        names.sort_by_key(|named| named.name.span.start);
+        let mut layout = collector.layouts.remove(file_id).unwrap_or_default();
+        layout.declared.sort_by_key(|declared| declared.start);
+        layout.elements.sort();
+        let exempt = files
+            .get(file_id)
+            .map(|exemptions| exemptions.exempt(&names, &layout))
+            .unwrap_or_default();
+        for named in names {
+            if exempt.contains(&named.name.span.start) {
+                continue;
+            }
+            let violations = named.rule.violations(&named.name.original);
+            if violations.is_empty() {
+                continue;
+            }
+            let mut diagnostic = Diagnostic::problem(
+                Problem::NamingConventionViolation,
+                Label::span(
+                    named.name.span.clone(),
+                    format!(
+                        "{} '{}' does not follow the naming convention",
+                        named.what, named.name.original
+                    ),
+                ),
+            )
+            .with_help(format!("The rule is `{}` in ironplc.toml", named.rule_key));
+            diagnostic.described.extend(violations);
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics.sort_by_key(|diagnostic| {
+        (
+            diagnostic.primary.file_id.to_string(),
+            diagnostic.primary.location.start,
+        )
+    });
+    diagnostics
+}
+
+struct Collector<'a> {
+    conventions: &'a NamingConventions,
+    names: Vec<Named<'a>>,
+    layouts: HashMap<FileId, Layout>,
+}
+
+impl<'a> Collector<'a> {
+    fn element(&mut self, element: &'a LibraryElementKind) {
+        let start = match element {
+            LibraryElementKind::GlobalVarDeclarations(globals) => {
+                globals.first().map(|decl| decl.span())
+            }
+            _ => element_name(element).map(|name| name.span),
+        };
+        if let Some(span) = start {
+            self.layout(&span.file_id).elements.push(span.start);
+        }
+
+        match element {
+            LibraryElementKind::DataTypeDeclaration(decl) => {
+                let (kind, type_name) = match decl {
+                    DataTypeDeclarationKind::Enumeration(decl) => {
+                        (NamedKind::Enumeration, &decl.type_name)
+                    }
+                    DataTypeDeclarationKind::Structure(decl) => {
+                        (NamedKind::Structure, &decl.type_name)
+                    }
+                    DataTypeDeclarationKind::StructureInitialization(decl) => {
+                        (NamedKind::Structure, &decl.type_name)
+                    }
+                    DataTypeDeclarationKind::Subrange(decl) => (NamedKind::Type, &decl.type_name),
+                    DataTypeDeclarationKind::Simple(decl) => (NamedKind::Type, &decl.type_name),
+                    DataTypeDeclarationKind::Array(decl) => (NamedKind::Type, &decl.type_name),
+                    DataTypeDeclarationKind::String(decl) => (NamedKind::Type, &decl.type_name),
+                    DataTypeDeclarationKind::Reference(decl) => (NamedKind::Type, &decl.type_name),
+                    DataTypeDeclarationKind::LateBound(decl) => {
+                        (NamedKind::Type, &decl.data_type_name)
+                    }
+                };
+                let what = match kind {
+                    NamedKind::Enumeration => "Enumeration",
+                    NamedKind::Structure => "Structure",
+                    _ => "Type",
+                };
+                self.declaration(&type_name.name, kind, what, true);
+            }
+            LibraryElementKind::FunctionDeclaration(function) => {
+                self.declaration(&function.name, NamedKind::Function, "Function", true);
+                self.variables(&function.variables);
+            }
+            LibraryElementKind::FunctionBlockDeclaration(fb) => {
+                self.declaration(
+                    &fb.name.name,
+                    NamedKind::FunctionBlock,
+                    "Function block",
+                    true,
+                );
+                self.variables(&fb.variables);
+                for method in &fb.methods {
+                    self.declaration(&method.name, NamedKind::Method, "Method", false);
+                    self.variables(&method.variables);
+                }
+            }
+            LibraryElementKind::ProgramDeclaration(program) => {
+                self.declaration(&program.name, NamedKind::Program, "Program", true);
+                self.variables(&program.variables);
+            }
+            LibraryElementKind::InterfaceDeclaration(interface) => {
+                self.declaration(&interface.name, NamedKind::Interface, "Interface", true);
+            }
+            LibraryElementKind::GlobalVarDeclarations(globals) => self.variables(globals),
+            LibraryElementKind::ConfigurationDeclaration(config) => self.configuration(config),
+        }
+    }
+
+    fn configuration(&mut self, config: &'a ConfigurationDeclaration) {
+        self.variables(&config.global_var);
+        for resource in &config.resource_decl {
+            self.variables(&resource.global_vars);
+        }
+    }
+
+    fn layout(&mut self, file_id: &FileId) -> &mut Layout {
+        self.layouts.entry(file_id.clone()).or_default()
+    }
+
+    fn declared(&mut self, name: &Id, pou_or_type: bool) {
+        self.layout(&name.span.file_id).declared.push(Declared {
+            start: name.span.start,
+            pou_or_type,
+        });
+    }
+
+    fn declaration(
+        &mut self,
+        name: &'a Id,
+        kind: NamedKind,
+        what: &'static str,
+        pou_or_type: bool,
+    ) {
+        self.declared(name, pou_or_type);
+        if let Some(rule) = self.conventions.rule(kind) {
+            self.names.push(Named {
+                name,
+                what,
+                rule_key: format!("[naming] {}", kind.key()),
+                rule,
+            });
+        }
+    }
+
+    fn variables(&mut self, variables: &'a [VarDecl]) {
+        for decl in variables {
+            let Some(name) = decl.identifier.symbolic_id() else {
+                continue;
+            };
+            self.declared(name, false);
+            let (type_name, instance) = variable_type(&decl.initializer);
+            if let Some(rule) = type_name.and_then(|t| self.conventions.type_rule(&t.original)) {
+                self.names.push(Named {
+                    name,
+                    what: "Variable",
+                    rule_key: format!("[naming.types] {}", type_name.unwrap().original),
+                    rule,
+                });
+                continue;
+            }
+
+            let section = match decl.var_type {
+                VariableType::Input => Some(NamedKind::Input),
+                VariableType::Output => Some(NamedKind::Output),
+                VariableType::InOut => Some(NamedKind::InOut),
+                VariableType::Global | VariableType::External => Some(NamedKind::Global),
+                _ => None,
+            };
+            let kinds = [
+                instance.then_some(NamedKind::FunctionBlockInstance),
+                (decl.qualifier == DeclarationQualifier::Constant).then_some(NamedKind::Constant),
+                section,
+                Some(NamedKind::Variable),
+            ];
+            if let Some((kind, rule)) = kinds
+                .into_iter()
+                .flatten()
+                .find_map(|kind| Some((kind, self.conventions.rule(kind)?)))
+            {
+                self.names.push(Named {
+                    name,
+                    what: "Variable",
+                    rule_key: format!("[naming] {}", kind.key()),
+                    rule,
+                });
+            }
+        }
+    }
+}
+
+/// The type a variable names, and whether it is a function block instance.
+fn variable_type(initializer: &InitialValueAssignmentKind) -> (Option<&Id>, bool) {
+    match initializer {
+        InitialValueAssignmentKind::Simple(init) => (Some(&init.type_name.name), false),
+        InitialValueAssignmentKind::EnumeratedType(init) => (Some(&init.type_name.name), false),
+        InitialValueAssignmentKind::Structure(init) => (Some(&init.type_name.name), false),
+        InitialValueAssignmentKind::LateResolvedType(type_name) => (Some(&type_name.name), false),
+        InitialValueAssignmentKind::FunctionBlock(init) => (Some(&init.type_name.name), true),
+        InitialValueAssignmentKind::FunctionBlockCall(init) => (Some(&init.type_name.name), true),
+        _ => (None, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ironplc_dsl::core::FileId;
+    use ironplc_parser::{options::CompilerOptions, tokenize_program};
+    use ironplc_sources::naming::{NameCase, NameRule, NamedKind, NamingConventions};
+
+    use super::*;
+
+    fn prefix(prefix: &str) -> NameRule {
+        NameRule {
+            prefix: Some(prefix.to_string()),
+            ..NameRule::default()
+        }
+    }
+
+    fn check_with(source: &str, conventions: &NamingConventions) -> Vec<Diagnostic> {
+        let options = CompilerOptions {
+            allow_pragmas: true,
+            ..CompilerOptions::default()
+        };
+        let file_id = FileId::from_string("main.st");
+        let library = ironplc_parser::parse_program(source, &file_id, &options).unwrap();
+        let (library, _context) = ironplc_analyzer::stages::analyze(&[&library], &options).unwrap();
+        let (tokens, _) = tokenize_program(source, &file_id, &options, 0, 0);
+        let files = HashMap::from([(file_id, Exemptions::from_tokens(&tokens))]);
+        check(&library, &files, conventions)
+    }
+
+    fn messages(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics
+            .iter()
+            .map(|d| d.primary.message.as_str())
+            .collect()
+    }
+
+    fn bool_and_fb_conventions() -> NamingConventions {
+        let mut conventions = NamingConventions::default();
+        conventions.set(NamedKind::FunctionBlock, prefix("FB_"));
+        conventions.set(NamedKind::FunctionBlockInstance, prefix("fb"));
+        conventions.set_type("BOOL", prefix("b"));
+        conventions
+    }
+
+    #[test]
+    fn check_when_names_follow_conventions_then_ok() {
+        let source = "
+FUNCTION_BLOCK FB_Motor
+VAR_INPUT
+    bRun : BOOL;
+END_VAR
+END_FUNCTION_BLOCK
+
+PROGRAM main
+VAR
+    fbMotor : FB_Motor;
+    bStart : BOOL;
+    count : INT;
+END_VAR
+fbMotor(bRun := bStart);
+END_PROGRAM";
+        assert!(check_with(source, &bool_and_fb_conventions()).is_empty());
+    }
+
+    #[test]
+    fn check_when_names_break_conventions_then_each_reported() {
+        let source = "
+FUNCTION_BLOCK Motor
+VAR_INPUT
+    run : BOOL;
+END_VAR
+END_FUNCTION_BLOCK
+
+PROGRAM main
+VAR
+    motor1 : Motor;
+END_VAR
+motor1(run := TRUE);
+END_PROGRAM";
+        let diagnostics = check_with(source, &bool_and_fb_conventions());
+        assert_eq!(
+            messages(&diagnostics),
+            vec![
+                "Function block 'Motor' does not follow the naming convention",
+                "Variable 'run' does not follow the naming convention",
+                "Variable 'motor1' does not follow the naming convention",
+            ]
+        );
+        assert_eq!(diagnostics[1].described, vec!["Expected prefix 'b'"]);
+        assert_eq!(diagnostics[1].code, "P4057");
+    }
+
+    #[test]
+    fn check_when_enumeration_and_pou_case_then_reports_case() {
+        let mut conventions = NamingConventions::default();
+        conventions.set(NamedKind::Enumeration, prefix("E_"));
+        conventions.set(
+            NamedKind::Program,
+            NameRule {
+                case: Some(NameCase::PascalCase),
+                ..NameRule::default()
+            },
+        );
+        let source = "
+TYPE
+    MODE : (IDLE, RUNNING);
+END_TYPE
+
+PROGRAM main_program
+VAR
+    m : MODE;
+END_VAR
+m := IDLE;
+END_PROGRAM";
+        let diagnostics = check_with(source, &conventions);
+        assert_eq!(
+            messages(&diagnostics),
+            vec![
+                "Enumeration 'MODE' does not follow the naming convention",
+                "Program 'main_program' does not follow the naming convention",
+            ]
+        );
+        assert_eq!(diagnostics[1].described, vec!["Expected PascalCase"]);
+    }
+
+    #[test]
+    fn check_when_naming_off_at_top_then_file_exempt() {
+        let source = "{attribute 'naming' := 'off'}
+FUNCTION_BLOCK Motor
+VAR_INPUT
+    run : BOOL;
+END_VAR
+END_FUNCTION_BLOCK";
+        assert!(check_with(source, &bool_and_fb_conventions()).is_empty());
+    }
+
+    #[test]
+    fn check_when_naming_off_then_on_then_only_region_exempt() {
+        let source = "
+FUNCTION_BLOCK FB_Motor
+VAR_INPUT
+{attribute 'naming' := 'off'}
+    run : BOOL;
+{attribute 'naming' := 'on'}
+    stop : BOOL;
+END_VAR
+END_FUNCTION_BLOCK";
+        let diagnostics = check_with(source, &bool_and_fb_conventions());
+        assert_eq!(
+            messages(&diagnostics),
+            vec!["Variable 'stop' does not follow the naming convention"]
+        );
+    }
+
+    #[test]
+    fn check_when_omit_before_pou_then_pou_exempt() {
+        let source = "
+{attribute 'naming' := 'omit'}
+FUNCTION_BLOCK Motor
+VAR_INPUT
+    run : BOOL;
+END_VAR
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK Pump
+END_FUNCTION_BLOCK";
+        let diagnostics = check_with(source, &bool_and_fb_conventions());
+        assert_eq!(
+            messages(&diagnostics),
+            vec!["Function block 'Pump' does not follow the naming convention"]
+        );
+    }
+
+    #[test]
+    fn check_when_omit_before_pou_followed_by_unruled_pou_then_next_pou_checked() {
+        let source = "
+{attribute 'naming' := 'omit'}
+FUNCTION_BLOCK Motor
+VAR_INPUT
+    run : BOOL;
+END_VAR
+END_FUNCTION_BLOCK
+
+PROGRAM main
+VAR
+    m : Motor;
+END_VAR
+m(run := TRUE);
+END_PROGRAM";
+        let diagnostics = check_with(source, &bool_and_fb_conventions());
+        assert_eq!(
+            messages(&diagnostics),
+            vec!["Variable 'm' does not follow the naming convention"]
+        );
+    }
+
+    #[test]
+    fn check_when_omit_before_unruled_pou_then_its_variables_exempt() {
+        let source = "
+{attribute 'naming' := 'omit'}
+PROGRAM main
+VAR
+    start : BOOL;
+    stop : BOOL;
+END_VAR
+END_PROGRAM";
+        assert!(check_with(source, &bool_and_fb_conventions()).is_empty());
+    }
+
+    #[test]
+    fn check_when_omit_before_variable_then_only_variable_exempt() {
+        let source = "
+FUNCTION_BLOCK FB_Motor
+VAR_INPUT
+    {attribute 'naming' := 'omit'}
+    run : BOOL;
+    stop : BOOL;
+END_VAR
+END_FUNCTION_BLOCK";
+        let diagnostics = check_with(source, &bool_and_fb_conventions());
+        assert_eq!(
+            messages(&diagnostics),
+            vec!["Variable 'stop' does not follow the naming convention"]
+        );
+    }
+}
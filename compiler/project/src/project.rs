@@ -3,36 +3,63 @@
 //!
 //! The trait enables easy testing of the language server protocol integration.
 
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
-use ironplc_analyzer::{stages::analyze, SemanticContext};
+use ironplc_analyzer::{stages::analyze, symbol_index::SymbolIndex, SemanticContext};
 use ironplc_dsl::{
     common::Library,
     core::{FileId, SourceSpan},
-    diagnostic::{Diagnostic, Label},
+    diagnostic::{Demotions, Diagnostic, Label},
 };
 use ironplc_parser::{options::CompilerOptions, token::Token, tokenize_program};
 use ironplc_problems::Problem;
-use ironplc_sources::{FileType, LibraryName, Source, SourceProject};
+use ironplc_sources::{naming::NamingConventions, FileType, LibraryName, Source, SourceProject};
 use log::{debug, trace};
 
+use crate::dependencies::{self, SourceNames};
+use crate::naming;
+
+/// Diagnostics, semantic context, analyzed library and compatibility
+/// libraries from one run of [`run_semantic_analysis`].
+type SemanticAnalysis = (
+    Vec<Diagnostic>,
+    Option<SemanticContext>,
+    Option<Library>,
+    Vec<Library>,
+);
+
 /// Runs semantic analysis on the given source project and compiler options.
 ///
 /// This is the shared implementation used by both [`FileBackedProject`] and
 /// [`MemoryBackedProject`]. It parses each source into a library, merges them,
 /// runs the analyzer, and returns the collected diagnostics (empty when the
 /// project is clean) plus the semantic context (when type resolution
-/// succeeds).
+/// succeeds). It also returns the compatibility libraries that were in
+/// effect, after shadowed declarations are removed, so that navigation can
+/// reach into them.
 ///
 /// `preparsed_libraries` are compatibility libraries the caller already parsed,
 /// injected alongside the ones the bundled registry loads. They exist for hosts
 /// that cannot read the registry from disk -- the playground fetches its
 /// library text over HTTP -- and are treated identically once loaded.
+///
+/// `only` limits analysis to the given sources; the others are neither parsed
+/// nor analyzed.
+///
+/// `demotions` and the demotions in the project's settings file turn the
+/// diagnostics with those codes into warnings (ADR-0039).
 fn run_semantic_analysis(
     source_project: &mut SourceProject,
     compiler_options: &CompilerOptions,
+    demotions: &Demotions,
     preparsed_libraries: &[Library],
-) -> (Vec<Diagnostic>, Option<SemanticContext>, Option<Library>) {
+    only: Option<&HashSet<FileId>>,
+) -> SemanticAnalysis {
+    let mut demotions = demotions.clone();
+    demotions.extend(source_project.demotions());
+    let naming_conventions = source_project.naming_conventions().clone();
+
     let mut all_libraries = vec![];
     let mut all_diagnostics: Vec<Diagnostic> = vec![];
 
@@ -53,10 +80,24 @@ fn run_semantic_analysis(
     // FileId first so the merged order (and every downstream result) is
     // deterministic.
     let mut sources = source_project.sources_mut();
+    if let Some(only) = only {
+        sources.retain(|source| only.contains(source.file_id()));
+    }
     sources.sort_by_key(|source| source.file_id().to_string());
 
+    // The naming pragmas of each user source; only these files have their
+    // names checked.
+    let mut naming_files = HashMap::new();
     let mut any_source_failed_to_parse = false;
     for source in sources {
+        if !naming_conventions.is_empty() {
+            let (tokens, _) =
+                tokenize_program(source.as_string(), source.file_id(), compiler_options, 0, 0);
+            naming_files.insert(
+                source.file_id().clone(),
+                naming::Exemptions::from_tokens(&tokens),
+            );
+        }
         match source.library() {
             Ok(library) => {
                 all_libraries.push(library);
@@ -78,7 +119,8 @@ fn run_semantic_analysis(
     // `NoContent` exists to report. Partial failure is unaffected -- analysis
     // runs on whatever did parse.
     if all_libraries.is_empty() && any_source_failed_to_parse {
-        return (all_diagnostics, None, None);
+        demotions.apply(&mut all_diagnostics);
+        return (all_diagnostics, None, None, compat_libraries);
     }
 
     // A user-declared function takes precedence over a library function of
@@ -94,18 +136,58 @@ fn run_semantic_analysis(
         .chain(all_libraries.iter().copied())
         .collect();
 
-    match analyze(&analyze_input, compiler_options) {
+    let (context, library) = match analyze(&analyze_input, compiler_options) {
         Ok((library, context)) => {
             debug!("Semantic analysis completed {context:?}");
             all_diagnostics.extend(context.diagnostics().iter().cloned());
-            (all_diagnostics, Some(context), Some(library))
+            if !naming_conventions.is_empty() {
+                all_diagnostics.extend(naming::check(&library, &naming_files, &naming_conventions));
+            }
+            (Some(context), Some(library))
         }
         Err(diagnostics) => {
             debug!("Semantic analysis errored {diagnostics:?}");
             all_diagnostics.extend(diagnostics);
-            (all_diagnostics, None, None)
+            (None, None)
         }
-    }
+    };
+    remove_unused_in_libraries(&mut all_diagnostics, &compat_libraries);
+    demotions.apply(&mut all_diagnostics);
+    (all_diagnostics, context, library, compat_libraries)
+}
+
+/// Drops the `--check-unused` findings in compatibility libraries: a
+/// project uses only part of a library, and the rest is not the project's
+/// to remove.
+fn remove_unused_in_libraries(diagnostics: &mut Vec<Diagnostic>, compat_libraries: &[Library]) {
+    let library_files: HashSet<FileId> = compat_libraries
+        .iter()
+        .flat_map(|library| &library.elements)
+        .filter_map(dependencies::element_name)
+        .map(|name| name.span.file_id)
+        .collect();
+    let unused = [
+        Problem::UnusedDeclaration.code(),
+        Problem::VariableNeverRead.code(),
+        Problem::UnreachablePou.code(),
+    ];
+    diagnostics.retain(|diagnostic| {
+        !unused.contains(&diagnostic.code.as_str())
+            || !library_files.contains(&diagnostic.primary.file_id)
+    });
+}
+
+/// The compatibility libraries followed by each parsed source, sorted by
+/// file so the order matches what analysis merged.
+fn parsed_libraries<'a>(
+    compat_libraries: &'a [Library],
+    mut sources: Vec<&'a Source>,
+) -> Vec<&'a Library> {
+    sources.sort_by_key(|source| source.file_id().to_string());
+    compat_libraries
+        .iter()
+        .chain(sources.into_iter().filter_map(Source::parsed_library))
+        .collect()
 }
 
 /// A project consisting of one or more files.
@@ -138,6 +220,14 @@ pub trait Project {
         }
     }
 
+    /// Changes the options the project compiles with. Every source parses
+    /// again at the next analysis.
+    fn set_compiler_options(&mut self, compiler_options: CompilerOptions);
+
+    /// Changes the problem codes the project reports as warnings, in addition
+    /// to those its settings file demotes.
+    fn set_demotions(&mut self, demotions: Demotions);
+
     /// Updates the text for a document.
     fn change_text_document(&mut self, file_id: &FileId, content: String);
 
@@ -152,6 +242,23 @@ pub trait Project {
     /// `semantic_context()` and `analyzed_library()`.
     fn semantic(&mut self) -> Vec<Diagnostic>;
 
+    /// Requests semantic analysis of what changed since the last analysis.
+    ///
+    /// Returns the same diagnostics as `semantic()`, but an implementation
+    /// may analyze only the sources a change can affect and keep the
+    /// diagnostics of the others from the last analysis. The cached
+    /// artifacts then cover only the sources that were analyzed; see
+    /// `is_analyzed()`.
+    fn semantic_incremental(&mut self) -> Vec<Diagnostic> {
+        self.semantic()
+    }
+
+    /// Whether the last analysis covered the file, so the cached artifacts
+    /// describe it and every declaration it uses.
+    fn is_analyzed(&self, _file_id: &FileId) -> bool {
+        true
+    }
+
     /// Gets the semantic context from the last analysis.
     ///
     /// Returns `Some` when the last call to `semantic()` succeeded in building
@@ -167,6 +274,24 @@ pub trait Project {
     /// has not been called or if foundational type resolution failed.
     fn analyzed_library(&self) -> Option<&Library>;
 
+    /// Gets the libraries as parsed, before analysis rewrites them: the
+    /// compatibility libraries in effect at the last `semantic()`, then each
+    /// source that parsed, in file order.
+    ///
+    /// Editor navigation walks these rather than the analyzed library, which
+    /// no longer has named call arguments or folded constants.
+    fn parsed_libraries(&self) -> Vec<&Library>;
+
+    /// Gets the index of the symbols in `parsed_libraries()`, built once per
+    /// analysis so that editor navigation does not index the workspace on
+    /// every request.
+    ///
+    /// Returns `None` if `semantic()` has not been called or the project does
+    /// not keep an index.
+    fn symbol_index(&self) -> Option<&SymbolIndex> {
+        None
+    }
+
     /// Gets the sources that are the project.
     fn sources(&self) -> Vec<&Source>;
 
@@ -181,10 +306,31 @@ pub struct FileBackedProject {
     source_project: SourceProject,
     /// Parse options for this project
     compiler_options: CompilerOptions,
+    /// Problem codes reported as warnings
+    demotions: Demotions,
     /// Cached semantic context from the last successful analysis
     semantic_context: Option<SemanticContext>,
     /// Cached analyzed library from the last successful analysis
     analyzed_library: Option<Library>,
+    /// Index of the libraries as parsed at the last analysis
+    symbol_index: Option<SymbolIndex>,
+    /// Compatibility libraries in effect at the last analysis
+    compat_libraries: Vec<Library>,
+    /// Sources whose text changed since the last analysis
+    changed: HashSet<FileId>,
+    /// What the next incremental analysis starts from; `None` until an
+    /// analysis in which every source parsed
+    incremental: Option<IncrementalState>,
+    /// Sources the last analysis covered when it did not cover them all
+    analyzed: Option<HashSet<FileId>>,
+}
+
+/// The result of the last analysis that an incremental analysis updates.
+struct IncrementalState {
+    /// The names each source declares and uses
+    names: HashMap<FileId, SourceNames>,
+    /// The diagnostics of every source
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl Default for FileBackedProject {
@@ -198,8 +344,14 @@ impl FileBackedProject {
         FileBackedProject {
             source_project: SourceProject::new(),
             compiler_options: CompilerOptions::default(),
+            demotions: Demotions::default(),
             semantic_context: None,
             analyzed_library: None,
+            symbol_index: None,
+            compat_libraries: Vec::new(),
+            changed: HashSet::new(),
+            incremental: None,
+            analyzed: None,
         }
     }
 
@@ -207,8 +359,14 @@ impl FileBackedProject {
         FileBackedProject {
             source_project: SourceProject::with_options(compiler_options),
             compiler_options,
+            demotions: Demotions::default(),
             semantic_context: None,
             analyzed_library: None,
+            symbol_index: None,
+            compat_libraries: Vec::new(),
+            changed: HashSet::new(),
+            incremental: None,
+            analyzed: None,
         }
     }
 
@@ -220,12 +378,29 @@ impl FileBackedProject {
         self.source_project.get_source(file_id)
     }
 
+    /// The problem codes reported as warnings: those set with
+    /// [`Project::set_demotions`] and those the project's settings file
+    /// demotes.
+    pub fn demotions(&self) -> Demotions {
+        let mut demotions = self.demotions.clone();
+        demotions.extend(self.source_project.demotions());
+        demotions
+    }
+
     /// Activate the named compatibility libraries (replacing any current set).
     ///
     /// Activation is out of band — it never modifies source — and comes only
     /// from an explicit channel such as a `--library` request.
     pub fn set_activated_libraries(&mut self, names: Vec<LibraryName>) {
         self.source_project.set_activated_libraries(names);
+        self.incremental = None;
+    }
+
+    /// Set the naming rules (replacing any current rules), for callers that
+    /// read the project's `ironplc.toml` themselves.
+    pub fn set_naming_conventions(&mut self, naming: NamingConventions) {
+        self.source_project.set_naming_conventions(naming);
+        self.incremental = None;
     }
 
     /// Load the activated compatibility libraries from the bundled registry.
@@ -240,15 +415,28 @@ impl FileBackedProject {
 impl Project for FileBackedProject {
     /// Create a new project from the files in the specified directory.
     fn initialize(&mut self, dir: &Path) -> Vec<Diagnostic> {
+        self.incremental = None;
         self.source_project.initialize_from_directory(dir)
     }
 
     /// Create a new project from the files in multiple directories,
     /// merged into one compilation unit.
     fn initialize_many(&mut self, dirs: &[&Path]) -> Vec<Diagnostic> {
+        self.incremental = None;
         self.source_project.initialize_from_directories(dirs)
     }
 
+    fn set_compiler_options(&mut self, compiler_options: CompilerOptions) {
+        self.compiler_options = compiler_options;
+        self.source_project.set_compiler_options(compiler_options);
+        self.incremental = None;
+    }
+
+    fn set_demotions(&mut self, demotions: Demotions) {
+        self.demotions = demotions;
+        self.incremental = None;
+    }
+
     fn change_text_document(&mut self, file_id: &FileId, content: String) {
         trace!(
             "Change text document sources initial length is {}",
@@ -256,6 +444,7 @@ impl Project for FileBackedProject {
         );
 
         self.source_project.add_source(file_id.clone(), content);
+        self.changed.insert(file_id.clone());
 
         trace!(
             "Change text document sources new length is {}",
@@ -281,13 +470,123 @@ impl Project for FileBackedProject {
     fn semantic(&mut self) -> Vec<Diagnostic> {
         self.semantic_context = None;
         self.analyzed_library = None;
-        let (diagnostics, context, library) =
-            run_semantic_analysis(&mut self.source_project, &self.compiler_options, &[]);
+        let (diagnostics, context, library, compat_libraries) = run_semantic_analysis(
+            &mut self.source_project,
+            &self.compiler_options,
+            &self.demotions,
+            &[],
+            None,
+        );
         self.semantic_context = context;
         self.analyzed_library = library;
+        self.compat_libraries = compat_libraries;
+        self.symbol_index = Some(SymbolIndex::build(&self.parsed_libraries()));
+        self.changed.clear();
+        self.analyzed = None;
+
+        // Analysis left out any source that failed to parse, so there is
+        // nothing complete for the next incremental analysis to update.
+        let names: Option<HashMap<FileId, SourceNames>> = self
+            .source_project
+            .sources()
+            .into_iter()
+            .map(|source| {
+                let library = source.parsed_library()?;
+                Some((source.file_id().clone(), SourceNames::of(library)))
+            })
+            .collect();
+        self.incremental = names.map(|names| IncrementalState {
+            names,
+            diagnostics: diagnostics.clone(),
+        });
         diagnostics
     }
 
+    /// Analyzes the sources that changed, the sources that use what they
+    /// declare, and the sources those depend on. Analysis is complete
+    /// instead when there is no earlier analysis to update or a changed
+    /// source is new, does not parse, or declares different names.
+    fn semantic_incremental(&mut self) -> Vec<Diagnostic> {
+        let Some(mut state) = self.incremental.take() else {
+            return self.semantic();
+        };
+        // These checks judge a declaration by every source of the project,
+        // so analysis of part of it reports what the rest already uses.
+        if self.compiler_options.check_unused || self.compiler_options.check_task_races {
+            return self.semantic();
+        }
+        // A source that a changed source used, before or after the change,
+        // can gain or lose findings about its declarations, such as an
+        // unreachable function block whose last use was deleted.
+        let mut used = dependencies::with_dependencies(&self.changed, &state.names);
+        for file_id in &self.changed {
+            let Some(previous) = state.names.get(file_id) else {
+                return self.semantic();
+            };
+            let Some(Ok(library)) = self
+                .source_project
+                .get_source_mut(file_id)
+                .map(|source| source.library())
+            else {
+                return self.semantic();
+            };
+            let names = SourceNames::of(library);
+            if names.declared != previous.declared {
+                return self.semantic();
+            }
+            state.names.insert(file_id.clone(), names);
+        }
+
+        used.extend(dependencies::with_dependencies(&self.changed, &state.names));
+        let affected = dependencies::affected(&used, &state.names);
+        let needed = dependencies::with_dependencies(&affected, &state.names);
+        debug!(
+            "Incremental analysis of {} of {} sources",
+            needed.len(),
+            state.names.len()
+        );
+
+        self.semantic_context = None;
+        self.analyzed_library = None;
+        let (diagnostics, context, library, compat_libraries) = run_semantic_analysis(
+            &mut self.source_project,
+            &self.compiler_options,
+            &self.demotions,
+            &[],
+            Some(&needed),
+        );
+        self.semantic_context = context;
+        self.analyzed_library = library;
+        self.compat_libraries = compat_libraries;
+        self.symbol_index = Some(SymbolIndex::build(&self.parsed_libraries()));
+        self.changed.clear();
+        self.analyzed = Some(needed);
+
+        // The diagnostics of a source the change cannot affect are the same
+        // as before; those outside any source come from the new analysis.
+        let is_kept =
+            |file_id: &FileId| state.names.contains_key(file_id) && !affected.contains(file_id);
+        let mut merged: Vec<Diagnostic> = state
+            .diagnostics
+            .into_iter()
+            .filter(|diagnostic| is_kept(&diagnostic.primary.file_id))
+            .collect();
+        merged.extend(
+            diagnostics
+                .into_iter()
+                .filter(|diagnostic| !is_kept(&diagnostic.primary.file_id)),
+        );
+        state.diagnostics = merged.clone();
+        self.incremental = Some(state);
+        merged
+    }
+
+    fn is_analyzed(&self, file_id: &FileId) -> bool {
+        self.analyzed
+            .as_ref()
+            .is_none_or(|analyzed| analyzed.contains(file_id))
+    }
+
     fn semantic_context(&self) -> Option<&SemanticContext> {
         self.semantic_context.as_ref()
     }
@@ -296,6 +595,14 @@ impl Project for FileBackedProject {
         self.analyzed_library.as_ref()
     }
 
+    fn parsed_libraries(&self) -> Vec<&Library> {
+        parsed_libraries(&self.compat_libraries, self.source_project.sources())
+    }
+
+    fn symbol_index(&self) -> Option<&SymbolIndex> {
+        self.symbol_index.as_ref()
+    }
+
     fn sources(&self) -> Vec<&Source> {
         self.source_project.sources()
     }
@@ -318,10 +625,14 @@ pub struct MemoryBackedProject {
     source_project: SourceProject,
     /// Parse options for this project
     compiler_options: CompilerOptions,
+    /// Problem codes reported as warnings
+    demotions: Demotions,
     /// Cached semantic context from the last successful analysis
     semantic_context: Option<SemanticContext>,
     /// Cached analyzed library from the last successful analysis
     analyzed_library: Option<Library>,
+    /// Compatibility libraries in effect at the last analysis
+    compat_libraries: Vec<Library>,
     /// Compatibility libraries the caller parsed itself, injected ahead of
     /// user source alongside any the bundled registry loads.
     preparsed_libraries: Vec<Library>,
@@ -333,8 +644,10 @@ impl MemoryBackedProject {
         MemoryBackedProject {
             source_project: SourceProject::with_options(compiler_options),
             compiler_options,
+            demotions: Demotions::default(),
             semantic_context: None,
             analyzed_library: None,
+            compat_libraries: Vec::new(),
             preparsed_libraries: Vec::new(),
         }
     }
@@ -379,6 +692,12 @@ impl MemoryBackedProject {
     pub fn set_preparsed_libraries(&mut self, libraries: Vec<Library>) {
         self.preparsed_libraries = libraries;
     }
+
+    /// Set the naming rules that a project's `ironplc.toml` would otherwise
+    /// supply (replacing any current rules).
+    pub fn set_naming_conventions(&mut self, naming: NamingConventions) {
+        self.source_project.set_naming_conventions(naming);
+    }
 }
 
 impl Project for MemoryBackedProject {
@@ -392,6 +711,15 @@ impl Project for MemoryBackedProject {
         )]
     }
 
+    fn set_compiler_options(&mut self, compiler_options: CompilerOptions) {
+        self.compiler_options = compiler_options;
+        self.source_project.set_compiler_options(compiler_options);
+    }
+
+    fn set_demotions(&mut self, demotions: Demotions) {
+        self.demotions = demotions;
+    }
+
     fn change_text_document(&mut self, file_id: &FileId, content: String) {
         self.source_project.add_source(file_id.clone(), content);
     }
@@ -414,13 +742,16 @@ impl Project for MemoryBackedProject {
     fn semantic(&mut self) -> Vec<Diagnostic> {
         self.semantic_context = None;
         self.analyzed_library = None;
-        let (diagnostics, context, library) = run_semantic_analysis(
+        let (diagnostics, context, library, compat_libraries) = run_semantic_analysis(
             &mut self.source_project,
             &self.compiler_options,
+            &self.demotions,
             &self.preparsed_libraries,
+            None,
         );
         self.semantic_context = context;
         self.analyzed_library = library;
+        self.compat_libraries = compat_libraries;
         diagnostics
     }
 
@@ -432,6 +763,10 @@ impl Project for MemoryBackedProject {
         self.analyzed_library.as_ref()
     }
 
+    fn parsed_libraries(&self) -> Vec<&Library> {
+        parsed_libraries(&self.compat_libraries, self.source_project.sources())
+    }
+
     fn sources(&self) -> Vec<&Source> {
         self.source_project.sources()
     }
@@ -447,8 +782,9 @@ impl Project for MemoryBackedProject {
 
 #[cfg(test)]
 mod test {
-    use ironplc_dsl::core::FileId;
+    use ironplc_dsl::{common::LibraryElementKind, core::FileId};
     use ironplc_parser::options::{CompilerOptions, Dialect};
+    use ironplc_problems::Problem;
     use std::path::Path;
 
     use super::{FileBackedProject, LibraryName, MemoryBackedProject, Project};
@@ -508,6 +844,30 @@ mod test {
         );
     }
 
+    #[test]
+    fn parsed_libraries_when_tc2_system_activated_then_library_precedes_source() {
+        let mut project = MemoryBackedProject::new(library_options());
+        project.set_activated_libraries(vec![LibraryName::from("Tc2_System")]);
+        project.add_source(FileId::from_string("main.st"), PI_PROGRAM.to_owned());
+        project.semantic();
+
+        let libraries = project.parsed_libraries();
+
+        assert_eq!(libraries.len(), 2);
+        assert!(matches!(
+            libraries[1].elements[0],
+            LibraryElementKind::FunctionBlockDeclaration(_)
+        ));
+    }
+
+    #[test]
+    fn parsed_libraries_when_semantic_not_called_then_empty() {
+        let mut project = MemoryBackedProject::new(library_options());
+        project.add_source(FileId::from_string("main.st"), PI_PROGRAM.to_owned());
+
+        assert!(project.parsed_libraries().is_empty());
+    }
+
     /// End-to-end (Phase 2): activation comes *only* from a discovered
     /// `.plcproj`'s library reference -- no `--library` flag and no source-level
     /// directive -- yet `PI` resolves and the initializer folds.
@@ -753,6 +1113,71 @@ mod test {
         );
     }
 
+    #[test]
+    fn semantic_when_check_unused_and_library_function_not_called_then_ok() {
+        let mut project = MemoryBackedProject::new(CompilerOptions {
+            check_unused: true,
+            ..CompilerOptions::default()
+        });
+        project.set_preparsed_libraries(vec![parse_library(LIB_FUNCTION)]);
+        project.add_source(
+            FileId::from_string("main.st"),
+            "PROGRAM main VAR a : LREAL; END_VAR a := a + 1.0; END_PROGRAM".to_owned(),
+        );
+
+        let result = project.semantic();
+        assert!(
+            result.is_empty(),
+            "an unused library function is not the project's: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn semantic_when_check_unused_and_user_function_not_called_then_unreachable() {
+        let mut project = MemoryBackedProject::new(CompilerOptions {
+            check_unused: true,
+            ..CompilerOptions::default()
+        });
+        project.add_source(
+            FileId::from_string("main.st"),
+            format!("{LIB_FUNCTION} PROGRAM main VAR a : LREAL; END_VAR a := a + 1.0; END_PROGRAM"),
+        );
+
+        let result = project.semantic();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].code, "P4056");
+    }
+
+    #[test]
+    fn semantic_when_naming_rule_and_library_breaks_it_then_only_user_name_reported() {
+        use ironplc_sources::naming::{NameRule, NamedKind, NamingConventions};
+
+        let mut conventions = NamingConventions::default();
+        conventions.set(
+            NamedKind::Function,
+            NameRule {
+                prefix: Some("F_".to_string()),
+                ..NameRule::default()
+            },
+        );
+        let mut project = MemoryBackedProject::new(CompilerOptions::default());
+        project.set_naming_conventions(conventions);
+        project.set_preparsed_libraries(vec![parse_library(LIB_FUNCTION)]);
+        project.add_source(
+            FileId::from_string("main.st"),
+            format!(
+                "FUNCTION Twice : LREAL VAR_INPUT x : LREAL; END_VAR Twice := x * 2.0; \
+                 END_FUNCTION {CALLS_LIB_FUNCTION}"
+            ),
+        );
+
+        let result = project.semantic();
+        assert_eq!(result.len(), 1, "{result:?}");
+        assert_eq!(result[0].code, "P4057");
+        assert_eq!(result[0].primary.file_id, FileId::from_string("main.st"));
+    }
+
     // -----------------------------------------------------------------
     // What analysis does when parsing produced nothing to analyze.
     // -----------------------------------------------------------------
@@ -854,8 +1279,13 @@ mod test {
         use ironplc_sources::SourceProject;
 
         fn element_names(source_project: &mut SourceProject) -> Vec<String> {
-            let (_, _, library) =
-                super::run_semantic_analysis(source_project, &CompilerOptions::default(), &[]);
+            let (_, _, library, _) = super::run_semantic_analysis(
+                source_project,
+                &CompilerOptions::default(),
+                &Default::default(),
+                &[],
+                None,
+            );
             let library = library.unwrap();
             library
                 .elements
@@ -904,6 +1334,170 @@ mod test {
         assert_eq!(element_names(&mut forward), element_names(&mut reverse));
     }
 
+    // -----------------------------------------------------------------
+    // Incremental analysis.
+    // See specs/plans/2026-10-18-lsp-incremental-analysis.md.
+    // -----------------------------------------------------------------
+
+    const COUNTER: &str = "FUNCTION_BLOCK Counter
+VAR_INPUT
+    Delta : INT;
+END_VAR
+END_FUNCTION_BLOCK";
+    const MAIN: &str = "PROGRAM Main
+VAR
+    Tally : Counter;
+END_VAR
+    Tally(Delta := 1);
+END_PROGRAM";
+    const OTHER: &str = "PROGRAM Other
+    Missing := 1;
+END_PROGRAM";
+
+    fn incremental_project() -> FileBackedProject {
+        let mut project = FileBackedProject::default();
+        project.change_text_document(&FileId::from_string("counter.st"), COUNTER.to_owned());
+        project.change_text_document(&FileId::from_string("main.st"), MAIN.to_owned());
+        project.change_text_document(&FileId::from_string("other.st"), OTHER.to_owned());
+        project
+    }
+
+    fn primary_files(diagnostics: &[ironplc_dsl::diagnostic::Diagnostic]) -> Vec<String> {
+        let mut files: Vec<String> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.primary.file_id.to_string())
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn semantic_incremental_when_body_changes_then_analyzes_only_dependent_sources() {
+        let mut project = incremental_project();
+        let full = project.semantic();
+
+        project.change_text_document(
+            &FileId::from_string("main.st"),
+            MAIN.replace("Delta := 1", "Delta := 2"),
+        );
+        let incremental = project.semantic_incremental();
+
+        assert_eq!(primary_files(&incremental), primary_files(&full));
+        assert!(project.is_analyzed(&FileId::from_string("main.st")));
+        assert!(project.is_analyzed(&FileId::from_string("counter.st")));
+        assert!(!project.is_analyzed(&FileId::from_string("other.st")));
+    }
+
+    #[test]
+    fn semantic_incremental_when_error_added_then_reported_with_kept_errors() {
+        let mut project = incremental_project();
+        project.semantic();
+
+        project.change_text_document(
+            &FileId::from_string("main.st"),
+            MAIN.replace(
+                "Tally(Delta := 1);",
+                "Tally(Delta := 1);\n    Unknown := 2;",
+            ),
+        );
+        let incremental = project.semantic_incremental();
+
+        assert_eq!(
+            primary_files(&incremental),
+            vec!["main.st".to_string(), "other.st".to_string()]
+        );
+    }
+
+    #[test]
+    fn semantic_incremental_when_declarations_change_then_analyzes_everything() {
+        let mut project = incremental_project();
+        project.semantic();
+
+        project.change_text_document(
+            &FileId::from_string("counter.st"),
+            COUNTER.replace("Counter", "Tally2"),
+        );
+        let incremental = project.semantic_incremental();
+
+        assert!(project.is_analyzed(&FileId::from_string("other.st")));
+        assert!(primary_files(&incremental).contains(&"main.st".to_string()));
+    }
+
+    #[test]
+    fn symbol_index_when_semantic_not_called_then_none() {
+        let project = incremental_project();
+
+        assert!(project.symbol_index().is_none());
+    }
+
+    #[test]
+    fn symbol_index_when_source_changed_then_rebuilt_by_next_analysis() {
+        let mut project = incremental_project();
+        project.semantic();
+        assert_eq!(project.symbol_index().unwrap().search("Primary").len(), 0);
+
+        project.change_text_document(
+            &FileId::from_string("main.st"),
+            MAIN.replace("PROGRAM Main", "PROGRAM Primary"),
+        );
+        project.semantic_incremental();
+
+        assert_eq!(project.symbol_index().unwrap().search("Primary").len(), 1);
+    }
+
+    #[test]
+    fn semantic_incremental_when_last_use_deleted_then_reports_unreachable_pou() {
+        let mut project = FileBackedProject::with_options(CompilerOptions {
+            check_unused: true,
+            ..CompilerOptions::default()
+        });
+        project.change_text_document(&FileId::from_string("counter.st"), COUNTER.to_owned());
+        project.change_text_document(&FileId::from_string("main.st"), MAIN.to_owned());
+        let before = project.semantic();
+        assert!(!before.iter().any(|diagnostic| {
+            diagnostic.code == Problem::UnreachablePou.code()
+                && diagnostic.primary.file_id == FileId::from_string("counter.st")
+        }));
+
+        project.change_text_document(
+            &FileId::from_string("main.st"),
+            "PROGRAM Main\nEND_PROGRAM".to_owned(),
+        );
+        let incremental = project.semantic_incremental();
+
+        assert!(incremental.iter().any(|diagnostic| {
+            diagnostic.code == Problem::UnreachablePou.code()
+                && diagnostic.primary.file_id == FileId::from_string("counter.st")
+        }));
+    }
+
+    #[test]
+    fn semantic_incremental_when_check_unused_then_analyzes_everything() {
+        let mut project = FileBackedProject::with_options(CompilerOptions {
+            check_unused: true,
+            ..CompilerOptions::default()
+        });
+        project.change_text_document(&FileId::from_string("counter.st"), COUNTER.to_owned());
+        project.change_text_document(
+            &FileId::from_string("main.st"),
+            "PROGRAM Main\nEND_PROGRAM".to_owned(),
+        );
+        let full = project.semantic();
+
+        project.change_text_document(
+            &FileId::from_string("counter.st"),
+            COUNTER.replace("END_VAR", "END_VAR\n    Delta := 1;"),
+        );
+        let incremental = project.semantic_incremental();
+
+        assert!(project.is_analyzed(&FileId::from_string("main.st")));
+        assert_eq!(primary_files(&incremental), primary_files(&full));
+        assert!(incremental.iter().any(|diagnostic| {
+            diagnostic.code == Problem::UnreachablePou.code()
+                && diagnostic.primary.file_id == FileId::from_string("counter.st")
+        }));
+    }
+
     // XML source handling (empty-library, parse errors) is owned and tested by
     // `ironplc_sources::source`; this crate only routes file content there.
 
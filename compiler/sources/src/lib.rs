@@ -51,8 +51,10 @@
 pub mod discovery;
 pub mod file_type;
 pub mod libraries;
+pub mod naming;
 pub mod parsers;
 pub mod project;
+pub mod settings;
 pub mod source;
 pub mod xml;
 
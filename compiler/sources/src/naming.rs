@@ -0,0 +1,392 @@
+//! Naming conventions from the `[naming]` table of `ironplc.toml`.
+//!
+//! Each rule gives the prefix, suffix and letter case that a kind of
+//! declaration must have. Rules in `[naming]` are keyed by the kind of
+//! declaration; rules in `[naming.types]` are keyed by the type of a
+//! variable:
+//!
+//! ```toml
+//! [naming]
+//! function_block = { prefix = "FB_", case = "PascalCase" }
+//! enumeration = { prefix = "E_" }
+//! function_block_instance = { prefix = "fb" }
+//!
+//! [naming.types]
+//! BOOL = { prefix = "b" }
+//! ```
+//!
+//! The checks themselves run with semantic analysis, in the project crate.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The letter case of the part of a name between its prefix and suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameCase {
+    /// `MotorSpeed`
+    PascalCase,
+    /// `motorSpeed`
+    CamelCase,
+    /// `MOTOR_SPEED`
+    UpperCase,
+    /// `motor_speed`
+    SnakeCase,
+}
+
+impl NameCase {
+    const ALL: [NameCase; 4] = [
+        NameCase::PascalCase,
+        NameCase::CamelCase,
+        NameCase::UpperCase,
+        NameCase::SnakeCase,
+    ];
+
+    fn key(&self) -> &'static str {
+        match self {
+            NameCase::PascalCase => "PascalCase",
+            NameCase::CamelCase => "camelCase",
+            NameCase::UpperCase => "UPPER_CASE",
+            NameCase::SnakeCase => "snake_case",
+        }
+    }
+
+    /// Returns `true` when `text` is written in this case. Digits may appear
+    /// anywhere but first.
+    pub fn matches(&self, text: &str) -> bool {
+        let Some(first) = text.chars().next() else {
+            return true;
+        };
+        let rest_is = |allowed: fn(char) -> bool| text.chars().skip(1).all(allowed);
+        match self {
+            NameCase::PascalCase => {
+                first.is_ascii_uppercase() && rest_is(|c| c.is_ascii_alphanumeric())
+            }
+            NameCase::CamelCase => {
+                first.is_ascii_lowercase() && rest_is(|c| c.is_ascii_alphanumeric())
+            }
+            NameCase::UpperCase => {
+                first.is_ascii_uppercase()
+                    && rest_is(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            }
+            NameCase::SnakeCase => {
+                first.is_ascii_lowercase()
+                    && rest_is(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            }
+        }
+    }
+}
+
+impl fmt::Display for NameCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// The kinds of declaration that `[naming]` has a rule for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NamedKind {
+    Program,
+    Function,
+    FunctionBlock,
+    Method,
+    Interface,
+    Enumeration,
+    Structure,
+    /// Any other data type: aliases, subranges, arrays, strings and
+    /// references.
+    Type,
+    /// `VAR` and `VAR_TEMP` variables, and any variable that no more
+    /// specific rule covers.
+    Variable,
+    Input,
+    Output,
+    InOut,
+    /// `VAR_GLOBAL` and `VAR_EXTERNAL` variables.
+    Global,
+    /// `CONSTANT` variables.
+    Constant,
+    FunctionBlockInstance,
+}
+
+impl NamedKind {
+    const ALL: [NamedKind; 15] = [
+        NamedKind::Program,
+        NamedKind::Function,
+        NamedKind::FunctionBlock,
+        NamedKind::Method,
+        NamedKind::Interface,
+        NamedKind::Enumeration,
+        NamedKind::Structure,
+        NamedKind::Type,
+        NamedKind::Variable,
+        NamedKind::Input,
+        NamedKind::Output,
+        NamedKind::InOut,
+        NamedKind::Global,
+        NamedKind::Constant,
+        NamedKind::FunctionBlockInstance,
+    ];
+
+    /// The key of the kind in `[naming]`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            NamedKind::Program => "program",
+            NamedKind::Function => "function",
+            NamedKind::FunctionBlock => "function_block",
+            NamedKind::Method => "method",
+            NamedKind::Interface => "interface",
+            NamedKind::Enumeration => "enumeration",
+            NamedKind::Structure => "structure",
+            NamedKind::Type => "type",
+            NamedKind::Variable => "variable",
+            NamedKind::Input => "input",
+            NamedKind::Output => "output",
+            NamedKind::InOut => "in_out",
+            NamedKind::Global => "global",
+            NamedKind::Constant => "constant",
+            NamedKind::FunctionBlockInstance => "function_block_instance",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.key() == key)
+    }
+}
+
+/// What a name must look like.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameRule {
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    /// The case of the name once the prefix and suffix are removed.
+    pub case: Option<NameCase>,
+}
+
+impl NameRule {
+    /// Describes each way `name` breaks the rule; empty when it follows it.
+    pub fn violations(&self, name: &str) -> Vec<String> {
+        let mut violations = vec![];
+        let mut stem = name;
+        if let Some(prefix) = &self.prefix {
+            match stem.strip_prefix(prefix.as_str()) {
+                Some(rest) => stem = rest,
+                None => violations.push(format!("Expected prefix '{prefix}'")),
+            }
+        }
+        if let Some(suffix) = &self.suffix {
+            match stem.strip_suffix(suffix.as_str()) {
+                Some(rest) => stem = rest,
+                None => violations.push(format!("Expected suffix '{suffix}'")),
+            }
+        }
+        if let Some(case) = &self.case {
+            if !case.matches(stem) {
+                let part = if self.prefix.is_some() || self.suffix.is_some() {
+                    " between the prefix and suffix"
+                } else {
+                    ""
+                };
+                violations.push(format!("Expected {case}{part}"));
+            }
+        }
+        violations
+    }
+
+    fn from_toml(value: &toml::Value, at: &str) -> Result<Self, String> {
+        let table = value
+            .as_table()
+            .ok_or_else(|| format!("`{at}` must be a table"))?;
+        let mut rule = NameRule::default();
+        for (key, value) in table {
+            let text = value
+                .as_str()
+                .ok_or_else(|| format!("`{at}.{key}` must be a string"))?;
+            match key.as_str() {
+                "prefix" => rule.prefix = Some(text.to_string()),
+                "suffix" => rule.suffix = Some(text.to_string()),
+                "case" => {
+                    let case = NameCase::ALL
+                        .into_iter()
+                        .find(|case| case.key() == text)
+                        .ok_or_else(|| {
+                            format!(
+                                "`{at}.case` must be one of {}",
+                                NameCase::ALL.map(|case| case.key()).join(", ")
+                            )
+                        })?;
+                    rule.case = Some(case);
+                }
+                _ => {
+                    return Err(format!(
+                        "`{at}` has unknown key `{key}`; expected prefix, suffix or case"
+                    ))
+                }
+            }
+        }
+        Ok(rule)
+    }
+}
+
+/// The naming rules of a project. A project without rules checks nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NamingConventions {
+    kinds: BTreeMap<NamedKind, NameRule>,
+    /// Rules for variables by type, keyed by the lower case type name.
+    types: BTreeMap<String, NameRule>,
+}
+
+impl NamingConventions {
+    /// Parses the `[naming]` table.
+    pub fn from_toml(value: &toml::Value) -> Result<Self, String> {
+        let table = value.as_table().ok_or("`naming` must be a table")?;
+        let mut conventions = NamingConventions::default();
+        for (key, value) in table {
+            if key == "types" {
+                let types = value.as_table().ok_or("`naming.types` must be a table")?;
+                for (type_name, value) in types {
+                    let rule = NameRule::from_toml(value, &format!("naming.types.{type_name}"))?;
+                    conventions.types.insert(type_name.to_lowercase(), rule);
+                }
+                continue;
+            }
+            let kind = NamedKind::from_key(key).ok_or_else(|| {
+                format!(
+                    "`naming.{key}` is not a kind of declaration; expected types or one of {}",
+                    NamedKind::ALL.map(|kind| kind.key()).join(", ")
+                )
+            })?;
+            let rule = NameRule::from_toml(value, &format!("naming.{key}"))?;
+            conventions.kinds.insert(kind, rule);
+        }
+        Ok(conventions)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty() && self.types.is_empty()
+    }
+
+    /// Sets `rule` for a kind of declaration.
+    pub fn set(&mut self, kind: NamedKind, rule: NameRule) {
+        self.kinds.insert(kind, rule);
+    }
+
+    /// Sets `rule` for variables of type `type_name`.
+    pub fn set_type(&mut self, type_name: &str, rule: NameRule) {
+        self.types.insert(type_name.to_lowercase(), rule);
+    }
+
+    /// The rule for a kind of declaration.
+    pub fn rule(&self, kind: NamedKind) -> Option<&NameRule> {
+        self.kinds.get(&kind)
+    }
+
+    /// The rule for variables of type `type_name`, ignoring case.
+    pub fn type_rule(&self, type_name: &str) -> Option<&NameRule> {
+        self.types.get(&type_name.to_lowercase())
+    }
+
+    /// Adds the rules of `other`, which replace rules for the same kind or
+    /// type.
+    pub fn extend(&mut self, other: &NamingConventions) {
+        self.kinds
+            .extend(other.kinds.iter().map(|(kind, rule)| (*kind, rule.clone())));
+        self.types.extend(
+            other
+                .types
+                .iter()
+                .map(|(name, rule)| (name.clone(), rule.clone())),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Result<NamingConventions, String> {
+        let table: toml::Table = toml::from_str(content).unwrap();
+        NamingConventions::from_toml(&toml::Value::Table(table))
+    }
+
+    #[test]
+    fn from_toml_when_kind_and_type_rules_then_parsed() {
+        let conventions = parse(
+            r#"
+function_block = { prefix = "FB_", case = "PascalCase" }
+
+[types]
+BOOL = { prefix = "b" }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            conventions.rule(NamedKind::FunctionBlock),
+            Some(&NameRule {
+                prefix: Some("FB_".to_string()),
+                suffix: None,
+                case: Some(NameCase::PascalCase),
+            })
+        );
+        assert_eq!(
+            conventions.type_rule("bool").unwrap().prefix.as_deref(),
+            Some("b")
+        );
+    }
+
+    #[test]
+    fn from_toml_when_unknown_kind_then_error() {
+        let err = parse(r#"pou = { case = "PascalCase" }"#).unwrap_err();
+        assert!(err.contains("naming.pou"));
+    }
+
+    #[test]
+    fn from_toml_when_unknown_case_then_error() {
+        let err = parse(r#"program = { case = "Title" }"#).unwrap_err();
+        assert!(err.contains("naming.program.case"));
+    }
+
+    #[test]
+    fn violations_when_prefix_and_case_followed_then_empty() {
+        let rule = NameRule {
+            prefix: Some("FB_".to_string()),
+            suffix: None,
+            case: Some(NameCase::PascalCase),
+        };
+        assert!(rule.violations("FB_MotorControl").is_empty());
+    }
+
+    #[test]
+    fn violations_when_prefix_missing_then_reports_prefix() {
+        let rule = NameRule {
+            prefix: Some("b".to_string()),
+            suffix: None,
+            case: None,
+        };
+        assert_eq!(rule.violations("running"), vec!["Expected prefix 'b'"]);
+    }
+
+    #[test]
+    fn violations_when_case_wrong_after_prefix_then_reports_case() {
+        let rule = NameRule {
+            prefix: Some("E_".to_string()),
+            suffix: None,
+            case: Some(NameCase::PascalCase),
+        };
+        assert_eq!(
+            rule.violations("E_machine_state"),
+            vec!["Expected PascalCase between the prefix and suffix"]
+        );
+    }
+
+    #[test]
+    fn matches_when_each_case_then_expected() {
+        assert!(NameCase::PascalCase.matches("MotorSpeed2"));
+        assert!(!NameCase::PascalCase.matches("motorSpeed"));
+        assert!(NameCase::CamelCase.matches("motorSpeed"));
+        assert!(!NameCase::CamelCase.matches("motor_speed"));
+        assert!(NameCase::UpperCase.matches("MAX_SPEED"));
+        assert!(!NameCase::UpperCase.matches("Max_Speed"));
+        assert!(NameCase::SnakeCase.matches("max_speed"));
+        assert!(!NameCase::SnakeCase.matches("MaxSpeed"));
+    }
+}
@@ -2,12 +2,18 @@
 
 use std::{collections::HashMap, path::Path};
 
-use ironplc_dsl::{common::Library, core::FileId, diagnostic::Diagnostic};
+use ironplc_dsl::{
+    common::Library,
+    core::FileId,
+    diagnostic::{Demotions, Diagnostic},
+};
 use ironplc_parser::options::CompilerOptions;
 use log::{debug, info, trace};
 
 use crate::file_type::FileType;
 use crate::libraries::{LibraryName, LibraryRegistry};
+use crate::naming::NamingConventions;
+use crate::settings::ProjectSettings;
 use crate::source::Source;
 
 /// A project consisting of one or more source files
@@ -22,6 +28,10 @@ pub struct SourceProject {
     /// reference or an explicit CLI/playground request) and is never inferred
     /// from source content (`REQ-CL-sources-005`).
     activated_libraries: Vec<LibraryName>,
+    /// Problem codes the discovered projects' `ironplc.toml` demote.
+    demotions: Demotions,
+    /// Naming rules from the discovered projects' `ironplc.toml`.
+    naming: NamingConventions,
 }
 
 impl Default for SourceProject {
@@ -37,6 +47,8 @@ impl SourceProject {
             sources: HashMap::new(),
             compiler_options: CompilerOptions::default(),
             activated_libraries: Vec::new(),
+            demotions: Demotions::default(),
+            naming: NamingConventions::default(),
         }
     }
 
@@ -46,6 +58,8 @@ impl SourceProject {
             sources: HashMap::new(),
             compiler_options,
             activated_libraries: Vec::new(),
+            demotions: Demotions::default(),
+            naming: NamingConventions::default(),
         }
     }
 
@@ -63,6 +77,22 @@ impl SourceProject {
         &self.activated_libraries
     }
 
+    /// The problem codes the discovered projects' settings demote to
+    /// warnings.
+    pub fn demotions(&self) -> &Demotions {
+        &self.demotions
+    }
+
+    /// The naming rules of the discovered projects' settings.
+    pub fn naming_conventions(&self) -> &NamingConventions {
+        &self.naming
+    }
+
+    /// Replaces the naming rules, for hosts that have no settings file.
+    pub fn set_naming_conventions(&mut self, naming: NamingConventions) {
+        self.naming = naming;
+    }
+
     /// Load the activated compatibility libraries from the bundled registry.
     ///
     /// Returns the parsed [`Library`] for each activated library that resolves,
@@ -94,6 +124,14 @@ impl SourceProject {
         }
     }
 
+    /// Changes the parse options of the project and of every source in it.
+    pub fn set_compiler_options(&mut self, compiler_options: CompilerOptions) {
+        self.compiler_options = compiler_options;
+        for source in self.sources.values_mut() {
+            source.set_compiler_options(compiler_options);
+        }
+    }
+
     /// Add source content directly to the project
     pub fn add_source(&mut self, file_id: FileId, content: String) {
         trace!("Adding source file: {}", file_id);
@@ -145,6 +183,8 @@ impl SourceProject {
     pub fn initialize_from_directory(&mut self, dir: &Path) -> Vec<Diagnostic> {
         self.sources.clear();
         self.activated_libraries.clear();
+        self.demotions = Demotions::default();
+        self.naming = NamingConventions::default();
         self.discover_and_add(dir)
     }
 
@@ -160,6 +200,8 @@ impl SourceProject {
     pub fn initialize_from_directories(&mut self, dirs: &[&Path]) -> Vec<Diagnostic> {
         self.sources.clear();
         self.activated_libraries.clear();
+        self.demotions = Demotions::default();
+        self.naming = NamingConventions::default();
         let mut errors = vec![];
         for dir in dirs {
             errors.extend(self.discover_and_add(dir));
@@ -202,6 +244,18 @@ impl SourceProject {
         }
         errors.extend(reference_diagnostics);
 
+        // Settings in the project's `ironplc.toml`. A code that cannot be
+        // demoted contributes a diagnostic and stays an error.
+        match ProjectSettings::load(&discovered.root_dir) {
+            Ok(settings) => {
+                let (demotions, refused) = settings.demotions();
+                self.demotions.extend(&demotions);
+                self.naming.extend(&settings.naming);
+                errors.extend(refused);
+            }
+            Err(err) => errors.push(err),
+        }
+
         for file_path in &discovered.files {
             let file_id = FileId::from_path(file_path);
             if let Err(err) = self.add_file(file_id) {
@@ -0,0 +1,232 @@
+//! Project settings (`ironplc.toml`) parsing.
+//!
+//! A project may keep an `ironplc.toml` next to its manifest (or in the
+//! folder of an unstructured project) for settings that belong to the
+//! project rather than to one invocation: the set of problem codes the
+//! project reports as warnings (ADR-0039), and the project's naming
+//! conventions (see [`crate::naming`]):
+//!
+//! ```toml
+//! [diagnostics]
+//! demote = ["P4041"]
+//!
+//! [naming]
+//! function_block = { prefix = "FB_" }
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use ironplc_dsl::core::FileId;
+use ironplc_dsl::diagnostic::{Demotions, Diagnostic, Label};
+use ironplc_problems::Problem;
+
+use crate::naming::NamingConventions;
+
+/// The name of the project settings file.
+pub const SETTINGS_FILE: &str = "ironplc.toml";
+
+/// Parsed `ironplc.toml` settings. A project without the file has the
+/// default settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectSettings {
+    /// The file the settings were read from, if any.
+    pub file_id: Option<FileId>,
+    /// Problem codes listed in `[diagnostics] demote`, as written.
+    pub demote: Vec<String>,
+    /// The rules in `[naming]`.
+    pub naming: NamingConventions,
+}
+
+impl ProjectSettings {
+    /// Reads the settings file in `root_dir`, returning the default settings
+    /// when there is none.
+    pub fn load(root_dir: &Path) -> Result<Self, Diagnostic> {
+        let path = root_dir.join(SETTINGS_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let file_id = FileId::from_path(&path);
+        let content = fs::read_to_string(&path).map_err(|e| {
+            Diagnostic::problem(
+                Problem::CannotReadFile,
+                Label::file(file_id.clone(), e.to_string()),
+            )
+        })?;
+        Self::from_toml(&content, &file_id)
+    }
+
+    /// Parses settings from `ironplc.toml` text. `file_id` locates the
+    /// diagnostic on the settings file.
+    pub fn from_toml(content: &str, file_id: &FileId) -> Result<Self, Diagnostic> {
+        let table: toml::Table = toml::from_str(content)
+            .map_err(|e| Self::invalid(file_id, format!("settings are not valid TOML: {e}")))?;
+
+        let mut demote = vec![];
+        if let Some(diagnostics) = table.get("diagnostics") {
+            let diagnostics = diagnostics
+                .as_table()
+                .ok_or_else(|| Self::invalid(file_id, "`diagnostics` must be a table"))?;
+            if let Some(codes) = diagnostics.get("demote") {
+                let codes = codes.as_array().ok_or_else(|| {
+                    Self::invalid(file_id, "`diagnostics.demote` must be an array of strings")
+                })?;
+                for code in codes {
+                    let code = code.as_str().ok_or_else(|| {
+                        Self::invalid(file_id, "`diagnostics.demote` must be an array of strings")
+                    })?;
+                    demote.push(code.to_string());
+                }
+            }
+        }
+
+        let naming = match table.get("naming") {
+            Some(naming) => {
+                NamingConventions::from_toml(naming).map_err(|e| Self::invalid(file_id, e))?
+            }
+            None => NamingConventions::default(),
+        };
+
+        Ok(ProjectSettings {
+            file_id: Some(file_id.clone()),
+            demote,
+            naming,
+        })
+    }
+
+    /// Returns the demotions the settings ask for, plus a diagnostic for
+    /// each code that cannot be demoted.
+    pub fn demotions(&self) -> (Demotions, Vec<Diagnostic>) {
+        let file_id = self.file_id.clone().unwrap_or_default();
+        demotions_from(self.demote.iter().map(String::as_str), &file_id)
+    }
+
+    /// Build a `ProjectSettingsInvalid` (P6015) diagnostic located on the
+    /// settings file.
+    fn invalid(file_id: &FileId, message: impl Into<String>) -> Diagnostic {
+        Diagnostic::problem(
+            Problem::ProjectSettingsInvalid,
+            Label::file(file_id.clone(), message),
+        )
+    }
+}
+
+/// Builds the demotions for `codes`, with a `ProblemNotDemotable` (P6016)
+/// diagnostic located on `file_id` for each code that is refused. Codes that
+/// do not come from a file, such as those given with `--demote`, pass
+/// `FileId::default()`, which the diagnostic shows without a location.
+pub fn demotions_from<'a>(
+    codes: impl IntoIterator<Item = &'a str>,
+    file_id: &FileId,
+) -> (Demotions, Vec<Diagnostic>) {
+    let mut demotions = Demotions::default();
+    let mut diagnostics = vec![];
+    for code in codes {
+        if let Err(reason) = demotions.demote(code) {
+            diagnostics.push(Diagnostic::problem(
+                Problem::ProblemNotDemotable,
+                Label::file(file_id.clone(), reason),
+            ));
+        }
+    }
+    (demotions, diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_id() -> FileId {
+        FileId::from_string(SETTINGS_FILE)
+    }
+
+    #[test]
+    fn from_toml_when_demote_listed_then_codes() {
+        let content = r#"
+[diagnostics]
+demote = ["P4041", "P4028"]
+"#;
+        let settings = ProjectSettings::from_toml(content, &file_id()).unwrap();
+        assert_eq!(settings.demote, vec!["P4041", "P4028"]);
+    }
+
+    #[test]
+    fn from_toml_when_empty_then_no_demotions() {
+        let settings = ProjectSettings::from_toml("", &file_id()).unwrap();
+        assert!(settings.demote.is_empty());
+    }
+
+    #[test]
+    fn from_toml_when_demote_not_array_then_error() {
+        let content = r#"
+[diagnostics]
+demote = "P4041"
+"#;
+        let err = ProjectSettings::from_toml(content, &file_id()).unwrap_err();
+        assert_eq!(err.code, Problem::ProjectSettingsInvalid.code());
+    }
+
+    #[test]
+    fn from_toml_when_naming_rules_then_conventions() {
+        let content = r#"
+[naming]
+enumeration = { prefix = "E_" }
+"#;
+        let settings = ProjectSettings::from_toml(content, &file_id()).unwrap();
+        assert!(settings
+            .naming
+            .rule(crate::naming::NamedKind::Enumeration)
+            .is_some());
+    }
+
+    #[test]
+    fn from_toml_when_naming_rule_unknown_then_error() {
+        let content = r#"
+[naming]
+pou = { prefix = "P_" }
+"#;
+        let err = ProjectSettings::from_toml(content, &file_id()).unwrap_err();
+        assert_eq!(err.code, Problem::ProjectSettingsInvalid.code());
+    }
+
+    #[test]
+    fn from_toml_when_invalid_toml_then_error() {
+        let err = ProjectSettings::from_toml("[diagnostics", &file_id()).unwrap_err();
+        assert_eq!(err.code, Problem::ProjectSettingsInvalid.code());
+    }
+
+    #[test]
+    fn demotions_when_safety_relevant_code_then_diagnostic_on_settings_file() {
+        let settings = ProjectSettings {
+            file_id: Some(file_id()),
+            demote: vec!["P4041".to_string(), "P4007".to_string()],
+            naming: NamingConventions::default(),
+        };
+        let (demotions, diagnostics) = settings.demotions();
+        assert_eq!(demotions.codes().collect::<Vec<_>>(), vec!["P4041"]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Problem::ProblemNotDemotable.code());
+        assert_eq!(diagnostics[0].primary.file_id, file_id());
+    }
+
+    #[test]
+    fn load_when_no_settings_file_then_default() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            ProjectSettings::load(dir.path()).unwrap(),
+            ProjectSettings::default()
+        );
+    }
+
+    #[test]
+    fn load_when_settings_file_then_reads_it() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(SETTINGS_FILE),
+            "[diagnostics]\ndemote = [\"P4041\"]\n",
+        )
+        .unwrap();
+        let settings = ProjectSettings::load(dir.path()).unwrap();
+        assert_eq!(settings.demote, vec!["P4041"]);
+    }
+}
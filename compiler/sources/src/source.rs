@@ -87,6 +87,13 @@ impl Source {
         self.file_type.clone()
     }
 
+    /// Changes the options the source parses with, dropping the library
+    /// parsed with the old ones.
+    pub fn set_compiler_options(&mut self, compiler_options: CompilerOptions) {
+        self.compiler_options = compiler_options;
+        self.library = None;
+    }
+
     /// Parse the source into a Library, caching the result
     pub fn library(&mut self) -> Result<&Library, Vec<Diagnostic>> {
         if self.library.is_none() {
@@ -105,6 +112,17 @@ impl Source {
         }
     }
 
+    /// The library from the last successful parse, without parsing again.
+    ///
+    /// `None` when the source has not been parsed since it last changed, or
+    /// when parsing failed.
+    pub fn parsed_library(&self) -> Option<&Library> {
+        match &self.library {
+            Some(Ok(library)) => Some(library),
+            _ => None,
+        }
+    }
+
     /// Parse the content using the appropriate parser
     fn parse_content(&self) -> Result<Library, Diagnostic> {
         parsers::parse_source(
@@ -348,6 +366,31 @@ mod tests {
         assert_eq!(errors.len(), 1);
     }
 
+    #[test]
+    fn parsed_library_when_not_parsed_then_none() {
+        let file_id = FileId::from_string("main.st");
+        let source = Source::new(
+            "PROGRAM Main END_PROGRAM".to_string(),
+            &file_id,
+            CompilerOptions::default(),
+        );
+
+        assert!(source.parsed_library().is_none());
+    }
+
+    #[test]
+    fn parsed_library_when_parsed_then_returns_cached_library() {
+        let file_id = FileId::from_string("main.st");
+        let mut source = Source::new(
+            "PROGRAM Main END_PROGRAM".to_string(),
+            &file_id,
+            CompilerOptions::default(),
+        );
+        assert!(source.library().is_ok());
+
+        assert_eq!(source.parsed_library().unwrap().elements.len(), 1);
+    }
+
     #[test]
     fn source_library_cached_error_is_returned_on_second_call() {
         let file_id = FileId::from_string("bad.st");
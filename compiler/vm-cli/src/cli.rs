@@ -6,24 +6,43 @@ use std::io::Write;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use ironplc_container::debug_format::{build_var_debug_map, format_variable_value, VarDebugInfo};
 use ironplc_container::Container;
-use ironplc_vm::{Vm, VmBuffers};
+use ironplc_vm::{OverflowPolicy, Vm, VmBuffers, VmRunning};
 use serde_json::json;
 
 use crate::error::{self, VmError};
+use crate::monitor::catalog::Catalog;
+use crate::monitor::Monitor;
 
 const BUILD_OPT_LEVEL: &str = env!("BUILD_OPT_LEVEL");
 
+/// How long the run loop waits for monitor requests at a time while paused.
+const MONITOR_POLL: Duration = Duration::from_millis(20);
+
 /// Loads a container file and executes it.
 ///
 /// When `scans` is `Some(n)`, runs exactly `n` scheduling rounds.
 /// When `scans` is `None`, runs continuously until Ctrl+C.
 /// When `dump_vars` is `Some(path)`, writes variable values after stopping.
 /// A path of "-" writes to stdout; any other path writes to a file.
-pub fn run(path: &Path, dump_vars: Option<&Path>, scans: Option<u64>) -> Result<(), VmError> {
+/// When `monitor` is `Some(address)`, serves the monitor protocol on that
+/// address while running; time spent paused by a monitor client does not
+/// advance the program's clock.
+/// Each of `forces` (`NAME=VALUE` or `NAME.BIT=VALUE`) is forced after the
+/// init functions run and before the first scan.
+/// `overflow` sets how integer arithmetic and narrowing handle a result that
+/// does not fit its type.
+pub fn run(
+    path: &Path,
+    dump_vars: Option<&Path>,
+    scans: Option<u64>,
+    monitor: Option<&str>,
+    forces: &[String],
+    overflow: OverflowPolicy,
+) -> Result<(), VmError> {
     let mut file = File::open(path).map_err(|e| {
         VmError::io(
             error::FILE_OPEN,
@@ -41,9 +60,44 @@ pub fn run(path: &Path, dump_vars: Option<&Path>, scans: Option<u64>) -> Result<
     let mut bufs = VmBuffers::from_container(&container);
 
     let mut running = Vm::new()
+        .overflow_policy(overflow)
         .load(&container, &mut bufs)
         .start()
-        .map_err(|ctx| VmError::from_trap(&ctx.trap, ctx.task_id, ctx.instance_id))?;
+        .map_err(|ctx| {
+            VmError::from_trap(
+                &ctx.trap,
+                ctx.task_id,
+                ctx.instance_id,
+                container.debug_section.as_ref(),
+            )
+        })?;
+
+    if !forces.is_empty() {
+        let catalog = Catalog::from_container(&container);
+        for argument in forces {
+            force(&catalog, &mut running, argument).map_err(|message| {
+                VmError::io(
+                    error::FORCE_ARGUMENT,
+                    format!("Unable to force '{argument}': {message}"),
+                )
+            })?;
+        }
+    }
+
+    let mut monitor = monitor
+        .map(|address| {
+            Monitor::bind(address, &container).map_err(|e| {
+                VmError::io(
+                    error::MONITOR_BIND,
+                    format!("Unable to listen for monitor clients on {address}: {e}"),
+                )
+            })
+        })
+        .transpose()?;
+    if let Some(monitor) = &monitor {
+        // Tools that pass port 0 read the chosen port from this line.
+        eprintln!("monitor listening on {}", monitor.address());
+    }
 
     // Install signal handler for clean shutdown
     let stop_flag = Arc::new(AtomicBool::new(false));
@@ -55,7 +109,7 @@ pub fn run(path: &Path, dump_vars: Option<&Path>, scans: Option<u64>) -> Result<
         )
     })?;
 
-    let start = Instant::now();
+    let mut clock = RunClock::new();
     let mut rounds = 0u64;
     loop {
         if stop_flag.load(Ordering::Relaxed) {
@@ -70,10 +124,25 @@ pub fn run(path: &Path, dump_vars: Option<&Path>, scans: Option<u64>) -> Result<
             }
         }
 
-        let current_us = start.elapsed().as_micros() as u64;
+        if let Some(monitor) = monitor.as_mut() {
+            monitor.service(&mut running);
+            if monitor.paused() {
+                let paused_at = Instant::now();
+                monitor.wait(&mut running, MONITOR_POLL);
+                clock.exclude(paused_at.elapsed());
+                continue;
+            }
+        }
+
+        let current_us = clock.now_us();
         if let Err(ctx) = running.run_round(current_us) {
             let faulted = running.fault(ctx);
-            let err = VmError::from_trap(faulted.trap(), faulted.task_id(), faulted.instance_id());
+            let err = VmError::from_trap(
+                faulted.trap(),
+                faulted.task_id(),
+                faulted.instance_id(),
+                container.debug_section.as_ref(),
+            );
             if let Some(dump_path) = dump_vars {
                 dump_variables_faulted(&faulted, &container, dump_path)?;
             }
@@ -81,13 +150,21 @@ pub fn run(path: &Path, dump_vars: Option<&Path>, scans: Option<u64>) -> Result<
         }
         rounds += 1;
 
+        if let Some(monitor) = monitor.as_mut() {
+            monitor.publish(&running);
+        }
+
         // Sleep until the next cyclic task is due to avoid burning CPU.
         // Freewheeling-only programs (next_due_us returns None) run every round.
         if let Some(due_us) = running.next_due_us() {
-            let now_us = start.elapsed().as_micros() as u64;
-            let sleep_us = due_us.saturating_sub(now_us);
-            if sleep_us > 0 {
-                std::thread::sleep(std::time::Duration::from_micros(sleep_us));
+            match monitor.as_mut() {
+                Some(monitor) => wait_for_monitor(monitor, &mut running, &clock, due_us),
+                None => {
+                    let sleep_us = due_us.saturating_sub(clock.now_us());
+                    if sleep_us > 0 {
+                        std::thread::sleep(Duration::from_micros(sleep_us));
+                    }
+                }
             }
         }
     }
@@ -101,6 +178,65 @@ pub fn run(path: &Path, dump_vars: Option<&Path>, scans: Option<u64>) -> Result<
     Ok(())
 }
 
+/// The program's clock for `run`: time since start, less any time a monitor
+/// client held the program paused, so a paused program resumes where it
+/// stopped rather than seeing every cyclic task overrun at once.
+struct RunClock {
+    start: Instant,
+    excluded: Duration,
+}
+
+impl RunClock {
+    fn new() -> RunClock {
+        RunClock {
+            start: Instant::now(),
+            excluded: Duration::ZERO,
+        }
+    }
+
+    fn exclude(&mut self, paused: Duration) {
+        self.excluded += paused;
+    }
+
+    fn now_us(&self) -> u64 {
+        self.start
+            .elapsed()
+            .saturating_sub(self.excluded)
+            .as_micros() as u64
+    }
+}
+
+/// Applies one `--force` argument: `NAME=VALUE` forces the variable,
+/// `NAME.BIT=VALUE` one bit of it. Names and values are those the monitor's
+/// `force` takes.
+fn force(catalog: &Catalog, running: &mut VmRunning, argument: &str) -> Result<(), String> {
+    let (target, value) = argument
+        .split_once('=')
+        .ok_or_else(|| "expected NAME=VALUE".to_string())?;
+    let (name, bit) = match target.split_once('.') {
+        Some((name, bit)) => {
+            let bit = bit
+                .parse::<u8>()
+                .map_err(|_| format!("'{bit}' is not a bit number"))?;
+            (name, Some(bit))
+        }
+        None => (target, None),
+    };
+    catalog.lookup(name.trim())?.force(running, value, bit)
+}
+
+/// Sleeps until `due_us` on `clock`, answering monitor requests as they
+/// arrive. Returns early if a client pauses the program.
+fn wait_for_monitor(monitor: &mut Monitor, running: &mut VmRunning, clock: &RunClock, due_us: u64) {
+    loop {
+        let sleep_us = due_us.saturating_sub(clock.now_us());
+        if sleep_us == 0 || monitor.paused() {
+            return;
+        }
+        monitor.wait(running, Duration::from_micros(sleep_us));
+    }
+}
+
 /// Benchmarks a bytecode container by running it for `cycles` scan rounds,
 /// preceded by `warmup` unmeasured rounds, then prints JSON timing statistics.
 pub fn benchmark(path: &Path, cycles: u64, warmup: u64) -> Result<(), VmError> {
@@ -123,7 +259,14 @@ pub fn benchmark(path: &Path, cycles: u64, warmup: u64) -> Result<(), VmError> {
     let mut running = Vm::new()
         .load(&container, &mut bufs)
         .start()
-        .map_err(|ctx| VmError::from_trap(&ctx.trap, ctx.task_id, ctx.instance_id))?;
+        .map_err(|ctx| {
+            VmError::from_trap(
+                &ctx.trap,
+                ctx.task_id,
+                ctx.instance_id,
+                container.debug_section.as_ref(),
+            )
+        })?;
 
     let clock = Instant::now();
 
@@ -136,6 +279,7 @@ pub fn benchmark(path: &Path, cycles: u64, warmup: u64) -> Result<(), VmError> {
                 faulted.trap(),
                 faulted.task_id(),
                 faulted.instance_id(),
+                container.debug_section.as_ref(),
             ));
         }
     }
@@ -151,6 +295,7 @@ pub fn benchmark(path: &Path, cycles: u64, warmup: u64) -> Result<(), VmError> {
                 faulted.trap(),
                 faulted.task_id(),
                 faulted.instance_id(),
+                container.debug_section.as_ref(),
             ));
         }
         let elapsed = round_start.elapsed().as_nanos() as f64 / 1000.0;
@@ -327,6 +472,15 @@ mod tests {
         assert_eq!(percentile(&empty, 50.0), 0.0);
     }
 
+    /// REQ-VC-vm-cli-021: time a monitor client holds the program paused does
+    /// not advance the program's clock.
+    #[spec_test(REQ_VC_vm_cli_021)]
+    fn run_clock_when_pause_excluded_then_clock_does_not_count_it() {
+        let mut clock = RunClock::new();
+        clock.exclude(Duration::from_secs(3600));
+        assert_eq!(clock.now_us(), 0);
+    }
+
     #[test]
     fn percentile_when_single_value_then_returns_that_value() {
         let sorted = vec![42.0];
@@ -0,0 +1,557 @@
+//! `attach`: debugging a program that `ironplcvm run --monitor` is already
+//! running.
+//!
+//! A launched session owns the VM; an attached one does not. The program runs
+//! in another process under the real scheduler, and this session is a client
+//! of that process's monitor (see `specs/design/vm-monitor.md`). So the session
+//! can pause the program between scans, show its program and global variables,
+//! and let it run on — but it has no frames to step through and no breakpoints
+//! to bind, because the monitor only ever stops the program at a scan
+//! boundary. Those requests are answered as such rather than failing the
+//! session. Forcing goes to the monitor too, so a force set here holds in the
+//! running program after the session ends, as it would on a PLC.
+//!
+//! The monitor protocol is a handful of JSON lines, so [`MonitorClient`]
+//! speaks it with [`serde_json::Value`]s rather than sharing the `ironplcvm`
+//! binary's message types, which this binary does not compile.
+
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+use ironplc_container::debug_section::var_section;
+use ironplc_container::{SourceColumn, SourceLine};
+use serde_json::{json, Value};
+
+use super::debug_info::ScopeKind;
+use super::inbox::Inbox;
+use super::problem_codes;
+use super::server::{
+    decode_frame_scope_ref, frame_scope_ref, invalidated_event, send, stopped_event, take_seq,
+    REQUEST_NOT_APPLICABLE, RUNTIME_REF, THREAD_ID,
+};
+use super::state::{self, Command, Phase};
+use super::types::{
+    AttachRequestArguments, Breakpoint, ContinueResponseBody, Event, Request, Response, Scope,
+    ScopesResponseBody, SetBreakpointsArguments, SetBreakpointsResponseBody, StackFrame,
+    StackTraceResponseBody, Thread, ThreadsResponseBody, Variable, VariablesArguments,
+    VariablesResponseBody,
+};
+
+/// The name of the one frame an attached session shows: the program is always
+/// stopped between scans.
+const BOUNDARY_FRAME: &str = "Scan boundary";
+
+/// Why a monitor request failed.
+#[derive(Debug)]
+pub enum MonitorError {
+    /// The connection failed; the session cannot continue.
+    Io(io::Error),
+    /// The monitor answered with an error.
+    Refused(String),
+}
+
+impl fmt::Display for MonitorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MonitorError::Io(err) => write!(f, "lost the monitor connection: {err}"),
+            MonitorError::Refused(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+/// A connection to a monitor.
+pub struct MonitorClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    next_id: i64,
+}
+
+impl MonitorClient {
+    /// Connects to the monitor listening on `address`.
+    pub fn connect(address: &str) -> io::Result<MonitorClient> {
+        let writer = TcpStream::connect(address)?;
+        let reader = BufReader::new(writer.try_clone()?);
+        Ok(MonitorClient {
+            reader,
+            writer,
+            next_id: 1,
+        })
+    }
+
+    /// Sends `command` and waits for its response, returning the body (`null`
+    /// when there is none). Events that arrive first are for subscriptions
+    /// this client never makes, and are skipped.
+    pub fn request(&mut self, command: &str, arguments: Value) -> Result<Value, MonitorError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut line = json!({"id": id, "command": command, "arguments": arguments}).to_string();
+        line.push('\n');
+        self.writer
+            .write_all(line.as_bytes())
+            .map_err(MonitorError::Io)?;
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line).map_err(MonitorError::Io)? == 0 {
+                return Err(MonitorError::Io(io::ErrorKind::UnexpectedEof.into()));
+            }
+            let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if message["id"] != id {
+                continue;
+            }
+            return match message["success"].as_bool() {
+                Some(true) => Ok(message["body"].clone()),
+                _ => Err(MonitorError::Refused(
+                    message["message"]
+                        .as_str()
+                        .unwrap_or("the monitor refused the request")
+                        .to_string(),
+                )),
+            };
+        }
+    }
+}
+
+/// Parses the `attach` arguments and connects to the monitor they name,
+/// rendering any failure as the DAP error message.
+pub fn connect(arguments: Option<&Value>) -> Result<MonitorClient, String> {
+    let args: AttachRequestArguments = arguments
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or_else(|| {
+            format!(
+                "{} - attach requires a 'monitor' address such as 127.0.0.1:7878",
+                problem_codes::ATTACH_CONNECT
+            )
+        })?;
+    MonitorClient::connect(&args.monitor).map_err(|e| {
+        format!(
+            "{} - unable to connect to the monitor at {}: {e}",
+            problem_codes::ATTACH_CONNECT,
+            args.monitor
+        )
+    })
+}
+
+/// Answers the `attach` request and serves the session until the client
+/// disconnects.
+///
+/// Unlike a launched session, this loop never runs anything itself, so it
+/// takes every request as it arrives. A `pause` pauses the program through the
+/// monitor and stops at the scan boundary; `continue` and `disconnect` resume
+/// it. Disconnecting leaves the program running.
+pub fn attached_session<W: Write>(
+    inbox: &Inbox,
+    writer: &mut W,
+    seq: &mut i64,
+    mut monitor: MonitorClient,
+    attach_request: Request,
+) -> io::Result<()> {
+    send(
+        writer,
+        &Response::success(take_seq(seq), &attach_request, None),
+    )?;
+    let mut phase = Phase::Configuring;
+
+    loop {
+        let Some(request) = inbox.next()? else {
+            if phase == Phase::Paused {
+                let _ = monitor.request("resume", Value::Null);
+            }
+            return Ok(());
+        };
+
+        let command = Command::from_request(&request.command);
+        let legal_here = command.is_some_and(|c| state::legal(phase, c));
+
+        let result = match command {
+            Some(Command::Disconnect) => {
+                // Leave the program as it was found: running.
+                if phase == Phase::Paused {
+                    let _ = monitor.request("resume", Value::Null);
+                }
+                send(writer, &Response::success(take_seq(seq), &request, None))?;
+                return Ok(());
+            }
+            Some(Command::ConfigurationDone) if legal_here => {
+                phase = Phase::Running;
+                Ok(None)
+            }
+            Some(Command::SetBreakpoints) if legal_here => Ok(unbound_breakpoints(&request)),
+            // The program's one thread exists whether or not it is paused, and
+            // answering does not touch it, so the attached session answers in
+            // every phase rather than queueing the request for a stop.
+            Some(Command::Threads) => Ok(serde_json::to_value(ThreadsResponseBody {
+                threads: vec![Thread {
+                    id: THREAD_ID,
+                    name: "plc".to_string(),
+                }],
+            })
+            .ok()),
+            Some(Command::Pause) if legal_here => {
+                let paused = monitor.request("pause", Value::Null).map(|_| None);
+                if paused.is_ok() && phase == Phase::Running {
+                    send(writer, &Response::success(take_seq(seq), &request, None))?;
+                    send(writer, &stopped_event(take_seq(seq), "pause"))?;
+                    phase = Phase::Paused;
+                    continue;
+                }
+                paused
+            }
+            Some(Command::Continue) if legal_here => {
+                monitor.request("resume", Value::Null).map(|_| {
+                    phase = Phase::Running;
+                    serde_json::to_value(ContinueResponseBody {
+                        all_threads_continued: true,
+                    })
+                    .ok()
+                })
+            }
+            // The monitor takes a force whether or not the program is paused
+            // -- commissioning forces a machine that is running -- so these
+            // are forwarded in every phase that still has a monitor.
+            Some(command @ (Command::ForceVariable | Command::UnforceVariable))
+                if phase != Phase::Terminated =>
+            {
+                let monitor_command = match command {
+                    Command::ForceVariable => "force",
+                    _ => "unforce",
+                };
+                let arguments = request.arguments.clone().unwrap_or(Value::Null);
+                let forced = monitor.request(monitor_command, arguments).map(|_| None);
+                if forced.is_ok() {
+                    send(writer, &Response::success(take_seq(seq), &request, None))?;
+                    send(writer, &invalidated_event(take_seq(seq)))?;
+                    continue;
+                }
+                forced
+            }
+            Some(Command::StackTrace) if legal_here => Ok(boundary_stack_trace()),
+            Some(Command::Scopes) if legal_here => scopes_body(&mut monitor),
+            Some(Command::Variables) if legal_here => {
+                let reference = request
+                    .arguments
+                    .as_ref()
+                    .and_then(|v| serde_json::from_value::<VariablesArguments>(v.clone()).ok())
+                    .map_or(0, |a| a.variables_reference);
+                variables_body(&mut monitor, reference)
+            }
+            _ => {
+                // Illegal in this phase, unknown, or needing a VM this session
+                // does not own: stepping, scan history, restart.
+                send(
+                    writer,
+                    &Response::error(take_seq(seq), &request, REQUEST_NOT_APPLICABLE),
+                )?;
+                continue;
+            }
+        };
+
+        match result {
+            Ok(body) => send(writer, &Response::success(take_seq(seq), &request, body))?,
+            Err(err) => {
+                send(
+                    writer,
+                    &Response::error(take_seq(seq), &request, err.to_string()),
+                )?;
+                if let MonitorError::Io(_) = err {
+                    // The program has gone (or the link has): nothing left to
+                    // debug, but the client still disconnects in its own time.
+                    send(writer, &Event::new(take_seq(seq), "terminated", None))?;
+                    phase = Phase::Terminated;
+                }
+            }
+        }
+    }
+}
+
+/// Answers `setBreakpoints` with every breakpoint unverified: the monitor can
+/// only stop the program between scans.
+fn unbound_breakpoints(request: &Request) -> Option<Value> {
+    let args = request
+        .arguments
+        .as_ref()
+        .and_then(|v| serde_json::from_value::<SetBreakpointsArguments>(v.clone()).ok())?;
+    let breakpoints = args
+        .breakpoints
+        .iter()
+        .map(|bp| Breakpoint {
+            verified: false,
+            line: bp.line,
+            source: Some(args.source.clone()),
+            message: Some("breakpoints need a launched debug session".to_string()),
+        })
+        .collect();
+    serde_json::to_value(SetBreakpointsResponseBody { breakpoints }).ok()
+}
+
+/// The stack of a program paused by the monitor: one frame, with no source,
+/// standing for the boundary between two scans.
+fn boundary_stack_trace() -> Option<Value> {
+    serde_json::to_value(StackTraceResponseBody {
+        stack_frames: vec![StackFrame {
+            id: 0,
+            name: BOUNDARY_FRAME.to_string(),
+            line: SourceLine::default(),
+            column: SourceColumn::default(),
+            source: None,
+        }],
+        total_frames: Some(1),
+    })
+    .ok()
+}
+
+/// The monitor's variables as `(name, type, scope)`, in declaration order.
+fn listed(monitor: &mut MonitorClient) -> Result<Vec<(String, String, ScopeKind)>, MonitorError> {
+    let body = monitor.request("list", Value::Null)?;
+    let variables = body["variables"].as_array().cloned().unwrap_or_default();
+    Ok(variables
+        .iter()
+        .filter_map(|v| {
+            let kind = var_section::from_keyword(v["section"].as_str()?)
+                .and_then(ScopeKind::of_section)?;
+            Some((
+                v["name"].as_str()?.to_string(),
+                v["type"].as_str()?.to_string(),
+                kind,
+            ))
+        })
+        .collect())
+}
+
+/// The scopes of the boundary frame: one per section the program declares
+/// variables in, then `Runtime`.
+fn scopes_body(monitor: &mut MonitorClient) -> Result<Option<Value>, MonitorError> {
+    let listed = listed(monitor)?;
+    let mut scopes: Vec<Scope> = ScopeKind::ALL
+        .iter()
+        .filter(|&&kind| listed.iter().any(|(_, _, k)| *k == kind))
+        .map(|&kind| Scope {
+            name: kind.name().to_string(),
+            variables_reference: frame_scope_ref(0, kind),
+            expensive: false,
+        })
+        .collect();
+    scopes.push(Scope {
+        name: "Runtime".to_string(),
+        variables_reference: RUNTIME_REF,
+        expensive: false,
+    });
+    Ok(serde_json::to_value(ScopesResponseBody { scopes }).ok())
+}
+
+/// The contents of one scope of the boundary frame, read through the monitor.
+fn variables_body(
+    monitor: &mut MonitorClient,
+    reference: i64,
+) -> Result<Option<Value>, MonitorError> {
+    let variables = match (reference, decode_frame_scope_ref(reference)) {
+        (RUNTIME_REF, _) => {
+            let status = monitor.request("status", Value::Null)?;
+            vec![Variable {
+                name: "scanCount".to_string(),
+                value: status["scanCount"].to_string(),
+                type_name: Some("ULINT".to_string()),
+                variables_reference: 0,
+                menu_context: None,
+            }]
+        }
+        (_, Some((0, kind))) => {
+            let (names, types): (Vec<String>, Vec<String>) = listed(monitor)?
+                .into_iter()
+                .filter(|(_, _, k)| *k == kind)
+                .map(|(name, type_name, _)| (name, type_name))
+                .unzip();
+            let body = monitor.request("read", json!({ "names": names }))?;
+            let values = body["variables"].as_array().cloned().unwrap_or_default();
+            values
+                .iter()
+                .zip(types)
+                .map(|(v, type_name)| {
+                    let variable = Variable {
+                        name: v["name"].as_str().unwrap_or_default().to_string(),
+                        value: v["value"].as_str().unwrap_or_default().to_string(),
+                        type_name: Some(type_name),
+                        variables_reference: 0,
+                        menu_context: None,
+                    };
+                    match v["forced"].as_bool() {
+                        Some(true) => variable.forced(),
+                        _ => variable,
+                    }
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+    Ok(serde_json::to_value(VariablesResponseBody { variables }).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dap::framing;
+    use crate::dap::server::serve;
+    use std::io::Cursor;
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// A stand-in monitor on a free port, answering one client with a fixed
+    /// program: `Count` (VAR) is 7 and `Limit` (VAR_GLOBAL) is 100, after 42
+    /// scans. Returns its address and a handle yielding the commands it saw.
+    fn fake_monitor() -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut seen = Vec::new();
+            for line in BufReader::new(stream).lines() {
+                let request: Value = serde_json::from_str(&line.unwrap()).unwrap();
+                let command = request["command"].as_str().unwrap().to_string();
+                let body = match command.as_str() {
+                    "list" => json!({"variables": [
+                        {"name": "Count", "type": "DINT", "section": "VAR", "forced": false},
+                        {"name": "Limit", "type": "DINT", "section": "VAR_GLOBAL", "forced": false},
+                    ]}),
+                    "read" => {
+                        json!({"variables": [{"name": "Limit", "value": "100", "forced": false}]})
+                    }
+                    "status" => {
+                        json!({"protocol": 1, "scanCount": 42, "paused": true, "forced": []})
+                    }
+                    _ => Value::Null,
+                };
+                let response = json!({"id": request["id"], "success": true, "body": body});
+                writeln!(writer, "{response}").unwrap();
+                seen.push(command);
+            }
+            seen
+        });
+        (address, handle)
+    }
+
+    fn run_server(requests: &[Value]) -> Vec<Value> {
+        let mut input = Vec::new();
+        for request in requests {
+            framing::write_message(&mut input, &serde_json::to_vec(request).unwrap()).unwrap();
+        }
+        let mut writer: Vec<u8> = Vec::new();
+        serve(Cursor::new(input), &mut writer).unwrap();
+        let mut out = Cursor::new(writer);
+        let mut messages = Vec::new();
+        while let Some(body) = framing::read_message(&mut out).unwrap() {
+            messages.push(serde_json::from_slice(&body).unwrap());
+        }
+        messages
+    }
+
+    fn request(seq: i64, command: &str, arguments: Value) -> Value {
+        json!({"seq": seq, "type": "request", "command": command, "arguments": arguments})
+    }
+
+    #[test]
+    fn serve_when_attach_pause_inspect_disconnect_then_reads_through_monitor() {
+        let (address, monitor) = fake_monitor();
+        let out = run_server(&[
+            request(1, "initialize", Value::Null),
+            request(2, "attach", json!({"monitor": address})),
+            request(3, "configurationDone", Value::Null),
+            request(4, "pause", Value::Null),
+            request(5, "scopes", json!({"frameId": 0})),
+            request(
+                6,
+                "variables",
+                json!({"variablesReference": frame_scope_ref(0, ScopeKind::Globals)}),
+            ),
+            request(7, "variables", json!({"variablesReference": RUNTIME_REF})),
+            request(8, "disconnect", Value::Null),
+        ]);
+
+        let scopes: Vec<&Value> = out[6]["body"]["scopes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| &s["name"])
+            .collect();
+        assert_eq!(out[2]["command"], "attach");
+        assert_eq!(out[2]["success"], true);
+        assert_eq!(out[5]["event"], "stopped");
+        assert_eq!(
+            scopes,
+            [&json!("Locals"), &json!("Globals"), &json!("Runtime")]
+        );
+        assert_eq!(
+            out[7]["body"]["variables"][0],
+            json!({"name": "Limit", "value": "100", "type": "DINT", "variablesReference": 0})
+        );
+        assert_eq!(out[8]["body"]["variables"][0]["value"], "42");
+        assert_eq!(out[9]["command"], "disconnect");
+        assert_eq!(
+            monitor.join().unwrap(),
+            ["pause", "list", "list", "read", "status", "resume"]
+        );
+    }
+
+    #[test]
+    fn serve_when_attached_force_while_running_then_forwarded_and_variables_invalidated() {
+        let (address, monitor) = fake_monitor();
+        let out = run_server(&[
+            request(1, "initialize", Value::Null),
+            request(2, "attach", json!({"monitor": address})),
+            request(3, "configurationDone", Value::Null),
+            request(
+                4,
+                "ironplc/forceVariable",
+                json!({"name": "Limit", "value": "5"}),
+            ),
+            request(5, "ironplc/unforceVariable", json!({"name": "Limit"})),
+            request(6, "disconnect", Value::Null),
+        ]);
+
+        assert_eq!(out[4]["command"], "ironplc/forceVariable");
+        assert_eq!(out[4]["success"], true);
+        assert_eq!(out[5]["event"], "invalidated");
+        assert_eq!(out[6]["command"], "ironplc/unforceVariable");
+        assert_eq!(out[6]["success"], true);
+        assert_eq!(monitor.join().unwrap(), ["force", "unforce"]);
+    }
+
+    #[test]
+    fn serve_when_attached_step_or_breakpoint_then_not_applicable_or_unverified() {
+        let (address, _monitor) = fake_monitor();
+        let out = run_server(&[
+            request(1, "initialize", Value::Null),
+            request(2, "attach", json!({"monitor": address})),
+            request(
+                3,
+                "setBreakpoints",
+                json!({"source": {"path": "demo.st"}, "breakpoints": [{"line": 10}]}),
+            ),
+            request(4, "configurationDone", Value::Null),
+            request(5, "pause", Value::Null),
+            request(7, "next", Value::Null),
+            request(8, "disconnect", Value::Null),
+        ]);
+
+        assert_eq!(out[3]["body"]["breakpoints"][0]["verified"], false);
+        assert_eq!(out[7]["command"], "next");
+        assert_eq!(out[7]["message"], "requestNotApplicable");
+    }
+
+    #[test]
+    fn serve_when_attach_to_closed_port_then_connect_error() {
+        let address = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().to_string()
+        };
+        let out = run_server(&[
+            request(1, "initialize", Value::Null),
+            request(2, "attach", json!({"monitor": address})),
+        ]);
+
+        assert_eq!(out[2]["success"], false);
+        assert!(out[2]["message"].as_str().unwrap().starts_with("V6012"));
+    }
+}
@@ -3,14 +3,20 @@
 //! Everything that maps between the debugger's `(FunctionId, bytecode_offset)`
 //! space and *source* coordinates — source line → offset for breakpoints,
 //! frame → name/source location for stack traces, and variable slot →
-//! name/type/value for inspection — lives here and nowhere else. The rest of
+//! name/type/value (grouped into per-frame scopes) for inspection — lives
+//! here and nowhere else. The rest of
 //! the server speaks only in resolved values, so the debug section (line map,
 //! VAR_NAME, FUNC_NAME, STRING layouts, source file table, `debug_format`) is
 //! a dependency of exactly one module.
 
-use ironplc_container::debug_format::{format_variable_value, read_string_value};
-use ironplc_container::debug_section::{iec_type_tag, DebugSection, SourceFileEntry};
-use ironplc_container::{FunctionId, SourceColumn, SourceFileId, SourceLine};
+use ironplc_container::debug_format::{
+    format_variable_value, parse_variable_value, read_string_value,
+};
+use ironplc_container::debug_section::{
+    iec_type_tag, var_section, DebugSection, SourceFileEntry, VarNameEntry,
+};
+use ironplc_container::{FunctionId, SourceColumn, SourceFileId, SourceLine, VarIndex};
+use ironplc_vm::ForceTable;
 
 use super::types::Variable;
 
@@ -203,11 +209,13 @@ pub fn resolve_frame(
 /// backs STRING reads. A slot with a VAR_NAME entry renders with its source
 /// name, declared type, and a value formatted per its IEC type tag; a slot
 /// without one (or a container without VAR_NAME) keeps the `var[i]` /
-/// signed-decimal fallback so the pane never goes blank.
+/// signed-decimal fallback so the pane never goes blank. A slot `forces`
+/// holds is flagged as forced.
 pub fn render_variables(
     debug: Option<&DebugSection>,
     values: &[u64],
     data_region: &[u8],
+    forces: &ForceTable,
 ) -> Vec<Variable> {
     let entries: std::collections::HashMap<usize, &_> = debug
         .map(|d| {
@@ -222,24 +230,265 @@ pub fn render_variables(
         .enumerate()
         .map(|(i, &raw)| {
             let entry = entries.get(&i);
-            match entry {
+            let variable = match entry {
                 Some(entry) => Variable {
                     name: entry.name.clone(),
                     value: variable_value(debug, entry.iec_type_tag, i, raw, data_region),
                     type_name: Some(entry.type_name.clone()),
                     variables_reference: 0,
+                    menu_context: None,
                 },
                 None => Variable {
                     name: format!("var[{i}]"),
                     value: (raw as i32).to_string(),
                     type_name: None,
                     variables_reference: 0,
+                    menu_context: None,
                 },
-            }
+            };
+            flag_forced(variable, i, forces)
         })
         .collect()
 }
 
+/// A variables scope of one stack frame, grouping VAR_NAME entries by their
+/// IEC variable section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScopeKind {
+    /// `VAR_INPUT`.
+    Inputs,
+    /// `VAR_OUTPUT`, plus a function's return value.
+    Outputs,
+    /// `VAR_IN_OUT`.
+    InOut,
+    /// `VAR` and `VAR_TEMP`.
+    Locals,
+    /// `VAR_GLOBAL`: the configuration's globals, visible from every frame.
+    Globals,
+    /// `VAR_EXTERNAL`.
+    Externals,
+}
+
+impl ScopeKind {
+    /// Every kind, in the order a `scopes` response lists them (which is
+    /// also declaration order, so `kind as usize` indexes this array).
+    pub const ALL: [ScopeKind; 6] = [
+        ScopeKind::Inputs,
+        ScopeKind::Outputs,
+        ScopeKind::InOut,
+        ScopeKind::Locals,
+        ScopeKind::Globals,
+        ScopeKind::Externals,
+    ];
+
+    /// The scope name shown in the client's variables pane.
+    pub fn name(self) -> &'static str {
+        match self {
+            ScopeKind::Inputs => "Inputs",
+            ScopeKind::Outputs => "Outputs",
+            ScopeKind::InOut => "In/Out",
+            ScopeKind::Locals => "Locals",
+            ScopeKind::Globals => "Globals",
+            ScopeKind::Externals => "Externals",
+        }
+    }
+
+    /// The kind a `var_section` value belongs to; `None` for a value this
+    /// server does not know.
+    pub fn of_section(section: u8) -> Option<ScopeKind> {
+        match section {
+            var_section::VAR_INPUT => Some(ScopeKind::Inputs),
+            var_section::VAR_OUTPUT => Some(ScopeKind::Outputs),
+            var_section::VAR_IN_OUT => Some(ScopeKind::InOut),
+            var_section::VAR | var_section::VAR_TEMP => Some(ScopeKind::Locals),
+            var_section::VAR_GLOBAL => Some(ScopeKind::Globals),
+            var_section::VAR_EXTERNAL => Some(ScopeKind::Externals),
+            _ => None,
+        }
+    }
+}
+
+/// The VAR_NAME entries shown in the `kind` scope of a frame whose variables
+/// are tagged with `owner`, in declaration order.
+///
+/// `owner` is the frame's function id for a function or function block frame
+/// and [`FunctionId::GLOBAL_SCOPE`] for the program's own frame, since program
+/// variables are tagged with the global scope. Globals are tagged the same
+/// way but belong to no frame, so the `Globals` scope lists them whatever the
+/// owner.
+fn scope_entries(
+    debug: Option<&DebugSection>,
+    owner: FunctionId,
+    kind: ScopeKind,
+) -> Vec<&VarNameEntry> {
+    let owner = match kind {
+        ScopeKind::Globals => FunctionId::GLOBAL_SCOPE,
+        _ => owner,
+    };
+    debug
+        .map(|d| {
+            d.var_names
+                .iter()
+                .filter(|e| {
+                    e.function_id == owner && ScopeKind::of_section(e.var_section) == Some(kind)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether the program's frame falls back to one `Locals` scope of every slot:
+/// a container without VAR_NAME has no sections to split by.
+fn has_no_var_names(debug: Option<&DebugSection>, owner: FunctionId) -> bool {
+    owner == FunctionId::GLOBAL_SCOPE && debug.is_none_or(|d| d.var_names.is_empty())
+}
+
+/// The scopes of a frame whose variables are tagged with `owner` (see
+/// [`scope_entries`]), in [`ScopeKind::ALL`] order. Empty scopes are left out
+/// so a frame shows only the sections it declares.
+pub fn frame_scopes(debug: Option<&DebugSection>, owner: FunctionId) -> Vec<ScopeKind> {
+    if has_no_var_names(debug, owner) {
+        return vec![ScopeKind::Locals];
+    }
+    ScopeKind::ALL
+        .into_iter()
+        .filter(|&kind| !scope_entries(debug, owner, kind).is_empty())
+        .collect()
+}
+
+/// Render one frame scope for a `variables` response.
+///
+/// `values[i]` is the raw 64-bit slot for variable index `i`; `data_region`
+/// backs STRING reads. Function and function block locals are read from
+/// their absolute slots, which hold the paused call's values. A container
+/// without VAR_NAME renders every slot in the program's `Locals` scope with
+/// the [`render_variables`] fallback. A slot `forces` holds is flagged as
+/// forced.
+pub fn render_scope(
+    debug: Option<&DebugSection>,
+    owner: FunctionId,
+    kind: ScopeKind,
+    values: &[u64],
+    data_region: &[u8],
+    forces: &ForceTable,
+) -> Vec<Variable> {
+    if has_no_var_names(debug, owner) {
+        return match kind {
+            ScopeKind::Locals => render_variables(debug, values, data_region, forces),
+            _ => vec![],
+        };
+    }
+    scope_entries(debug, owner, kind)
+        .into_iter()
+        .filter_map(|entry| {
+            let i = entry.var_index.raw() as usize;
+            let raw = *values.get(i)?;
+            let variable = Variable {
+                name: entry.name.clone(),
+                value: variable_value(debug, entry.iec_type_tag, i, raw, data_region),
+                type_name: Some(entry.type_name.clone()),
+                variables_reference: 0,
+                menu_context: None,
+            };
+            Some(flag_forced(variable, i, forces))
+        })
+        .collect()
+}
+
+/// `variable`, flagged as forced when `forces` holds any bit of slot `index`.
+fn flag_forced(variable: Variable, index: usize, forces: &ForceTable) -> Variable {
+    let forced = u16::try_from(index).is_ok_and(|i| forces.get(VarIndex::new(i)).is_some());
+    if forced {
+        variable.forced()
+    } else {
+        variable
+    }
+}
+
+/// An `ironplc/forceVariable` request resolved against VAR_NAME.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ResolvedForce {
+    /// Hold the whole slot at `raw`.
+    Whole { var_index: VarIndex, raw: u64 },
+    /// Hold one bit of a bit string's slot.
+    Bit {
+        var_index: VarIndex,
+        bit: u8,
+        on: bool,
+    },
+}
+
+/// The program or global variable called `name`, matched without regard to
+/// case as IEC 61131-3 identifiers are. These are the variables whose values
+/// mean something between scans, which is where a force is applied.
+pub fn resolve_program_variable<'a>(
+    debug: Option<&'a DebugSection>,
+    name: &str,
+) -> Result<&'a VarNameEntry, String> {
+    debug
+        .and_then(|d| {
+            d.var_names.iter().find(|e| {
+                e.function_id == FunctionId::GLOBAL_SCOPE && e.name.eq_ignore_ascii_case(name)
+            })
+        })
+        .ok_or_else(|| format!("no program or global variable is named '{name}'"))
+}
+
+/// Resolve a force of `name` to `value`, or with `bit`, of that one bit of a
+/// `BYTE`, `WORD`, `DWORD` or `LWORD` to `TRUE` or `FALSE`. The messages match
+/// the ones `ironplcvm run --force` and the monitor give.
+pub fn resolve_force(
+    debug: Option<&DebugSection>,
+    name: &str,
+    value: &str,
+    bit: Option<u8>,
+) -> Result<ResolvedForce, String> {
+    let entry = resolve_program_variable(debug, name)?;
+    let Some(bit) = bit else {
+        let raw = parse_variable_value(value, entry.iec_type_tag).map_err(|err| {
+            format!(
+                "'{value}' cannot be set on {} ({}): {err}",
+                entry.name, entry.type_name
+            )
+        })?;
+        return Ok(ResolvedForce::Whole {
+            var_index: entry.var_index,
+            raw,
+        });
+    };
+    let bits = match entry.iec_type_tag {
+        iec_type_tag::BYTE => 8,
+        iec_type_tag::WORD => 16,
+        iec_type_tag::DWORD => 32,
+        iec_type_tag::LWORD => 64,
+        _ => {
+            return Err(format!(
+                "{} ({}) is not a bit string; only BYTE, WORD, DWORD and LWORD bits can be forced",
+                entry.name, entry.type_name
+            ))
+        }
+    };
+    if bit >= bits {
+        return Err(format!(
+            "{} ({}) has bits 0 to {}; there is no bit {bit}",
+            entry.name,
+            entry.type_name,
+            bits - 1
+        ));
+    }
+    let on = parse_variable_value(value, iec_type_tag::BOOL).map_err(|_| {
+        format!(
+            "'{value}' cannot be set on bit {bit} of {}: expected TRUE or FALSE",
+            entry.name
+        )
+    })?;
+    Ok(ResolvedForce::Bit {
+        var_index: entry.var_index,
+        bit,
+        on: on != 0,
+    })
+}
+
 /// Format one variable's value per its IEC type tag. STRING values live in
 /// the data region (the slot is unused); everything else renders from the
 /// raw slot via the shared `debug_format` helper.
@@ -449,7 +698,12 @@ mod tests {
             ],
             ..DebugSection::default()
         };
-        let vars = render_variables(Some(&debug), &[42, 1, 1.5f32.to_bits() as u64], &[]);
+        let vars = render_variables(
+            Some(&debug),
+            &[42, 1, 1.5f32.to_bits() as u64],
+            &[],
+            &ForceTable::new(),
+        );
         assert_eq!(vars[0].name, "counter");
         assert_eq!(vars[0].value, "42");
         assert_eq!(vars[0].type_name.as_deref(), Some("DINT"));
@@ -465,7 +719,7 @@ mod tests {
             var_names: vec![var_name(0, iec_type_tag::DINT, "counter", "DINT")],
             ..DebugSection::default()
         };
-        let vars = render_variables(Some(&debug), &[7, 0xFFFF_FFFF], &[]);
+        let vars = render_variables(Some(&debug), &[7, 0xFFFF_FFFF], &[], &ForceTable::new());
         assert_eq!(vars[0].name, "counter");
         // Slot 1 has no VAR_NAME entry: passthrough name and i32 rendering.
         assert_eq!(vars[1].name, "var[1]");
@@ -475,7 +729,7 @@ mod tests {
 
     #[test]
     fn render_variables_when_no_debug_then_all_indexed_fallback() {
-        let vars = render_variables(None, &[10], &[]);
+        let vars = render_variables(None, &[10], &[], &ForceTable::new());
         assert_eq!(vars[0].name, "var[0]");
         assert_eq!(vars[0].value, "10");
     }
@@ -495,7 +749,7 @@ mod tests {
         let mut data = vec![8, 0, 2, 0, 1, 0];
         data.extend_from_slice(b"hi");
         data.extend_from_slice(&[0; 6]);
-        let vars = render_variables(Some(&debug), &[0], &data);
+        let vars = render_variables(Some(&debug), &[0], &data, &ForceTable::new());
         assert_eq!(vars[0].value, "'hi'");
     }
 
@@ -510,7 +764,7 @@ mod tests {
             }],
             ..DebugSection::default()
         };
-        let vars = render_variables(Some(&debug), &[0], &[0, 0, 0, 0]);
+        let vars = render_variables(Some(&debug), &[0], &[0, 0, 0, 0], &ForceTable::new());
         assert_eq!(vars[0].value, VALUE_NOT_AVAILABLE);
     }
 
@@ -526,7 +780,12 @@ mod tests {
             ..DebugSection::default()
         };
         // cur_len (40) reads past the end of the region.
-        let vars = render_variables(Some(&debug), &[0], &[8, 0, 40, 0, 1, 0, b'h', b'i']);
+        let vars = render_variables(
+            Some(&debug),
+            &[0],
+            &[8, 0, 40, 0, 1, 0, b'h', b'i'],
+            &ForceTable::new(),
+        );
         assert_eq!(vars[0].value, VALUE_NOT_AVAILABLE);
     }
 
@@ -536,7 +795,7 @@ mod tests {
             var_names: vec![var_name(0, iec_type_tag::STRING, "msg", "STRING")],
             ..DebugSection::default()
         };
-        let vars = render_variables(Some(&debug), &[0], &[]);
+        let vars = render_variables(Some(&debug), &[0], &[], &ForceTable::new());
         assert_eq!(vars[0].value, VALUE_NOT_AVAILABLE);
     }
 
@@ -546,12 +805,195 @@ mod tests {
             var_names: vec![var_name(0, iec_type_tag::WSTRING, "wmsg", "WSTRING")],
             ..DebugSection::default()
         };
-        let vars = render_variables(Some(&debug), &[0], &[]);
+        let vars = render_variables(Some(&debug), &[0], &[], &ForceTable::new());
         assert_eq!(vars[0].value, VALUE_NOT_AVAILABLE);
     }
 
     #[test]
     fn render_variables_when_no_slots_then_empty() {
-        assert!(render_variables(None, &[], &[]).is_empty());
+        assert!(render_variables(None, &[], &[], &ForceTable::new()).is_empty());
+    }
+
+    fn owned_var(index: u16, function_id: FunctionId, section: u8, name: &str) -> VarNameEntry {
+        VarNameEntry {
+            function_id,
+            var_section: section,
+            ..var_name(index, iec_type_tag::DINT, name, "DINT")
+        }
+    }
+
+    /// Program `x` (VAR) and `out` (VAR_OUTPUT), the global `total`, and the
+    /// input `n` and temp `t` of function 2.
+    fn a_sectioned_debug_section() -> DebugSection {
+        DebugSection {
+            var_names: vec![
+                owned_var(0, FunctionId::GLOBAL_SCOPE, var_section::VAR, "x"),
+                owned_var(1, FunctionId::GLOBAL_SCOPE, var_section::VAR_OUTPUT, "out"),
+                owned_var(
+                    2,
+                    FunctionId::GLOBAL_SCOPE,
+                    var_section::VAR_GLOBAL,
+                    "total",
+                ),
+                owned_var(3, FunctionId::new(2), var_section::VAR_INPUT, "n"),
+                owned_var(4, FunctionId::new(2), var_section::VAR_TEMP, "t"),
+            ],
+            ..DebugSection::default()
+        }
+    }
+
+    #[test]
+    fn frame_scopes_when_program_frame_then_program_sections_and_globals() {
+        let debug = a_sectioned_debug_section();
+        assert_eq!(
+            frame_scopes(Some(&debug), FunctionId::GLOBAL_SCOPE),
+            vec![ScopeKind::Outputs, ScopeKind::Locals, ScopeKind::Globals]
+        );
+    }
+
+    #[test]
+    fn frame_scopes_when_function_frame_then_own_sections_and_globals() {
+        let debug = a_sectioned_debug_section();
+        assert_eq!(
+            frame_scopes(Some(&debug), FunctionId::new(2)),
+            vec![ScopeKind::Inputs, ScopeKind::Locals, ScopeKind::Globals]
+        );
+    }
+
+    #[test]
+    fn frame_scopes_when_no_var_names_then_program_has_locals_only() {
+        assert_eq!(
+            frame_scopes(None, FunctionId::GLOBAL_SCOPE),
+            vec![ScopeKind::Locals]
+        );
+        assert!(frame_scopes(None, FunctionId::new(2)).is_empty());
+    }
+
+    #[test]
+    fn render_scope_when_function_locals_then_excludes_program_locals() {
+        let debug = a_sectioned_debug_section();
+        let vars = render_scope(
+            Some(&debug),
+            FunctionId::new(2),
+            ScopeKind::Locals,
+            &[1, 2, 3, 4, 5],
+            &[],
+            &ForceTable::new(),
+        );
+        assert_eq!(vars.len(), 1);
+        assert_eq!(vars[0].name, "t");
+        assert_eq!(vars[0].value, "5");
+    }
+
+    #[test]
+    fn render_scope_when_globals_then_same_from_every_frame() {
+        let debug = a_sectioned_debug_section();
+        let values = [1, 2, 3, 4, 5];
+        let from_program = render_scope(
+            Some(&debug),
+            FunctionId::GLOBAL_SCOPE,
+            ScopeKind::Globals,
+            &values,
+            &[],
+            &ForceTable::new(),
+        );
+        let from_function = render_scope(
+            Some(&debug),
+            FunctionId::new(2),
+            ScopeKind::Globals,
+            &values,
+            &[],
+            &ForceTable::new(),
+        );
+        assert_eq!(from_program, from_function);
+        assert_eq!(from_program[0].name, "total");
+        assert_eq!(from_program[0].value, "3");
+    }
+
+    #[test]
+    fn render_scope_when_slot_forced_then_flags_only_that_variable() {
+        let debug = a_sectioned_debug_section();
+        let mut forces = ForceTable::new();
+        forces.force_bit(VarIndex::new(1), 0, true);
+        let vars = render_scope(
+            Some(&debug),
+            FunctionId::GLOBAL_SCOPE,
+            ScopeKind::Outputs,
+            &[1, 2, 3, 4, 5],
+            &[],
+            &forces,
+        );
+        assert_eq!(vars[0].value, "2 (forced)");
+        assert_eq!(vars[0].menu_context, Some("forced"));
+    }
+
+    #[test]
+    fn resolve_force_when_name_differs_in_case_then_resolves_whole_slot() {
+        let debug = a_sectioned_debug_section();
+        assert_eq!(
+            resolve_force(Some(&debug), "OUT", "12", None),
+            Ok(ResolvedForce::Whole {
+                var_index: VarIndex::new(1),
+                raw: 12
+            })
+        );
+    }
+
+    #[test]
+    fn resolve_force_when_function_local_then_not_found() {
+        let debug = a_sectioned_debug_section();
+        assert_eq!(
+            resolve_force(Some(&debug), "t", "1", None),
+            Err("no program or global variable is named 't'".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_force_when_bit_of_word_then_resolves_bit() {
+        let debug = DebugSection {
+            var_names: vec![var_name(0, iec_type_tag::WORD, "inputs", "WORD")],
+            ..DebugSection::default()
+        };
+        assert_eq!(
+            resolve_force(Some(&debug), "inputs", "TRUE", Some(15)),
+            Ok(ResolvedForce::Bit {
+                var_index: VarIndex::new(0),
+                bit: 15,
+                on: true
+            })
+        );
+    }
+
+    #[test]
+    fn resolve_force_when_bit_past_width_then_error_names_the_range() {
+        let debug = DebugSection {
+            var_names: vec![var_name(0, iec_type_tag::BYTE, "flags", "BYTE")],
+            ..DebugSection::default()
+        };
+        assert_eq!(
+            resolve_force(Some(&debug), "flags", "TRUE", Some(8)),
+            Err("flags (BYTE) has bits 0 to 7; there is no bit 8".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_force_when_bit_of_dint_then_not_a_bit_string() {
+        let debug = a_sectioned_debug_section();
+        let err = resolve_force(Some(&debug), "x", "TRUE", Some(0)).unwrap_err();
+        assert!(err.contains("is not a bit string"));
+    }
+
+    #[test]
+    fn render_scope_when_no_var_names_then_program_locals_fall_back_to_every_slot() {
+        let vars = render_scope(
+            None,
+            FunctionId::GLOBAL_SCOPE,
+            ScopeKind::Locals,
+            &[7, 8],
+            &[],
+            &ForceTable::new(),
+        );
+        assert_eq!(vars.len(), 2);
+        assert_eq!(vars[1].name, "var[1]");
     }
 }
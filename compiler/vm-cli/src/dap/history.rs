@@ -0,0 +1,258 @@
+//! Scan history for reverse debugging.
+//!
+//! The debug loop copies the program state — the variable table and the data
+//! region — at every scan boundary into a ring buffer of [`ScanSnapshot`]s.
+//! Going back is then a matter of handing one of them to
+//! [`VmRunning::rewind`] and letting the loop land on the first instruction of
+//! the restored scan, exactly as a scan step lands. The granularity is the scan
+//! cycle: a snapshot is the state a scan starts from, so "scan `n`" is the
+//! cycle that runs once `n` scans have completed, the way the `Runtime`
+//! scope's `scanCount` counts.
+//!
+//! A snapshot holds no scheduler state: the debug loop drives the VM without
+//! the scheduler, so each task's counts and timing are the same at every scan
+//! boundary. The snapshot's clock is the one part of timing a scan reads, and
+//! the loop replays the restored scan on it.
+//!
+//! Snapshots after the scan the VM is rewound to are kept, so the client can
+//! jump forwards again, until the program runs on: the next recorded boundary
+//! replaces them, because re-running from an earlier state is not guaranteed to
+//! retrace the same history.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use ironplc_container::VarIndex;
+use ironplc_vm::error::SnapshotMismatch;
+use ironplc_vm::VmRunning;
+
+/// How many scans of history a session keeps when the launch configuration
+/// does not say.
+pub const DEFAULT_SCAN_HISTORY: usize = 100;
+
+/// The program state at the start of one scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanSnapshot {
+    /// The scan this state starts: the number of scans completed when it was
+    /// taken.
+    pub scan: u64,
+    /// The debug clock the scan reads, so a replay sees the same uptime.
+    pub time_us: u64,
+    /// Raw variable slots, indexed by `VarIndex`.
+    pub variables: Vec<u64>,
+    /// A copy of the data region.
+    pub data_region: Vec<u8>,
+}
+
+impl ScanSnapshot {
+    /// Copies the state of `running`, which must be at a scan boundary, for
+    /// the scan that will start at `time_us`.
+    pub fn capture(running: &VmRunning, time_us: u64) -> ScanSnapshot {
+        let variables = (0..running.num_variables())
+            .map(|i| running.read_variable_raw(VarIndex::new(i)).unwrap_or(0))
+            .collect();
+        ScanSnapshot {
+            scan: running.scan_count(),
+            time_us,
+            variables,
+            data_region: running.data_region().to_vec(),
+        }
+    }
+
+    /// Rewinds `running` to this snapshot; the next debug round starts the
+    /// snapshot's scan.
+    pub fn restore(&self, running: &mut VmRunning) -> Result<(), SnapshotMismatch> {
+        running.rewind(self.scan, &self.variables, &self.data_region)
+    }
+}
+
+/// Why the history could not supply the scan a request asked for.
+#[derive(Debug, PartialEq, Eq)]
+pub enum HistoryError {
+    /// The session keeps no history (`scanHistory: 0`).
+    Disabled,
+    /// The scan is older than the oldest snapshot or newer than the newest.
+    NotRetained { scan: u64, oldest: u64, newest: u64 },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::Disabled => write!(
+                f,
+                "scan history is off; set scanHistory in the launch configuration"
+            ),
+            HistoryError::NotRetained {
+                scan,
+                oldest,
+                newest,
+            } => write!(
+                f,
+                "scan {scan} is not in the scan history, which holds scans {oldest} to {newest}"
+            ),
+        }
+    }
+}
+
+/// A bounded, scan-ordered ring buffer of [`ScanSnapshot`]s.
+#[derive(Debug)]
+pub struct ScanHistory {
+    capacity: usize,
+    snapshots: VecDeque<ScanSnapshot>,
+}
+
+impl ScanHistory {
+    /// An empty history that keeps at most `capacity` snapshots. A capacity of
+    /// zero records nothing.
+    pub fn new(capacity: usize) -> ScanHistory {
+        ScanHistory {
+            capacity,
+            snapshots: VecDeque::new(),
+        }
+    }
+
+    /// Records `snapshot`, dropping any snapshot for the same or a later scan
+    /// (a future the program has now left) and then the oldest snapshots
+    /// beyond the capacity.
+    pub fn record(&mut self, snapshot: ScanSnapshot) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self
+            .snapshots
+            .back()
+            .is_some_and(|last| last.scan >= snapshot.scan)
+        {
+            self.snapshots.pop_back();
+        }
+        self.snapshots.push_back(snapshot);
+        while self.snapshots.len() > self.capacity {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// The snapshot that starts `scan`.
+    pub fn get(&self, scan: u64) -> Result<&ScanSnapshot, HistoryError> {
+        let (oldest, newest) = match (self.snapshots.front(), self.snapshots.back()) {
+            (Some(oldest), Some(newest)) => (oldest.scan, newest.scan),
+            _ => return Err(HistoryError::Disabled),
+        };
+        self.snapshots
+            .iter()
+            .find(|s| s.scan == scan)
+            .ok_or(HistoryError::NotRetained {
+                scan,
+                oldest,
+                newest,
+            })
+    }
+
+    /// The snapshot a step back from `scan` lands on. From part-way through
+    /// the scan, that is the start of the same scan; from its start, the
+    /// start of the previous one. Once the history runs out it is the oldest
+    /// snapshot kept.
+    pub fn step_back_from(
+        &self,
+        scan: u64,
+        at_scan_start: bool,
+    ) -> Result<&ScanSnapshot, HistoryError> {
+        let target = if at_scan_start {
+            scan.saturating_sub(1)
+        } else {
+            scan
+        };
+        self.get(target).or_else(|_| self.oldest())
+    }
+
+    /// The oldest snapshot kept, where a reverse continue lands.
+    pub fn oldest(&self) -> Result<&ScanSnapshot, HistoryError> {
+        self.snapshots.front().ok_or(HistoryError::Disabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(scan: u64) -> ScanSnapshot {
+        ScanSnapshot {
+            scan,
+            time_us: scan * 1000,
+            variables: vec![scan],
+            data_region: vec![],
+        }
+    }
+
+    fn history_of(capacity: usize, scans: &[u64]) -> ScanHistory {
+        let mut history = ScanHistory::new(capacity);
+        scans
+            .iter()
+            .for_each(|&scan| history.record(snapshot(scan)));
+        history
+    }
+
+    #[test]
+    fn record_when_over_capacity_then_evicts_oldest() {
+        let history = history_of(2, &[0, 1, 2]);
+        assert_eq!(history.oldest().unwrap().scan, 1);
+        assert_eq!(
+            history.get(0),
+            Err(HistoryError::NotRetained {
+                scan: 0,
+                oldest: 1,
+                newest: 2
+            })
+        );
+    }
+
+    #[test]
+    fn record_when_rewound_then_replaces_the_abandoned_future() {
+        let mut history = history_of(10, &[0, 1, 2, 3]);
+        // Rewound to scan 1 and ran it again: the boundary after it is new.
+        let mut rerun = snapshot(2);
+        rerun.variables = vec![20];
+        history.record(rerun);
+
+        assert_eq!(history.get(2).unwrap().variables, vec![20]);
+        assert!(history.get(3).is_err());
+    }
+
+    #[test]
+    fn record_when_capacity_zero_then_history_disabled() {
+        let history = history_of(0, &[0, 1]);
+        assert_eq!(history.get(0), Err(HistoryError::Disabled));
+        assert_eq!(history.oldest(), Err(HistoryError::Disabled));
+    }
+
+    #[test]
+    fn step_back_from_when_at_scan_start_and_previous_scan_kept_then_returns_it() {
+        let history = history_of(10, &[0, 1, 2, 3]);
+        assert_eq!(history.step_back_from(3, true).unwrap().scan, 2);
+    }
+
+    #[test]
+    fn step_back_from_when_mid_scan_then_returns_start_of_same_scan() {
+        let history = history_of(10, &[0, 1, 2, 3]);
+        assert_eq!(history.step_back_from(3, false).unwrap().scan, 3);
+    }
+
+    #[test]
+    fn step_back_from_when_previous_scan_evicted_then_returns_oldest() {
+        let history = history_of(2, &[0, 1, 2, 3]);
+        assert_eq!(history.step_back_from(2, true).unwrap().scan, 2);
+        assert_eq!(history.step_back_from(0, true).unwrap().scan, 2);
+    }
+
+    #[test]
+    fn history_error_when_not_retained_then_names_the_range() {
+        let err = HistoryError::NotRetained {
+            scan: 9,
+            oldest: 1,
+            newest: 4,
+        };
+        assert_eq!(
+            err.to_string(),
+            "scan 9 is not in the scan history, which holds scans 1 to 4"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! The request inbox shared between the reader thread and the DAP loop.
+//!
+//! The DAP loop cannot read the wire while `run_round_debug` is executing, so
+//! a dedicated reader thread owns the input stream: it decodes each framed
+//! request and queues it here, in arrival order. The loop takes requests off
+//! the front whenever it is stopped, exactly as it used to read them off the
+//! wire, so request ordering and the [`state::legal`](super::state::legal)
+//! table are unchanged.
+//!
+//! The one request that must not wait for a natural stop is `pause`. On
+//! queueing a `pause` the reader thread also raises [`Inbox::pause_flag`],
+//! which the loop lends to the VM's `DebuggerHook`; the hook stops before the
+//! next instruction. The loop then answers that queued `pause` out of order
+//! with [`Inbox::take_pause`], ahead of the `stopped` event it causes.
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+use super::framing;
+use super::types::Request;
+
+/// The DAP `command` the reader thread acts on before queueing.
+const PAUSE_COMMAND: &str = "pause";
+
+/// Queue state behind the [`Inbox`] mutex.
+#[derive(Default)]
+struct Queue {
+    requests: VecDeque<Request>,
+    /// Set once the stream has ended; carries the read error, if any, that
+    /// ended it.
+    closed: Option<Option<io::Error>>,
+}
+
+/// Requests decoded by the reader thread, waiting for the DAP loop.
+#[derive(Default)]
+pub struct Inbox {
+    queue: Mutex<Queue>,
+    arrived: Condvar,
+    pause: AtomicBool,
+}
+
+impl Inbox {
+    /// Spawns the reader thread over `reader` and returns the inbox it fills.
+    ///
+    /// The thread is detached: it ends when the stream does, and a session
+    /// that finishes first (a `disconnect`) does not wait for the client to
+    /// close its end.
+    pub fn spawn<R: BufRead + Send + 'static>(mut reader: R) -> Arc<Inbox> {
+        let inbox = Arc::new(Inbox::default());
+        let filler = Arc::clone(&inbox);
+        thread::spawn(move || loop {
+            match framing::read_message(&mut reader) {
+                Ok(Some(body)) => {
+                    // A frame we cannot parse as a request carries no seq to
+                    // answer; skip it rather than crash the session.
+                    if let Ok(request) = serde_json::from_slice::<Request>(&body) {
+                        filler.push(request);
+                    }
+                }
+                Ok(None) => return filler.close(None),
+                Err(err) => return filler.close(Some(err)),
+            }
+        });
+        inbox
+    }
+
+    /// Queues `request`, raising the pause flag when it is a `pause` so a
+    /// running round sees it at its next instruction.
+    fn push(&self, request: Request) {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        if request.command == PAUSE_COMMAND {
+            self.pause.store(true, Ordering::Release);
+        }
+        queue.requests.push_back(request);
+        self.arrived.notify_one();
+    }
+
+    /// Re-derives the pause flag after a `pause` leaves the queue: it stays
+    /// raised only while another `pause` is still waiting. Called with the
+    /// queue locked, so it cannot race [`push`](Self::push).
+    fn settle_pause_flag(&self, queue: &Queue) {
+        let pending = queue.requests.iter().any(|r| r.command == PAUSE_COMMAND);
+        self.pause.store(pending, Ordering::Release);
+    }
+
+    /// Marks the stream ended, optionally by `error`.
+    fn close(&self, error: Option<io::Error>) {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        queue.closed = Some(error);
+        self.arrived.notify_one();
+    }
+
+    /// Blocks until the next request arrives and returns it.
+    ///
+    /// Returns `Ok(None)` at a clean end of stream once every queued request
+    /// has been taken, and the read error once, in place of the end of stream,
+    /// if the stream failed.
+    pub fn next(&self) -> io::Result<Option<Request>> {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if let Some(request) = queue.requests.pop_front() {
+                if request.command == PAUSE_COMMAND {
+                    // Taken at a stop, this pause has nothing left to
+                    // interrupt; it must not stop the next run.
+                    self.settle_pause_flag(&queue);
+                }
+                return Ok(Some(request));
+            }
+            if let Some(closed) = queue.closed.as_mut() {
+                return match closed.take() {
+                    Some(err) => Err(err),
+                    None => Ok(None),
+                };
+            }
+            queue = self.arrived.wait(queue).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Removes and returns the oldest queued `pause` request, if any, so the
+    /// loop can answer the pause that stopped the VM before the requests
+    /// queued ahead of it.
+    pub fn take_pause(&self) -> Option<Request> {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        let position = queue
+            .requests
+            .iter()
+            .position(|r| r.command == PAUSE_COMMAND)?;
+        let pause = queue.requests.remove(position);
+        self.settle_pause_flag(&queue);
+        pause
+    }
+
+    /// The flag raised by every queued `pause`. The loop lends it to the
+    /// `DebuggerHook` for each round, which lowers it when it stops; taking a
+    /// `pause` off the queue lowers it too, unless another one is waiting.
+    pub fn pause_flag(&self) -> &AtomicBool {
+        &self.pause
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Cursor;
+
+    fn framed(requests: &[serde_json::Value]) -> Cursor<Vec<u8>> {
+        let mut buf = Vec::new();
+        for request in requests {
+            framing::write_message(&mut buf, &serde_json::to_vec(request).unwrap()).unwrap();
+        }
+        Cursor::new(buf)
+    }
+
+    #[test]
+    fn next_when_requests_queued_then_returns_them_in_order_then_end() {
+        let inbox = Inbox::spawn(framed(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "threads"}),
+        ]));
+        assert_eq!(inbox.next().unwrap().unwrap().command, "initialize");
+        assert_eq!(inbox.next().unwrap().unwrap().command, "threads");
+        assert!(inbox.next().unwrap().is_none());
+    }
+
+    fn request(seq: i64, command: &str) -> Request {
+        serde_json::from_value(json!({"seq": seq, "type": "request", "command": command})).unwrap()
+    }
+
+    #[test]
+    fn push_when_pause_then_flag_raised_until_it_is_taken() {
+        let inbox = Inbox::default();
+        inbox.push(request(1, "pause"));
+        assert!(inbox.pause_flag().load(Ordering::Acquire));
+
+        assert_eq!(inbox.next().unwrap().unwrap().command, "pause");
+        assert!(!inbox.pause_flag().load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn next_when_second_pause_still_queued_then_flag_stays_raised() {
+        let inbox = Inbox::default();
+        inbox.push(request(1, "pause"));
+        inbox.push(request(2, "pause"));
+
+        assert_eq!(inbox.next().unwrap().unwrap().seq, 1);
+        assert!(inbox.pause_flag().load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn take_pause_when_queued_behind_other_requests_then_removes_only_the_pause() {
+        let inbox = Inbox::default();
+        inbox.push(request(1, "threads"));
+        inbox.push(request(2, "pause"));
+        inbox.push(request(3, "stackTrace"));
+
+        assert_eq!(inbox.take_pause().unwrap().seq, 2);
+        assert!(inbox.take_pause().is_none());
+        // The requests around it keep their order.
+        assert_eq!(inbox.next().unwrap().unwrap().seq, 1);
+        assert_eq!(inbox.next().unwrap().unwrap().seq, 3);
+    }
+
+    #[test]
+    fn next_when_stream_failed_then_reports_error_after_queued_requests() {
+        let inbox = Inbox::default();
+        inbox.push(request(1, "threads"));
+        inbox.close(Some(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "bad frame",
+        )));
+
+        assert_eq!(inbox.next().unwrap().unwrap().seq, 1);
+        assert!(inbox.next().is_err());
+    }
+}
@@ -135,7 +135,10 @@ pub fn start_vm<'a>(
         .start()
         .map_err(|ctx| LaunchError::VmStartFailed {
             v_code: ctx.trap.v_code(),
-            detail: ctx.trap.to_string(),
+            detail: ctx
+                .trap
+                .with_debug(container.debug_section.as_ref())
+                .to_string(),
         })
 }
 
@@ -7,12 +7,17 @@
 //! `specs/plans/2026-06-25-dap-server-scaffold.md`). So far: the wire
 //! [`framing`] layer, the hand-rolled message [`types`], the request [`state`]
 //! legality table, the [`launch`] preconditions, the isolated [`debug_info`]
-//! resolver, and the [`server`] event loop implementing the
-//! `initialize`/`launch`/`disconnect` handshake. The run/stop loop that drives
-//! execution arrives in a later commit.
+//! resolver, the [`inbox`] that a reader thread fills so `pause` can reach a
+//! running program, and the [`server`] event loop implementing the
+//! `initialize`/`launch`/`disconnect` handshake. An `attach` request hands the
+//! session to [`attach`], which debugs a program `ironplcvm run --monitor` is
+//! already running.
 
+pub mod attach;
 pub mod debug_info;
 pub mod framing;
+pub mod history;
+pub mod inbox;
 pub mod launch;
 pub mod problem_codes;
 pub mod server;
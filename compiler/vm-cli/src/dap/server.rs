@@ -1,73 +1,88 @@
-//! The single-threaded DAP event loop.
+//! The DAP event loop.
 //!
 //! This module drives the `initialize` → `launch` → `setBreakpoints` →
 //! `configurationDone` → (run) → `stopped` → inspect → `continue` →
 //! `terminated` → `disconnect` lifecycle against the VM debug engine. Every
 //! request is gated through the [`state::legal`] table; an illegal or
 //! not-yet-supported request is answered with a DAP error whose message is
-//! `requestNotApplicable`. The single-threaded design is described in
+//! `requestNotApplicable`. The loop design is described in
 //! `specs/design/debugger-support.md` §"Single-threaded DAP loop (v1)".
 //!
+//! One thread runs the loop and the VM. A second, the [`inbox`](super::inbox)
+//! reader, owns the input stream so that a `pause` can reach a program that
+//! is running: the reader raises a flag the VM's hook checks before every
+//! instruction. Every other request still waits in the inbox for the next
+//! natural stop.
+//!
 //! The loop is split at the launch boundary so lifetimes stay simple: the
 //! *pre-launch* loop ([`serve`]) handles `initialize` / `disconnect` and the
 //! `launch` preconditions with nothing borrowed; once preconditions pass it
 //! hands the owned [`Container`] to [`launched_session`], which sizes the VM
-//! buffers, starts the VM, and runs the *post-launch* run/stop loop borrowing
-//! them.
+//! buffers, starts the VM, and runs the *post-launch* run/stop loop
+//! ([`run_session`]) borrowing them. A `restart` ends one run/stop loop and
+//! starts the next against the reloaded container, inside the same session.
 //!
 //! Not yet implemented: a trap ends the session as `terminated` rather than
 //! surfacing a `stopped{reason:"exception"}`.
 
+use std::collections::BTreeMap;
 use std::io::{self, BufRead, Write};
 use std::path::Path;
 
 use ironplc_container::debug_section::DebugSection;
-use ironplc_container::{Container, VarIndex};
+use ironplc_container::{Container, FunctionId, VarIndex};
 use ironplc_vm::{
     BreakpointTable, DebuggerHook, PauseReason, RoundOutcome, StepMode, VmBuffers, VmRunning,
 };
 use serde::Serialize;
 use serde_json::Value;
 
-use super::debug_info;
+use super::attach;
+use super::debug_info::{self, ResolvedForce, ScopeKind};
 use super::framing;
+use super::history::{ScanHistory, ScanSnapshot, DEFAULT_SCAN_HISTORY};
+use super::inbox::Inbox;
 use super::launch;
 use super::state::{self, Command, Phase};
 use super::types::{
-    Breakpoint, Capabilities, ContinueResponseBody, Event, LaunchRequestArguments, Request,
-    Response, Scope, ScopesResponseBody, SetBreakpointsArguments, SetBreakpointsResponseBody,
+    Breakpoint, Capabilities, ContinueResponseBody, Event, ForceVariableArguments,
+    GoToScanArguments, LaunchRequestArguments, Request, Response, RestartArguments, Scope,
+    ScopesArguments, ScopesResponseBody, SetBreakpointsArguments, SetBreakpointsResponseBody,
     Source, StackFrame, StackTraceResponseBody, StoppedEventBody, Thread, ThreadsResponseBody,
-    Variable, VariablesArguments, VariablesResponseBody,
+    UnforceVariableArguments, Variable, VariablesArguments, VariablesResponseBody,
 };
 
 /// The id of the single synthetic thread the v1 server exposes.
-const THREAD_ID: i64 = 1;
-
-/// The `variablesReference` handle for the `Program` scope: the program's ST
-/// variables. Non-zero so DAP treats it as expandable; the (flat) list of
-/// program variables is returned for it. Structured expansion (nested FB fields)
-/// is a later phase, so every returned
-/// [`Variable`](super::types::Variable) has `variablesReference: 0`.
-const PROGRAM_REF: i64 = 1;
+pub(super) const THREAD_ID: i64 = 1;
 
 /// The `variablesReference` handle for the `Runtime` scope: VM-level state that
 /// is not a program variable. Keeping it in its own scope means a synthetic
 /// entry can never collide with an ST variable of the same name.
-const RUNTIME_REF: i64 = 2;
+pub(super) const RUNTIME_REF: i64 = 2;
+
+/// The first `variablesReference` handle for a frame's variable scopes.
+/// The scope of kind `k` in frame `f` is `FRAME_SCOPE_BASE + f * 6 + k`,
+/// with `k` the kind's position in [`ScopeKind::ALL`]
+/// (see [`frame_scope_ref`]), so a handle decodes back to its frame and
+/// section without a table. Handles are only good until the next resume,
+/// which is all DAP asks of them. The flat [`Variable`]s they enumerate have
+/// `variablesReference: 0`; structured expansion (nested FB fields) is a
+/// later phase.
+const FRAME_SCOPE_BASE: i64 = 16;
 
 /// The DAP `message` returned for any request that is illegal in the current
 /// phase or not supported by this server slice.
-const REQUEST_NOT_APPLICABLE: &str = "requestNotApplicable";
+pub(super) const REQUEST_NOT_APPLICABLE: &str = "requestNotApplicable";
 
 /// Serializes a DAP message and writes it with Content-Length framing.
-fn send<W: Write, T: Serialize>(writer: &mut W, message: &T) -> io::Result<()> {
+pub(super) fn send<W: Write, T: Serialize>(writer: &mut W, message: &T) -> io::Result<()> {
     let body =
         serde_json::to_vec(message).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
     framing::write_message(writer, &body)
 }
 
 /// Returns the current outgoing sequence number and advances the counter.
-fn take_seq(seq: &mut i64) -> i64 {
+pub(super) fn take_seq(seq: &mut i64) -> i64 {
     let current = *seq;
     *seq += 1;
     current
@@ -76,25 +91,22 @@ fn take_seq(seq: &mut i64) -> i64 {
 /// Runs the DAP server over `reader`/`writer` until the client disconnects or
 /// the stream ends.
 ///
-/// This is the pre-launch loop: it services `initialize` and `disconnect`,
-/// evaluates the `launch` preconditions, and — on a satisfied `launch` — hands
-/// off to [`launched_session`] and returns whatever that returns. A launch that
-/// fails a precondition is answered with an error and the loop continues, so
-/// the client may retry or disconnect.
-pub fn serve<R: BufRead, W: Write>(reader: &mut R, writer: &mut W) -> io::Result<()> {
+/// `reader` is handed to the [`inbox`](super::inbox) reader thread, which is why it must be
+/// owned and `Send`. This is the pre-launch loop: it services `initialize` and
+/// `disconnect`, evaluates the `launch` preconditions, and — on a satisfied
+/// `launch` — hands off to [`launched_session`] and returns whatever that
+/// returns. A launch that fails a precondition is answered with an error and
+/// the loop continues, so the client may retry or disconnect.
+pub fn serve<R: BufRead + Send + 'static, W: Write>(reader: R, writer: &mut W) -> io::Result<()> {
+    let inbox = Inbox::spawn(reader);
     let mut seq: i64 = 1;
     let mut phase = Phase::Initialized;
 
     loop {
-        let Some(body) = framing::read_message(reader)? else {
+        let Some(request) = inbox.next()? else {
             // Clean end-of-stream between messages: the client went away.
             return Ok(());
         };
-        let Ok(request) = serde_json::from_slice::<Request>(&body) else {
-            // A frame we cannot parse as a request carries no seq to answer;
-            // skip it rather than crash the session.
-            continue;
-        };
 
         let command = Command::from_request(&request.command);
         let legal_here = command.is_some_and(|c| state::legal(phase, c));
@@ -103,6 +115,8 @@ pub fn serve<R: BufRead, W: Write>(reader: &mut R, writer: &mut W) -> io::Result
             Some(Command::Initialize) if legal_here => {
                 let caps = Capabilities {
                     supports_configuration_done_request: true,
+                    supports_restart_request: true,
+                    supports_step_back: true,
                 };
                 let body = serde_json::to_value(caps).ok();
                 send(
@@ -114,12 +128,27 @@ pub fn serve<R: BufRead, W: Write>(reader: &mut R, writer: &mut W) -> io::Result
                 phase = Phase::Configuring;
             }
             Some(Command::Launch) if legal_here => {
-                match load_and_check(&request) {
+                match load_and_check(request.arguments.as_ref()) {
                     Ok((container, args)) => {
                         // Preconditions hold: own the container and run the
                         // rest of the session against a live VM.
                         return launched_session(
-                            reader, writer, &mut seq, container, args, &request,
+                            &inbox, writer, &mut seq, container, args, request,
+                        );
+                    }
+                    Err(message) => {
+                        send(
+                            writer,
+                            &Response::error(take_seq(&mut seq), &request, message),
+                        )?;
+                    }
+                }
+            }
+            Some(Command::Attach) if legal_here => {
+                match attach::connect(request.arguments.as_ref()) {
+                    Ok(monitor) => {
+                        return attach::attached_session(
+                            &inbox, writer, &mut seq, monitor, request,
                         );
                     }
                     Err(message) => {
@@ -152,29 +181,132 @@ pub fn serve<R: BufRead, W: Write>(reader: &mut R, writer: &mut W) -> io::Result
 /// Parses the `launch` arguments, loads the container, and checks the launch
 /// preconditions. Returns the loaded container and the parsed arguments (run
 /// bounds) on success, or the DAP error message to report on failure.
-fn load_and_check(request: &Request) -> Result<(Container, LaunchRequestArguments), String> {
-    let args: LaunchRequestArguments = request
-        .arguments
-        .as_ref()
+fn load_and_check(
+    arguments: Option<&Value>,
+) -> Result<(Container, LaunchRequestArguments), String> {
+    let args: LaunchRequestArguments = arguments
         .and_then(|v| serde_json::from_value(v.clone()).ok())
         .ok_or_else(|| launch::LaunchError::ProgramArgMissing.to_string())?;
+    let container = load_checked(&args)?;
+    Ok((container, args))
+}
 
+/// Loads the container `args` names and checks the launch preconditions
+/// against it, rendering any failure as the DAP error message.
+fn load_checked(args: &LaunchRequestArguments) -> Result<Container, String> {
     let container = launch::load_container(Path::new(&args.program)).map_err(|e| e.to_string())?;
     launch::check_preconditions(&container).map_err(|e| e.to_string())?;
-    Ok((container, args))
+    Ok(container)
+}
+
+/// How one run/stop loop ([`run_session`]) ended.
+enum SessionEnd {
+    /// The client disconnected, or the stream ended.
+    Disconnected,
+    /// The client asked to `restart` with a container that has already been
+    /// loaded and checked; the `request` is answered once the new VM starts.
+    Restart {
+        container: Box<Container>,
+        args: LaunchRequestArguments,
+        request: Request,
+    },
 }
 
-/// Owns the loaded `container`, starts the VM, answers the `launch` request,
-/// and runs the post-launch run/stop loop.
+/// Owns the loaded `container`, starts the VM, answers the request that
+/// started it, and runs the post-launch run/stop loop — repeatedly, once per
+/// `restart`.
 ///
 /// The `container` and the buffers sized from it live here so the [`VmRunning`]
-/// can borrow them for the remainder of the session.
+/// can borrow them for one run. A `restart` replaces both: the reloaded
+/// container is owned by the next iteration, and its fresh buffers mean the
+/// init functions run again from a clean variable table. The breakpoints the
+/// client set survive the restart as *source* breakpoints and are re-resolved
+/// against the new debug section, so a recompile that moved code keeps them on
+/// the same lines.
+fn launched_session<W: Write>(
+    inbox: &Inbox,
+    writer: &mut W,
+    seq: &mut i64,
+    container: Container,
+    args: LaunchRequestArguments,
+    launch_request: Request,
+) -> io::Result<()> {
+    let mut container = container;
+    let mut args = args;
+    let mut start_request = launch_request;
+    // The session opens in `Configuring`: the client sets breakpoints and then
+    // sends `configurationDone`. A restarted run skips that handshake -- the
+    // client does not repeat it -- and starts running straight away.
+    let mut phase = Phase::Configuring;
+    let mut source_breakpoints = SourceBreakpoints::new();
+
+    loop {
+        let mut bufs = VmBuffers::from_container(&container);
+
+        // Construct + start the VM. Buffer sizing (operand stack, variable
+        // table, data region, and the frame stack from `header.max_call_depth`)
+        // is done by `VmBuffers::from_container`, reused from the `ironplcvm`
+        // embedding path.
+        let running = match launch::start_vm(&container, &mut bufs) {
+            Ok(running) => running,
+            Err(err) => {
+                send(
+                    writer,
+                    &Response::error(take_seq(seq), &start_request, err.to_string()),
+                )?;
+                return Ok(());
+            }
+        };
+
+        // Preconditions, buffer sizing, and start all succeeded.
+        send(
+            writer,
+            &Response::success(take_seq(seq), &start_request, None),
+        )?;
+
+        let session = Session {
+            inbox,
+            debug: container.debug_section.as_ref(),
+            args: &args,
+        };
+        match run_session(
+            &session,
+            writer,
+            seq,
+            running,
+            phase,
+            &mut source_breakpoints,
+        )? {
+            SessionEnd::Disconnected => return Ok(()),
+            SessionEnd::Restart {
+                container: reloaded,
+                args: restart_args,
+                request,
+            } => {
+                container = *reloaded;
+                args = restart_args;
+                start_request = request;
+                phase = Phase::Running;
+            }
+        }
+    }
+}
+
+/// What one run/stop loop reads but does not own.
+struct Session<'a> {
+    inbox: &'a Inbox,
+    debug: Option<&'a DebugSection>,
+    args: &'a LaunchRequestArguments,
+}
+
+/// Runs the post-launch run/stop loop for one started VM, until the client
+/// disconnects or restarts.
 ///
 /// The loop alternates between two modes (see
 /// `specs/design/debugger-support.md` §"Single-threaded DAP loop (v1)"): when
 /// `Running`, it drives one `run_round_debug` and reacts to the outcome;
-/// otherwise it reads and services one client request. Because the
-/// [`BreakpointTable`] is mutated between rounds (a `setBreakpoints` at a
+/// otherwise it takes and services one client request from the inbox. Because
+/// the [`BreakpointTable`] is mutated between rounds (a `setBreakpoints` at a
 /// pause) while the [`DebuggerHook`] borrows it during a round, the hook is
 /// built fresh per round; after a breakpoint pause the next hook is told to
 /// suppress that location once so `continue` makes forward progress.
@@ -192,45 +324,33 @@ fn load_and_check(request: &Request) -> Result<(Container, LaunchRequestArgument
 /// to `RoundOutcome::PausedAfterScan`, the second stops at the first
 /// instruction of the new scan so the stop has a frame to show.
 ///
-/// Current limitations: trap→`exception` is not yet implemented. A free-running
-/// program with no breakpoint and no `scanLimit` scans until the client
-/// disconnects — the single-threaded loop cannot service an interactive `pause`
-/// (a Phase 6 cut).
-fn launched_session<R: BufRead, W: Write>(
-    reader: &mut R,
+/// Each scan boundary is copied into a [`ScanHistory`], which `stepBack`,
+/// `reverseContinue`, and `ironplc/goToScan` rewind the VM to; the trip back
+/// lands like a scan step, on the first instruction of the restored scan.
+///
+/// `ironplc/forceVariable` and `ironplc/unforceVariable` change the VM's force
+/// table before the run or at a pause; the VM holds the forced values across
+/// every scan from then on. A restart starts a new VM, with nothing forced.
+///
+/// Every round's hook watches the inbox's pause flag, so a `pause` sent while
+/// the program free-runs stops it before the next instruction; the loop
+/// answers that `pause` and then reports `stopped{reason:"pause"}`.
+///
+/// Current limitation: trap→`exception` is not yet implemented.
+fn run_session<W: Write>(
+    session: &Session<'_>,
     writer: &mut W,
     seq: &mut i64,
-    container: Container,
-    args: LaunchRequestArguments,
-    launch_request: &Request,
-) -> io::Result<()> {
-    let mut bufs = VmBuffers::from_container(&container);
-
-    // Construct + start the VM. Buffer sizing (operand stack, variable table,
-    // data region, and the frame stack from `header.max_call_depth`) is done by
-    // `VmBuffers::from_container`, reused from the `ironplcvm` embedding path.
-    let mut running = match launch::start_vm(&container, &mut bufs) {
-        Ok(running) => running,
-        Err(err) => {
-            send(
-                writer,
-                &Response::error(take_seq(seq), launch_request, err.to_string()),
-            )?;
-            return Ok(());
-        }
-    };
-
-    // Preconditions, buffer sizing, and start all succeeded.
-    send(
-        writer,
-        &Response::success(take_seq(seq), launch_request, None),
-    )?;
-
-    let debug = container.debug_section.as_ref();
+    mut running: VmRunning<'_>,
+    phase: Phase,
+    source_breakpoints: &mut SourceBreakpoints,
+) -> io::Result<SessionEnd> {
+    let Session { inbox, debug, args } = *session;
+    let mut phase = phase;
+    // Rebuilt from the client's source breakpoints so a restarted run binds
+    // them against its own debug section.
     let mut breakpoints = BreakpointTable::new();
-    // The session opens in `Configuring`: the client sets breakpoints and then
-    // sends `configurationDone` to begin the run.
-    let mut phase = Phase::Configuring;
+    bind_breakpoints(source_breakpoints, debug, &mut breakpoints);
     // A monotonic clock for the debug driver. `run_round_debug` bypasses the
     // scheduler and watchdog, so the exact value only feeds the uptime system
     // variable; a per-round bump keeps it non-decreasing.
@@ -239,7 +359,7 @@ fn launched_session<R: BufRead, W: Write>(
     // instead of re-triggering in place.
     let mut suppress_bp = false;
     // Upper bound on scan cycles (runaway prevention); `None` runs until the
-    // client disconnects.
+    // client pauses or disconnects.
     let scan_limit = args.scan_limit;
     // Armed once, before the first scan, when the launch requested `stopOnEntry`.
     let mut pending_stop_on_entry = args.stop_on_entry;
@@ -250,11 +370,19 @@ fn launched_session<R: BufRead, W: Write>(
     // first instruction, so the scan step lands at the start of the new scan
     // rather than at the frame-less boundary between the two.
     let mut pending_scan_landing = false;
+    // The `stopped` reason the next step landing reports: "step", or "goto"
+    // when the landing ends an `ironplc/goToScan`.
+    let mut step_reason = "step";
+    // Snapshots of the state each scan started from, for going back. The
+    // first is the state the init functions left.
+    let mut history = ScanHistory::new(args.scan_history.unwrap_or(DEFAULT_SCAN_HISTORY));
+    history.record(ScanSnapshot::capture(&running, current_time_us));
 
     loop {
         if phase == Phase::Running {
             let outcome = {
                 let mut hook = DebuggerHook::new(&breakpoints);
+                hook.watch_pause_requests(inbox.pause_flag());
                 if suppress_bp {
                     hook.suppress_next_breakpoint();
                     suppress_bp = false;
@@ -291,6 +419,7 @@ fn launched_session<R: BufRead, W: Write>(
                 // A completed scan keeps the debugger scanning: run the next
                 // cycle unless a `scanLimit` bound has been reached.
                 Ok(RoundOutcome::Completed) => {
+                    history.record(ScanSnapshot::capture(&running, current_time_us));
                     if scan_limit_reached(scan_limit, &running) {
                         send(writer, &Event::new(take_seq(seq), "terminated", None))?;
                         phase = Phase::Terminated;
@@ -303,6 +432,7 @@ fn launched_session<R: BufRead, W: Write>(
                 // unless the finished cycle reached the `scanLimit`, in which
                 // case there is no next scan to land in.
                 Ok(RoundOutcome::PausedAfterScan) => {
+                    history.record(ScanSnapshot::capture(&running, current_time_us));
                     if scan_limit_reached(scan_limit, &running) {
                         send(writer, &Event::new(take_seq(seq), "terminated", None))?;
                         phase = Phase::Terminated;
@@ -318,12 +448,23 @@ fn launched_session<R: BufRead, W: Write>(
                             "breakpoint"
                         }
                         // A step landing, whether from `next`/`stepIn`/
-                        // `stepOut` or from a scan step's landing round.
+                        // `stepOut` or from a scan step's or a trip back
+                        // through the history's landing round.
                         PauseReason::Step => {
                             suppress_bp = true;
-                            "step"
+                            std::mem::replace(&mut step_reason, "step")
                         }
                         PauseReason::Entry => "entry",
+                        // DAP answers the `pause` request before the stop it
+                        // causes, so take it out of turn -- requests queued
+                        // ahead of it wait for the stop, as they always have.
+                        PauseReason::Requested => {
+                            if let Some(pause) = inbox.take_pause() {
+                                send(writer, &Response::success(take_seq(seq), &pause, None))?;
+                            }
+                            suppress_bp = true;
+                            "pause"
+                        }
                     };
                     send(writer, &stopped_event(take_seq(seq), dap_reason))?;
                     phase = Phase::Paused;
@@ -339,12 +480,9 @@ fn launched_session<R: BufRead, W: Write>(
             continue;
         }
 
-        // Stopped mode: read and service one request.
-        let Some(body) = framing::read_message(reader)? else {
-            return Ok(());
-        };
-        let Ok(request) = serde_json::from_slice::<Request>(&body) else {
-            continue;
+        // Stopped mode: take and service one request.
+        let Some(request) = inbox.next()? else {
+            return Ok(SessionEnd::Disconnected);
         };
 
         let command = Command::from_request(&request.command);
@@ -353,14 +491,18 @@ fn launched_session<R: BufRead, W: Write>(
         match command {
             Some(Command::Disconnect) => {
                 send(writer, &Response::success(take_seq(seq), &request, None))?;
-                return Ok(());
+                return Ok(SessionEnd::Disconnected);
             }
             Some(Command::ConfigurationDone) if legal_here => {
                 send(writer, &Response::success(take_seq(seq), &request, None))?;
                 phase = Phase::Running;
             }
             Some(Command::SetBreakpoints) if legal_here => {
-                let body = set_breakpoints(&request, debug, &mut breakpoints);
+                let bp_args = request.arguments.as_ref().and_then(|v| {
+                    serde_json::from_value::<SetBreakpointsArguments>(v.clone()).ok()
+                });
+                let body = bp_args
+                    .and_then(|a| set_breakpoints(a, source_breakpoints, debug, &mut breakpoints));
                 send(writer, &Response::success(take_seq(seq), &request, body))?;
             }
             Some(Command::Threads) if legal_here => {
@@ -378,29 +520,33 @@ fn launched_session<R: BufRead, W: Write>(
                 send(writer, &Response::success(take_seq(seq), &request, body))?;
             }
             Some(Command::Scopes) if legal_here => {
-                // Two scopes: the program's own variables, and VM-level runtime
-                // state. The client re-requests both at every stop, so the
-                // runtime values track execution without any polling.
-                let body = serde_json::to_value(ScopesResponseBody {
-                    scopes: vec![
-                        Scope {
-                            // Not "Variables": DAP clients render scopes inside
-                            // a pane already titled Variables, so that name
-                            // reads as Variables > Variables. The scope names
-                            // what kind of state it holds -- the program's, as
-                            // against the VM's.
-                            name: "Program".to_string(),
-                            variables_reference: PROGRAM_REF,
-                            expensive: false,
-                        },
-                        Scope {
-                            name: "Runtime".to_string(),
-                            variables_reference: RUNTIME_REF,
-                            expensive: false,
-                        },
-                    ],
-                })
-                .ok();
+                // The frame's variables split by section, then VM-level
+                // runtime state. The client re-requests both at every stop,
+                // so the runtime values track execution without any polling.
+                let frame = request
+                    .arguments
+                    .as_ref()
+                    .and_then(|v| serde_json::from_value::<ScopesArguments>(v.clone()).ok())
+                    .and_then(|a| usize::try_from(a.frame_id).ok());
+                let mut scopes: Vec<Scope> = frame
+                    .and_then(|frame| Some((frame, frame_owner(&running, frame)?)))
+                    .map(|(frame, owner)| {
+                        debug_info::frame_scopes(debug, owner)
+                            .into_iter()
+                            .map(|kind| Scope {
+                                name: kind.name().to_string(),
+                                variables_reference: frame_scope_ref(frame, kind),
+                                expensive: false,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                scopes.push(Scope {
+                    name: "Runtime".to_string(),
+                    variables_reference: RUNTIME_REF,
+                    expensive: false,
+                });
+                let body = serde_json::to_value(ScopesResponseBody { scopes }).ok();
                 send(writer, &Response::success(take_seq(seq), &request, body))?;
             }
             Some(Command::Variables) if legal_here => {
@@ -413,11 +559,10 @@ fn launched_session<R: BufRead, W: Write>(
                     .arguments
                     .as_ref()
                     .and_then(|v| serde_json::from_value::<VariablesArguments>(v.clone()).ok())
-                    .map_or(PROGRAM_REF, |a| a.variables_reference);
+                    .map_or(0, |a| a.variables_reference);
                 let body = match reference {
-                    PROGRAM_REF => program_variables_body(&running, debug),
                     RUNTIME_REF => runtime_variables_body(&running),
-                    _ => serde_json::to_value(VariablesResponseBody { variables: vec![] }).ok(),
+                    _ => frame_variables_body(&running, debug, reference),
                 };
                 send(writer, &Response::success(take_seq(seq), &request, body))?;
             }
@@ -449,6 +594,91 @@ fn launched_session<R: BufRead, W: Write>(
                 pending_step = Some(StepMode::Scan);
                 phase = Phase::Running;
             }
+            Some(command @ (Command::StepBack | Command::ReverseContinue | Command::GoToScan))
+                if legal_here =>
+            {
+                let target =
+                    match command {
+                        Command::StepBack => {
+                            history.step_back_from(running.scan_count(), at_scan_start(&running))
+                        }
+                        Command::ReverseContinue => history.oldest(),
+                        _ => match request.arguments.as_ref().and_then(|v| {
+                            serde_json::from_value::<GoToScanArguments>(v.clone()).ok()
+                        }) {
+                            Some(go_to) => history.get(go_to.scan),
+                            None => {
+                                send(
+                                    writer,
+                                    &Response::error(take_seq(seq), &request, "missing scan"),
+                                )?;
+                                continue;
+                            }
+                        },
+                    };
+                let restored = target.map_err(|e| e.to_string()).and_then(|snapshot| {
+                    snapshot
+                        .restore(&mut running)
+                        .map(|()| snapshot.time_us)
+                        .map_err(|trap| trap.to_string())
+                });
+                match restored {
+                    Ok(time_us) => {
+                        send(writer, &Response::success(take_seq(seq), &request, None))?;
+                        // Land on the first instruction of the restored scan,
+                        // replaying it on the clock it first ran with. Nothing
+                        // armed at the abandoned stop carries over.
+                        current_time_us = time_us;
+                        pending_step = None;
+                        suppress_bp = false;
+                        pending_scan_landing = true;
+                        if command == Command::GoToScan {
+                            step_reason = "goto";
+                        }
+                        phase = Phase::Running;
+                    }
+                    Err(message) => {
+                        send(writer, &Response::error(take_seq(seq), &request, message))?;
+                    }
+                }
+            }
+            Some(command @ (Command::ForceVariable | Command::UnforceVariable)) if legal_here => {
+                let forced = match command {
+                    Command::ForceVariable => force_variable(&request, debug, &mut running),
+                    _ => unforce_variable(&request, debug, &mut running),
+                };
+                match forced {
+                    Ok(()) => {
+                        send(writer, &Response::success(take_seq(seq), &request, None))?;
+                        // The values on screen may have changed under the
+                        // client; have it fetch them again.
+                        send(writer, &invalidated_event(take_seq(seq)))?;
+                    }
+                    Err(message) => {
+                        send(writer, &Response::error(take_seq(seq), &request, message))?;
+                    }
+                }
+            }
+            Some(Command::Pause) if legal_here => {
+                // Already stopped -- at a breakpoint reached before the pause
+                // could land, say. Acknowledge it; there is no new stop to
+                // report, and taking it off the queue lowered the flag.
+                send(writer, &Response::success(take_seq(seq), &request, None))?;
+            }
+            Some(Command::Restart) if legal_here => match reload(&request, args) {
+                Ok((container, args)) => {
+                    return Ok(SessionEnd::Restart {
+                        container: Box::new(container),
+                        args,
+                        request,
+                    });
+                }
+                // A restart that cannot load keeps the current run: the user
+                // can fix the build and try again without losing the session.
+                Err(message) => {
+                    send(writer, &Response::error(take_seq(seq), &request, message))?;
+                }
+            },
             _ => {
                 // Illegal in this phase or an unknown command.
                 send(
@@ -460,6 +690,87 @@ fn launched_session<R: BufRead, W: Write>(
     }
 }
 
+/// Whether the paused VM has not yet run any of its current scan: it is
+/// stopped before the first instruction of the program's entry frame, or at
+/// the boundary between two scans, where there are no frames at all.
+fn at_scan_start(running: &VmRunning) -> bool {
+    match running.debug_frames() {
+        [] => true,
+        [entry] => entry.pc == 0,
+        _ => false,
+    }
+}
+
+/// Loads the container a `restart` will run and proves it can start.
+///
+/// The container is read from disk again, so a restart after a recompile runs
+/// the new program; an unchanged file simply re-runs the init functions. The
+/// preconditions are re-checked and the VM is started once on scratch buffers,
+/// so an init function that traps is reported now, while the current run is
+/// still there to fall back to.
+fn reload(
+    request: &Request,
+    current: &LaunchRequestArguments,
+) -> Result<(Container, LaunchRequestArguments), String> {
+    let args = request
+        .arguments
+        .as_ref()
+        .and_then(|v| serde_json::from_value::<RestartArguments>(v.clone()).ok())
+        .and_then(|a| a.arguments)
+        .unwrap_or_else(|| current.clone());
+    let container = load_checked(&args)?;
+    let mut scratch = VmBuffers::from_container(&container);
+    launch::start_vm(&container, &mut scratch).map_err(|e| e.to_string())?;
+    Ok((container, args))
+}
+
+/// Applies an `ironplc/forceVariable` request to the VM's force table. The
+/// forced value is written at once, so the variables the client shows at this
+/// pause already hold it.
+fn force_variable(
+    request: &Request,
+    debug: Option<&DebugSection>,
+    running: &mut VmRunning,
+) -> Result<(), String> {
+    let args = request
+        .arguments
+        .as_ref()
+        .and_then(|v| serde_json::from_value::<ForceVariableArguments>(v.clone()).ok())
+        .ok_or_else(|| "forceVariable requires a 'name' and a 'value'".to_string())?;
+    let forced = match debug_info::resolve_force(debug, &args.name, &args.value, args.bit)? {
+        ResolvedForce::Whole { var_index, raw } => running.force_variable(var_index, raw),
+        ResolvedForce::Bit { var_index, bit, on } => running.force_bit(var_index, bit, on),
+    };
+    forced.map_err(|trap| trap.to_string())
+}
+
+/// Applies an `ironplc/unforceVariable` request. Releasing a variable that is
+/// not forced is not an error: the variable is already the program's.
+fn unforce_variable(
+    request: &Request,
+    debug: Option<&DebugSection>,
+    running: &mut VmRunning,
+) -> Result<(), String> {
+    let args = request
+        .arguments
+        .as_ref()
+        .and_then(|v| serde_json::from_value::<UnforceVariableArguments>(v.clone()).ok())
+        .ok_or_else(|| "unforceVariable requires a 'name'".to_string())?;
+    let entry = debug_info::resolve_program_variable(debug, &args.name)?;
+    running.unforce_variable(entry.var_index);
+    Ok(())
+}
+
+/// The `invalidated` event telling the client that variable values changed
+/// without the program running.
+pub(super) fn invalidated_event(seq: i64) -> Event {
+    Event::new(
+        seq,
+        "invalidated",
+        Some(serde_json::json!({ "areas": ["variables"] })),
+    )
+}
+
 /// Whether the launch's `scanLimit` bound (if any) has been reached, so the
 /// session should terminate rather than start another cycle.
 fn scan_limit_reached(scan_limit: Option<u64>, running: &VmRunning) -> bool {
@@ -467,7 +778,7 @@ fn scan_limit_reached(scan_limit: Option<u64>, running: &VmRunning) -> bool {
 }
 
 /// Builds the `stopped` event for `reason`, scoped to the single thread.
-fn stopped_event(seq: i64, reason: &'static str) -> Event {
+pub(super) fn stopped_event(seq: i64, reason: &'static str) -> Event {
     let body = serde_json::to_value(StoppedEventBody {
         reason,
         thread_id: Some(THREAD_ID),
@@ -478,65 +789,87 @@ fn stopped_event(seq: i64, reason: &'static str) -> Event {
     Event::new(seq, "stopped", body)
 }
 
-/// Applies a `setBreakpoints` request: replaces the breakpoint set and returns
-/// the response body echoing each requested breakpoint's resolved state.
+/// The client's source breakpoints, keyed by source path.
 ///
-/// DAP `setBreakpoints` carries the full set for one source, so the table is
-/// cleared and rebuilt. v1 debugs a single source, so a table-wide clear is
-/// correct. Line→location resolution is delegated to [`debug_info`], which
-/// snaps each breakpoint forward to the nearest executable line; the response
-/// echoes the *bound* line so the editor moves the marker to where the
-/// breakpoint actually took effect.
+/// DAP `setBreakpoints` carries the full set for one source only, so each
+/// request replaces its own source's entry and leaves the others alone. The
+/// requests are kept rather than the bound locations so a restarted run can
+/// bind them again against its own debug section.
+type SourceBreakpoints = BTreeMap<String, SetBreakpointsArguments>;
+
+/// Applies a `setBreakpoints` request: stores the source's breakpoints,
+/// rebuilds the table from every source, and returns the response body
+/// echoing each requested breakpoint's resolved state.
 fn set_breakpoints(
-    request: &Request,
+    args: SetBreakpointsArguments,
+    sources: &mut SourceBreakpoints,
     debug: Option<&DebugSection>,
     breakpoints: &mut BreakpointTable,
 ) -> Option<Value> {
-    let args: SetBreakpointsArguments = request
-        .arguments
-        .as_ref()
-        .and_then(|v| serde_json::from_value(v.clone()).ok())?;
+    let path = args.source.path.clone().unwrap_or_default();
+    sources.insert(path.clone(), args);
+    bind_breakpoints(sources, debug, breakpoints)
+        .remove(&path)
+        .and_then(|resolved| {
+            serde_json::to_value(SetBreakpointsResponseBody {
+                breakpoints: resolved,
+            })
+            .ok()
+        })
+}
 
+/// Clears the table and binds every source's breakpoints into it, returning
+/// each source's breakpoints as resolved.
+///
+/// Line→location resolution is delegated to [`debug_info`], which snaps each
+/// breakpoint forward to the nearest executable line; the resolved breakpoint
+/// carries the *bound* line so the editor moves the marker to where the
+/// breakpoint actually took effect.
+fn bind_breakpoints(
+    sources: &SourceBreakpoints,
+    debug: Option<&DebugSection>,
+    breakpoints: &mut BreakpointTable,
+) -> BTreeMap<String, Vec<Breakpoint>> {
     breakpoints.clear();
-    let source_path = args.source.path.clone().unwrap_or_default();
-    let source = Some(args.source.clone());
-
-    let resolved: Vec<Breakpoint> = args
-        .breakpoints
+    sources
         .iter()
-        .map(|bp| {
-            // `bp.line` is already narrowed to the container's `SourceLine` by
-            // the serde layer; `None` means the client sent a line the debug
-            // section cannot represent, which resolves to nothing.
-            let resolved = bp
-                .line
-                .and_then(|line| debug_info::resolve_breakpoint(debug, &source_path, line));
-            match resolved {
-                Some(resolved) => {
-                    for (function_id, offset) in resolved.locations {
-                        breakpoints.add(function_id, offset);
-                    }
-                    Breakpoint {
-                        verified: true,
-                        line: Some(resolved.line),
-                        source: source.clone(),
-                        message: None,
+        .map(|(path, args)| {
+            let source = Some(args.source.clone());
+            let resolved = args
+                .breakpoints
+                .iter()
+                .map(|bp| {
+                    // `bp.line` is already narrowed to the container's
+                    // `SourceLine` by the serde layer; `None` means the client
+                    // sent a line the debug section cannot represent, which
+                    // resolves to nothing.
+                    let resolved = bp
+                        .line
+                        .and_then(|line| debug_info::resolve_breakpoint(debug, path, line));
+                    match resolved {
+                        Some(resolved) => {
+                            for (function_id, offset) in resolved.locations {
+                                breakpoints.add(function_id, offset);
+                            }
+                            Breakpoint {
+                                verified: true,
+                                line: Some(resolved.line),
+                                source: source.clone(),
+                                message: None,
+                            }
+                        }
+                        None => Breakpoint {
+                            verified: false,
+                            line: bp.line,
+                            source: source.clone(),
+                            message: Some("no executable location for this line".to_string()),
+                        },
                     }
-                }
-                None => Breakpoint {
-                    verified: false,
-                    line: bp.line,
-                    source: source.clone(),
-                    message: Some("no executable location for this line".to_string()),
-                },
-            }
+                })
+                .collect();
+            (path.clone(), resolved)
         })
-        .collect();
-
-    serde_json::to_value(SetBreakpointsResponseBody {
-        breakpoints: resolved,
-    })
-    .ok()
+        .collect()
 }
 
 /// Builds the `stackTrace` response body from the paused instance's live
@@ -571,20 +904,61 @@ fn stack_trace_body(running: &VmRunning, debug: Option<&DebugSection>) -> Option
     .ok()
 }
 
-/// Builds the `Program` scope's contents: every program variable slot, rendered
-/// by [`debug_info`] with its VAR_NAME name/type and a value formatted per its
-/// IEC type tag (STRING values are read from the data region).
+/// The `variablesReference` handle for the `kind` scope of frame `frame`
+/// (outermost-first, the numbering `stackTrace` ids use).
+pub(super) fn frame_scope_ref(frame: usize, kind: ScopeKind) -> i64 {
+    FRAME_SCOPE_BASE + (frame * ScopeKind::ALL.len() + kind as usize) as i64
+}
+
+/// The frame and scope kind a [`frame_scope_ref`] handle names; `None` for any
+/// other handle.
+pub(super) fn decode_frame_scope_ref(reference: i64) -> Option<(usize, ScopeKind)> {
+    let offset = usize::try_from(reference.checked_sub(FRAME_SCOPE_BASE)?).ok()?;
+    let kinds = ScopeKind::ALL.len();
+    Some((offset / kinds, ScopeKind::ALL[offset % kinds]))
+}
+
+/// The function id that frame `frame`'s VAR_NAME entries are tagged with, or
+/// `None` when the paused instance has no such frame.
 ///
-/// The list is unfiltered — locals and globals together — which is why the scope
-/// is named `Program` rather than `Locals`. Splitting it by `var_section` into
-/// Locals / Inputs / Outputs / In-Out / Globals is the design's end state; the
-/// data for it is already in `VarNameEntry::var_section`.
-fn program_variables_body(running: &VmRunning, debug: Option<&DebugSection>) -> Option<Value> {
-    let count = running.num_variables();
-    let values: Vec<u64> = (0..count)
-        .map(|i| running.read_variable_raw(VarIndex::new(i)).unwrap_or(0))
-        .collect();
-    let variables = debug_info::render_variables(debug, &values, running.data_region());
+/// The outermost frame is the program's body, whose variables the compiler
+/// tags with [`FunctionId::GLOBAL_SCOPE`]; every deeper frame is a function or
+/// function block call whose parameters and locals carry its own id.
+fn frame_owner(running: &VmRunning, frame: usize) -> Option<FunctionId> {
+    let function_id = running.debug_frames().get(frame)?.function_id;
+    Some(match frame {
+        0 => FunctionId::GLOBAL_SCOPE,
+        _ => function_id,
+    })
+}
+
+/// Builds the contents of one frame scope: the frame's variables in that
+/// section, rendered by [`debug_info`] with their VAR_NAME name/type and a
+/// value formatted per their IEC type tag (STRING values are read from the
+/// data region). A handle this server never handed out, or one for a frame
+/// that has since returned, yields an empty list rather than a
+/// plausible-looking wrong answer.
+fn frame_variables_body(
+    running: &VmRunning,
+    debug: Option<&DebugSection>,
+    reference: i64,
+) -> Option<Value> {
+    let variables = decode_frame_scope_ref(reference)
+        .and_then(|(frame, kind)| Some((frame_owner(running, frame)?, kind)))
+        .map(|(owner, kind)| {
+            let values: Vec<u64> = (0..running.num_variables())
+                .map(|i| running.read_variable_raw(VarIndex::new(i)).unwrap_or(0))
+                .collect();
+            debug_info::render_scope(
+                debug,
+                owner,
+                kind,
+                &values,
+                running.data_region(),
+                running.forces(),
+            )
+        })
+        .unwrap_or_default();
     serde_json::to_value(VariablesResponseBody { variables }).ok()
 }
 
@@ -600,6 +974,7 @@ fn runtime_variables_body(running: &VmRunning) -> Option<Value> {
         // The VM counter is a u64; ULINT is its IEC 61131-3 spelling.
         type_name: Some("ULINT".to_string()),
         variables_reference: 0,
+        menu_context: None,
     }];
     serde_json::to_value(VariablesResponseBody { variables }).ok()
 }
@@ -611,7 +986,7 @@ mod tests {
         iec_type_tag, var_section, VarNameEntry, SOURCE_FILE_HASH_LEN,
     };
     use ironplc_container::{
-        ContainerBuilder, FuncNameEntry, FunctionId, InstanceId, LineMapEntry,
+        opcode, ContainerBuilder, FuncNameEntry, FunctionId, InstanceId, LineMapEntry,
         ProgramInstanceEntry, SourceColumn, SourceFileEntry, SourceFileId, SourceLine, TaskEntry,
         TaskId, TaskType, VarIndex,
     };
@@ -760,9 +1135,8 @@ mod tests {
         for req in requests {
             input.extend_from_slice(&frame(req));
         }
-        let mut reader = Cursor::new(input);
         let mut writer: Vec<u8> = Vec::new();
-        serve(&mut reader, &mut writer).unwrap();
+        serve(Cursor::new(input), &mut writer).unwrap();
 
         let mut out_reader = Cursor::new(writer);
         let mut messages = Vec::new();
@@ -780,8 +1154,9 @@ mod tests {
         assert_eq!(out[0]["command"], "initialize");
         assert_eq!(out[0]["success"], true);
         assert_eq!(out[0]["body"]["supportsConfigurationDoneRequest"], true);
-        // Only the one capability is advertised in this phase.
-        assert_eq!(out[0]["body"].as_object().unwrap().len(), 1);
+        // `configurationDone`, `restart`, and `stepBack`; `pause` needs no
+        // capability.
+        assert_eq!(out[0]["body"].as_object().unwrap().len(), 3);
         assert_eq!(out[1]["type"], "event");
         assert_eq!(out[1]["event"], "initialized");
     }
@@ -911,7 +1286,7 @@ mod tests {
         // A custom request in IronPLC's namespace that the server does not
         // implement (unlike `ironplc/stepScan`, which it does).
         let out = run_server(&[json!({"seq": 1, "type": "request",
-                                      "command": "ironplc/forceVariable"})]);
+                                      "command": "ironplc/evaluateScan"})]);
         assert_eq!(out[0]["success"], false);
         assert_eq!(out[0]["message"], "requestNotApplicable");
     }
@@ -1045,6 +1420,75 @@ mod tests {
         write_container_to_temp(&container)
     }
 
+    /// A single-instance container whose scan (`MAIN`, line 10) passes 21 to
+    /// `DOUBLE` (function 2, line 20). Its variables cover three owners: the
+    /// program's `x` (VAR, `var[0]`), the global `total` (VAR_GLOBAL,
+    /// `var[1]`), and `DOUBLE`'s parameter `n` (VAR_INPUT, `var[2]`).
+    fn calling_scan_container_file() -> (tempfile::NamedTempFile, String) {
+        #[rustfmt::skip]
+        let scan: Vec<u8> = vec![
+            opcode::LOAD_CONST_I32, 0x00, 0x00,   // pool[0] (21)   (offset 0, line 10)
+            opcode::CALL, 0x02, 0x00, 0x02, 0x00, // DOUBLE, args at var[2]
+            opcode::STORE_VAR_I32, 0x00, 0x00,    // var[0]
+            opcode::RET_VOID,
+        ];
+        #[rustfmt::skip]
+        let double: Vec<u8> = vec![
+            opcode::LOAD_VAR_I32, 0x02, 0x00,     // var[2]          (offset 0, line 20)
+            opcode::LOAD_VAR_I32, 0x02, 0x00,
+            opcode::ADD_I32,
+            opcode::RET,
+        ];
+        let var = |index: u16, function_id: FunctionId, section: u8, name: &str| VarNameEntry {
+            var_index: VarIndex::new(index),
+            function_id,
+            var_section: section,
+            iec_type_tag: iec_type_tag::DINT,
+            name: name.into(),
+            type_name: "DINT".into(),
+        };
+        let double_id = FunctionId::new(2);
+        let container = ContainerBuilder::new()
+            .num_variables(3)
+            .add_i32_constant(21)
+            .add_function(FunctionId::INIT, &[opcode::RET_VOID], 0, 3, 0)
+            .add_function(FunctionId::SCAN, &scan, 16, 3, 0)
+            .add_function(double_id, &double, 4, 1, 1)
+            .max_call_depth(2)
+            .add_var_name(var(0, FunctionId::GLOBAL_SCOPE, var_section::VAR, "x"))
+            .add_var_name(var(
+                1,
+                FunctionId::GLOBAL_SCOPE,
+                var_section::VAR_GLOBAL,
+                "total",
+            ))
+            .add_var_name(var(2, double_id, var_section::VAR_INPUT, "n"))
+            .add_func_name(FuncNameEntry {
+                function_id: FunctionId::SCAN,
+                name: "MAIN".into(),
+            })
+            .add_func_name(FuncNameEntry {
+                function_id: double_id,
+                name: "DOUBLE".into(),
+            })
+            .add_source_file(demo_source_file())
+            .add_line_map_entry(line_entry(FunctionId::SCAN, 0, 10))
+            .add_line_map_entry(line_entry(double_id, 0, 20))
+            .add_task(a_task(TaskId::new(0)))
+            .add_program_instance(ProgramInstanceEntry {
+                instance_id: InstanceId::new(0),
+                task_id: TaskId::new(0),
+                entry_function_id: FunctionId::SCAN,
+                var_table_offset: 0,
+                var_table_count: 3,
+                fb_instance_offset: 0,
+                fb_instance_count: 0,
+                init_function_id: FunctionId::INIT,
+            })
+            .build();
+        write_container_to_temp(&container)
+    }
+
     /// All response messages for `command`, in order.
     fn responses<'a>(out: &'a [Value], command: &str) -> Vec<&'a Value> {
         out.iter()
@@ -1104,7 +1548,7 @@ mod tests {
             json!({"seq": 7, "type": "request", "command": "scopes",
                    "arguments": {"frameId": 0}}),
             json!({"seq": 8, "type": "request", "command": "variables",
-                   "arguments": {"variablesReference": 1}}),
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
             json!({"seq": 9, "type": "request", "command": "continue",
                    "arguments": {"threadId": 1}}),
             json!({"seq": 10, "type": "request", "command": "disconnect"}),
@@ -1132,11 +1576,13 @@ mod tests {
         assert_eq!(st[0]["body"]["stackFrames"][0]["line"], 10);
         assert_eq!(st[0]["body"]["stackFrames"][0]["source"]["path"], "demo.st");
 
-        // One scope, whose handle enumerates the variables.
+        // The program frame's Locals scope, whose handle enumerates the
+        // variables.
         let sc = responses(&out, "scopes");
+        assert_eq!(sc[0]["body"]["scopes"][0]["name"], "Locals");
         assert_eq!(
             sc[0]["body"]["scopes"][0]["variablesReference"],
-            PROGRAM_REF
+            frame_scope_ref(0, ScopeKind::Locals)
         );
 
         // The single program variable is rendered with its source name and
@@ -1177,17 +1623,17 @@ mod tests {
             json!({"seq": 4, "type": "request", "command": "configurationDone"}),
             // Scan 1 paused: inspect, then continue to scan 2.
             json!({"seq": 5, "type": "request", "command": "variables",
-                   "arguments": {"variablesReference": 1}}),
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
             json!({"seq": 6, "type": "request", "command": "continue",
                    "arguments": {"threadId": 1}}),
             // Scan 2 paused: inspect, then continue to scan 3.
             json!({"seq": 7, "type": "request", "command": "variables",
-                   "arguments": {"variablesReference": 1}}),
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
             json!({"seq": 8, "type": "request", "command": "continue",
                    "arguments": {"threadId": 1}}),
             // Scan 3 paused: inspect, then tear down.
             json!({"seq": 9, "type": "request", "command": "variables",
-                   "arguments": {"variablesReference": 1}}),
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
             json!({"seq": 10, "type": "request", "command": "disconnect"}),
         ]);
 
@@ -1206,6 +1652,68 @@ mod tests {
         assert_eq!(vars[2]["body"]["variables"][0]["value"], "3");
     }
 
+    #[test]
+    fn serve_when_force_before_run_then_holds_each_scan_until_unforced() {
+        // `x` is incremented every scan. Forced to 100 before the run, it reads
+        // 100 at the top of each scan; released at the second stop, it takes
+        // the program's increment and the flag goes.
+        let (_file, path) = incrementing_scan_container_file();
+        let locals = json!({"variablesReference": frame_scope_ref(0, ScopeKind::Locals)});
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path}}),
+            json!({"seq": 3, "type": "request", "command": "setBreakpoints",
+                   "arguments": {"source": {"path": "demo.st"},
+                                 "breakpoints": [{"line": 10}]}}),
+            json!({"seq": 4, "type": "request", "command": "ironplc/forceVariable",
+                   "arguments": {"name": "X", "value": "100"}}),
+            json!({"seq": 5, "type": "request", "command": "configurationDone"}),
+            json!({"seq": 6, "type": "request", "command": "variables", "arguments": locals}),
+            json!({"seq": 7, "type": "request", "command": "continue",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 8, "type": "request", "command": "variables", "arguments": locals}),
+            json!({"seq": 9, "type": "request", "command": "ironplc/unforceVariable",
+                   "arguments": {"name": "x"}}),
+            json!({"seq": 10, "type": "request", "command": "continue",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 11, "type": "request", "command": "variables", "arguments": locals}),
+            json!({"seq": 12, "type": "request", "command": "disconnect"}),
+        ]);
+
+        assert_eq!(responses(&out, "ironplc/forceVariable")[0]["success"], true);
+        assert_eq!(events(&out, "invalidated").len(), 2);
+        let vars = responses(&out, "variables");
+        assert_eq!(vars[0]["body"]["variables"][0]["value"], "100 (forced)");
+        assert_eq!(
+            vars[0]["body"]["variables"][0]["__vscodeVariableMenuContext"],
+            "forced"
+        );
+        assert_eq!(vars[1]["body"]["variables"][0]["value"], "100 (forced)");
+        assert_eq!(vars[2]["body"]["variables"][0]["value"], "101");
+    }
+
+    #[test]
+    fn serve_when_force_names_unknown_variable_then_error_and_session_continues() {
+        let (_file, path) = incrementing_scan_container_file();
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path}}),
+            json!({"seq": 3, "type": "request", "command": "ironplc/forceVariable",
+                   "arguments": {"name": "missing", "value": "1"}}),
+            json!({"seq": 4, "type": "request", "command": "disconnect"}),
+        ]);
+
+        let force = responses(&out, "ironplc/forceVariable");
+        assert_eq!(force[0]["success"], false);
+        assert_eq!(
+            force[0]["message"],
+            "no program or global variable is named 'missing'"
+        );
+        assert_eq!(responses(&out, "disconnect")[0]["success"], true);
+    }
+
     #[test]
     fn serve_when_stop_on_entry_then_pauses_before_first_instruction() {
         // `stopOnEntry` pauses before any logic runs, so the incrementing
@@ -1217,7 +1725,7 @@ mod tests {
                    "arguments": {"program": path, "stopOnEntry": true}}),
             json!({"seq": 3, "type": "request", "command": "configurationDone"}),
             json!({"seq": 4, "type": "request", "command": "variables",
-                   "arguments": {"variablesReference": 1}}),
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
             json!({"seq": 5, "type": "request", "command": "disconnect"}),
         ]);
 
@@ -1252,7 +1760,7 @@ mod tests {
     }
 
     #[test]
-    fn serve_when_scopes_requested_then_offers_variables_and_runtime() {
+    fn serve_when_scopes_requested_then_offers_frame_scopes_and_runtime() {
         let (_file, path) = incrementing_scan_container_file();
         let out = run_server(&[
             json!({"seq": 1, "type": "request", "command": "initialize"}),
@@ -1265,9 +1773,9 @@ mod tests {
         ]);
 
         let scopes = &responses(&out, "scopes")[0]["body"]["scopes"];
-        // Not "Variables" -- that would render as Variables > Variables inside
-        // the client's variables pane.
-        assert_eq!(scopes[0]["name"], "Program");
+        // The fixture declares one VAR, so the frame has only a Locals scope.
+        assert_eq!(scopes.as_array().unwrap().len(), 2);
+        assert_eq!(scopes[0]["name"], "Locals");
         assert_eq!(scopes[1]["name"], "Runtime");
         // The two scopes must be addressable independently.
         assert_ne!(
@@ -1276,6 +1784,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serve_when_paused_in_function_then_each_frame_scopes_its_own_variables() {
+        let (_file, path) = calling_scan_container_file();
+        let frame_scopes = |frame: i64| {
+            json!({"seq": 5, "type": "request", "command": "scopes",
+                   "arguments": {"frameId": frame}})
+        };
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path, "scanLimit": 1}}),
+            json!({"seq": 3, "type": "request", "command": "setBreakpoints",
+                   "arguments": {"source": {"path": "demo.st"},
+                                 "breakpoints": [{"line": 20}]}}),
+            json!({"seq": 4, "type": "request", "command": "configurationDone"}),
+            frame_scopes(1),
+            frame_scopes(0),
+            json!({"seq": 6, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": frame_scope_ref(1, ScopeKind::Inputs)}}),
+            json!({"seq": 7, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": frame_scope_ref(1, ScopeKind::Globals)}}),
+            json!({"seq": 8, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
+            json!({"seq": 9, "type": "request", "command": "disconnect"}),
+        ]);
+
+        // The callee's frame (innermost, id 1) shows its parameter and the
+        // globals, not the program's locals.
+        let scopes = responses(&out, "scopes");
+        assert_eq!(scopes[0]["body"]["scopes"][0]["name"], "Inputs");
+        assert_eq!(scopes[0]["body"]["scopes"][1]["name"], "Globals");
+        assert_eq!(scopes[0]["body"]["scopes"][2]["name"], "Runtime");
+        // The program's frame shows its locals and the globals.
+        assert_eq!(scopes[1]["body"]["scopes"][0]["name"], "Locals");
+        assert_eq!(scopes[1]["body"]["scopes"][1]["name"], "Globals");
+
+        let vars = responses(&out, "variables");
+        assert_eq!(vars[0]["body"]["variables"][0]["name"], "n");
+        assert_eq!(vars[0]["body"]["variables"][0]["value"], "21");
+        assert_eq!(vars[0]["body"]["variables"].as_array().unwrap().len(), 1);
+        assert_eq!(vars[1]["body"]["variables"][0]["name"], "total");
+        assert_eq!(vars[2]["body"]["variables"][0]["name"], "x");
+        assert_eq!(vars[2]["body"]["variables"].as_array().unwrap().len(), 1);
+    }
+
     #[test]
     fn serve_when_runtime_scope_expanded_then_shows_scan_count_advancing() {
         // The Runtime scope is what replaces the old "show scan count" button:
@@ -1363,6 +1916,39 @@ mod tests {
         assert_eq!(bps[2]["line"], 10);
     }
 
+    #[test]
+    fn serve_when_setbreakpoints_for_second_source_then_keeps_first_source_breakpoints() {
+        // Each `setBreakpoints` carries one source's set; a later request for
+        // another source must not drop the breakpoints already bound.
+        let (_file, path) = scan_container_file(&[0x8C], 0, &[(0, 10)]);
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path, "scanLimit": 1}}),
+            json!({"seq": 3, "type": "request", "command": "setBreakpoints",
+                   "arguments": {"source": {"path": "demo.st"},
+                                 "breakpoints": [{"line": 10}]}}),
+            json!({"seq": 4, "type": "request", "command": "setBreakpoints",
+                   "arguments": {"source": {"path": "other.st"},
+                                 "breakpoints": [{"line": 3}]}}),
+            json!({"seq": 5, "type": "request", "command": "configurationDone"}),
+            json!({"seq": 6, "type": "request", "command": "continue",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 7, "type": "request", "command": "disconnect"}),
+        ]);
+        let sbp = responses(&out, "setBreakpoints");
+        // Each response echoes only its own source.
+        assert_eq!(sbp[1]["body"]["breakpoints"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            sbp[1]["body"]["breakpoints"][0]["source"]["path"],
+            "other.st"
+        );
+        // The first source's breakpoint still fires.
+        let stopped = events(&out, "stopped");
+        assert_eq!(stopped.len(), 1);
+        assert_eq!(stopped[0]["body"]["reason"], "breakpoint");
+    }
+
     /// A scan of four single-byte-operand statements at the same call depth,
     /// then `RET_VOID`, needing no constant pool: `LOAD_VAR var[0]; STORE_VAR
     /// var[0]; LOAD_VAR var[0]; STORE_VAR var[0]; RET_VOID`. Statement starts
@@ -1485,7 +2071,7 @@ mod tests {
             json!({"seq": 5, "type": "request", "command": "stackTrace",
                    "arguments": {"threadId": 1}}),
             json!({"seq": 6, "type": "request", "command": "variables",
-                   "arguments": {"variablesReference": 1}}),
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
             json!({"seq": 7, "type": "request", "command": "variables",
                    "arguments": {"variablesReference": 2}}),
             json!({"seq": 8, "type": "request", "command": "disconnect"}),
@@ -1635,4 +2221,319 @@ mod tests {
         assert_eq!(stopped[0]["body"]["reason"], "entry");
         assert_eq!(events(&out, "terminated").len(), 1);
     }
+
+    #[test]
+    fn serve_when_pause_while_free_running_then_answers_pause_before_stopped_event() {
+        // No breakpoint and no `scanLimit`: only the pause can stop this run.
+        let (_file, path) = incrementing_scan_container_file();
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path}}),
+            json!({"seq": 3, "type": "request", "command": "configurationDone"}),
+            json!({"seq": 4, "type": "request", "command": "pause",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 5, "type": "request", "command": "stackTrace",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 6, "type": "request", "command": "disconnect"}),
+        ]);
+
+        assert_eq!(responses(&out, "pause")[0]["success"], true);
+        let stopped = events(&out, "stopped");
+        assert_eq!(stopped.len(), 1);
+        assert_eq!(stopped[0]["body"]["reason"], "pause");
+        // Paused mid-scan, so there is a frame to show.
+        assert_eq!(
+            responses(&out, "stackTrace")[0]["body"]["stackFrames"][0]["name"],
+            "MAIN"
+        );
+
+        let pause_at = index_of(&out, |m| m["command"] == "pause");
+        let stopped_at = index_of(&out, |m| m["event"] == "stopped");
+        assert!(pause_at < stopped_at);
+    }
+
+    #[test]
+    fn serve_when_pause_while_already_paused_then_succeeds_without_new_stop() {
+        let (_file, path) = incrementing_scan_container_file();
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path, "stopOnEntry": true}}),
+            json!({"seq": 3, "type": "request", "command": "configurationDone"}),
+            json!({"seq": 4, "type": "request", "command": "pause",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 5, "type": "request", "command": "disconnect"}),
+        ]);
+
+        // The entry stop lands first; the pause is acknowledged at it.
+        assert_eq!(responses(&out, "pause")[0]["success"], true);
+        let stopped = events(&out, "stopped");
+        assert_eq!(stopped.len(), 1);
+        assert_eq!(stopped[0]["body"]["reason"], "entry");
+    }
+
+    #[test]
+    fn serve_when_restart_at_breakpoint_then_reruns_init_and_keeps_breakpoints() {
+        let (_file, path) = incrementing_scan_container_file();
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path}}),
+            json!({"seq": 3, "type": "request", "command": "setBreakpoints",
+                   "arguments": {"source": {"path": "demo.st"},
+                                 "breakpoints": [{"line": 11}]}}),
+            json!({"seq": 4, "type": "request", "command": "configurationDone"}),
+            json!({"seq": 5, "type": "request", "command": "continue",
+                   "arguments": {"threadId": 1}}),
+            // Scan 2 paused with x = 2: restart from scratch.
+            json!({"seq": 6, "type": "request", "command": "restart"}),
+            json!({"seq": 7, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
+            json!({"seq": 8, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": 2}}),
+            json!({"seq": 9, "type": "request", "command": "disconnect"}),
+        ]);
+
+        assert_eq!(responses(&out, "restart")[0]["success"], true);
+        // Two stops before the restart, and the breakpoint fires again after.
+        let stopped = events(&out, "stopped");
+        assert_eq!(stopped.len(), 3);
+        assert_eq!(stopped[2]["body"]["reason"], "breakpoint");
+        // The restarted run is back in its first scan.
+        assert_eq!(
+            responses(&out, "variables")[0]["body"]["variables"][0]["value"],
+            "1"
+        );
+        assert_eq!(
+            responses(&out, "variables")[1]["body"]["variables"][0]["value"],
+            "0"
+        );
+    }
+
+    #[test]
+    fn serve_when_restart_after_termination_then_runs_again() {
+        let (_file, path) = incrementing_scan_container_file();
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path, "scanLimit": 1}}),
+            json!({"seq": 3, "type": "request", "command": "configurationDone"}),
+            json!({"seq": 4, "type": "request", "command": "restart"}),
+            json!({"seq": 5, "type": "request", "command": "disconnect"}),
+        ]);
+
+        assert_eq!(responses(&out, "restart")[0]["success"], true);
+        assert_eq!(events(&out, "terminated").len(), 2);
+    }
+
+    #[test]
+    fn serve_when_restart_program_missing_then_error_and_session_continues() {
+        let (_file, path) = incrementing_scan_container_file();
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path, "stopOnEntry": true}}),
+            json!({"seq": 3, "type": "request", "command": "configurationDone"}),
+            json!({"seq": 4, "type": "request", "command": "restart",
+                   "arguments": {"arguments": {"program": "/no/such/program.iplc"}}}),
+            json!({"seq": 5, "type": "request", "command": "stackTrace",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 6, "type": "request", "command": "disconnect"}),
+        ]);
+
+        assert_eq!(responses(&out, "restart")[0]["success"], false);
+        // Still paused at entry in the original run.
+        assert_eq!(responses(&out, "stackTrace")[0]["success"], true);
+        assert_eq!(events(&out, "stopped").len(), 1);
+    }
+
+    #[test]
+    fn serve_when_restart_before_configuration_done_then_request_not_applicable() {
+        let (_file, path) = incrementing_scan_container_file();
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path}}),
+            json!({"seq": 3, "type": "request", "command": "restart"}),
+            json!({"seq": 4, "type": "request", "command": "disconnect"}),
+        ]);
+
+        let restart = responses(&out, "restart");
+        assert_eq!(restart[0]["success"], false);
+        assert_eq!(restart[0]["message"], "requestNotApplicable");
+    }
+
+    /// The requests that pause `incrementing_scan_container_file` at its
+    /// line-11 breakpoint in scans 0, 1, and 2, where `x` reads 1, 2, and 3.
+    fn three_scans_at_breakpoint(path: &str) -> Vec<Value> {
+        vec![
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path}}),
+            json!({"seq": 3, "type": "request", "command": "setBreakpoints",
+                   "arguments": {"source": {"path": "demo.st"},
+                                 "breakpoints": [{"line": 11}]}}),
+            json!({"seq": 4, "type": "request", "command": "configurationDone"}),
+            json!({"seq": 5, "type": "request", "command": "continue",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 6, "type": "request", "command": "continue",
+                   "arguments": {"threadId": 1}}),
+        ]
+    }
+
+    #[test]
+    fn serve_when_step_back_mid_scan_then_lands_at_start_of_current_scan_then_previous() {
+        let (_file, path) = incrementing_scan_container_file();
+        let mut requests = three_scans_at_breakpoint(&path);
+        requests.extend([
+            json!({"seq": 7, "type": "request", "command": "stepBack",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 8, "type": "request", "command": "stackTrace",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 9, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
+            json!({"seq": 10, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": 2}}),
+            json!({"seq": 11, "type": "request", "command": "stepBack",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 12, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
+            json!({"seq": 13, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": 2}}),
+            json!({"seq": 14, "type": "request", "command": "disconnect"}),
+        ]);
+        let out = run_server(&requests);
+
+        let step_back = responses(&out, "stepBack");
+        assert_eq!(step_back[0]["success"], true);
+        assert_eq!(step_back[1]["success"], true);
+        let stopped = events(&out, "stopped");
+        assert_eq!(stopped.len(), 5);
+        assert_eq!(stopped[3]["body"]["reason"], "step");
+        // From the breakpoint part-way through scan 2, stopped before the
+        // first instruction of scan 2 ...
+        assert_eq!(
+            responses(&out, "stackTrace")[0]["body"]["stackFrames"][0]["line"],
+            10
+        );
+        // ... with the value scan 1 left behind.
+        let vars = responses(&out, "variables");
+        assert_eq!(vars[0]["body"]["variables"][0]["value"], "2");
+        assert_eq!(vars[1]["body"]["variables"][0]["value"], "2");
+        // From the start of scan 2, a second step back goes to scan 1.
+        assert_eq!(vars[2]["body"]["variables"][0]["value"], "1");
+        assert_eq!(vars[3]["body"]["variables"][0]["value"], "1");
+    }
+
+    #[test]
+    fn serve_when_continue_after_step_back_then_runs_forward_from_restored_state() {
+        let (_file, path) = incrementing_scan_container_file();
+        let mut requests = three_scans_at_breakpoint(&path);
+        requests.extend([
+            json!({"seq": 7, "type": "request", "command": "stepBack",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 8, "type": "request", "command": "continue",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 9, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
+            json!({"seq": 10, "type": "request", "command": "disconnect"}),
+        ]);
+        let out = run_server(&requests);
+
+        // Scan 2 again, at its breakpoint: the same value it had first time.
+        let stopped = events(&out, "stopped");
+        assert_eq!(stopped[4]["body"]["reason"], "breakpoint");
+        assert_eq!(
+            responses(&out, "variables")[0]["body"]["variables"][0]["value"],
+            "3"
+        );
+    }
+
+    #[test]
+    fn serve_when_reverse_continue_then_lands_at_oldest_scan_in_history() {
+        let (_file, path) = incrementing_scan_container_file();
+        let mut requests = three_scans_at_breakpoint(&path);
+        requests.extend([
+            json!({"seq": 7, "type": "request", "command": "reverseContinue",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 8, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
+            json!({"seq": 9, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": 2}}),
+            json!({"seq": 10, "type": "request", "command": "disconnect"}),
+        ]);
+        let out = run_server(&requests);
+
+        assert_eq!(responses(&out, "reverseContinue")[0]["success"], true);
+        // The state the init functions left, before any scan ran.
+        let vars = responses(&out, "variables");
+        assert_eq!(vars[0]["body"]["variables"][0]["value"], "0");
+        assert_eq!(vars[1]["body"]["variables"][0]["value"], "0");
+    }
+
+    #[test]
+    fn serve_when_go_to_scan_then_stops_with_goto_reason_at_that_scan() {
+        let (_file, path) = incrementing_scan_container_file();
+        let mut requests = three_scans_at_breakpoint(&path);
+        requests.extend([
+            json!({"seq": 7, "type": "request", "command": "ironplc/goToScan",
+                   "arguments": {"scan": 2}}),
+            json!({"seq": 8, "type": "request", "command": "variables",
+                   "arguments": {"variablesReference": frame_scope_ref(0, ScopeKind::Locals)}}),
+            json!({"seq": 9, "type": "request", "command": "disconnect"}),
+        ]);
+        let out = run_server(&requests);
+
+        assert_eq!(responses(&out, "ironplc/goToScan")[0]["success"], true);
+        let stopped = events(&out, "stopped");
+        assert_eq!(stopped[3]["body"]["reason"], "goto");
+        assert_eq!(
+            responses(&out, "variables")[0]["body"]["variables"][0]["value"],
+            "2"
+        );
+    }
+
+    #[test]
+    fn serve_when_go_to_scan_not_in_history_then_error_names_the_range() {
+        let (_file, path) = incrementing_scan_container_file();
+        let mut requests = three_scans_at_breakpoint(&path);
+        requests.extend([
+            json!({"seq": 7, "type": "request", "command": "ironplc/goToScan",
+                   "arguments": {"scan": 9}}),
+            json!({"seq": 8, "type": "request", "command": "disconnect"}),
+        ]);
+        let out = run_server(&requests);
+
+        let go_to = responses(&out, "ironplc/goToScan");
+        assert_eq!(go_to[0]["success"], false);
+        assert_eq!(
+            go_to[0]["message"],
+            "scan 9 is not in the scan history, which holds scans 0 to 2"
+        );
+        // Still paused where it was.
+        assert_eq!(events(&out, "stopped").len(), 3);
+    }
+
+    #[test]
+    fn serve_when_scan_history_off_then_step_back_errors() {
+        let (_file, path) = incrementing_scan_container_file();
+        let out = run_server(&[
+            json!({"seq": 1, "type": "request", "command": "initialize"}),
+            json!({"seq": 2, "type": "request", "command": "launch",
+                   "arguments": {"program": path, "stopOnEntry": true,
+                                 "scanHistory": 0}}),
+            json!({"seq": 3, "type": "request", "command": "configurationDone"}),
+            json!({"seq": 4, "type": "request", "command": "stepBack",
+                   "arguments": {"threadId": 1}}),
+            json!({"seq": 5, "type": "request", "command": "disconnect"}),
+        ]);
+
+        let step_back = responses(&out, "stepBack");
+        assert_eq!(step_back[0]["success"], false);
+        assert_eq!(
+            step_back[0]["message"],
+            "scan history is off; set scanHistory in the launch configuration"
+        );
+    }
 }
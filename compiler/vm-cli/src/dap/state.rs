@@ -1,7 +1,8 @@
 //! DAP request legality per VM phase.
 //!
-//! The v1 server is a single-threaded state machine (see
-//! `specs/plans/2026-06-25-dap-server-scaffold.md`). This module owns the
+//! The server is a state machine driven by one loop thread (see
+//! `specs/plans/2026-06-25-dap-server-scaffold.md`); a reader thread only
+//! queues requests and raises the pause flag. This module owns the
 //! *legality table* only — a pure `legal(phase, command)` predicate. A request
 //! that is illegal in the current phase short-circuits to a DAP error response
 //! with the message `requestNotApplicable`, without touching the VM. The phase
@@ -19,11 +20,12 @@
 /// - [`Configuring`](Phase::Configuring): initialized; the client is setting
 ///   breakpoints / launching before `configurationDone`.
 /// - [`Running`](Phase::Running): the VM is executing under `run_round_debug`.
-///   The single-threaded loop services no requests here — it reads the next
-///   request only at a natural stop — so every request is illegal in this
-///   phase except the always-legal `disconnect`.
-/// - [`Paused`](Phase::Paused): stopped at a breakpoint, step landing, or entry
-///   (non-terminal); inspection and execution control are accepted.
+///   The loop services no requests here — it reads the next request only at a
+///   natural stop — so every request is illegal in this phase except the
+///   always-legal `disconnect` and `pause`, which the reader thread turns into
+///   a stop at the next instruction.
+/// - [`Paused`](Phase::Paused): stopped at a breakpoint, step landing, entry,
+///   or an interactive pause (non-terminal); inspection and execution control are accepted.
 /// - [`Terminated`](Phase::Terminated): the program ran to completion; only
 ///   `disconnect` remains.
 /// - [`Faulted`](Phase::Faulted): stopped on a trap (terminal pause). Inspection
@@ -40,15 +42,17 @@ pub enum Phase {
 
 /// A DAP request the v1 server recognises.
 ///
-/// This includes requests that are always refused ([`Pause`](Command::Pause),
-/// [`SetVariable`](Command::SetVariable), [`Evaluate`](Command::Evaluate),
-/// [`Restart`](Command::Restart)): modelling them explicitly lets the legality
-/// table return the documented `requestNotApplicable` for a *known-but-cut*
-/// request, distinct from an entirely unknown command.
+/// This includes requests that are always refused
+/// ([`SetVariable`](Command::SetVariable), [`Evaluate`](Command::Evaluate)):
+/// modelling them explicitly lets the legality table return the documented
+/// `requestNotApplicable` for a *known-but-cut* request, distinct from an
+/// entirely unknown command.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Command {
     Initialize,
     Launch,
+    /// Attach to a program `ironplcvm run --monitor` is already running.
+    Attach,
     SetBreakpoints,
     ConfigurationDone,
     Threads,
@@ -62,12 +66,28 @@ pub enum Command {
     /// The `ironplc/stepScan` custom request: run the rest of the current scan
     /// cycle and stop at the start of the next.
     StepScan,
+    /// Go back to the start of the previous scan cycle in the scan history.
+    StepBack,
+    /// Go back to the start of the oldest scan cycle in the scan history.
+    ReverseContinue,
+    /// The `ironplc/goToScan` custom request: go to the start of a given scan
+    /// cycle in the scan history.
+    GoToScan,
+    /// The `ironplc/forceVariable` custom request: hold a program or global
+    /// variable (or one bit of it) at a value the program cannot override.
+    ForceVariable,
+    /// The `ironplc/unforceVariable` custom request: hand a forced variable
+    /// back to the program.
+    UnforceVariable,
     Disconnect,
-    // Known DAP requests deliberately unsupported in v1: always illegal.
+    /// Interrupt a free-running program at its next instruction.
     Pause,
+    /// Reload the container and re-run its init functions, keeping the
+    /// session (and its breakpoints) open.
+    Restart,
+    // Known DAP requests deliberately unsupported in v1: always illegal.
     SetVariable,
     Evaluate,
-    Restart,
 }
 
 impl Command {
@@ -79,6 +99,7 @@ impl Command {
         let cmd = match command {
             "initialize" => Command::Initialize,
             "launch" => Command::Launch,
+            "attach" => Command::Attach,
             "setBreakpoints" => Command::SetBreakpoints,
             "configurationDone" => Command::ConfigurationDone,
             "threads" => Command::Threads,
@@ -90,6 +111,11 @@ impl Command {
             "stepIn" => Command::StepIn,
             "stepOut" => Command::StepOut,
             "ironplc/stepScan" => Command::StepScan,
+            "stepBack" => Command::StepBack,
+            "reverseContinue" => Command::ReverseContinue,
+            "ironplc/goToScan" => Command::GoToScan,
+            "ironplc/forceVariable" => Command::ForceVariable,
+            "ironplc/unforceVariable" => Command::UnforceVariable,
             "disconnect" => Command::Disconnect,
             "pause" => Command::Pause,
             "setVariable" => Command::SetVariable,
@@ -109,20 +135,30 @@ pub fn legal(phase: Phase, command: Command) -> bool {
     match command {
         // Handshake.
         Initialize => phase == Initialized,
-        Launch | ConfigurationDone => phase == Configuring,
-        // Breakpoints can be (re)set before the run and at any live pause.
-        SetBreakpoints => matches!(phase, Configuring | Paused),
+        Launch | Attach | ConfigurationDone => phase == Configuring,
+        // Breakpoints can be (re)set before the run and at any live pause, and
+        // so can forces: a force set before `configurationDone` holds from
+        // the first scan.
+        SetBreakpoints | ForceVariable | UnforceVariable => matches!(phase, Configuring | Paused),
         // Inspection: at any pause, including the terminal trap pause. The scan
         // count is inspected through the `Runtime` scope, so it needs no
         // request of its own — `scopes`/`variables` already carry it.
         Threads | StackTrace | Scopes | Variables => matches!(phase, Paused | Faulted),
         // Execution control: only at a non-terminal pause. Scan stepping is
-        // execution control like the rest — it just measures in cycles.
-        Continue | Next | StepIn | StepOut | StepScan => phase == Paused,
+        // execution control like the rest — it just measures in cycles, and so
+        // does travelling back through the scan history.
+        Continue | Next | StepIn | StepOut | StepScan | StepBack | ReverseContinue | GoToScan => {
+            phase == Paused
+        }
         // Teardown is always accepted.
         Disconnect => true,
+        // A pause interrupts a run; at a pause it is an acknowledged no-op.
+        Pause => matches!(phase, Running | Paused),
+        // Restart from any stop, including the terminal ones: the point is to
+        // run the (possibly recompiled) program again without a new session.
+        Restart => matches!(phase, Paused | Terminated | Faulted),
         // Cut from v1: refused in every phase.
-        Pause | SetVariable | Evaluate | Restart => false,
+        SetVariable | Evaluate => false,
     }
 }
 
@@ -139,9 +175,10 @@ mod tests {
         Phase::Faulted,
     ];
 
-    const ALL_COMMANDS: [Command; 18] = [
+    const ALL_COMMANDS: [Command; 24] = [
         Command::Initialize,
         Command::Launch,
+        Command::Attach,
         Command::SetBreakpoints,
         Command::ConfigurationDone,
         Command::Threads,
@@ -153,11 +190,16 @@ mod tests {
         Command::StepIn,
         Command::StepOut,
         Command::StepScan,
+        Command::StepBack,
+        Command::ReverseContinue,
+        Command::GoToScan,
+        Command::ForceVariable,
+        Command::UnforceVariable,
         Command::Disconnect,
         Command::Pause,
+        Command::Restart,
         Command::SetVariable,
         Command::Evaluate,
-        Command::Restart,
     ];
 
     /// The single source of truth the table is checked against: the exact set
@@ -168,11 +210,12 @@ mod tests {
         use Phase::*;
         match command {
             Initialize => &[Initialized],
-            Launch => &[Configuring],
+            Launch | Attach => &[Configuring],
             ConfigurationDone => &[Configuring],
-            SetBreakpoints => &[Configuring, Paused],
+            SetBreakpoints | ForceVariable | UnforceVariable => &[Configuring, Paused],
             Threads | StackTrace | Scopes | Variables => &[Paused, Faulted],
-            Continue | Next | StepIn | StepOut | StepScan => &[Paused],
+            Continue | Next | StepIn | StepOut | StepScan | StepBack | ReverseContinue
+            | GoToScan => &[Paused],
             Disconnect => &[
                 Initialized,
                 Configuring,
@@ -181,7 +224,9 @@ mod tests {
                 Terminated,
                 Faulted,
             ],
-            Pause | SetVariable | Evaluate | Restart => &[],
+            Pause => &[Running, Paused],
+            Restart => &[Paused, Terminated, Faulted],
+            SetVariable | Evaluate => &[],
         }
     }
 
@@ -202,12 +247,7 @@ mod tests {
 
     #[test]
     fn legal_when_command_is_cut_from_v1_then_illegal_in_every_phase() {
-        for command in [
-            Command::Pause,
-            Command::SetVariable,
-            Command::Evaluate,
-            Command::Restart,
-        ] {
+        for command in [Command::SetVariable, Command::Evaluate] {
             for &phase in &ALL_PHASES {
                 assert!(
                     !legal(phase, command),
@@ -225,15 +265,27 @@ mod tests {
     }
 
     #[test]
-    fn legal_when_running_then_only_disconnect_is_accepted() {
-        // The single-threaded loop never services a request mid-run except the
-        // teardown; the table encodes that invariant.
+    fn legal_when_running_then_only_disconnect_and_pause_are_accepted() {
+        // The loop never services a request mid-run except the teardown and
+        // the interrupt; the table encodes that invariant.
         for &command in &ALL_COMMANDS {
             let accepted = legal(Phase::Running, command);
-            assert_eq!(accepted, command == Command::Disconnect);
+            assert_eq!(
+                accepted,
+                matches!(command, Command::Disconnect | Command::Pause)
+            );
         }
     }
 
+    #[test]
+    fn legal_when_restart_then_accepted_at_every_stop_but_not_before_launch() {
+        assert!(legal(Phase::Paused, Command::Restart));
+        assert!(legal(Phase::Terminated, Command::Restart));
+        assert!(legal(Phase::Faulted, Command::Restart));
+        assert!(!legal(Phase::Configuring, Command::Restart));
+        assert!(!legal(Phase::Running, Command::Restart));
+    }
+
     #[test]
     fn legal_when_faulted_then_inspection_yes_but_execution_control_no() {
         for command in [
@@ -250,6 +302,9 @@ mod tests {
             Command::StepIn,
             Command::StepOut,
             Command::StepScan,
+            Command::StepBack,
+            Command::ReverseContinue,
+            Command::GoToScan,
         ] {
             assert!(!legal(Phase::Faulted, command));
         }
@@ -262,21 +317,41 @@ mod tests {
             Some(Command::ConfigurationDone)
         );
         assert_eq!(Command::from_request("stepIn"), Some(Command::StepIn));
-        // The one custom (non-DAP-standard) request the server models.
+        assert_eq!(Command::from_request("attach"), Some(Command::Attach));
+        // The custom (non-DAP-standard) requests the server models.
         assert_eq!(
             Command::from_request("ironplc/stepScan"),
             Some(Command::StepScan)
         );
+        assert_eq!(
+            Command::from_request("ironplc/goToScan"),
+            Some(Command::GoToScan)
+        );
+        assert_eq!(
+            Command::from_request("ironplc/forceVariable"),
+            Some(Command::ForceVariable)
+        );
+        assert_eq!(
+            Command::from_request("ironplc/unforceVariable"),
+            Some(Command::UnforceVariable)
+        );
+        assert_eq!(Command::from_request("stepBack"), Some(Command::StepBack));
+        assert_eq!(
+            Command::from_request("reverseContinue"),
+            Some(Command::ReverseContinue)
+        );
+        assert_eq!(Command::from_request("pause"), Some(Command::Pause));
+        assert_eq!(Command::from_request("restart"), Some(Command::Restart));
         // Known-but-cut requests still map, so the caller can answer
         // requestNotApplicable rather than "unknown command".
-        assert_eq!(Command::from_request("pause"), Some(Command::Pause));
+        assert_eq!(Command::from_request("evaluate"), Some(Command::Evaluate));
     }
 
     #[test]
     fn from_request_when_unknown_command_then_none() {
         // A custom request in IronPLC's namespace that the server does not
         // implement is as unknown as any other.
-        assert_eq!(Command::from_request("ironplc/forceVariable"), None);
+        assert_eq!(Command::from_request("ironplc/evaluateScan"), None);
         assert_eq!(Command::from_request("stepScan"), None);
         assert_eq!(Command::from_request(""), None);
     }
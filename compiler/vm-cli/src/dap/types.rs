@@ -186,15 +186,18 @@ pub struct InitializeRequestArguments {
 
 /// Capabilities advertised in the `initialize` response.
 ///
-/// The v1 server advertises exactly one: it handles `configurationDone`. Every
-/// optional capability (`supportsLogPoints`, `supportsConditionalBreakpoints`,
-/// `supportsEvaluateForHovers`, `supportsSetVariable`,
-/// `supportsStepInTargetsRequest`, …) is off, so it is simply omitted from the
-/// serialized body.
+/// The server advertises that it handles `configurationDone`, `restart`, and
+/// the reverse requests (`stepBack`, `reverseContinue`). Every other optional capability (`supportsLogPoints`,
+/// `supportsConditionalBreakpoints`, `supportsEvaluateForHovers`,
+/// `supportsSetVariable`, `supportsStepInTargetsRequest`, …) is off, so it is
+/// simply omitted from the serialized body. `pause` needs no capability: DAP
+/// clients always offer it.
 #[derive(Debug, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Capabilities {
     pub supports_configuration_done_request: bool,
+    pub supports_restart_request: bool,
+    pub supports_step_back: bool,
 }
 
 // ---------------------------------------------------------------------------
@@ -202,7 +205,7 @@ pub struct Capabilities {
 // ---------------------------------------------------------------------------
 
 /// Arguments to `launch`: the container to debug plus optional run bounds.
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LaunchRequestArguments {
     /// Path to the compiled `.iplc` container to debug.
@@ -210,10 +213,72 @@ pub struct LaunchRequestArguments {
     /// Pause on entry before executing the first instruction.
     #[serde(default)]
     pub stop_on_entry: bool,
-    /// Upper bound on scan cycles, to bound a runaway program (the
-    /// single-threaded loop has no interactive `pause`).
+    /// Upper bound on scan cycles, to bound a runaway program without
+    /// having to `pause` it.
     #[serde(default)]
     pub scan_limit: Option<u64>,
+    /// How many past scans to keep snapshots of for stepping backwards.
+    /// `None` keeps the server's default; `0` turns history off.
+    #[serde(default)]
+    pub scan_history: Option<usize>,
+}
+
+// ---------------------------------------------------------------------------
+// attach
+// ---------------------------------------------------------------------------
+
+/// Arguments to `attach`: where an `ironplcvm run --monitor` process listens.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AttachRequestArguments {
+    /// The monitor address, for example `127.0.0.1:7878`.
+    pub monitor: String,
+}
+
+// ---------------------------------------------------------------------------
+// restart
+// ---------------------------------------------------------------------------
+
+/// Arguments to `restart`. A client may send the (possibly edited) launch
+/// configuration to restart with; without one the session restarts with the
+/// arguments it was launched with.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestartArguments {
+    #[serde(default)]
+    pub arguments: Option<LaunchRequestArguments>,
+}
+
+// ---------------------------------------------------------------------------
+// ironplc/goToScan
+// ---------------------------------------------------------------------------
+
+/// Arguments to `ironplc/goToScan`: the scan to go back to, counted the way the
+/// `Runtime` scope's `scanCount` counts, so scan `n` starts once `n` scans have
+/// completed.
+#[derive(Debug, Deserialize)]
+pub struct GoToScanArguments {
+    pub scan: u64,
+}
+
+// ---------------------------------------------------------------------------
+// ironplc/forceVariable / ironplc/unforceVariable
+// ---------------------------------------------------------------------------
+
+/// Arguments to `ironplc/forceVariable`: a program or global variable and the
+/// IEC 61131-3 literal to hold it at, or with `bit` set, one bit of a bit
+/// string to hold at `TRUE` or `FALSE`.
+#[derive(Debug, Deserialize)]
+pub struct ForceVariableArguments {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub bit: Option<u8>,
+}
+
+/// Arguments to `ironplc/unforceVariable`: the variable to release.
+#[derive(Debug, Deserialize)]
+pub struct UnforceVariableArguments {
+    pub name: String,
 }
 
 // ---------------------------------------------------------------------------
@@ -232,7 +297,7 @@ pub struct Source {
 /// A breakpoint the client wants set, at a source line. `logMessage` (which
 /// would make this a logpoint) is deliberately not modelled — logpoints are
 /// deferred out of the first phase.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct SourceBreakpoint {
     /// The requested line, narrowed to the container's representation.
     /// `None` when the client sent a value the debug section cannot hold —
@@ -244,7 +309,7 @@ pub struct SourceBreakpoint {
 }
 
 /// Arguments to `setBreakpoints`: replace all breakpoints in one `source`.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct SetBreakpointsArguments {
     pub source: Source,
     #[serde(default)]
@@ -362,7 +427,7 @@ pub struct VariablesArguments {
 }
 
 /// One variable's rendered name/value.
-#[derive(Debug, Serialize)]
+#[derive(Debug, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Variable {
     pub name: String,
@@ -371,8 +436,30 @@ pub struct Variable {
     pub type_name: Option<String>,
     /// Non-zero when the variable is itself structured and can be expanded.
     pub variables_reference: i64,
+    /// VS Code's context key for the variable's menu: `forced` lets the
+    /// extension offer "Unforce" on exactly the forced variables.
+    #[serde(
+        rename = "__vscodeVariableMenuContext",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub menu_context: Option<&'static str>,
+}
+
+impl Variable {
+    /// Flags the variable as forced: the value says so in every client, and
+    /// VS Code gets the `forced` menu context.
+    pub fn forced(self) -> Variable {
+        Variable {
+            value: format!("{} (forced)", self.value),
+            menu_context: Some(FORCED_MENU_CONTEXT),
+            ..self
+        }
+    }
 }
 
+/// The [`Variable::menu_context`] of a forced variable.
+const FORCED_MENU_CONTEXT: &str = "forced";
+
 /// Body of the `variables` response.
 #[derive(Debug, Serialize)]
 pub struct VariablesResponseBody {
@@ -418,7 +505,7 @@ pub struct DisconnectArguments {
 // ---------------------------------------------------------------------------
 
 /// Body of a `stopped` event. `reason` is one of `"breakpoint"`, `"step"`,
-/// `"entry"`, or `"exception"` (a trap).
+/// `"entry"`, `"pause"`, or `"exception"` (a trap).
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StoppedEventBody {
@@ -506,14 +593,31 @@ mod tests {
     }
 
     #[test]
-    fn capabilities_when_serialized_then_only_advertises_configuration_done() {
+    fn capabilities_when_serialized_then_advertises_configuration_done_restart_and_step_back() {
         let caps = Capabilities {
             supports_configuration_done_request: true,
+            supports_restart_request: true,
+            supports_step_back: true,
         };
         let value = serde_json::to_value(&caps).unwrap();
         assert_eq!(value["supportsConfigurationDoneRequest"], true);
-        // Nothing else is advertised in the first phase.
-        assert_eq!(value.as_object().unwrap().len(), 1);
+        assert_eq!(value["supportsRestartRequest"], true);
+        assert_eq!(value["supportsStepBack"], true);
+        assert_eq!(value.as_object().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn restart_arguments_when_launch_configuration_given_then_carries_it() {
+        let args: RestartArguments = serde_json::from_value(
+            json!({ "arguments": { "program": "demo.iplc", "stopOnEntry": true } }),
+        )
+        .unwrap();
+        let launch = args.arguments.unwrap();
+        assert_eq!(launch.program, "demo.iplc");
+        assert!(launch.stop_on_entry);
+        // A bare restart carries nothing and reuses the launch arguments.
+        let bare: RestartArguments = serde_json::from_value(json!({})).unwrap();
+        assert!(bare.arguments.is_none());
     }
 
     #[test]
@@ -523,6 +627,13 @@ mod tests {
         assert_eq!(args.program, "demo.iplc");
         assert!(!args.stop_on_entry);
         assert!(args.scan_limit.is_none());
+        assert!(args.scan_history.is_none());
+    }
+
+    #[test]
+    fn go_to_scan_arguments_when_deserialized_then_reads_scan() {
+        let args: GoToScanArguments = serde_json::from_value(json!({ "scan": 7 })).unwrap();
+        assert_eq!(args.scan, 7);
     }
 
     #[test]
@@ -617,11 +728,36 @@ mod tests {
             value: "42".to_string(),
             type_name: None,
             variables_reference: 0,
+            menu_context: None,
         };
         let value = serde_json::to_value(&var).unwrap();
         assert_eq!(value["value"], "42");
         assert_eq!(value["variablesReference"], 0);
         assert!(value.get("type").is_none());
+        assert!(value.get("__vscodeVariableMenuContext").is_none());
+    }
+
+    #[test]
+    fn variable_when_forced_then_value_and_menu_context_say_so() {
+        let var = Variable {
+            name: "count".to_string(),
+            value: "42".to_string(),
+            type_name: Some("DINT".to_string()),
+            variables_reference: 0,
+            menu_context: None,
+        };
+        let value = serde_json::to_value(var.forced()).unwrap();
+        assert_eq!(value["value"], "42 (forced)");
+        assert_eq!(value["__vscodeVariableMenuContext"], "forced");
+    }
+
+    #[test]
+    fn force_variable_arguments_when_no_bit_then_bit_is_none() {
+        let args: ForceVariableArguments =
+            serde_json::from_value(json!({ "name": "count", "value": "5" })).unwrap();
+        assert_eq!(args.name, "count");
+        assert_eq!(args.value, "5");
+        assert!(args.bit.is_none());
     }
 
     #[test]
@@ -12,10 +12,10 @@ mod dap;
 use std::io::{self, BufReader};
 
 fn main() -> io::Result<()> {
-    let stdin = io::stdin();
-    let mut reader = BufReader::new(stdin.lock());
+    // The reader thread owns stdin, so it takes the handle rather than a lock.
+    let reader = BufReader::new(io::stdin());
     let stdout = io::stdout();
     let mut writer = stdout.lock();
 
-    dap::server::serve(&mut reader, &mut writer)
+    dap::server::serve(reader, &mut writer)
 }
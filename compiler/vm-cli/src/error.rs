@@ -2,11 +2,11 @@
 
 use std::fmt;
 
-use ironplc_container::{InstanceId, TaskId};
+use ironplc_container::{DebugSection, InstanceId, TaskId};
 use ironplc_vm::error::Trap;
 
 // V6xxx code constants are generated from resources/problem-codes.csv. Some
-// codes (the DAP launch codes V6008–V6010) are consumed only by the
+// codes (the DAP launch codes V6008–V6010 and V6012) are consumed only by the
 // `ironplcvmd` binary, so they are dead in this binary — the allow keeps that
 // from warning while the constants stay re-exported at `error::*`.
 #[allow(dead_code)]
@@ -27,12 +27,20 @@ pub struct VmError {
 
 impl VmError {
     /// Creates a `VmError` from a runtime trap with execution context.
-    pub fn from_trap(trap: &Trap, task_id: TaskId, instance_id: InstanceId) -> Self {
+    /// `debug` is the container's debug section, which names the variable
+    /// in the message when the trap refers to one.
+    pub fn from_trap(
+        trap: &Trap,
+        task_id: TaskId,
+        instance_id: InstanceId,
+        debug: Option<&DebugSection>,
+    ) -> Self {
         VmError {
             v_code: trap.v_code(),
             exit_code: trap.exit_code(),
             message: format!(
-                "runtime error: {trap} (task {}, instance {})",
+                "runtime error: {} (task {}, instance {})",
+                trap.with_debug(debug),
                 task_id.raw(),
                 instance_id.raw()
             ),
@@ -66,7 +74,12 @@ mod tests {
 
     #[test]
     fn from_trap_when_divide_by_zero_then_v4001_exit_1() {
-        let err = VmError::from_trap(&Trap::DivideByZero, TaskId::new(0), InstanceId::new(0));
+        let err = VmError::from_trap(
+            &Trap::DivideByZero,
+            TaskId::new(0),
+            InstanceId::new(0),
+            None,
+        );
         assert_eq!(err.exit_code(), 1);
         assert!(err.to_string().starts_with("V4001"));
     }
@@ -80,7 +93,12 @@ mod tests {
 
     #[test]
     fn display_when_trap_then_includes_context() {
-        let err = VmError::from_trap(&Trap::DivideByZero, TaskId::new(2), InstanceId::new(5));
+        let err = VmError::from_trap(
+            &Trap::DivideByZero,
+            TaskId::new(2),
+            InstanceId::new(5),
+            None,
+        );
         assert_eq!(
             err.to_string(),
             "V4001 - runtime error: divide by zero (task 2, instance 5)"
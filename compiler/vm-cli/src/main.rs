@@ -1,11 +1,14 @@
 use std::path::PathBuf;
 use std::process::ExitCode;
 
+use clap::builder::{PossibleValuesParser, TypedValueParser};
 use clap::Parser;
+use ironplc_vm::OverflowPolicy;
 
 mod cli;
 mod error;
 mod logger;
+mod monitor;
 
 #[cfg(test)]
 mod spec_requirements {
@@ -54,6 +57,27 @@ enum Action {
         /// Run N scheduling rounds then stop (default: continuous until Ctrl+C).
         #[arg(long)]
         scans: Option<u64>,
+
+        /// Serve the monitor protocol on ADDR (for example 127.0.0.1:7878) so
+        /// tools can inspect and change the running program.
+        #[arg(long, value_name = "ADDR")]
+        monitor: Option<String>,
+
+        /// Force a program or global variable for the whole run: NAME=VALUE,
+        /// or NAME.BIT=VALUE for one bit of a BYTE, WORD, DWORD or LWORD.
+        /// Repeat to force several.
+        #[arg(long, value_name = "NAME=VALUE")]
+        force: Vec<String>,
+
+        /// How integer arithmetic and narrowing handle a result that does
+        /// not fit its type: wrap, saturate, or fault with V4006.
+        #[arg(
+            long,
+            default_value = "wrap",
+            value_parser = PossibleValuesParser::new(["wrap", "saturate", "fault"])
+                .map(|policy| policy.parse::<OverflowPolicy>().expect("a possible value")),
+        )]
+        overflow: OverflowPolicy,
     },
     /// Benchmarks a bytecode container by running it many times and reporting timing statistics.
     Benchmark {
@@ -80,7 +104,17 @@ pub fn main() -> ExitCode {
             file,
             dump_vars,
             scans,
-        } => cli::run(&file, dump_vars.as_deref(), scans),
+            monitor,
+            force,
+            overflow,
+        } => cli::run(
+            &file,
+            dump_vars.as_deref(),
+            scans,
+            monitor.as_deref(),
+            &force,
+            overflow,
+        ),
         Action::Benchmark {
             file,
             cycles,
@@ -0,0 +1,277 @@
+//! The variables a monitor client can address by name.
+//!
+//! Built once from the container's debug section. Only program and global
+//! variables are listed: between scans — the only time the monitor touches
+//! the VM — function and function block locals hold no meaningful value.
+//!
+//! `ironplcvm run --force` resolves its arguments here too, so the command
+//! line and the monitor accept the same names and values.
+
+use ironplc_container::debug_format::{
+    format_variable_value, parse_variable_value, read_string_value,
+};
+use ironplc_container::debug_section::{iec_type_tag, var_section};
+use ironplc_container::{Container, FunctionId, VarIndex};
+use ironplc_vm::VmRunning;
+
+/// One addressable variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogEntry {
+    pub name: String,
+    pub type_name: String,
+    /// The IEC keyword of the declaring section, e.g. `VAR_GLOBAL`.
+    pub section: &'static str,
+    pub iec_type_tag: u8,
+    pub var_index: VarIndex,
+    /// Where a STRING's bytes live in the data region.
+    pub string_offset: Option<u32>,
+}
+
+impl CatalogEntry {
+    /// Renders the variable's value, held in slot `raw` (or, for a STRING, in
+    /// `data_region`), the way the debugger and `--dump-vars` do.
+    pub fn render(&self, raw: u64, data_region: &[u8]) -> String {
+        match self.iec_type_tag {
+            iec_type_tag::STRING => self
+                .string_offset
+                .and_then(|offset| read_string_value(data_region, offset).ok())
+                .unwrap_or_else(|| "<not available>".to_string()),
+            iec_type_tag::WSTRING => "<not available>".to_string(),
+            tag => format_variable_value(raw, tag),
+        }
+    }
+
+    /// Parses `text` as a value of this variable's type, or explains why it
+    /// is not one.
+    pub fn parse(&self, text: &str) -> Result<u64, String> {
+        parse_variable_value(text, self.iec_type_tag).map_err(|err| {
+            format!(
+                "'{text}' cannot be set on {} ({}): {err}",
+                self.name, self.type_name
+            )
+        })
+    }
+
+    /// How many bits a bit force can address: bit strings (`BYTE`, `WORD`,
+    /// `DWORD`, `LWORD`) have IEC 61131-3 partial access to single bits;
+    /// other types have none.
+    pub fn bits(&self) -> Option<u8> {
+        match self.iec_type_tag {
+            iec_type_tag::BYTE => Some(8),
+            iec_type_tag::WORD => Some(16),
+            iec_type_tag::DWORD => Some(32),
+            iec_type_tag::LWORD => Some(64),
+            _ => None,
+        }
+    }
+
+    /// Forces the variable to `value`, or, with `bit`, forces that one bit
+    /// to `value` (`TRUE` or `FALSE`) and leaves the others to the program.
+    pub fn force(
+        &self,
+        running: &mut VmRunning,
+        value: &str,
+        bit: Option<u8>,
+    ) -> Result<(), String> {
+        let forced = match bit {
+            None => running.force_variable(self.var_index, self.parse(value)?),
+            Some(bit) => {
+                let bits = self.bits().ok_or_else(|| {
+                    format!(
+                        "{} ({}) is not a bit string; only BYTE, WORD, DWORD and LWORD bits can be forced",
+                        self.name, self.type_name
+                    )
+                })?;
+                if bit >= bits {
+                    return Err(format!(
+                        "{} ({}) has bits 0 to {}; there is no bit {bit}",
+                        self.name,
+                        self.type_name,
+                        bits - 1
+                    ));
+                }
+                let on = parse_variable_value(value, iec_type_tag::BOOL).map_err(|_| {
+                    format!(
+                        "'{value}' cannot be set on bit {bit} of {}: expected TRUE or FALSE",
+                        self.name
+                    )
+                })?;
+                running.force_bit(self.var_index, bit, on != 0)
+            }
+        };
+        forced.map_err(|trap| trap.to_string())
+    }
+}
+
+/// Program and global variables by name.
+#[derive(Debug, Default)]
+pub struct Catalog {
+    entries: Vec<CatalogEntry>,
+}
+
+impl Catalog {
+    /// Lists the container's program and global variables, in declaration
+    /// order. A container without a debug section has none.
+    pub fn from_container(container: &Container) -> Catalog {
+        let Some(debug) = container.debug_section.as_ref() else {
+            return Catalog::default();
+        };
+        let entries = debug
+            .var_names
+            .iter()
+            .filter(|e| e.function_id == FunctionId::GLOBAL_SCOPE)
+            .map(|e| CatalogEntry {
+                name: e.name.clone(),
+                type_name: e.type_name.clone(),
+                section: var_section::keyword(e.var_section).unwrap_or("VAR"),
+                iec_type_tag: e.iec_type_tag,
+                var_index: e.var_index,
+                string_offset: debug
+                    .string_layouts
+                    .iter()
+                    .find(|layout| layout.var_index == e.var_index)
+                    .map(|layout| layout.data_offset),
+            })
+            .collect();
+        Catalog { entries }
+    }
+
+    /// Every addressable variable.
+    pub fn entries(&self) -> &[CatalogEntry] {
+        &self.entries
+    }
+
+    /// The variable called `name`. IEC 61131-3 identifiers are not case
+    /// sensitive, so neither is the lookup.
+    pub fn find(&self, name: &str) -> Option<&CatalogEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The variable called `name`, or the error for an unknown name.
+    pub fn lookup(&self, name: &str) -> Result<&CatalogEntry, String> {
+        self.find(name)
+            .ok_or_else(|| format!("no program or global variable is named '{name}'"))
+    }
+
+    /// The name of the variable at `var_index`, if it is addressable.
+    pub fn name_of(&self, var_index: VarIndex) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.var_index == var_index)
+            .map(|e| e.name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironplc_container::debug_section::VarNameEntry;
+    use ironplc_container::{opcode, ContainerBuilder};
+    use ironplc_vm::{Vm, VmBuffers};
+
+    fn var(index: u16, function_id: FunctionId, section: u8, name: &str) -> VarNameEntry {
+        VarNameEntry {
+            var_index: VarIndex::new(index),
+            function_id,
+            var_section: section,
+            iec_type_tag: iec_type_tag::DINT,
+            name: name.into(),
+            type_name: "DINT".into(),
+        }
+    }
+
+    fn a_catalog() -> Catalog {
+        let container = ContainerBuilder::new()
+            .num_variables(3)
+            .add_var_name(var(
+                0,
+                FunctionId::GLOBAL_SCOPE,
+                var_section::VAR,
+                "Counter",
+            ))
+            .add_var_name(var(
+                1,
+                FunctionId::GLOBAL_SCOPE,
+                var_section::VAR_GLOBAL,
+                "Total",
+            ))
+            .add_var_name(var(2, FunctionId::new(2), var_section::VAR_INPUT, "n"))
+            .build();
+        Catalog::from_container(&container)
+    }
+
+    #[test]
+    fn from_container_when_function_locals_then_lists_program_and_globals_only() {
+        let catalog = a_catalog();
+        assert_eq!(catalog.entries().len(), 2);
+        assert_eq!(catalog.entries()[1].section, "VAR_GLOBAL");
+    }
+
+    #[test]
+    fn find_when_name_differs_in_case_then_found() {
+        let catalog = a_catalog();
+        assert_eq!(catalog.find("COUNTER").unwrap().var_index, VarIndex::new(0));
+        assert!(catalog.find("n").is_none());
+    }
+
+    /// A program with one `WORD` variable, `Inputs`, started and ready to
+    /// force.
+    fn word_container() -> Container {
+        ContainerBuilder::new()
+            .num_variables(1)
+            .add_function(FunctionId::INIT, &[opcode::RET_VOID], 0, 1, 0)
+            .add_function(FunctionId::SCAN, &[opcode::RET_VOID], 0, 1, 0)
+            .max_call_depth(1)
+            .add_var_name(VarNameEntry {
+                iec_type_tag: iec_type_tag::WORD,
+                type_name: "WORD".into(),
+                ..var(
+                    0,
+                    FunctionId::GLOBAL_SCOPE,
+                    var_section::VAR_GLOBAL,
+                    "Inputs",
+                )
+            })
+            .build()
+    }
+
+    #[test]
+    fn force_when_bit_of_word_then_only_that_bit_is_held() {
+        let container = word_container();
+        let catalog = Catalog::from_container(&container);
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let inputs = catalog.lookup("inputs").unwrap();
+
+        inputs.force(&mut running, "TRUE", Some(3)).unwrap();
+
+        assert_eq!(running.read_variable_raw(VarIndex::new(0)).unwrap(), 0b1000);
+        assert_eq!(running.forces().get(VarIndex::new(0)).unwrap().mask, 0b1000);
+    }
+
+    #[test]
+    fn force_when_bit_past_width_then_error_names_the_range() {
+        let container = word_container();
+        let catalog = Catalog::from_container(&container);
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+
+        let forced = catalog
+            .lookup("Inputs")
+            .unwrap()
+            .force(&mut running, "TRUE", Some(16));
+
+        assert_eq!(
+            forced,
+            Err("Inputs (WORD) has bits 0 to 15; there is no bit 16".to_string())
+        );
+    }
+
+    #[test]
+    fn from_container_when_no_debug_section_then_empty() {
+        let container = ContainerBuilder::new().num_variables(1).build();
+        assert!(Catalog::from_container(&container).entries().is_empty());
+    }
+}
@@ -0,0 +1,12 @@
+//! TCP monitor for a running program (`ironplcvm run --monitor ADDR`).
+//!
+//! Lets tools attach to a VM that is already running: list, read, write and
+//! force program and global variables by name, subscribe to their changes,
+//! read the scheduler's per-task statistics, and pause and resume scanning.
+//! The debug adapter's `attach` request is a client of this protocol.
+
+pub mod catalog;
+pub mod protocol;
+pub mod server;
+
+pub use server::Monitor;
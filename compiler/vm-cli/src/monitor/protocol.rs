@@ -0,0 +1,141 @@
+//! Monitor protocol messages.
+//!
+//! Each message is one line of JSON. A client sends requests; the monitor
+//! answers each with a response carrying the request's `id`, and pushes
+//! `changed` events to clients that subscribed to variables. Values travel as
+//! IEC 61131-3 literal text (`TRUE`, `-5`, `T#250ms`) in both directions, the
+//! same text the debugger's variables view shows.
+//!
+//! The message shapes are specified in `specs/design/vm-monitor.md`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The protocol revision `status` reports, bumped on any incompatible change.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// A client request.
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    /// Echoed in the response so a client can match them up.
+    #[serde(default)]
+    pub id: Option<i64>,
+    pub command: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+/// The answer to one [`Request`].
+#[derive(Debug, Serialize)]
+pub struct Response {
+    pub id: Option<i64>,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+impl Response {
+    pub fn success(id: Option<i64>, body: Option<Value>) -> Response {
+        Response {
+            id,
+            success: true,
+            message: None,
+            body,
+        }
+    }
+
+    pub fn error(id: Option<i64>, message: impl Into<String>) -> Response {
+        Response {
+            id,
+            success: false,
+            message: Some(message.into()),
+            body: None,
+        }
+    }
+}
+
+/// A message the monitor sends unprompted.
+#[derive(Debug, Serialize)]
+pub struct Event {
+    pub event: &'static str,
+    pub body: Value,
+}
+
+/// Arguments naming several variables (`read`, `subscribe`, `unsubscribe`).
+#[derive(Debug, Default, Deserialize)]
+pub struct NamesArguments {
+    #[serde(default)]
+    pub names: Vec<String>,
+}
+
+/// Arguments naming one variable (`unforce`).
+#[derive(Debug, Deserialize)]
+pub struct NameArguments {
+    pub name: String,
+}
+
+/// Arguments setting one variable (`write`).
+#[derive(Debug, Deserialize)]
+pub struct ValueArguments {
+    pub name: String,
+    pub value: String,
+}
+
+/// Arguments forcing one variable, or one bit of a bit string (`force`).
+#[derive(Debug, Deserialize)]
+pub struct ForceArguments {
+    pub name: String,
+    pub value: String,
+    /// The bit to force, 0 being the least significant; absent to force the
+    /// whole variable.
+    #[serde(default)]
+    pub bit: Option<u8>,
+}
+
+/// One entry of the `list` response.
+#[derive(Debug, Serialize)]
+pub struct VariableInfo {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub section: &'static str,
+    pub forced: bool,
+}
+
+/// A variable's current value, in `read` and `subscribe` responses and
+/// `changed` events.
+#[derive(Debug, Serialize)]
+pub struct VariableValue {
+    pub name: String,
+    pub value: String,
+    pub forced: bool,
+}
+
+/// One entry of the `tasks` response: a task's scheduler statistics.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskInfo {
+    pub task_id: u16,
+    #[serde(rename = "type")]
+    pub task_type: &'static str,
+    pub priority: u16,
+    pub interval_us: u64,
+    pub watchdog_us: u64,
+    pub enabled: bool,
+    pub scan_count: u64,
+    pub last_execute_us: u64,
+    pub max_execute_us: u64,
+    pub overrun_count: u64,
+}
+
+/// Body of the `status` response.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusBody {
+    pub protocol: u32,
+    pub scan_count: u64,
+    pub paused: bool,
+    pub forced: Vec<String>,
+}
@@ -0,0 +1,865 @@
+//! The monitor's listener and request handling.
+//!
+//! A listener thread accepts connections and a reader thread per connection
+//! decodes lines; both only forward [`Message`]s over a channel. The run loop
+//! owns the [`Monitor`] and services that channel between scheduling rounds,
+//! so every request sees the VM at a scan boundary and nothing shares the VM
+//! across threads.
+//!
+//! The run loop never writes to a socket either. Each connection has a writer
+//! thread fed by a bounded queue, so a client that stops reading cannot stall
+//! the scan: once its queue is full the client is dropped. Request lines are
+//! capped at [`MAX_REQUEST_LINE`] bytes for the same reason on the way in.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, SyncSender, TrySendError};
+use std::thread;
+use std::time::Duration;
+
+use ironplc_container::{Container, VarIndex};
+use ironplc_vm::VmRunning;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::catalog::{Catalog, CatalogEntry};
+use super::protocol::{
+    Event, ForceArguments, NameArguments, NamesArguments, Request, Response, StatusBody, TaskInfo,
+    ValueArguments, VariableInfo, VariableValue, PROTOCOL_VERSION,
+};
+
+type ClientId = u64;
+
+/// How many messages may wait to be written to one client. A client this far
+/// behind is not reading, and is dropped rather than buffered without bound.
+const CLIENT_QUEUE: usize = 256;
+
+/// The longest request line a client may send, in bytes. A longer line ends
+/// the connection.
+const MAX_REQUEST_LINE: usize = 64 * 1024;
+
+/// What the listener and reader threads tell the run loop.
+#[derive(Debug)]
+enum Message {
+    Connected(ClientId, TcpStream),
+    Line(ClientId, String),
+    /// The client sent a line longer than [`MAX_REQUEST_LINE`]; its reader
+    /// has stopped.
+    Oversized(ClientId),
+    Closed(ClientId),
+}
+
+/// A subscribed variable and the value last sent for it.
+#[derive(Debug)]
+struct Subscription {
+    entry: CatalogEntry,
+    last: Option<String>,
+}
+
+/// A connected client.
+struct Client {
+    /// The queue the client's writer thread drains to its socket.
+    outbox: SyncSender<Vec<u8>>,
+    /// The socket, kept to cut the connection of a client that falls behind.
+    stream: TcpStream,
+    subscriptions: Vec<Subscription>,
+}
+
+impl Client {
+    /// Starts the writer thread for `stream`.
+    fn connect(stream: TcpStream) -> io::Result<Client> {
+        let mut writer = stream.try_clone()?;
+        let (outbox, queue) = mpsc::sync_channel::<Vec<u8>>(CLIENT_QUEUE);
+        thread::spawn(move || {
+            // Ends when the run loop drops the client, after writing what was
+            // already queued, or when the socket fails.
+            for line in queue {
+                if writer.write_all(&line).is_err() {
+                    break;
+                }
+            }
+            let _ = writer.shutdown(Shutdown::Both);
+        });
+        Ok(Client {
+            outbox,
+            stream,
+            subscriptions: Vec::new(),
+        })
+    }
+
+    /// Queues one message as a line of JSON. A client whose queue is full has
+    /// its connection cut at once, so it sees the drop rather than a gap.
+    fn send<T: Serialize>(&self, message: &T) -> io::Result<()> {
+        let mut line = serde_json::to_vec(message).map_err(io::Error::other)?;
+        line.push(b'\n');
+        match self.outbox.try_send(line) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                let _ = self.stream.shutdown(Shutdown::Both);
+                Err(io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    "client is not reading",
+                ))
+            }
+            Err(TrySendError::Disconnected(_)) => Err(io::ErrorKind::BrokenPipe.into()),
+        }
+    }
+}
+
+/// A listening monitor, serviced by the run loop.
+pub struct Monitor {
+    address: SocketAddr,
+    messages: Receiver<Message>,
+    clients: BTreeMap<ClientId, Client>,
+    catalog: Catalog,
+    /// The client that paused the VM, if it is paused.
+    paused_by: Option<ClientId>,
+}
+
+impl Monitor {
+    /// Listens on `address` (for example `127.0.0.1:7878`; port `0` picks a
+    /// free one) for clients of `container`'s program.
+    pub fn bind(address: &str, container: &Container) -> io::Result<Monitor> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || accept(listener, sender));
+        Ok(Monitor {
+            address,
+            messages,
+            clients: BTreeMap::new(),
+            catalog: Catalog::from_container(container),
+            paused_by: None,
+        })
+    }
+
+    /// The address the monitor listens on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Whether a client has paused the VM. The run loop runs no rounds while
+    /// paused and calls [`wait`](Self::wait) instead.
+    pub fn paused(&self) -> bool {
+        self.paused_by.is_some()
+    }
+
+    /// Handles every message that has already arrived.
+    pub fn service(&mut self, running: &mut VmRunning) {
+        while let Ok(message) = self.messages.try_recv() {
+            self.handle(message, running);
+        }
+    }
+
+    /// Waits up to `timeout` for a message, then handles everything that has
+    /// arrived. The run loop sleeps here between rounds so requests are
+    /// answered while it waits for the next task to come due.
+    pub fn wait(&mut self, running: &mut VmRunning, timeout: Duration) {
+        match self.messages.recv_timeout(timeout) {
+            Ok(message) => {
+                self.handle(message, running);
+                self.service(running);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            // The listener thread has gone; keep the caller's pacing.
+            Err(RecvTimeoutError::Disconnected) => thread::sleep(timeout),
+        }
+    }
+
+    /// Sends each subscriber the subscribed values that changed in the round
+    /// that just completed.
+    pub fn publish(&mut self, running: &VmRunning) {
+        let scan = running.scan_count();
+        let mut gone = Vec::new();
+        for (&id, client) in self.clients.iter_mut() {
+            let changed: Vec<VariableValue> = client
+                .subscriptions
+                .iter_mut()
+                .filter_map(|sub| {
+                    let value = read(&sub.entry, running);
+                    if sub.last.as_ref() == Some(&value) {
+                        return None;
+                    }
+                    sub.last = Some(value.clone());
+                    Some(VariableValue {
+                        name: sub.entry.name.clone(),
+                        value,
+                        forced: is_forced(running, sub.entry.var_index),
+                    })
+                })
+                .collect();
+            if changed.is_empty() {
+                continue;
+            }
+            let event = Event {
+                event: "changed",
+                body: json!({ "scan": scan, "variables": changed }),
+            };
+            if client.send(&event).is_err() {
+                gone.push(id);
+            }
+        }
+        for id in gone {
+            self.disconnect(id);
+        }
+    }
+
+    fn handle(&mut self, message: Message, running: &mut VmRunning) {
+        match message {
+            Message::Connected(id, stream) => {
+                if let Ok(client) = Client::connect(stream) {
+                    self.clients.insert(id, client);
+                }
+            }
+            Message::Line(id, line) => {
+                let response = match serde_json::from_str::<Request>(&line) {
+                    Ok(request) => {
+                        let result = self.dispatch(id, &request, running);
+                        match result {
+                            Ok(body) => Response::success(request.id, body),
+                            Err(message) => Response::error(request.id, message),
+                        }
+                    }
+                    Err(err) => Response::error(None, format!("invalid request: {err}")),
+                };
+                self.respond(id, &response);
+            }
+            Message::Oversized(id) => {
+                let response = Response::error(
+                    None,
+                    format!("request line is longer than {MAX_REQUEST_LINE} bytes"),
+                );
+                self.respond(id, &response);
+                self.disconnect(id);
+            }
+            Message::Closed(id) => self.disconnect(id),
+        }
+    }
+
+    /// Sends `response` to a client, dropping the client if that fails.
+    fn respond(&mut self, id: ClientId, response: &Response) {
+        let sent = self
+            .clients
+            .get(&id)
+            .is_some_and(|client| client.send(response).is_ok());
+        if !sent {
+            self.disconnect(id);
+        }
+    }
+
+    /// Drops a client; its writer thread closes the connection once it has
+    /// written what is queued. A client that goes away while its pause holds the VM
+    /// releases it, so a crashed tool cannot leave the program stopped.
+    /// Forced values stay in the VM: forcing outlives the session that set
+    /// it, as it does on a PLC, until something unforces it.
+    fn disconnect(&mut self, id: ClientId) {
+        self.clients.remove(&id);
+        if self.paused_by == Some(id) {
+            self.paused_by = None;
+        }
+    }
+
+    /// Runs one request and returns its response body, or the error message.
+    fn dispatch(
+        &mut self,
+        client: ClientId,
+        request: &Request,
+        running: &mut VmRunning,
+    ) -> Result<Option<Value>, String> {
+        match request.command.as_str() {
+            "status" => to_body(StatusBody {
+                protocol: PROTOCOL_VERSION,
+                scan_count: running.scan_count(),
+                paused: self.paused(),
+                forced: running
+                    .forces()
+                    .entries()
+                    .iter()
+                    .map(|f| match self.catalog.name_of(f.var_index) {
+                        Some(name) => name.to_string(),
+                        None => format!("var[{}]", f.var_index.raw()),
+                    })
+                    .collect(),
+            }),
+            "list" => {
+                let variables: Vec<VariableInfo> = self
+                    .catalog
+                    .entries()
+                    .iter()
+                    .map(|entry| VariableInfo {
+                        name: entry.name.clone(),
+                        type_name: entry.type_name.clone(),
+                        section: entry.section,
+                        forced: is_forced(running, entry.var_index),
+                    })
+                    .collect();
+                to_body(json!({ "variables": variables }))
+            }
+            "read" => {
+                let args: NamesArguments = arguments(request)?;
+                let variables = self.values(&args.names, running)?;
+                to_body(json!({ "variables": variables }))
+            }
+            "write" => {
+                let args: ValueArguments = arguments(request)?;
+                let entry = self.catalog.lookup(&args.name)?;
+                let raw = entry.parse(&args.value)?;
+                if is_forced(running, entry.var_index) {
+                    return Err(format!("{} is forced; unforce it first", entry.name));
+                }
+                write(running, entry.var_index, raw)?;
+                Ok(None)
+            }
+            "force" => {
+                let args: ForceArguments = arguments(request)?;
+                let entry = self.catalog.lookup(&args.name)?;
+                entry.force(running, &args.value, args.bit)?;
+                Ok(None)
+            }
+            "unforce" => {
+                let args: NameArguments = arguments(request)?;
+                let entry = self.catalog.lookup(&args.name)?;
+                running.unforce_variable(entry.var_index);
+                Ok(None)
+            }
+            "subscribe" => {
+                let args: NamesArguments = arguments(request)?;
+                let values = self.values(&args.names, running)?;
+                let entries: Vec<CatalogEntry> = args
+                    .names
+                    .iter()
+                    .filter_map(|name| self.catalog.find(name).cloned())
+                    .collect();
+                if let Some(client) = self.clients.get_mut(&client) {
+                    for (entry, value) in entries.into_iter().zip(&values) {
+                        client
+                            .subscriptions
+                            .retain(|s| s.entry.var_index != entry.var_index);
+                        client.subscriptions.push(Subscription {
+                            entry,
+                            last: Some(value.value.clone()),
+                        });
+                    }
+                }
+                to_body(json!({ "variables": values }))
+            }
+            "unsubscribe" => {
+                let args: NamesArguments = arguments(request)?;
+                if let Some(client) = self.clients.get_mut(&client) {
+                    // No names drops every subscription.
+                    client.subscriptions.retain(|s| {
+                        !args.names.is_empty()
+                            && !args
+                                .names
+                                .iter()
+                                .any(|n| s.entry.name.eq_ignore_ascii_case(n))
+                    });
+                }
+                Ok(None)
+            }
+            "tasks" => {
+                let tasks: Vec<TaskInfo> = running
+                    .task_states()
+                    .iter()
+                    .map(|t| TaskInfo {
+                        task_id: t.task_id.raw(),
+                        task_type: t.task_type.as_str(),
+                        priority: t.priority,
+                        interval_us: t.interval_us,
+                        watchdog_us: t.watchdog_us,
+                        enabled: t.enabled,
+                        scan_count: t.scan_count,
+                        last_execute_us: t.last_execute_us,
+                        max_execute_us: t.max_execute_us,
+                        overrun_count: t.overrun_count,
+                    })
+                    .collect();
+                to_body(json!({ "tasks": tasks }))
+            }
+            "pause" => {
+                self.paused_by.get_or_insert(client);
+                to_body(json!({ "scanCount": running.scan_count() }))
+            }
+            "resume" => {
+                self.paused_by = None;
+                Ok(None)
+            }
+            other => Err(format!("unknown command '{other}'")),
+        }
+    }
+
+    /// The current values of the named variables.
+    fn values(&self, names: &[String], running: &VmRunning) -> Result<Vec<VariableValue>, String> {
+        names
+            .iter()
+            .map(|name| {
+                let entry = self.catalog.lookup(name)?;
+                Ok(VariableValue {
+                    name: entry.name.clone(),
+                    value: read(entry, running),
+                    forced: is_forced(running, entry.var_index),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Accepts connections until the listener fails, giving each a reader thread.
+fn accept(listener: TcpListener, sender: Sender<Message>) {
+    for (id, stream) in (1..).zip(listener.incoming()) {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let Ok(writer) = stream.try_clone() else {
+            continue;
+        };
+        if sender.send(Message::Connected(id, writer)).is_err() {
+            return;
+        }
+        let sender = sender.clone();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stream);
+            let end = loop {
+                match read_request(&mut reader) {
+                    Ok(Some(line)) => {
+                        if sender.send(Message::Line(id, line)).is_err() {
+                            return;
+                        }
+                    }
+                    Ok(None) => break Message::Closed(id),
+                    Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                        break Message::Oversized(id)
+                    }
+                    Err(_) => break Message::Closed(id),
+                }
+            };
+            let _ = sender.send(end);
+        });
+    }
+}
+
+/// Reads one request line, without its line ending, or `None` at the end of
+/// the stream. A line longer than [`MAX_REQUEST_LINE`] is an
+/// [`io::ErrorKind::InvalidData`] error, read no further than the limit.
+fn read_request(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    let read =
+        io::Read::take(&mut *reader, MAX_REQUEST_LINE as u64 + 1).read_until(b'\n', &mut line)?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    } else if line.len() > MAX_REQUEST_LINE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "request line too long",
+        ));
+    }
+    String::from_utf8(line).map(Some).map_err(io::Error::other)
+}
+
+fn to_body<T: Serialize>(body: T) -> Result<Option<Value>, String> {
+    serde_json::to_value(body)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// Decodes a request's arguments, naming the command when they do not fit.
+fn arguments<T: DeserializeOwned>(request: &Request) -> Result<T, String> {
+    serde_json::from_value(request.arguments.clone())
+        .map_err(|e| format!("invalid arguments to '{}': {e}", request.command))
+}
+
+fn read(entry: &CatalogEntry, running: &VmRunning) -> String {
+    let raw = running.read_variable_raw(entry.var_index).unwrap_or(0);
+    entry.render(raw, running.data_region())
+}
+
+fn write(running: &mut VmRunning, var_index: VarIndex, raw: u64) -> Result<(), String> {
+    running
+        .write_variable_raw(var_index, raw)
+        .map_err(|trap| trap.to_string())
+}
+
+/// Whether any bit of the variable is forced.
+fn is_forced(running: &VmRunning, var_index: VarIndex) -> bool {
+    running.forces().get(var_index).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironplc_container::debug_section::{iec_type_tag, var_section, VarNameEntry};
+    use ironplc_container::{
+        opcode, ContainerBuilder, FunctionId, InstanceId, ProgramInstanceEntry, TaskEntry, TaskId,
+        TaskType,
+    };
+    use ironplc_vm::{Vm, VmBuffers};
+    use spec_test_macro::spec_test;
+
+    /// A program whose scan increments `Count` (`var[0]`, a DINT) and leaves
+    /// the global `Limit` (`var[1]`, a DINT) alone.
+    fn counting_container() -> Container {
+        #[rustfmt::skip]
+        let scan: Vec<u8> = vec![
+            opcode::LOAD_VAR_I32, 0x00, 0x00,
+            opcode::LOAD_CONST_I32, 0x00, 0x00,
+            opcode::ADD_I32,
+            opcode::STORE_VAR_I32, 0x00, 0x00,
+            opcode::RET_VOID,
+        ];
+        let var = |index: u16, section: u8, name: &str| VarNameEntry {
+            var_index: VarIndex::new(index),
+            function_id: FunctionId::GLOBAL_SCOPE,
+            var_section: section,
+            iec_type_tag: iec_type_tag::DINT,
+            name: name.into(),
+            type_name: "DINT".into(),
+        };
+        ContainerBuilder::new()
+            .num_variables(2)
+            .add_i32_constant(1)
+            .add_function(FunctionId::INIT, &[opcode::RET_VOID], 0, 2, 0)
+            .add_function(FunctionId::SCAN, &scan, 2, 2, 0)
+            .max_call_depth(1)
+            .add_var_name(var(0, var_section::VAR, "Count"))
+            .add_var_name(var(1, var_section::VAR_GLOBAL, "Limit"))
+            .add_task(TaskEntry {
+                task_id: TaskId::new(0),
+                priority: 0,
+                task_type: TaskType::Freewheeling,
+                flags: 0x01,
+                interval_us: 0,
+                single_var_index: VarIndex::NO_SINGLE_VAR,
+                watchdog_us: 0,
+                input_image_offset: 0,
+                output_image_offset: 0,
+                reserved: [0; 4],
+            })
+            .add_program_instance(ProgramInstanceEntry {
+                instance_id: InstanceId::new(0),
+                task_id: TaskId::new(0),
+                entry_function_id: FunctionId::SCAN,
+                var_table_offset: 0,
+                var_table_count: 2,
+                fb_instance_offset: 0,
+                fb_instance_count: 0,
+                init_function_id: FunctionId::INIT,
+            })
+            .build()
+    }
+
+    /// Sends `request` as client 1.
+    fn dispatch(
+        monitor: &mut Monitor,
+        running: &mut VmRunning,
+        request: Value,
+    ) -> Result<Option<Value>, String> {
+        let request: Request = serde_json::from_value(request).unwrap();
+        monitor.dispatch(1, &request, running)
+    }
+
+    /// Services the monitor until `done` holds; the reader threads deliver
+    /// messages asynchronously.
+    fn pump_until(monitor: &mut Monitor, running: &mut VmRunning, done: impl Fn(&Monitor) -> bool) {
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while !done(monitor) {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "monitor did not answer"
+            );
+            monitor.wait(running, Duration::from_millis(50));
+        }
+    }
+
+    /// Sends a `read` of `name` as client 1 and returns the value it reports.
+    fn read_value(monitor: &mut Monitor, running: &mut VmRunning, name: &str) -> Value {
+        let body = dispatch(
+            monitor,
+            running,
+            json!({"command": "read", "arguments": {"names": [name]}}),
+        )
+        .unwrap()
+        .unwrap();
+        body["variables"][0]["value"].clone()
+    }
+
+    #[test]
+    fn dispatch_when_list_then_names_program_and_global_variables() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+
+        let body = dispatch(&mut monitor, &mut running, json!({"command": "list"}))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            body["variables"][1],
+            json!({"name": "Limit", "type": "DINT", "section": "VAR_GLOBAL", "forced": false})
+        );
+    }
+
+    #[test]
+    fn dispatch_when_write_then_read_returns_written_value() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+
+        let written = dispatch(
+            &mut monitor,
+            &mut running,
+            json!({"command": "write", "arguments": {"name": "limit", "value": "16#10"}}),
+        );
+
+        assert_eq!(written, Ok(None));
+        assert_eq!(read_value(&mut monitor, &mut running, "Limit"), "16");
+    }
+
+    #[test]
+    fn dispatch_when_value_out_of_range_then_error_names_the_type() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+
+        let written = dispatch(
+            &mut monitor,
+            &mut running,
+            json!({"command": "write", "arguments": {"name": "Limit", "value": "3000000000"}}),
+        );
+
+        assert!(written.unwrap_err().contains("Limit (DINT)"));
+    }
+
+    #[test]
+    fn dispatch_when_unknown_variable_or_command_then_error() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+
+        let read = dispatch(
+            &mut monitor,
+            &mut running,
+            json!({"command": "read", "arguments": {"names": ["Missing"]}}),
+        );
+        let unknown = dispatch(&mut monitor, &mut running, json!({"command": "reboot"}));
+
+        assert_eq!(
+            read,
+            Err("no program or global variable is named 'Missing'".to_string())
+        );
+        assert_eq!(unknown, Err("unknown command 'reboot'".to_string()));
+    }
+
+    /// REQ-VC-vm-cli-020: a forced value holds across a scan that writes the
+    /// variable.
+    #[spec_test(REQ_VC_vm_cli_020)]
+    fn dispatch_when_forced_then_scan_does_not_change_value() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+
+        dispatch(
+            &mut monitor,
+            &mut running,
+            json!({"command": "force", "arguments": {"name": "Count", "value": "7"}}),
+        )
+        .unwrap();
+        running.run_round(0).unwrap();
+
+        assert_eq!(read_value(&mut monitor, &mut running, "Count"), "7");
+        let write = dispatch(
+            &mut monitor,
+            &mut running,
+            json!({"command": "write", "arguments": {"name": "Count", "value": "1"}}),
+        );
+        assert_eq!(write, Err("Count is forced; unforce it first".to_string()));
+    }
+
+    #[test]
+    fn dispatch_when_unforced_then_scan_changes_value_again() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+
+        dispatch(
+            &mut monitor,
+            &mut running,
+            json!({"command": "force", "arguments": {"name": "Count", "value": "7"}}),
+        )
+        .unwrap();
+        dispatch(
+            &mut monitor,
+            &mut running,
+            json!({"command": "unforce", "arguments": {"name": "Count"}}),
+        )
+        .unwrap();
+        running.run_round(0).unwrap();
+
+        assert_eq!(read_value(&mut monitor, &mut running, "Count"), "8");
+    }
+
+    #[test]
+    fn dispatch_when_forced_then_status_names_it_and_bit_force_of_dint_refused() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+
+        dispatch(
+            &mut monitor,
+            &mut running,
+            json!({"command": "force", "arguments": {"name": "Limit", "value": "3"}}),
+        )
+        .unwrap();
+        let bit = dispatch(
+            &mut monitor,
+            &mut running,
+            json!({"command": "force", "arguments": {"name": "Count", "value": "TRUE", "bit": 0}}),
+        );
+        let status = dispatch(&mut monitor, &mut running, json!({"command": "status"}))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(status["forced"], json!(["Limit"]));
+        assert!(bit.unwrap_err().contains("not a bit string"));
+    }
+
+    #[test]
+    fn dispatch_when_tasks_then_reports_scheduler_statistics() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+        running.run_round(0).unwrap();
+        running.run_round(0).unwrap();
+
+        let body = dispatch(&mut monitor, &mut running, json!({"command": "tasks"}))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(body["tasks"][0]["type"], "Freewheeling");
+        assert_eq!(body["tasks"][0]["scanCount"], 2);
+    }
+
+    /// REQ-VC-vm-cli-021: a client that disconnects while its pause holds the
+    /// program releases it.
+    #[spec_test(REQ_VC_vm_cli_021)]
+    fn disconnect_when_client_held_pause_then_resumes() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+
+        dispatch(&mut monitor, &mut running, json!({"command": "pause"})).unwrap();
+        assert!(monitor.paused());
+        monitor.handle(Message::Closed(1), &mut running);
+
+        assert!(!monitor.paused());
+    }
+
+    #[test]
+    fn wait_when_client_sends_lines_then_answers_each_and_publishes_changes() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+        let mut client = TcpStream::connect(monitor.address()).unwrap();
+        let mut responses = BufReader::new(client.try_clone().unwrap()).lines();
+
+        client
+            .write_all(b"not json\n{\"id\":4,\"command\":\"subscribe\",\"arguments\":{\"names\":[\"Count\"]}}\n")
+            .unwrap();
+        pump_until(&mut monitor, &mut running, |m| {
+            m.clients.values().any(|c| !c.subscriptions.is_empty())
+        });
+        running.run_round(0).unwrap();
+        monitor.publish(&running);
+
+        let invalid: Value = serde_json::from_str(&responses.next().unwrap().unwrap()).unwrap();
+        let subscribed: Value = serde_json::from_str(&responses.next().unwrap().unwrap()).unwrap();
+        let changed: Value = serde_json::from_str(&responses.next().unwrap().unwrap()).unwrap();
+        assert_eq!(invalid["id"], Value::Null);
+        assert_eq!(invalid["success"], false);
+        assert_eq!(subscribed["id"], 4);
+        assert_eq!(subscribed["body"]["variables"][0]["value"], "0");
+        assert_eq!(
+            changed,
+            json!({"event": "changed", "body": {"scan": 1, "variables": [
+                {"name": "Count", "value": "1", "forced": false}
+            ]}})
+        );
+    }
+
+    #[test]
+    fn read_request_when_crlf_then_strips_line_ending() {
+        let mut reader = io::Cursor::new(b"{\"command\":\"status\"}\r\nlast".to_vec());
+
+        assert_eq!(
+            read_request(&mut reader).unwrap().as_deref(),
+            Some("{\"command\":\"status\"}")
+        );
+        assert_eq!(read_request(&mut reader).unwrap().as_deref(), Some("last"));
+        assert_eq!(read_request(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn read_request_when_line_over_limit_then_invalid_data() {
+        let mut reader = io::Cursor::new(vec![b'x'; MAX_REQUEST_LINE + 10]);
+
+        let err = read_request(&mut reader).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn send_when_client_queue_full_then_error_and_connection_cut() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut peer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        // No writer thread drains this queue: the client never catches up.
+        let (outbox, _queue) = mpsc::sync_channel(1);
+        let client = Client {
+            outbox,
+            stream,
+            subscriptions: Vec::new(),
+        };
+
+        assert!(client.send(&json!({"n": 1})).is_ok());
+        assert!(client.send(&json!({"n": 2})).is_err());
+
+        let mut rest = Vec::new();
+        assert_eq!(io::Read::read_to_end(&mut peer, &mut rest).unwrap(), 0);
+    }
+
+    #[test]
+    fn wait_when_request_line_too_long_then_answers_error_and_closes() {
+        let container = counting_container();
+        let mut bufs = VmBuffers::from_container(&container);
+        let mut running = Vm::new().load(&container, &mut bufs).start().unwrap();
+        let mut monitor = Monitor::bind("127.0.0.1:0", &container).unwrap();
+        let mut client = TcpStream::connect(monitor.address()).unwrap();
+        let responses = BufReader::new(client.try_clone().unwrap());
+
+        pump_until(&mut monitor, &mut running, |m| !m.clients.is_empty());
+        client.write_all(&vec![b'x'; MAX_REQUEST_LINE + 1]).unwrap();
+        pump_until(&mut monitor, &mut running, |m| m.clients.is_empty());
+
+        let lines: Vec<String> = responses.lines().map(Result::unwrap).collect();
+        assert_eq!(lines.len(), 1);
+        let response: Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(response["success"], false);
+    }
+}
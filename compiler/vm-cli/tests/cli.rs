@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 
 use assert_cmd::cargo;
 use assert_cmd::prelude::*;
-use ironplc_container::debug_section::{iec_type_tag, VarNameEntry};
+use ironplc_container::debug_section::{iec_type_tag, var_section, VarNameEntry};
 use ironplc_container::{
     ContainerBuilder, FunctionId, InstanceId, ProgramInstanceEntry, TaskEntry, TaskId, TaskType,
     VarIndex,
@@ -416,6 +416,159 @@ fn run_when_divide_by_zero_then_exit_1_and_v4001() -> Result<(), Box<dyn std::er
     Ok(())
 }
 
+/// Writes a container whose only function stores `i32::MAX + 1` to var[0].
+fn write_add_overflow_container(path: &Path) {
+    #[rustfmt::skip]
+    let bytecode: Vec<u8> = vec![
+        0x00, 0x00, 0x00,       // LOAD_CONST_I32 pool[0]  (i32::MAX)
+        0x00, 0x01, 0x00,       // LOAD_CONST_I32 pool[1]  (1)
+        0x20,                   // ADD_I32
+        0x10, 0x00, 0x00,       // STORE_VAR_I32 var[0]
+        0x8C,                   // RET_VOID
+    ];
+
+    let container = ContainerBuilder::new()
+        .num_variables(1)
+        .add_i32_constant(i32::MAX)
+        .add_i32_constant(1)
+        .add_function(ironplc_container::FunctionId::new(0), &bytecode, 2, 1, 0)
+        .max_call_depth(1)
+        .build();
+
+    let mut buf = Vec::new();
+    container.write_to(&mut buf).unwrap();
+    std::fs::write(path, &buf).unwrap();
+}
+
+/// REQ-VC-vm-cli-024: `run --overflow fault` turns an overflow into a V4006 trap.
+#[spec_test(REQ_VC_vm_cli_024)]
+fn run_when_overflow_fault_and_add_overflows_then_exit_1_and_v4006(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("overflow.iplc");
+    write_add_overflow_container(&container_path);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
+    cmd.arg("run")
+        .arg(&container_path)
+        .arg("--overflow")
+        .arg("fault")
+        .arg("--scans")
+        .arg("1");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("V4006"))
+        .stderr(predicate::str::contains("integer overflow"));
+
+    Ok(())
+}
+
+#[test]
+fn run_when_overflow_saturate_and_add_overflows_then_max() -> Result<(), Box<dyn std::error::Error>>
+{
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("overflow.iplc");
+    write_add_overflow_container(&container_path);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
+    cmd.arg("run")
+        .arg(&container_path)
+        .arg("--overflow")
+        .arg("saturate")
+        .arg("--scans")
+        .arg("1")
+        .arg("--dump-vars");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("var[0]: 2147483647"));
+
+    Ok(())
+}
+
+/// Writes a container whose only function stores 120 to var[0], which is
+/// checked against 0..100. With `named`, the debug section names var[0].
+fn write_range_violation_container(path: &Path, named: bool) {
+    #[rustfmt::skip]
+    let bytecode: Vec<u8> = vec![
+        0x00, 0x00, 0x00,       // LOAD_CONST_I32 pool[0]  (120)
+        0xF8, 0x00, 0x00,       // RANGE_CHECK_I32 var[0]
+        0x01, 0x00, 0x02, 0x00, //   min pool[1], max pool[2]
+        0x10, 0x00, 0x00,       // STORE_VAR_I32 var[0]
+        0x8C,                   // RET_VOID
+    ];
+
+    let mut builder = ContainerBuilder::new()
+        .num_variables(1)
+        .add_i32_constant(120)
+        .add_i32_constant(0)
+        .add_i32_constant(100)
+        .add_function(ironplc_container::FunctionId::new(0), &bytecode, 1, 1, 0)
+        .max_call_depth(1);
+    if named {
+        builder = builder.add_var_name(VarNameEntry {
+            var_index: VarIndex::new(0),
+            function_id: FunctionId::GLOBAL_SCOPE,
+            var_section: var_section::VAR,
+            iec_type_tag: iec_type_tag::INT,
+            name: "level".to_string(),
+            type_name: "PERCENT".to_string(),
+        });
+    }
+    let container = builder.build();
+
+    let mut buf = Vec::new();
+    container.write_to(&mut buf).unwrap();
+    std::fs::write(path, &buf).unwrap();
+}
+
+#[test]
+fn run_when_range_violation_and_debug_info_then_names_variable(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("range.iplc");
+    write_range_violation_container(&container_path, true);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
+    cmd.arg("run").arg(&container_path).arg("--scans").arg("1");
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "V4007 - runtime error: range violation: value 120 outside 0..100 for variable level",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn run_when_range_violation_and_no_debug_info_then_variable_index(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("range.iplc");
+    write_range_violation_container(&container_path, false);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
+    cmd.arg("run").arg(&container_path).arg("--scans").arg("1");
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "V4007 - runtime error: range violation: value 120 outside 0..100 for variable 0",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn run_when_overflow_unknown_policy_then_exit_2() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("overflow.iplc");
+    write_add_overflow_container(&container_path);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
+    cmd.arg("run")
+        .arg(&container_path)
+        .arg("--overflow")
+        .arg("clamp");
+    cmd.assert().code(2);
+
+    Ok(())
+}
+
 #[test]
 fn version_then_ok() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
@@ -759,6 +912,238 @@ fn write_cyclic_task_container(path: &Path, interval_us: u64) {
     std::fs::write(path, &buf).unwrap();
 }
 
+/// Builds a container whose 10 ms cyclic task increments `count` (`var[0]`)
+/// each scan and leaves `limit` (`var[1]`) alone, with debug names for both.
+fn write_counting_container(path: &Path) {
+    use ironplc_container::debug_section::var_section;
+    use ironplc_container::opcode;
+
+    #[rustfmt::skip]
+    let bytecode: Vec<u8> = vec![
+        opcode::LOAD_VAR_I32, 0x00, 0x00,
+        opcode::LOAD_CONST_I32, 0x00, 0x00,
+        opcode::ADD_I32,
+        opcode::STORE_VAR_I32, 0x00, 0x00,
+        opcode::RET_VOID,
+    ];
+    let var = |index: u16, name: &str| VarNameEntry {
+        var_index: VarIndex::new(index),
+        function_id: FunctionId::GLOBAL_SCOPE,
+        var_section: var_section::VAR,
+        iec_type_tag: iec_type_tag::DINT,
+        name: name.to_string(),
+        type_name: "DINT".to_string(),
+    };
+
+    let container = ContainerBuilder::new()
+        .num_variables(2)
+        .add_i32_constant(1)
+        .add_function(FunctionId::new(0), &bytecode, 2, 2, 0)
+        .add_function(FunctionId::new(1), &[opcode::RET_VOID], 0, 2, 0)
+        .add_var_name(var(0, "count"))
+        .add_var_name(var(1, "limit"))
+        .add_task(TaskEntry {
+            task_id: TaskId::DEFAULT,
+            priority: 0,
+            task_type: TaskType::Cyclic,
+            flags: 0x01,
+            interval_us: 10_000,
+            single_var_index: VarIndex::NO_SINGLE_VAR,
+            watchdog_us: 0,
+            input_image_offset: 0,
+            output_image_offset: 0,
+            reserved: [0; 4],
+        })
+        .add_program_instance(ProgramInstanceEntry {
+            instance_id: InstanceId::DEFAULT,
+            task_id: TaskId::DEFAULT,
+            entry_function_id: FunctionId::new(0),
+            var_table_offset: 0,
+            var_table_count: 2,
+            fb_instance_offset: 0,
+            fb_instance_count: 0,
+            init_function_id: FunctionId::new(1),
+        })
+        .max_call_depth(1)
+        .build();
+
+    let mut buf = Vec::new();
+    container.write_to(&mut buf).unwrap();
+    std::fs::write(path, &buf).unwrap();
+}
+
+/// Sends one monitor request line and returns the response line as JSON.
+fn monitor_request(
+    stream: &mut std::net::TcpStream,
+    responses: &mut impl std::io::BufRead,
+    request: serde_json::Value,
+) -> serde_json::Value {
+    use std::io::Write;
+    writeln!(stream, "{request}").unwrap();
+    let mut line = String::new();
+    responses.read_line(&mut line).unwrap();
+    serde_json::from_str(&line).unwrap()
+}
+
+/// REQ-VC-vm-cli-018: `run --monitor` announces the address it listens on, and
+/// a client there reads and writes variables by name while the program runs.
+#[spec_test(REQ_VC_vm_cli_018)]
+fn run_when_monitor_then_client_writes_and_reads_variables_by_name(
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("counting.iplc");
+    write_counting_container(&container_path);
+
+    let mut child = Command::new(cargo::cargo_bin!("ironplcvm"))
+        .arg("run")
+        .arg(&container_path)
+        .arg("--monitor")
+        .arg("127.0.0.1:0")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut announcement = String::new();
+    BufReader::new(child.stderr.take().unwrap()).read_line(&mut announcement)?;
+    let address = announcement
+        .trim()
+        .strip_prefix("monitor listening on ")
+        .unwrap()
+        .to_string();
+
+    let mut stream = std::net::TcpStream::connect(&address)?;
+    let mut responses = BufReader::new(stream.try_clone()?);
+    let written = monitor_request(
+        &mut stream,
+        &mut responses,
+        serde_json::json!({"id": 1, "command": "write", "arguments": {"name": "LIMIT", "value": "5"}}),
+    );
+    let read = monitor_request(
+        &mut stream,
+        &mut responses,
+        serde_json::json!({"id": 2, "command": "read", "arguments": {"names": ["limit"]}}),
+    );
+    child.kill()?;
+    child.wait()?;
+
+    assert_eq!(written["success"], true);
+    assert_eq!(read["id"], 2);
+    assert_eq!(read["body"]["variables"][0]["value"], "5");
+    Ok(())
+}
+
+/// REQ-VC-vm-cli-018: a monitor address that cannot be listened on is a V6011
+/// error before the program runs.
+#[spec_test(REQ_VC_vm_cli_018)]
+fn run_when_monitor_address_in_use_then_v6011() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("counting.iplc");
+    write_counting_container(&container_path);
+    let taken = std::net::TcpListener::bind("127.0.0.1:0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
+    cmd.arg("run")
+        .arg(&container_path)
+        .arg("--monitor")
+        .arg(taken.local_addr()?.to_string());
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains("V6011"));
+
+    Ok(())
+}
+
+/// REQ-VC-vm-cli-019: serving the monitor between rounds does not change how
+/// many rounds `--scans` runs.
+#[spec_test(REQ_VC_vm_cli_019)]
+fn run_when_monitor_and_scans_then_runs_exactly_n_rounds() -> Result<(), Box<dyn std::error::Error>>
+{
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("counting.iplc");
+    write_counting_container(&container_path);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
+    cmd.arg("run")
+        .arg(&container_path)
+        .arg("--monitor")
+        .arg("127.0.0.1:0")
+        .arg("--scans")
+        .arg("3")
+        .arg("--dump-vars");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("count: 3"));
+
+    Ok(())
+}
+
+/// REQ-VC-vm-cli-022: a forced variable holds its value although the
+/// program increments it every round.
+#[spec_test(REQ_VC_vm_cli_022)]
+fn run_when_force_then_variable_holds_forced_value() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("counting.iplc");
+    write_counting_container(&container_path);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
+    cmd.arg("run")
+        .arg(&container_path)
+        .arg("--force")
+        .arg("count=100")
+        .arg("--scans")
+        .arg("3")
+        .arg("--dump-vars");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("count: 100"));
+
+    Ok(())
+}
+
+/// REQ-VC-vm-cli-023: a `--force` argument naming no variable is refused.
+#[spec_test(REQ_VC_vm_cli_023)]
+fn run_when_force_names_unknown_variable_then_v6013() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("counting.iplc");
+    write_counting_container(&container_path);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
+    cmd.arg("run")
+        .arg(&container_path)
+        .arg("--force")
+        .arg("missing=1")
+        .arg("--scans")
+        .arg("1");
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains("V6013"));
+
+    Ok(())
+}
+
+/// REQ-VC-vm-cli-023: a `--force` value the variable cannot hold is refused.
+#[spec_test(REQ_VC_vm_cli_023)]
+fn run_when_force_value_not_of_type_then_v6013() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let container_path = dir.path().join("counting.iplc");
+    write_counting_container(&container_path);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("ironplcvm"));
+    cmd.arg("run")
+        .arg(&container_path)
+        .arg("--force")
+        .arg("count.3=TRUE")
+        .arg("--scans")
+        .arg("1");
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains("V6013"));
+
+    Ok(())
+}
+
 /// REQ-VC-vm-cli-015: `benchmark` emits per-cyclic-task `budget_pct` when the task's
 /// interval is non-zero.
 #[spec_test(REQ_VC_vm_cli_015)]
@@ -5,12 +5,17 @@
 //! leave the frame stack intact for inspection — all in `(FunctionId,
 //! bytecode_offset)` space, with no dependency on source-line debug info.
 //!
-//! It is deliberately single-threaded: the [`BreakpointTable`] is a plain
-//! sorted `Vec` owned and mutated directly by the caller (the DAP server
-//! loop). There are no atomics, no `ArcSwap`, and no cross-thread pause.
+//! The [`BreakpointTable`] is a plain sorted `Vec` owned and mutated directly
+//! by the caller (the DAP server loop) between rounds; there is no `ArcSwap`.
+//! The one cross-thread input is the interactive pause: the caller may lend the
+//! hook an [`AtomicBool`] that another thread raises, and the hook stops before
+//! the next instruction once it sees the flag. The engine never owns a thread
+//! or an `Arc` itself, so it stays within `core`.
 //!
 //! [`DebugHook`]: crate::debug_hook::DebugHook
 
+use core::sync::atomic::{AtomicBool, Ordering};
+
 use ironplc_container::FunctionId;
 
 use crate::debug_hook::{DebugHook, HookAction};
@@ -35,6 +40,9 @@ pub enum PauseReason {
     Step,
     /// Stopped on entry, before executing the first instruction.
     Entry,
+    /// Stopped because the embedder raised the pause flag lent to the hook
+    /// with [`DebuggerHook::watch_pause_requests`] (the DAP `pause` request).
+    Requested,
 }
 
 /// One breakpoint: a `(function_id, offset)` location plus an enabled flag.
@@ -219,6 +227,9 @@ impl StepController {
 /// location does not re-trigger the same breakpoint in place.
 pub struct DebuggerHook<'a> {
     breakpoints: &'a BreakpointTable,
+    /// Raised from outside the round (typically by another thread) to
+    /// interrupt a free-running program. Consumed by the hook when it pauses.
+    pause_requests: Option<&'a AtomicBool>,
     /// When set, the next instruction skips the breakpoint check exactly
     /// once. Set on every pause so resume makes forward progress.
     skip_breakpoint_once: bool,
@@ -247,6 +258,7 @@ impl<'a> DebuggerHook<'a> {
     pub fn new(breakpoints: &'a BreakpointTable) -> Self {
         Self {
             breakpoints,
+            pause_requests: None,
             skip_breakpoint_once: false,
             stop_on_entry: false,
             scan_landing: false,
@@ -267,6 +279,19 @@ impl<'a> DebuggerHook<'a> {
         self.stop_on_entry = true;
     }
 
+    /// Watch `flag` for interactive pause requests: when it is set, the next
+    /// instruction pauses with [`PauseReason::Requested`] and the flag is
+    /// cleared.
+    ///
+    /// The flag is the only state the hook shares across threads. The DAP
+    /// server's reader thread raises it on a `pause` request while the loop
+    /// thread is inside `run_round_debug`, so a program with no breakpoint can
+    /// still be stopped mid-scan. The per-instruction cost is one atomic load,
+    /// paid only by debug rounds.
+    pub fn watch_pause_requests(&mut self, flag: &'a AtomicBool) {
+        self.pause_requests = Some(flag);
+    }
+
     /// Arm a step-over from the current (paused) location: run to the next
     /// instruction at the same or a shallower call depth.
     pub fn step_over(&mut self) {
@@ -358,6 +383,16 @@ impl DebugHook for DebuggerHook<'_> {
             self.stop_on_entry = false;
             return HookAction::Pause(PauseReason::Entry);
         }
+        if self
+            .pause_requests
+            .is_some_and(|flag| flag.load(Ordering::Acquire) && flag.swap(false, Ordering::AcqRel))
+        {
+            // An interactive pause wins over breakpoints and steps: the user
+            // asked to stop *here*, whatever else this instruction is.
+            // Suppress a co-located breakpoint so resume makes progress.
+            self.skip_breakpoint_once = true;
+            return HookAction::Pause(PauseReason::Requested);
+        }
         let skip = self.skip_breakpoint_once;
         self.skip_breakpoint_once = false;
         if !skip {
@@ -660,6 +695,50 @@ mod tests {
         assert!(!crate::debug_hook::NoopDebugHook.stepping_scan());
     }
 
+    #[test]
+    fn debugger_hook_when_pause_requested_then_pauses_once_and_clears_flag() {
+        use crate::debug_hook::{DebugHook, HookAction};
+        let table = BreakpointTable::new();
+        let flag = AtomicBool::new(false);
+        let mut hook = DebuggerHook::new(&table);
+        hook.watch_pause_requests(&flag);
+        // Not raised: the program runs.
+        assert!(matches!(
+            hook.before_instruction(FunctionId::SCAN, 0, 0),
+            HookAction::Continue
+        ));
+        flag.store(true, Ordering::Release);
+        assert!(matches!(
+            hook.before_instruction(FunctionId::SCAN, 3, 0),
+            HookAction::Pause(PauseReason::Requested)
+        ));
+        // The hook consumed the request, so the resume runs on.
+        assert!(!flag.load(Ordering::Acquire));
+        assert!(matches!(
+            hook.before_instruction(FunctionId::SCAN, 6, 0),
+            HookAction::Continue
+        ));
+    }
+
+    #[test]
+    fn debugger_hook_when_pause_requested_at_breakpoint_then_pause_wins_and_resume_skips_it() {
+        use crate::debug_hook::{DebugHook, HookAction};
+        let mut table = BreakpointTable::new();
+        table.add(FunctionId::SCAN, 4);
+        let flag = AtomicBool::new(true);
+        let mut hook = DebuggerHook::new(&table);
+        hook.watch_pause_requests(&flag);
+        assert!(matches!(
+            hook.before_instruction(FunctionId::SCAN, 4, 0),
+            HookAction::Pause(PauseReason::Requested)
+        ));
+        // Resuming re-executes the same instruction without stopping again.
+        assert!(matches!(
+            hook.before_instruction(FunctionId::SCAN, 4, 0),
+            HookAction::Continue
+        ));
+    }
+
     #[test]
     fn breakpoint_table_when_many_functions_then_sorted_lookup_works() {
         let mut table = BreakpointTable::new();
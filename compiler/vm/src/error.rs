@@ -1,6 +1,8 @@
 use core::fmt;
 
-use ironplc_container::{ConstantIndex, FbTypeId, FunctionId, TaskId, VarIndex};
+use ironplc_container::{
+    ConstantIndex, DebugSection, FbTypeId, FunctionId, InstanceId, TaskId, VarIndex,
+};
 
 /// Runtime traps that halt VM execution.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -24,6 +26,22 @@ pub enum Trap {
         index: i32,
         total_elements: u32,
     },
+    /// A value stored into a subrange- or enumeration-typed location lay
+    /// outside the declared range. `var_index` names the store target and
+    /// resolves to a name through the debug section's variable table.
+    RangeViolation {
+        var_index: VarIndex,
+        value: i128,
+        min: i128,
+        max: i128,
+    },
+    /// An integer result did not fit its type under the `fault` overflow
+    /// policy (ADR-0002). Carries the task and program instance that were
+    /// executing.
+    IntegerOverflow {
+        task_id: TaskId,
+        instance_id: InstanceId,
+    },
     UnexpectedEndOfBytecode,
     CallStackOverflow,
     InvalidCmpOp(u8),
@@ -51,6 +69,13 @@ pub enum Trap {
         required: u16,
         capacity: u16,
     },
+    /// A bit force named bit `bit` of the variable at `var_index`, but a
+    /// variable slot has only bits 0 to 63. The variable itself exists;
+    /// the caller resolved the bit wrongly.
+    InvalidBitIndex {
+        var_index: VarIndex,
+        bit: u8,
+    },
     /// The container's `header.max_call_depth` is zero. Every program needs
     /// at least one call frame for its entry function, so codegen always
     /// declares a depth of one or more; a zero means the field was never
@@ -59,6 +84,32 @@ pub enum Trap {
     ZeroCallDepth,
 }
 
+/// A scan snapshot that does not fit the VM it is rewound into, such as one
+/// taken from a container with a different variable table or data region.
+/// Unlike a [`Trap`], nothing ran: the VM is unchanged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotMismatch {
+    /// The snapshot holds `actual` variable slots; the VM has `expected`.
+    Variables { expected: usize, actual: usize },
+    /// The snapshot's data region is `actual` bytes; the VM's is `expected`.
+    DataRegion { expected: usize, actual: usize },
+}
+
+impl fmt::Display for SnapshotMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotMismatch::Variables { expected, actual } => write!(
+                f,
+                "snapshot has {actual} variables but the program has {expected}"
+            ),
+            SnapshotMismatch::DataRegion { expected, actual } => write!(
+                f,
+                "snapshot data region is {actual} bytes but the program's is {expected}"
+            ),
+        }
+    }
+}
+
 // v_code() and exit_code() are generated from resources/problem-codes.csv
 include!(concat!(env!("OUT_DIR"), "/trap_codes.rs"));
 
@@ -95,6 +146,16 @@ impl fmt::Display for Trap {
                     "array index out of bounds: index {index} for array variable {var_index} with {total_elements} elements"
                 )
             }
+            Trap::RangeViolation {
+                var_index,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "range violation: value {value} outside {min}..{max} for variable {var_index}"
+            ),
+            Trap::IntegerOverflow { .. } => write!(f, "integer overflow"),
             Trap::UnexpectedEndOfBytecode => write!(f, "bytecode ended mid-instruction"),
             Trap::CallStackOverflow => write!(f, "call stack overflow"),
             Trap::InvalidCmpOp(code) => write!(f, "invalid comparison operator code: 0x{code:02X}"),
@@ -114,6 +175,47 @@ impl fmt::Display for Trap {
             Trap::ZeroCallDepth => {
                 write!(f, "container declares a maximum call depth of zero")
             }
+            Trap::InvalidBitIndex { var_index, bit } => write!(
+                f,
+                "invalid bit index: bit {bit} of variable {var_index} (a slot has bits 0 to 63)"
+            ),
+        }
+    }
+}
+
+impl Trap {
+    /// Returns a form of the trap for display that names the variable a
+    /// [`Trap::RangeViolation`] refers to through the container's debug
+    /// section. Without a debug section, or when the debug section does not
+    /// name the variable, the message gives the variable index instead.
+    pub fn with_debug<'a>(&'a self, debug: Option<&'a DebugSection>) -> TrapWithDebug<'a> {
+        TrapWithDebug { trap: self, debug }
+    }
+}
+
+/// A [`Trap`] displayed with the variable names in a debug section. See
+/// [`Trap::with_debug`].
+pub struct TrapWithDebug<'a> {
+    trap: &'a Trap,
+    debug: Option<&'a DebugSection>,
+}
+
+impl fmt::Display for TrapWithDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.trap {
+            Trap::RangeViolation {
+                var_index,
+                value,
+                min,
+                max,
+            } => match self.debug.and_then(|debug| debug.var_name(*var_index)) {
+                Some(name) => write!(
+                    f,
+                    "range violation: value {value} outside {min}..{max} for variable {name}"
+                ),
+                None => self.trap.fmt(f),
+            },
+            trap => trap.fmt(f),
         }
     }
 }
@@ -121,6 +223,7 @@ impl fmt::Display for Trap {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ironplc_container::debug_section::{iec_type_tag, var_section, VarNameEntry};
     use rstest::rstest;
 
     #[rstest]
@@ -161,6 +264,22 @@ mod tests {
         },
         "array index out of bounds: index 10 for array variable 2 with 5 elements"
     )]
+    #[case(
+        Trap::RangeViolation {
+            var_index: VarIndex::new(3),
+            value: 11,
+            min: 1,
+            max: 10,
+        },
+        "range violation: value 11 outside 1..10 for variable 3"
+    )]
+    #[case(
+        Trap::IntegerOverflow {
+            task_id: TaskId::new(1),
+            instance_id: InstanceId::new(2),
+        },
+        "integer overflow"
+    )]
     #[case(Trap::UnexpectedEndOfBytecode, "bytecode ended mid-instruction")]
     #[case(Trap::CallStackOverflow, "call stack overflow")]
     #[case(Trap::InvalidCmpOp(0x07), "invalid comparison operator code: 0x07")]
@@ -181,6 +300,63 @@ mod tests {
         assert_eq!(format!("{trap}"), expected);
     }
 
+    fn range_violation() -> Trap {
+        Trap::RangeViolation {
+            var_index: VarIndex::new(3),
+            value: 120,
+            min: 0,
+            max: 100,
+        }
+    }
+
+    #[test]
+    fn with_debug_when_range_violation_and_var_named_then_names_variable() {
+        let debug = DebugSection {
+            var_names: vec![VarNameEntry {
+                var_index: VarIndex::new(3),
+                function_id: FunctionId::GLOBAL_SCOPE,
+                var_section: var_section::VAR,
+                iec_type_tag: iec_type_tag::INT,
+                name: "level".into(),
+                type_name: "PERCENT".into(),
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            range_violation().with_debug(Some(&debug)).to_string(),
+            "range violation: value 120 outside 0..100 for variable level"
+        );
+    }
+
+    #[test]
+    fn with_debug_when_range_violation_and_no_debug_then_variable_index() {
+        assert_eq!(
+            range_violation().with_debug(None).to_string(),
+            "range violation: value 120 outside 0..100 for variable 3"
+        );
+    }
+
+    #[test]
+    fn with_debug_when_range_violation_and_var_not_in_table_then_variable_index() {
+        let debug = DebugSection::default();
+
+        assert_eq!(
+            range_violation().with_debug(Some(&debug)).to_string(),
+            "range violation: value 120 outside 0..100 for variable 3"
+        );
+    }
+
+    #[test]
+    fn with_debug_when_other_trap_then_same_as_display() {
+        let debug = DebugSection::default();
+
+        assert_eq!(
+            Trap::DivideByZero.with_debug(Some(&debug)).to_string(),
+            "divide by zero"
+        );
+    }
+
     #[rstest]
     #[case(Trap::DivideByZero, "V4001")]
     #[case(Trap::NegativeExponent, "V4002")]
@@ -194,6 +370,22 @@ mod tests {
         },
         "V4005"
     )]
+    #[case(
+        Trap::IntegerOverflow {
+            task_id: TaskId::new(0),
+            instance_id: InstanceId::new(0),
+        },
+        "V4006"
+    )]
+    #[case(
+        Trap::RangeViolation {
+            var_index: VarIndex::new(0),
+            value: -1,
+            min: 0,
+            max: 3,
+        },
+        "V4007"
+    )]
     #[case(Trap::StackOverflow, "V9001")]
     #[case(Trap::StackUnderflow, "V9002")]
     #[case(Trap::InvalidInstruction(0xFF), "V9003")]
@@ -211,6 +403,7 @@ mod tests {
         "V9016"
     )]
     #[case(Trap::ZeroCallDepth, "V9017")]
+    #[case(Trap::InvalidBitIndex { var_index: VarIndex::new(0), bit: 64 }, "V9018")]
     fn v_code_when_variant_then_expected(#[case] trap: Trap, #[case] expected: &str) {
         assert_eq!(trap.v_code(), expected);
     }
@@ -230,6 +423,24 @@ mod tests {
             .exit_code(),
             1
         );
+        assert_eq!(
+            Trap::IntegerOverflow {
+                task_id: TaskId::new(0),
+                instance_id: InstanceId::new(0),
+            }
+            .exit_code(),
+            1
+        );
+        assert_eq!(
+            Trap::RangeViolation {
+                var_index: VarIndex::new(0),
+                value: 0,
+                min: 1,
+                max: 2,
+            }
+            .exit_code(),
+            1
+        );
     }
 
     #[test]
@@ -242,6 +453,14 @@ mod tests {
             3
         );
         assert_eq!(Trap::InvalidVariableIndex(VarIndex::new(0)).exit_code(), 3);
+        assert_eq!(
+            Trap::InvalidBitIndex {
+                var_index: VarIndex::new(0),
+                bit: 64,
+            }
+            .exit_code(),
+            3
+        );
         assert_eq!(Trap::InvalidFunctionId(FunctionId::new(0)).exit_code(), 3);
         assert_eq!(
             Trap::InvalidBuiltinFunction(FunctionId::new(0)).exit_code(),
@@ -269,4 +488,16 @@ mod tests {
         );
         assert_eq!(Trap::ZeroCallDepth.exit_code(), 3);
     }
+
+    #[test]
+    fn snapshot_mismatch_display_when_variables_then_names_both_counts() {
+        let err = SnapshotMismatch::Variables {
+            expected: 2,
+            actual: 3,
+        };
+        assert_eq!(
+            err.to_string(),
+            "snapshot has 3 variables but the program has 2"
+        );
+    }
 }
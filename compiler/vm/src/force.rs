@@ -0,0 +1,203 @@
+//! Variable forcing: values held against the program's own writes.
+//!
+//! Commissioning forces inputs and outputs so the program cannot override
+//! them. A force is not a one-shot write: the VM re-applies every entry of its
+//! [`ForceTable`] at the two process-image boundaries of each scan — after
+//! INPUT_FREEZE, so the program reads the forced value, and before
+//! OUTPUT_FLUSH, so whatever the program stored in between never leaves the
+//! scan. A program that writes a forced variable sees its own write for the
+//! rest of that scan, as it would on a PLC with a forced output.
+//!
+//! An entry forces a whole variable slot or individual bits of one (for
+//! example, one input bit of a `%IW` word); the unforced bits of a
+//! partially forced slot keep following the program.
+
+use ironplc_container::VarIndex;
+
+use crate::value::Slot;
+use crate::variable_table::VariableTable;
+
+/// The forced bits of one variable slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForceEntry {
+    pub var_index: VarIndex,
+    /// The bits of the slot that are forced; all ones for a whole-variable
+    /// force.
+    pub mask: u64,
+    /// The forced bits' values. Bits outside `mask` are zero.
+    pub value: u64,
+}
+
+impl ForceEntry {
+    /// Whether the entry forces the whole slot rather than some of its bits.
+    pub fn is_whole(&self) -> bool {
+        self.mask == u64::MAX
+    }
+
+    /// `raw` with the forced bits replaced by their forced values.
+    pub fn apply_to(&self, raw: u64) -> u64 {
+        (raw & !self.mask) | self.value
+    }
+}
+
+/// Forced variables, at most one entry per slot, ordered by variable index.
+///
+/// Like [`BreakpointTable`](crate::BreakpointTable), this is a plain `Vec`:
+/// the single-threaded run loop owns the VM and changes forces between
+/// rounds.
+#[derive(Debug, Default)]
+pub struct ForceTable {
+    entries: Vec<ForceEntry>,
+}
+
+impl ForceTable {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of forced slots.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing is forced.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The forced slots, in variable index order.
+    pub fn entries(&self) -> &[ForceEntry] {
+        &self.entries
+    }
+
+    /// The entry for `var_index`, if any of its bits are forced.
+    pub fn get(&self, var_index: VarIndex) -> Option<&ForceEntry> {
+        self.position(var_index).ok().map(|pos| &self.entries[pos])
+    }
+
+    /// Force the whole slot at `var_index` to `raw`, replacing any earlier
+    /// force of that slot or its bits.
+    pub fn force(&mut self, var_index: VarIndex, raw: u64) {
+        self.merge(var_index, u64::MAX, raw);
+    }
+
+    /// Force bit `bit` (0 is the least significant) of the slot at
+    /// `var_index` to `on`, leaving the slot's other forced bits as they are.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is not below 64.
+    pub fn force_bit(&mut self, var_index: VarIndex, bit: u8, on: bool) {
+        assert!(bit < 64, "a slot has 64 bits; bit {bit} does not exist");
+        let mask = 1u64 << bit;
+        self.merge(var_index, mask, if on { mask } else { 0 });
+    }
+
+    /// Release every forced bit of the slot at `var_index`. Returns whether
+    /// anything was forced.
+    pub fn unforce(&mut self, var_index: VarIndex) -> bool {
+        match self.position(var_index) {
+            Ok(pos) => {
+                self.entries.remove(pos);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Release every force.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Write every forced value into `variables`. The VM checks each index
+    /// when the force is set, so every entry names an existing slot.
+    pub(crate) fn apply(&self, variables: &mut VariableTable<'_>) {
+        for entry in &self.entries {
+            if let Ok(slot) = variables.load(entry.var_index) {
+                let raw = entry.apply_to(slot.as_u64());
+                let _ = variables.store(entry.var_index, Slot::from_u64(raw));
+            }
+        }
+    }
+
+    fn position(&self, var_index: VarIndex) -> Result<usize, usize> {
+        self.entries
+            .binary_search_by_key(&var_index.raw(), |e| e.var_index.raw())
+    }
+
+    /// Forces the `mask` bits of `var_index` to `value`, keeping the slot's
+    /// other forced bits.
+    fn merge(&mut self, var_index: VarIndex, mask: u64, value: u64) {
+        match self.position(var_index) {
+            Ok(pos) => {
+                let entry = &mut self.entries[pos];
+                entry.mask |= mask;
+                entry.value = (entry.value & !mask) | (value & mask);
+            }
+            Err(pos) => self.entries.insert(
+                pos,
+                ForceEntry {
+                    var_index,
+                    mask,
+                    value: value & mask,
+                },
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn force_when_slot_already_has_forced_bits_then_whole_force_replaces_them() {
+        let mut table = ForceTable::new();
+        table.force_bit(VarIndex::new(3), 1, true);
+        table.force(VarIndex::new(3), 0x10);
+
+        let entry = table.get(VarIndex::new(3)).unwrap();
+        assert!(entry.is_whole());
+        assert_eq!(entry.value, 0x10);
+    }
+
+    #[test]
+    fn force_bit_when_two_bits_of_one_slot_then_one_entry_holds_both() {
+        let mut table = ForceTable::new();
+        table.force_bit(VarIndex::new(0), 0, true);
+        table.force_bit(VarIndex::new(0), 2, false);
+
+        assert_eq!(table.len(), 1);
+        let entry = table.get(VarIndex::new(0)).unwrap();
+        assert_eq!(entry.apply_to(0b0100), 0b0001);
+        assert_eq!(entry.apply_to(0b1010), 0b1011);
+    }
+
+    #[test]
+    fn entries_when_forced_out_of_order_then_sorted_by_index() {
+        let mut table = ForceTable::new();
+        table.force(VarIndex::new(5), 1);
+        table.force(VarIndex::new(2), 1);
+
+        let indexes: Vec<u16> = table.entries().iter().map(|e| e.var_index.raw()).collect();
+        assert_eq!(indexes, vec![2, 5]);
+    }
+
+    #[test]
+    fn unforce_when_not_forced_then_false() {
+        let mut table = ForceTable::new();
+        table.force(VarIndex::new(1), 7);
+
+        assert!(!table.unforce(VarIndex::new(0)));
+        assert!(table.unforce(VarIndex::new(1)));
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "bit 64 does not exist")]
+    fn force_bit_when_bit_out_of_range_then_panics() {
+        ForceTable::new().force_bit(VarIndex::new(0), 64, true);
+    }
+}
@@ -3,8 +3,10 @@ pub(crate) mod builtin;
 pub mod debug;
 pub mod debug_hook;
 pub mod error;
+pub mod force;
 pub(crate) mod frame_stack;
 pub(crate) mod intrinsic;
+pub mod overflow;
 #[cfg(feature = "profiling")]
 mod profile;
 pub(crate) mod scheduler;
@@ -19,7 +21,9 @@ mod vm;
 pub use buffers::VmBuffers;
 pub use debug::{BreakpointId, BreakpointTable, DebuggerHook, PauseReason, StepMode};
 pub use debug_hook::{DebugHook, HookAction, NoopDebugHook};
+pub use force::{ForceEntry, ForceTable};
 pub use frame_stack::{FbCallReturn, Frame, FrameStack};
+pub use overflow::OverflowPolicy;
 #[cfg(feature = "profiling")]
 pub use profile::InstructionProfile;
 pub use scheduler::{ProgramInstanceState, TaskState};
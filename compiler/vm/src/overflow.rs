@@ -0,0 +1,162 @@
+//! Integer overflow policy (ADR-0002).
+//!
+//! The policy is a VM configuration, not part of the bytecode, so the same
+//! container runs under any policy. It applies to the `TRUNC_*` narrowing
+//! instructions, to the full-width `ADD`, `SUB`, `MUL` and `NEG`
+//! instructions on `I32` and `I64`, and to the unsigned `ADD_U32` ..
+//! `MUL_U64` builtins.
+
+use core::fmt;
+use core::str::FromStr;
+
+use ironplc_container::{InstanceId, TaskId};
+
+use crate::error::Trap;
+
+/// How integer arithmetic and narrowing handle a result outside the range
+/// of the result type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Two's complement wrapping, as CODESYS and TwinCAT do.
+    #[default]
+    Wrap,
+    /// Clamp to the minimum or maximum of the type, as Siemens S7 does.
+    Saturate,
+    /// Trap with [`Trap::IntegerOverflow`], as safety-rated runtimes do.
+    Fault,
+}
+
+impl OverflowPolicy {
+    /// The name of the policy, as `ironplcvm run --overflow` takes it.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverflowPolicy::Wrap => "wrap",
+            OverflowPolicy::Saturate => "saturate",
+            OverflowPolicy::Fault => "fault",
+        }
+    }
+}
+
+impl fmt::Display for OverflowPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OverflowPolicy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wrap" => Ok(OverflowPolicy::Wrap),
+            "saturate" => Ok(OverflowPolicy::Saturate),
+            "fault" => Ok(OverflowPolicy::Fault),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The overflow policy of the instance being executed, together with the
+/// task and instance that a fault names.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct OverflowCheck {
+    pub(crate) policy: OverflowPolicy,
+    pub(crate) task_id: TaskId,
+    pub(crate) instance_id: InstanceId,
+}
+
+impl OverflowCheck {
+    /// The result of an operation under the policy. Only the chosen
+    /// closure runs, so wrapping costs what it did before the policy
+    /// existed. `checked` returns `None` when the exact result is out of
+    /// range.
+    #[inline(always)]
+    pub(crate) fn apply<T>(
+        &self,
+        wrapped: impl FnOnce() -> T,
+        saturated: impl FnOnce() -> T,
+        checked: impl FnOnce() -> Option<T>,
+    ) -> Result<T, Trap> {
+        match self.policy {
+            OverflowPolicy::Wrap => Ok(wrapped()),
+            OverflowPolicy::Saturate => Ok(saturated()),
+            OverflowPolicy::Fault => checked().ok_or(Trap::IntegerOverflow {
+                task_id: self.task_id,
+                instance_id: self.instance_id,
+            }),
+        }
+    }
+
+    /// Narrows an `i32` to the range `min..=max` of a sub-32-bit type. A
+    /// wrapped value keeps the low bits, through `wrap`.
+    #[inline(always)]
+    pub(crate) fn narrow(
+        &self,
+        value: i32,
+        min: i32,
+        max: i32,
+        wrap: impl FnOnce(i32) -> i32,
+    ) -> Result<i32, Trap> {
+        self.apply(
+            || wrap(value),
+            || value.clamp(min, max),
+            || (min..=max).contains(&value).then_some(value),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(policy: OverflowPolicy) -> OverflowCheck {
+        OverflowCheck {
+            policy,
+            task_id: TaskId::new(2),
+            instance_id: InstanceId::new(3),
+        }
+    }
+
+    fn narrow_i8(policy: OverflowPolicy, value: i32) -> Result<i32, Trap> {
+        check(policy).narrow(value, i8::MIN as i32, i8::MAX as i32, |v| v as i8 as i32)
+    }
+
+    #[test]
+    fn narrow_when_wrap_then_low_bits() {
+        assert_eq!(narrow_i8(OverflowPolicy::Wrap, 150), Ok(-106));
+    }
+
+    #[test]
+    fn narrow_when_saturate_then_clamped() {
+        assert_eq!(narrow_i8(OverflowPolicy::Saturate, 150), Ok(127));
+        assert_eq!(narrow_i8(OverflowPolicy::Saturate, -150), Ok(-128));
+    }
+
+    #[test]
+    fn narrow_when_fault_and_out_of_range_then_trap_names_task_and_instance() {
+        assert_eq!(
+            narrow_i8(OverflowPolicy::Fault, 150),
+            Err(Trap::IntegerOverflow {
+                task_id: TaskId::new(2),
+                instance_id: InstanceId::new(3),
+            })
+        );
+    }
+
+    #[test]
+    fn narrow_when_fault_and_in_range_then_value() {
+        assert_eq!(narrow_i8(OverflowPolicy::Fault, -128), Ok(-128));
+    }
+
+    #[test]
+    fn from_str_when_each_name_then_round_trips() {
+        for policy in [
+            OverflowPolicy::Wrap,
+            OverflowPolicy::Saturate,
+            OverflowPolicy::Fault,
+        ] {
+            assert_eq!(policy.as_str().parse(), Ok(policy));
+        }
+        assert_eq!("clamp".parse::<OverflowPolicy>(), Err(()));
+    }
+}
@@ -27,6 +27,11 @@ impl<'a> OperandStack<'a> {
         self.len
     }
 
+    /// Discards every value on the stack.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
     /// Pushes a slot onto the stack.
     pub fn push(&mut self, slot: Slot) -> Result<(), Trap> {
         if self.len >= self.data.len() {
@@ -18,6 +18,17 @@ impl Slot {
         self.0 as i32
     }
 
+    /// Creates a slot from a 32-bit unsigned integer, stored as the I32
+    /// with the same bits so that it reads back through `as_i32`.
+    pub fn from_u32(v: u32) -> Self {
+        Slot::from_i32(v as i32)
+    }
+
+    /// Extracts a 32-bit unsigned integer from this slot.
+    pub fn as_u32(self) -> u32 {
+        self.0 as u32
+    }
+
     /// Creates a slot from a 64-bit signed integer.
     pub fn from_i64(v: i64) -> Self {
         Slot(v as u64)
@@ -88,6 +99,13 @@ mod tests {
         assert_eq!(slot.0, 0xFFFFFFFFFFFFFFFF);
     }
 
+    #[test]
+    fn slot_from_u32_when_above_i32_max_then_same_bits_as_i32() {
+        let slot = Slot::from_u32(4_000_000_000);
+        assert_eq!(slot.as_u32(), 4_000_000_000);
+        assert_eq!(slot, Slot::from_i32(4_000_000_000u32 as i32));
+    }
+
     #[test]
     fn slot_from_i64_when_negative_then_roundtrips() {
         let slot = Slot::from_i64(-1);
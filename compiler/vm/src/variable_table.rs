@@ -72,6 +72,14 @@ impl<'a> VariableTable<'a> {
         Ok(())
     }
 
+    /// Overwrites every slot with the raw values in `raw`, which must hold
+    /// exactly [`len`](Self::len) values.
+    pub fn restore_raw(&mut self, raw: &[u64]) {
+        for (slot, &value) in self.slots.iter_mut().zip(raw) {
+            *slot = Slot::from_u64(value);
+        }
+    }
+
     /// Copies pre-computed Slot values from a template byte slice into
     /// consecutive variable slots starting at `start`.
     ///
@@ -7,8 +7,10 @@ use crate::buffers::VmBuffers;
 use crate::builtin;
 use crate::debug::PauseReason;
 use crate::debug_hook::{DebugHook, HookAction, NoopDebugHook};
-use crate::error::Trap;
+use crate::error::{SnapshotMismatch, Trap};
+use crate::force::ForceTable;
 use crate::frame_stack::{FbCallReturn, Frame, FrameStack};
+use crate::overflow::{OverflowCheck, OverflowPolicy};
 #[cfg(feature = "profiling")]
 use crate::profile::InstructionProfile;
 use crate::scheduler::{ProgramInstanceState, TaskScheduler, TaskState};
@@ -31,14 +33,26 @@ pub struct FaultContext {
 
 /// A newly created VM with no loaded program.
 ///
-/// The only valid operation is [`load`](Vm::load), which consumes
-/// this value and produces a [`VmReady`].
-pub struct Vm;
+/// The only valid operations are configuring it, such as with
+/// [`overflow_policy`](Vm::overflow_policy), and [`load`](Vm::load), which
+/// consumes this value and produces a [`VmReady`].
+pub struct Vm {
+    overflow_policy: OverflowPolicy,
+}
 
 impl Vm {
     /// Creates a new VM.
     pub fn new() -> Self {
-        Vm
+        Vm {
+            overflow_policy: OverflowPolicy::default(),
+        }
+    }
+
+    /// Sets how integer arithmetic and narrowing handle a result outside
+    /// the range of its type (ADR-0002). The default wraps.
+    pub fn overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow_policy = policy;
+        self
     }
 
     /// Loads a container, using caller-provided buffers for execution state.
@@ -94,6 +108,7 @@ impl Vm {
             program_instances: &mut bufs.programs,
             ready_buf: &mut bufs.ready,
             frames: &mut bufs.frames,
+            overflow_policy: self.overflow_policy,
             #[cfg(feature = "profiling")]
             profile: InstructionProfile::new(),
         }
@@ -120,11 +135,17 @@ pub struct VmReady<'a> {
     program_instances: &'a mut [ProgramInstanceState],
     ready_buf: &'a mut [usize],
     frames: &'a mut [Frame],
+    overflow_policy: OverflowPolicy,
     #[cfg(feature = "profiling")]
     profile: InstructionProfile,
 }
 
 impl<'a> VmReady<'a> {
+    /// The overflow policy the VM was configured with.
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
     /// Number of values currently on the operand stack.
     ///
     /// After `start` has run the init functions this is 0: every function
@@ -186,6 +207,11 @@ impl<'a> VmReady<'a> {
                 instance_count: var_table_count,
             };
 
+            let overflow = OverflowCheck {
+                policy: self.overflow_policy,
+                task_id,
+                instance_id,
+            };
             execute(
                 self.container,
                 &mut self.stack,
@@ -195,6 +221,7 @@ impl<'a> VmReady<'a> {
                 self.max_temp_buf_bytes,
                 self.frames,
                 &scope,
+                overflow,
                 0, // init functions don't need real time
                 init_fn,
                 #[cfg(feature = "profiling")]
@@ -218,12 +245,14 @@ impl<'a> VmReady<'a> {
             program_instances: self.program_instances,
             ready_buf: self.ready_buf,
             frames: self.frames,
+            overflow_policy: self.overflow_policy,
             shared_globals_size,
             scan_count: 0,
             stop_requested: false,
             phase: Phase::Ready,
             debug_frame_count: 0,
             debug_temp_alloc_next: 0,
+            forces: ForceTable::new(),
             #[cfg(feature = "profiling")]
             profile: self.profile,
         })
@@ -248,12 +277,14 @@ impl<'a> VmReady<'a> {
             program_instances: self.program_instances,
             ready_buf: self.ready_buf,
             frames: self.frames,
+            overflow_policy: self.overflow_policy,
             shared_globals_size,
             scan_count: initial_scan_count,
             stop_requested: false,
             phase: Phase::Ready,
             debug_frame_count: 0,
             debug_temp_alloc_next: 0,
+            forces: ForceTable::new(),
             #[cfg(feature = "profiling")]
             profile: self.profile,
         }
@@ -328,6 +359,7 @@ pub struct VmRunning<'a> {
     program_instances: &'a mut [ProgramInstanceState],
     ready_buf: &'a mut [usize],
     frames: &'a mut [Frame],
+    overflow_policy: OverflowPolicy,
     shared_globals_size: u16,
     scan_count: u64,
     stop_requested: bool,
@@ -337,6 +369,8 @@ pub struct VmRunning<'a> {
     debug_frame_count: usize,
     /// Temp-buffer allocator bump position preserved across a debug pause.
     debug_temp_alloc_next: u16,
+    /// Values held against the program's writes (see [`ForceTable`]).
+    forces: ForceTable,
     #[cfg(feature = "profiling")]
     profile: InstructionProfile,
 }
@@ -369,7 +403,9 @@ impl<'a> VmRunning<'a> {
         // System variable injection: write monotonic uptime before task execution.
         self.inject_system_uptime(current_time_us);
 
-        // Stub: INPUT_FREEZE (no-op)
+        // INPUT_FREEZE: there is no input image to copy yet, so the phase is
+        // the forced values taking over their variables for this scan.
+        self.forces.apply(&mut self.variables);
 
         for ri in 0..ready_count {
             let task_idx = self.ready_buf[ri];
@@ -422,7 +458,9 @@ impl<'a> VmRunning<'a> {
             scheduler.record_execution(task_idx, elapsed_us, current_time_us);
         }
 
-        // Stub: OUTPUT_FLUSH (no-op)
+        // OUTPUT_FLUSH: there is no output image to hand over yet, so the
+        // phase is the forced values overriding whatever the scan stored.
+        self.forces.apply(&mut self.variables);
 
         // Deliberately no stack-balance assertion here. A completed round
         // leaving values on the operand stack means the bytecode is not
@@ -485,11 +523,13 @@ impl<'a> VmRunning<'a> {
         let task_id = self.program_instances[0].task_id;
 
         if !resuming {
-            // Fresh scan: inject system uptime, then reset the resume state so
-            // run_instance starts with an empty frame stack (the dispatch loop
-            // pushes the entry frame). When resuming, the preserved frame count
-            // is non-zero and the paused frames survive in place.
+            // Fresh scan: inject system uptime and apply forces, then reset the
+            // resume state so run_instance starts with an empty frame stack
+            // (the dispatch loop pushes the entry frame). When resuming, the
+            // preserved frame count is non-zero and the paused frames survive
+            // in place.
             self.inject_system_uptime(current_time_us);
+            self.forces.apply(&mut self.variables);
             self.debug_frame_count = 0;
             self.debug_temp_alloc_next = 0;
         }
@@ -517,6 +557,7 @@ impl<'a> VmRunning<'a> {
                 Ok(RoundOutcome::Paused(reason))
             }
             ExecuteOutcome::Completed => {
+                self.forces.apply(&mut self.variables);
                 self.scan_count += 1;
                 self.phase = Phase::CompletedScan;
                 // A scan step's landing is this boundary, which no
@@ -584,6 +625,11 @@ impl<'a> VmRunning<'a> {
             instance_offset: var_table_offset,
             instance_count: var_table_count,
         };
+        let overflow = OverflowCheck {
+            policy: self.overflow_policy,
+            task_id: self.program_instances[instance_index].task_id,
+            instance_id: self.program_instances[instance_index].instance_id,
+        };
 
         let mut frame_count = frame_count;
         let mut temp_alloc_next = temp_alloc_next;
@@ -596,6 +642,7 @@ impl<'a> VmRunning<'a> {
             self.max_temp_buf_bytes,
             self.frames,
             &scope,
+            overflow,
             current_time_us,
             entry_function_id,
             &mut frame_count,
@@ -670,6 +717,61 @@ impl<'a> VmRunning<'a> {
         self.variables.store(index, Slot::from_u64(value))
     }
 
+    /// Forces the variable at `index` to the raw slot value `raw` until
+    /// [`unforce_variable`](Self::unforce_variable) releases it.
+    ///
+    /// Unlike [`write_variable_raw`](Self::write_variable_raw), the value
+    /// survives the program's own writes: every scan applies it after
+    /// INPUT_FREEZE and again before OUTPUT_FLUSH. It is also written now, so
+    /// a read before the next scan already sees it. Replaces any earlier force
+    /// of the variable or its bits.
+    pub fn force_variable(&mut self, index: VarIndex, raw: u64) -> Result<(), Trap> {
+        self.variables.load(index)?;
+        self.forces.force(index, raw);
+        self.forces.apply(&mut self.variables);
+        Ok(())
+    }
+
+    /// Forces bit `bit` (0 is the least significant) of the variable at
+    /// `index` to `on`, as [`force_variable`](Self::force_variable) does for a
+    /// whole variable. The variable's other bits keep following the program
+    /// unless they are forced too. This is how a single located I/O bit
+    /// packed into a wider variable is held.
+    ///
+    /// A `bit` of 64 or more names no bit of the slot and is rejected with
+    /// [`Trap::InvalidBitIndex`]. The VM does not know the variable's IEC
+    /// type, so a bit past a narrower type's width is for the caller to
+    /// reject.
+    pub fn force_bit(&mut self, index: VarIndex, bit: u8, on: bool) -> Result<(), Trap> {
+        self.variables.load(index)?;
+        if bit >= 64 {
+            return Err(Trap::InvalidBitIndex {
+                var_index: index,
+                bit,
+            });
+        }
+        self.forces.force_bit(index, bit, on);
+        self.forces.apply(&mut self.variables);
+        Ok(())
+    }
+
+    /// Releases every force of the variable at `index`. The variable keeps
+    /// its forced value until the program next writes it. Returns whether
+    /// anything was forced.
+    pub fn unforce_variable(&mut self, index: VarIndex) -> bool {
+        self.forces.unforce(index)
+    }
+
+    /// Releases every force.
+    pub fn clear_forces(&mut self) {
+        self.forces.clear();
+    }
+
+    /// The forced variables.
+    pub fn forces(&self) -> &ForceTable {
+        &self.forces
+    }
+
     /// Returns a reference to the data region.
     pub fn data_region(&self) -> &[u8] {
         self.data_region
@@ -685,6 +787,64 @@ impl<'a> VmRunning<'a> {
         self.scan_count
     }
 
+    /// Puts the VM back at a scan boundary it passed earlier.
+    ///
+    /// `variables` (raw slots, as read by
+    /// [`read_variable_raw`](Self::read_variable_raw)) and `data_region` are a
+    /// copy of the program state taken when `scan_count` scans had completed.
+    /// Any scan in flight is abandoned — its frames and operand-stack values
+    /// are discarded — and the next [`run_round_debug`](Self::run_round_debug)
+    /// starts a fresh scan from the restored state. A debugger uses this to step
+    /// backwards through scan history. Forced variables keep their forced
+    /// values over the restored copy.
+    ///
+    /// The copy must match this VM's buffers: a `variables` slice whose length
+    /// differs from [`num_variables`](Self::num_variables), or a `data_region`
+    /// whose length differs from this VM's, is rejected with a
+    /// [`SnapshotMismatch`]. The VM is unchanged on error.
+    ///
+    /// The scheduler's [`task_states`](Self::task_states) are not part of the
+    /// copy and are left as they are. The debug driver, the only caller that
+    /// rewinds, never updates them (see
+    /// [`run_round_debug`](Self::run_round_debug)), so they hold the same
+    /// values at every scan it can return to. The scan's clock is the caller's
+    /// to restore: it passes it to the next round.
+    pub fn rewind(
+        &mut self,
+        scan_count: u64,
+        variables: &[u64],
+        data_region: &[u8],
+    ) -> Result<(), SnapshotMismatch> {
+        if variables.len() != self.variables.len() as usize {
+            return Err(SnapshotMismatch::Variables {
+                expected: self.variables.len() as usize,
+                actual: variables.len(),
+            });
+        }
+        if data_region.len() != self.data_region.len() {
+            return Err(SnapshotMismatch::DataRegion {
+                expected: self.data_region.len(),
+                actual: data_region.len(),
+            });
+        }
+        self.variables.restore_raw(variables);
+        self.data_region.copy_from_slice(data_region);
+        // Forces belong to the operator, not to the scan being revisited.
+        self.forces.apply(&mut self.variables);
+        self.stack.clear();
+        self.debug_frame_count = 0;
+        self.debug_temp_alloc_next = 0;
+        self.scan_count = scan_count;
+        self.phase = Phase::CompletedScan;
+        Ok(())
+    }
+
+    /// The scheduler's per-task state: counts, timing, and overruns as of the
+    /// last completed round. Monitoring tools read it between rounds.
+    pub fn task_states(&self) -> &[TaskState] {
+        self.task_states
+    }
+
     /// Returns the earliest `next_due_us` across all enabled cyclic tasks,
     /// or `None` if no cyclic tasks exist (e.g. freewheeling only).
     pub fn next_due_us(&self) -> Option<u64> {
@@ -831,6 +991,17 @@ macro_rules! binop {
     }};
 }
 
+/// Integer binary operation under the overflow policy: pop b then a, push
+/// the wrapping, saturating or checked result as the policy chooses.
+macro_rules! overflow_binop {
+    ($stack:expr, $overflow:expr, $as_ty:ident, $from_ty:ident, $wrapping:ident, $saturating:ident, $checked:ident) => {{
+        let b = $stack.pop()?.$as_ty();
+        let a = $stack.pop()?.$as_ty();
+        let result = $overflow.apply(|| a.$wrapping(b), || a.$saturating(b), || a.$checked(b))?;
+        $stack.push(Slot::$from_ty(result))?;
+    }};
+}
+
 /// Comparison: pop b then a, compare, push i32 boolean.
 macro_rules! cmpop {
     ($stack:expr, $as_ty:ident, $a:ident, $b:ident, $cond:expr) => {{
@@ -889,6 +1060,7 @@ fn execute(
     max_temp_buf_bytes: usize,
     frames: &mut [Frame],
     entry_scope: &VariableScope,
+    overflow: OverflowCheck,
     current_time_us: u64,
     entry_function_id: FunctionId,
     #[cfg(feature = "profiling")] profile: &mut InstructionProfile,
@@ -908,6 +1080,7 @@ fn execute(
         max_temp_buf_bytes,
         frames,
         entry_scope,
+        overflow,
         current_time_us,
         entry_function_id,
         &mut frame_count,
@@ -964,6 +1137,7 @@ pub(crate) fn execute_with_hook<H: DebugHook>(
     max_temp_buf_bytes: usize,
     frames: &mut [Frame],
     entry_scope: &VariableScope,
+    overflow: OverflowCheck,
     current_time_us: u64,
     entry_function_id: FunctionId,
     frame_count: &mut usize,
@@ -1130,13 +1304,61 @@ pub(crate) fn execute_with_hook<H: DebugHook>(
                 let value = stack.pop()?;
                 variables.store(target_index, value)?;
             }
-            // --- Integer arithmetic (wrapping) ---
-            opcode::ADD_I32 => binop!(stack, as_i32, from_i32, a, b, a.wrapping_add(b)),
-            opcode::SUB_I32 => binop!(stack, as_i32, from_i32, a, b, a.wrapping_sub(b)),
-            opcode::MUL_I32 => binop!(stack, as_i32, from_i32, a, b, a.wrapping_mul(b)),
-            opcode::ADD_I64 => binop!(stack, as_i64, from_i64, a, b, a.wrapping_add(b)),
-            opcode::SUB_I64 => binop!(stack, as_i64, from_i64, a, b, a.wrapping_sub(b)),
-            opcode::MUL_I64 => binop!(stack, as_i64, from_i64, a, b, a.wrapping_mul(b)),
+            // --- Integer arithmetic (overflow policy, ADR-0002) ---
+            opcode::ADD_I32 => overflow_binop!(
+                stack,
+                overflow,
+                as_i32,
+                from_i32,
+                wrapping_add,
+                saturating_add,
+                checked_add
+            ),
+            opcode::SUB_I32 => overflow_binop!(
+                stack,
+                overflow,
+                as_i32,
+                from_i32,
+                wrapping_sub,
+                saturating_sub,
+                checked_sub
+            ),
+            opcode::MUL_I32 => overflow_binop!(
+                stack,
+                overflow,
+                as_i32,
+                from_i32,
+                wrapping_mul,
+                saturating_mul,
+                checked_mul
+            ),
+            opcode::ADD_I64 => overflow_binop!(
+                stack,
+                overflow,
+                as_i64,
+                from_i64,
+                wrapping_add,
+                saturating_add,
+                checked_add
+            ),
+            opcode::SUB_I64 => overflow_binop!(
+                stack,
+                overflow,
+                as_i64,
+                from_i64,
+                wrapping_sub,
+                saturating_sub,
+                checked_sub
+            ),
+            opcode::MUL_I64 => overflow_binop!(
+                stack,
+                overflow,
+                as_i64,
+                from_i64,
+                wrapping_mul,
+                saturating_mul,
+                checked_mul
+            ),
             // --- Integer division (checked for zero) ---
             opcode::DIV_I32 => {
                 checked_divop!(stack, as_i32, from_i32, 0i32, a, b, a.wrapping_div(b))
@@ -1197,15 +1419,35 @@ pub(crate) fn execute_with_hook<H: DebugHook>(
             opcode::MUL_F64 => binop!(stack, as_f64, from_f64, a, b, a * b),
             opcode::DIV_F64 => binop!(stack, as_f64, from_f64, a, b, a / b),
             // --- Negation ---
-            opcode::NEG_I32 => unaryop!(stack, as_i32, from_i32, a, a.wrapping_neg()),
-            opcode::NEG_I64 => unaryop!(stack, as_i64, from_i64, a, a.wrapping_neg()),
+            opcode::NEG_I32 => unaryop!(stack, as_i32, from_i32, a, {
+                overflow.apply(
+                    || a.wrapping_neg(),
+                    || a.saturating_neg(),
+                    || a.checked_neg(),
+                )?
+            }),
+            opcode::NEG_I64 => unaryop!(stack, as_i64, from_i64, a, {
+                overflow.apply(
+                    || a.wrapping_neg(),
+                    || a.saturating_neg(),
+                    || a.checked_neg(),
+                )?
+            }),
             opcode::NEG_F32 => unaryop!(stack, as_f32, from_f32, a, -a),
             opcode::NEG_F64 => unaryop!(stack, as_f64, from_f64, a, -a),
-            // --- Truncation ---
-            opcode::TRUNC_I8 => unaryop!(stack, as_i32, from_i32, a, (a as i8) as i32),
-            opcode::TRUNC_U8 => unaryop!(stack, as_i32, from_i32, a, (a as u8) as i32),
-            opcode::TRUNC_I16 => unaryop!(stack, as_i32, from_i32, a, (a as i16) as i32),
-            opcode::TRUNC_U16 => unaryop!(stack, as_i32, from_i32, a, (a as u16) as i32),
+            // --- Truncation (overflow policy, ADR-0002) ---
+            opcode::TRUNC_I8 => unaryop!(stack, as_i32, from_i32, a, {
+                overflow.narrow(a, i8::MIN.into(), i8::MAX.into(), |v| (v as i8) as i32)?
+            }),
+            opcode::TRUNC_U8 => unaryop!(stack, as_i32, from_i32, a, {
+                overflow.narrow(a, u8::MIN.into(), u8::MAX.into(), |v| (v as u8) as i32)?
+            }),
+            opcode::TRUNC_I16 => unaryop!(stack, as_i32, from_i32, a, {
+                overflow.narrow(a, i16::MIN.into(), i16::MAX.into(), |v| (v as i16) as i32)?
+            }),
+            opcode::TRUNC_U16 => unaryop!(stack, as_i32, from_i32, a, {
+                overflow.narrow(a, u16::MIN.into(), u16::MAX.into(), |v| (v as u16) as i32)?
+            }),
             // --- Signed comparison ---
             opcode::EQ_I32 => cmpop!(stack, as_i32, a, b, a == b),
             opcode::NE_I32 => cmpop!(stack, as_i32, a, b, a != b),
@@ -1309,6 +1551,55 @@ pub(crate) fn execute_with_hook<H: DebugHook>(
                     pc = (pc as isize + offset as isize) as usize;
                 }
             }
+            opcode::RANGE_CHECK_I32
+            | opcode::RANGE_CHECK_I64
+            | opcode::RANGE_CHECK_U32
+            | opcode::RANGE_CHECK_U64 => {
+                let var_index = VarIndex::new(read_u16_le(bytecode, &mut pc)?);
+                let min_idx = ConstantIndex::new(read_u16_le(bytecode, &mut pc)?);
+                let max_idx = ConstantIndex::new(read_u16_le(bytecode, &mut pc)?);
+                let top = stack.peek()?;
+                let (value, min, max) =
+                    if op == opcode::RANGE_CHECK_I32 || op == opcode::RANGE_CHECK_U32 {
+                        let min = container
+                            .constant_pool
+                            .get_i32(min_idx)
+                            .map_err(|_| Trap::InvalidConstantIndex(min_idx))?;
+                        let max = container
+                            .constant_pool
+                            .get_i32(max_idx)
+                            .map_err(|_| Trap::InvalidConstantIndex(max_idx))?;
+                        let value = top.as_i32();
+                        if op == opcode::RANGE_CHECK_I32 {
+                            (value as i128, min as i128, max as i128)
+                        } else {
+                            (value as u32 as i128, min as u32 as i128, max as u32 as i128)
+                        }
+                    } else {
+                        let min = container
+                            .constant_pool
+                            .get_i64(min_idx)
+                            .map_err(|_| Trap::InvalidConstantIndex(min_idx))?;
+                        let max = container
+                            .constant_pool
+                            .get_i64(max_idx)
+                            .map_err(|_| Trap::InvalidConstantIndex(max_idx))?;
+                        let value = top.as_i64();
+                        if op == opcode::RANGE_CHECK_I64 {
+                            (value as i128, min as i128, max as i128)
+                        } else {
+                            (value as u64 as i128, min as u64 as i128, max as u64 as i128)
+                        }
+                    };
+                if value < min || value > max {
+                    return Err(Trap::RangeViolation {
+                        var_index,
+                        value,
+                        min,
+                        max,
+                    });
+                }
+            }
             opcode::BUILTIN => {
                 let func_id = read_u16_le(bytecode, &mut pc)?;
                 match func_id {
@@ -1458,6 +1749,64 @@ pub(crate) fn execute_with_hook<H: DebugHook>(
                         };
                         stack.push(Slot::from_i32(cmp_val))?;
                     }
+                    // --- Unsigned arithmetic (overflow policy, ADR-0002) ---
+                    //
+                    // Handled inline because the overflow check needs the
+                    // policy and the unsigned bounds of UDINT and ULINT.
+                    opcode::builtin::ADD_U32 => overflow_binop!(
+                        stack,
+                        overflow,
+                        as_u32,
+                        from_u32,
+                        wrapping_add,
+                        saturating_add,
+                        checked_add
+                    ),
+                    opcode::builtin::SUB_U32 => overflow_binop!(
+                        stack,
+                        overflow,
+                        as_u32,
+                        from_u32,
+                        wrapping_sub,
+                        saturating_sub,
+                        checked_sub
+                    ),
+                    opcode::builtin::MUL_U32 => overflow_binop!(
+                        stack,
+                        overflow,
+                        as_u32,
+                        from_u32,
+                        wrapping_mul,
+                        saturating_mul,
+                        checked_mul
+                    ),
+                    opcode::builtin::ADD_U64 => overflow_binop!(
+                        stack,
+                        overflow,
+                        as_u64,
+                        from_u64,
+                        wrapping_add,
+                        saturating_add,
+                        checked_add
+                    ),
+                    opcode::builtin::SUB_U64 => overflow_binop!(
+                        stack,
+                        overflow,
+                        as_u64,
+                        from_u64,
+                        wrapping_sub,
+                        saturating_sub,
+                        checked_sub
+                    ),
+                    opcode::builtin::MUL_U64 => overflow_binop!(
+                        stack,
+                        overflow,
+                        as_u64,
+                        from_u64,
+                        wrapping_mul,
+                        saturating_mul,
+                        checked_mul
+                    ),
                     _ => builtin::dispatch(func_id, stack)?,
                 }
             }
@@ -2536,6 +2885,38 @@ pub(crate) fn execute_with_hook<H: DebugHook>(
                 data_region[byte_offset..byte_offset + 8]
                     .copy_from_slice(&value_slot.as_i64().to_le_bytes());
             }
+            opcode::LOAD_ARRAY_UNCHECKED => {
+                let var_index = VarIndex::new(read_u16_le(bytecode, &mut pc)?);
+                let _desc_index = read_u16_le(bytecode, &mut pc)?;
+                let index_slot = stack.pop()?;
+
+                scope.check_access(var_index)?;
+
+                // Codegen proved the index in bounds, so only the data
+                // region itself is checked.
+                let data_offset = variables.load(var_index)?.as_i32() as u32 as usize;
+                let byte_offset =
+                    unchecked_element_offset(data_offset, index_slot.as_i64(), data_region.len())?;
+
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(&data_region[byte_offset..byte_offset + 8]);
+                stack.push(Slot::from_i64(i64::from_le_bytes(buf)))?;
+            }
+            opcode::STORE_ARRAY_UNCHECKED => {
+                let var_index = VarIndex::new(read_u16_le(bytecode, &mut pc)?);
+                let _desc_index = read_u16_le(bytecode, &mut pc)?;
+                let index_slot = stack.pop()?;
+                let value_slot = stack.pop()?;
+
+                scope.check_access(var_index)?;
+
+                let data_offset = variables.load(var_index)?.as_i32() as u32 as usize;
+                let byte_offset =
+                    unchecked_element_offset(data_offset, index_slot.as_i64(), data_region.len())?;
+
+                data_region[byte_offset..byte_offset + 8]
+                    .copy_from_slice(&value_slot.as_i64().to_le_bytes());
+            }
             opcode::LOAD_ARRAY_DEREF => {
                 let ref_var_index = VarIndex::new(read_u16_le(bytecode, &mut pc)?);
                 let desc_index = read_u16_le(bytecode, &mut pc)?;
@@ -2663,6 +3044,26 @@ pub(crate) fn execute_with_hook<H: DebugHook>(
     Ok(ExecuteOutcome::Completed)
 }
 
+/// Byte offset of element `index` of the array at `data_offset` for the
+/// `*_ARRAY_UNCHECKED` opcodes. The element count is not consulted, but an
+/// element outside the data region still traps.
+fn unchecked_element_offset(
+    data_offset: usize,
+    index: i64,
+    region_len: usize,
+) -> Result<usize, Trap> {
+    let offset = usize::try_from(index)
+        .ok()
+        .and_then(|index| index.checked_mul(8))
+        .and_then(|offset| offset.checked_add(data_offset));
+    match offset {
+        Some(offset) if offset.checked_add(8).is_some_and(|end| end <= region_len) => Ok(offset),
+        _ => Err(Trap::DataRegionOutOfBounds(
+            offset.map_or(u32::MAX, |offset| offset as u32),
+        )),
+    }
+}
+
 /// Commit the dispatch loop's working `pc` back onto the topmost frame.
 ///
 /// This is the single "store pc to the frame" operation behind every
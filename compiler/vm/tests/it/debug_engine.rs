@@ -4,6 +4,7 @@
 
 use crate::common::{single_function_container, VmBuffers};
 use ironplc_container::{opcode, ContainerBuilder, FunctionId, VarIndex};
+use ironplc_vm::error::SnapshotMismatch;
 use ironplc_vm::{
     BreakpointTable, DebugHook, DebuggerHook, HookAction, PauseReason, Phase, RoundOutcome,
 };
@@ -200,6 +201,32 @@ fn run_round_debug_when_breakpoint_in_entry_then_pauses_there_and_resumes_to_com
     assert_eq!(vm.read_variable(VarIndex::new(1)).unwrap(), 42);
 }
 
+#[test]
+fn run_round_debug_when_pause_requested_then_pauses_mid_scan_and_resumes_to_completion() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let c = single_function_container(&steel_thread_scan(), 2, &[10, 32]);
+    let mut b = VmBuffers::from_container(&c);
+    let mut vm = crate::common::load_and_start(&c, &mut b).unwrap();
+
+    // A flag raised before the round stands in for a `pause` that arrives
+    // from another thread while the program free-runs.
+    let table = BreakpointTable::new();
+    let flag = AtomicBool::new(true);
+    let mut hook = DebuggerHook::new(&table);
+    hook.watch_pause_requests(&flag);
+
+    let outcome = vm.run_round_debug(0, &mut hook).unwrap();
+    assert_eq!(outcome, RoundOutcome::Paused(PauseReason::Requested));
+    assert_eq!(vm.debug_frames().len(), 1);
+    assert_eq!(vm.debug_frames()[0].pc, 0);
+    assert!(!flag.load(Ordering::Acquire));
+
+    let outcome = vm.run_round_debug(0, &mut hook).unwrap();
+    assert_eq!(outcome, RoundOutcome::Completed);
+    assert_eq!(vm.read_variable(VarIndex::new(1)).unwrap(), 42);
+}
+
 #[test]
 fn run_round_debug_when_breakpoint_in_callee_then_frame_stack_shows_caller_beneath() {
     // Same doubling function as the callback test.
@@ -579,3 +606,61 @@ fn run_round_debug_when_no_step_scan_then_completed_scan_is_not_a_pause() {
         RoundOutcome::Completed
     );
 }
+
+#[test]
+fn rewind_when_paused_mid_scan_then_abandons_scan_and_restores_boundary_state() {
+    let c = incrementing_scan_container();
+    let mut b = VmBuffers::from_container(&c);
+    let mut vm = crate::common::load_and_start(&c, &mut b).unwrap();
+
+    // Snapshot the boundary after scan 1.
+    let table = BreakpointTable::new();
+    let mut hook = DebuggerHook::new(&table);
+    vm.run_round_debug(0, &mut hook).unwrap();
+    let saved = [vm.read_variable_raw(VarIndex::new(0)).unwrap()];
+    let saved_data = vm.data_region().to_vec();
+
+    // Run on and pause mid-scan 2 with a value on the operand stack.
+    let mut table = BreakpointTable::new();
+    table.add(FunctionId::SCAN, 6); // the ADD, with both operands pushed
+    let mut hook = DebuggerHook::new(&table);
+    vm.run_round_debug(1000, &mut hook).unwrap();
+    assert_eq!(vm.operand_stack_depth(), 2);
+
+    vm.rewind(1, &saved, &saved_data).unwrap();
+    assert_eq!(vm.scan_count(), 1);
+    assert_eq!(vm.read_variable(VarIndex::new(0)).unwrap(), 1);
+    assert_eq!(vm.operand_stack_depth(), 0);
+    assert!(vm.debug_frames().is_empty());
+
+    // The next round is a fresh scan from the restored state.
+    let table = BreakpointTable::new();
+    let mut hook = DebuggerHook::new(&table);
+    assert_eq!(
+        vm.run_round_debug(2000, &mut hook).unwrap(),
+        RoundOutcome::Completed
+    );
+    assert_eq!(vm.read_variable(VarIndex::new(0)).unwrap(), 2);
+    assert_eq!(vm.scan_count(), 2);
+}
+
+#[test]
+fn rewind_when_variable_count_differs_then_error_and_vm_unchanged() {
+    let c = incrementing_scan_container();
+    let mut b = VmBuffers::from_container(&c);
+    let mut vm = crate::common::load_and_start(&c, &mut b).unwrap();
+    let table = BreakpointTable::new();
+    let mut hook = DebuggerHook::new(&table);
+    vm.run_round_debug(0, &mut hook).unwrap();
+
+    let data = vm.data_region().to_vec();
+    assert_eq!(
+        vm.rewind(0, &[0, 0, 0], &data),
+        Err(SnapshotMismatch::Variables {
+            expected: 1,
+            actual: 3
+        })
+    );
+    assert_eq!(vm.scan_count(), 1);
+    assert_eq!(vm.read_variable(VarIndex::new(0)).unwrap(), 1);
+}
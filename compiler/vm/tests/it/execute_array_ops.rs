@@ -163,3 +163,45 @@ fn execute_when_load_array_uninitialized_then_returns_zero() {
 
     assert_eq!(vm.read_variable(VarIndex::new(1)).unwrap(), 0);
 }
+
+#[test]
+fn execute_when_store_array_unchecked_then_load_array_unchecked_roundtrips_i32() {
+    #[rustfmt::skip]
+    let bytecode: Vec<u8> = vec![
+        opcode::LOAD_CONST_I32,        0x00, 0x00,    // push 42 (value)
+        opcode::LOAD_CONST_I32,        0x01, 0x00,    // push 4 (index)
+        opcode::STORE_ARRAY_UNCHECKED, 0x00, 0x00, 0x00, 0x00,
+
+        opcode::LOAD_CONST_I32,        0x01, 0x00,    // push 4 (index)
+        opcode::LOAD_ARRAY_UNCHECKED,  0x00, 0x00, 0x00, 0x00,
+
+        opcode::STORE_VAR_I32,         0x01, 0x00,
+        opcode::RET_VOID,
+    ];
+    let c = array_container(&bytecode, 5, &[42, 4]);
+    let mut b = VmBuffers::from_container(&c);
+    let mut vm = crate::common::load_and_start(&c, &mut b).unwrap();
+    vm.run_round(0).unwrap();
+
+    assert_eq!(vm.read_variable(VarIndex::new(1)).unwrap(), 42);
+}
+
+/// The unchecked opcodes skip the element-count check but never reach
+/// outside the data region.
+#[rstest]
+#[case::negative_index(-1)]
+#[case::past_data_region(5)]
+fn execute_when_load_array_unchecked_outside_data_region_then_trap(#[case] index: i32) {
+    #[rustfmt::skip]
+    let bytecode: Vec<u8> = vec![
+        opcode::LOAD_CONST_I32,       0x00, 0x00,    // push index
+        opcode::LOAD_ARRAY_UNCHECKED, 0x00, 0x00, 0x00, 0x00,
+        opcode::RET_VOID,
+    ];
+    let c = array_container(&bytecode, 5, &[index]);
+    let mut b = VmBuffers::from_container(&c);
+    let mut vm = crate::common::load_and_start(&c, &mut b).unwrap();
+    let err = vm.run_round(0).unwrap_err();
+
+    assert!(matches!(err.trap, Trap::DataRegionOutOfBounds(_)));
+}
@@ -0,0 +1,112 @@
+//! Integration tests for the RANGE_CHECK opcodes.
+
+use crate::common::{
+    run_and_expect_trap_i32, run_and_read_i32, run_and_read_i64, single_function_container_i64,
+    VmBuffers,
+};
+use ironplc_container::{opcode, VarIndex};
+use ironplc_vm::error::Trap;
+use ironplc_vm::test_support::load_and_start;
+use spec_test_macro::spec_test;
+
+/// `var[0] := pool[0]`, checked against `[pool[1], pool[2]]`.
+#[rustfmt::skip]
+fn checked_store(check: u8, load: u8, store: u8) -> Vec<u8> {
+    vec![
+        load, 0x00, 0x00,
+        check, 0x00, 0x00, 0x01, 0x00, 0x02, 0x00,
+        store, 0x00, 0x00,
+        opcode::RET_VOID,
+    ]
+}
+
+fn checked_store_i32(check: u8) -> Vec<u8> {
+    checked_store(check, opcode::LOAD_CONST_I32, opcode::STORE_VAR_I32)
+}
+
+#[test]
+fn execute_when_range_check_i32_value_at_bounds_then_stores() {
+    let bytecode = checked_store_i32(opcode::RANGE_CHECK_I32);
+    assert_eq!(run_and_read_i32(&bytecode, 1, &[0, 0, 100]), 0);
+    assert_eq!(run_and_read_i32(&bytecode, 1, &[100, 0, 100]), 100);
+}
+
+#[spec_test(REQ_RT_vm_007)]
+fn execute_when_range_check_i32_value_above_max_then_range_violation() {
+    let trap = run_and_expect_trap_i32(
+        &checked_store_i32(opcode::RANGE_CHECK_I32),
+        1,
+        &[101, 0, 100],
+    );
+    assert_eq!(
+        trap,
+        Trap::RangeViolation {
+            var_index: VarIndex::new(0),
+            value: 101,
+            min: 0,
+            max: 100,
+        }
+    );
+}
+
+#[test]
+fn execute_when_range_check_i32_negative_value_below_min_then_range_violation() {
+    let trap = run_and_expect_trap_i32(
+        &checked_store_i32(opcode::RANGE_CHECK_I32),
+        1,
+        &[-11, -10, 10],
+    );
+    assert!(matches!(trap, Trap::RangeViolation { value: -11, .. }));
+}
+
+#[test]
+fn execute_when_range_check_u32_large_value_then_compares_unsigned() {
+    // 0xFFFF_FFFF is -1 as i32 but 4294967295 as u32: outside [0, 100].
+    let trap = run_and_expect_trap_i32(
+        &checked_store_i32(opcode::RANGE_CHECK_U32),
+        1,
+        &[-1, 0, 100],
+    );
+    assert!(matches!(
+        trap,
+        Trap::RangeViolation {
+            value: 4_294_967_295,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn execute_when_range_check_i64_value_in_range_then_stores() {
+    let bytecode = checked_store(
+        opcode::RANGE_CHECK_I64,
+        opcode::LOAD_CONST_I64,
+        opcode::STORE_VAR_I64,
+    );
+    assert_eq!(
+        run_and_read_i64(&bytecode, 1, &[5_000_000_000, 0, 10_000_000_000]),
+        5_000_000_000
+    );
+}
+
+#[test]
+fn execute_when_range_check_i64_value_below_min_then_range_violation() {
+    let bytecode = checked_store(
+        opcode::RANGE_CHECK_I64,
+        opcode::LOAD_CONST_I64,
+        opcode::STORE_VAR_I64,
+    );
+    let c = single_function_container_i64(&bytecode, 1, &[-1, 0, 10_000_000_000]);
+    let mut b = VmBuffers::from_container(&c);
+    let mut vm = load_and_start(&c, &mut b).unwrap();
+    let trap = vm.run_round(0).unwrap_err().trap;
+    assert_eq!(
+        trap,
+        Trap::RangeViolation {
+            var_index: VarIndex::new(0),
+            value: -1,
+            min: 0,
+            max: 10_000_000_000,
+        }
+    );
+}
@@ -0,0 +1,169 @@
+//! Integration tests for variable forcing: values the VM holds against the
+//! program's own writes at the scan's process-image boundaries.
+
+use crate::common::{load_and_start, single_function_container, VmBuffers};
+use ironplc_container::{opcode, Container, VarIndex};
+use ironplc_vm::error::Trap;
+use ironplc_vm::{NoopDebugHook, RoundOutcome};
+use spec_test_macro::spec_test;
+
+/// `output := input + 1; input := 100;` with `input` at `var[0]` and `output`
+/// at `var[1]`: the scan reads its input and then overwrites it.
+fn input_output_container() -> Container {
+    #[rustfmt::skip]
+    let bytecode: Vec<u8> = vec![
+        opcode::LOAD_VAR_I32, 0x00, 0x00,   // input
+        opcode::LOAD_CONST_I32, 0x00, 0x00, // 1
+        opcode::ADD_I32,
+        opcode::STORE_VAR_I32, 0x01, 0x00,  // output := input + 1
+        opcode::LOAD_CONST_I32, 0x01, 0x00, // 100
+        opcode::STORE_VAR_I32, 0x00, 0x00,  // input := 100
+        opcode::RET_VOID,
+    ];
+    single_function_container(&bytecode, 2, &[1, 100])
+}
+
+#[spec_test(REQ_RT_vm_002)]
+fn run_round_when_input_forced_then_scan_reads_it_and_program_write_does_not_stick() {
+    let container = input_output_container();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+
+    vm.force_variable(VarIndex::new(0), 5).unwrap();
+    vm.run_round(0).unwrap();
+
+    assert_eq!(vm.read_variable(VarIndex::new(1)).unwrap(), 6);
+    assert_eq!(vm.read_variable(VarIndex::new(0)).unwrap(), 5);
+}
+
+#[spec_test(REQ_RT_vm_003)]
+fn run_round_when_output_forced_then_scan_result_is_overridden() {
+    let container = input_output_container();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+
+    vm.force_variable(VarIndex::new(1), 0).unwrap();
+    vm.run_round(0).unwrap();
+    vm.run_round(0).unwrap();
+
+    assert_eq!(vm.read_variable(VarIndex::new(1)).unwrap(), 0);
+}
+
+#[spec_test(REQ_RT_vm_004)]
+fn run_round_when_one_bit_forced_then_other_bits_follow_program() {
+    let container = input_output_container();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+
+    vm.force_bit(VarIndex::new(0), 0, true).unwrap();
+    vm.run_round(0).unwrap();
+
+    // The program stored 100 (0b110_0100); bit 0 stays forced on.
+    assert_eq!(vm.read_variable(VarIndex::new(0)).unwrap(), 101);
+}
+
+#[test]
+fn run_round_when_unforced_then_program_write_sticks_again() {
+    let container = input_output_container();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+
+    vm.force_variable(VarIndex::new(0), 5).unwrap();
+    assert!(vm.unforce_variable(VarIndex::new(0)));
+    vm.run_round(0).unwrap();
+
+    assert_eq!(vm.read_variable(VarIndex::new(1)).unwrap(), 6);
+    assert_eq!(vm.read_variable(VarIndex::new(0)).unwrap(), 100);
+    assert!(vm.forces().is_empty());
+}
+
+#[test]
+fn force_variable_when_set_then_readable_before_next_scan() {
+    let container = input_output_container();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+
+    vm.force_variable(VarIndex::new(1), 42).unwrap();
+
+    assert_eq!(vm.read_variable(VarIndex::new(1)).unwrap(), 42);
+    assert!(vm.forces().get(VarIndex::new(1)).unwrap().is_whole());
+}
+
+#[test]
+fn force_variable_when_index_out_of_range_then_invalid_variable_index() {
+    let container = input_output_container();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+
+    assert_eq!(
+        vm.force_variable(VarIndex::new(2), 1),
+        Err(Trap::InvalidVariableIndex(VarIndex::new(2)))
+    );
+    assert_eq!(
+        vm.force_bit(VarIndex::new(2), 0, true),
+        Err(Trap::InvalidVariableIndex(VarIndex::new(2)))
+    );
+    assert!(vm.forces().is_empty());
+}
+
+#[test]
+fn force_bit_when_bit_past_slot_then_invalid_bit_index() {
+    let container = input_output_container();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+
+    assert_eq!(
+        vm.force_bit(VarIndex::new(0), 64, true),
+        Err(Trap::InvalidBitIndex {
+            var_index: VarIndex::new(0),
+            bit: 64
+        })
+    );
+    assert_eq!(
+        vm.force_bit(VarIndex::new(0), u8::MAX, false),
+        Err(Trap::InvalidBitIndex {
+            var_index: VarIndex::new(0),
+            bit: u8::MAX
+        })
+    );
+    assert!(vm.forces().is_empty());
+}
+
+#[test]
+fn force_bit_when_last_bit_of_slot_then_forced() {
+    let container = input_output_container();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+
+    vm.force_bit(VarIndex::new(0), 63, true).unwrap();
+
+    assert_eq!(vm.forces().get(VarIndex::new(0)).unwrap().mask, 1 << 63);
+}
+
+#[test]
+fn run_round_debug_when_output_forced_then_completed_scan_holds_forced_value() {
+    let container = input_output_container();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+
+    vm.force_variable(VarIndex::new(0), 5).unwrap();
+    vm.force_variable(VarIndex::new(1), 0).unwrap();
+    let outcome = vm.run_round_debug(0, &mut NoopDebugHook).unwrap();
+
+    assert_eq!(outcome, RoundOutcome::Completed);
+    assert_eq!(vm.read_variable(VarIndex::new(0)).unwrap(), 5);
+    assert_eq!(vm.read_variable(VarIndex::new(1)).unwrap(), 0);
+}
+
+#[test]
+fn rewind_when_variable_forced_then_restored_state_keeps_forced_value() {
+    let container = input_output_container();
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = load_and_start(&container, &mut bufs).unwrap();
+
+    vm.force_variable(VarIndex::new(0), 5).unwrap();
+    vm.rewind(0, &[9, 9], &[]).unwrap();
+
+    assert_eq!(vm.read_variable(VarIndex::new(0)).unwrap(), 5);
+    assert_eq!(vm.read_variable(VarIndex::new(1)).unwrap(), 9);
+}
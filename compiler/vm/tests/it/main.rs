@@ -60,10 +60,13 @@ mod execute_loops;
 mod execute_mod_i32;
 mod execute_mul_i32;
 mod execute_neg_i32;
+mod execute_range_check;
 mod execute_stack_overflow;
 mod execute_string_ops;
 mod execute_sub_i32;
+mod force;
 mod load_max_call_depth;
+mod overflow_policy;
 mod profiling;
 mod proptest_robustness;
 mod scenarios;
@@ -0,0 +1,299 @@
+//! Integration tests for the integer overflow policy (ADR-0002): the same
+//! bytecode wraps, saturates or faults as the VM is configured.
+
+use crate::common::{single_function_container, single_function_container_i64, VmBuffers};
+use ironplc_container::{opcode, Container, InstanceId, TaskId, VarIndex};
+use ironplc_vm::error::Trap;
+use ironplc_vm::{OverflowPolicy, Vm};
+use spec_test_macro::spec_test;
+
+/// `var[0] := pool[0] <op> pool[1]` on 32-bit values.
+#[rustfmt::skip]
+fn binary_i32(op: u8) -> Vec<u8> {
+    vec![
+        opcode::LOAD_CONST_I32, 0x00, 0x00,
+        opcode::LOAD_CONST_I32, 0x01, 0x00,
+        op,
+        opcode::STORE_VAR_I32, 0x00, 0x00,
+        opcode::RET_VOID,
+    ]
+}
+
+/// `var[0] := <op>(pool[0])` on 32-bit values.
+#[rustfmt::skip]
+fn unary_i32(op: u8) -> Vec<u8> {
+    vec![
+        opcode::LOAD_CONST_I32, 0x00, 0x00,
+        op,
+        opcode::STORE_VAR_I32, 0x00, 0x00,
+        opcode::RET_VOID,
+    ]
+}
+
+/// `var[0] := pool[0] <builtin> pool[1]` for a two-argument builtin.
+#[rustfmt::skip]
+fn builtin_binary(func_id: u16, load: u8, store: u8) -> Vec<u8> {
+    let id = func_id.to_le_bytes();
+    vec![
+        load, 0x00, 0x00,
+        load, 0x01, 0x00,
+        opcode::BUILTIN, id[0], id[1],
+        store, 0x00, 0x00,
+        opcode::RET_VOID,
+    ]
+}
+
+fn run_u32(func_id: u16, constants: &[u32], policy: OverflowPolicy) -> Result<u32, Trap> {
+    let bytecode = builtin_binary(func_id, opcode::LOAD_CONST_I32, opcode::STORE_VAR_I32);
+    let constants: Vec<i32> = constants.iter().map(|c| *c as i32).collect();
+    run_i32(&bytecode, &constants, policy).map(|v| v as u32)
+}
+
+fn run_u64(func_id: u16, constants: &[u64], policy: OverflowPolicy) -> Result<u64, Trap> {
+    let bytecode = builtin_binary(func_id, opcode::LOAD_CONST_I64, opcode::STORE_VAR_I64);
+    let constants: Vec<i64> = constants.iter().map(|c| *c as i64).collect();
+    let container = single_function_container_i64(&bytecode, 1, &constants);
+    run(&container, policy)
+}
+
+/// Runs one round of the container under the policy and returns `var[0]`
+/// as the raw slot, or the trap.
+fn run(container: &Container, policy: OverflowPolicy) -> Result<u64, Trap> {
+    let mut bufs = VmBuffers::from_container(container);
+    let mut vm = Vm::new()
+        .overflow_policy(policy)
+        .load(container, &mut bufs)
+        .start()
+        .map_err(|ctx| ctx.trap)?;
+    vm.run_round(0).map_err(|ctx| ctx.trap)?;
+    Ok(vm.read_variable_raw(VarIndex::new(0)).unwrap())
+}
+
+fn run_i32(bytecode: &[u8], constants: &[i32], policy: OverflowPolicy) -> Result<i32, Trap> {
+    let container = single_function_container(bytecode, 1, constants);
+    run(&container, policy).map(|raw| raw as i32)
+}
+
+fn overflow() -> Trap {
+    Trap::IntegerOverflow {
+        task_id: TaskId::DEFAULT,
+        instance_id: InstanceId::DEFAULT,
+    }
+}
+
+#[test]
+fn trunc_i8_when_wrap_then_low_bits() {
+    let result = run_i32(&unary_i32(opcode::TRUNC_I8), &[150], OverflowPolicy::Wrap);
+    assert_eq!(result, Ok(-106));
+}
+
+#[spec_test(REQ_RT_vm_006)]
+fn trunc_i8_when_saturate_then_sint_max() {
+    let result = run_i32(
+        &unary_i32(opcode::TRUNC_I8),
+        &[150],
+        OverflowPolicy::Saturate,
+    );
+    assert_eq!(result, Ok(127));
+}
+
+#[spec_test(REQ_RT_vm_005)]
+fn trunc_i8_when_fault_then_integer_overflow() {
+    let result = run_i32(&unary_i32(opcode::TRUNC_I8), &[150], OverflowPolicy::Fault);
+    assert_eq!(result, Err(overflow()));
+}
+
+#[test]
+fn trunc_u8_when_saturate_and_negative_then_zero() {
+    let result = run_i32(
+        &unary_i32(opcode::TRUNC_U8),
+        &[-5],
+        OverflowPolicy::Saturate,
+    );
+    assert_eq!(result, Ok(0));
+}
+
+#[test]
+fn trunc_u16_when_fault_and_in_range_then_value() {
+    let result = run_i32(
+        &unary_i32(opcode::TRUNC_U16),
+        &[65535],
+        OverflowPolicy::Fault,
+    );
+    assert_eq!(result, Ok(65535));
+}
+
+#[test]
+fn add_i32_when_saturate_then_max() {
+    let result = run_i32(
+        &binary_i32(opcode::ADD_I32),
+        &[i32::MAX, 1],
+        OverflowPolicy::Saturate,
+    );
+    assert_eq!(result, Ok(i32::MAX));
+}
+
+#[test]
+fn sub_i32_when_saturate_then_min() {
+    let result = run_i32(
+        &binary_i32(opcode::SUB_I32),
+        &[i32::MIN, 1],
+        OverflowPolicy::Saturate,
+    );
+    assert_eq!(result, Ok(i32::MIN));
+}
+
+#[test]
+fn mul_i32_when_fault_then_integer_overflow() {
+    let result = run_i32(
+        &binary_i32(opcode::MUL_I32),
+        &[65536, 65536],
+        OverflowPolicy::Fault,
+    );
+    assert_eq!(result, Err(overflow()));
+}
+
+#[test]
+fn add_i32_when_fault_and_in_range_then_sum() {
+    let result = run_i32(
+        &binary_i32(opcode::ADD_I32),
+        &[40, 2],
+        OverflowPolicy::Fault,
+    );
+    assert_eq!(result, Ok(42));
+}
+
+#[test]
+fn neg_i32_when_saturate_and_min_then_max() {
+    let result = run_i32(
+        &unary_i32(opcode::NEG_I32),
+        &[i32::MIN],
+        OverflowPolicy::Saturate,
+    );
+    assert_eq!(result, Ok(i32::MAX));
+}
+
+#[test]
+fn add_i64_when_fault_then_integer_overflow() {
+    #[rustfmt::skip]
+    let bytecode = vec![
+        opcode::LOAD_CONST_I64, 0x00, 0x00,
+        opcode::LOAD_CONST_I64, 0x01, 0x00,
+        opcode::ADD_I64,
+        opcode::STORE_VAR_I64, 0x00, 0x00,
+        opcode::RET_VOID,
+    ];
+    let container = single_function_container_i64(&bytecode, 1, &[i64::MAX, 1]);
+
+    assert_eq!(run(&container, OverflowPolicy::Fault), Err(overflow()));
+}
+
+#[test]
+fn neg_i64_when_saturate_and_min_then_max() {
+    #[rustfmt::skip]
+    let bytecode = vec![
+        opcode::LOAD_CONST_I64, 0x00, 0x00,
+        opcode::NEG_I64,
+        opcode::STORE_VAR_I64, 0x00, 0x00,
+        opcode::RET_VOID,
+    ];
+    let container = single_function_container_i64(&bytecode, 1, &[i64::MIN]);
+
+    let result = run(&container, OverflowPolicy::Saturate).map(|raw| raw as i64);
+
+    assert_eq!(result, Ok(i64::MAX));
+}
+
+#[test]
+fn add_u32_when_saturate_and_above_i32_max_then_exact() {
+    let result = run_u32(
+        opcode::builtin::ADD_U32,
+        &[2_000_000_000, 2_000_000_000],
+        OverflowPolicy::Saturate,
+    );
+    assert_eq!(result, Ok(4_000_000_000));
+}
+
+#[test]
+fn add_u32_when_saturate_and_above_u32_max_then_max() {
+    let result = run_u32(
+        opcode::builtin::ADD_U32,
+        &[u32::MAX, 1],
+        OverflowPolicy::Saturate,
+    );
+    assert_eq!(result, Ok(u32::MAX));
+}
+
+#[test]
+fn add_u32_when_wrap_and_above_u32_max_then_low_bits() {
+    let result = run_u32(
+        opcode::builtin::ADD_U32,
+        &[u32::MAX, 2],
+        OverflowPolicy::Wrap,
+    );
+    assert_eq!(result, Ok(1));
+}
+
+#[test]
+fn sub_u32_when_fault_and_below_zero_then_integer_overflow() {
+    let result = run_u32(opcode::builtin::SUB_U32, &[1, 2], OverflowPolicy::Fault);
+    assert_eq!(result, Err(overflow()));
+}
+
+#[test]
+fn mul_u32_when_fault_and_above_i32_max_then_product() {
+    let result = run_u32(
+        opcode::builtin::MUL_U32,
+        &[65536, 65535],
+        OverflowPolicy::Fault,
+    );
+    assert_eq!(result, Ok(65536 * 65535));
+}
+
+#[test]
+fn add_u64_when_fault_and_above_i64_max_then_sum() {
+    let result = run_u64(
+        opcode::builtin::ADD_U64,
+        &[i64::MAX as u64, 1],
+        OverflowPolicy::Fault,
+    );
+    assert_eq!(result, Ok(i64::MAX as u64 + 1));
+}
+
+#[test]
+fn sub_u64_when_saturate_and_below_zero_then_zero() {
+    let result = run_u64(opcode::builtin::SUB_U64, &[1, 2], OverflowPolicy::Saturate);
+    assert_eq!(result, Ok(0));
+}
+
+#[test]
+fn mul_u64_when_saturate_and_above_u64_max_then_max() {
+    let result = run_u64(
+        opcode::builtin::MUL_U64,
+        &[u64::MAX / 2 + 1, 2],
+        OverflowPolicy::Saturate,
+    );
+    assert_eq!(result, Ok(u64::MAX));
+}
+
+#[test]
+fn run_round_when_fault_then_context_and_trap_name_same_task_and_instance() {
+    let container = single_function_container(&binary_i32(opcode::ADD_I32), 1, &[i32::MAX, 1]);
+    let mut bufs = VmBuffers::from_container(&container);
+    let mut vm = Vm::new()
+        .overflow_policy(OverflowPolicy::Fault)
+        .load(&container, &mut bufs)
+        .start()
+        .unwrap();
+
+    let ctx = vm.run_round(0).unwrap_err();
+
+    assert_eq!(
+        ctx.trap,
+        Trap::IntegerOverflow {
+            task_id: ctx.task_id,
+            instance_id: ctx.instance_id,
+        }
+    );
+    assert_eq!(ctx.trap.v_code(), "V4006");
+}
@@ -69,6 +69,19 @@ fn scenario_when_stop_then_scan_count_reflects_completed_rounds() {
     assert_eq!(stopped.read_variable(VarIndex::new(0)).unwrap(), 5);
 }
 
+#[test]
+fn scenario_when_running_then_task_states_count_each_task_execution() {
+    let c = counter_container();
+    let mut b = VmBuffers::from_container(&c);
+    let mut vm = load_and_start(&c, &mut b).unwrap();
+
+    vm.run_round(0).unwrap();
+    vm.run_round(0).unwrap();
+
+    assert_eq!(vm.task_states().len(), 1);
+    assert_eq!(vm.task_states()[0].scan_count, 2);
+}
+
 /// One task with two program instances: the counter runs first, then a
 /// fault program traps. The counter's write is visible on the faulted VM.
 ///
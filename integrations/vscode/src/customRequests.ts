@@ -1,22 +1,36 @@
 import * as vscode from 'vscode';
 import { IRONPLC_DEBUG_TYPE } from './debugAdapter';
-import { customRequestFailedMessage } from './debugAdapterLogic';
+import {
+  ForceArguments,
+  customRequestFailedMessage,
+  parseForceArgument,
+  parseScanNumber,
+} from './debugAdapterLogic';
 
 /**
- * Registers the scan-cycle custom-request command, forwarding IronPLC's
- * `ironplc/stepScan` request to the active debug session; without it the
+ * Registers the scan-cycle custom-request commands, forwarding IronPLC's
+ * `ironplc/stepScan` and `ironplc/goToScan` requests to the active debug
+ * session; without them the
  * request is unreachable from the UI (see
  * `specs/design/debugger-support.md` §"Phase 5"). The debug toolbar button that
  * invokes it is contributed in `package.json`.
  *
  * The server runs the rest of the current scan cycle and stops at the start of
- * the next, so one press advances the program by exactly one cycle.
+ * the next, so one press advances the program by exactly one cycle. Going to a
+ * scan rewinds the program to the start of a cycle in the server's scan
+ * history; it lives in the Command Palette, since it needs a number.
  *
  * The scan *count* is deliberately not a command. It is a value you watch while
  * stepping, not one you ask for: the server publishes it in the `Runtime`
  * scope, which the client re-reads at every stop, so it is simply on screen in
  * the Variables panel. A button that popped it in a notification was the wrong
  * shape for it — see §Scopes in the design doc.
+ *
+ * Forcing (`ironplc/forceVariable`, `ironplc/unforceVariable`) holds a program
+ * or global variable at a value the program cannot override. Both commands sit
+ * on the Variables panel's context menu; "Unforce" shows only on variables the
+ * server flags as forced. From the Command Palette, a force is typed the way
+ * `ironplcvm run --force` takes it.
  */
 export function registerCustomRequests(context: vscode.ExtensionContext): void {
   context.subscriptions.push(
@@ -35,9 +49,99 @@ export function registerCustomRequests(context: vscode.ExtensionContext): void {
         void vscode.window.showWarningMessage(customRequestFailedMessage('Step Scan Cycle'));
       }
     }),
+    vscode.commands.registerCommand('ironplc.goToScan', async () => {
+      const session = activeIronplcSession();
+      if (!session) {
+        return;
+      }
+      const text = await vscode.window.showInputBox({
+        prompt: 'Scan cycle to go to (0 is the first)',
+        validateInput: (value) =>
+          parseScanNumber(value) === undefined ? 'Enter a scan number of 0 or more.' : undefined,
+      });
+      const scan = text === undefined ? undefined : parseScanNumber(text);
+      if (scan === undefined) {
+        return;
+      }
+      try {
+        await session.customRequest('ironplc/goToScan', { scan });
+      } catch (err) {
+        // Besides the phase refusal, the server names the scans it still holds
+        // when the one asked for has left the history; show that as sent.
+        const message =
+          err instanceof Error && err.message !== 'requestNotApplicable'
+            ? `IronPLC: ${err.message}`
+            : customRequestFailedMessage('Go to Scan Cycle');
+        void vscode.window.showWarningMessage(message);
+      }
+    }),
+    vscode.commands.registerCommand('ironplc.forceVariable', async (context?: VariableContext) => {
+      const session = activeIronplcSession();
+      if (!session) {
+        return;
+      }
+      const force = await promptForForce(context?.variable?.name);
+      if (!force) {
+        return;
+      }
+      await sendForceRequest(session, 'ironplc/forceVariable', force, 'Force Variable');
+    }),
+    vscode.commands.registerCommand('ironplc.unforceVariable', async (context?: VariableContext) => {
+      const session = activeIronplcSession();
+      const name = context?.variable?.name;
+      if (!session || !name) {
+        return;
+      }
+      await sendForceRequest(session, 'ironplc/unforceVariable', { name }, 'Unforce Variable');
+    }),
   );
 }
 
+/** What VS Code passes a `debug/variables/context` menu command. */
+interface VariableContext {
+  variable?: { name?: string };
+}
+
+/**
+ * Asks for the force to apply: the value alone when the variable was picked in
+ * the Variables panel, otherwise `NAME=VALUE` or `NAME.BIT=VALUE`.
+ */
+async function promptForForce(name: string | undefined): Promise<ForceArguments | undefined> {
+  if (name) {
+    const value = await vscode.window.showInputBox({
+      prompt: `Value to force ${name} to (for example TRUE, 42 or 16#FF)`,
+    });
+    return value?.trim() ? { name, value: value.trim() } : undefined;
+  }
+  const text = await vscode.window.showInputBox({
+    prompt: 'Variable to force, as NAME=VALUE or NAME.BIT=VALUE',
+    validateInput: (value) =>
+      parseForceArgument(value) === undefined ? 'Enter NAME=VALUE or NAME.BIT=VALUE.' : undefined,
+  });
+  return text === undefined ? undefined : parseForceArgument(text);
+}
+
+/**
+ * Sends a force or unforce request, showing the server's reason when it is
+ * refused: an unknown name or a value the type cannot hold is worth reading.
+ */
+async function sendForceRequest(
+  session: vscode.DebugSession,
+  command: string,
+  args: object,
+  title: string,
+): Promise<void> {
+  try {
+    await session.customRequest(command, args);
+  } catch (err) {
+    const message =
+      err instanceof Error && err.message !== 'requestNotApplicable'
+        ? `IronPLC: ${err.message}`
+        : customRequestFailedMessage(title);
+    void vscode.window.showWarningMessage(message);
+  }
+}
+
 /**
  * The active debug session when it belongs to the IronPLC debugger, else
  * `undefined`. Custom requests are only meaningful against an IronPLC session,
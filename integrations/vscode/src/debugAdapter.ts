@@ -69,6 +69,11 @@ implements vscode.DebugConfigurationProvider {
       config.name = 'IronPLC: Debug Active File';
     }
 
+    // An attach names a running program's monitor; there is nothing to compile.
+    if (config.request === 'attach') {
+      return config;
+    }
+
     // Fall back to the active editor only when it is itself a debuggable file,
     // so pressing Run from the launch.json editor does not pick launch.json as
     // the program. A literal "${file}" is kept as-is for VS Code to substitute.
@@ -87,6 +92,9 @@ implements vscode.DebugConfigurationProvider {
     _folder: vscode.WorkspaceFolder | undefined,
     config: vscode.DebugConfiguration,
   ): Promise<vscode.DebugConfiguration | undefined> {
+    if (config.request === 'attach') {
+      return config;
+    }
     const program: string | undefined = config.program;
     if (!program) {
       return undefined;
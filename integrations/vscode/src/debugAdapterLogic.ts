@@ -231,3 +231,40 @@ export function firstLine(text: string): string {
 export function customRequestFailedMessage(title: string): string {
   return `IronPLC: "${title}" is only available while the program is paused.`;
 }
+
+/**
+ * Parses the scan number typed into the "Go to Scan Cycle" prompt, or returns
+ * `undefined` when the text is not a whole number of zero or more. Scans are
+ * counted the way the `Runtime` scope's `scanCount` counts, so scan 0 is the
+ * first cycle.
+ */
+export function parseScanNumber(text: string): number | undefined {
+  const trimmed = text.trim();
+  if (!/^\d+$/.test(trimmed)) {
+    return undefined;
+  }
+  const scan = Number(trimmed);
+  return Number.isSafeInteger(scan) ? scan : undefined;
+}
+
+/** The arguments of an `ironplc/forceVariable` request. */
+export interface ForceArguments {
+  name: string;
+  value: string;
+  bit?: number;
+}
+
+/**
+ * Parses a force typed into the "Force Variable" prompt, written the way
+ * `ironplcvm run --force` takes it: `NAME=VALUE`, or `NAME.BIT=VALUE` to force
+ * one bit of a bit string. Returns `undefined` when the text has no name or no
+ * value. Whether the name exists and the value fits is the server's to say.
+ */
+export function parseForceArgument(text: string): ForceArguments | undefined {
+  const match = /^\s*([A-Za-z_][A-Za-z0-9_]*)(?:\.(\d+))?\s*=\s*(\S.*?)\s*$/.exec(text);
+  if (!match) {
+    return undefined;
+  }
+  const [, name, bit, value] = match;
+  return bit === undefined ? { name, value } : { name, value, bit: Number(bit) };
+}
@@ -13,6 +13,7 @@ import { CompilerEnvironment, findCompilerPath, formatStartFailure } from './com
 import { ProblemCode, formatProblem } from './problems';
 import { problemHelpUrl } from './problemUrl';
 import { RunSession, RunState } from './runSession';
+import { MONITOR_NOTIFICATION, MonitorParams, inlineValues } from './monitorLogic';
 import { findProgramLenses } from './runCodeLensProvider';
 import {
   IRONPLC_DEBUG_TYPE,
@@ -21,6 +22,12 @@ import {
 } from './debugAdapter';
 import { registerCustomRequests } from './customRequests';
 import { sourceExtensionsFromLanguages } from './debugAdapterLogic';
+import {
+  ENABLE_OPTION_COMMAND,
+  featureSettingName,
+  initializationOptions,
+  withFeature,
+} from './featureSettingsLogic';
 
 /**
  * Reactive code lens provider for PROGRAM declarations. Shows "Run Program"
@@ -181,6 +188,32 @@ function registerRunSupport(context: vscode.ExtensionContext) {
   stopItem.command = 'ironplc.stopProgram';
   context.subscriptions.push(stopItem);
 
+  // Live values shown after the names of variables while a program runs
+  const monitorDecoration = vscode.window.createTextEditorDecorationType({
+    after: {
+      color: new vscode.ThemeColor('editorCodeLens.foreground'),
+      margin: '0 0 0 0.5em',
+    },
+  });
+  context.subscriptions.push(monitorDecoration);
+  let latestMonitor: MonitorParams | undefined;
+  let monitorSubscription: vscode.Disposable | undefined;
+
+  function renderMonitor() {
+    for (const editor of vscode.window.visibleTextEditors) {
+      const values = latestMonitor ? inlineValues(latestMonitor, editor.document.uri.toString()) : [];
+      editor.setDecorations(
+        monitorDecoration,
+        values.map((v) => ({
+          range: new vscode.Range(v.position.line, v.position.character, v.position.line, v.position.character),
+          hoverMessage: v.hover,
+          renderOptions: { after: { contentText: v.text } },
+        })),
+      );
+    }
+  }
+  context.subscriptions.push(vscode.window.onDidChangeVisibleTextEditors(renderMonitor));
+
   // CodeLens provider (reactive — refreshes when the run state changes)
   const runLensProvider = new RunProgramCodeLensProvider(() => !!client);
   context.subscriptions.push(runLensProvider);
@@ -200,6 +233,10 @@ function registerRunSupport(context: vscode.ExtensionContext) {
       pauseItem.hide();
       stopItem.hide();
     }
+    if (state === 'idle') {
+      latestMonitor = undefined;
+      renderMonitor();
+    }
     runLensProvider.setState(state);
   }
 
@@ -261,8 +298,15 @@ function registerRunSupport(context: vscode.ExtensionContext) {
         },
       });
 
+      monitorSubscription?.dispose();
+      monitorSubscription = client.onNotification(MONITOR_NOTIFICATION, (params: MonitorParams) => {
+        latestMonitor = params;
+        renderMonitor();
+      });
+      context.subscriptions.push(monitorSubscription);
+
       outputChannel.show(true);
-      await runSession.start(source);
+      await runSession.start(source, undefined, editor.document.uri.toString());
     }),
   );
 
@@ -322,6 +366,26 @@ function registerDebugSupport(context: vscode.ExtensionContext, compilerPath: st
   );
 }
 
+/**
+ * Adds the compiler option to the `ironplc.features` setting, in the
+ * workspace when one is open.
+ */
+async function enableFeatureSetting(optionKey: string): Promise<void> {
+  const config = vscode.workspace.getConfiguration('ironplc');
+  const features = withFeature(config.get<string[]>('features', []), featureSettingName(optionKey));
+  if (!features) {
+    return;
+  }
+  const target = vscode.workspace.workspaceFolders
+    ? vscode.ConfigurationTarget.Workspace
+    : vscode.ConfigurationTarget.Global;
+  try {
+    await config.update('features', features, target);
+  } catch (err) {
+    vscode.window.showWarningMessage(`IronPLC could not save the enabled feature in the settings: ${err}`);
+  }
+}
+
 function createClient(compilerFilePath: string, config: vscode.WorkspaceConfiguration) {
   let args = [];
 
@@ -350,8 +414,11 @@ function createClient(compilerFilePath: string, config: vscode.WorkspaceConfigur
 
   const serverOptions: ServerOptions = application;
 
-  // Read the dialect setting
+  // Read the dialect, the features enabled on top of it and the problem
+  // codes reported as warnings
   const dialect = config.get<string>('dialect', 'iec61131-3-ed2');
+  const features = config.get<string[]>('features', []);
+  const demote = config.get<string[]>('demote', []);
 
   // Options to control the language client
   const clientOptions: LanguageClientOptions = {
@@ -362,8 +429,16 @@ function createClient(compilerFilePath: string, config: vscode.WorkspaceConfigur
       { scheme: 'file', language: 'twincat-gvl' },
       { scheme: 'file', language: 'twincat-dut' },
     ],
-    initializationOptions: {
-      dialect: dialect,
+    initializationOptions: initializationOptions(dialect, features, demote),
+    middleware: {
+      // The server enables a feature for its session only; record it in the
+      // settings so the next session starts with it.
+      executeCommand: async (command, args, next) => {
+        if (command === ENABLE_OPTION_COMMAND && typeof args[0] === 'string') {
+          await enableFeatureSetting(args[0]);
+        }
+        return next(command, args);
+      },
     },
   };
 
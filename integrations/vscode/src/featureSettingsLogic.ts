@@ -0,0 +1,47 @@
+/**
+ * Pure logic for the `ironplc.features` setting: the language features the
+ * user enabled on top of the dialect. The language server reads them as
+ * `initializationOptions`, and its *Enable ...* quick fix asks for one more,
+ * which the extension adds to the setting so it outlives the session. The
+ * `vscode`-facing glue lives in `extension.ts`.
+ */
+
+/** The server command a gated-feature quick fix runs. The arguments are the
+ * compiler option key (`allow_ref_to`) and the URI of the document. */
+export const ENABLE_OPTION_COMMAND = 'ironplc.enableOption';
+
+/**
+ * The setting name of a compiler option key: the key in lowerCamelCase, as
+ * the server reads it from `initializationOptions` (`allow_ref_to` ->
+ * `allowRefTo`).
+ */
+export function featureSettingName(optionKey: string): string {
+  return optionKey.replace(/_([a-z0-9])/g, (_, c: string) => c.toUpperCase());
+}
+
+/**
+ * The features with `name` added, or `undefined` when it is already there and
+ * the setting needs no update.
+ */
+export function withFeature(features: readonly string[], name: string): string[] | undefined {
+  return features.includes(name) ? undefined : [...features, name];
+}
+
+/**
+ * The `initializationOptions` for the dialect, the enabled features and the
+ * problem codes to report as warnings.
+ */
+export function initializationOptions(
+  dialect: string,
+  features: readonly string[],
+  demote: readonly string[] = [],
+): Record<string, unknown> {
+  const options: Record<string, unknown> = { dialect };
+  for (const name of features) {
+    options[name] = true;
+  }
+  if (demote.length > 0) {
+    options.demote = [...demote];
+  }
+  return options;
+}
@@ -0,0 +1,60 @@
+/** The notification the language server sends after each step of a run. */
+export const MONITOR_NOTIFICATION = 'ironplc/monitor';
+
+/** A zero-based position, as the language server protocol sends it. */
+export interface MonitorPosition {
+  line: number;
+  character: number;
+}
+
+export interface MonitorRange {
+  start: MonitorPosition;
+  end: MonitorPosition;
+}
+
+/** The value of one variable and the ranges in the document that name it. */
+export interface MonitorValue {
+  name: string;
+  type_name?: string;
+  value: string;
+  ranges: MonitorRange[];
+}
+
+/** Parameters of the `ironplc/monitor` notification. */
+export interface MonitorParams {
+  uri: string;
+  total_scans: number;
+  values: MonitorValue[];
+}
+
+/** A value to show in the editor just after the name it belongs to. */
+export interface InlineValue {
+  position: MonitorPosition;
+  text: string;
+  hover: string;
+}
+
+/**
+ * The inline values for a document: one after every name of a monitored
+ * variable, in document order. Values of another document are left out.
+ */
+export function inlineValues(params: MonitorParams, uri: string): InlineValue[] {
+  if (params.uri !== uri) {
+    return [];
+  }
+  const values = params.values.flatMap((value) =>
+    value.ranges.map((range) => ({
+      position: range.end,
+      text: value.value,
+      hover: hoverText(value, params.total_scans),
+    })),
+  );
+  return values.sort(
+    (a, b) => a.position.line - b.position.line || a.position.character - b.position.character,
+  );
+}
+
+function hoverText(value: MonitorValue, totalScans: number): string {
+  const typeSuffix = value.type_name ? ` : ${value.type_name}` : '';
+  return `${value.name}${typeSuffix} = ${value.value} (scan ${totalScans})`;
+}
@@ -55,8 +55,12 @@ export class RunSession {
     return this.state;
   }
 
-  /** Start running the program from source text. */
-  async start(source: string, cycleTimeUs?: number): Promise<void> {
+  /**
+   * Start running the program from source text. When `uri` names the
+   * document, the server also sends `ironplc/monitor` after each step with
+   * the values placed on the names in that document.
+   */
+  async start(source: string, cycleTimeUs?: number, uri?: string): Promise<void> {
     if (this.state === 'running' || this.state === 'paused') {
       await this.stop();
     }
@@ -64,6 +68,7 @@ export class RunSession {
     const result = await this.client.sendRequest('ironplc/run', {
       source,
       cycleTimeUs: cycleTimeUs ?? DEFAULT_CYCLE_TIME_US,
+      ...(uri === undefined ? {} : { uri }),
     }) as RunResult;
 
     if (!result.ok) {
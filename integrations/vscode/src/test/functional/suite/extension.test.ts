@@ -80,6 +80,17 @@ suite('Extension Test Suite', () => {
     assert.ok(commands.includes('ironplc.stepScan'));
   });
 
+  test('ironplc.goToScan command is registered', async () => {
+    const commands = await vscode.commands.getCommands(true);
+    assert.ok(commands.includes('ironplc.goToScan'));
+  });
+
+  test('ironplc.forceVariable and ironplc.unforceVariable commands are registered', async () => {
+    const commands = await vscode.commands.getCommands(true);
+    assert.ok(commands.includes('ironplc.forceVariable'));
+    assert.ok(commands.includes('ironplc.unforceVariable'));
+  });
+
   test('does not detect non-ST extension as 61131-3-st', async () => {
     const filePath = testResourcePath('invalid-ext.notst');
     const textDocument = await vscode.workspace.openTextDocument(filePath);
@@ -18,6 +18,8 @@ import {
   programKind,
   resolveProgramPath,
   customRequestFailedMessage,
+  parseForceArgument,
+  parseScanNumber,
   sourceExtensionsFromLanguages,
 } from '../../debugAdapterLogic';
 
@@ -242,6 +244,44 @@ suite('customRequestFailedMessage', () => {
   });
 });
 
+suite('parseScanNumber', () => {
+  test('parseScanNumber_when_whole_number_then_returns_it', () => {
+    assert.strictEqual(parseScanNumber(' 12 '), 12);
+  });
+
+  test('parseScanNumber_when_zero_then_returns_zero', () => {
+    assert.strictEqual(parseScanNumber('0'), 0);
+  });
+
+  test('parseScanNumber_when_negative_then_undefined', () => {
+    assert.strictEqual(parseScanNumber('-1'), undefined);
+  });
+
+  test('parseScanNumber_when_not_a_number_then_undefined', () => {
+    assert.strictEqual(parseScanNumber('1.5'), undefined);
+    assert.strictEqual(parseScanNumber(''), undefined);
+  });
+});
+
+suite('parseForceArgument', () => {
+  test('parseForceArgument_when_name_and_value_then_returns_both', () => {
+    assert.deepStrictEqual(parseForceArgument(' Count = 16#FF '), { name: 'Count', value: '16#FF' });
+  });
+
+  test('parseForceArgument_when_bit_then_returns_bit', () => {
+    assert.deepStrictEqual(parseForceArgument('Inputs.3=TRUE'), {
+      name: 'Inputs',
+      value: 'TRUE',
+      bit: 3,
+    });
+  });
+
+  test('parseForceArgument_when_no_value_then_undefined', () => {
+    assert.strictEqual(parseForceArgument('Count='), undefined);
+    assert.strictEqual(parseForceArgument('Count'), undefined);
+  });
+});
+
 suite('debugServerFileName', () => {
   test('debugServerFileName_when_posix_then_returns_bare_binary', () => {
     assert.strictEqual(debugServerFileName('linux'), DEBUG_SERVER_BINARY);
@@ -0,0 +1,35 @@
+import * as assert from 'assert';
+import { featureSettingName, initializationOptions, withFeature } from '../../featureSettingsLogic';
+
+suite('featureSettingName', () => {
+  test('featureSettingName_when_snake_case_key_then_lower_camel_case', () => {
+    assert.strictEqual(featureSettingName('allow_ref_to'), 'allowRefTo');
+    assert.strictEqual(featureSettingName('allow_c_style_comments'), 'allowCStyleComments');
+  });
+});
+
+suite('withFeature', () => {
+  test('withFeature_when_new_then_appends', () => {
+    assert.deepStrictEqual(withFeature(['allowRefTo'], 'allowSizeof'), ['allowRefTo', 'allowSizeof']);
+  });
+
+  test('withFeature_when_already_enabled_then_undefined', () => {
+    assert.strictEqual(withFeature(['allowRefTo'], 'allowRefTo'), undefined);
+  });
+});
+
+suite('initializationOptions', () => {
+  test('initializationOptions_when_features_then_each_set_true', () => {
+    assert.deepStrictEqual(initializationOptions('iec61131-3-ed2', ['allowRefTo']), {
+      dialect: 'iec61131-3-ed2',
+      allowRefTo: true,
+    });
+  });
+
+  test('initializationOptions_when_demote_then_codes_listed', () => {
+    assert.deepStrictEqual(initializationOptions('iec61131-3-ed2', [], ['P4041']), {
+      dialect: 'iec61131-3-ed2',
+      demote: ['P4041'],
+    });
+  });
+});
@@ -0,0 +1,41 @@
+import * as assert from 'assert';
+import { MonitorParams, inlineValues } from '../../monitorLogic';
+
+function range(line: number, start: number, end: number) {
+  return { start: { line, character: start }, end: { line, character: end } };
+}
+
+const PARAMS: MonitorParams = {
+  uri: 'file:///main.st',
+  total_scans: 3,
+  values: [
+    { name: 'x', type_name: 'DINT', value: '42', ranges: [range(4, 4, 5), range(2, 4, 5)] },
+    { name: 'done', value: 'TRUE', ranges: [range(4, 0, 4)] },
+  ],
+};
+
+suite('inlineValues', () => {
+  test('inlineValues_when_document_monitored_then_value_after_each_name_in_order', () => {
+    const values = inlineValues(PARAMS, 'file:///main.st');
+
+    assert.deepStrictEqual(
+      values.map((v) => [v.position.line, v.position.character, v.text]),
+      [
+        [2, 5, '42'],
+        [4, 4, 'TRUE'],
+        [4, 5, '42'],
+      ],
+    );
+  });
+
+  test('inlineValues_when_type_name_then_hover_names_type_and_scan', () => {
+    const values = inlineValues(PARAMS, 'file:///main.st');
+
+    assert.strictEqual(values[0].hover, 'x : DINT = 42 (scan 3)');
+    assert.strictEqual(values[1].hover, 'done = TRUE (scan 3)');
+  });
+
+  test('inlineValues_when_other_document_then_empty', () => {
+    assert.deepStrictEqual(inlineValues(PARAMS, 'file:///other.st'), []);
+  });
+});
@@ -180,4 +180,16 @@ suite('RunSession', () => {
     assert.strictEqual(session.getState(), 'running');
     session.dispose();
   });
+
+  test('start_when_uri_then_run_request_names_monitored_document', async () => {
+    const { client, requests } = createRunMockClient();
+    const { callbacks } = createTrackingCallbacks();
+    const session = new RunSession(client, callbacks);
+
+    await session.start('PROGRAM main END_PROGRAM', undefined, 'file:///main.st');
+
+    const run = requests.find(r => r.method === 'ironplc/run');
+    assert.strictEqual((run?.params as { uri?: string }).uri, 'file:///main.st');
+    session.dispose();
+  });
 });